@@ -1,3 +1,4 @@
+use alloc::sync::Arc;
 use core::{net::SocketAddr, time::Duration};
 
 use authorization::{
@@ -7,10 +8,17 @@ use authorization::{
 };
 use clap::Parser;
 use error_stack::{Result, ResultExt};
+use futures::future::try_join;
 use graph::{
+    ontology::domain_validator::DomainValidator,
     snapshot::SnapshotEntry,
-    store::{DatabaseConnectionInfo, DatabasePoolConfig, PostgresStorePool},
+    store::{
+        DatabaseConnectionInfo, DatabasePoolConfig, FetchingPool, MemoryStorePool,
+        PostgresStorePool,
+    },
 };
+use graph_api::rest::{RestRouterDependencies, rest_api_router};
+use regex::Regex;
 use reqwest::Client;
 use tokio::{net::TcpListener, time::timeout};
 use tokio_postgres::NoTls;
@@ -33,6 +41,31 @@ pub struct TestServerArgs {
     #[clap(flatten)]
     pub api_address: ApiAddress,
 
+    /// Runs the test server on an in-memory store instead of Postgres.
+    ///
+    /// The in-memory store only lives inside of this process, so the Graph API is served
+    /// alongside the test server at `--graph-api-host` and `--graph-api-port`. Snapshots cannot be
+    /// restored into the in-memory store.
+    #[clap(long, default_value_t = false)]
+    pub memory_store: bool,
+
+    /// The host the Graph API is listening at when running on the in-memory store.
+    #[clap(long, default_value = "127.0.0.1", requires = "memory_store")]
+    pub graph_api_host: String,
+
+    /// The port the Graph API is listening at when running on the in-memory store.
+    #[clap(long, default_value_t = 4000, requires = "memory_store")]
+    pub graph_api_port: u16,
+
+    /// A regex which *new* Type System URLs are checked against when running on the in-memory
+    /// store, see the `server` subcommand for details.
+    #[clap(
+        long,
+        default_value_t = Regex::new(r"http://localhost:3000/@(?P<shortname>[\w-]+)/types/(?P<kind>(?:data-type)|(?:property-type)|(?:entity-type))/[\w\-_%]+/").unwrap(),
+        env = "HASH_GRAPH_ALLOWED_URL_DOMAIN_PATTERN",
+    )]
+    pub allowed_url_domain: Regex,
+
     /// Runs the healthcheck for the test server.
     #[clap(long, default_value_t = false)]
     pub healthcheck: bool,
@@ -71,14 +104,6 @@ pub async fn test_server(args: TestServerArgs) -> Result<(), GraphError> {
         .change_context(GraphError);
     }
 
-    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config, NoTls)
-        .await
-        .change_context(GraphError)
-        .map_err(|report| {
-            tracing::error!(error = ?report, "Failed to connect to database");
-            report
-        })?;
-
     let mut spicedb_client = SpiceDbOpenApi::new(
         format!("{}:{}", args.spicedb_host, args.spicedb_http_port),
         args.spicedb_grpc_preshared_key.as_deref(),
@@ -94,17 +119,68 @@ pub async fn test_server(args: TestServerArgs) -> Result<(), GraphError> {
     let mut zanzibar_client = ZanzibarClient::new(spicedb_client);
     zanzibar_client.seed().await.change_context(GraphError)?;
 
-    let router = test_server::routes(pool, zanzibar_client);
+    let listener = TcpListener::bind((
+        args.api_address.api_host.as_str(),
+        args.api_address.api_port,
+    ))
+    .await
+    .change_context(GraphError)?;
 
-    tracing::info!("Listening on {}", args.api_address);
-    axum::serve(
-        TcpListener::bind((args.api_address.api_host, args.api_address.api_port))
+    if args.memory_store {
+        let pool = MemoryStorePool::new();
+
+        let graph_api = rest_api_router(RestRouterDependencies {
+            store: Arc::new(FetchingPool::<_, (String, u16)>::new_offline(pool.clone())),
+            authorization_api: Arc::new(zanzibar_client.clone()),
+            domain_regex: DomainValidator::new(args.allowed_url_domain),
+            temporal_client: None,
+        });
+        let graph_api_listener =
+            TcpListener::bind((args.graph_api_host.as_str(), args.graph_api_port))
+                .await
+                .change_context(GraphError)?;
+
+        let router = test_server::memory_routes(pool, zanzibar_client);
+
+        tracing::info!("Listening on {}", args.api_address);
+        tracing::info!(
+            "Graph API listening on {}:{}",
+            args.graph_api_host,
+            args.graph_api_port
+        );
+        try_join(
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .into_future(),
+            axum::serve(
+                graph_api_listener,
+                graph_api.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .into_future(),
+        )
+        .await
+        .expect("failed to start server");
+    } else {
+        let pool = PostgresStorePool::new(&args.db_info, &args.pool_config, NoTls)
             .await
-            .change_context(GraphError)?,
-        router.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await
-    .expect("failed to start server");
+            .change_context(GraphError)
+            .map_err(|report| {
+                tracing::error!(error = ?report, "Failed to connect to database");
+                report
+            })?;
+
+        let router = test_server::routes(pool, zanzibar_client);
+
+        tracing::info!("Listening on {}", args.api_address);
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .expect("failed to start server");
+    }
 
     Ok(())
 }
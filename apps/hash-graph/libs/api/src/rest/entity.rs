@@ -26,9 +26,12 @@ use graph::store::{
     EntityValidationType, NullOrdering, Ordering, StorePool,
     error::{EntityDoesNotExist, RaceConditionOnUpdate},
     knowledge::{
-        CountEntitiesParams, CreateEntityRequest, DiffEntityParams, DiffEntityResult,
-        GetEntitiesParams, GetEntitiesResponse, GetEntitySubgraphParams, PatchEntityParams,
-        QueryConversion, UpdateEntityEmbeddingsParams, ValidateEntityParams,
+        CountEntitiesParams, CreateEntityRequest, DeleteEntitiesParams, DeleteEntitiesResponse,
+        DeletedEntity, DiffEntityParams, DiffEntityResult, EntityDeletionReason,
+        EntityKeywordSearch, EntitySearchMatch, EntitySearchResult, EntitySearchSignal,
+        EntityVectorSearch, GetEntitiesParams, GetEntitiesResponse, GetEntitySubgraphParams,
+        PatchEntityParams, QueryConversion, SearchEntitiesParams, SearchEntitiesResponse,
+        UpdateEntityEmbeddingsParams, ValidateEntityParams,
     },
 };
 use graph_types::{
@@ -37,10 +40,10 @@ use graph_types::{
     knowledge::{
         Confidence, EntityTypeIdDiff,
         entity::{
-            ActorType, Entity, EntityEditionId, EntityEditionProvenance, EntityEmbedding, EntityId,
-            EntityMetadata, EntityProvenance, EntityRecordId, EntityTemporalMetadata, EntityUuid,
-            InferredEntityProvenance, Location, OriginProvenance, ProvidedEntityEditionProvenance,
-            SourceProvenance, SourceType,
+            ActorType, DraftId, Entity, EntityEditionId, EntityEditionProvenance, EntityEmbedding,
+            EntityId, EntityMetadata, EntityProvenance, EntityRecordId, EntityTemporalMetadata,
+            EntityUuid, InferredEntityProvenance, Location, OriginProvenance,
+            ProvidedEntityEditionProvenance, SourceProvenance, SourceType,
         },
         link::LinkData,
         property::{
@@ -83,6 +86,8 @@ use crate::rest::{
         patch_entity,
         update_entity_embeddings,
         diff_entity,
+        delete_entities,
+        search_entities,
 
         get_entity_authorization_relationships,
         modify_entity_authorization_relationships,
@@ -168,6 +173,20 @@ use crate::rest::{
             PropertyPath,
             PropertyPathElement,
             Confidence,
+
+            DeleteEntitiesParams,
+            DeleteEntitiesResponse,
+            DeletedEntity,
+            EntityDeletionReason,
+            DraftId,
+
+            SearchEntitiesParams,
+            EntityVectorSearch,
+            EntityKeywordSearch,
+            SearchEntitiesResponse,
+            EntitySearchResult,
+            EntitySearchMatch,
+            EntitySearchSignal,
         )
     ),
     tags(
@@ -194,6 +213,8 @@ impl RoutedResource for EntityResource {
                     post(modify_entity_authorization_relationships::<A>),
                 )
                 .route("/diff", post(diff_entity::<S, A>))
+                .route("/delete", post(delete_entities::<S, A>))
+                .route("/search", post(search_entities::<S, A>))
                 .route("/validate", post(validate_entity::<S, A>))
                 .route("/embeddings", post(update_entity_embeddings::<S, A>))
                 .nest(
@@ -987,6 +1008,113 @@ where
         .map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/entities/delete",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The entities which were removed, or would have been removed for a dry run", body = DeleteEntitiesResponse),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Insufficient permissions to delete one or more entities"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = DeleteEntitiesParams,
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client, body)
+)]
+async fn delete_entities<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    Json(body): Json<serde_json::Value>,
+) -> Result<Json<DeleteEntitiesResponse>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    // Manually deserialize the request from a JSON value to allow borrowed deserialization and
+    // better error reporting.
+    let params = DeleteEntitiesParams::deserialize(body)
+        .attach(hash_status::StatusCode::InvalidArgument)
+        .map_err(report_to_response)?;
+
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let mut store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .permanently_delete_entities(actor_id, params)
+        .await
+        .map_err(report_to_response)
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/search",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The entities matching the vector or keyword search, ordered by their fused score", body = SearchEntitiesResponse),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = SearchEntitiesParams,
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client, body)
+)]
+async fn search_entities<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    Json(body): Json<serde_json::Value>,
+) -> Result<Json<SearchEntitiesResponse>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    // Manually deserialize the request from a JSON value to allow borrowed deserialization and
+    // better error reporting.
+    let params = SearchEntitiesParams::deserialize(body)
+        .attach(hash_status::StatusCode::InvalidArgument)
+        .map_err(report_to_response)?;
+
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .search_entities(actor_id, params)
+        .await
+        .map_err(report_to_response)
+        .map(Json)
+}
+
 #[utoipa::path(
     get,
     path = "/entities/{entity_id}/relationships",
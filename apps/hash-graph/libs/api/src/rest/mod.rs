@@ -595,6 +595,18 @@ impl Modify for FilterSchemaAddon {
                                 )
                                 .required("notEqual"),
                         )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("ExistsFilter"))
+                                .property("exists", Ref::from_schema_name("FilterExpression"))
+                                .required("exists"),
+                        )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("IsNullFilter"))
+                                .property("isNull", Ref::from_schema_name("FilterExpression"))
+                                .required("isNull"),
+                        )
                         .item(
                             ObjectBuilder::new()
                                 .title(Some("GreaterFilter"))
@@ -655,6 +667,22 @@ impl Modify for FilterSchemaAddon {
                                 )
                                 .required("cosineDistance"),
                         )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("InFilter"))
+                                .property(
+                                    "in",
+                                    ArrayBuilder::new()
+                                        .items(
+                                            OneOfBuilder::new()
+                                                .item(Ref::from_schema_name("FilterExpression"))
+                                                .item(ArrayBuilder::new().items(Any::schema().1)),
+                                        )
+                                        .min_items(Some(2))
+                                        .max_items(Some(2)),
+                                )
+                                .required("in"),
+                        )
                         .item(
                             ObjectBuilder::new()
                                 .title(Some("StartsWithFilter"))
@@ -691,6 +719,49 @@ impl Modify for FilterSchemaAddon {
                                 )
                                 .required("containsSegment"),
                         )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("ILikeFilter"))
+                                .property(
+                                    "ilike",
+                                    ArrayBuilder::new()
+                                        .items(Ref::from_schema_name("FilterExpression"))
+                                        .min_items(Some(2))
+                                        .max_items(Some(2)),
+                                )
+                                .required("ilike"),
+                        )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("MatchesFilter"))
+                                .property(
+                                    "matches",
+                                    ArrayBuilder::new()
+                                        .items(Ref::from_schema_name("FilterExpression"))
+                                        .min_items(Some(2))
+                                        .max_items(Some(2)),
+                                )
+                                .required("matches"),
+                        )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("FullTextSearchFilter"))
+                                .property(
+                                    "fullTextSearch",
+                                    ArrayBuilder::new()
+                                        .items(
+                                            OneOfBuilder::new()
+                                                .item(Ref::from_schema_name("FilterExpression"))
+                                                .item(
+                                                    ObjectBuilder::new()
+                                                        .schema_type(SchemaType::String),
+                                                ),
+                                        )
+                                        .min_items(Some(2))
+                                        .max_items(Some(2)),
+                                )
+                                .required("fullTextSearch"),
+                        )
                         .build(),
                 )
                 .into(),
@@ -0,0 +1,8 @@
+-- Indexes the text search vector of all string values in the properties of an entity edition.
+--
+-- This is an expression index instead of a generated column as `entity_editions` rows are inserted
+-- using the composite row type of the table, which would also include a generated column. The
+-- expression has to match the expression used in full-text search queries exactly.
+CREATE INDEX entity_editions_properties_search_idx
+    ON entity_editions
+        USING gin (jsonb_to_tsvector('english'::regconfig, properties, '["string"]'::jsonb));
@@ -52,10 +52,12 @@ use crate::{
         DataTypeStore, EntityStore, EntityTypeStore, InsertionError, PropertyTypeStore, QueryError,
         StoreError, StorePool, UpdateError,
         crud::{QueryResult, Read, ReadPaginated, Sorting},
+        error::DeletionError,
         knowledge::{
-            CountEntitiesParams, CreateEntityParams, GetEntitiesParams, GetEntitiesResponse,
-            GetEntitySubgraphParams, GetEntitySubgraphResponse, PatchEntityParams,
-            UpdateEntityEmbeddingsParams, ValidateEntityError, ValidateEntityParams,
+            CountEntitiesParams, CreateEntityParams, DeleteEntitiesParams, DeleteEntitiesResponse,
+            GetEntitiesParams, GetEntitiesResponse, GetEntitySubgraphParams,
+            GetEntitySubgraphResponse, PatchEntityParams, UpdateEntityEmbeddingsParams,
+            ValidateEntityError, ValidateEntityParams,
         },
         ontology::{
             ArchiveDataTypeParams, ArchiveEntityTypeParams, ArchivePropertyTypeParams,
@@ -1264,6 +1266,14 @@ where
         self.store.update_entity_embeddings(actor_id, params).await
     }
 
+    async fn permanently_delete_entities(
+        &mut self,
+        actor_id: AccountId,
+        params: DeleteEntitiesParams<'_>,
+    ) -> Result<DeleteEntitiesResponse, DeletionError> {
+        self.store.permanently_delete_entities(actor_id, params).await
+    }
+
     async fn reindex_entity_cache(&mut self) -> Result<(), UpdateError> {
         self.store.reindex_entity_cache().await
     }
@@ -1,15 +1,19 @@
 use alloc::borrow::Cow;
 use core::{error::Error, fmt};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, hash_map::Entry};
 
 use authorization::{schema::EntityRelationAndSubject, zanzibar::Consistency};
 use error_stack::Report;
 use futures::TryFutureExt;
 use graph_types::{
+    Embedding,
     account::{AccountId, CreatedById, EditionCreatedById},
     knowledge::{
         Confidence, EntityTypeIdDiff,
-        entity::{Entity, EntityEmbedding, EntityId, EntityUuid, ProvidedEntityEditionProvenance},
+        entity::{
+            DraftId, Entity, EntityEditionId, EntityEmbedding, EntityId, EntityUuid,
+            ProvidedEntityEditionProvenance,
+        },
         link::LinkData,
         property::{
             PropertyDiff, PropertyPatchOperation, PropertyPath, PropertyWithMetadataObject,
@@ -19,9 +23,10 @@ use graph_types::{
 };
 use hash_graph_store::{
     entity::EntityQueryPath,
-    filter::Filter,
+    filter::{Filter, FilterExpression, Parameter, TextSearchQuery},
     subgraph::{Subgraph, edges::GraphResolveDepths, temporal_axes::QueryTemporalAxesUnresolved},
 };
+use hash_status::StatusCode;
 use serde::{Deserialize, Serialize};
 use temporal_versioning::{DecisionTime, Timestamp, TransactionTime};
 use type_system::{schema::ClosedMultiEntityType, url::VersionedUrl};
@@ -34,7 +39,7 @@ use validation::ValidateEntityComponents;
 
 use crate::store::{
     InsertionError, NullOrdering, Ordering, QueryError, UpdateError, crud::Sorting,
-    postgres::CursorField,
+    error::DeletionError, postgres::CursorField,
 };
 
 #[derive(Debug, Clone, Deserialize)]
@@ -362,6 +367,320 @@ pub struct DiffEntityResult<'e> {
     pub draft_state: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DeleteEntitiesParams<'a> {
+    #[serde(borrow)]
+    pub filter: Filter<'a, Entity>,
+    pub temporal_axes: QueryTemporalAxesUnresolved,
+    pub include_drafts: bool,
+    /// If set, nothing is removed but the response reports what would have been removed.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum EntityDeletionReason {
+    /// The entity was matched by the filter.
+    Matched,
+    /// The entity is a link whose left or right entity is deleted.
+    LinkEndpointDeleted,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedEntity {
+    pub entity_id: EntityId,
+    pub reason: EntityDeletionReason,
+    pub draft_ids: Vec<DraftId>,
+    pub edition_ids: Vec<EntityEditionId>,
+    pub num_temporal_versions: usize,
+    pub num_embeddings: usize,
+    pub relationships: Vec<EntityRelationAndSubject>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteEntitiesResponse {
+    pub dry_run: bool,
+    pub entities: Vec<DeletedEntity>,
+}
+
+const fn default_search_weight() -> f64 {
+    1.0
+}
+
+const fn default_rank_constant() -> u32 {
+    60
+}
+
+/// The signals which contribute to the score of an [`EntitySearchResult`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum EntitySearchSignal {
+    /// An embedding of the entity is close to the searched embedding.
+    Vector,
+    /// The properties of the entity match the full-text search query.
+    Keyword,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EntityVectorSearch<'e> {
+    pub embedding: Embedding<'e>,
+    /// The maximum cosine distance of an entity embedding to the searched embedding.
+    pub max_distance: f64,
+    #[serde(default = "default_search_weight")]
+    #[cfg_attr(feature = "utoipa", schema(default = 1.0))]
+    pub weight: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EntityKeywordSearch {
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub query: TextSearchQuery,
+    #[serde(default = "default_search_weight")]
+    #[cfg_attr(feature = "utoipa", schema(default = 1.0))]
+    pub weight: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SearchEntitiesParams<'a> {
+    #[serde(borrow)]
+    pub filter: Filter<'a, Entity>,
+    pub temporal_axes: QueryTemporalAxesUnresolved,
+    pub include_drafts: bool,
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(nullable = false))]
+    pub vector: Option<EntityVectorSearch<'a>>,
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(nullable = false))]
+    pub keyword: Option<EntityKeywordSearch>,
+    /// The maximum number of results, which is also the number of candidates taken from each
+    /// signal.
+    pub limit: usize,
+    /// The constant added to the rank of a candidate in the reciprocal rank fusion.
+    #[serde(default = "default_rank_constant")]
+    #[cfg_attr(feature = "utoipa", schema(default = 60))]
+    pub rank_constant: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EntitySearchMatch {
+    pub signal: EntitySearchSignal,
+    /// The 1-based rank of the entity within the candidates of the signal.
+    pub rank: usize,
+    /// The contribution of the signal to the score of the result.
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EntitySearchResult {
+    pub entity: Entity,
+    pub score: f64,
+    pub matches: Vec<EntitySearchMatch>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SearchEntitiesResponse {
+    pub results: Vec<EntitySearchResult>,
+}
+
+/// Combines the ranked candidates of each signal using weighted reciprocal rank fusion.
+///
+/// Every signal contributes `weight / (rank_constant + rank)` to the score of a candidate. An
+/// entity is ranked once per signal and the edition of its highest contribution is returned.
+/// Results are ordered by descending score, ties keep the order in which the signals are passed.
+#[expect(
+    clippy::float_arithmetic,
+    reason = "The fused score is a floating point calculation"
+)]
+fn fuse_search_candidates(
+    candidates: impl IntoIterator<Item = (EntitySearchSignal, f64, Vec<Entity>)>,
+    limit: usize,
+    rank_constant: u32,
+) -> Vec<EntitySearchResult> {
+    let mut results = Vec::<EntitySearchResult>::new();
+    let mut positions = HashMap::<EntityId, usize>::new();
+
+    for (signal, weight, entities) in candidates {
+        // Entities can be returned once per edition, only the best rank is used.
+        let mut ranked = HashSet::new();
+        for entity in entities {
+            let entity_id = entity.metadata.record_id.entity_id;
+            if ranked.len() == limit {
+                break;
+            }
+            if !ranked.insert(entity_id) {
+                continue;
+            }
+
+            let rank = ranked.len();
+            #[expect(
+                clippy::cast_precision_loss,
+                reason = "The rank is bounded by the number of returned entities"
+            )]
+            let score = weight / (f64::from(rank_constant) + rank as f64);
+            let search_match = EntitySearchMatch {
+                signal,
+                rank,
+                score,
+            };
+
+            match positions.entry(entity_id) {
+                Entry::Occupied(position) => {
+                    let result = &mut results[*position.get()];
+                    if result
+                        .matches
+                        .iter()
+                        .all(|search_match| search_match.score < score)
+                    {
+                        result.entity = entity;
+                    }
+                    result.score += score;
+                    result.matches.push(search_match);
+                }
+                Entry::Vacant(position) => {
+                    position.insert(results.len());
+                    results.push(EntitySearchResult {
+                        entity,
+                        score,
+                        matches: vec![search_match],
+                    });
+                }
+            }
+        }
+    }
+
+    results.sort_by(|lhs, rhs| rhs.score.total_cmp(&lhs.score));
+    results.truncate(limit);
+    results
+}
+
+/// Returns the parameters to read the entities closest to the searched embedding.
+///
+/// The entities are ordered by their distance. Entities ordered by their distance cannot be
+/// continued with a cursor, so all entities within the maximum distance are read and the candidates
+/// are bounded after the entities the actor is not allowed to view are filtered out.
+fn vector_search_params<'p>(
+    filter: Filter<'p, Entity>,
+    temporal_axes: QueryTemporalAxesUnresolved,
+    include_drafts: bool,
+    vector: EntityVectorSearch<'p>,
+) -> GetEntitiesParams<'p> {
+    GetEntitiesParams {
+        filter: Filter::All(vec![
+            filter,
+            Filter::CosineDistance(
+                FilterExpression::Path {
+                    path: EntityQueryPath::Embedding,
+                },
+                FilterExpression::Parameter {
+                    parameter: Parameter::Vector(vector.embedding),
+                    convert: None,
+                },
+                FilterExpression::Parameter {
+                    parameter: Parameter::F64(vector.max_distance),
+                    convert: None,
+                },
+            ),
+        ]),
+        temporal_axes,
+        sorting: EntityQuerySorting {
+            paths: Vec::new(),
+            cursor: None,
+        },
+        conversions: Vec::new(),
+        limit: None,
+        include_drafts,
+        include_count: false,
+        include_web_ids: false,
+        include_created_by_ids: false,
+        include_edition_created_by_ids: false,
+        include_type_ids: false,
+    }
+}
+
+/// Returns the parameters to read the entities ranked best by the full-text search.
+///
+/// The entities are read in pages of `limit` editions, starting after the `cursor`.
+fn keyword_search_params<'p>(
+    filter: Filter<'p, Entity>,
+    temporal_axes: QueryTemporalAxesUnresolved,
+    include_drafts: bool,
+    query: TextSearchQuery,
+    limit: usize,
+    cursor: Option<EntityQueryCursor<'static>>,
+) -> GetEntitiesParams<'p> {
+    let temporal_axis_path = match temporal_axes {
+        QueryTemporalAxesUnresolved::TransactionTime { .. } => EntityQueryPath::TransactionTime,
+        QueryTemporalAxesUnresolved::DecisionTime { .. } => EntityQueryPath::DecisionTime,
+    };
+
+    GetEntitiesParams {
+        filter: Filter::All(vec![
+            filter,
+            Filter::FullTextSearch(
+                FilterExpression::Path {
+                    path: EntityQueryPath::Properties(None),
+                },
+                query.clone(),
+            ),
+        ]),
+        temporal_axes,
+        sorting: EntityQuerySorting {
+            paths: vec![
+                EntityQuerySortingRecord {
+                    path: EntityQueryPath::TextSearchRank(query),
+                    ordering: Ordering::Descending,
+                    nulls: Some(NullOrdering::Last),
+                },
+                EntityQuerySortingRecord {
+                    path: temporal_axis_path,
+                    ordering: Ordering::Descending,
+                    nulls: None,
+                },
+                EntityQuerySortingRecord {
+                    path: EntityQueryPath::Uuid,
+                    ordering: Ordering::Ascending,
+                    nulls: None,
+                },
+                EntityQuerySortingRecord {
+                    path: EntityQueryPath::OwnedById,
+                    ordering: Ordering::Ascending,
+                    nulls: None,
+                },
+            ],
+            cursor,
+        },
+        conversions: Vec::new(),
+        limit: Some(limit),
+        include_drafts,
+        include_count: false,
+        include_web_ids: false,
+        include_created_by_ids: false,
+        include_edition_created_by_ids: false,
+        include_type_ids: false,
+    }
+}
+
 /// Describes the API of a store implementation for [Entities].
 ///
 /// [Entities]: Entity
@@ -548,6 +867,132 @@ pub trait EntityStore {
         params: UpdateEntityEmbeddingsParams<'_>,
     ) -> impl Future<Output = Result<(), Report<UpdateError>>> + Send;
 
+    /// Permanently removes the entities matched by the [`DeleteEntitiesParams`].
+    ///
+    /// In contrast to archiving an entity, this removes every edition of the entity, including
+    /// drafts, its embeddings and its authorization relationships. Links, which have a deleted
+    /// entity as left or right entity, are deleted as well. If `dry_run` is set, nothing is
+    /// removed but the returned response reports what would have been removed.
+    ///
+    /// # Errors
+    ///
+    /// - if the actor does not have full access to every entity which would be deleted
+    /// - if removing the entities from the store fails
+    fn permanently_delete_entities(
+        &mut self,
+        actor_id: AccountId,
+        params: DeleteEntitiesParams<'_>,
+    ) -> impl Future<Output = Result<DeleteEntitiesResponse, Report<DeletionError>>> + Send;
+
+    /// Searches for entities by blending a vector similarity search and a full-text search.
+    ///
+    /// Both searches are run as [`get_entities`] with the [`Filter`], temporal axes and drafts of
+    /// the [`SearchEntitiesParams`], so they only return entities the actor is allowed to view.
+    /// The best `limit` candidates of each search are fused using weighted reciprocal rank fusion,
+    /// and every result reports its score and which signals matched.
+    ///
+    /// # Errors
+    ///
+    /// - if neither a vector nor a keyword search is requested
+    /// - if the `limit` is zero or a weight is negative or not finite
+    /// - if the requested [`Entities`][Entity] cannot be retrieved
+    ///
+    /// [`get_entities`]: Self::get_entities
+    fn search_entities(
+        &self,
+        actor_id: AccountId,
+        params: SearchEntitiesParams<'_>,
+    ) -> impl Future<Output = Result<SearchEntitiesResponse, Report<QueryError>>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            if params.vector.is_none() && params.keyword.is_none() {
+                return Err(Report::new(QueryError)
+                    .attach_printable("Either a vector or a keyword search has to be requested")
+                    .attach(StatusCode::InvalidArgument));
+            }
+            if params.limit == 0 {
+                return Err(Report::new(QueryError)
+                    .attach_printable("The limit of a search has to be positive")
+                    .attach(StatusCode::InvalidArgument));
+            }
+            let weights = params
+                .vector
+                .iter()
+                .map(|vector| vector.weight)
+                .chain(params.keyword.iter().map(|keyword| keyword.weight));
+            for weight in weights {
+                if !weight.is_finite() || weight < 0.0 {
+                    return Err(Report::new(QueryError)
+                        .attach_printable(format!(
+                            "The weight of a search has to be a finite, non-negative number, got \
+                             {weight}"
+                        ))
+                        .attach(StatusCode::InvalidArgument));
+                }
+            }
+
+            let mut candidates = Vec::new();
+
+            if let Some(vector) = params.vector {
+                let weight = vector.weight;
+                let entities = self
+                    .get_entities(
+                        actor_id,
+                        vector_search_params(
+                            params.filter.clone(),
+                            params.temporal_axes.clone(),
+                            params.include_drafts,
+                            vector,
+                        ),
+                    )
+                    .await?
+                    .entities;
+                candidates.push((EntitySearchSignal::Vector, weight, entities));
+            }
+
+            if let Some(keyword) = params.keyword {
+                // Every edition of an entity is returned, so pages are read until enough distinct
+                // entities are found.
+                let mut entities = Vec::new();
+                let mut entity_ids = HashSet::new();
+                let mut cursor = None;
+                loop {
+                    let response = self
+                        .get_entities(
+                            actor_id,
+                            keyword_search_params(
+                                params.filter.clone(),
+                                params.temporal_axes.clone(),
+                                params.include_drafts,
+                                keyword.query.clone(),
+                                params.limit,
+                                cursor,
+                            ),
+                        )
+                        .await?;
+                    entity_ids.extend(
+                        response
+                            .entities
+                            .iter()
+                            .map(|entity| entity.metadata.record_id.entity_id),
+                    );
+                    entities.extend(response.entities);
+                    cursor = response.cursor;
+                    if cursor.is_none() || entity_ids.len() >= params.limit {
+                        break;
+                    }
+                }
+                candidates.push((EntitySearchSignal::Keyword, keyword.weight, entities));
+            }
+
+            Ok(SearchEntitiesResponse {
+                results: fuse_search_candidates(candidates, params.limit, params.rank_constant),
+            })
+        }
+    }
+
     /// Re-indexes the cache for entities.
     ///
     /// This is only needed if the entity was changed in place without an update procedure. This is
@@ -0,0 +1,201 @@
+use core::{cell::RefCell, cmp::Ordering};
+
+use error_stack::Report;
+use futures::{Stream, stream};
+use hash_graph_store::{filter::Filter, subgraph::temporal_axes::QueryTemporalAxes};
+
+use crate::store::{
+    QueryError,
+    crud::{QueryResult, Read, ReadPaginated, Sorting},
+    memory::{
+        MemoryStore,
+        filter::{Context, MemoryRecord, distance, evaluate},
+    },
+};
+
+/// A [`Sorting`] which can be applied to rows of the in-memory tables.
+pub(crate) trait MemorySorting<R: MemoryRecord>: Sorting {
+    /// Returns the cursor pointing to `row`.
+    fn cursor_of<'t>(&self, context: &Context<'t>, row: R::Row<'t>) -> Self::Cursor;
+
+    /// Compares two cursors in the order specified by the sorting.
+    fn compare_cursors(&self, lhs: &Self::Cursor, rhs: &Self::Cursor) -> Ordering;
+}
+
+pub struct MemoryQueryResult<R, S: Sorting> {
+    record: R,
+    cursor: S::Cursor,
+}
+
+impl<R, S> QueryResult<R, S> for MemoryQueryResult<R, S>
+where
+    R: MemoryRecord,
+    S: Sorting<Cursor: Clone + Send>,
+{
+    type Indices = ();
+
+    fn decode_record(&self, (): &Self::Indices) -> R {
+        self.record.clone_record()
+    }
+
+    fn decode_cursor(&self, (): &Self::Indices) -> S::Cursor {
+        self.cursor.clone()
+    }
+}
+
+/// Orders rows with a distance before rows without a distance.
+fn compare_distances(lhs: Option<f64>, rhs: Option<f64>) -> Ordering {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => lhs.total_cmp(&rhs),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+impl<A> MemoryStore<A>
+where
+    A: Send + Sync,
+{
+    /// Returns all records matching `filter`.
+    ///
+    /// If the filter contains a cosine distance, the records are ordered by their distance.
+    pub(crate) fn read_records<R: MemoryRecord>(
+        &self,
+        filter: &Filter<'_, R>,
+        temporal_axes: Option<&QueryTemporalAxes>,
+        include_drafts: bool,
+    ) -> Vec<R> {
+        let tables = self.snapshot();
+        let context = Context {
+            tables: &tables,
+            temporal_axes,
+            include_drafts,
+            patterns: RefCell::default(),
+        };
+
+        let rows = R::rows(&context)
+            .into_iter()
+            .filter(|row| evaluate(&context, *row, filter, false));
+
+        if filter.contains_cosine_distance() {
+            let mut rows = rows
+                .map(|row| (distance(&context, row, filter), row))
+                .collect::<Vec<_>>();
+            rows.sort_by(|(lhs, _), (rhs, _)| compare_distances(*lhs, *rhs));
+            rows.into_iter()
+                .map(|(_, row)| R::materialize(&context, row))
+                .collect()
+        } else {
+            rows.map(|row| R::materialize(&context, row)).collect()
+        }
+    }
+}
+
+impl<A, R, S> ReadPaginated<R, S> for MemoryStore<A>
+where
+    A: Send + Sync,
+    R: MemoryRecord + Send + Sync,
+    S: MemorySorting<R, Cursor: Clone + Send + Sync> + Sync,
+{
+    type QueryResult = MemoryQueryResult<R, S>;
+
+    type ReadPaginatedStream =
+        impl Stream<Item = Result<Self::QueryResult, Report<QueryError>>> + Send + Sync;
+
+    #[tracing::instrument(level = "info", skip(self, filter, sorting))]
+    async fn read_paginated(
+        &self,
+        filter: &Filter<'_, R>,
+        temporal_axes: Option<&QueryTemporalAxes>,
+        sorting: &S,
+        limit: Option<usize>,
+        include_drafts: bool,
+    ) -> Result<(Self::ReadPaginatedStream, ()), Report<QueryError>> {
+        let tables = self.snapshot();
+        let context = Context {
+            tables: &tables,
+            temporal_axes,
+            include_drafts,
+            patterns: RefCell::default(),
+        };
+
+        let calculate_distance = filter.contains_cosine_distance();
+        let mut rows = R::rows(&context)
+            .into_iter()
+            .filter(|row| evaluate(&context, *row, filter, false))
+            .map(|row| {
+                (
+                    calculate_distance
+                        .then(|| distance(&context, row, filter))
+                        .flatten(),
+                    sorting.cursor_of(&context, row),
+                    row,
+                )
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(
+            |(lhs_distance, lhs_cursor, _), (rhs_distance, rhs_cursor, _)| {
+                compare_distances(*lhs_distance, *rhs_distance)
+                    .then_with(|| sorting.compare_cursors(lhs_cursor, rhs_cursor))
+            },
+        );
+
+        let results = rows
+            .into_iter()
+            .filter(|(_, cursor, _)| {
+                sorting
+                    .cursor()
+                    .is_none_or(|after| sorting.compare_cursors(cursor, after).is_gt())
+            })
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|(_, cursor, row)| {
+                Ok(MemoryQueryResult {
+                    record: R::materialize(&context, row),
+                    cursor,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok((stream::iter(results), ()))
+    }
+}
+
+impl<A, R> Read<R> for MemoryStore<A>
+where
+    A: Send + Sync,
+    R: MemoryRecord + Send + Sync,
+{
+    type ReadStream = impl Stream<Item = Result<R, Report<QueryError>>> + Send + Sync;
+
+    #[tracing::instrument(level = "info", skip(self, filter))]
+    async fn read(
+        &self,
+        filter: &Filter<'_, R>,
+        temporal_axes: Option<&QueryTemporalAxes>,
+        include_drafts: bool,
+    ) -> Result<Self::ReadStream, Report<QueryError>> {
+        Ok(stream::iter(
+            self.read_records(filter, temporal_axes, include_drafts)
+                .into_iter()
+                .map(Ok),
+        ))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, filter))]
+    async fn read_one(
+        &self,
+        filter: &Filter<'_, R>,
+        temporal_axes: Option<&QueryTemporalAxes>,
+        include_drafts: bool,
+    ) -> Result<R, Report<QueryError>> {
+        let mut records = self.read_records(filter, temporal_axes, include_drafts);
+        match records.len() {
+            1 => Ok(records.pop().expect("should have exactly one record")),
+            len => {
+                Err(Report::new(QueryError)
+                    .attach_printable(format!("Expected 1 result, got {len}")))
+            }
+        }
+    }
+}
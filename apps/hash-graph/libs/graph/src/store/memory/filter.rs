@@ -0,0 +1,1224 @@
+//! Evaluation of [`Filter`]s against the in-memory tables.
+//!
+//! The evaluation mirrors the SQL generated by the Postgres store: a path may resolve to multiple
+//! values (e.g. when following an edge, which is a join in SQL) and a comparison holds if it holds
+//! for any combination of values. A path which doesn't resolve to any value behaves like `NULL`
+//! in SQL, i.e. it neither satisfies a comparison nor its negation.
+
+use alloc::borrow::Cow;
+use core::{cell::RefCell, cmp::Ordering};
+use std::collections::HashMap;
+
+use graph_types::{
+    Embedding, ontology::OntologyTypeClassificationMetadata, owned_by_id::OwnedById,
+};
+use hash_graph_store::{
+    filter::{
+        Filter, FilterExpression, JsonPath, Parameter, ParameterList, PathToken, Pattern,
+        QueryRecord, TextSearchPhrase, TextSearchQuery,
+    },
+    subgraph::temporal_axes::QueryTemporalAxes,
+};
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use temporal_versioning::{
+    LeftClosedTemporalInterval, TemporalInterval, TemporalTagged, Timestamp,
+};
+use time::OffsetDateTime;
+use type_system::{schema::OntologyTypeUuid, url::OntologyTypeVersion};
+use uuid::Uuid;
+
+use crate::store::memory::table::{
+    EntityTemporalMetadataRow, MemoryTables, OntologyEmbeddingRow, OntologyIdRow,
+    OntologyTemporalMetadataRow,
+};
+
+/// A single value a query path resolves to.
+#[derive(Debug, Clone)]
+pub(crate) enum Value<'t> {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    Text(Cow<'t, str>),
+    Uuid(Uuid),
+    Timestamp(Timestamp<()>),
+    Interval(TemporalInterval<()>),
+    /// The version of an ontology type together with the latest version of its base URL.
+    ///
+    /// The latest version is used to evaluate comparisons against the special `"latest"` text.
+    Version {
+        version: OntologyTypeVersion,
+        latest: OntologyTypeVersion,
+    },
+    Vector(&'t Embedding<'t>),
+    /// A JSON array or object, scalar JSON values are represented by their respective variants.
+    Json(Cow<'t, JsonValue>),
+}
+
+impl<'t> Value<'t> {
+    pub(crate) fn from_json(json: Cow<'t, JsonValue>) -> Self {
+        match json {
+            Cow::Borrowed(json) => match json {
+                JsonValue::Null => Self::Null,
+                JsonValue::Bool(bool) => Self::Boolean(*bool),
+                JsonValue::Number(number) => number.as_f64().map_or(Self::Null, Self::Number),
+                JsonValue::String(string) => Self::Text(Cow::Borrowed(string)),
+                JsonValue::Array(_) | JsonValue::Object(_) => Self::Json(Cow::Borrowed(json)),
+            },
+            Cow::Owned(json) => match json {
+                JsonValue::Null => Self::Null,
+                JsonValue::Bool(bool) => Self::Boolean(bool),
+                JsonValue::Number(number) => number.as_f64().map_or(Self::Null, Self::Number),
+                JsonValue::String(string) => Self::Text(Cow::Owned(string)),
+                json @ (JsonValue::Array(_) | JsonValue::Object(_)) => Self::Json(Cow::Owned(json)),
+            },
+        }
+    }
+
+    /// Serializes `value` and returns the value at `path`.
+    pub(crate) fn serialized(value: &impl Serialize, path: Option<&JsonPath<'_>>) -> Self {
+        serde_json::to_value(value)
+            .map_or(Self::Null, |json| Self::json_path(Cow::Owned(json), path))
+    }
+
+    /// Returns the value at `path` inside of `json`.
+    ///
+    /// If `path` does not exist in `json`, [`Value::Null`] is returned.
+    pub(crate) fn json_path(json: Cow<'t, JsonValue>, path: Option<&JsonPath<'_>>) -> Self {
+        let Some(path) = path else {
+            return Self::from_json(json);
+        };
+
+        match json {
+            Cow::Borrowed(json) => path
+                .path_tokens()
+                .iter()
+                .try_fold(json, |json, token| match token {
+                    PathToken::Field(field) => json.get(field.as_ref()),
+                    PathToken::Index(index) => json.get(*index),
+                })
+                .map_or(Self::Null, |json| Self::from_json(Cow::Borrowed(json))),
+            Cow::Owned(mut json) => path
+                .path_tokens()
+                .iter()
+                .try_fold(&mut json, |json, token| match token {
+                    PathToken::Field(field) => json.get_mut(field.as_ref()),
+                    PathToken::Index(index) => json.get_mut(*index),
+                })
+                .map(JsonValue::take)
+                .map_or(Self::Null, |json| Self::from_json(Cow::Owned(json))),
+        }
+    }
+
+    pub(crate) fn interval<A>(interval: LeftClosedTemporalInterval<A>) -> Self {
+        Self::Interval(interval.cast().convert())
+    }
+
+    fn from_parameter(parameter: &'t Parameter<'_>) -> Self {
+        match parameter {
+            Parameter::Boolean(bool) => Self::Boolean(*bool),
+            Parameter::I32(number) => Self::Number(f64::from(*number)),
+            Parameter::F64(number) => Self::Number(*number),
+            Parameter::Text(text) => Self::Text(Cow::Borrowed(text)),
+            Parameter::Vector(vector) => Self::Vector(vector),
+            Parameter::Any(json) => Self::from_json(Cow::Borrowed(json)),
+            Parameter::Uuid(uuid) => Self::Uuid(*uuid),
+            Parameter::OntologyTypeVersion(version) => Self::Number(f64::from(version.inner())),
+            Parameter::Timestamp(timestamp) => Self::Timestamp(*timestamp),
+        }
+    }
+
+    const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns the textual representation of the value as used by text comparisons.
+    fn text(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Self::Null | Self::Vector(_) | Self::Interval(_) => None,
+            Self::Boolean(bool) => Some(Cow::Borrowed(if *bool { "true" } else { "false" })),
+            Self::Number(number) => Some(Cow::Owned(number.to_string())),
+            Self::Text(text) => Some(Cow::Borrowed(text)),
+            Self::Uuid(uuid) => Some(Cow::Owned(uuid.to_string())),
+            Self::Timestamp(timestamp) => Some(Cow::Owned(timestamp.to_string())),
+            Self::Version { version, .. } => Some(Cow::Owned(version.inner().to_string())),
+            Self::Json(json) => Some(Cow::Owned(json.to_string())),
+        }
+    }
+
+    /// The rank of the value's type, used to provide a total order over values of different types.
+    const fn type_rank(&self) -> u8 {
+        match self {
+            Self::Null => 0,
+            Self::Boolean(_) => 1,
+            Self::Number(_) | Self::Version { .. } => 2,
+            Self::Text(_) => 3,
+            Self::Uuid(_) => 4,
+            Self::Timestamp(_) => 5,
+            Self::Interval(_) => 6,
+            Self::Vector(_) => 7,
+            Self::Json(_) => 8,
+        }
+    }
+
+    /// Compares two values in a total order.
+    ///
+    /// Values are compared by [`compare`] if possible, otherwise by their type and lastly by
+    /// their textual representation.
+    pub(crate) fn total_cmp(&self, other: &Self) -> Ordering {
+        compare(self, other).unwrap_or_else(|| {
+            self.type_rank()
+                .cmp(&other.type_rank())
+                .then_with(|| self.text().cmp(&other.text()))
+        })
+    }
+}
+
+fn compare_version(
+    version: OntologyTypeVersion,
+    latest: OntologyTypeVersion,
+    text: &str,
+) -> Option<Ordering> {
+    if text == "latest" {
+        Some(version.cmp(&latest))
+    } else {
+        text.parse::<u32>()
+            .ok()
+            .map(|other| version.inner().cmp(&other))
+    }
+}
+
+fn compare_ordered(lhs: &Value<'_>, rhs: &Value<'_>) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Boolean(lhs), Value::Boolean(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Number(lhs), Value::Number(rhs)) => lhs.partial_cmp(rhs),
+        (Value::Text(lhs), Value::Text(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Uuid(lhs), Value::Uuid(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Uuid(lhs), Value::Text(rhs)) => Uuid::parse_str(rhs).ok().map(|rhs| lhs.cmp(&rhs)),
+        (Value::Timestamp(lhs), Value::Timestamp(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Timestamp(lhs), Value::Text(rhs)) => {
+            rhs.parse::<Timestamp<()>>().ok().map(|rhs| lhs.cmp(&rhs))
+        }
+        (Value::Interval(lhs), Value::Interval(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Version { version: lhs, .. }, Value::Version { version: rhs, .. }) => {
+            Some(lhs.cmp(rhs))
+        }
+        (Value::Version { version, .. }, Value::Number(rhs)) => {
+            f64::from(version.inner()).partial_cmp(rhs)
+        }
+        (Value::Version { version, latest }, Value::Text(rhs)) => {
+            compare_version(*version, *latest, rhs)
+        }
+        (Value::Json(lhs), Value::Json(rhs)) => (lhs == rhs).then_some(Ordering::Equal),
+        (Value::Vector(lhs), Value::Vector(rhs)) => (lhs == rhs).then_some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+/// Compares two values.
+///
+/// Returns `None` if any value is null or the values cannot be compared.
+pub(crate) fn compare(lhs: &Value<'_>, rhs: &Value<'_>) -> Option<Ordering> {
+    compare_ordered(lhs, rhs).or_else(|| compare_ordered(rhs, lhs).map(Ordering::reverse))
+}
+
+/// Checks two values for equality.
+///
+/// Returns `None` if any value is null.
+fn equals(lhs: &Value<'_>, rhs: &Value<'_>) -> Option<bool> {
+    if lhs.is_null() || rhs.is_null() {
+        return None;
+    }
+
+    match (lhs, rhs) {
+        (Value::Json(lhs), Value::Json(rhs)) => Some(lhs == rhs),
+        (Value::Vector(lhs), Value::Vector(rhs)) => Some(lhs == rhs),
+        (Value::Interval(lhs), Value::Interval(rhs)) => Some(lhs == rhs),
+        _ => Some(compare(lhs, rhs) == Some(Ordering::Equal)),
+    }
+}
+
+/// Matches `text` against a SQL `LIKE` pattern.
+///
+/// `%` matches any sequence of characters, `_` matches a single character.
+fn like(text: &str, pattern: &str) -> bool {
+    let text = text.chars().collect::<Vec<_>>();
+    let pattern = pattern.chars().collect::<Vec<_>>();
+
+    // Greedy matching with backtracking to the last `%`
+    let (mut text_index, mut pattern_index) = (0, 0);
+    let mut backtrack = None;
+    while text_index < text.len() {
+        match pattern.get(pattern_index) {
+            Some('%') => {
+                backtrack = Some((pattern_index, text_index));
+                pattern_index += 1;
+            }
+            Some(&character) if character == '_' || character == text[text_index] => {
+                text_index += 1;
+                pattern_index += 1;
+            }
+            _ => match backtrack {
+                Some((wildcard_index, matched_index)) => {
+                    pattern_index = wildcard_index + 1;
+                    text_index = matched_index + 1;
+                    backtrack = Some((wildcard_index, matched_index + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[pattern_index..]
+        .iter()
+        .all(|character| *character == '%')
+}
+
+/// Calculates the cosine distance between two vectors.
+///
+/// Returns `None` if the vectors have different dimensions or any vector has no magnitude.
+#[expect(
+    clippy::float_arithmetic,
+    reason = "The cosine distance is a floating point calculation"
+)]
+pub(crate) fn cosine_distance(lhs: &Embedding<'_>, rhs: &Embedding<'_>) -> Option<f64> {
+    let (mut dot, mut lhs_norm, mut rhs_norm, mut dimensions) = (0.0_f64, 0.0_f64, 0.0_f64, 0);
+    let mut rhs_iter = rhs.iter();
+    for lhs in lhs.iter() {
+        let rhs = f64::from(rhs_iter.next()?);
+        let lhs = f64::from(lhs);
+        dot += lhs * rhs;
+        lhs_norm += lhs * lhs;
+        rhs_norm += rhs * rhs;
+        dimensions += 1;
+    }
+    if rhs_iter.next().is_some() || dimensions == 0 || lhs_norm == 0.0 || rhs_norm == 0.0 {
+        return None;
+    }
+
+    Some(1.0 - dot / (lhs_norm.sqrt() * rhs_norm.sqrt()))
+}
+
+/// The stop words of the `english` text search configuration of Postgres.
+#[rustfmt::skip]
+const STOP_WORDS: [&str; 127] = [
+    "a", "about", "above", "after", "again", "against", "all", "am", "an", "and", "any", "are",
+    "as", "at", "be", "because", "been", "before", "being", "below", "between", "both", "but", "by",
+    "can", "did", "do", "does", "doing", "don", "down", "during", "each", "few", "for", "from",
+    "further", "had", "has", "have", "having", "he", "her", "here", "hers", "herself", "him",
+    "himself", "his", "how", "i", "if", "in", "into", "is", "it", "its", "itself", "just", "me",
+    "more", "most", "my", "myself", "no", "nor", "not", "now", "of", "off", "on", "once", "only",
+    "or", "other", "our", "ours", "ourselves", "out", "over", "own", "s", "same", "she", "should",
+    "so", "some", "such", "t", "than", "that", "the", "their", "theirs", "them", "themselves",
+    "then", "there", "these", "they", "this", "those", "through", "to", "too", "under", "until",
+    "up", "very", "was", "we", "were", "what", "when", "where", "which", "while", "who", "whom",
+    "why", "will", "with", "you", "your", "yours", "yourself", "yourselves",
+];
+
+/// Normalizes `word` like the `english` text search configuration of Postgres.
+///
+/// Words are lowercased and stemmed, stop words are `None` as they only occupy a position.
+fn lexeme(stemmer: &Stemmer, word: &str) -> Option<String> {
+    let word = word.to_lowercase();
+    (!STOP_WORDS.contains(&word.as_str())).then(|| stemmer.stem(&word).into_owned())
+}
+
+/// Splits `value` into documents of lexemes, one document per string.
+///
+/// Strings inside of JSON values are searched recursively, object keys are not searched.
+fn text_search_documents(stemmer: &Stemmer, value: &Value<'_>) -> Vec<Vec<Option<String>>> {
+    fn collect(stemmer: &Stemmer, json: &JsonValue, documents: &mut Vec<Vec<Option<String>>>) {
+        match json {
+            JsonValue::String(text) => documents.push(text_search_words(stemmer, text)),
+            JsonValue::Array(values) => {
+                for value in values {
+                    collect(stemmer, value, documents);
+                }
+            }
+            JsonValue::Object(object) => {
+                for value in object.values() {
+                    collect(stemmer, value, documents);
+                }
+            }
+            JsonValue::Null | JsonValue::Bool(_) | JsonValue::Number(_) => {}
+        }
+    }
+
+    fn text_search_words(stemmer: &Stemmer, text: &str) -> Vec<Option<String>> {
+        text.split(|character: char| !character.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| lexeme(stemmer, word))
+            .collect()
+    }
+
+    let mut documents = Vec::new();
+    match value {
+        Value::Text(text) => documents.push(text_search_words(stemmer, text)),
+        Value::Json(json) => collect(stemmer, json, &mut documents),
+        _ => {}
+    }
+    documents
+}
+
+/// The lexemes of a [`TextSearchPhrase`].
+///
+/// Stop words inside of the phrase match any word, leading and trailing stop words are removed.
+struct PhraseLexemes {
+    lexemes: Vec<Option<String>>,
+    prefix: bool,
+}
+
+impl PhraseLexemes {
+    /// Returns `None` if the phrase only consists of stop words.
+    fn new(stemmer: &Stemmer, phrase: &TextSearchPhrase) -> Option<Self> {
+        let lexemes = phrase
+            .words()
+            .iter()
+            .map(|word| lexeme(stemmer, word))
+            .collect::<Vec<_>>();
+        let first = lexemes.iter().position(Option::is_some)?;
+        let last = lexemes.iter().rposition(Option::is_some)?;
+        Some(Self {
+            prefix: phrase.is_prefix() && last + 1 == lexemes.len(),
+            lexemes: lexemes[first..=last].to_vec(),
+        })
+    }
+
+    /// Counts how often the phrase occurs in `document`.
+    fn occurrences(&self, document: &[Option<String>]) -> u32 {
+        document
+            .windows(self.lexemes.len())
+            .filter(|window| {
+                window.iter().zip(&self.lexemes).enumerate().all(
+                    |(index, (word, expected))| match (word, expected) {
+                        (_, None) => true,
+                        (None, Some(_)) => false,
+                        (Some(word), Some(expected))
+                            if self.prefix && index + 1 == self.lexemes.len() =>
+                        {
+                            word.starts_with(expected.as_str())
+                        }
+                        (Some(word), Some(expected)) => word == expected,
+                    },
+                )
+            })
+            .fold(0, |count, _| count.saturating_add(1))
+    }
+}
+
+/// Ranks `value` for a full-text search `query`.
+///
+/// Words are normalized like the `english` text search configuration of Postgres. The rank is the
+/// number of occurrences of the phrases in `query` if every phrase occurs and `0` otherwise. A
+/// query which only consists of stop words does not match anything.
+///
+/// Returns `None` if `value` is null.
+pub(crate) fn text_search_rank(value: &Value<'_>, query: &TextSearchQuery) -> Option<f64> {
+    if value.is_null() {
+        return None;
+    }
+
+    let stemmer = Stemmer::create(Algorithm::English);
+    let documents = text_search_documents(&stemmer, value);
+    let phrases = query
+        .phrases()
+        .iter()
+        .filter_map(|phrase| PhraseLexemes::new(&stemmer, phrase))
+        .collect::<Vec<_>>();
+    if phrases.is_empty() {
+        return Some(0.0);
+    }
+
+    let mut rank = 0_u32;
+    for phrase in phrases {
+        let occurrences = documents
+            .iter()
+            .map(|document| phrase.occurrences(document))
+            .fold(0_u32, u32::saturating_add);
+        if occurrences == 0 {
+            return Some(0.0);
+        }
+        rank = rank.saturating_add(occurrences);
+    }
+    Some(f64::from(rank))
+}
+
+/// The state a [`Filter`] is evaluated with.
+pub(crate) struct Context<'t> {
+    pub tables: &'t MemoryTables,
+    pub temporal_axes: Option<&'t QueryTemporalAxes>,
+    pub include_drafts: bool,
+    /// Patterns of [`Filter::Matches`] compiled so far, `None` if a pattern is invalid.
+    pub patterns: RefCell<HashMap<String, Option<Pattern>>>,
+}
+
+impl Context<'_> {
+    /// Returns the compiled `pattern`, compiling every pattern only once.
+    fn pattern(&self, pattern: &str) -> Option<Pattern> {
+        if let Some(compiled) = self.patterns.borrow().get(pattern) {
+            return compiled.clone();
+        }
+        let compiled = pattern.parse::<Pattern>().ok();
+        self.patterns
+            .borrow_mut()
+            .insert(pattern.to_owned(), compiled.clone());
+        compiled
+    }
+
+    /// Returns `true` if the ontology temporal metadata is visible at the temporal axes.
+    pub(crate) fn ontology_visible(&self, temporal_metadata: &OntologyTemporalMetadataRow) -> bool {
+        match self.temporal_axes {
+            None => true,
+            Some(QueryTemporalAxes::DecisionTime { pinned, .. }) => temporal_metadata
+                .transaction_time
+                .contains_point(&pinned.timestamp),
+            Some(QueryTemporalAxes::TransactionTime { variable, .. }) => temporal_metadata
+                .transaction_time
+                .overlaps(&variable.interval),
+        }
+    }
+
+    /// Returns `true` if the entity temporal metadata is visible at the temporal axes.
+    ///
+    /// Drafts are only visible if drafts are included.
+    pub(crate) fn entity_visible(&self, temporal_metadata: &EntityTemporalMetadataRow) -> bool {
+        if !self.include_drafts && temporal_metadata.draft_id.is_some() {
+            return false;
+        }
+
+        match self.temporal_axes {
+            None => true,
+            Some(QueryTemporalAxes::DecisionTime { pinned, variable }) => {
+                temporal_metadata
+                    .transaction_time
+                    .contains_point(&pinned.timestamp)
+                    && temporal_metadata.decision_time.overlaps(&variable.interval)
+            }
+            Some(QueryTemporalAxes::TransactionTime { pinned, variable }) => {
+                temporal_metadata
+                    .decision_time
+                    .contains_point(&pinned.timestamp)
+                    && temporal_metadata
+                        .transaction_time
+                        .overlaps(&variable.interval)
+            }
+        }
+    }
+}
+
+/// A record which can be read from the in-memory tables.
+pub(crate) trait MemoryRecord: QueryRecord + Sized {
+    /// A reference to the rows in the tables making up a single record.
+    type Row<'t>: Copy;
+
+    /// Returns all rows which are visible in `context`.
+    fn rows<'t>(context: &Context<'t>) -> Vec<Self::Row<'t>>;
+
+    /// Returns the values `path` resolves to for `row`.
+    fn values<'t>(
+        context: &Context<'t>,
+        row: Self::Row<'t>,
+        path: &Self::QueryPath<'_>,
+    ) -> Vec<Value<'t>>;
+
+    /// Creates the record from `row`.
+    fn materialize<'t>(context: &Context<'t>, row: Self::Row<'t>) -> Self;
+
+    /// Creates a copy of the record.
+    ///
+    /// Not all records implement [`Clone`], so this is provided by the record itself.
+    fn clone_record(&self) -> Self;
+}
+
+/// The stored data of an ontology type.
+pub(crate) trait OntologyTypeRow {
+    type Schema: Serialize;
+
+    fn schema(&self) -> &Self::Schema;
+
+    fn embedding(&self) -> Option<&OntologyEmbeddingRow>;
+}
+
+/// The rows making up an ontology type record.
+pub(crate) struct OntologyRow<'t, T> {
+    pub ontology_id: OntologyTypeUuid,
+    pub metadata: &'t OntologyIdRow,
+    pub temporal_metadata: &'t OntologyTemporalMetadataRow,
+    pub record: &'t T,
+}
+
+impl<T> Clone for OntologyRow<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for OntologyRow<'_, T> {}
+
+/// Returns the ontology rows of `records` which are visible in `context`.
+pub(crate) fn ontology_rows<'t, K, T>(
+    context: &Context<'t>,
+    records: impl IntoIterator<Item = (K, &'t T)>,
+) -> Vec<OntologyRow<'t, T>>
+where
+    K: Into<OntologyTypeUuid>,
+    T: 't,
+{
+    records
+        .into_iter()
+        .flat_map(|(ontology_id, record)| {
+            let ontology_id: OntologyTypeUuid = ontology_id.into();
+            context
+                .tables
+                .ontology_ids
+                .get(&ontology_id)
+                .into_iter()
+                .flat_map(move |metadata| {
+                    metadata
+                        .temporal_metadata
+                        .iter()
+                        .filter(|temporal_metadata| context.ontology_visible(temporal_metadata))
+                        .map(move |temporal_metadata| OntologyRow {
+                            ontology_id,
+                            metadata,
+                            temporal_metadata,
+                            record,
+                        })
+                })
+        })
+        .collect()
+}
+
+/// The fields shared by all ontology types.
+pub(crate) enum OntologyField<'q, 'p> {
+    BaseUrl,
+    Version,
+    VersionedUrl,
+    TransactionTime,
+    OwnedById,
+    OntologyId,
+    AdditionalMetadata,
+    EditionProvenance(Option<&'q JsonPath<'p>>),
+    Embedding,
+    Schema(Option<&'q JsonPath<'p>>),
+    /// A top-level text field of the schema.
+    SchemaText(&'static str),
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum AdditionalMetadata {
+    Owned {
+        web_id: OwnedById,
+    },
+    External {
+        #[serde(with = "codec::serde::time")]
+        fetched_at: OffsetDateTime,
+    },
+}
+
+/// Returns the values of a field shared by all ontology types.
+pub(crate) fn ontology_values<'t, T: OntologyTypeRow>(
+    context: &Context<'t>,
+    row: OntologyRow<'t, T>,
+    field: &OntologyField<'_, '_>,
+) -> Vec<Value<'t>> {
+    let url = &row.metadata.url;
+    let value = match field {
+        OntologyField::BaseUrl => Value::Text(Cow::Borrowed(url.base_url.as_str())),
+        OntologyField::Version => Value::Version {
+            version: url.version,
+            latest: context
+                .tables
+                .base_urls
+                .get(&url.base_url)
+                .copied()
+                .unwrap_or(url.version),
+        },
+        OntologyField::VersionedUrl => Value::Text(Cow::Owned(url.to_string())),
+        OntologyField::TransactionTime => Value::interval(row.temporal_metadata.transaction_time),
+        OntologyField::OwnedById => match row.metadata.classification {
+            OntologyTypeClassificationMetadata::Owned { owned_by_id } => {
+                Value::Uuid(owned_by_id.into_uuid())
+            }
+            OntologyTypeClassificationMetadata::External { .. } => Value::Null,
+        },
+        OntologyField::OntologyId => Value::Uuid(row.ontology_id.into_uuid()),
+        OntologyField::AdditionalMetadata => Value::serialized(
+            &match row.metadata.classification {
+                OntologyTypeClassificationMetadata::Owned { owned_by_id } => {
+                    AdditionalMetadata::Owned {
+                        web_id: owned_by_id,
+                    }
+                }
+                OntologyTypeClassificationMetadata::External { fetched_at } => {
+                    AdditionalMetadata::External { fetched_at }
+                }
+            },
+            None,
+        ),
+        OntologyField::EditionProvenance(path) => {
+            Value::serialized(&row.temporal_metadata.provenance, *path)
+        }
+        OntologyField::Embedding => row
+            .record
+            .embedding()
+            .map_or(Value::Null, |embedding| Value::Vector(&embedding.embedding)),
+        OntologyField::Schema(path) => Value::serialized(row.record.schema(), *path),
+        OntologyField::SchemaText(field) => {
+            match Value::serialized(row.record.schema(), None) {
+                Value::Json(Cow::Owned(JsonValue::Object(mut object))) => object
+                    .remove(*field)
+                    .map_or(Value::Null, |json| match json {
+                        JsonValue::Null => Value::Null,
+                        JsonValue::String(text) => Value::Text(Cow::Owned(text)),
+                        json => Value::Text(Cow::Owned(json.to_string())),
+                    }),
+                _ => Value::Null,
+            }
+        }
+    };
+
+    vec![value]
+}
+
+/// Evaluates the values of `expression` for `row`.
+///
+/// If a path does not resolve to any value, a single [`Value::Null`] is returned.
+fn expression_values<'t, R: MemoryRecord>(
+    context: &Context<'t>,
+    row: R::Row<'t>,
+    expression: &'t FilterExpression<'_, R>,
+) -> Vec<Value<'t>> {
+    let mut values = match expression {
+        FilterExpression::Path { path } => R::values(context, row, path),
+        FilterExpression::Parameter { parameter, .. } => vec![Value::from_parameter(parameter)],
+    };
+    if values.is_empty() {
+        values.push(Value::Null);
+    }
+    values
+}
+
+/// Returns `true` if `predicate` returns `expected` for any combination of values.
+fn any_pair<'t, R: MemoryRecord>(
+    context: &Context<'t>,
+    row: R::Row<'t>,
+    lhs: &'t FilterExpression<'_, R>,
+    rhs: &'t FilterExpression<'_, R>,
+    expected: bool,
+    predicate: impl Fn(&Value<'_>, &Value<'_>) -> Option<bool>,
+) -> bool {
+    let lhs = expression_values(context, row, lhs);
+    let rhs = expression_values(context, row, rhs);
+    lhs.iter()
+        .any(|lhs| rhs.iter().any(|rhs| predicate(lhs, rhs) == Some(expected)))
+}
+
+fn like_pair(lhs: &Value<'_>, rhs: &Value<'_>, pattern: impl Fn(&str) -> String) -> Option<bool> {
+    Some(like(&lhs.text()?, &pattern(&rhs.text()?)))
+}
+
+fn contained_in(value: &Value<'_>, list: &ParameterList<'_>) -> Option<bool> {
+    if let ParameterList::Parameters(parameters) = list {
+        // Like `IN` in SQL, a match takes precedence over comparisons with null
+        let mut contained = Some(false);
+        for parameter in parameters {
+            match equals(value, &Value::from_parameter(parameter)) {
+                Some(true) => return Some(true),
+                Some(false) => {}
+                None => contained = None,
+            }
+        }
+        return contained;
+    }
+
+    let Value::Uuid(uuid) = value else {
+        return value.text().map(|text| {
+            Uuid::parse_str(&text)
+                .is_ok_and(|uuid| contained_in(&Value::Uuid(uuid), list) == Some(true))
+        });
+    };
+
+    Some(match list {
+        ParameterList::DataTypeIds(ids) => ids.iter().any(|id| id.into_uuid() == *uuid),
+        ParameterList::PropertyTypeIds(ids) => ids.iter().any(|id| id.into_uuid() == *uuid),
+        ParameterList::EntityTypeIds(ids) => ids.iter().any(|id| id.into_uuid() == *uuid),
+        ParameterList::EntityEditionIds(ids) => ids.iter().any(|id| id.into_uuid() == *uuid),
+        ParameterList::Parameters(_) => {
+            unreachable!("A list of parameters should be handled by now")
+        }
+    })
+}
+
+/// Evaluates `filter` for `row`.
+///
+/// If `negated` is `true`, the negation of the filter is evaluated. Negations are pushed down to
+/// the individual comparisons so that the semantics of `NULL` values match the semantics of SQL.
+pub(crate) fn evaluate<'t, R: MemoryRecord>(
+    context: &Context<'t>,
+    row: R::Row<'t>,
+    filter: &'t Filter<'_, R>,
+    negated: bool,
+) -> bool {
+    match filter {
+        Filter::All(filters) if negated => filters
+            .iter()
+            .any(|filter| evaluate(context, row, filter, true)),
+        Filter::All(filters) => filters
+            .iter()
+            .all(|filter| evaluate(context, row, filter, false)),
+        Filter::Any(filters) if negated => filters
+            .iter()
+            .all(|filter| evaluate(context, row, filter, true)),
+        Filter::Any(filters) => filters
+            .iter()
+            .any(|filter| evaluate(context, row, filter, false)),
+        Filter::Not(filter) => evaluate(context, row, filter, !negated),
+        Filter::Equal(lhs, rhs) => {
+            evaluate_equal(context, row, lhs.as_ref(), rhs.as_ref(), negated)
+        }
+        Filter::NotEqual(lhs, rhs) => {
+            evaluate_equal(context, row, lhs.as_ref(), rhs.as_ref(), !negated)
+        }
+        Filter::Exists(expression) => {
+            evaluate_equal(context, row, Some(expression), None, !negated)
+        }
+        Filter::IsNull(expression) => evaluate_equal(context, row, Some(expression), None, negated),
+        Filter::Greater(lhs, rhs) => any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+            compare(lhs, rhs).map(Ordering::is_gt)
+        }),
+        Filter::GreaterOrEqual(lhs, rhs) => {
+            any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+                compare(lhs, rhs).map(Ordering::is_ge)
+            })
+        }
+        Filter::Less(lhs, rhs) => any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+            compare(lhs, rhs).map(Ordering::is_lt)
+        }),
+        Filter::LessOrEqual(lhs, rhs) => any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+            compare(lhs, rhs).map(Ordering::is_le)
+        }),
+        Filter::CosineDistance(lhs, rhs, max) => {
+            let max = expression_values(context, row, max);
+            any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+                let (Value::Vector(lhs), Value::Vector(rhs)) = (lhs, rhs) else {
+                    return None;
+                };
+                let distance = Value::Number(cosine_distance(lhs, rhs)?);
+                max.iter()
+                    .find_map(|max| compare(&distance, max))
+                    .map(Ordering::is_le)
+            })
+        }
+        Filter::In(expression, list) => expression_values(context, row, expression)
+            .iter()
+            .any(|value| contained_in(value, list) == Some(!negated)),
+        Filter::StartsWith(lhs, rhs) => any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+            like_pair(lhs, rhs, |rhs| format!("{rhs}%"))
+        }),
+        Filter::EndsWith(lhs, rhs) => any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+            like_pair(lhs, rhs, |rhs| format!("%{rhs}"))
+        }),
+        Filter::ContainsSegment(lhs, rhs) => {
+            any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+                like_pair(lhs, rhs, |rhs| format!("%{rhs}%"))
+            })
+        }
+        Filter::ILike(lhs, rhs) => any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+            Some(like(
+                &lhs.text()?.to_lowercase(),
+                &rhs.text()?.to_lowercase(),
+            ))
+        }),
+        Filter::Matches(lhs, rhs) => any_pair(context, row, lhs, rhs, !negated, |lhs, rhs| {
+            // Parameters are validated beforehand, but a pattern read from a path may be invalid,
+            // which is an error in Postgres, so it neither matches nor mismatches
+            let pattern = context.pattern(&rhs.text()?)?;
+            Some(pattern.is_match(&lhs.text()?))
+        }),
+        Filter::FullTextSearch(expression, query) => expression_values(context, row, expression)
+            .iter()
+            .any(|value| text_search_rank(value, query).map(|rank| rank > 0.0) == Some(!negated)),
+    }
+}
+
+fn evaluate_equal<'t, 'p, R: MemoryRecord>(
+    context: &Context<'t>,
+    row: R::Row<'t>,
+    lhs: Option<&'t FilterExpression<'p, R>>,
+    rhs: Option<&'t FilterExpression<'p, R>>,
+    negated: bool,
+) -> bool {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => any_pair(context, row, lhs, rhs, !negated, equals),
+        (Some(expression), None) | (None, Some(expression)) => {
+            let values = expression_values(context, row, expression);
+            if negated {
+                values.iter().any(|value| !value.is_null())
+            } else {
+                values.iter().any(Value::is_null)
+            }
+        }
+        (None, None) => !negated,
+    }
+}
+
+/// Returns the minimal cosine distance of `row` for the first [`Filter::CosineDistance`] in
+/// `filter`.
+///
+/// Returns `None` if `filter` does not contain a cosine distance or the distance cannot be
+/// calculated.
+pub(crate) fn distance<'t, R: MemoryRecord>(
+    context: &Context<'t>,
+    row: R::Row<'t>,
+    filter: &'t Filter<'_, R>,
+) -> Option<f64> {
+    match filter {
+        Filter::All(filters) | Filter::Any(filters) => filters
+            .iter()
+            .find_map(|filter| distance(context, row, filter)),
+        Filter::Not(filter) => distance(context, row, filter),
+        Filter::CosineDistance(lhs, rhs, _) => {
+            let lhs = expression_values(context, row, lhs);
+            let rhs = expression_values(context, row, rhs);
+            lhs.iter()
+                .filter_map(|lhs| match lhs {
+                    Value::Vector(lhs) => Some(*lhs),
+                    _ => None,
+                })
+                .flat_map(|lhs| {
+                    rhs.iter().filter_map(move |rhs| match rhs {
+                        Value::Vector(rhs) => cosine_distance(lhs, rhs),
+                        _ => None,
+                    })
+                })
+                .min_by(f64::total_cmp)
+        }
+        Filter::Equal(..)
+        | Filter::NotEqual(..)
+        | Filter::Exists(..)
+        | Filter::IsNull(..)
+        | Filter::Greater(..)
+        | Filter::GreaterOrEqual(..)
+        | Filter::Less(..)
+        | Filter::LessOrEqual(..)
+        | Filter::In(..)
+        | Filter::StartsWith(..)
+        | Filter::EndsWith(..)
+        | Filter::ContainsSegment(..)
+        | Filter::ILike(..)
+        | Filter::Matches(..)
+        | Filter::FullTextSearch(..) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::Cow;
+    use core::{cell::RefCell, cmp::Ordering};
+
+    use graph_types::{
+        Embedding,
+        knowledge::entity::{DraftId, EntityEditionId, EntityUuid},
+        owned_by_id::OwnedById,
+    };
+    use hash_graph_store::{
+        filter::{JsonPath, PathToken, TextSearchQuery},
+        subgraph::temporal_axes::{
+            PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
+            VariableTemporalAxisUnresolved,
+        },
+    };
+    use serde_json::json;
+    use temporal_versioning::{
+        ClosedTemporalBound, LeftClosedTemporalInterval, LimitedTemporalBound, OpenTemporalBound,
+        TemporalBound, Timestamp,
+    };
+    use type_system::url::OntologyTypeVersion;
+    use uuid::Uuid;
+
+    use super::{Context, Value, compare, cosine_distance, equals, like, text_search_rank};
+    use crate::store::memory::table::{EntityTemporalMetadataRow, MemoryTables};
+
+    fn timestamp<A>(timestamp: &str) -> Timestamp<A> {
+        timestamp.parse().expect("could not parse timestamp")
+    }
+
+    fn json_path(tokens: &[&'static str]) -> JsonPath<'static> {
+        JsonPath::from_path_tokens(
+            tokens
+                .iter()
+                .map(|token| {
+                    token.parse().map_or_else(
+                        |_| PathToken::Field(Cow::Borrowed(*token)),
+                        PathToken::Index,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn like_patterns() {
+        assert!(like("Hello World", "Hello%"));
+        assert!(like("Hello World", "%World"));
+        assert!(like("Hello World", "%o W%"));
+        assert!(like("Hello World", "H_llo World"));
+        assert!(like("Hello World", "%"));
+        assert!(like("", "%"));
+        assert!(like("aXbXc", "%X%c"));
+        assert!(like("\u{e4}\u{e4}h", "_\u{e4}h"));
+
+        assert!(!like("Hello World", "hello%"));
+        assert!(!like("Hello World", "Hello"));
+        assert!(!like("Hello World", "H_llo"));
+        assert!(!like("abc", "a%c%d"));
+        assert!(!like("", "_"));
+    }
+
+    #[test]
+    fn json_paths() {
+        let json = json!({
+            "null": null,
+            "object": { "number": 1 },
+            "array": [true, "text"],
+        });
+
+        let value = |tokens| Value::json_path(Cow::Borrowed(&json), Some(&json_path(tokens)));
+        assert!(matches!(value(&["null"]), Value::Null));
+        assert!(matches!(value(&["missing"]), Value::Null));
+        assert!(matches!(value(&["object", "missing"]), Value::Null));
+        assert!(matches!(value(&["array", "2"]), Value::Null));
+        assert_eq!(
+            compare(&value(&["object", "number"]), &Value::Number(1.0)),
+            Some(Ordering::Equal)
+        );
+        assert!(matches!(value(&["array", "0"]), Value::Boolean(true)));
+        assert!(matches!(value(&["array", "1"]), Value::Text(text) if text == "text"));
+        assert!(matches!(value(&["object"]), Value::Json(_)));
+
+        assert!(matches!(
+            Value::json_path(Cow::Owned(json.clone()), Some(&json_path(&["array", "1"]))),
+            Value::Text(text) if text == "text"
+        ));
+    }
+
+    #[test]
+    fn comparisons() {
+        let uuid = Uuid::new_v4();
+        let text = |text: &'static str| Value::Text(Cow::Borrowed(text));
+
+        assert_eq!(
+            compare(&Value::Number(1.0), &Value::Number(2.0)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(compare(&text("b"), &text("a")), Some(Ordering::Greater));
+        assert_eq!(
+            compare(
+                &Value::Uuid(uuid),
+                &Value::Text(Cow::Owned(uuid.to_string()))
+            ),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare(
+                &Value::Text(Cow::Owned(uuid.to_string())),
+                &Value::Uuid(uuid)
+            ),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(compare(&Value::Number(1.0), &text("1")), None);
+
+        let version = Value::Version {
+            version: OntologyTypeVersion::new(2),
+            latest: OntologyTypeVersion::new(3),
+        };
+        assert_eq!(compare(&version, &text("latest")), Some(Ordering::Less));
+        assert_eq!(compare(&version, &text("2")), Some(Ordering::Equal));
+        assert_eq!(
+            compare(&version, &Value::Number(1.0)),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn null_semantics() {
+        assert_eq!(equals(&Value::Null, &Value::Null), None);
+        assert_eq!(equals(&Value::Null, &Value::Boolean(true)), None);
+        assert_eq!(compare(&Value::Null, &Value::Number(1.0)), None);
+        assert_eq!(
+            equals(&Value::Boolean(true), &Value::Boolean(false)),
+            Some(false)
+        );
+
+        assert_eq!(
+            Value::Null.total_cmp(&Value::Boolean(false)),
+            Ordering::Less
+        );
+        assert_eq!(
+            Value::Number(1.0).total_cmp(&Value::Text(Cow::Borrowed("1"))),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn cosine_distances() {
+        let embedding = |vector: &[f32]| Embedding::from(vector.to_vec());
+
+        assert_eq!(
+            cosine_distance(&embedding(&[1.0, 0.0]), &embedding(&[2.0, 0.0])),
+            Some(0.0)
+        );
+        assert_eq!(
+            cosine_distance(&embedding(&[1.0, 0.0]), &embedding(&[0.0, 1.0])),
+            Some(1.0)
+        );
+        assert_eq!(
+            cosine_distance(&embedding(&[1.0, 0.0]), &embedding(&[-1.0, 0.0])),
+            Some(2.0)
+        );
+        assert_eq!(
+            cosine_distance(&embedding(&[1.0, 0.0]), &embedding(&[1.0, 0.0, 0.0])),
+            None
+        );
+        assert_eq!(
+            cosine_distance(&embedding(&[0.0, 0.0]), &embedding(&[1.0, 0.0])),
+            None
+        );
+        assert_eq!(cosine_distance(&embedding(&[]), &embedding(&[])), None);
+    }
+
+    #[test]
+    fn text_search_ranks() {
+        let query = |query: &str| {
+            query
+                .parse::<TextSearchQuery>()
+                .expect("could not parse query")
+        };
+        let text = Value::Text(Cow::Borrowed("The quick brown fox jumps over the lazy dog"));
+        let json = Value::Json(Cow::Owned(json!({
+            "title": "Quick brown",
+            "tags": ["fox", "quick"],
+        })));
+
+        assert_eq!(text_search_rank(&text, &query("fox")), Some(1.0));
+        assert_eq!(text_search_rank(&text, &query("the fox")), Some(1.0));
+        assert_eq!(text_search_rank(&text, &query("the")), Some(0.0));
+        assert_eq!(text_search_rank(&text, &query("jumping dogs")), Some(2.0));
+        assert_eq!(
+            text_search_rank(&text, &query("\"quick the fox\"")),
+            Some(1.0)
+        );
+        assert_eq!(
+            text_search_rank(&text, &query("\"brown the fox\"")),
+            Some(0.0)
+        );
+        assert_eq!(text_search_rank(&text, &query("\"brown fox\"")), Some(1.0));
+        assert_eq!(text_search_rank(&text, &query("\"fox brown\"")), Some(0.0));
+        assert_eq!(text_search_rank(&text, &query("jump*")), Some(1.0));
+        assert_eq!(text_search_rank(&text, &query("fox cat")), Some(0.0));
+
+        assert_eq!(text_search_rank(&json, &query("quick")), Some(2.0));
+        assert_eq!(text_search_rank(&json, &query("\"brown fox\"")), Some(0.0));
+        assert_eq!(text_search_rank(&json, &query("title")), Some(0.0));
+
+        assert_eq!(text_search_rank(&Value::Null, &query("fox")), None);
+    }
+
+    #[test]
+    fn entity_visibility() {
+        let tables = MemoryTables::default();
+        let row = |draft_id| EntityTemporalMetadataRow {
+            owned_by_id: OwnedById::new(Uuid::new_v4()),
+            entity_uuid: EntityUuid::new(Uuid::new_v4()),
+            draft_id,
+            edition_id: EntityEditionId::new(Uuid::new_v4()),
+            decision_time: LeftClosedTemporalInterval::new(
+                ClosedTemporalBound::Inclusive(timestamp("2000-01-01T00:00:00Z")),
+                OpenTemporalBound::Exclusive(timestamp("2001-01-01T00:00:00Z")),
+            ),
+            transaction_time: LeftClosedTemporalInterval::new(
+                ClosedTemporalBound::Inclusive(timestamp("2000-01-01T00:00:00Z")),
+                OpenTemporalBound::Unbounded,
+            ),
+        };
+        let decision_time = |start: &str, end: &str| {
+            QueryTemporalAxesUnresolved::DecisionTime {
+                pinned: PinnedTemporalAxisUnresolved::new(None),
+                variable: VariableTemporalAxisUnresolved::new(
+                    Some(TemporalBound::Inclusive(timestamp(start))),
+                    Some(LimitedTemporalBound::Inclusive(timestamp(end))),
+                ),
+            }
+            .resolve()
+        };
+        let transaction_time = |pinned: &str, start: &str| {
+            QueryTemporalAxesUnresolved::TransactionTime {
+                pinned: PinnedTemporalAxisUnresolved::new(Some(timestamp(pinned))),
+                variable: VariableTemporalAxisUnresolved::new(
+                    Some(TemporalBound::Inclusive(timestamp(start))),
+                    None,
+                ),
+            }
+            .resolve()
+        };
+        let visible = |temporal_axes, include_drafts, row| {
+            Context {
+                tables: &tables,
+                temporal_axes: Some(&temporal_axes),
+                include_drafts,
+                patterns: RefCell::default(),
+            }
+            .entity_visible(&row)
+        };
+
+        let live = || row(None);
+        let draft = || row(Some(DraftId::new(Uuid::new_v4())));
+
+        for (temporal_axes, expected) in [
+            (
+                decision_time("2000-06-01T00:00:00Z", "2000-06-01T00:00:00Z"),
+                true,
+            ),
+            (
+                decision_time("1999-01-01T00:00:00Z", "2000-01-01T00:00:00Z"),
+                true,
+            ),
+            (
+                decision_time("1999-01-01T00:00:00Z", "1999-12-31T00:00:00Z"),
+                false,
+            ),
+            (
+                decision_time("2001-01-01T00:00:00Z", "2002-01-01T00:00:00Z"),
+                false,
+            ),
+            (
+                transaction_time("2000-06-01T00:00:00Z", "1999-01-01T00:00:00Z"),
+                true,
+            ),
+            (
+                transaction_time("2001-06-01T00:00:00Z", "1999-01-01T00:00:00Z"),
+                false,
+            ),
+        ] {
+            assert_eq!(
+                visible(temporal_axes.clone(), false, live()),
+                expected,
+                "unexpected visibility at {temporal_axes:?}"
+            );
+            assert_eq!(visible(temporal_axes.clone(), true, draft()), expected);
+            assert!(!visible(temporal_axes, false, draft()));
+        }
+
+        let context = Context {
+            tables: &tables,
+            temporal_axes: None,
+            include_drafts: false,
+            patterns: RefCell::default(),
+        };
+        assert!(context.entity_visible(&live()));
+        assert!(!context.entity_visible(&draft()));
+    }
+}
@@ -0,0 +1,2816 @@
+use alloc::{borrow::Cow, sync::Arc};
+use core::{borrow::Borrow, cell::RefCell, cmp::Ordering, iter::once, mem};
+use std::collections::{HashMap, HashSet};
+
+use authorization::{
+    AuthorizationApi,
+    backend::ModifyRelationshipOperation,
+    schema::{
+        EntityOwnerSubject, EntityPermission, EntityRelationAndSubject, EntityTypePermission,
+        WebPermission,
+    },
+    zanzibar::{Consistency, Zookie},
+};
+use error_stack::{Report, ReportSink, Result, ResultExt, bail};
+use futures::{StreamExt as _, TryStreamExt, stream};
+use graph_types::{
+    account::{AccountId, CreatedById, EditionArchivedById, EditionCreatedById},
+    knowledge::{
+        entity::{
+            DraftId, Entity, EntityEditionId, EntityEditionProvenance, EntityId, EntityMetadata,
+            EntityProvenance, EntityRecordId, EntityTemporalMetadata, EntityUuid,
+            InferredEntityProvenance,
+        },
+        property::{
+            Property, PropertyMetadata, PropertyPath, PropertyPathError, PropertyWithMetadata,
+            PropertyWithMetadataObject, PropertyWithMetadataValue, visitor::EntityVisitor,
+        },
+    },
+    ontology::{DataTypeProvider, OntologyTypeProvider},
+    owned_by_id::OwnedById,
+};
+use hash_graph_store::{
+    entity::EntityQueryPath,
+    entity_type::EntityTypeQueryPath,
+    filter::Filter,
+    subgraph::{
+        Subgraph, SubgraphRecord,
+        edges::{EdgeDirection, GraphResolveDepths, KnowledgeGraphEdgeKind, SharedEdgeKind},
+        identifier::{EntityIdWithInterval, EntityTypeVertexId, EntityVertexId},
+        temporal_axes::{
+            PinnedAxis, PinnedTemporalAxisUnresolved, QueryTemporalAxes,
+            QueryTemporalAxesUnresolved, VariableAxis, VariableTemporalAxisUnresolved,
+        },
+    },
+};
+use hash_status::StatusCode;
+use serde_json::Value as JsonValue;
+use temporal_versioning::{
+    ClosedTemporalBound, DecisionTime, LeftClosedTemporalInterval, LimitedTemporalBound,
+    OpenTemporalBound, RightBoundedTemporalInterval, TemporalBound, TemporalInterval,
+    TemporalTagged, TimeAxis, Timestamp, TransactionTime,
+};
+use type_system::{
+    schema::{ClosedEntityType, ClosedMultiEntityType, EntityTypeUuid, OntologyTypeUuid},
+    url::VersionedUrl,
+};
+use uuid::Uuid;
+use validation::{EntityPreprocessor, Validate, ValidateEntityComponents};
+
+use crate::store::{
+    EntityStore, InsertionError, NullOrdering, Ordering as SortingOrdering, QueryError, StoreCache,
+    UpdateError,
+    crud::{QueryResult, Read, ReadPaginated, Sorting},
+    error::{DeletionError, EntityDoesNotExist},
+    knowledge::{
+        CountEntitiesParams, CreateEntityParams, DeleteEntitiesParams, DeleteEntitiesResponse,
+        DeletedEntity, EntityDeletionReason, EntityQueryCursor, EntityQuerySorting,
+        EntityValidationType, GetEntitiesParams, GetEntitiesResponse, GetEntitySubgraphParams,
+        GetEntitySubgraphResponse, PatchEntityParams, QueryConversion,
+        UpdateEntityEmbeddingsParams, ValidateEntityError, ValidateEntityParams,
+    },
+    memory::{
+        MemoryStore,
+        crud::MemorySorting,
+        filter::{Context, MemoryRecord, Value, text_search_rank},
+        ontology::entity_type_values,
+        table::{
+            EntityEditionRow, EntityEmbeddingRow, EntityIdRow, EntityTemporalMetadataRow,
+            MemoryTables,
+        },
+    },
+    postgres::{CursorField, ResponseCountMap, TraversalContext},
+    validation::StoreProvider,
+};
+
+/// The rows making up an entity record.
+#[derive(Clone, Copy)]
+pub(crate) struct EntityRow<'t> {
+    pub temporal_metadata: &'t EntityTemporalMetadataRow,
+    pub edition: &'t EntityEditionRow,
+    pub entity: &'t EntityIdRow,
+}
+
+/// Returns the interval of `temporal_metadata` on the variable `time_axis`.
+fn variable_interval(
+    temporal_metadata: &EntityTemporalMetadataRow,
+    time_axis: TimeAxis,
+) -> LeftClosedTemporalInterval<VariableAxis> {
+    match time_axis {
+        TimeAxis::DecisionTime => temporal_metadata.decision_time.cast(),
+        TimeAxis::TransactionTime => temporal_metadata.transaction_time.cast(),
+    }
+}
+
+/// Returns `true` if the pinned axis of `temporal_metadata` contains `pinned_timestamp`.
+fn contains_pinned_timestamp(
+    temporal_metadata: &EntityTemporalMetadataRow,
+    time_axis: TimeAxis,
+    pinned_timestamp: Timestamp<PinnedAxis>,
+) -> bool {
+    match time_axis {
+        TimeAxis::DecisionTime => temporal_metadata
+            .transaction_time
+            .contains_point(&pinned_timestamp.cast()),
+        TimeAxis::TransactionTime => temporal_metadata
+            .decision_time
+            .contains_point(&pinned_timestamp.cast()),
+    }
+}
+
+/// Intersects an interval of a row with the interval an edge is traversed in.
+fn traversal_interval(
+    interval: LeftClosedTemporalInterval<VariableAxis>,
+    traversal_interval: RightBoundedTemporalInterval<VariableAxis>,
+) -> Option<RightBoundedTemporalInterval<VariableAxis>> {
+    let interval: TemporalInterval<VariableAxis> = interval.convert();
+    interval
+        .intersect(traversal_interval.convert())
+        .map(TemporalInterval::convert)
+}
+
+/// An edge from an entity to its entity type which was followed while traversing the graph.
+struct SharedEdgeTraversal {
+    left_endpoint: EntityVertexId,
+    right_endpoint: VersionedUrl,
+    right_endpoint_ontology_id: EntityTypeUuid,
+    resolve_depths: GraphResolveDepths,
+    traversal_interval: RightBoundedTemporalInterval<VariableAxis>,
+}
+
+/// An edge between two entities which was followed while traversing the graph.
+struct KnowledgeEdgeTraversal {
+    left_endpoint: EntityVertexId,
+    right_endpoint: EntityVertexId,
+    right_endpoint_edition_id: EntityEditionId,
+    resolve_depths: GraphResolveDepths,
+    edge_interval: LeftClosedTemporalInterval<VariableAxis>,
+    traversal_interval: RightBoundedTemporalInterval<VariableAxis>,
+}
+
+type EntityTraversalQueue = [(
+    EntityVertexId,
+    GraphResolveDepths,
+    RightBoundedTemporalInterval<VariableAxis>,
+)];
+
+impl MemoryTables {
+    fn entity_row<'t>(
+        &'t self,
+        temporal_metadata: &'t EntityTemporalMetadataRow,
+    ) -> Option<EntityRow<'t>> {
+        Some(EntityRow {
+            temporal_metadata,
+            edition: self.entity_editions.get(&temporal_metadata.edition_id)?,
+            entity: self
+                .entity_ids
+                .get(&(temporal_metadata.owned_by_id, temporal_metadata.entity_uuid))?,
+        })
+    }
+
+    /// Returns the entity types of `edition` together with their inheritance depth.
+    ///
+    /// The Postgres store materializes this into the `entity_is_of_type` table.
+    fn entity_type_depths(&self, edition: &EntityEditionRow) -> HashMap<EntityTypeUuid, u32> {
+        let mut depths = HashMap::new();
+        for entity_type_url in &edition.entity_type_ids {
+            let entity_type_id = EntityTypeUuid::from_url(entity_type_url);
+            depths.insert(entity_type_id, 0);
+
+            if let Some(entity_type) = self.entity_types.get(&entity_type_id) {
+                for (parent_id, depth) in entity_type.resolve_data.inheritance_depths() {
+                    let depth = u32::from(depth.inner()) + 1;
+                    depths
+                        .entry(parent_id)
+                        .and_modify(|current: &mut u32| *current = (*current).min(depth))
+                        .or_insert(depth);
+                }
+            }
+        }
+        depths
+    }
+
+    /// Returns the entity types of `edition` up to `inheritance_depth`.
+    fn entity_type_ids_within(
+        &self,
+        edition: &EntityEditionRow,
+        inheritance_depth: Option<u32>,
+    ) -> Vec<OntologyTypeUuid> {
+        self.entity_type_depths(edition)
+            .into_iter()
+            .filter(|(_, depth)| inheritance_depth.is_none_or(|max_depth| *depth <= max_depth))
+            .map(|(entity_type_id, _)| OntologyTypeUuid::from(entity_type_id))
+            .collect()
+    }
+
+    /// Returns the index of the temporal metadata of `entity_id` which is valid at the given
+    /// timestamps.
+    fn entity_edition_index(
+        &self,
+        entity_id: EntityId,
+        transaction_time: Timestamp<TransactionTime>,
+        decision_time: Timestamp<DecisionTime>,
+    ) -> Option<usize> {
+        self.entity_temporal_metadata.iter().position(|row| {
+            row.owned_by_id == entity_id.owned_by_id
+                && row.entity_uuid == entity_id.entity_uuid
+                && row.draft_id == entity_id.draft_id
+                && row.transaction_time.contains_point(&transaction_time)
+                && row.decision_time.contains_point(&decision_time)
+        })
+    }
+
+    /// Returns the rows of the entity identified by `vertex_id` at the pinned timestamp.
+    fn source_entity_rows(
+        &self,
+        vertex_id: EntityVertexId,
+        pinned_timestamp: Timestamp<PinnedAxis>,
+        time_axis: TimeAxis,
+    ) -> impl Iterator<Item = &EntityTemporalMetadataRow> {
+        self.entity_temporal_metadata.iter().filter(move |row| {
+            row.owned_by_id == vertex_id.base_id.owned_by_id
+                && row.entity_uuid == vertex_id.base_id.entity_uuid
+                && contains_pinned_timestamp(row, time_axis, pinned_timestamp)
+                && Timestamp::from(*variable_interval(row, time_axis).start())
+                    == vertex_id.revision_id
+        })
+    }
+
+    fn read_shared_edges(
+        &self,
+        traversal_data: &EntityTraversalQueue,
+        pinned_timestamp: Timestamp<PinnedAxis>,
+        time_axis: TimeAxis,
+    ) -> Vec<(EntityTypeUuid, SharedEdgeTraversal)> {
+        let mut edges = Vec::new();
+        for (vertex_id, resolve_depths, interval) in traversal_data {
+            for source in self.source_entity_rows(*vertex_id, pinned_timestamp, time_axis) {
+                let Some(edition) = self.entity_editions.get(&source.edition_id) else {
+                    continue;
+                };
+                let Some(traversal_interval) =
+                    traversal_interval(variable_interval(source, time_axis), *interval)
+                else {
+                    continue;
+                };
+
+                for entity_type_url in &edition.entity_type_ids {
+                    let entity_type_id = EntityTypeUuid::from_url(entity_type_url);
+                    edges.push((entity_type_id, SharedEdgeTraversal {
+                        left_endpoint: EntityVertexId {
+                            base_id: EntityId {
+                                owned_by_id: vertex_id.base_id.owned_by_id,
+                                entity_uuid: vertex_id.base_id.entity_uuid,
+                                draft_id: None,
+                            },
+                            revision_id: vertex_id.revision_id,
+                        },
+                        right_endpoint: entity_type_url.clone(),
+                        right_endpoint_ontology_id: entity_type_id,
+                        resolve_depths: *resolve_depths,
+                        traversal_interval,
+                    }));
+                }
+            }
+        }
+        edges
+    }
+
+    /// Returns the entities `source` is connected to by `edge_kind` in `edge_direction`.
+    fn linked_entities(
+        &self,
+        owned_by_id: OwnedById,
+        entity_uuid: EntityUuid,
+        edge_kind: KnowledgeGraphEdgeKind,
+        edge_direction: EdgeDirection,
+    ) -> Vec<(OwnedById, EntityUuid)> {
+        let endpoint = |link_entity: &EntityIdRow| {
+            link_entity
+                .link_data
+                .as_ref()
+                .map(|link_data| match edge_kind {
+                    KnowledgeGraphEdgeKind::HasLeftEntity => link_data.left_entity_id,
+                    KnowledgeGraphEdgeKind::HasRightEntity => link_data.right_entity_id,
+                })
+        };
+
+        match edge_direction {
+            EdgeDirection::Outgoing => self
+                .entity_ids
+                .get(&(owned_by_id, entity_uuid))
+                .and_then(endpoint)
+                .map(|target| (target.owned_by_id, target.entity_uuid))
+                .into_iter()
+                .collect(),
+            EdgeDirection::Incoming => self
+                .entity_ids
+                .iter()
+                .filter(|(_, link_entity)| {
+                    endpoint(link_entity).is_some_and(|target| {
+                        target.owned_by_id == owned_by_id && target.entity_uuid == entity_uuid
+                    })
+                })
+                .map(|(link_entity_id, _)| *link_entity_id)
+                .collect(),
+        }
+    }
+
+    fn read_knowledge_edges(
+        &self,
+        traversal_data: &EntityTraversalQueue,
+        edge_kind: KnowledgeGraphEdgeKind,
+        edge_direction: EdgeDirection,
+        pinned_timestamp: Timestamp<PinnedAxis>,
+        time_axis: TimeAxis,
+    ) -> Vec<(EntityId, KnowledgeEdgeTraversal)> {
+        let mut edges = Vec::new();
+        for (vertex_id, resolve_depths, interval) in traversal_data {
+            for source in self.source_entity_rows(*vertex_id, pinned_timestamp, time_axis) {
+                let source_interval = variable_interval(source, time_axis);
+
+                for (owned_by_id, entity_uuid) in self.linked_entities(
+                    source.owned_by_id,
+                    source.entity_uuid,
+                    edge_kind,
+                    edge_direction,
+                ) {
+                    let right_endpoint_base_id = EntityId {
+                        owned_by_id,
+                        entity_uuid,
+                        draft_id: None,
+                    };
+
+                    for target in self.entity_temporal_metadata.iter().filter(|target| {
+                        target.owned_by_id == owned_by_id
+                            && target.entity_uuid == entity_uuid
+                            && contains_pinned_timestamp(target, time_axis, pinned_timestamp)
+                    }) {
+                        let target_interval = variable_interval(target, time_axis);
+                        let Some(edge_interval) = source_interval.intersect(target_interval) else {
+                            continue;
+                        };
+                        let Some(traversal_interval) = traversal_interval(edge_interval, *interval)
+                        else {
+                            continue;
+                        };
+
+                        edges.push((right_endpoint_base_id, KnowledgeEdgeTraversal {
+                            left_endpoint: EntityVertexId {
+                                base_id: EntityId {
+                                    owned_by_id: vertex_id.base_id.owned_by_id,
+                                    entity_uuid: vertex_id.base_id.entity_uuid,
+                                    draft_id: None,
+                                },
+                                revision_id: vertex_id.revision_id,
+                            },
+                            right_endpoint: EntityVertexId {
+                                base_id: right_endpoint_base_id,
+                                revision_id: Timestamp::from(*target_interval.start()),
+                            },
+                            right_endpoint_edition_id: target.edition_id,
+                            resolve_depths: *resolve_depths,
+                            edge_interval,
+                            traversal_interval,
+                        }));
+                    }
+                }
+            }
+        }
+        edges
+    }
+
+    fn insert_temporal_metadata(
+        &mut self,
+        entity_id: EntityId,
+        edition_id: EntityEditionId,
+        transaction_time: Timestamp<TransactionTime>,
+        decision_time: Timestamp<DecisionTime>,
+    ) -> EntityTemporalMetadata {
+        let temporal_versioning = EntityTemporalMetadata {
+            decision_time: LeftClosedTemporalInterval::new(
+                ClosedTemporalBound::Inclusive(decision_time),
+                OpenTemporalBound::Unbounded,
+            ),
+            transaction_time: LeftClosedTemporalInterval::new(
+                ClosedTemporalBound::Inclusive(transaction_time),
+                OpenTemporalBound::Unbounded,
+            ),
+        };
+        self.entity_temporal_metadata
+            .push(EntityTemporalMetadataRow {
+                owned_by_id: entity_id.owned_by_id,
+                entity_uuid: entity_id.entity_uuid,
+                draft_id: entity_id.draft_id,
+                edition_id,
+                decision_time: temporal_versioning.decision_time,
+                transaction_time: temporal_versioning.transaction_time,
+            });
+        temporal_versioning
+    }
+
+    /// Replaces the edition of the temporal metadata at `index` from `decision_time` onwards.
+    ///
+    /// The previous edition is kept for the previous transaction time and for the decision time
+    /// before `decision_time`.
+    fn update_temporal_metadata(
+        &mut self,
+        index: usize,
+        transaction_time: Timestamp<TransactionTime>,
+        decision_time: Timestamp<DecisionTime>,
+        edition_id: EntityEditionId,
+        undraft: bool,
+    ) -> EntityTemporalMetadata {
+        let locked_row = self.entity_temporal_metadata[index].clone();
+        let ClosedTemporalBound::Inclusive(locked_transaction_time) =
+            *locked_row.transaction_time.start();
+        let ClosedTemporalBound::Inclusive(locked_decision_time) =
+            *locked_row.decision_time.start();
+
+        let row = &mut self.entity_temporal_metadata[index];
+        row.transaction_time = LeftClosedTemporalInterval::new(
+            ClosedTemporalBound::Inclusive(transaction_time),
+            OpenTemporalBound::Unbounded,
+        );
+        row.decision_time = LeftClosedTemporalInterval::new(
+            ClosedTemporalBound::Inclusive(decision_time),
+            *locked_row.decision_time.end(),
+        );
+        row.edition_id = edition_id;
+        if undraft {
+            row.draft_id = None;
+        }
+        let temporal_versioning = EntityTemporalMetadata {
+            decision_time: row.decision_time,
+            transaction_time: row.transaction_time,
+        };
+
+        if locked_decision_time < decision_time {
+            self.entity_temporal_metadata
+                .push(EntityTemporalMetadataRow {
+                    transaction_time: LeftClosedTemporalInterval::new(
+                        ClosedTemporalBound::Inclusive(transaction_time),
+                        OpenTemporalBound::Unbounded,
+                    ),
+                    decision_time: LeftClosedTemporalInterval::new(
+                        ClosedTemporalBound::Inclusive(locked_decision_time),
+                        OpenTemporalBound::Exclusive(decision_time),
+                    ),
+                    ..locked_row
+                });
+        }
+        self.entity_temporal_metadata
+            .push(EntityTemporalMetadataRow {
+                transaction_time: LeftClosedTemporalInterval::new(
+                    ClosedTemporalBound::Inclusive(locked_transaction_time),
+                    OpenTemporalBound::Exclusive(transaction_time),
+                ),
+                ..locked_row
+            });
+
+        temporal_versioning
+    }
+
+    /// Ends the decision time of the temporal metadata at `index` at `decision_time`.
+    ///
+    /// This may remove the row at `index`, so indices of other rows are invalidated.
+    fn archive_entity(
+        &mut self,
+        actor_id: AccountId,
+        index: usize,
+        transaction_time: Timestamp<TransactionTime>,
+        decision_time: Timestamp<DecisionTime>,
+    ) {
+        let locked_row = self.entity_temporal_metadata[index].clone();
+        let ClosedTemporalBound::Inclusive(locked_transaction_time) =
+            *locked_row.transaction_time.start();
+        let ClosedTemporalBound::Inclusive(locked_decision_time) =
+            *locked_row.decision_time.start();
+
+        if locked_decision_time < decision_time {
+            let row = &mut self.entity_temporal_metadata[index];
+            row.transaction_time = LeftClosedTemporalInterval::new(
+                ClosedTemporalBound::Inclusive(transaction_time),
+                OpenTemporalBound::Unbounded,
+            );
+            row.decision_time = LeftClosedTemporalInterval::new(
+                ClosedTemporalBound::Inclusive(locked_decision_time),
+                OpenTemporalBound::Exclusive(decision_time),
+            );
+        } else {
+            // The row would have an empty decision time, which Postgres never returns.
+            self.entity_temporal_metadata.remove(index);
+        }
+
+        self.entity_temporal_metadata
+            .push(EntityTemporalMetadataRow {
+                transaction_time: LeftClosedTemporalInterval::new(
+                    ClosedTemporalBound::Inclusive(locked_transaction_time),
+                    OpenTemporalBound::Exclusive(transaction_time),
+                ),
+                ..locked_row
+            });
+
+        if let Some(edition) = self.entity_editions.get_mut(&locked_row.edition_id) {
+            Arc::make_mut(edition).provenance.archived_by_id =
+                Some(EditionArchivedById::new(actor_id));
+        }
+    }
+}
+
+/// Returns the rows of the entity identified by `owned_by_id` and `entity_uuid` which are visible
+/// in `context`.
+fn visible_entity_rows<'t>(
+    context: &Context<'t>,
+    owned_by_id: OwnedById,
+    entity_uuid: EntityUuid,
+) -> Vec<EntityRow<'t>> {
+    context
+        .tables
+        .entity_temporal_metadata
+        .iter()
+        .filter(|temporal_metadata| {
+            temporal_metadata.owned_by_id == owned_by_id
+                && temporal_metadata.entity_uuid == entity_uuid
+                && context.entity_visible(temporal_metadata)
+        })
+        .filter_map(|temporal_metadata| context.tables.entity_row(temporal_metadata))
+        .collect()
+}
+
+/// Returns the values of `path` for the entities of type `entity_type_id`.
+pub(crate) fn entities_of_type_values<'t>(
+    context: &Context<'t>,
+    entity_type_id: EntityTypeUuid,
+    inheritance_depth: Option<u32>,
+    path: &EntityQueryPath<'_>,
+) -> Vec<Value<'t>> {
+    Entity::rows(context)
+        .into_iter()
+        .filter(|row| {
+            context
+                .tables
+                .entity_type_depths(row.edition)
+                .get(&entity_type_id)
+                .is_some_and(|depth| inheritance_depth.is_none_or(|max_depth| *depth <= max_depth))
+        })
+        .flat_map(|row| Entity::values(context, row, path))
+        .collect()
+}
+
+fn entity_edge_values<'t>(
+    context: &Context<'t>,
+    row: EntityRow<'t>,
+    edge_kind: KnowledgeGraphEdgeKind,
+    path: &EntityQueryPath<'_>,
+    direction: EdgeDirection,
+) -> Vec<Value<'t>> {
+    if direction == EdgeDirection::Outgoing {
+        // Postgres reads the endpoints of a link directly without joining the linked entity.
+        if let Some(link_data) = &row.entity.link_data {
+            let endpoint = match edge_kind {
+                KnowledgeGraphEdgeKind::HasLeftEntity => link_data.left_entity_id,
+                KnowledgeGraphEdgeKind::HasRightEntity => link_data.right_entity_id,
+            };
+            match path {
+                EntityQueryPath::Uuid => {
+                    return vec![Value::Uuid(endpoint.entity_uuid.into_uuid())];
+                }
+                EntityQueryPath::OwnedById => {
+                    return vec![Value::Uuid(endpoint.owned_by_id.into_uuid())];
+                }
+                _ => {}
+            }
+        }
+    }
+
+    context
+        .tables
+        .linked_entities(
+            row.temporal_metadata.owned_by_id,
+            row.temporal_metadata.entity_uuid,
+            edge_kind,
+            direction,
+        )
+        .into_iter()
+        .flat_map(|(owned_by_id, entity_uuid)| {
+            visible_entity_rows(context, owned_by_id, entity_uuid)
+        })
+        .flat_map(|target| Entity::values(context, target, path))
+        .collect()
+}
+
+fn label_values<'t>(
+    context: &Context<'t>,
+    row: EntityRow<'t>,
+    inheritance_depth: Option<u32>,
+) -> Vec<Value<'t>> {
+    let Value::Json(properties) = Value::serialized(&row.edition.properties, None) else {
+        return Vec::new();
+    };
+
+    entity_type_values(
+        context,
+        context
+            .tables
+            .entity_type_ids_within(row.edition, inheritance_depth),
+        &EntityTypeQueryPath::LabelProperty,
+    )
+    .into_iter()
+    .filter_map(|label_property| match label_property {
+        Value::Text(label_property) => Some(
+            properties
+                .get(label_property.as_ref())
+                .map_or(Value::Null, |label| {
+                    Value::from_json(Cow::Owned(label.clone()))
+                }),
+        ),
+        _ => None,
+    })
+    .collect()
+}
+
+impl MemoryRecord for Entity {
+    type Row<'t> = EntityRow<'t>;
+
+    fn rows<'t>(context: &Context<'t>) -> Vec<Self::Row<'t>> {
+        context
+            .tables
+            .entity_temporal_metadata
+            .iter()
+            .filter(|temporal_metadata| context.entity_visible(temporal_metadata))
+            .filter_map(|temporal_metadata| context.tables.entity_row(temporal_metadata))
+            .collect()
+    }
+
+    fn values<'t>(
+        context: &Context<'t>,
+        row: Self::Row<'t>,
+        path: &EntityQueryPath<'_>,
+    ) -> Vec<Value<'t>> {
+        let temporal_metadata = row.temporal_metadata;
+        let link_data = row.entity.link_data.as_ref();
+        let value = match path {
+            EntityQueryPath::Uuid => Value::Uuid(temporal_metadata.entity_uuid.into_uuid()),
+            EntityQueryPath::OwnedById => Value::Uuid(temporal_metadata.owned_by_id.into_uuid()),
+            EntityQueryPath::DraftId => temporal_metadata
+                .draft_id
+                .map_or(Value::Null, |draft_id| Value::Uuid(draft_id.into_uuid())),
+            EntityQueryPath::EditionId => Value::Uuid(temporal_metadata.edition_id.into_uuid()),
+            EntityQueryPath::DecisionTime => Value::interval(temporal_metadata.decision_time),
+            EntityQueryPath::TransactionTime => Value::interval(temporal_metadata.transaction_time),
+            EntityQueryPath::TypeBaseUrls => Value::serialized(
+                &row.edition
+                    .entity_type_ids
+                    .iter()
+                    .map(|entity_type_id| &entity_type_id.base_url)
+                    .collect::<Vec<_>>(),
+                None,
+            ),
+            EntityQueryPath::TypeVersions => Value::serialized(
+                &row.edition
+                    .entity_type_ids
+                    .iter()
+                    .map(|entity_type_id| entity_type_id.version)
+                    .collect::<Vec<_>>(),
+                None,
+            ),
+            EntityQueryPath::EntityConfidence => Value::serialized(&row.edition.confidence, None),
+            EntityQueryPath::LeftEntityConfidence => link_data.map_or(Value::Null, |link_data| {
+                Value::serialized(&link_data.left_entity_confidence, None)
+            }),
+            EntityQueryPath::LeftEntityProvenance => link_data.map_or(Value::Null, |link_data| {
+                Value::serialized(&link_data.left_entity_provenance, None)
+            }),
+            EntityQueryPath::RightEntityConfidence => link_data.map_or(Value::Null, |link_data| {
+                Value::serialized(&link_data.right_entity_confidence, None)
+            }),
+            EntityQueryPath::RightEntityProvenance => link_data.map_or(Value::Null, |link_data| {
+                Value::serialized(&link_data.right_entity_provenance, None)
+            }),
+            EntityQueryPath::Archived => Value::Boolean(row.edition.archived),
+            EntityQueryPath::EntityTypeEdge {
+                edge_kind: SharedEdgeKind::IsOfType,
+                path,
+                inheritance_depth,
+            } => {
+                return entity_type_values(
+                    context,
+                    context
+                        .tables
+                        .entity_type_ids_within(row.edition, *inheritance_depth),
+                    path,
+                );
+            }
+            EntityQueryPath::EntityEdge {
+                edge_kind,
+                path,
+                direction,
+            } => return entity_edge_values(context, row, *edge_kind, path, *direction),
+            EntityQueryPath::Properties(path) => {
+                Value::serialized(&row.edition.properties, path.as_ref())
+            }
+            EntityQueryPath::Label { inheritance_depth } => {
+                return label_values(context, row, *inheritance_depth);
+            }
+            EntityQueryPath::Provenance(path) => {
+                Value::serialized(&row.entity.provenance, path.as_ref())
+            }
+            EntityQueryPath::EditionProvenance(path) => {
+                Value::serialized(&row.edition.provenance, path.as_ref())
+            }
+            EntityQueryPath::PropertyMetadata(path) => {
+                Value::serialized(&row.edition.property_metadata, path.as_ref())
+            }
+            EntityQueryPath::TextSearchRank(query) => {
+                text_search_rank(&Value::serialized(&row.edition.properties, None), query)
+                    .map_or(Value::Null, Value::Number)
+            }
+            EntityQueryPath::Embedding => {
+                return context
+                    .tables
+                    .entity_embeddings
+                    .iter()
+                    .filter(|embedding| {
+                        embedding.owned_by_id == temporal_metadata.owned_by_id
+                            && embedding.entity_uuid == temporal_metadata.entity_uuid
+                    })
+                    .map(|embedding| Value::Vector(&embedding.embedding))
+                    .collect();
+            }
+        };
+
+        vec![value]
+    }
+
+    fn materialize<'t>(_: &Context<'t>, row: Self::Row<'t>) -> Self {
+        let temporal_metadata = row.temporal_metadata;
+        Self {
+            properties: row.edition.properties.clone(),
+            link_data: row.entity.link_data.clone(),
+            metadata: EntityMetadata {
+                record_id: EntityRecordId {
+                    entity_id: EntityId {
+                        owned_by_id: temporal_metadata.owned_by_id,
+                        entity_uuid: temporal_metadata.entity_uuid,
+                        draft_id: temporal_metadata.draft_id,
+                    },
+                    edition_id: temporal_metadata.edition_id,
+                },
+                temporal_versioning: EntityTemporalMetadata {
+                    decision_time: temporal_metadata.decision_time,
+                    transaction_time: temporal_metadata.transaction_time,
+                },
+                entity_type_ids: row.edition.entity_type_ids.iter().cloned().collect(),
+                archived: row.edition.archived,
+                provenance: EntityProvenance {
+                    inferred: row.entity.provenance.clone(),
+                    edition: row.edition.provenance.clone(),
+                },
+                confidence: row.edition.confidence,
+                properties: row.edition.property_metadata.clone(),
+            },
+        }
+    }
+
+    fn clone_record(&self) -> Self {
+        self.clone()
+    }
+}
+
+fn cursor_field(value: Value<'_>) -> CursorField<'static> {
+    match value {
+        Value::Null | Value::Vector(_) => CursorField::Json(JsonValue::Null),
+        Value::Boolean(bool) => CursorField::Bool(bool),
+        Value::Number(number) => CursorField::F64(number),
+        Value::Text(text) => CursorField::String(Cow::Owned(text.into_owned())),
+        Value::Uuid(uuid) => CursorField::Uuid(uuid),
+        Value::Timestamp(timestamp) => CursorField::Timestamp(timestamp),
+        Value::Interval(interval) => CursorField::TimeInterval(interval),
+        Value::Version { version, .. } => CursorField::F64(f64::from(version.inner())),
+        Value::Json(json) => CursorField::Json(json.into_owned()),
+    }
+}
+
+fn cursor_value<'c>(field: &'c CursorField<'_>) -> Value<'c> {
+    match field {
+        CursorField::Bool(bool) => Value::Boolean(*bool),
+        CursorField::I32(number) => Value::Number(f64::from(*number)),
+        CursorField::F64(number) => Value::Number(*number),
+        CursorField::String(text) => Value::Text(Cow::Borrowed(text)),
+        CursorField::Timestamp(timestamp) => Value::Timestamp(*timestamp),
+        CursorField::TimeInterval(interval) => Value::Interval(*interval),
+        CursorField::Json(json) => Value::from_json(Cow::Borrowed(json)),
+        CursorField::Uuid(uuid) => Value::Uuid(*uuid),
+    }
+}
+
+impl MemorySorting<Entity> for EntityQuerySorting<'_> {
+    fn cursor_of<'t>(&self, context: &Context<'t>, row: EntityRow<'t>) -> Self::Cursor {
+        EntityQueryCursor {
+            values: self
+                .paths
+                .iter()
+                .map(|sorting_record| {
+                    Entity::values(context, row, &sorting_record.path)
+                        .into_iter()
+                        .next()
+                        .map_or(CursorField::Json(JsonValue::Null), cursor_field)
+                })
+                .collect(),
+        }
+    }
+
+    fn compare_cursors(&self, lhs: &Self::Cursor, rhs: &Self::Cursor) -> Ordering {
+        self.paths
+            .iter()
+            .zip(lhs.values.iter().zip(&rhs.values))
+            .map(|(sorting_record, (lhs, rhs))| {
+                // Postgres sorts `NULL` values as if they were larger than any other value.
+                let nulls = sorting_record
+                    .nulls
+                    .unwrap_or(match sorting_record.ordering {
+                        SortingOrdering::Ascending => NullOrdering::Last,
+                        SortingOrdering::Descending => NullOrdering::First,
+                    });
+                let (lhs, rhs) = (cursor_value(lhs), cursor_value(rhs));
+
+                match (lhs, rhs) {
+                    (Value::Null, Value::Null) => Ordering::Equal,
+                    (Value::Null, _) => match nulls {
+                        NullOrdering::First => Ordering::Less,
+                        NullOrdering::Last => Ordering::Greater,
+                    },
+                    (_, Value::Null) => match nulls {
+                        NullOrdering::First => Ordering::Greater,
+                        NullOrdering::Last => Ordering::Less,
+                    },
+                    (lhs, rhs) => match sorting_record.ordering {
+                        SortingOrdering::Ascending => lhs.total_cmp(&rhs),
+                        SortingOrdering::Descending => rhs.total_cmp(&lhs),
+                    },
+                }
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+async fn convert_entity_properties<P: DataTypeProvider + Sync>(
+    provider: &P,
+    entity: &mut PropertyWithMetadata,
+    path: &PropertyPath<'_>,
+    target_data_type_id: &VersionedUrl,
+) {
+    let Ok(PropertyWithMetadata::Value(PropertyWithMetadataValue { value, metadata })) =
+        entity.get_mut(path.as_ref())
+    else {
+        // If the property does not exist or is not a value, we can ignore it.
+        return;
+    };
+
+    let Some(source_data_type_id) = &mut metadata.data_type_id else {
+        // If the property does not have a data type, we can ignore it.
+        return;
+    };
+
+    let Ok(conversions) = provider
+        .find_conversion(source_data_type_id, target_data_type_id)
+        .await
+    else {
+        // If no conversion is found, we can ignore the property.
+        return;
+    };
+
+    let Some(mut value_number) = value.as_f64() else {
+        // If the value is not a number, we can ignore the property.
+        return;
+    };
+
+    for conversion in conversions.borrow() {
+        value_number = conversion.evaluate(value_number);
+    }
+    drop(conversions);
+
+    *value = JsonValue::from(value_number);
+
+    metadata.data_type_id = Some(target_data_type_id.clone());
+}
+
+async fn convert_entity<P: DataTypeProvider + Sync>(
+    provider: &P,
+    entity: &mut Entity,
+    conversions: &[QueryConversion<'_>],
+) -> Result<(), PropertyPathError> {
+    let mut property = PropertyWithMetadata::Object(PropertyWithMetadataObject::from_parts(
+        mem::take(&mut entity.properties),
+        Some(mem::take(&mut entity.metadata.properties)),
+    )?);
+    for conversion in conversions {
+        convert_entity_properties(
+            provider,
+            &mut property,
+            &conversion.path,
+            &conversion.data_type_id,
+        )
+        .await;
+    }
+    let PropertyWithMetadata::Object(property) = property else {
+        unreachable!("The property was just converted to an object");
+    };
+    let (properties, metadata) = property.into_parts();
+    entity.properties = properties;
+    entity.metadata.properties = metadata;
+    Ok(())
+}
+
+#[derive(Debug)]
+#[expect(clippy::struct_excessive_bools, reason = "Parameter struct")]
+struct GetEntitiesImplParams<'a> {
+    filter: Filter<'a, Entity>,
+    sorting: EntityQuerySorting<'static>,
+    limit: Option<usize>,
+    include_drafts: bool,
+    include_count: bool,
+    include_web_ids: bool,
+    include_created_by_ids: bool,
+    include_edition_created_by_ids: bool,
+    include_type_ids: bool,
+}
+
+const ENTITY_EDGES: [(KnowledgeGraphEdgeKind, EdgeDirection); 4] = [
+    (
+        KnowledgeGraphEdgeKind::HasLeftEntity,
+        EdgeDirection::Incoming,
+    ),
+    (
+        KnowledgeGraphEdgeKind::HasRightEntity,
+        EdgeDirection::Incoming,
+    ),
+    (
+        KnowledgeGraphEdgeKind::HasLeftEntity,
+        EdgeDirection::Outgoing,
+    ),
+    (
+        KnowledgeGraphEdgeKind::HasRightEntity,
+        EdgeDirection::Outgoing,
+    ),
+];
+
+impl<A> MemoryStore<A>
+where
+    A: AuthorizationApi,
+{
+    /// Internal method to read an [`Entity`] into a [`TraversalContext`].
+    ///
+    /// This is used to recursively resolve a type, so the result can be reused.
+    #[tracing::instrument(level = "info", skip(self, traversal_context, subgraph, zookie))]
+    pub(crate) async fn traverse_entities(
+        &self,
+        mut entity_queue: Vec<(
+            EntityVertexId,
+            GraphResolveDepths,
+            RightBoundedTemporalInterval<VariableAxis>,
+        )>,
+        traversal_context: &mut TraversalContext,
+        actor_id: AccountId,
+        zookie: &Zookie<'static>,
+        subgraph: &mut Subgraph,
+    ) -> Result<(), QueryError> {
+        let tables = self.snapshot();
+        let time_axis = subgraph.temporal_axes.resolved.variable_time_axis();
+        let pinned_timestamp = subgraph.temporal_axes.resolved.pinned_timestamp();
+
+        let mut entity_type_queue = Vec::new();
+
+        while !entity_queue.is_empty() {
+            let mut shared_edges_to_traverse = Vec::new();
+            let mut knowledge_edges_to_traverse =
+                HashMap::<(KnowledgeGraphEdgeKind, EdgeDirection), Vec<_>>::new();
+
+            #[expect(clippy::iter_with_drain, reason = "false positive, vector is reused")]
+            for (entity_vertex_id, graph_resolve_depths, traversal_interval) in
+                entity_queue.drain(..)
+            {
+                if let Some(new_graph_resolve_depths) = graph_resolve_depths
+                    .decrement_depth_for_edge(SharedEdgeKind::IsOfType, EdgeDirection::Outgoing)
+                {
+                    shared_edges_to_traverse.push((
+                        entity_vertex_id,
+                        new_graph_resolve_depths,
+                        traversal_interval,
+                    ));
+                }
+
+                for (edge_kind, edge_direction) in ENTITY_EDGES {
+                    if let Some(new_graph_resolve_depths) =
+                        graph_resolve_depths.decrement_depth_for_edge(edge_kind, edge_direction)
+                    {
+                        knowledge_edges_to_traverse
+                            .entry((edge_kind, edge_direction))
+                            .or_default()
+                            .push((
+                                entity_vertex_id,
+                                new_graph_resolve_depths,
+                                traversal_interval,
+                            ));
+                    }
+                }
+            }
+
+            if !shared_edges_to_traverse.is_empty() {
+                entity_type_queue.extend(
+                    Self::filter_entity_types_by_permission(
+                        tables.read_shared_edges(
+                            &shared_edges_to_traverse,
+                            pinned_timestamp,
+                            time_axis,
+                        ),
+                        actor_id,
+                        &self.authorization_api,
+                        zookie,
+                    )
+                    .await?
+                    .flat_map(|edge| {
+                        subgraph.insert_edge(
+                            &edge.left_endpoint,
+                            SharedEdgeKind::IsOfType,
+                            EdgeDirection::Outgoing,
+                            EntityTypeVertexId::from(edge.right_endpoint),
+                        );
+
+                        traversal_context.add_entity_type_id(
+                            edge.right_endpoint_ontology_id,
+                            edge.resolve_depths,
+                            edge.traversal_interval,
+                        )
+                    }),
+                );
+            }
+
+            for (edge_kind, edge_direction) in ENTITY_EDGES {
+                if let Some(traversal_data) =
+                    knowledge_edges_to_traverse.get(&(edge_kind, edge_direction))
+                {
+                    let (entity_ids, knowledge_edges): (Vec<_>, Vec<_>) = tables
+                        .read_knowledge_edges(
+                            traversal_data,
+                            edge_kind,
+                            edge_direction,
+                            pinned_timestamp,
+                            time_axis,
+                        )
+                        .into_iter()
+                        .unzip();
+
+                    if knowledge_edges.is_empty() {
+                        continue;
+                    }
+
+                    let permissions = self
+                        .authorization_api
+                        .check_entities_permission(
+                            actor_id,
+                            EntityPermission::View,
+                            entity_ids.iter().copied(),
+                            Consistency::AtExactSnapshot(zookie),
+                        )
+                        .await
+                        .change_context(QueryError)?
+                        .0;
+
+                    entity_queue.extend(
+                        knowledge_edges
+                            .into_iter()
+                            .zip(entity_ids)
+                            .filter_map(|(edge, entity_id)| {
+                                permissions
+                                    .get(&entity_id.entity_uuid)
+                                    .copied()
+                                    .unwrap_or(true)
+                                    .then_some(edge)
+                            })
+                            .flat_map(|edge| {
+                                subgraph.insert_edge(
+                                    &edge.left_endpoint,
+                                    edge_kind,
+                                    edge_direction,
+                                    EntityIdWithInterval {
+                                        entity_id: edge.right_endpoint.base_id,
+                                        interval: edge.edge_interval,
+                                    },
+                                );
+
+                                traversal_context
+                                    .add_entity_id(
+                                        edge.right_endpoint_edition_id,
+                                        edge.resolve_depths,
+                                        edge.traversal_interval,
+                                    )
+                                    .map(move |(_, resolve_depths, interval)| {
+                                        (edge.right_endpoint, resolve_depths, interval)
+                                    })
+                            }),
+                    );
+                }
+            }
+        }
+
+        self.traverse_entity_types(
+            entity_type_queue,
+            traversal_context,
+            actor_id,
+            zookie,
+            subgraph,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn delete_entities(&mut self) -> Result<(), DeletionError> {
+        tracing::debug!("Deleting all entities");
+        let transaction = self.transaction().await.change_context(DeletionError)?;
+        transaction.write(|tables| {
+            tables.entity_ids.clear();
+            tables.entity_editions.clear();
+            tables.entity_temporal_metadata.clear();
+            tables.entity_embeddings.clear();
+        });
+        transaction.commit().await.change_context(DeletionError)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn get_entities_impl(
+        &self,
+        actor_id: AccountId,
+        mut params: GetEntitiesImplParams<'_>,
+        temporal_axes: &QueryTemporalAxes,
+    ) -> Result<(GetEntitiesResponse<'static>, Zookie<'static>), QueryError> {
+        let mut root_entities = Vec::new();
+
+        let (permissions, count, web_ids, created_by_ids, edition_created_by_ids, type_ids) =
+            if params.include_count
+                || params.include_web_ids
+                || params.include_created_by_ids
+                || params.include_edition_created_by_ids
+                || params.include_type_ids
+            {
+                let mut web_ids = params.include_web_ids.then(ResponseCountMap::default);
+                let mut created_by_ids = params
+                    .include_created_by_ids
+                    .then(ResponseCountMap::default);
+                let mut edition_created_by_ids = params
+                    .include_edition_created_by_ids
+                    .then(ResponseCountMap::default);
+                let mut include_type_ids = params.include_type_ids.then(ResponseCountMap::default);
+
+                let entity_ids = Read::<Entity>::read(
+                    self,
+                    &params.filter,
+                    Some(temporal_axes),
+                    params.include_drafts,
+                )
+                .await?
+                .map_ok(|entity| {
+                    if let Some(web_ids) = &mut web_ids {
+                        web_ids.increment(&entity.metadata.record_id.entity_id.owned_by_id);
+                    }
+                    if let Some(created_by_ids) = &mut created_by_ids {
+                        created_by_ids
+                            .increment(&entity.metadata.provenance.inferred.created_by_id);
+                    }
+                    if let Some(edition_created_by_ids) = &mut edition_created_by_ids {
+                        edition_created_by_ids
+                            .increment(&entity.metadata.provenance.edition.created_by_id);
+                    }
+                    if let Some(include_type_ids) = &mut include_type_ids {
+                        for entity_type_id in &entity.metadata.entity_type_ids {
+                            include_type_ids.increment(entity_type_id);
+                        }
+                    }
+                    entity.metadata.record_id.entity_id
+                })
+                .try_collect::<Vec<_>>()
+                .await?;
+
+                let (permissions, zookie) = self
+                    .authorization_api
+                    .check_entities_permission(
+                        actor_id,
+                        EntityPermission::View,
+                        entity_ids.iter().copied(),
+                        Consistency::FullyConsistent,
+                    )
+                    .await
+                    .change_context(QueryError)?;
+
+                let permitted_ids = permissions
+                    .into_iter()
+                    .filter_map(|(entity_id, has_permission)| has_permission.then_some(entity_id))
+                    .collect::<HashSet<_>>();
+
+                let count = entity_ids
+                    .into_iter()
+                    .filter(|id| permitted_ids.contains(&id.entity_uuid))
+                    .count();
+                (
+                    Some((permitted_ids, zookie)),
+                    Some(count),
+                    web_ids.map(HashMap::from),
+                    created_by_ids.map(HashMap::from),
+                    edition_created_by_ids.map(HashMap::from),
+                    include_type_ids.map(HashMap::from),
+                )
+            } else {
+                (None, None, None, None, None, None)
+            };
+
+        let (latest_zookie, last) = loop {
+            // We query one more than requested to determine if there are more entities to return.
+            let (rows, artifacts) =
+                ReadPaginated::<Entity, EntityQuerySorting>::read_paginated_vec(
+                    self,
+                    &params.filter,
+                    Some(temporal_axes),
+                    &params.sorting,
+                    params.limit,
+                    params.include_drafts,
+                )
+                .await?;
+            let entities = rows
+                .into_iter()
+                .map(|row| (row.decode_record(&artifacts), row))
+                .collect::<Vec<_>>();
+            if let Some(cursor) = entities
+                .last()
+                .map(|(_, row)| row.decode_cursor(&artifacts))
+            {
+                params.sorting.set_cursor(cursor);
+            }
+
+            let num_returned_entities = entities.len();
+
+            let (permitted_ids, zookie) = if let Some((permitted_ids, zookie)) = &permissions {
+                (
+                    Cow::<HashSet<EntityUuid>>::Borrowed(permitted_ids),
+                    Cow::<Zookie>::Borrowed(zookie),
+                )
+            } else {
+                let filtered_ids = entities
+                    .iter()
+                    .map(|(entity, _)| entity.metadata.record_id.entity_id)
+                    .collect::<HashSet<_>>();
+
+                let (permissions, zookie) = self
+                    .authorization_api
+                    .check_entities_permission(
+                        actor_id,
+                        EntityPermission::View,
+                        filtered_ids,
+                        Consistency::FullyConsistent,
+                    )
+                    .await
+                    .change_context(QueryError)?;
+
+                (
+                    Cow::Owned(
+                        permissions
+                            .into_iter()
+                            .filter_map(|(entity_id, has_permission)| {
+                                has_permission.then_some(entity_id)
+                            })
+                            .collect::<HashSet<_>>(),
+                    ),
+                    Cow::Owned(zookie),
+                )
+            };
+
+            root_entities.extend(
+                entities
+                    .into_iter()
+                    .filter(|(entity, _)| {
+                        permitted_ids.contains(&entity.metadata.record_id.entity_id.entity_uuid)
+                    })
+                    .take(params.limit.unwrap_or(usize::MAX) - root_entities.len()),
+            );
+
+            if let Some(limit) = params.limit {
+                if num_returned_entities < limit {
+                    // When the returned entities are less than the requested amount we know
+                    // that there are no more entities to return.
+                    break (zookie, None);
+                }
+                if root_entities.len() == limit {
+                    // The requested limit is reached, so we can stop here.
+                    break (
+                        zookie,
+                        root_entities
+                            .last()
+                            .map(|(_, row)| row.decode_cursor(&artifacts)),
+                    );
+                }
+            } else {
+                // Without a limit all entities are returned.
+                break (zookie, None);
+            }
+        };
+
+        Ok((
+            GetEntitiesResponse {
+                entities: root_entities
+                    .into_iter()
+                    .map(|(entity, _)| entity)
+                    .collect(),
+                cursor: last,
+                count,
+                web_ids,
+                created_by_ids,
+                edition_created_by_ids,
+                type_ids,
+            },
+            latest_zookie.into_owned(),
+        ))
+    }
+}
+
+impl<A> EntityStore for MemoryStore<A>
+where
+    A: AuthorizationApi,
+{
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn create_entities<R>(
+        &mut self,
+        actor_id: AccountId,
+        params: Vec<CreateEntityParams<R>>,
+    ) -> Result<Vec<Entity>, InsertionError>
+    where
+        R: IntoIterator<Item = EntityRelationAndSubject> + Send,
+    {
+        let mut relationships = Vec::with_capacity(params.len());
+        let mut entity_type_ids = HashMap::new();
+        let mut checked_web_ids = HashSet::new();
+        let mut preprocessed_params = Vec::with_capacity(params.len());
+
+        let validator_provider = StoreProvider {
+            store: &*self,
+            cache: StoreCache::default(),
+            authorization: Some((actor_id, Consistency::FullyConsistent)),
+        };
+
+        for mut params in params {
+            let entity_type = ClosedMultiEntityType::from_multi_type_closed_schema(
+                stream::iter(&params.entity_type_ids)
+                    .then(|entity_type_url| async {
+                        OntologyTypeProvider::<ClosedEntityType>::provide_type(
+                            &validator_provider,
+                            entity_type_url,
+                        )
+                        .await
+                        .map(|entity_type| (*entity_type).clone())
+                    })
+                    .try_collect::<Vec<ClosedEntityType>>()
+                    .await
+                    .change_context(InsertionError)?,
+            )
+            .change_context(InsertionError)?;
+
+            let validation_components = if params.draft {
+                ValidateEntityComponents {
+                    num_items: false,
+                    required_properties: false,
+                    ..ValidateEntityComponents::full()
+                }
+            } else {
+                ValidateEntityComponents::full()
+            };
+            EntityPreprocessor {
+                components: validation_components,
+            }
+            .visit_object(&entity_type, &mut params.properties, &validator_provider)
+            .await
+            .attach(StatusCode::InvalidArgument)
+            .change_context(InsertionError)?;
+
+            let entity_id = EntityId {
+                owned_by_id: params.owned_by_id,
+                entity_uuid: params
+                    .entity_uuid
+                    .unwrap_or_else(|| EntityUuid::new(Uuid::new_v4())),
+                draft_id: params.draft.then(|| DraftId::new(Uuid::new_v4())),
+            };
+
+            if entity_id.entity_uuid.as_uuid() != entity_id.owned_by_id.as_uuid() {
+                checked_web_ids.insert(entity_id.owned_by_id);
+            }
+
+            for entity_type in &entity_type.all_of {
+                entity_type_ids.insert(
+                    EntityTypeUuid::from_url(&entity_type.id),
+                    entity_type.id.clone(),
+                );
+            }
+
+            let current_num_relationships = relationships.len();
+            relationships.extend(
+                params
+                    .relationships
+                    .into_iter()
+                    .chain(once(EntityRelationAndSubject::Owner {
+                        subject: EntityOwnerSubject::Web {
+                            id: params.owned_by_id,
+                        },
+                        level: 0,
+                    }))
+                    .map(|relation_and_subject| (entity_id, relation_and_subject)),
+            );
+            if relationships.len() == current_num_relationships {
+                return Err(Report::new(InsertionError)
+                    .attach_printable("At least one relationship must be provided"));
+            }
+
+            preprocessed_params.push((
+                entity_id,
+                entity_type,
+                validation_components,
+                params.properties,
+                params.decision_time,
+                params.entity_type_ids,
+                params.confidence,
+                params.link_data,
+                params.provenance,
+            ));
+        }
+        // We move out the cache, so we can re-use `&mut self` later.
+        let store_cache = validator_provider.cache;
+
+        let (instantiate_permissions, zookie) = self
+            .authorization_api
+            .check_entity_types_permission(
+                actor_id,
+                EntityTypePermission::Instantiate,
+                entity_type_ids.keys().copied(),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(InsertionError)?;
+        let forbidden_instantiations = instantiate_permissions
+            .iter()
+            .filter_map(|(entity_type_id, permission)| {
+                if *permission {
+                    None
+                } else {
+                    entity_type_ids.get(entity_type_id)
+                }
+            })
+            .collect::<Vec<_>>();
+        if !forbidden_instantiations.is_empty() {
+            return Err(Report::new(InsertionError)
+                .attach(StatusCode::PermissionDenied)
+                .attach_printable(
+                    "The actor does not have permission to instantiate one or more entity types",
+                )
+                .attach_printable(
+                    forbidden_instantiations
+                        .into_iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ));
+        }
+
+        if !checked_web_ids.is_empty() {
+            let (create_entity_permissions, _zookie) = self
+                .authorization_api
+                .check_webs_permission(
+                    actor_id,
+                    WebPermission::CreateEntity,
+                    checked_web_ids,
+                    Consistency::AtLeastAsFresh(&zookie),
+                )
+                .await
+                .change_context(InsertionError)?;
+            let forbidden_webs = create_entity_permissions
+                .iter()
+                .filter_map(
+                    |(web_id, permission)| {
+                        if *permission { None } else { Some(web_id) }
+                    },
+                )
+                .collect::<Vec<_>>();
+            if !forbidden_webs.is_empty() {
+                return Err(Report::new(InsertionError)
+                    .attach(StatusCode::PermissionDenied)
+                    .attach_printable(
+                        "The actor does not have permission to create entities for one or more \
+                         web ids",
+                    )
+                    .attach_printable(
+                        forbidden_webs
+                            .into_iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ));
+            }
+        }
+
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+        let transaction_time = transaction.write(MemoryTables::transaction_time);
+
+        let mut entities = Vec::with_capacity(preprocessed_params.len());
+        let mut validation_params = Vec::with_capacity(preprocessed_params.len());
+        for (
+            entity_id,
+            entity_type,
+            validation_components,
+            properties,
+            decision_time,
+            entity_type_ids,
+            confidence,
+            link_data,
+            provenance,
+        ) in preprocessed_params
+        {
+            let (properties, property_metadata) = properties.into_parts();
+
+            let decision_time =
+                decision_time.map_or_else(|| transaction_time.cast(), Timestamp::remove_nanosecond);
+
+            let entity_provenance = EntityProvenance {
+                inferred: InferredEntityProvenance {
+                    created_by_id: CreatedById::new(actor_id),
+                    created_at_transaction_time: transaction_time,
+                    created_at_decision_time: decision_time,
+                    first_non_draft_created_at_transaction_time: entity_id
+                        .draft_id
+                        .is_none()
+                        .then_some(transaction_time),
+                    first_non_draft_created_at_decision_time: entity_id
+                        .draft_id
+                        .is_none()
+                        .then_some(decision_time),
+                },
+                edition: EntityEditionProvenance {
+                    created_by_id: EditionCreatedById::new(actor_id),
+                    archived_by_id: None,
+                    provided: provenance,
+                },
+            };
+            let edition_id = EntityEditionId::new(Uuid::new_v4());
+
+            let temporal_versioning = transaction.write(|tables| {
+                if !tables.webs.contains(&entity_id.owned_by_id) {
+                    return Err(Report::new(InsertionError)
+                        .attach_printable("Web does not exist")
+                        .attach_printable(entity_id.owned_by_id));
+                }
+                if tables
+                    .entity_ids
+                    .contains_key(&(entity_id.owned_by_id, entity_id.entity_uuid))
+                {
+                    return Err(Report::new(InsertionError)
+                        .attach_printable("Entity does already exist")
+                        .attach_printable(entity_id));
+                }
+
+                tables.entity_ids.insert(
+                    (entity_id.owned_by_id, entity_id.entity_uuid),
+                    EntityIdRow {
+                        provenance: entity_provenance.inferred.clone(),
+                        link_data: link_data.clone(),
+                    },
+                );
+                tables.entity_editions.insert(
+                    edition_id,
+                    Arc::new(EntityEditionRow {
+                        properties: properties.clone(),
+                        property_metadata: property_metadata.clone(),
+                        archived: false,
+                        confidence,
+                        provenance: entity_provenance.edition.clone(),
+                        entity_type_ids: entity_type
+                            .all_of
+                            .iter()
+                            .map(|entity_type| entity_type.id.clone())
+                            .collect(),
+                    }),
+                );
+                Ok(tables.insert_temporal_metadata(
+                    entity_id,
+                    edition_id,
+                    transaction_time,
+                    decision_time,
+                ))
+            })?;
+
+            entities.push(Entity {
+                properties,
+                link_data,
+                metadata: EntityMetadata {
+                    record_id: EntityRecordId {
+                        entity_id,
+                        edition_id,
+                    },
+                    temporal_versioning,
+                    entity_type_ids,
+                    archived: false,
+                    provenance: entity_provenance,
+                    confidence,
+                    properties: property_metadata,
+                },
+            });
+            validation_params.push((entity_type, validation_components));
+        }
+
+        transaction.write(|tables| {
+            // Postgres enforces this with foreign keys on the link tables.
+            for link_data in entities
+                .iter()
+                .filter_map(|entity| entity.link_data.as_ref())
+            {
+                for endpoint in [link_data.left_entity_id, link_data.right_entity_id] {
+                    if !tables
+                        .entity_ids
+                        .contains_key(&(endpoint.owned_by_id, endpoint.entity_uuid))
+                    {
+                        return Err(Report::new(InsertionError)
+                            .attach_printable("Linked entity does not exist")
+                            .attach_printable(endpoint));
+                    }
+                }
+            }
+            Ok(())
+        })?;
+
+        transaction
+            .authorization_api
+            .modify_entity_relations(relationships.iter().copied().map(
+                |(entity_id, relation_and_subject)| {
+                    (
+                        ModifyRelationshipOperation::Create,
+                        entity_id,
+                        relation_and_subject,
+                    )
+                },
+            ))
+            .await
+            .change_context(InsertionError)?;
+
+        let validator_provider = StoreProvider {
+            store: &transaction,
+            cache: store_cache,
+            authorization: Some((actor_id, Consistency::FullyConsistent)),
+        };
+
+        for (entity, (schema, components)) in entities.iter().zip(validation_params) {
+            entity
+                .validate(&schema, components, &validator_provider)
+                .await
+                .change_context(InsertionError)?;
+        }
+
+        let commit_result = transaction.commit().await.change_context(InsertionError);
+        if let Err(error) = commit_result {
+            let mut error = error.expand();
+
+            if let Err(auth_error) = self
+                .authorization_api
+                .modify_entity_relations(relationships.into_iter().map(
+                    |(entity_id, relation_and_subject)| {
+                        (
+                            ModifyRelationshipOperation::Delete,
+                            entity_id,
+                            relation_and_subject,
+                        )
+                    },
+                ))
+                .await
+                .change_context(InsertionError)
+            {
+                error.push(auth_error);
+            }
+
+            Err(error.change_context(InsertionError))
+        } else {
+            if let Some(temporal_client) = &self.temporal_client {
+                temporal_client
+                    .start_update_entity_embeddings_workflow(actor_id, &entities)
+                    .await
+                    .change_context(InsertionError)?;
+            }
+
+            Ok(entities)
+        }
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn validate_entities(
+        &self,
+        actor_id: AccountId,
+        consistency: Consistency<'_>,
+        params: Vec<ValidateEntityParams<'_>>,
+    ) -> Result<(), ValidateEntityError> {
+        let mut status = ReportSink::new();
+
+        let validator_provider = StoreProvider {
+            store: self,
+            cache: StoreCache::default(),
+            authorization: Some((actor_id, Consistency::FullyConsistent)),
+        };
+
+        for mut params in params {
+            let schema = match params.entity_types {
+                EntityValidationType::ClosedSchema(schema) => schema,
+                EntityValidationType::Id(entity_type_urls) => Cow::Owned(
+                    ClosedMultiEntityType::from_multi_type_closed_schema(
+                        stream::iter(entity_type_urls.as_ref())
+                            .then(|entity_type_url| async {
+                                OntologyTypeProvider::<ClosedEntityType>::provide_type(
+                                    &validator_provider,
+                                    entity_type_url,
+                                )
+                                .await
+                                .map(|entity_type| (*entity_type).clone())
+                            })
+                            .try_collect::<Vec<ClosedEntityType>>()
+                            .await
+                            .change_context(ValidateEntityError)?,
+                    )
+                    .change_context(ValidateEntityError)?,
+                ),
+            };
+
+            if schema.all_of.is_empty() {
+                let error = Report::new(validation::EntityValidationError::EmptyEntityTypes);
+                status.append(error);
+            };
+
+            let pre_process_result = EntityPreprocessor {
+                components: params.components,
+            }
+            .visit_object(
+                schema.as_ref(),
+                params.properties.to_mut(),
+                &validator_provider,
+            )
+            .await
+            .change_context(validation::EntityValidationError::InvalidProperties);
+            if let Err(error) = pre_process_result {
+                status.append(error);
+            }
+
+            if let Err(error) = params
+                .link_data
+                .as_deref()
+                .validate(&schema, params.components, &validator_provider)
+                .await
+            {
+                status.append(error);
+            }
+        }
+
+        status
+            .finish()
+            .change_context(ValidateEntityError)
+            .attach(StatusCode::InvalidArgument)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn get_entities(
+        &self,
+        actor_id: AccountId,
+        mut params: GetEntitiesParams<'_>,
+    ) -> Result<GetEntitiesResponse<'static>, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        let temporal_axes = params.temporal_axes.resolve();
+
+        let mut response = self
+            .get_entities_impl(
+                actor_id,
+                GetEntitiesImplParams {
+                    filter: params.filter,
+                    sorting: params.sorting,
+                    limit: params.limit,
+                    include_drafts: params.include_drafts,
+                    include_count: params.include_count,
+                    include_web_ids: params.include_web_ids,
+                    include_created_by_ids: params.include_created_by_ids,
+                    include_edition_created_by_ids: params.include_edition_created_by_ids,
+                    include_type_ids: params.include_type_ids,
+                },
+                &temporal_axes,
+            )
+            .await
+            .map(|(response, _)| response)?;
+
+        if !params.conversions.is_empty() {
+            let provider = StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            };
+            for entity in &mut response.entities {
+                convert_entity(&provider, entity, &params.conversions)
+                    .await
+                    .change_context(QueryError)?;
+            }
+        }
+
+        Ok(response)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn get_entity_subgraph(
+        &self,
+        actor_id: AccountId,
+        mut params: GetEntitySubgraphParams<'_>,
+    ) -> Result<GetEntitySubgraphResponse<'static>, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        let unresolved_temporal_axes = params.temporal_axes;
+        let temporal_axes = unresolved_temporal_axes.clone().resolve();
+
+        let time_axis = temporal_axes.variable_time_axis();
+
+        let (
+            GetEntitiesResponse {
+                entities: root_entities,
+                cursor,
+                count,
+                web_ids,
+                created_by_ids,
+                edition_created_by_ids,
+                type_ids,
+            },
+            zookie,
+        ) = self
+            .get_entities_impl(
+                actor_id,
+                GetEntitiesImplParams {
+                    filter: params.filter,
+                    sorting: params.sorting,
+                    limit: params.limit,
+                    include_drafts: params.include_drafts,
+                    include_count: false,
+                    include_web_ids: params.include_web_ids,
+                    include_created_by_ids: params.include_created_by_ids,
+                    include_edition_created_by_ids: params.include_edition_created_by_ids,
+                    include_type_ids: params.include_type_ids,
+                },
+                &temporal_axes,
+            )
+            .await?;
+
+        let mut subgraph = Subgraph::new(
+            params.graph_resolve_depths,
+            unresolved_temporal_axes,
+            temporal_axes,
+        );
+
+        subgraph.roots.extend(
+            root_entities
+                .iter()
+                .map(|entity| entity.vertex_id(time_axis).into()),
+        );
+        subgraph.vertices.entities = root_entities
+            .into_iter()
+            .map(|entity| (entity.vertex_id(time_axis), entity))
+            .collect();
+
+        let mut traversal_context = TraversalContext::default();
+
+        self.traverse_entities(
+            subgraph
+                .vertices
+                .entities
+                .keys()
+                .map(|id| {
+                    (
+                        *id,
+                        subgraph.depths,
+                        subgraph.temporal_axes.resolved.variable_interval(),
+                    )
+                })
+                .collect(),
+            &mut traversal_context,
+            actor_id,
+            &zookie,
+            &mut subgraph,
+        )
+        .await?;
+
+        traversal_context
+            .read_traversed_vertices(self, &mut subgraph, params.include_drafts)
+            .await?;
+
+        if !params.conversions.is_empty() {
+            let provider = StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            };
+            for entity in subgraph.vertices.entities.values_mut() {
+                convert_entity(&provider, entity, &params.conversions)
+                    .await
+                    .change_context(QueryError)?;
+            }
+        }
+
+        Ok(GetEntitySubgraphResponse {
+            subgraph,
+            cursor,
+            count,
+            web_ids,
+            created_by_ids,
+            edition_created_by_ids,
+            type_ids,
+        })
+    }
+
+    async fn count_entities(
+        &self,
+        actor_id: AccountId,
+        mut params: CountEntitiesParams<'_>,
+    ) -> Result<usize, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        let temporal_axes = params.temporal_axes.resolve();
+
+        let entity_ids = Read::<Entity>::read(
+            self,
+            &params.filter,
+            Some(&temporal_axes),
+            params.include_drafts,
+        )
+        .await?
+        .map_ok(|entity| entity.metadata.record_id.entity_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        let permitted_ids = self
+            .authorization_api
+            .check_entities_permission(
+                actor_id,
+                EntityPermission::View,
+                entity_ids.iter().copied(),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?
+            .0
+            .into_iter()
+            .filter_map(|(entity_id, has_permission)| has_permission.then_some(entity_id))
+            .collect::<HashSet<_>>();
+
+        Ok(entity_ids
+            .into_iter()
+            .filter(|id| permitted_ids.contains(&id.entity_uuid))
+            .count())
+    }
+
+    async fn get_entity_by_id(
+        &self,
+        actor_id: AccountId,
+        entity_id: EntityId,
+        transaction_time: Option<Timestamp<TransactionTime>>,
+        decision_time: Option<Timestamp<DecisionTime>>,
+    ) -> Result<Entity, QueryError> {
+        self.authorization_api
+            .check_entity_permission(
+                actor_id,
+                EntityPermission::View,
+                entity_id,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?
+            .assert_permission()
+            .change_context(QueryError)?;
+
+        let temporal_axes = QueryTemporalAxesUnresolved::TransactionTime {
+            pinned: PinnedTemporalAxisUnresolved::new(decision_time),
+            variable: VariableTemporalAxisUnresolved::new(
+                transaction_time.map(TemporalBound::Inclusive),
+                transaction_time.map(LimitedTemporalBound::Inclusive),
+            ),
+        }
+        .resolve();
+
+        Read::<Entity>::read_one(
+            self,
+            &Filter::for_entity_by_entity_id(entity_id),
+            Some(&temporal_axes),
+            entity_id.draft_id.is_some(),
+        )
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn patch_entity(
+        &mut self,
+        actor_id: AccountId,
+        mut params: PatchEntityParams,
+    ) -> Result<Entity, UpdateError> {
+        let entity_type_ids = params
+            .entity_type_ids
+            .iter()
+            .map(EntityTypeUuid::from_url)
+            .collect::<Vec<_>>();
+
+        if !self
+            .authorization_api
+            .check_entity_types_permission(
+                actor_id,
+                EntityTypePermission::Instantiate,
+                entity_type_ids,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .0
+            .into_iter()
+            .all(|(_, permission)| permission)
+        {
+            bail!(Report::new(UpdateError).attach(StatusCode::PermissionDenied));
+        }
+
+        self.authorization_api
+            .check_entity_permission(
+                actor_id,
+                EntityPermission::Update,
+                params.entity_id,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        // In contrast to Postgres the transaction time is determined after the transaction was
+        // started, so concurrent updates cannot result in overlapping transaction times.
+        let transaction_time = transaction.write(MemoryTables::transaction_time);
+        let decision_time = params
+            .decision_time
+            .map_or_else(|| transaction_time.cast(), Timestamp::remove_nanosecond);
+
+        let tables = transaction.snapshot();
+        let locked_index = tables
+            .entity_edition_index(params.entity_id, transaction_time, decision_time)
+            .ok_or_else(|| {
+                Report::new(EntityDoesNotExist)
+                    .attach(StatusCode::NotFound)
+                    .attach_printable(params.entity_id)
+                    .change_context(UpdateError)
+            })?;
+        let previous_entity = tables
+            .entity_row(&tables.entity_temporal_metadata[locked_index])
+            .map(|row| {
+                Entity::materialize(
+                    &Context {
+                        tables: &tables,
+                        temporal_axes: None,
+                        include_drafts: true,
+                        patterns: RefCell::default(),
+                    },
+                    row,
+                )
+            })
+            .ok_or_else(|| {
+                Report::new(EntityDoesNotExist)
+                    .attach(params.entity_id)
+                    .change_context(UpdateError)
+            })?;
+        drop(tables);
+
+        let mut first_non_draft_created_at_decision_time = previous_entity
+            .metadata
+            .provenance
+            .inferred
+            .first_non_draft_created_at_decision_time;
+        let mut first_non_draft_created_at_transaction_time = previous_entity
+            .metadata
+            .provenance
+            .inferred
+            .first_non_draft_created_at_transaction_time;
+
+        let was_draft_before = previous_entity
+            .metadata
+            .record_id
+            .entity_id
+            .draft_id
+            .is_some();
+        let draft = params.draft.unwrap_or(was_draft_before);
+        let archived = params.archived.unwrap_or(previous_entity.metadata.archived);
+        let (entity_type_ids, entity_types_updated) = if params.entity_type_ids.is_empty() {
+            (previous_entity.metadata.entity_type_ids, false)
+        } else {
+            let added_types = previous_entity
+                .metadata
+                .entity_type_ids
+                .difference(&params.entity_type_ids);
+            let removed_types = params
+                .entity_type_ids
+                .difference(&previous_entity.metadata.entity_type_ids);
+
+            let mut has_changed = false;
+            for entity_type_id in added_types.chain(removed_types) {
+                has_changed = true;
+
+                let entity_type_id = EntityTypeUuid::from_url(entity_type_id);
+                transaction
+                    .authorization_api
+                    .check_entity_type_permission(
+                        actor_id,
+                        EntityTypePermission::Instantiate,
+                        entity_type_id,
+                        Consistency::FullyConsistent,
+                    )
+                    .await
+                    .change_context(UpdateError)?
+                    .assert_permission()
+                    .change_context(UpdateError)
+                    .attach(StatusCode::PermissionDenied)?;
+            }
+
+            (params.entity_type_ids, has_changed)
+        };
+
+        let previous_properties = previous_entity.properties.clone();
+        let previous_property_metadata = previous_entity.metadata.properties.clone();
+
+        let mut properties_with_metadata = PropertyWithMetadata::from_parts(
+            Property::Object(previous_entity.properties),
+            Some(PropertyMetadata::Object {
+                value: previous_entity.metadata.properties.value,
+                metadata: previous_entity.metadata.properties.metadata,
+            }),
+        )
+        .change_context(UpdateError)?;
+        properties_with_metadata
+            .patch(params.properties)
+            .change_context(UpdateError)?;
+
+        let validator_provider = StoreProvider {
+            store: &transaction,
+            cache: StoreCache::default(),
+            authorization: Some((actor_id, Consistency::FullyConsistent)),
+        };
+        let entity_type = ClosedMultiEntityType::from_multi_type_closed_schema(
+            stream::iter(&entity_type_ids)
+                .then(|entity_type_url| async {
+                    OntologyTypeProvider::<ClosedEntityType>::provide_type(
+                        &validator_provider,
+                        entity_type_url,
+                    )
+                    .await
+                    .map(|entity_type| (*entity_type).clone())
+                })
+                .try_collect::<Vec<ClosedEntityType>>()
+                .await
+                .change_context(UpdateError)?,
+        )
+        .change_context(UpdateError)?;
+
+        let validation_components = if draft {
+            ValidateEntityComponents::draft()
+        } else {
+            ValidateEntityComponents::full()
+        };
+
+        let (properties, property_metadata) =
+            if let PropertyWithMetadata::Object(mut object) = properties_with_metadata {
+                EntityPreprocessor {
+                    components: validation_components,
+                }
+                .visit_object(&entity_type, &mut object, &validator_provider)
+                .await
+                .attach(StatusCode::InvalidArgument)
+                .change_context(UpdateError)?;
+                let (properties, property_metadata) = object.into_parts();
+                (properties, property_metadata)
+            } else {
+                unreachable!("patching should not change the property type");
+            };
+        // We move out the cache, so we can re-use the transaction later.
+        let store_cache = validator_provider.cache;
+
+        if previous_properties == properties
+            && was_draft_before == draft
+            && archived == previous_entity.metadata.archived
+            && !entity_types_updated
+            && previous_property_metadata == property_metadata
+            && params.confidence == previous_entity.metadata.confidence
+        {
+            // No changes were made to the entity.
+            return Ok(Entity {
+                properties: previous_properties,
+                link_data: previous_entity.link_data,
+                metadata: EntityMetadata {
+                    record_id: previous_entity.metadata.record_id,
+                    temporal_versioning: previous_entity.metadata.temporal_versioning,
+                    entity_type_ids,
+                    provenance: previous_entity.metadata.provenance,
+                    archived,
+                    confidence: previous_entity.metadata.confidence,
+                    properties: property_metadata,
+                },
+            });
+        }
+
+        let link_data = previous_entity.link_data;
+
+        let edition_provenance = EntityEditionProvenance {
+            created_by_id: EditionCreatedById::new(actor_id),
+            archived_by_id: None,
+            provided: params.provenance,
+        };
+        let edition_id = EntityEditionId::new(Uuid::new_v4());
+
+        let temporal_versioning = transaction.write(|tables| {
+            tables.entity_editions.insert(
+                edition_id,
+                Arc::new(EntityEditionRow {
+                    properties: properties.clone(),
+                    property_metadata: property_metadata.clone(),
+                    archived,
+                    confidence: params.confidence,
+                    provenance: edition_provenance.clone(),
+                    entity_type_ids: entity_type_ids.iter().cloned().collect(),
+                }),
+            );
+
+            match (was_draft_before, draft) {
+                (true, true) | (false, false) => {
+                    // regular update
+                    tables.update_temporal_metadata(
+                        locked_index,
+                        transaction_time,
+                        decision_time,
+                        edition_id,
+                        false,
+                    )
+                }
+                (false, true) => {
+                    params.entity_id.draft_id = Some(DraftId::new(Uuid::new_v4()));
+                    tables.insert_temporal_metadata(
+                        params.entity_id,
+                        edition_id,
+                        transaction_time,
+                        decision_time,
+                    )
+                }
+                (true, false) => {
+                    // Publish a draft
+                    params.entity_id.draft_id = None;
+
+                    if first_non_draft_created_at_decision_time.is_none() {
+                        if let Some(entity) = tables
+                            .entity_ids
+                            .get_mut(&(params.entity_id.owned_by_id, params.entity_id.entity_uuid))
+                        {
+                            entity
+                                .provenance
+                                .first_non_draft_created_at_transaction_time =
+                                Some(transaction_time);
+                            entity.provenance.first_non_draft_created_at_decision_time =
+                                Some(decision_time);
+                        }
+
+                        first_non_draft_created_at_transaction_time = Some(transaction_time);
+                        first_non_draft_created_at_decision_time = Some(decision_time);
+                    }
+
+                    let previous_live_entity = tables.entity_edition_index(
+                        params.entity_id,
+                        transaction_time,
+                        decision_time,
+                    );
+                    let temporal_versioning = tables.update_temporal_metadata(
+                        locked_index,
+                        transaction_time,
+                        decision_time,
+                        edition_id,
+                        true,
+                    );
+                    // Archiving may remove a row, so it has to happen after the draft is updated.
+                    if let Some(previous_live_entity) = previous_live_entity {
+                        tables.archive_entity(
+                            actor_id,
+                            previous_live_entity,
+                            transaction_time,
+                            decision_time,
+                        );
+                    }
+                    temporal_versioning
+                }
+            }
+        });
+
+        let entity_metadata = EntityMetadata {
+            record_id: EntityRecordId {
+                entity_id: params.entity_id,
+                edition_id,
+            },
+            temporal_versioning,
+            entity_type_ids,
+            provenance: EntityProvenance {
+                inferred: InferredEntityProvenance {
+                    first_non_draft_created_at_transaction_time,
+                    first_non_draft_created_at_decision_time,
+                    ..previous_entity.metadata.provenance.inferred
+                },
+                edition: edition_provenance,
+            },
+            confidence: params.confidence,
+            properties: property_metadata,
+            archived,
+        };
+        let entities = [Entity {
+            properties,
+            link_data,
+            metadata: entity_metadata,
+        }];
+
+        let validator_provider = StoreProvider {
+            store: &transaction,
+            cache: store_cache,
+            authorization: Some((actor_id, Consistency::FullyConsistent)),
+        };
+        entities[0]
+            .validate(&entity_type, validation_components, &validator_provider)
+            .await
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        if let Some(temporal_client) = &self.temporal_client {
+            temporal_client
+                .start_update_entity_embeddings_workflow(actor_id, &entities)
+                .await
+                .change_context(UpdateError)?;
+        }
+        let [entity] = entities;
+        Ok(entity)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn update_entity_embeddings(
+        &mut self,
+        _: AccountId,
+        params: UpdateEntityEmbeddingsParams<'_>,
+    ) -> Result<(), UpdateError> {
+        // TODO: Add permission to allow updating embeddings
+        //   see https://linear.app/hash/issue/H-1870
+
+        let entity_id = params.entity_id;
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        transaction.write(|tables| {
+            if params.reset {
+                tables.entity_embeddings.retain(|row| {
+                    row.owned_by_id != entity_id.owned_by_id
+                        || row.entity_uuid != entity_id.entity_uuid
+                        || row.draft_id != entity_id.draft_id
+                        || row.updated_at_transaction_time > params.updated_at_transaction_time
+                        || row.updated_at_decision_time > params.updated_at_decision_time
+                });
+            }
+
+            for embedding in params.embeddings {
+                if let Some(row) = tables.entity_embeddings.iter_mut().find(|row| {
+                    row.owned_by_id == entity_id.owned_by_id
+                        && row.entity_uuid == entity_id.entity_uuid
+                        && row.property == embedding.property
+                }) {
+                    if row.updated_at_transaction_time <= params.updated_at_transaction_time
+                        && row.updated_at_decision_time <= params.updated_at_decision_time
+                    {
+                        row.embedding = embedding.embedding.into_owned();
+                        row.updated_at_transaction_time = params.updated_at_transaction_time;
+                        row.updated_at_decision_time = params.updated_at_decision_time;
+                    }
+                } else {
+                    tables.entity_embeddings.push(EntityEmbeddingRow {
+                        owned_by_id: entity_id.owned_by_id,
+                        entity_uuid: entity_id.entity_uuid,
+                        draft_id: entity_id.draft_id,
+                        property: embedding.property,
+                        embedding: embedding.embedding.into_owned(),
+                        updated_at_transaction_time: params.updated_at_transaction_time,
+                        updated_at_decision_time: params.updated_at_decision_time,
+                    });
+                }
+            }
+        });
+        transaction.commit().await.change_context(UpdateError)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn reindex_entity_cache(&mut self) -> Result<(), UpdateError> {
+        // The inherited entity types are resolved from the entity types when the entities are
+        // read, so there is no cache to be updated.
+        tracing::info!("Reindexing entity cache");
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn permanently_delete_entities(
+        &mut self,
+        actor_id: AccountId,
+        mut params: DeleteEntitiesParams<'_>,
+    ) -> Result<DeleteEntitiesResponse, DeletionError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: &*self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(DeletionError)?;
+
+        let temporal_axes = params.temporal_axes.resolve();
+
+        let mut entity_ids = Read::<Entity>::read_vec(
+            self,
+            &params.filter,
+            Some(&temporal_axes),
+            params.include_drafts,
+        )
+        .await
+        .change_context(DeletionError)?
+        .into_iter()
+        .map(|entity| {
+            let entity_id = entity.metadata.record_id.entity_id;
+            (entity_id.owned_by_id, entity_id.entity_uuid)
+        })
+        .collect::<Vec<_>>();
+        let mut deleted_ids = HashSet::new();
+        entity_ids.retain(|entity_id| deleted_ids.insert(*entity_id));
+        let mut reasons = vec![EntityDeletionReason::Matched; entity_ids.len()];
+
+        // Links cannot exist without their endpoints, so links to deleted entities are deleted
+        // as well. As links may be linked themselves, the queue is processed until no new link is
+        // found.
+        let tables = self.snapshot();
+        let mut num_visited_entities = 0;
+        while let Some(&(owned_by_id, entity_uuid)) = entity_ids.get(num_visited_entities) {
+            num_visited_entities += 1;
+            for edge_kind in [
+                KnowledgeGraphEdgeKind::HasLeftEntity,
+                KnowledgeGraphEdgeKind::HasRightEntity,
+            ] {
+                for link_entity_id in tables.linked_entities(
+                    owned_by_id,
+                    entity_uuid,
+                    edge_kind,
+                    EdgeDirection::Incoming,
+                ) {
+                    if deleted_ids.insert(link_entity_id) {
+                        entity_ids.push(link_entity_id);
+                        reasons.push(EntityDeletionReason::LinkEndpointDeleted);
+                    }
+                }
+            }
+        }
+        let entity_ids = entity_ids
+            .into_iter()
+            .map(|(owned_by_id, entity_uuid)| EntityId {
+                owned_by_id,
+                entity_uuid,
+                draft_id: None,
+            })
+            .collect::<Vec<_>>();
+
+        let (permissions, _zookie) = self
+            .authorization_api
+            .check_entities_permission(
+                actor_id,
+                EntityPermission::FullAccess,
+                entity_ids.iter().copied(),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(DeletionError)?;
+        // The forbidden entities are not reported as the link closure may contain entities the
+        // actor is not allowed to view.
+        let is_forbidden = entity_ids.iter().any(|entity_id| {
+            !permissions
+                .get(&entity_id.entity_uuid)
+                .copied()
+                .unwrap_or(false)
+        });
+        if is_forbidden {
+            return Err(Report::new(DeletionError)
+                .attach(StatusCode::PermissionDenied)
+                .attach_printable(
+                    "The actor does not have permission to delete one or more entities",
+                ));
+        }
+
+        let mut entities = Vec::with_capacity(entity_ids.len());
+        for (entity_id, reason) in entity_ids.into_iter().zip(reasons) {
+            let is_entity = |owned_by_id: OwnedById, entity_uuid: EntityUuid| {
+                owned_by_id == entity_id.owned_by_id && entity_uuid == entity_id.entity_uuid
+            };
+            let temporal_rows = tables
+                .entity_temporal_metadata
+                .iter()
+                .filter(|row| is_entity(row.owned_by_id, row.entity_uuid))
+                .collect::<Vec<_>>();
+
+            let mut draft_ids = Vec::new();
+            let mut edition_ids = Vec::new();
+            for row in &temporal_rows {
+                if let Some(draft_id) = row.draft_id {
+                    if !draft_ids.contains(&draft_id) {
+                        draft_ids.push(draft_id);
+                    }
+                }
+                if !edition_ids.contains(&row.edition_id) {
+                    edition_ids.push(row.edition_id);
+                }
+            }
+
+            entities.push(DeletedEntity {
+                entity_id,
+                reason,
+                draft_ids,
+                edition_ids,
+                num_temporal_versions: temporal_rows.len(),
+                num_embeddings: tables
+                    .entity_embeddings
+                    .iter()
+                    .filter(|row| is_entity(row.owned_by_id, row.entity_uuid))
+                    .count(),
+                relationships: self
+                    .authorization_api
+                    .get_entity_relations(entity_id, Consistency::FullyConsistent)
+                    .await
+                    .change_context(DeletionError)?,
+            });
+        }
+
+        if params.dry_run {
+            return Ok(DeleteEntitiesResponse {
+                dry_run: true,
+                entities,
+            });
+        }
+
+        let relationships = entities
+            .iter()
+            .flat_map(|entity| {
+                entity
+                    .relationships
+                    .iter()
+                    .map(|relation_and_subject| (entity.entity_id, *relation_and_subject))
+            })
+            .collect::<Vec<_>>();
+
+        let transaction = self.transaction().await.change_context(DeletionError)?;
+        transaction.write(|tables| {
+            tables
+                .entity_ids
+                .retain(|entity_id, _| !deleted_ids.contains(entity_id));
+            tables.entity_temporal_metadata.retain(|row| {
+                let deleted = deleted_ids.contains(&(row.owned_by_id, row.entity_uuid));
+                if deleted {
+                    tables.entity_editions.remove(&row.edition_id);
+                }
+                !deleted
+            });
+            tables
+                .entity_embeddings
+                .retain(|row| !deleted_ids.contains(&(row.owned_by_id, row.entity_uuid)));
+        });
+
+        transaction
+            .authorization_api
+            .modify_entity_relations(relationships.iter().copied().map(
+                |(entity_id, relation_and_subject)| {
+                    (
+                        ModifyRelationshipOperation::Delete,
+                        entity_id,
+                        relation_and_subject,
+                    )
+                },
+            ))
+            .await
+            .change_context(DeletionError)?;
+
+        if let Err(error) = transaction.commit().await.change_context(DeletionError) {
+            let mut error = error.expand();
+
+            if let Err(auth_error) = self
+                .authorization_api
+                .modify_entity_relations(relationships.into_iter().map(
+                    |(entity_id, relation_and_subject)| {
+                        (
+                            ModifyRelationshipOperation::Create,
+                            entity_id,
+                            relation_and_subject,
+                        )
+                    },
+                ))
+                .await
+                .change_context(DeletionError)
+            {
+                error.push(auth_error);
+            }
+
+            Err(error.change_context(DeletionError))
+        } else {
+            Ok(DeleteEntitiesResponse {
+                dry_run: false,
+                entities,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use graph_types::{
+        account::AccountId,
+        knowledge::entity::{DraftId, EntityEditionId, EntityId, EntityUuid},
+        owned_by_id::OwnedById,
+    };
+    use temporal_versioning::{
+        ClosedTemporalBound, DecisionTime, LeftClosedTemporalInterval, OpenTemporalBound,
+        Timestamp, TransactionTime,
+    };
+    use uuid::Uuid;
+
+    use crate::store::memory::table::MemoryTables;
+
+    type Row = (
+        EntityEditionId,
+        LeftClosedTemporalInterval<TransactionTime>,
+        LeftClosedTemporalInterval<DecisionTime>,
+    );
+
+    fn timestamp<A>(year: u16) -> Timestamp<A> {
+        format!("{year}-01-01T00:00:00Z")
+            .parse()
+            .expect("could not parse timestamp")
+    }
+
+    fn interval<A>(start: u16, end: Option<u16>) -> LeftClosedTemporalInterval<A> {
+        LeftClosedTemporalInterval::new(
+            ClosedTemporalBound::Inclusive(timestamp(start)),
+            end.map_or(OpenTemporalBound::Unbounded, |end| {
+                OpenTemporalBound::Exclusive(timestamp(end))
+            }),
+        )
+    }
+
+    fn entity_id() -> EntityId {
+        EntityId {
+            owned_by_id: OwnedById::new(Uuid::new_v4()),
+            entity_uuid: EntityUuid::new(Uuid::new_v4()),
+            draft_id: None,
+        }
+    }
+
+    fn rows(tables: &MemoryTables) -> Vec<Row> {
+        tables
+            .entity_temporal_metadata
+            .iter()
+            .map(|row| (row.edition_id, row.transaction_time, row.decision_time))
+            .collect()
+    }
+
+    #[test]
+    fn update_splits_decision_time() {
+        let mut tables = MemoryTables::default();
+        let entity_id = entity_id();
+        let [first, second] = [(); 2].map(|()| EntityEditionId::new(Uuid::new_v4()));
+
+        tables.insert_temporal_metadata(entity_id, first, timestamp(2010), timestamp(2000));
+        let index = tables
+            .entity_edition_index(entity_id, timestamp(2010), timestamp(2000))
+            .expect("edition should exist");
+        let temporal_versioning =
+            tables.update_temporal_metadata(index, timestamp(2011), timestamp(2001), second, false);
+
+        assert_eq!(temporal_versioning.transaction_time, interval(2011, None));
+        assert_eq!(temporal_versioning.decision_time, interval(2001, None));
+        assert_eq!(rows(&tables), [
+            (second, interval(2011, None), interval(2001, None)),
+            (first, interval(2011, None), interval(2000, Some(2001))),
+            (first, interval(2010, Some(2011)), interval(2000, None)),
+        ]);
+
+        for (transaction_time, decision_time, edition) in [
+            (2010, 2005, Some(first)),
+            (2011, 2000, Some(first)),
+            (2011, 2001, Some(second)),
+            (2011, 1999, None),
+            (2009, 2005, None),
+        ] {
+            assert_eq!(
+                tables
+                    .entity_edition_index(
+                        entity_id,
+                        timestamp(transaction_time),
+                        timestamp(decision_time)
+                    )
+                    .map(|index| tables.entity_temporal_metadata[index].edition_id),
+                edition,
+                "unexpected edition at transaction time {transaction_time} and decision time \
+                 {decision_time}"
+            );
+        }
+    }
+
+    #[test]
+    fn update_at_same_decision_time_replaces_edition() {
+        let mut tables = MemoryTables::default();
+        let entity_id = entity_id();
+        let [first, second] = [(); 2].map(|()| EntityEditionId::new(Uuid::new_v4()));
+
+        tables.insert_temporal_metadata(entity_id, first, timestamp(2010), timestamp(2000));
+        tables.update_temporal_metadata(0, timestamp(2011), timestamp(2000), second, false);
+
+        assert_eq!(rows(&tables), [
+            (second, interval(2011, None), interval(2000, None)),
+            (first, interval(2010, Some(2011)), interval(2000, None)),
+        ]);
+    }
+
+    #[test]
+    fn undraft_removes_draft_id() {
+        let mut tables = MemoryTables::default();
+        let entity_id = EntityId {
+            draft_id: Some(DraftId::new(Uuid::new_v4())),
+            ..entity_id()
+        };
+        let [first, second] = [(); 2].map(|()| EntityEditionId::new(Uuid::new_v4()));
+
+        tables.insert_temporal_metadata(entity_id, first, timestamp(2010), timestamp(2000));
+        tables.update_temporal_metadata(0, timestamp(2011), timestamp(2001), second, true);
+
+        let draft_ids = tables
+            .entity_temporal_metadata
+            .iter()
+            .map(|row| (row.edition_id, row.draft_id))
+            .collect::<Vec<_>>();
+        assert_eq!(draft_ids, [
+            (second, None),
+            (first, entity_id.draft_id),
+            (first, entity_id.draft_id),
+        ]);
+    }
+
+    #[test]
+    fn archive_ends_decision_time() {
+        let mut tables = MemoryTables::default();
+        let actor_id = AccountId::new(Uuid::new_v4());
+        let edition = EntityEditionId::new(Uuid::new_v4());
+
+        tables.insert_temporal_metadata(entity_id(), edition, timestamp(2010), timestamp(2000));
+        tables.archive_entity(actor_id, 0, timestamp(2011), timestamp(2001));
+        assert_eq!(rows(&tables), [
+            (edition, interval(2011, None), interval(2000, Some(2001))),
+            (edition, interval(2010, Some(2011)), interval(2000, None)),
+        ]);
+
+        // Archiving at the start of the decision time would leave an empty interval
+        let mut tables = MemoryTables::default();
+        tables.insert_temporal_metadata(entity_id(), edition, timestamp(2010), timestamp(2000));
+        tables.archive_entity(actor_id, 0, timestamp(2011), timestamp(2000));
+        assert_eq!(rows(&tables), [(
+            edition,
+            interval(2010, Some(2011)),
+            interval(2000, None)
+        )]);
+    }
+}
@@ -0,0 +1,330 @@
+//! An in-memory implementation of the graph store.
+//!
+//! The [`MemoryStore`] implements the same traits as the [`PostgresStore`] and aims to behave
+//! identically from the perspective of a caller, including temporal versioning, drafts,
+//! archiving, and the evaluation of [`Filter`]s. It's intended to be used in tests and for local
+//! development where spinning up a database is not desired.
+//!
+//! [`PostgresStore`]: crate::store::PostgresStore
+//! [`Filter`]: hash_graph_store::filter::Filter
+
+mod crud;
+mod filter;
+mod knowledge;
+mod ontology;
+mod pool;
+mod table;
+
+use alloc::sync::Arc;
+use std::sync::{PoisonError, RwLock};
+
+use authorization::{
+    AuthorizationApi,
+    backend::ModifyRelationshipOperation,
+    schema::{
+        AccountGroupAdministratorSubject, AccountGroupRelationAndSubject, WebDataTypeViewerSubject,
+        WebEntityCreatorSubject, WebEntityEditorSubject, WebEntityTypeViewerSubject,
+        WebOwnerSubject, WebPropertyTypeViewerSubject, WebRelationAndSubject, WebSubjectSet,
+    },
+};
+use error_stack::{Report, Result, ResultExt};
+use futures::future::OptionFuture;
+use graph_types::{
+    account::{AccountGroupId, AccountId},
+    owned_by_id::OwnedById,
+};
+use hash_graph_store::account::{
+    AccountGroupInsertionError, AccountInsertionError, AccountStore, InsertAccountGroupIdParams,
+    InsertAccountIdParams, InsertWebIdParams, QueryWebError, WebInsertionError,
+};
+use temporal_client::TemporalClient;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+pub use self::pool::MemoryStorePool;
+use self::table::MemoryTables;
+use crate::store::{StoreError, error::DeletionError};
+
+/// The storage location of the tables of a [`MemoryStore`].
+///
+/// The tables are never mutated in place while they are shared: writers clone the tables on
+/// first write (copy-on-write), so readers always observe a consistent snapshot.
+type TableSlot = RwLock<Arc<MemoryTables>>;
+
+struct MemoryTransaction {
+    parent: Arc<TableSlot>,
+    _writer_guard: Option<OwnedMutexGuard<()>>,
+}
+
+/// An in-memory store
+///
+/// Stores are acquired from a [`MemoryStorePool`]. All stores acquired from the same pool operate
+/// on the same data.
+pub struct MemoryStore<A> {
+    tables: Arc<TableSlot>,
+    transaction: Option<MemoryTransaction>,
+    writer: Arc<Mutex<()>>,
+    pub authorization_api: A,
+    pub temporal_client: Option<Arc<TemporalClient>>,
+}
+
+impl<A> MemoryStore<A>
+where
+    A: Send + Sync,
+{
+    /// Returns a snapshot of the current state of the tables.
+    ///
+    /// Changes made to the store after the snapshot was taken are not reflected in the snapshot.
+    pub(crate) fn snapshot(&self) -> Arc<MemoryTables> {
+        Arc::clone(&self.tables.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Applies `operation` to the tables of this store.
+    ///
+    /// Writes should only be issued inside of a [`transaction`] as the transaction serializes
+    /// concurrent writers.
+    ///
+    /// [`transaction`]: Self::transaction
+    pub(crate) fn write<T>(&self, operation: impl FnOnce(&mut MemoryTables) -> T) -> T {
+        let mut tables = self.tables.write().unwrap_or_else(PoisonError::into_inner);
+        operation(Arc::make_mut(&mut tables))
+    }
+
+    /// Starts a new transaction.
+    ///
+    /// Only a single top-level transaction can be active for all stores of a [`MemoryStorePool`]
+    /// at a time, other writers wait until the transaction is committed or dropped. Dropping the
+    /// transaction without calling [`commit`] discards all changes.
+    ///
+    /// # Errors
+    ///
+    /// This currently does not fail but the signature mirrors the other store implementations.
+    ///
+    /// [`commit`]: Self::commit
+    pub async fn transaction(&mut self) -> Result<MemoryStore<&'_ mut A>, StoreError> {
+        // Nested transactions are already holding the writer lock of the outermost transaction.
+        let writer_guard = OptionFuture::from(
+            self.transaction
+                .is_none()
+                .then(|| Arc::clone(&self.writer).lock_owned()),
+        )
+        .await;
+
+        Ok(MemoryStore {
+            tables: Arc::new(RwLock::new(self.snapshot())),
+            transaction: Some(MemoryTransaction {
+                parent: Arc::clone(&self.tables),
+                _writer_guard: writer_guard,
+            }),
+            writer: Arc::clone(&self.writer),
+            authorization_api: &mut self.authorization_api,
+            temporal_client: self.temporal_client.clone(),
+        })
+    }
+
+    /// Makes the changes of this transaction visible to the store it was started from.
+    ///
+    /// Committing a store, which is not a transaction, is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// This currently does not fail but the signature mirrors the other store implementations.
+    #[expect(
+        clippy::unused_async,
+        reason = "The signature mirrors `PostgresStore::commit`"
+    )]
+    pub async fn commit(self) -> Result<(), StoreError> {
+        if let Some(transaction) = &self.transaction {
+            let tables = self.snapshot();
+            *transaction
+                .parent
+                .write()
+                .unwrap_or_else(PoisonError::into_inner) = tables;
+        }
+        Ok(())
+    }
+
+    /// Discards the changes of this transaction.
+    ///
+    /// # Errors
+    ///
+    /// This currently does not fail but the signature mirrors the other store implementations.
+    #[expect(
+        clippy::unused_async,
+        reason = "The signature mirrors `PostgresStore::rollback`"
+    )]
+    pub async fn rollback(self) -> Result<(), StoreError> {
+        Ok(())
+    }
+}
+
+impl<A: AuthorizationApi> AccountStore for MemoryStore<A> {
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn insert_account_id(
+        &mut self,
+        actor_id: AccountId,
+        params: InsertAccountIdParams,
+    ) -> Result<(), AccountInsertionError> {
+        let transaction = self
+            .transaction()
+            .await
+            .change_context(AccountInsertionError)?;
+
+        if !transaction.write(|tables| tables.accounts.insert(params.account_id)) {
+            return Err(Report::new(AccountInsertionError)
+                .attach_printable("Account does already exist")
+                .attach_printable(params.account_id));
+        }
+
+        transaction
+            .commit()
+            .await
+            .change_context(AccountInsertionError)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn insert_account_group_id(
+        &mut self,
+        actor_id: AccountId,
+        params: InsertAccountGroupIdParams,
+    ) -> Result<(), AccountGroupInsertionError> {
+        let transaction = self
+            .transaction()
+            .await
+            .change_context(AccountGroupInsertionError)?;
+
+        if !transaction.write(|tables| tables.account_groups.insert(params.account_group_id)) {
+            return Err(Report::new(AccountGroupInsertionError)
+                .attach_printable("Account group does already exist")
+                .attach_printable(params.account_group_id));
+        }
+
+        transaction
+            .authorization_api
+            .modify_account_group_relations([(
+                ModifyRelationshipOperation::Create,
+                params.account_group_id,
+                AccountGroupRelationAndSubject::Administrator {
+                    subject: AccountGroupAdministratorSubject::Account { id: actor_id },
+                    level: 0,
+                },
+            )])
+            .await
+            .change_context(AccountGroupInsertionError)?;
+
+        transaction
+            .commit()
+            .await
+            .change_context(AccountGroupInsertionError)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn insert_web_id(
+        &mut self,
+        actor_id: AccountId,
+        params: InsertWebIdParams,
+    ) -> Result<(), WebInsertionError> {
+        let transaction = self.transaction().await.change_context(WebInsertionError)?;
+
+        if !transaction.write(|tables| tables.webs.insert(params.owned_by_id)) {
+            return Err(Report::new(WebInsertionError)
+                .attach_printable("Web does already exist")
+                .attach_printable(params.owned_by_id));
+        }
+
+        let mut relationships = vec![
+            WebRelationAndSubject::Owner {
+                subject: params.owner,
+                level: 0,
+            },
+            WebRelationAndSubject::EntityTypeViewer {
+                subject: WebEntityTypeViewerSubject::Public,
+                level: 0,
+            },
+            WebRelationAndSubject::PropertyTypeViewer {
+                subject: WebPropertyTypeViewerSubject::Public,
+                level: 0,
+            },
+            WebRelationAndSubject::DataTypeViewer {
+                subject: WebDataTypeViewerSubject::Public,
+                level: 0,
+            },
+        ];
+        if let WebOwnerSubject::AccountGroup { id } = params.owner {
+            relationships.extend([
+                WebRelationAndSubject::EntityCreator {
+                    subject: WebEntityCreatorSubject::AccountGroup {
+                        id,
+                        set: WebSubjectSet::Member,
+                    },
+                    level: 0,
+                },
+                WebRelationAndSubject::EntityEditor {
+                    subject: WebEntityEditorSubject::AccountGroup {
+                        id,
+                        set: WebSubjectSet::Member,
+                    },
+                    level: 0,
+                },
+            ]);
+        }
+
+        transaction
+            .authorization_api
+            .modify_web_relations(relationships.into_iter().map(|relation_and_subject| {
+                (
+                    ModifyRelationshipOperation::Create,
+                    params.owned_by_id,
+                    relation_and_subject,
+                )
+            }))
+            .await
+            .change_context(WebInsertionError)?;
+
+        transaction.commit().await.change_context(WebInsertionError)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn identify_owned_by_id(
+        &self,
+        owned_by_id: OwnedById,
+    ) -> Result<WebOwnerSubject, QueryWebError> {
+        let tables = self.snapshot();
+        let is_account = tables
+            .accounts
+            .contains(&AccountId::new(owned_by_id.into_uuid()));
+        let is_account_group = tables
+            .account_groups
+            .contains(&AccountGroupId::new(owned_by_id.into_uuid()));
+
+        match (is_account, is_account_group) {
+            (false, false) => Err(Report::new(QueryWebError)
+                .attach_printable("Record does not exist")
+                .attach_printable(owned_by_id)),
+            (true, false) => Ok(WebOwnerSubject::Account {
+                id: AccountId::new(owned_by_id.into_uuid()),
+            }),
+            (false, true) => Ok(WebOwnerSubject::AccountGroup {
+                id: AccountGroupId::new(owned_by_id.into_uuid()),
+            }),
+            (true, true) => Err(Report::new(QueryWebError)
+                .attach_printable("Record exists in both accounts and account_groups")
+                .attach_printable(owned_by_id)),
+        }
+    }
+}
+
+impl<A> MemoryStore<A>
+where
+    A: Send + Sync,
+{
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn delete_accounts(&mut self, actor_id: AccountId) -> Result<(), DeletionError> {
+        let transaction = self.transaction().await.change_context(DeletionError)?;
+        transaction.write(|tables| {
+            tables.webs.clear();
+            tables.accounts.clear();
+            tables.account_groups.clear();
+        });
+        transaction.commit().await.change_context(DeletionError)
+    }
+}
@@ -0,0 +1,1068 @@
+use alloc::sync::Arc;
+use core::iter::once;
+use std::collections::{HashMap, HashSet};
+
+use authorization::{
+    AuthorizationApi,
+    backend::ModifyRelationshipOperation,
+    schema::{DataTypeOwnerSubject, DataTypePermission, DataTypeRelationAndSubject, WebPermission},
+    zanzibar::{Consistency, Zookie},
+};
+use error_stack::{Result, ResultExt};
+use futures::StreamExt;
+use graph_types::{
+    account::{AccountId, EditionArchivedById, EditionCreatedById},
+    ontology::{
+        DataTypeMetadata, DataTypeWithMetadata, OntologyEditionProvenance, OntologyProvenance,
+        OntologyTemporalMetadata, OntologyTypeClassificationMetadata, OntologyTypeRecordId,
+    },
+};
+use hash_graph_store::{
+    data_type::DataTypeQueryPath,
+    subgraph::{
+        Subgraph, SubgraphRecord,
+        edges::{EdgeDirection, GraphResolveDepths, OntologyEdgeKind},
+        identifier::{DataTypeVertexId, GraphElementVertexId},
+        temporal_axes::{QueryTemporalAxes, VariableAxis},
+    },
+};
+use hash_status::StatusCode;
+use temporal_versioning::{RightBoundedTemporalInterval, Timestamp};
+use type_system::{
+    Validator,
+    schema::{
+        ClosedDataType, DataType, DataTypeUuid, DataTypeValidator, OntologyTypeResolver,
+        OntologyTypeUuid,
+    },
+    url::{OntologyTypeVersion, VersionedUrl},
+};
+
+use crate::store::{
+    DataTypeStore, InsertionError, QueryError, StoreCache, StoreProvider, UpdateError,
+    crud::{QueryResult, Read, ReadPaginated, VersionedUrlSorting},
+    error::DeletionError,
+    memory::{
+        MemoryStore,
+        filter::{
+            Context, MemoryRecord, OntologyField, OntologyRow, OntologyTypeRow, Value,
+            ontology_rows, ontology_values,
+        },
+        ontology::{OntologyReference, property_type::property_type_values, referenced_rows},
+        table::{DataTypeRow, MemoryTables, OntologyEmbeddingRow},
+    },
+    ontology::{
+        ArchiveDataTypeParams, CountDataTypesParams, CreateDataTypeParams,
+        GetDataTypeSubgraphParams, GetDataTypeSubgraphResponse, GetDataTypesParams,
+        GetDataTypesResponse, UnarchiveDataTypeParams, UpdateDataTypeEmbeddingParams,
+        UpdateDataTypesParams,
+    },
+    postgres::TraversalContext,
+};
+
+impl OntologyTypeRow for DataTypeRow {
+    type Schema = DataType;
+
+    fn schema(&self) -> &Self::Schema {
+        &self.schema
+    }
+
+    fn embedding(&self) -> Option<&OntologyEmbeddingRow> {
+        self.embedding.as_ref()
+    }
+}
+
+/// Returns the values of `path` for the data types identified by `data_type_ids`.
+pub(crate) fn data_type_values<'t>(
+    context: &Context<'t>,
+    data_type_ids: impl IntoIterator<Item = OntologyTypeUuid>,
+    path: &DataTypeQueryPath<'_>,
+) -> Vec<Value<'t>> {
+    data_type_ids
+        .into_iter()
+        .filter_map(|data_type_id| {
+            context
+                .tables
+                .data_types
+                .get(&DataTypeUuid::from(data_type_id))
+                .map(|record| (data_type_id, record))
+        })
+        .flat_map(|(data_type_id, record)| referenced_rows(context, data_type_id, record))
+        .flat_map(|row| DataTypeWithMetadata::values(context, row, path))
+        .collect()
+}
+
+impl MemoryRecord for DataTypeWithMetadata {
+    type Row<'t> = OntologyRow<'t, DataTypeRow>;
+
+    fn rows<'t>(context: &Context<'t>) -> Vec<Self::Row<'t>> {
+        ontology_rows(
+            context,
+            context
+                .tables
+                .data_types
+                .iter()
+                .map(|(data_type_id, row)| (*data_type_id, row)),
+        )
+    }
+
+    fn values<'t>(
+        context: &Context<'t>,
+        row: Self::Row<'t>,
+        path: &DataTypeQueryPath<'_>,
+    ) -> Vec<Value<'t>> {
+        let field = match path {
+            DataTypeQueryPath::BaseUrl => OntologyField::BaseUrl,
+            DataTypeQueryPath::Version => OntologyField::Version,
+            DataTypeQueryPath::VersionedUrl => OntologyField::VersionedUrl,
+            DataTypeQueryPath::TransactionTime => OntologyField::TransactionTime,
+            DataTypeQueryPath::OwnedById => OntologyField::OwnedById,
+            DataTypeQueryPath::OntologyId => OntologyField::OntologyId,
+            DataTypeQueryPath::AdditionalMetadata => OntologyField::AdditionalMetadata,
+            DataTypeQueryPath::EditionProvenance(path) => {
+                OntologyField::EditionProvenance(path.as_ref())
+            }
+            DataTypeQueryPath::Embedding => OntologyField::Embedding,
+            DataTypeQueryPath::Schema(path) => OntologyField::Schema(path.as_ref()),
+            DataTypeQueryPath::Title => OntologyField::SchemaText("title"),
+            DataTypeQueryPath::Description => OntologyField::SchemaText("description"),
+            DataTypeQueryPath::Type => OntologyField::SchemaText("type"),
+            DataTypeQueryPath::DataTypeEdge {
+                edge_kind: OntologyEdgeKind::InheritsFrom,
+                path,
+                direction,
+                inheritance_depth,
+            } => {
+                return data_type_values(
+                    context,
+                    context.tables.ontology_edges(
+                        row.ontology_id,
+                        OntologyReference::DataTypeInheritsFrom {
+                            inheritance_depth: *inheritance_depth,
+                        },
+                        *direction,
+                    ),
+                    path,
+                );
+            }
+            DataTypeQueryPath::PropertyTypeEdge {
+                edge_kind: OntologyEdgeKind::ConstrainsValuesOn,
+                path,
+            } => {
+                return property_type_values(
+                    context,
+                    context.tables.ontology_edges(
+                        row.ontology_id,
+                        OntologyReference::PropertyTypeConstrainsValuesOn,
+                        EdgeDirection::Incoming,
+                    ),
+                    path,
+                );
+            }
+            DataTypeQueryPath::DataTypeEdge { .. } | DataTypeQueryPath::PropertyTypeEdge { .. } => {
+                return Vec::new();
+            }
+            DataTypeQueryPath::TargetConversionBaseUrls => {
+                return vec![Value::serialized(
+                    &row.record.conversions.keys().collect::<Vec<_>>(),
+                    None,
+                )];
+            }
+            DataTypeQueryPath::FromConversions => {
+                return vec![Value::serialized(
+                    &row.record
+                        .conversions
+                        .values()
+                        .map(|conversions| &conversions.from)
+                        .collect::<Vec<_>>(),
+                    None,
+                )];
+            }
+            DataTypeQueryPath::IntoConversions => {
+                return vec![Value::serialized(
+                    &row.record
+                        .conversions
+                        .values()
+                        .map(|conversions| &conversions.to)
+                        .collect::<Vec<_>>(),
+                    None,
+                )];
+            }
+        };
+
+        ontology_values(context, row, &field)
+    }
+
+    fn materialize<'t>(_: &Context<'t>, row: Self::Row<'t>) -> Self {
+        Self {
+            schema: (*row.record.schema).clone(),
+            metadata: DataTypeMetadata {
+                record_id: OntologyTypeRecordId::from(row.metadata.url.clone()),
+                classification: row.metadata.classification.clone(),
+                temporal_versioning: OntologyTemporalMetadata {
+                    transaction_time: row.temporal_metadata.transaction_time,
+                },
+                provenance: OntologyProvenance {
+                    edition: row.temporal_metadata.provenance.clone(),
+                },
+                conversions: row.record.conversions.clone(),
+            },
+        }
+    }
+
+    fn clone_record(&self) -> Self {
+        Self {
+            schema: self.schema.clone(),
+            metadata: self.metadata.clone(),
+        }
+    }
+}
+
+impl MemoryTables {
+    /// Adds the data types referenced by `data_type_ids` to `ontology_type_resolver`.
+    ///
+    /// Only data types, which are not archived, are added.
+    fn add_parent_data_types(
+        &self,
+        data_type_ids: impl IntoIterator<Item = DataTypeUuid>,
+        ontology_type_resolver: &mut OntologyTypeResolver,
+    ) {
+        let now = Timestamp::now();
+        for data_type_id in data_type_ids {
+            if self.ontology_row_at(data_type_id.into(), now).is_none() {
+                continue;
+            }
+            if let Some(parent) = self.data_types.get(&data_type_id) {
+                ontology_type_resolver.add_closed_data_type(
+                    data_type_id,
+                    Arc::clone(&parent.schema),
+                    Arc::clone(&parent.resolve_data),
+                );
+            }
+        }
+    }
+}
+
+impl<A> MemoryStore<A>
+where
+    A: AuthorizationApi,
+{
+    #[tracing::instrument(level = "trace", skip(data_types, authorization_api, zookie))]
+    pub(crate) async fn filter_data_types_by_permission<I, T>(
+        data_types: impl IntoIterator<Item = (I, T)> + Send,
+        actor_id: AccountId,
+        authorization_api: &A,
+        zookie: &Zookie<'static>,
+    ) -> Result<impl Iterator<Item = T>, QueryError>
+    where
+        I: Into<DataTypeUuid> + Send,
+        T: Send,
+    {
+        let (ids, data_types): (Vec<_>, Vec<_>) = data_types
+            .into_iter()
+            .map(|(id, edge)| (id.into(), edge))
+            .unzip();
+
+        let permissions = authorization_api
+            .check_data_types_permission(
+                actor_id,
+                DataTypePermission::View,
+                ids.iter().copied(),
+                Consistency::AtExactSnapshot(zookie),
+            )
+            .await
+            .change_context(QueryError)?
+            .0;
+
+        Ok(ids
+            .into_iter()
+            .zip(data_types)
+            .filter_map(move |(id, data_type)| {
+                permissions
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(false)
+                    .then_some(data_type)
+            }))
+    }
+
+    async fn get_data_types_impl(
+        &self,
+        actor_id: AccountId,
+        params: GetDataTypesParams<'_>,
+        temporal_axes: &QueryTemporalAxes,
+    ) -> Result<(GetDataTypesResponse, Zookie<'static>), QueryError> {
+        #[expect(clippy::if_then_some_else_none, reason = "Function is async")]
+        let count = if params.include_count {
+            Some(
+                self.count_data_types(actor_id, CountDataTypesParams {
+                    filter: params.filter.clone(),
+                    temporal_axes: params.temporal_axes.clone(),
+                    include_drafts: params.include_drafts,
+                })
+                .await?,
+            )
+        } else {
+            None
+        };
+
+        let mut visited_ontology_ids = HashSet::new();
+
+        let (data, artifacts) =
+            ReadPaginated::<DataTypeWithMetadata, VersionedUrlSorting>::read_paginated_vec(
+                self,
+                &params.filter,
+                Some(temporal_axes),
+                &VersionedUrlSorting {
+                    cursor: params.after,
+                },
+                params.limit,
+                params.include_drafts,
+            )
+            .await?;
+        let data_types = data
+            .into_iter()
+            .filter_map(|row| {
+                let data_type = row.decode_record(&artifacts);
+                let id = DataTypeUuid::from_url(&data_type.schema.id);
+                visited_ontology_ids.insert(id).then_some((id, data_type))
+            })
+            .collect::<Vec<_>>();
+
+        let filtered_ids = data_types
+            .iter()
+            .map(|(data_type_id, _)| *data_type_id)
+            .collect::<Vec<_>>();
+
+        let (permissions, zookie) = self
+            .authorization_api
+            .check_data_types_permission(
+                actor_id,
+                DataTypePermission::View,
+                filtered_ids,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?;
+
+        let data_types = data_types
+            .into_iter()
+            .filter_map(|(id, data_type)| {
+                permissions
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(false)
+                    .then_some(data_type)
+            })
+            .collect::<Vec<_>>();
+
+        Ok((
+            GetDataTypesResponse {
+                cursor: if params.limit.is_some() {
+                    data_types
+                        .last()
+                        .map(|data_type| data_type.schema.id.clone())
+                } else {
+                    None
+                },
+                data_types,
+                count,
+            },
+            zookie,
+        ))
+    }
+
+    /// Internal method to read a [`DataTypeWithMetadata`] into a [`TraversalContext`].
+    ///
+    /// This is used to recursively resolve a type, so the result can be reused.
+    #[tracing::instrument(level = "info", skip(self))]
+    pub(crate) async fn traverse_data_types(
+        &self,
+        mut data_type_queue: Vec<(
+            DataTypeUuid,
+            GraphResolveDepths,
+            RightBoundedTemporalInterval<VariableAxis>,
+        )>,
+        traversal_context: &mut TraversalContext,
+        actor_id: AccountId,
+        zookie: &Zookie<'static>,
+        subgraph: &mut Subgraph,
+    ) -> Result<(), QueryError> {
+        let tables = self.snapshot();
+
+        while !data_type_queue.is_empty() {
+            let mut edges_to_traverse = HashMap::<OntologyEdgeKind, Vec<_>>::new();
+
+            #[expect(clippy::iter_with_drain, reason = "false positive, vector is reused")]
+            for (data_type_ontology_id, graph_resolve_depths, traversal_interval) in
+                data_type_queue.drain(..)
+            {
+                for edge_kind in [
+                    OntologyEdgeKind::InheritsFrom,
+                    OntologyEdgeKind::ConstrainsValuesOn,
+                ] {
+                    if let Some(new_graph_resolve_depths) = graph_resolve_depths
+                        .decrement_depth_for_edge(edge_kind, EdgeDirection::Outgoing)
+                    {
+                        edges_to_traverse.entry(edge_kind).or_default().push((
+                            OntologyTypeUuid::from(data_type_ontology_id),
+                            new_graph_resolve_depths,
+                            traversal_interval,
+                        ));
+                    }
+                }
+            }
+
+            for (edge_kind, reference) in [
+                (
+                    OntologyEdgeKind::InheritsFrom,
+                    OntologyReference::DataTypeInheritsFrom {
+                        inheritance_depth: Some(0),
+                    },
+                ),
+                (
+                    OntologyEdgeKind::ConstrainsValuesOn,
+                    OntologyReference::DataTypeConstrainsValuesOn,
+                ),
+            ] {
+                if let Some(traversal_data) = edges_to_traverse.get(&edge_kind) {
+                    data_type_queue.extend(
+                        Self::filter_data_types_by_permission(
+                            tables
+                                .read_ontology_edges(traversal_data, reference)
+                                .into_iter()
+                                .map(|(target_id, edge)| (DataTypeUuid::from(target_id), edge)),
+                            actor_id,
+                            &self.authorization_api,
+                            zookie,
+                        )
+                        .await?
+                        .flat_map(|edge| {
+                            subgraph.insert_edge(
+                                &DataTypeVertexId::from(edge.left_endpoint),
+                                edge_kind,
+                                EdgeDirection::Outgoing,
+                                DataTypeVertexId::from(edge.right_endpoint),
+                            );
+
+                            traversal_context.add_data_type_id(
+                                DataTypeUuid::from(edge.right_endpoint_ontology_id),
+                                edge.resolve_depths,
+                                edge.traversal_interval,
+                            )
+                        }),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn delete_data_types(&mut self) -> Result<(), DeletionError> {
+        let transaction = self.transaction().await.change_context(DeletionError)?;
+
+        transaction.write(|tables| {
+            let data_types = tables
+                .data_types
+                .drain()
+                .map(|(data_type_id, _)| OntologyTypeUuid::from(data_type_id))
+                .collect::<Vec<_>>();
+            tables.delete_ontology_ids(data_types);
+        });
+
+        transaction.commit().await.change_context(DeletionError)?;
+
+        Ok(())
+    }
+}
+
+impl<A> DataTypeStore for MemoryStore<A>
+where
+    A: AuthorizationApi,
+{
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn create_data_types<P, R>(
+        &mut self,
+        actor_id: AccountId,
+        params: P,
+    ) -> Result<Vec<DataTypeMetadata>, InsertionError>
+    where
+        P: IntoIterator<Item = CreateDataTypeParams<R>, IntoIter: Send> + Send,
+        R: IntoIterator<Item = DataTypeRelationAndSubject> + Send + Sync,
+    {
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        let mut relationships = HashSet::new();
+
+        let mut inserted_data_type_metadata = Vec::new();
+        let mut inserted_data_types = Vec::new();
+        let mut data_type_reference_ids = HashSet::new();
+
+        for parameters in params {
+            let provenance = OntologyProvenance {
+                edition: OntologyEditionProvenance {
+                    created_by_id: EditionCreatedById::new(actor_id),
+                    archived_by_id: None,
+                    user_defined: parameters.provenance,
+                },
+            };
+
+            let record_id = OntologyTypeRecordId::from(parameters.schema.id.clone());
+            let data_type_id = DataTypeUuid::from_url(&parameters.schema.id);
+            if let OntologyTypeClassificationMetadata::Owned { owned_by_id } =
+                &parameters.classification
+            {
+                transaction
+                    .authorization_api
+                    .check_web_permission(
+                        actor_id,
+                        WebPermission::CreateDataType,
+                        *owned_by_id,
+                        Consistency::FullyConsistent,
+                    )
+                    .await
+                    .change_context(InsertionError)?
+                    .assert_permission()
+                    .change_context(InsertionError)?;
+
+                relationships.insert((data_type_id, DataTypeRelationAndSubject::Owner {
+                    subject: DataTypeOwnerSubject::Web { id: *owned_by_id },
+                    level: 0,
+                }));
+            }
+
+            relationships.extend(
+                parameters
+                    .relationships
+                    .into_iter()
+                    .map(|relation_and_subject| (data_type_id, relation_and_subject)),
+            );
+
+            if let Some((_ontology_id, temporal_versioning)) = transaction.write(|tables| {
+                tables.create_ontology_metadata(
+                    &parameters.schema.id,
+                    &parameters.classification,
+                    parameters.conflict_behavior,
+                    &provenance,
+                )
+            })? {
+                data_type_reference_ids.extend(
+                    parameters
+                        .schema
+                        .data_type_references()
+                        .map(|(reference, _)| DataTypeUuid::from_url(&reference.url)),
+                );
+                inserted_data_types.push((
+                    data_type_id,
+                    Arc::new(parameters.schema),
+                    parameters.conversions.clone(),
+                ));
+                inserted_data_type_metadata.push(DataTypeMetadata {
+                    record_id,
+                    classification: parameters.classification,
+                    temporal_versioning,
+                    provenance,
+                    conversions: parameters.conversions,
+                });
+            }
+        }
+
+        let mut ontology_type_resolver = OntologyTypeResolver::default();
+
+        for (data_type_id, inserted_data_type, _) in &inserted_data_types {
+            ontology_type_resolver
+                .add_unresolved_data_type(*data_type_id, Arc::clone(inserted_data_type));
+        }
+
+        transaction
+            .snapshot()
+            .add_parent_data_types(data_type_reference_ids, &mut ontology_type_resolver);
+
+        let closed_schemas = inserted_data_types
+            .iter()
+            .map(|(data_type_id, data_type, _)| {
+                let closed_metadata = ontology_type_resolver
+                    .resolve_data_type_metadata(*data_type_id)
+                    .change_context(InsertionError)?;
+                let closed_schema =
+                    ClosedDataType::from_resolve_data((**data_type).clone(), &closed_metadata)
+                        .change_context(InsertionError)?;
+
+                Ok((closed_schema, closed_metadata))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let data_type_validator = DataTypeValidator;
+        for (_, data_type, _) in &inserted_data_types {
+            data_type_validator
+                .validate_ref(&**data_type)
+                .await
+                .attach(StatusCode::InvalidArgument)
+                .change_context(InsertionError)?;
+        }
+        for ((closed_schema, closed_metadata), (data_type_id, data_type, conversions)) in
+            closed_schemas.into_iter().zip(&inserted_data_types)
+        {
+            data_type_validator
+                .validate_ref(&closed_schema)
+                .await
+                .attach(StatusCode::InvalidArgument)
+                .change_context(InsertionError)?;
+
+            transaction.write(|tables| {
+                tables.data_types.insert(*data_type_id, DataTypeRow {
+                    schema: Arc::clone(data_type),
+                    resolve_data: closed_metadata,
+                    conversions: conversions.clone(),
+                    embedding: None,
+                });
+            });
+        }
+
+        #[expect(clippy::needless_collect, reason = "Higher ranked lifetime error")]
+        transaction
+            .authorization_api
+            .modify_data_type_relations(
+                relationships
+                    .iter()
+                    .map(|(resource, relation_and_subject)| {
+                        (
+                            ModifyRelationshipOperation::Create,
+                            *resource,
+                            *relation_and_subject,
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .await
+            .change_context(InsertionError)?;
+
+        if let Err(error) = transaction.commit().await.change_context(InsertionError) {
+            let mut error = error.expand();
+
+            if let Err(auth_error) = self
+                .authorization_api
+                .modify_data_type_relations(relationships.into_iter().map(
+                    |(resource, relation_and_subject)| {
+                        (
+                            ModifyRelationshipOperation::Delete,
+                            resource,
+                            relation_and_subject,
+                        )
+                    },
+                ))
+                .await
+                .change_context(InsertionError)
+            {
+                error.push(auth_error);
+            }
+
+            Err(error.change_context(InsertionError))
+        } else {
+            if let Some(temporal_client) = &self.temporal_client {
+                temporal_client
+                    .start_update_data_type_embeddings_workflow(
+                        actor_id,
+                        &inserted_data_types
+                            .iter()
+                            .zip(&inserted_data_type_metadata)
+                            .map(|((_, schema, _), metadata)| DataTypeWithMetadata {
+                                schema: (**schema).clone(),
+                                metadata: metadata.clone(),
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+            }
+
+            Ok(inserted_data_type_metadata)
+        }
+    }
+
+    async fn get_data_types(
+        &self,
+        actor_id: AccountId,
+        mut params: GetDataTypesParams<'_>,
+    ) -> Result<GetDataTypesResponse, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        let temporal_axes = params.temporal_axes.clone().resolve();
+        self.get_data_types_impl(actor_id, params, &temporal_axes)
+            .await
+            .map(|(response, _)| response)
+    }
+
+    async fn count_data_types(
+        &self,
+        actor_id: AccountId,
+        mut params: CountDataTypesParams<'_>,
+    ) -> Result<usize, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        Ok(self
+            .read(
+                &params.filter,
+                Some(&params.temporal_axes.resolve()),
+                params.include_drafts,
+            )
+            .await?
+            .count()
+            .await)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_data_type_subgraph(
+        &self,
+        actor_id: AccountId,
+        mut params: GetDataTypeSubgraphParams<'_>,
+    ) -> Result<GetDataTypeSubgraphResponse, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        let temporal_axes = params.temporal_axes.clone().resolve();
+        let time_axis = temporal_axes.variable_time_axis();
+
+        let (
+            GetDataTypesResponse {
+                data_types,
+                cursor,
+                count,
+            },
+            zookie,
+        ) = self
+            .get_data_types_impl(
+                actor_id,
+                GetDataTypesParams {
+                    filter: params.filter,
+                    temporal_axes: params.temporal_axes.clone(),
+                    after: params.after,
+                    limit: params.limit,
+                    include_drafts: params.include_drafts,
+                    include_count: params.include_count,
+                },
+                &temporal_axes,
+            )
+            .await?;
+
+        let mut subgraph = Subgraph::new(
+            params.graph_resolve_depths,
+            params.temporal_axes,
+            temporal_axes.clone(),
+        );
+
+        let (data_type_ids, data_type_vertex_ids): (Vec<_>, Vec<_>) = data_types
+            .iter()
+            .map(|data_type| {
+                (
+                    DataTypeUuid::from_url(&data_type.schema.id),
+                    GraphElementVertexId::from(data_type.vertex_id(time_axis)),
+                )
+            })
+            .unzip();
+        subgraph.roots.extend(data_type_vertex_ids);
+        subgraph.vertices.data_types = data_types
+            .into_iter()
+            .map(|data_type| (data_type.vertex_id(time_axis), data_type))
+            .collect();
+
+        let mut traversal_context = TraversalContext::default();
+
+        self.traverse_data_types(
+            data_type_ids
+                .into_iter()
+                .map(|id| {
+                    (
+                        id,
+                        subgraph.depths,
+                        subgraph.temporal_axes.resolved.variable_interval(),
+                    )
+                })
+                .collect(),
+            &mut traversal_context,
+            actor_id,
+            &zookie,
+            &mut subgraph,
+        )
+        .await?;
+
+        traversal_context
+            .read_traversed_vertices(self, &mut subgraph, params.include_drafts)
+            .await?;
+
+        Ok(GetDataTypeSubgraphResponse {
+            subgraph,
+            cursor,
+            count,
+        })
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn update_data_type<R>(
+        &mut self,
+        actor_id: AccountId,
+        params: UpdateDataTypesParams<R>,
+    ) -> Result<DataTypeMetadata, UpdateError>
+    where
+        R: IntoIterator<Item = DataTypeRelationAndSubject> + Send + Sync,
+    {
+        let data_type_validator = DataTypeValidator;
+
+        let old_ontology_id = DataTypeUuid::from_url(&VersionedUrl {
+            base_url: params.schema.id.base_url.clone(),
+            version: OntologyTypeVersion::new(
+                params
+                    .schema
+                    .id
+                    .version
+                    .inner()
+                    .checked_sub(1)
+                    .ok_or(UpdateError)
+                    .attach_printable(
+                        "The version of the data type is already at the lowest possible value",
+                    )?,
+            ),
+        });
+        let new_ontology_id = DataTypeUuid::from_url(&params.schema.id);
+        self.authorization_api
+            .check_data_type_permission(
+                actor_id,
+                DataTypePermission::Update,
+                old_ontology_id,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let provenance = OntologyProvenance {
+            edition: OntologyEditionProvenance {
+                created_by_id: EditionCreatedById::new(actor_id),
+                archived_by_id: None,
+                user_defined: params.provenance,
+            },
+        };
+
+        let schema = data_type_validator
+            .validate(params.schema)
+            .await
+            .change_context(UpdateError)?;
+
+        let mut ontology_type_resolver = OntologyTypeResolver::default();
+        transaction.snapshot().add_parent_data_types(
+            schema
+                .data_type_references()
+                .map(|(reference, _)| DataTypeUuid::from_url(&reference.url)),
+            &mut ontology_type_resolver,
+        );
+
+        let schema = Arc::new(schema.into_inner());
+        ontology_type_resolver.add_unresolved_data_type(new_ontology_id, Arc::clone(&schema));
+        let resolve_data = ontology_type_resolver
+            .resolve_data_type_metadata(new_ontology_id)
+            .change_context(UpdateError)?;
+
+        let closed_schema = data_type_validator
+            .validate(
+                ClosedDataType::from_resolve_data((*schema).clone(), &resolve_data)
+                    .change_context(UpdateError)?,
+            )
+            .await
+            .change_context(UpdateError)?;
+        let (_ontology_id, owned_by_id, temporal_versioning) = transaction.write(|tables| {
+            let updated = tables.update_owned_ontology_id(&schema.id, &provenance.edition)?;
+            tables.data_types.insert(new_ontology_id, DataTypeRow {
+                schema: Arc::clone(&schema),
+                resolve_data,
+                conversions: params.conversions.clone(),
+                embedding: None,
+            });
+            Ok::<_, error_stack::Report<UpdateError>>(updated)
+        })?;
+
+        let relationships = params
+            .relationships
+            .into_iter()
+            .chain(once(DataTypeRelationAndSubject::Owner {
+                subject: DataTypeOwnerSubject::Web { id: owned_by_id },
+                level: 0,
+            }))
+            .collect::<Vec<_>>();
+
+        transaction
+            .authorization_api
+            .modify_data_type_relations(relationships.clone().into_iter().map(
+                |relation_and_subject| {
+                    (
+                        ModifyRelationshipOperation::Create,
+                        new_ontology_id,
+                        relation_and_subject,
+                    )
+                },
+            ))
+            .await
+            .change_context(UpdateError)?;
+
+        if let Err(error) = transaction.commit().await.change_context(UpdateError) {
+            let mut error = error.expand();
+
+            if let Err(auth_error) = self
+                .authorization_api
+                .modify_data_type_relations(relationships.into_iter().map(|relation_and_subject| {
+                    (
+                        ModifyRelationshipOperation::Delete,
+                        new_ontology_id,
+                        relation_and_subject,
+                    )
+                }))
+                .await
+                .change_context(UpdateError)
+            {
+                error.push(auth_error);
+            }
+
+            Err(error.change_context(UpdateError))
+        } else {
+            let metadata = DataTypeMetadata {
+                record_id: OntologyTypeRecordId::from(closed_schema.id.clone()),
+                classification: OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                temporal_versioning,
+                provenance,
+                conversions: params.conversions,
+            };
+
+            if let Some(temporal_client) = &self.temporal_client {
+                temporal_client
+                    .start_update_data_type_embeddings_workflow(actor_id, &[DataTypeWithMetadata {
+                        schema: (*schema).clone(),
+                        metadata: metadata.clone(),
+                    }])
+                    .await
+                    .change_context(UpdateError)?;
+            }
+
+            Ok(metadata)
+        }
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn archive_data_type(
+        &mut self,
+        actor_id: AccountId,
+        params: ArchiveDataTypeParams<'_>,
+    ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        let temporal_metadata = transaction.write(|tables| {
+            tables.archive_ontology_type(&params.data_type_id, EditionArchivedById::new(actor_id))
+        })?;
+        transaction.commit().await.change_context(UpdateError)?;
+        Ok(temporal_metadata)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn unarchive_data_type(
+        &mut self,
+        actor_id: AccountId,
+        params: UnarchiveDataTypeParams,
+    ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        let temporal_metadata = transaction.write(|tables| {
+            tables.unarchive_ontology_type(&params.data_type_id, &OntologyEditionProvenance {
+                created_by_id: EditionCreatedById::new(actor_id),
+                archived_by_id: None,
+                user_defined: params.provenance,
+            })
+        })?;
+        transaction.commit().await.change_context(UpdateError)?;
+        Ok(temporal_metadata)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn update_data_type_embeddings(
+        &mut self,
+        _: AccountId,
+        params: UpdateDataTypeEmbeddingParams<'_>,
+    ) -> Result<(), UpdateError> {
+        // TODO: Add permission to allow updating embeddings
+        //   see https://linear.app/hash/issue/H-1870
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        transaction.write(|tables| {
+            tables.update_ontology_embedding(
+                &params.data_type_id,
+                OntologyEmbeddingRow {
+                    embedding: params.embedding.into_owned(),
+                    updated_at_transaction_time: params.updated_at_transaction_time,
+                },
+                |tables, ontology_id| {
+                    tables
+                        .data_types
+                        .get_mut(&DataTypeUuid::from(ontology_id))
+                        .map(|row| &mut row.embedding)
+                },
+            );
+        });
+        transaction.commit().await.change_context(UpdateError)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn reindex_data_type_cache(&mut self) -> Result<(), UpdateError> {
+        tracing::info!("Reindexing data type cache");
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let mut ontology_type_resolver = OntologyTypeResolver::default();
+        let data_type_ids = transaction
+            .snapshot()
+            .data_types
+            .iter()
+            .map(|(data_type_id, row)| {
+                ontology_type_resolver
+                    .add_unresolved_data_type(*data_type_id, Arc::clone(&row.schema));
+                *data_type_id
+            })
+            .collect::<Vec<_>>();
+
+        for data_type_id in data_type_ids {
+            let resolve_data = ontology_type_resolver
+                .resolve_data_type_metadata(data_type_id)
+                .change_context(UpdateError)?;
+
+            transaction.write(|tables| {
+                if let Some(row) = tables.data_types.get_mut(&data_type_id) {
+                    row.resolve_data = resolve_data;
+                }
+            });
+        }
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1225 @@
+use alloc::sync::Arc;
+use core::iter::once;
+use std::collections::{HashMap, HashSet};
+
+use authorization::{
+    AuthorizationApi,
+    backend::ModifyRelationshipOperation,
+    schema::{
+        EntityTypeOwnerSubject, EntityTypePermission, EntityTypeRelationAndSubject, WebPermission,
+    },
+    zanzibar::{Consistency, Zookie},
+};
+use error_stack::{Report, Result, ResultExt};
+use futures::{StreamExt, TryStreamExt};
+use graph_types::{
+    account::{AccountId, EditionArchivedById, EditionCreatedById},
+    ontology::{
+        EntityTypeMetadata, EntityTypeWithMetadata, OntologyEditionProvenance, OntologyProvenance,
+        OntologyTemporalMetadata, OntologyTypeClassificationMetadata, OntologyTypeRecordId,
+    },
+};
+use hash_graph_store::{
+    entity_type::EntityTypeQueryPath,
+    subgraph::{
+        Subgraph, SubgraphRecord,
+        edges::{EdgeDirection, GraphResolveDepths, OntologyEdgeKind, SharedEdgeKind},
+        identifier::{EntityTypeVertexId, GraphElementVertexId, PropertyTypeVertexId},
+        temporal_axes::{QueryTemporalAxes, VariableAxis},
+    },
+};
+use temporal_versioning::{RightBoundedTemporalInterval, Timestamp};
+use type_system::{
+    Validator,
+    schema::{
+        ClosedEntityType, EntityType, EntityTypeResolveData, EntityTypeUuid, EntityTypeValidator,
+        OntologyTypeResolver, OntologyTypeUuid, PropertyTypeUuid,
+    },
+    url::{OntologyTypeVersion, VersionedUrl},
+};
+
+use crate::store::{
+    EntityTypeStore, InsertionError, QueryError, StoreCache, StoreProvider, UpdateError,
+    crud::{QueryResult, Read, ReadPaginated, VersionedUrlSorting},
+    error::DeletionError,
+    memory::{
+        MemoryStore,
+        filter::{
+            Context, MemoryRecord, OntologyField, OntologyRow, OntologyTypeRow, Value,
+            ontology_rows, ontology_values,
+        },
+        knowledge::entities_of_type_values,
+        ontology::{OntologyReference, property_type::property_type_values, referenced_rows},
+        table::{EntityTypeRow, MemoryTables, OntologyEmbeddingRow},
+    },
+    ontology::{
+        ArchiveEntityTypeParams, CountEntityTypesParams, CreateEntityTypeParams,
+        GetEntityTypeSubgraphParams, GetEntityTypeSubgraphResponse, GetEntityTypesParams,
+        GetEntityTypesResponse, UnarchiveEntityTypeParams, UpdateEntityTypeEmbeddingParams,
+        UpdateEntityTypesParams,
+    },
+    postgres::{ResponseCountMap, TraversalContext},
+};
+
+impl OntologyTypeRow for EntityTypeRow {
+    type Schema = EntityType;
+
+    fn schema(&self) -> &Self::Schema {
+        &self.schema
+    }
+
+    fn embedding(&self) -> Option<&OntologyEmbeddingRow> {
+        self.embedding.as_ref()
+    }
+}
+
+/// Returns the values of `path` for the entity types identified by `entity_type_ids`.
+pub(crate) fn entity_type_values<'t>(
+    context: &Context<'t>,
+    entity_type_ids: impl IntoIterator<Item = OntologyTypeUuid>,
+    path: &EntityTypeQueryPath<'_>,
+) -> Vec<Value<'t>> {
+    entity_type_ids
+        .into_iter()
+        .filter_map(|entity_type_id| {
+            context
+                .tables
+                .entity_types
+                .get(&EntityTypeUuid::from(entity_type_id))
+                .map(|record| (entity_type_id, record))
+        })
+        .flat_map(|(entity_type_id, record)| referenced_rows(context, entity_type_id, record))
+        .flat_map(|row| EntityTypeWithMetadata::values(context, row, path))
+        .collect()
+}
+
+impl MemoryRecord for EntityTypeWithMetadata {
+    type Row<'t> = OntologyRow<'t, EntityTypeRow>;
+
+    fn rows<'t>(context: &Context<'t>) -> Vec<Self::Row<'t>> {
+        ontology_rows(
+            context,
+            context
+                .tables
+                .entity_types
+                .iter()
+                .map(|(entity_type_id, row)| (*entity_type_id, row)),
+        )
+    }
+
+    fn values<'t>(
+        context: &Context<'t>,
+        row: Self::Row<'t>,
+        path: &EntityTypeQueryPath<'_>,
+    ) -> Vec<Value<'t>> {
+        let field = match path {
+            EntityTypeQueryPath::BaseUrl => OntologyField::BaseUrl,
+            EntityTypeQueryPath::Version => OntologyField::Version,
+            EntityTypeQueryPath::VersionedUrl => OntologyField::VersionedUrl,
+            EntityTypeQueryPath::TransactionTime => OntologyField::TransactionTime,
+            EntityTypeQueryPath::OwnedById => OntologyField::OwnedById,
+            EntityTypeQueryPath::OntologyId => OntologyField::OntologyId,
+            EntityTypeQueryPath::AdditionalMetadata => OntologyField::AdditionalMetadata,
+            EntityTypeQueryPath::EditionProvenance(path) => {
+                OntologyField::EditionProvenance(path.as_ref())
+            }
+            EntityTypeQueryPath::Embedding => OntologyField::Embedding,
+            EntityTypeQueryPath::Schema(path) => OntologyField::Schema(path.as_ref()),
+            EntityTypeQueryPath::Title => OntologyField::SchemaText("title"),
+            EntityTypeQueryPath::Description => OntologyField::SchemaText("description"),
+            EntityTypeQueryPath::Required => OntologyField::SchemaText("required"),
+            EntityTypeQueryPath::LabelProperty => OntologyField::SchemaText("labelProperty"),
+            EntityTypeQueryPath::Icon => OntologyField::SchemaText("icon"),
+            EntityTypeQueryPath::ClosedSchema(path) => {
+                return vec![Value::serialized(&*row.record.closed_schema, path.as_ref())];
+            }
+            EntityTypeQueryPath::PropertyTypeEdge {
+                edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                path,
+                inheritance_depth,
+            } => {
+                return property_type_values(
+                    context,
+                    context.tables.ontology_edges(
+                        row.ontology_id,
+                        OntologyReference::EntityTypeConstrainsPropertiesOn {
+                            inheritance_depth: *inheritance_depth,
+                        },
+                        EdgeDirection::Outgoing,
+                    ),
+                    path,
+                );
+            }
+            EntityTypeQueryPath::EntityTypeEdge {
+                edge_kind,
+                path,
+                direction,
+                inheritance_depth,
+            } => {
+                let reference = match edge_kind {
+                    OntologyEdgeKind::InheritsFrom => OntologyReference::EntityTypeInheritsFrom {
+                        inheritance_depth: *inheritance_depth,
+                    },
+                    OntologyEdgeKind::ConstrainsLinksOn => {
+                        OntologyReference::EntityTypeConstrainsLinksOn {
+                            inheritance_depth: *inheritance_depth,
+                        }
+                    }
+                    OntologyEdgeKind::ConstrainsLinkDestinationsOn => {
+                        OntologyReference::EntityTypeConstrainsLinkDestinationsOn {
+                            inheritance_depth: *inheritance_depth,
+                        }
+                    }
+                    OntologyEdgeKind::ConstrainsValuesOn
+                    | OntologyEdgeKind::ConstrainsPropertiesOn => return Vec::new(),
+                };
+
+                return entity_type_values(
+                    context,
+                    context
+                        .tables
+                        .ontology_edges(row.ontology_id, reference, *direction),
+                    path,
+                );
+            }
+            EntityTypeQueryPath::EntityEdge {
+                edge_kind: SharedEdgeKind::IsOfType,
+                path,
+                inheritance_depth,
+            } => {
+                return entities_of_type_values(
+                    context,
+                    EntityTypeUuid::from(row.ontology_id),
+                    *inheritance_depth,
+                    path,
+                );
+            }
+            EntityTypeQueryPath::PropertyTypeEdge { .. } => return Vec::new(),
+        };
+
+        ontology_values(context, row, &field)
+    }
+
+    fn materialize<'t>(_: &Context<'t>, row: Self::Row<'t>) -> Self {
+        Self {
+            schema: (*row.record.schema).clone(),
+            metadata: EntityTypeMetadata {
+                record_id: OntologyTypeRecordId::from(row.metadata.url.clone()),
+                classification: row.metadata.classification.clone(),
+                temporal_versioning: OntologyTemporalMetadata {
+                    transaction_time: row.temporal_metadata.transaction_time,
+                },
+                provenance: OntologyProvenance {
+                    edition: row.temporal_metadata.provenance.clone(),
+                },
+            },
+        }
+    }
+
+    fn clone_record(&self) -> Self {
+        Self {
+            schema: self.schema.clone(),
+            metadata: self.metadata.clone(),
+        }
+    }
+}
+
+impl MemoryTables {
+    /// Adds the entity types referenced by `entity_type_ids` to `ontology_type_resolver`.
+    ///
+    /// Only entity types, which are not archived, are added.
+    fn add_parent_entity_types(
+        &self,
+        entity_type_ids: impl IntoIterator<Item = EntityTypeUuid>,
+        ontology_type_resolver: &mut OntologyTypeResolver,
+    ) {
+        let now = Timestamp::now();
+        for entity_type_id in entity_type_ids {
+            if self.ontology_row_at(entity_type_id.into(), now).is_none() {
+                continue;
+            }
+            if let Some(parent) = self.entity_types.get(&entity_type_id) {
+                ontology_type_resolver.add_closed_entity_type(
+                    entity_type_id,
+                    Arc::clone(&parent.schema),
+                    Arc::clone(&parent.resolve_data),
+                );
+            }
+        }
+    }
+
+    /// Ensures that all types referenced by the resolved entity type exist.
+    ///
+    /// The Postgres store enforces this through foreign keys on the reference tables.
+    fn check_entity_type_references(
+        &self,
+        resolve_data: &EntityTypeResolveData,
+    ) -> Result<(), InsertionError> {
+        let missing = resolve_data
+            .properties()
+            .map(|(property_type_id, _)| OntologyTypeUuid::from(property_type_id))
+            .chain(
+                resolve_data
+                    .inheritance_depths()
+                    .chain(resolve_data.links())
+                    .chain(resolve_data.link_destinations())
+                    .map(|(entity_type_id, _)| OntologyTypeUuid::from(entity_type_id)),
+            )
+            .filter(|ontology_id| !self.ontology_ids.contains_key(ontology_id))
+            .collect::<Vec<_>>();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            let mut error = Report::new(InsertionError);
+            for ontology_id in missing {
+                error = error.attach_printable(ontology_id.into_uuid());
+            }
+            Err(error)
+        }
+    }
+}
+
+impl<A> MemoryStore<A>
+where
+    A: AuthorizationApi,
+{
+    #[tracing::instrument(level = "trace", skip(entity_types, authorization_api, zookie))]
+    pub(crate) async fn filter_entity_types_by_permission<I, T>(
+        entity_types: impl IntoIterator<Item = (I, T)> + Send,
+        actor_id: AccountId,
+        authorization_api: &A,
+        zookie: &Zookie<'static>,
+    ) -> Result<impl Iterator<Item = T>, QueryError>
+    where
+        I: Into<EntityTypeUuid> + Send,
+        T: Send,
+    {
+        let (ids, entity_types): (Vec<_>, Vec<_>) = entity_types
+            .into_iter()
+            .map(|(id, edge)| (id.into(), edge))
+            .unzip();
+
+        let permissions = authorization_api
+            .check_entity_types_permission(
+                actor_id,
+                EntityTypePermission::View,
+                ids.iter().copied(),
+                Consistency::AtExactSnapshot(zookie),
+            )
+            .await
+            .change_context(QueryError)?
+            .0;
+
+        Ok(ids
+            .into_iter()
+            .zip(entity_types)
+            .filter_map(move |(id, entity_type)| {
+                permissions
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(false)
+                    .then_some(entity_type)
+            }))
+    }
+
+    #[expect(clippy::too_many_lines)]
+    async fn get_entity_types_impl(
+        &self,
+        actor_id: AccountId,
+        params: GetEntityTypesParams<'_>,
+        temporal_axes: &QueryTemporalAxes,
+    ) -> Result<(GetEntityTypesResponse, Zookie<'static>), QueryError> {
+        let (count, web_ids, edition_created_by_ids) = if params.include_count
+            || params.include_web_ids
+            || params.include_edition_created_by_ids
+        {
+            let mut web_ids = params.include_web_ids.then(ResponseCountMap::default);
+            let mut edition_created_by_ids = params
+                .include_edition_created_by_ids
+                .then(ResponseCountMap::default);
+
+            let entity_type_ids = Read::<EntityTypeWithMetadata>::read(
+                self,
+                &params.filter,
+                Some(temporal_axes),
+                params.include_drafts,
+            )
+            .await?
+            .map_ok(|entity_type| {
+                if let (Some(web_ids), OntologyTypeClassificationMetadata::Owned { owned_by_id }) =
+                    (&mut web_ids, &entity_type.metadata.classification)
+                {
+                    web_ids.increment(owned_by_id);
+                }
+                if let Some(edition_created_by_ids) = &mut edition_created_by_ids {
+                    edition_created_by_ids
+                        .increment(&entity_type.metadata.provenance.edition.created_by_id);
+                }
+                EntityTypeUuid::from_url(&entity_type.schema.id)
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+
+            let (permissions, _zookie) = self
+                .authorization_api
+                .check_entity_types_permission(
+                    actor_id,
+                    EntityTypePermission::View,
+                    entity_type_ids.iter().copied(),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(QueryError)?;
+
+            let permitted_ids = permissions
+                .into_iter()
+                .filter_map(|(entity_type_id, has_permission)| {
+                    has_permission.then_some(entity_type_id)
+                })
+                .collect::<HashSet<_>>();
+
+            let count = entity_type_ids
+                .into_iter()
+                .filter(|id| permitted_ids.contains(id))
+                .count();
+            (
+                Some(count),
+                web_ids.map(HashMap::from),
+                edition_created_by_ids.map(HashMap::from),
+            )
+        } else {
+            (None, None, None)
+        };
+
+        let mut visited_ontology_ids = HashSet::new();
+
+        let (data, artifacts) =
+            ReadPaginated::<EntityTypeWithMetadata, VersionedUrlSorting>::read_paginated_vec(
+                self,
+                &params.filter,
+                Some(temporal_axes),
+                &VersionedUrlSorting {
+                    cursor: params.after,
+                },
+                params.limit,
+                params.include_drafts,
+            )
+            .await?;
+        let entity_types = data
+            .into_iter()
+            .filter_map(|row| {
+                let entity_type = row.decode_record(&artifacts);
+                let id = EntityTypeUuid::from_url(&entity_type.schema.id);
+                visited_ontology_ids.insert(id).then_some((id, entity_type))
+            })
+            .collect::<Vec<_>>();
+
+        let filtered_ids = entity_types
+            .iter()
+            .map(|(entity_type_id, _)| *entity_type_id)
+            .collect::<Vec<_>>();
+
+        let (permissions, zookie) = self
+            .authorization_api
+            .check_entity_types_permission(
+                actor_id,
+                EntityTypePermission::View,
+                filtered_ids,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?;
+
+        let entity_types = entity_types
+            .into_iter()
+            .filter_map(|(id, entity_type)| {
+                permissions
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(false)
+                    .then_some(entity_type)
+            })
+            .collect::<Vec<_>>();
+
+        Ok((
+            GetEntityTypesResponse {
+                cursor: if params.limit.is_some() {
+                    entity_types
+                        .last()
+                        .map(|entity_type| entity_type.schema.id.clone())
+                } else {
+                    None
+                },
+                entity_types,
+                count,
+                web_ids,
+                edition_created_by_ids,
+            },
+            zookie,
+        ))
+    }
+
+    /// Internal method to read a [`EntityTypeWithMetadata`] into a [`TraversalContext`].
+    ///
+    /// This is used to recursively resolve a type, so the result can be reused.
+    #[tracing::instrument(level = "info", skip(self, traversal_context, subgraph, zookie))]
+    pub(crate) async fn traverse_entity_types(
+        &self,
+        mut entity_type_queue: Vec<(
+            EntityTypeUuid,
+            GraphResolveDepths,
+            RightBoundedTemporalInterval<VariableAxis>,
+        )>,
+        traversal_context: &mut TraversalContext,
+        actor_id: AccountId,
+        zookie: &Zookie<'static>,
+        subgraph: &mut Subgraph,
+    ) -> Result<(), QueryError> {
+        let tables = self.snapshot();
+        let mut property_type_queue = Vec::new();
+
+        while !entity_type_queue.is_empty() {
+            let mut edges_to_traverse = HashMap::<OntologyEdgeKind, Vec<_>>::new();
+
+            #[expect(clippy::iter_with_drain, reason = "false positive, vector is reused")]
+            for (entity_type_ontology_id, graph_resolve_depths, traversal_interval) in
+                entity_type_queue.drain(..)
+            {
+                for edge_kind in [
+                    OntologyEdgeKind::ConstrainsPropertiesOn,
+                    OntologyEdgeKind::InheritsFrom,
+                    OntologyEdgeKind::ConstrainsLinksOn,
+                    OntologyEdgeKind::ConstrainsLinkDestinationsOn,
+                ] {
+                    if let Some(new_graph_resolve_depths) = graph_resolve_depths
+                        .decrement_depth_for_edge(edge_kind, EdgeDirection::Outgoing)
+                    {
+                        edges_to_traverse.entry(edge_kind).or_default().push((
+                            OntologyTypeUuid::from(entity_type_ontology_id),
+                            new_graph_resolve_depths,
+                            traversal_interval,
+                        ));
+                    }
+                }
+            }
+
+            if let Some(traversal_data) =
+                edges_to_traverse.get(&OntologyEdgeKind::ConstrainsPropertiesOn)
+            {
+                property_type_queue.extend(
+                    Self::filter_property_types_by_permission(
+                        tables
+                            .read_ontology_edges(
+                                traversal_data,
+                                OntologyReference::EntityTypeConstrainsPropertiesOn {
+                                    inheritance_depth: Some(0),
+                                },
+                            )
+                            .into_iter()
+                            .map(|(target_id, edge)| (PropertyTypeUuid::from(target_id), edge)),
+                        actor_id,
+                        &self.authorization_api,
+                        zookie,
+                    )
+                    .await?
+                    .flat_map(|edge| {
+                        subgraph.insert_edge(
+                            &EntityTypeVertexId::from(edge.left_endpoint),
+                            OntologyEdgeKind::ConstrainsPropertiesOn,
+                            EdgeDirection::Outgoing,
+                            PropertyTypeVertexId::from(edge.right_endpoint),
+                        );
+
+                        traversal_context.add_property_type_id(
+                            PropertyTypeUuid::from(edge.right_endpoint_ontology_id),
+                            edge.resolve_depths,
+                            edge.traversal_interval,
+                        )
+                    }),
+                );
+            }
+
+            for (edge_kind, reference) in [
+                (
+                    OntologyEdgeKind::InheritsFrom,
+                    OntologyReference::EntityTypeInheritsFrom {
+                        inheritance_depth: Some(0),
+                    },
+                ),
+                (
+                    OntologyEdgeKind::ConstrainsLinksOn,
+                    OntologyReference::EntityTypeConstrainsLinksOn {
+                        inheritance_depth: Some(0),
+                    },
+                ),
+                (
+                    OntologyEdgeKind::ConstrainsLinkDestinationsOn,
+                    OntologyReference::EntityTypeConstrainsLinkDestinationsOn {
+                        inheritance_depth: Some(0),
+                    },
+                ),
+            ] {
+                if let Some(traversal_data) = edges_to_traverse.get(&edge_kind) {
+                    entity_type_queue.extend(
+                        Self::filter_entity_types_by_permission(
+                            tables
+                                .read_ontology_edges(traversal_data, reference)
+                                .into_iter()
+                                .map(|(target_id, edge)| (EntityTypeUuid::from(target_id), edge)),
+                            actor_id,
+                            &self.authorization_api,
+                            zookie,
+                        )
+                        .await?
+                        .flat_map(|edge| {
+                            subgraph.insert_edge(
+                                &EntityTypeVertexId::from(edge.left_endpoint),
+                                edge_kind,
+                                EdgeDirection::Outgoing,
+                                EntityTypeVertexId::from(edge.right_endpoint),
+                            );
+
+                            traversal_context.add_entity_type_id(
+                                EntityTypeUuid::from(edge.right_endpoint_ontology_id),
+                                edge.resolve_depths,
+                                edge.traversal_interval,
+                            )
+                        }),
+                    );
+                }
+            }
+        }
+
+        self.traverse_property_types(
+            property_type_queue,
+            traversal_context,
+            actor_id,
+            zookie,
+            subgraph,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn delete_entity_types(&mut self) -> Result<(), DeletionError> {
+        let transaction = self.transaction().await.change_context(DeletionError)?;
+
+        transaction.write(|tables| {
+            let entity_types = tables
+                .entity_types
+                .drain()
+                .map(|(entity_type_id, _)| OntologyTypeUuid::from(entity_type_id))
+                .collect::<Vec<_>>();
+            tables.delete_ontology_ids(entity_types);
+        });
+
+        transaction.commit().await.change_context(DeletionError)?;
+
+        Ok(())
+    }
+}
+
+impl<A> EntityTypeStore for MemoryStore<A>
+where
+    A: AuthorizationApi,
+{
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn create_entity_types<P, R>(
+        &mut self,
+        actor_id: AccountId,
+        params: P,
+    ) -> Result<Vec<EntityTypeMetadata>, InsertionError>
+    where
+        P: IntoIterator<Item = CreateEntityTypeParams<R>, IntoIter: Send> + Send,
+        R: IntoIterator<Item = EntityTypeRelationAndSubject> + Send + Sync,
+    {
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        let mut relationships = HashSet::new();
+
+        let mut inserted_entity_type_metadata = Vec::new();
+        let mut inserted_entity_types = Vec::new();
+        let mut entity_type_reference_ids = HashSet::new();
+
+        for parameters in params {
+            let provenance = OntologyProvenance {
+                edition: OntologyEditionProvenance {
+                    created_by_id: EditionCreatedById::new(actor_id),
+                    archived_by_id: None,
+                    user_defined: parameters.provenance,
+                },
+            };
+
+            let record_id = OntologyTypeRecordId::from(parameters.schema.id.clone());
+            let entity_type_id = EntityTypeUuid::from_url(&parameters.schema.id);
+
+            if let OntologyTypeClassificationMetadata::Owned { owned_by_id } =
+                &parameters.classification
+            {
+                transaction
+                    .authorization_api
+                    .check_web_permission(
+                        actor_id,
+                        WebPermission::CreateEntityType,
+                        *owned_by_id,
+                        Consistency::FullyConsistent,
+                    )
+                    .await
+                    .change_context(InsertionError)?
+                    .assert_permission()
+                    .change_context(InsertionError)?;
+
+                relationships.insert((entity_type_id, EntityTypeRelationAndSubject::Owner {
+                    subject: EntityTypeOwnerSubject::Web { id: *owned_by_id },
+                    level: 0,
+                }));
+            }
+
+            relationships.extend(
+                parameters
+                    .relationships
+                    .into_iter()
+                    .map(|relation_and_subject| (entity_type_id, relation_and_subject)),
+            );
+
+            if let Some((_ontology_id, temporal_versioning)) = transaction.write(|tables| {
+                tables.create_ontology_metadata(
+                    &parameters.schema.id,
+                    &parameters.classification,
+                    parameters.conflict_behavior,
+                    &provenance,
+                )
+            })? {
+                entity_type_reference_ids.extend(
+                    parameters
+                        .schema
+                        .entity_type_references()
+                        .map(|(reference, _)| EntityTypeUuid::from_url(&reference.url)),
+                );
+                inserted_entity_types.push((entity_type_id, Arc::new(parameters.schema)));
+                inserted_entity_type_metadata.push(EntityTypeMetadata {
+                    record_id,
+                    classification: parameters.classification,
+                    temporal_versioning,
+                    provenance,
+                });
+            }
+        }
+
+        let mut ontology_type_resolver = OntologyTypeResolver::default();
+
+        for (entity_type_id, inserted_entity_type) in &inserted_entity_types {
+            ontology_type_resolver
+                .add_unresolved_entity_type(*entity_type_id, Arc::clone(inserted_entity_type));
+        }
+
+        transaction
+            .snapshot()
+            .add_parent_entity_types(entity_type_reference_ids, &mut ontology_type_resolver);
+
+        let closed_schemas = inserted_entity_types
+            .iter()
+            .map(|(entity_type_id, entity_type)| {
+                let closed_metadata = ontology_type_resolver
+                    .resolve_entity_type_metadata(*entity_type_id)
+                    .change_context(InsertionError)?;
+                let closed_schema =
+                    ClosedEntityType::from_resolve_data((**entity_type).clone(), &closed_metadata)
+                        .change_context(InsertionError)?;
+
+                Ok((closed_schema, closed_metadata))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let entity_type_validator = EntityTypeValidator;
+        for ((entity_type_id, entity_type), (closed_schema, resolve_data)) in
+            inserted_entity_types.iter().zip(closed_schemas)
+        {
+            entity_type_validator
+                .validate_ref(&**entity_type)
+                .await
+                .change_context(InsertionError)?;
+            entity_type_validator
+                .validate_ref(&closed_schema)
+                .await
+                .change_context(InsertionError)?;
+
+            transaction.write(|tables| {
+                tables.entity_types.insert(*entity_type_id, EntityTypeRow {
+                    schema: Arc::clone(entity_type),
+                    closed_schema: Arc::new(closed_schema),
+                    resolve_data,
+                    embedding: None,
+                });
+            });
+        }
+
+        let tables = transaction.snapshot();
+        for (entity_type_id, _) in &inserted_entity_types {
+            if let Some(row) = tables.entity_types.get(entity_type_id) {
+                tables.check_entity_type_references(&row.resolve_data)?;
+            }
+        }
+
+        transaction
+            .authorization_api
+            .modify_entity_type_relations(relationships.clone().into_iter().map(
+                |(resource, relation_and_subject)| {
+                    (
+                        ModifyRelationshipOperation::Create,
+                        resource,
+                        relation_and_subject,
+                    )
+                },
+            ))
+            .await
+            .change_context(InsertionError)?;
+
+        if let Err(error) = transaction.commit().await.change_context(InsertionError) {
+            let mut error = error.expand();
+
+            if let Err(auth_error) = self
+                .authorization_api
+                .modify_entity_type_relations(relationships.into_iter().map(
+                    |(resource, relation_and_subject)| {
+                        (
+                            ModifyRelationshipOperation::Delete,
+                            resource,
+                            relation_and_subject,
+                        )
+                    },
+                ))
+                .await
+                .change_context(InsertionError)
+            {
+                error.push(auth_error);
+            }
+
+            Err(error.change_context(InsertionError))
+        } else {
+            if let Some(temporal_client) = &self.temporal_client {
+                temporal_client
+                    .start_update_entity_type_embeddings_workflow(
+                        actor_id,
+                        &inserted_entity_types
+                            .iter()
+                            .zip(&inserted_entity_type_metadata)
+                            .map(|((_, schema), metadata)| EntityTypeWithMetadata {
+                                schema: (**schema).clone(),
+                                metadata: metadata.clone(),
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+            }
+
+            Ok(inserted_entity_type_metadata)
+        }
+    }
+
+    async fn count_entity_types(
+        &self,
+        actor_id: AccountId,
+        mut params: CountEntityTypesParams<'_>,
+    ) -> Result<usize, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        Ok(self
+            .read(
+                &params.filter,
+                Some(&params.temporal_axes.resolve()),
+                params.include_drafts,
+            )
+            .await?
+            .count()
+            .await)
+    }
+
+    async fn get_entity_types(
+        &self,
+        actor_id: AccountId,
+        mut params: GetEntityTypesParams<'_>,
+    ) -> Result<GetEntityTypesResponse, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        let temporal_axes = params.temporal_axes.clone().resolve();
+        self.get_entity_types_impl(actor_id, params, &temporal_axes)
+            .await
+            .map(|(response, _)| response)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_entity_type_subgraph(
+        &self,
+        actor_id: AccountId,
+        mut params: GetEntityTypeSubgraphParams<'_>,
+    ) -> Result<GetEntityTypeSubgraphResponse, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        let temporal_axes = params.temporal_axes.clone().resolve();
+        let time_axis = temporal_axes.variable_time_axis();
+
+        let (
+            GetEntityTypesResponse {
+                entity_types,
+                cursor,
+                count,
+                web_ids,
+                edition_created_by_ids,
+            },
+            zookie,
+        ) = self
+            .get_entity_types_impl(
+                actor_id,
+                GetEntityTypesParams {
+                    filter: params.filter,
+                    temporal_axes: params.temporal_axes.clone(),
+                    after: params.after,
+                    limit: params.limit,
+                    include_drafts: params.include_drafts,
+                    include_count: params.include_count,
+                    include_web_ids: params.include_web_ids,
+                    include_edition_created_by_ids: params.include_edition_created_by_ids,
+                },
+                &temporal_axes,
+            )
+            .await?;
+
+        let mut subgraph = Subgraph::new(
+            params.graph_resolve_depths,
+            params.temporal_axes,
+            temporal_axes.clone(),
+        );
+
+        let (entity_type_ids, entity_type_vertex_ids): (Vec<_>, Vec<_>) = entity_types
+            .iter()
+            .map(|entity_type| {
+                (
+                    EntityTypeUuid::from_url(&entity_type.schema.id),
+                    GraphElementVertexId::from(entity_type.vertex_id(time_axis)),
+                )
+            })
+            .unzip();
+        subgraph.roots.extend(entity_type_vertex_ids);
+        subgraph.vertices.entity_types = entity_types
+            .into_iter()
+            .map(|entity_type| (entity_type.vertex_id(time_axis), entity_type))
+            .collect();
+
+        let mut traversal_context = TraversalContext::default();
+
+        self.traverse_entity_types(
+            entity_type_ids
+                .into_iter()
+                .map(|id| {
+                    (
+                        id,
+                        subgraph.depths,
+                        subgraph.temporal_axes.resolved.variable_interval(),
+                    )
+                })
+                .collect(),
+            &mut traversal_context,
+            actor_id,
+            &zookie,
+            &mut subgraph,
+        )
+        .await?;
+
+        traversal_context
+            .read_traversed_vertices(self, &mut subgraph, params.include_drafts)
+            .await?;
+
+        Ok(GetEntityTypeSubgraphResponse {
+            subgraph,
+            cursor,
+            count,
+            web_ids,
+            edition_created_by_ids,
+        })
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn update_entity_type<R>(
+        &mut self,
+        actor_id: AccountId,
+        params: UpdateEntityTypesParams<R>,
+    ) -> Result<EntityTypeMetadata, UpdateError>
+    where
+        R: IntoIterator<Item = EntityTypeRelationAndSubject> + Send + Sync,
+    {
+        let entity_type_validator = EntityTypeValidator;
+
+        let old_ontology_id = EntityTypeUuid::from_url(&VersionedUrl {
+            base_url: params.schema.id.base_url.clone(),
+            version: OntologyTypeVersion::new(
+                params
+                    .schema
+                    .id
+                    .version
+                    .inner()
+                    .checked_sub(1)
+                    .ok_or(UpdateError)
+                    .attach_printable(
+                        "The version of the data type is already at the lowest possible value",
+                    )?,
+            ),
+        });
+        let new_ontology_id = EntityTypeUuid::from_url(&params.schema.id);
+        self.authorization_api
+            .check_entity_type_permission(
+                actor_id,
+                EntityTypePermission::Update,
+                old_ontology_id,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let provenance = OntologyProvenance {
+            edition: OntologyEditionProvenance {
+                created_by_id: EditionCreatedById::new(actor_id),
+                archived_by_id: None,
+                user_defined: params.provenance,
+            },
+        };
+
+        let schema = entity_type_validator
+            .validate(params.schema)
+            .await
+            .change_context(UpdateError)?;
+
+        let mut ontology_type_resolver = OntologyTypeResolver::default();
+        transaction.snapshot().add_parent_entity_types(
+            schema
+                .entity_type_references()
+                .map(|(reference, _)| EntityTypeUuid::from_url(&reference.url)),
+            &mut ontology_type_resolver,
+        );
+
+        let schema = Arc::new(schema.into_inner());
+        ontology_type_resolver.add_unresolved_entity_type(new_ontology_id, Arc::clone(&schema));
+        let resolve_data = ontology_type_resolver
+            .resolve_entity_type_metadata(new_ontology_id)
+            .change_context(UpdateError)?;
+
+        let closed_schema = entity_type_validator
+            .validate(
+                ClosedEntityType::from_resolve_data((*schema).clone(), &resolve_data)
+                    .change_context(UpdateError)?,
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        let (_ontology_id, owned_by_id, temporal_versioning) = transaction.write(|tables| {
+            let updated = tables.update_owned_ontology_id(&schema.id, &provenance.edition)?;
+            tables
+                .check_entity_type_references(&resolve_data)
+                .change_context(UpdateError)?;
+            tables.entity_types.insert(new_ontology_id, EntityTypeRow {
+                schema: Arc::clone(&schema),
+                closed_schema: Arc::new(closed_schema.into_inner()),
+                resolve_data,
+                embedding: None,
+            });
+            Ok::<_, Report<UpdateError>>(updated)
+        })?;
+
+        let relationships = params
+            .relationships
+            .into_iter()
+            .chain(once(EntityTypeRelationAndSubject::Owner {
+                subject: EntityTypeOwnerSubject::Web { id: owned_by_id },
+                level: 0,
+            }))
+            .collect::<Vec<_>>();
+
+        transaction
+            .authorization_api
+            .modify_entity_type_relations(relationships.clone().into_iter().map(
+                |relation_and_subject| {
+                    (
+                        ModifyRelationshipOperation::Create,
+                        new_ontology_id,
+                        relation_and_subject,
+                    )
+                },
+            ))
+            .await
+            .change_context(UpdateError)?;
+
+        if let Err(error) = transaction.commit().await.change_context(UpdateError) {
+            let mut error = error.expand();
+
+            if let Err(auth_error) = self
+                .authorization_api
+                .modify_entity_type_relations(relationships.into_iter().map(
+                    |relation_and_subject| {
+                        (
+                            ModifyRelationshipOperation::Delete,
+                            new_ontology_id,
+                            relation_and_subject,
+                        )
+                    },
+                ))
+                .await
+                .change_context(UpdateError)
+            {
+                error.push(auth_error);
+            }
+
+            Err(error.change_context(UpdateError))
+        } else {
+            let metadata = EntityTypeMetadata {
+                record_id: OntologyTypeRecordId::from(schema.id.clone()),
+                classification: OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                temporal_versioning,
+                provenance,
+            };
+
+            if let Some(temporal_client) = &self.temporal_client {
+                temporal_client
+                    .start_update_entity_type_embeddings_workflow(actor_id, &[
+                        EntityTypeWithMetadata {
+                            schema: (*schema).clone(),
+                            metadata: metadata.clone(),
+                        },
+                    ])
+                    .await
+                    .change_context(UpdateError)?;
+            }
+
+            Ok(metadata)
+        }
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn archive_entity_type(
+        &mut self,
+        actor_id: AccountId,
+        params: ArchiveEntityTypeParams<'_>,
+    ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        let temporal_metadata = transaction.write(|tables| {
+            tables.archive_ontology_type(&params.entity_type_id, EditionArchivedById::new(actor_id))
+        })?;
+        transaction.commit().await.change_context(UpdateError)?;
+        Ok(temporal_metadata)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn unarchive_entity_type(
+        &mut self,
+        actor_id: AccountId,
+        params: UnarchiveEntityTypeParams<'_>,
+    ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        let temporal_metadata = transaction.write(|tables| {
+            tables.unarchive_ontology_type(&params.entity_type_id, &OntologyEditionProvenance {
+                created_by_id: EditionCreatedById::new(actor_id),
+                archived_by_id: None,
+                user_defined: params.provenance,
+            })
+        })?;
+        transaction.commit().await.change_context(UpdateError)?;
+        Ok(temporal_metadata)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn update_entity_type_embeddings(
+        &mut self,
+        _: AccountId,
+        params: UpdateEntityTypeEmbeddingParams<'_>,
+    ) -> Result<(), UpdateError> {
+        // TODO: Add permission to allow updating embeddings
+        //   see https://linear.app/hash/issue/H-1870
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        transaction.write(|tables| {
+            tables.update_ontology_embedding(
+                &params.entity_type_id,
+                OntologyEmbeddingRow {
+                    embedding: params.embedding.into_owned(),
+                    updated_at_transaction_time: params.updated_at_transaction_time,
+                },
+                |tables, ontology_id| {
+                    tables
+                        .entity_types
+                        .get_mut(&EntityTypeUuid::from(ontology_id))
+                        .map(|row| &mut row.embedding)
+                },
+            );
+        });
+        transaction.commit().await.change_context(UpdateError)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn reindex_entity_type_cache(&mut self) -> Result<(), UpdateError> {
+        tracing::info!("Reindexing entity type cache");
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let mut ontology_type_resolver = OntologyTypeResolver::default();
+        let entity_types = transaction
+            .snapshot()
+            .entity_types
+            .iter()
+            .map(|(entity_type_id, row)| {
+                ontology_type_resolver
+                    .add_unresolved_entity_type(*entity_type_id, Arc::clone(&row.schema));
+                (*entity_type_id, Arc::clone(&row.schema))
+            })
+            .collect::<Vec<_>>();
+
+        for (entity_type_id, schema) in entity_types {
+            let resolve_data = ontology_type_resolver
+                .resolve_entity_type_metadata(entity_type_id)
+                .change_context(UpdateError)?;
+            let closed_schema =
+                ClosedEntityType::from_resolve_data((*schema).clone(), &resolve_data)
+                    .change_context(UpdateError)?;
+
+            transaction.write(|tables| {
+                if let Some(row) = tables.entity_types.get_mut(&entity_type_id) {
+                    row.resolve_data = resolve_data;
+                    row.closed_schema = Arc::new(closed_schema);
+                }
+            });
+        }
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,855 @@
+mod data_type;
+mod entity_type;
+mod property_type;
+
+use core::cmp::Ordering;
+
+use error_stack::{Report, Result, ResultExt};
+use graph_types::{
+    account::EditionArchivedById,
+    ontology::{
+        DataTypeWithMetadata, EntityTypeWithMetadata, OntologyEditionProvenance,
+        OntologyProvenance, OntologyTemporalMetadata, OntologyTypeClassificationMetadata,
+        PropertyTypeWithMetadata,
+    },
+    owned_by_id::OwnedById,
+};
+use hash_graph_store::{
+    ConflictBehavior,
+    subgraph::{
+        edges::{EdgeDirection, GraphResolveDepths},
+        temporal_axes::VariableAxis,
+    },
+};
+use temporal_versioning::{
+    ClosedTemporalBound, LeftClosedTemporalInterval, OpenTemporalBound,
+    RightBoundedTemporalInterval, Timestamp, TransactionTime,
+};
+use type_system::{
+    schema::{DataTypeUuid, EntityTypeUuid, InheritanceDepth, OntologyTypeUuid, PropertyTypeUuid},
+    url::{BaseUrl, OntologyTypeVersion, VersionedUrl},
+};
+
+pub(crate) use self::entity_type::entity_type_values;
+use crate::store::{
+    BaseUrlAlreadyExists, InsertionError, UpdateError,
+    crud::VersionedUrlSorting,
+    error::{OntologyTypeIsNotOwned, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
+    memory::{
+        crud::MemorySorting,
+        filter::{Context, MemoryRecord, OntologyRow, ontology_rows},
+        table::{MemoryTables, OntologyEmbeddingRow, OntologyIdRow, OntologyTemporalMetadataRow},
+    },
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum OntologyLocation {
+    Owned,
+    External,
+}
+
+impl OntologyLocation {
+    const fn matches(self, classification: &OntologyTypeClassificationMetadata) -> bool {
+        matches!(
+            (self, classification),
+            (
+                Self::Owned,
+                OntologyTypeClassificationMetadata::Owned { .. }
+            ) | (
+                Self::External,
+                OntologyTypeClassificationMetadata::External { .. }
+            )
+        )
+    }
+}
+
+impl MemoryTables {
+    /// Returns the metadata of the ontology type if it's not archived at `transaction_time`.
+    pub fn ontology_row_at(
+        &self,
+        ontology_id: OntologyTypeUuid,
+        transaction_time: Timestamp<TransactionTime>,
+    ) -> Option<&OntologyIdRow> {
+        self.ontology_ids.get(&ontology_id).filter(|row| {
+            row.temporal_metadata
+                .iter()
+                .any(|temporal| temporal.transaction_time.contains_point(&transaction_time))
+        })
+    }
+
+    /// Returns `true` if `url` is the latest version of its [`BaseUrl`].
+    pub fn is_latest_version(&self, url: &VersionedUrl) -> bool {
+        self.base_urls.get(&url.base_url) == Some(&url.version)
+    }
+
+    fn create_base_url(
+        &self,
+        base_url: &BaseUrl,
+        on_conflict: ConflictBehavior,
+        location: OntologyLocation,
+    ) -> Result<(), InsertionError> {
+        if !self.base_urls.contains_key(base_url) {
+            return Ok(());
+        }
+
+        match on_conflict {
+            ConflictBehavior::Fail => Err(Report::new(BaseUrlAlreadyExists)
+                .attach_printable(base_url.clone())
+                .change_context(InsertionError)),
+            ConflictBehavior::Skip => {
+                let exists_in_specified_location = self.ontology_ids.values().any(|row| {
+                    row.url.base_url == *base_url && location.matches(&row.classification)
+                });
+
+                if exists_in_specified_location {
+                    Ok(())
+                } else {
+                    Err(Report::new(BaseUrlAlreadyExists)
+                        .attach_printable(base_url.clone())
+                        .change_context(InsertionError))
+                }
+            }
+        }
+    }
+
+    fn create_ontology_id(
+        &mut self,
+        url: &VersionedUrl,
+        classification: &OntologyTypeClassificationMetadata,
+        on_conflict: ConflictBehavior,
+    ) -> Result<Option<OntologyTypeUuid>, InsertionError> {
+        let ontology_id = OntologyTypeUuid::from_url(url);
+        if self.ontology_ids.contains_key(&ontology_id) {
+            return match on_conflict {
+                ConflictBehavior::Skip => Ok(None),
+                ConflictBehavior::Fail => Err(Report::new(VersionedUrlAlreadyExists)
+                    .attach_printable(url.clone())
+                    .change_context(InsertionError)),
+            };
+        }
+
+        self.ontology_ids.insert(ontology_id, OntologyIdRow {
+            url: url.clone(),
+            classification: classification.clone(),
+            temporal_metadata: Vec::new(),
+        });
+        let latest_version = self
+            .base_urls
+            .entry(url.base_url.clone())
+            .or_insert(url.version);
+        if url.version > *latest_version {
+            *latest_version = url.version;
+        }
+
+        Ok(Some(ontology_id))
+    }
+
+    fn create_ontology_temporal_metadata(
+        &mut self,
+        ontology_id: OntologyTypeUuid,
+        provenance: &OntologyEditionProvenance,
+    ) -> OntologyTemporalMetadata {
+        let transaction_time = LeftClosedTemporalInterval::new(
+            ClosedTemporalBound::Inclusive(self.transaction_time()),
+            OpenTemporalBound::Unbounded,
+        );
+        self.ontology_ids
+            .get_mut(&ontology_id)
+            .expect("ontology id should have been created")
+            .temporal_metadata
+            .push(OntologyTemporalMetadataRow {
+                transaction_time,
+                provenance: provenance.clone(),
+            });
+
+        OntologyTemporalMetadata { transaction_time }
+    }
+
+    /// Inserts the metadata for an ontology type.
+    ///
+    /// Returns `None` if the ontology type already exists and `on_conflict` is
+    /// [`ConflictBehavior::Skip`].
+    ///
+    /// # Errors
+    ///
+    /// - [`BaseUrlAlreadyExists`] if the [`BaseUrl`] already exists and `on_conflict` is
+    ///   [`ConflictBehavior::Fail`]
+    /// - [`VersionedUrlAlreadyExists`] if the [`VersionedUrl`] already exists and `on_conflict` is
+    ///   [`ConflictBehavior::Fail`]
+    pub fn create_ontology_metadata(
+        &mut self,
+        url: &VersionedUrl,
+        classification: &OntologyTypeClassificationMetadata,
+        on_conflict: ConflictBehavior,
+        provenance: &OntologyProvenance,
+    ) -> Result<Option<(OntologyTypeUuid, OntologyTemporalMetadata)>, InsertionError> {
+        match classification {
+            OntologyTypeClassificationMetadata::Owned { .. } => {
+                self.create_base_url(&url.base_url, on_conflict, OntologyLocation::Owned)?;
+            }
+            OntologyTypeClassificationMetadata::External { .. } => {
+                self.create_base_url(
+                    &url.base_url,
+                    ConflictBehavior::Skip,
+                    OntologyLocation::External,
+                )?;
+            }
+        }
+
+        Ok(self
+            .create_ontology_id(url, classification, on_conflict)?
+            .map(|ontology_id| {
+                (
+                    ontology_id,
+                    self.create_ontology_temporal_metadata(ontology_id, &provenance.edition),
+                )
+            }))
+    }
+
+    /// Creates a new version of an owned ontology type.
+    ///
+    /// # Errors
+    ///
+    /// - [`VersionedUrlAlreadyExists`] if [`VersionedUrl`] does already exist
+    /// - [`OntologyVersionDoesNotExist`] if the previous version does not exist
+    /// - [`OntologyTypeIsNotOwned`] if ontology type is an external ontology type
+    pub fn update_owned_ontology_id(
+        &mut self,
+        url: &VersionedUrl,
+        provenance: &OntologyEditionProvenance,
+    ) -> Result<(OntologyTypeUuid, OwnedById, OntologyTemporalMetadata), UpdateError> {
+        let previous_version = OntologyTypeVersion::new(
+            url.version
+                .inner()
+                .checked_sub(1)
+                .ok_or(UpdateError)
+                .attach_printable(
+                    "The version of the data type is already at the lowest possible value",
+                )?,
+        );
+
+        let owned_by_id = match self
+            .ontology_ids
+            .get(&OntologyTypeUuid::from_url(&VersionedUrl {
+                base_url: url.base_url.clone(),
+                version: previous_version,
+            }))
+            .map(|row| &row.classification)
+        {
+            Some(OntologyTypeClassificationMetadata::Owned { owned_by_id }) => *owned_by_id,
+            Some(OntologyTypeClassificationMetadata::External { .. }) => {
+                return Err(Report::new(OntologyTypeIsNotOwned)
+                    .attach_printable(url.clone())
+                    .change_context(UpdateError));
+            }
+            None => {
+                return Err(Report::new(OntologyVersionDoesNotExist)
+                    .attach_printable(url.clone())
+                    .change_context(UpdateError));
+            }
+        };
+
+        let ontology_id = self
+            .create_ontology_id(
+                url,
+                &OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                ConflictBehavior::Fail,
+            )
+            .change_context(UpdateError)?
+            .expect("ontology id should have been created");
+        let temporal_versioning = self.create_ontology_temporal_metadata(ontology_id, provenance);
+
+        Ok((ontology_id, owned_by_id, temporal_versioning))
+    }
+
+    /// Archives the ontology type identified by `id`.
+    ///
+    /// # Errors
+    ///
+    /// - [`VersionedUrlAlreadyExists`] if the ontology type is already archived
+    /// - [`OntologyVersionDoesNotExist`] if the ontology type does not exist
+    pub fn archive_ontology_type(
+        &mut self,
+        id: &VersionedUrl,
+        archived_by_id: EditionArchivedById,
+    ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        let now = self.transaction_time();
+        let Some(row) = self.ontology_ids.get_mut(&OntologyTypeUuid::from_url(id)) else {
+            return Err(Report::new(OntologyVersionDoesNotExist)
+                .attach_printable(id.clone())
+                .change_context(UpdateError));
+        };
+
+        let Some(temporal_metadata) = row
+            .temporal_metadata
+            .iter_mut()
+            .find(|temporal_metadata| temporal_metadata.transaction_time.contains_point(&now))
+        else {
+            return Err(Report::new(VersionedUrlAlreadyExists)
+                .attach_printable(id.clone())
+                .change_context(UpdateError));
+        };
+
+        temporal_metadata.transaction_time = LeftClosedTemporalInterval::new(
+            *temporal_metadata.transaction_time.start(),
+            OpenTemporalBound::Exclusive(now),
+        );
+        temporal_metadata.provenance.archived_by_id = Some(archived_by_id);
+
+        Ok(OntologyTemporalMetadata {
+            transaction_time: temporal_metadata.transaction_time,
+        })
+    }
+
+    /// Unarchives the ontology type identified by `id`.
+    ///
+    /// # Errors
+    ///
+    /// - [`VersionedUrlAlreadyExists`] if the ontology type is not archived
+    /// - [`OntologyVersionDoesNotExist`] if the ontology type does not exist
+    pub fn unarchive_ontology_type(
+        &mut self,
+        id: &VersionedUrl,
+        provenance: &OntologyEditionProvenance,
+    ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        let now = self.transaction_time();
+        let Some(row) = self.ontology_ids.get_mut(&OntologyTypeUuid::from_url(id)) else {
+            return Err(Report::new(OntologyVersionDoesNotExist)
+                .attach_printable(id.clone())
+                .change_context(UpdateError));
+        };
+
+        let transaction_time = LeftClosedTemporalInterval::new(
+            ClosedTemporalBound::Inclusive(now),
+            OpenTemporalBound::Unbounded,
+        );
+        if row.temporal_metadata.iter().any(|temporal_metadata| {
+            temporal_metadata
+                .transaction_time
+                .overlaps(&transaction_time)
+        }) {
+            return Err(Report::new(VersionedUrlAlreadyExists)
+                .attach_printable(id.clone())
+                .change_context(UpdateError));
+        }
+
+        row.temporal_metadata.push(OntologyTemporalMetadataRow {
+            transaction_time,
+            provenance: provenance.clone(),
+        });
+
+        Ok(OntologyTemporalMetadata { transaction_time })
+    }
+
+    /// Removes the ontology metadata of the provided ontology types.
+    ///
+    /// The [`BaseUrl`]s of the removed ontology types are removed as well.
+    pub fn delete_ontology_ids(
+        &mut self,
+        ontology_ids: impl IntoIterator<Item = OntologyTypeUuid>,
+    ) {
+        for ontology_id in ontology_ids {
+            if let Some(row) = self.ontology_ids.remove(&ontology_id) {
+                self.base_urls.remove(&row.url.base_url);
+            }
+        }
+    }
+}
+
+/// A reference between two ontology types.
+///
+/// The variants correspond to the reference tables of the Postgres store.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum OntologyReference {
+    DataTypeInheritsFrom { inheritance_depth: Option<u32> },
+    DataTypeConstrainsValuesOn,
+    PropertyTypeConstrainsValuesOn,
+    PropertyTypeConstrainsPropertiesOn,
+    EntityTypeInheritsFrom { inheritance_depth: Option<u32> },
+    EntityTypeConstrainsPropertiesOn { inheritance_depth: Option<u32> },
+    EntityTypeConstrainsLinksOn { inheritance_depth: Option<u32> },
+    EntityTypeConstrainsLinkDestinationsOn { inheritance_depth: Option<u32> },
+}
+
+fn within_depth<T: Into<OntologyTypeUuid>>(
+    max_depth: Option<u32>,
+) -> impl Fn((T, InheritanceDepth)) -> Option<OntologyTypeUuid> {
+    move |(target_id, depth)| {
+        max_depth
+            .is_none_or(|max_depth| u32::from(depth.inner()) <= max_depth)
+            .then(|| target_id.into())
+    }
+}
+
+/// An edge between two ontology types which was followed while traversing the graph.
+pub(crate) struct OntologyEdgeTraversal {
+    pub left_endpoint: VersionedUrl,
+    pub right_endpoint: VersionedUrl,
+    pub right_endpoint_ontology_id: OntologyTypeUuid,
+    pub resolve_depths: GraphResolveDepths,
+    pub traversal_interval: RightBoundedTemporalInterval<VariableAxis>,
+}
+
+impl MemoryTables {
+    /// Returns the ontology types `source` refers to by `reference`.
+    pub fn ontology_references(
+        &self,
+        source: OntologyTypeUuid,
+        reference: OntologyReference,
+    ) -> Vec<OntologyTypeUuid> {
+        match reference {
+            OntologyReference::DataTypeInheritsFrom { inheritance_depth } => self
+                .data_types
+                .get(&DataTypeUuid::from(source))
+                .map(|row| {
+                    row.resolve_data
+                        .inheritance_depths()
+                        .filter_map(within_depth(inheritance_depth))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            OntologyReference::DataTypeConstrainsValuesOn => Vec::new(),
+            OntologyReference::PropertyTypeConstrainsValuesOn => self
+                .property_types
+                .get(&PropertyTypeUuid::from(source))
+                .map(|row| {
+                    row.schema
+                        .data_type_references()
+                        .into_iter()
+                        .map(|reference| OntologyTypeUuid::from_url(&reference.url))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            OntologyReference::PropertyTypeConstrainsPropertiesOn => self
+                .property_types
+                .get(&PropertyTypeUuid::from(source))
+                .map(|row| {
+                    row.schema
+                        .property_type_references()
+                        .into_iter()
+                        .map(|reference| OntologyTypeUuid::from_url(&reference.url))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            OntologyReference::EntityTypeInheritsFrom { inheritance_depth } => self
+                .entity_types
+                .get(&EntityTypeUuid::from(source))
+                .map(|row| {
+                    row.resolve_data
+                        .inheritance_depths()
+                        .filter_map(within_depth(inheritance_depth))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            OntologyReference::EntityTypeConstrainsPropertiesOn { inheritance_depth } => self
+                .entity_types
+                .get(&EntityTypeUuid::from(source))
+                .map(|row| {
+                    row.resolve_data
+                        .properties()
+                        .filter_map(within_depth(inheritance_depth))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            OntologyReference::EntityTypeConstrainsLinksOn { inheritance_depth } => self
+                .entity_types
+                .get(&EntityTypeUuid::from(source))
+                .map(|row| {
+                    row.resolve_data
+                        .links()
+                        .filter_map(within_depth(inheritance_depth))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            OntologyReference::EntityTypeConstrainsLinkDestinationsOn { inheritance_depth } => self
+                .entity_types
+                .get(&EntityTypeUuid::from(source))
+                .map(|row| {
+                    row.resolve_data
+                        .link_destinations()
+                        .filter_map(within_depth(inheritance_depth))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the ontology types referring to `target` by `reference`.
+    pub fn ontology_referenced_by(
+        &self,
+        target: OntologyTypeUuid,
+        reference: OntologyReference,
+    ) -> Vec<OntologyTypeUuid> {
+        let sources = match reference {
+            OntologyReference::DataTypeInheritsFrom { .. }
+            | OntologyReference::DataTypeConstrainsValuesOn => self
+                .data_types
+                .keys()
+                .copied()
+                .map(OntologyTypeUuid::from)
+                .collect::<Vec<_>>(),
+            OntologyReference::PropertyTypeConstrainsValuesOn
+            | OntologyReference::PropertyTypeConstrainsPropertiesOn => self
+                .property_types
+                .keys()
+                .copied()
+                .map(OntologyTypeUuid::from)
+                .collect(),
+            OntologyReference::EntityTypeInheritsFrom { .. }
+            | OntologyReference::EntityTypeConstrainsPropertiesOn { .. }
+            | OntologyReference::EntityTypeConstrainsLinksOn { .. }
+            | OntologyReference::EntityTypeConstrainsLinkDestinationsOn { .. } => self
+                .entity_types
+                .keys()
+                .copied()
+                .map(OntologyTypeUuid::from)
+                .collect(),
+        };
+
+        sources
+            .into_iter()
+            .filter(|source| {
+                self.ontology_references(*source, reference)
+                    .contains(&target)
+            })
+            .collect()
+    }
+
+    /// Returns the ontology types connected to `ontology_id` by `reference` in `direction`.
+    pub fn ontology_edges(
+        &self,
+        ontology_id: OntologyTypeUuid,
+        reference: OntologyReference,
+        direction: EdgeDirection,
+    ) -> Vec<OntologyTypeUuid> {
+        match direction {
+            EdgeDirection::Outgoing => self.ontology_references(ontology_id, reference),
+            EdgeDirection::Incoming => self.ontology_referenced_by(ontology_id, reference),
+        }
+    }
+
+    /// Reads the outgoing edges of the provided ontology types.
+    ///
+    /// Similar to the Postgres store, the edges are not filtered by the temporal axes.
+    pub fn read_ontology_edges(
+        &self,
+        sources: &[(
+            OntologyTypeUuid,
+            GraphResolveDepths,
+            RightBoundedTemporalInterval<VariableAxis>,
+        )],
+        reference: OntologyReference,
+    ) -> Vec<(OntologyTypeUuid, OntologyEdgeTraversal)> {
+        sources
+            .iter()
+            .filter_map(|(source_id, resolve_depths, traversal_interval)| {
+                self.ontology_ids.get(source_id).map(|source| {
+                    self.ontology_references(*source_id, reference)
+                        .into_iter()
+                        .filter_map(|target_id| {
+                            self.ontology_ids.get(&target_id).map(|target| {
+                                (target_id, OntologyEdgeTraversal {
+                                    left_endpoint: source.url.clone(),
+                                    right_endpoint: target.url.clone(),
+                                    right_endpoint_ontology_id: target_id,
+                                    resolve_depths: *resolve_depths,
+                                    traversal_interval: *traversal_interval,
+                                })
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Stores `embedding` for the ontology type identified by `url`.
+    ///
+    /// Only the latest version of an ontology type has an embedding, embeddings of previous
+    /// versions are removed. An existing embedding is only replaced by a more recent one.
+    pub fn update_ontology_embedding(
+        &mut self,
+        url: &VersionedUrl,
+        embedding: OntologyEmbeddingRow,
+        embedding_slot: impl Fn(
+            &mut Self,
+            OntologyTypeUuid,
+        ) -> Option<&mut Option<OntologyEmbeddingRow>>,
+    ) {
+        if !self.is_latest_version(url) {
+            return;
+        }
+
+        let previous_versions = self
+            .ontology_ids
+            .iter()
+            .filter(|(_, row)| row.url.base_url == url.base_url && row.url.version < url.version)
+            .map(|(ontology_id, _)| *ontology_id)
+            .collect::<Vec<_>>();
+        for ontology_id in previous_versions {
+            if let Some(slot) = embedding_slot(self, ontology_id) {
+                *slot = None;
+            }
+        }
+
+        if let Some(slot) = embedding_slot(self, OntologyTypeUuid::from_url(url)) {
+            if slot.as_ref().is_none_or(|existing| {
+                existing.updated_at_transaction_time <= embedding.updated_at_transaction_time
+            }) {
+                *slot = Some(embedding);
+            }
+        }
+    }
+}
+
+/// Returns the rows of the ontology type `ontology_id` visible in `context`.
+///
+/// References to other ontology types are not restricted by the temporal axes in the Postgres
+/// store unless temporal data of the referenced type is requested. To allow filtering on
+/// archived types, all rows of the type are returned if none of them is visible.
+pub(crate) fn referenced_rows<'t, T>(
+    context: &Context<'t>,
+    ontology_id: OntologyTypeUuid,
+    record: &'t T,
+) -> Vec<OntologyRow<'t, T>> {
+    let rows = ontology_rows(context, [(ontology_id, record)]);
+    if !rows.is_empty() {
+        return rows;
+    }
+
+    context
+        .tables
+        .ontology_ids
+        .get(&ontology_id)
+        .into_iter()
+        .flat_map(|metadata| {
+            metadata
+                .temporal_metadata
+                .iter()
+                .map(move |temporal_metadata| OntologyRow {
+                    ontology_id,
+                    metadata,
+                    temporal_metadata,
+                    record,
+                })
+        })
+        .collect()
+}
+
+macro_rules! impl_ontology_cursor {
+    ($ty:ty) => {
+        impl MemorySorting<$ty> for VersionedUrlSorting {
+            fn cursor_of<'t>(
+                &self,
+                _: &Context<'t>,
+                row: <$ty as MemoryRecord>::Row<'t>,
+            ) -> Self::Cursor {
+                row.metadata.url.clone()
+            }
+
+            fn compare_cursors(&self, lhs: &Self::Cursor, rhs: &Self::Cursor) -> Ordering {
+                lhs.base_url
+                    .as_str()
+                    .cmp(rhs.base_url.as_str())
+                    .then_with(|| rhs.version.cmp(&lhs.version))
+            }
+        }
+    };
+}
+
+impl_ontology_cursor!(DataTypeWithMetadata);
+impl_ontology_cursor!(PropertyTypeWithMetadata);
+impl_ontology_cursor!(EntityTypeWithMetadata);
+
+#[cfg(test)]
+mod tests {
+    use graph_types::{
+        account::{AccountId, EditionArchivedById, EditionCreatedById},
+        ontology::{
+            OntologyEditionProvenance, OntologyProvenance, OntologyTypeClassificationMetadata,
+            ProvidedOntologyEditionProvenance,
+        },
+        owned_by_id::OwnedById,
+    };
+    use hash_graph_store::ConflictBehavior;
+    use type_system::{
+        schema::OntologyTypeUuid,
+        url::{BaseUrl, OntologyTypeVersion, VersionedUrl},
+    };
+    use uuid::Uuid;
+
+    use crate::store::{
+        BaseUrlAlreadyExists,
+        error::{OntologyTypeIsNotOwned, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
+        memory::table::MemoryTables,
+    };
+
+    fn url(version: u32) -> VersionedUrl {
+        VersionedUrl {
+            base_url: BaseUrl::new("https://example.com/data-type/text/".to_owned())
+                .expect("could not parse base URL"),
+            version: OntologyTypeVersion::new(version),
+        }
+    }
+
+    fn provenance() -> OntologyEditionProvenance {
+        OntologyEditionProvenance {
+            created_by_id: EditionCreatedById::new(AccountId::new(Uuid::new_v4())),
+            archived_by_id: None,
+            user_defined: ProvidedOntologyEditionProvenance::default(),
+        }
+    }
+
+    fn owned() -> OntologyTypeClassificationMetadata {
+        OntologyTypeClassificationMetadata::Owned {
+            owned_by_id: OwnedById::new(Uuid::new_v4()),
+        }
+    }
+
+    fn create(
+        tables: &mut MemoryTables,
+        url: &VersionedUrl,
+        classification: &OntologyTypeClassificationMetadata,
+        on_conflict: ConflictBehavior,
+    ) -> bool {
+        tables
+            .create_ontology_metadata(url, classification, on_conflict, &OntologyProvenance {
+                edition: provenance(),
+            })
+            .expect("could not create ontology metadata")
+            .is_some()
+    }
+
+    #[test]
+    fn create_conflicts() {
+        let mut tables = MemoryTables::default();
+        let classification = owned();
+
+        assert!(create(
+            &mut tables,
+            &url(1),
+            &classification,
+            ConflictBehavior::Fail
+        ));
+        assert!(!create(
+            &mut tables,
+            &url(1),
+            &classification,
+            ConflictBehavior::Skip
+        ));
+
+        let error = tables
+            .create_ontology_metadata(
+                &url(2),
+                &classification,
+                ConflictBehavior::Fail,
+                &OntologyProvenance {
+                    edition: provenance(),
+                },
+            )
+            .expect_err("the base URL should already exist");
+        assert!(error.contains::<BaseUrlAlreadyExists>());
+    }
+
+    #[test]
+    fn update_versions() {
+        let mut tables = MemoryTables::default();
+        let OntologyTypeClassificationMetadata::Owned { owned_by_id } = owned() else {
+            unreachable!()
+        };
+        let classification = OntologyTypeClassificationMetadata::Owned { owned_by_id };
+        create(
+            &mut tables,
+            &url(1),
+            &classification,
+            ConflictBehavior::Fail,
+        );
+        assert!(tables.is_latest_version(&url(1)));
+
+        let (ontology_id, updated_owned_by_id, _) = tables
+            .update_owned_ontology_id(&url(2), &provenance())
+            .expect("could not update ontology type");
+        assert_eq!(ontology_id, OntologyTypeUuid::from_url(&url(2)));
+        assert_eq!(updated_owned_by_id, owned_by_id);
+        assert!(!tables.is_latest_version(&url(1)));
+        assert!(tables.is_latest_version(&url(2)));
+
+        let error = tables
+            .update_owned_ontology_id(&url(2), &provenance())
+            .expect_err("the version should already exist");
+        assert!(error.contains::<VersionedUrlAlreadyExists>());
+        let error = tables
+            .update_owned_ontology_id(&url(4), &provenance())
+            .expect_err("the previous version should not exist");
+        assert!(error.contains::<OntologyVersionDoesNotExist>());
+    }
+
+    #[test]
+    fn external_types_cannot_be_updated() {
+        let mut tables = MemoryTables::default();
+        let classification = OntologyTypeClassificationMetadata::External {
+            fetched_at: time::OffsetDateTime::now_utc(),
+        };
+        create(
+            &mut tables,
+            &url(1),
+            &classification,
+            ConflictBehavior::Fail,
+        );
+
+        let error = tables
+            .update_owned_ontology_id(&url(2), &provenance())
+            .expect_err("external types should not be updatable");
+        assert!(error.contains::<OntologyTypeIsNotOwned>());
+    }
+
+    #[test]
+    fn archive_and_unarchive() {
+        let mut tables = MemoryTables::default();
+        let ontology_id = OntologyTypeUuid::from_url(&url(1));
+        let archived_by_id = EditionArchivedById::new(AccountId::new(Uuid::new_v4()));
+        create(&mut tables, &url(1), &owned(), ConflictBehavior::Fail);
+
+        let created_at = tables.transaction_time();
+        assert!(tables.ontology_row_at(ontology_id, created_at).is_some());
+
+        let archived = tables
+            .archive_ontology_type(&url(1), archived_by_id)
+            .expect("could not archive ontology type");
+        let archived_at = tables.transaction_time();
+        assert!(tables.ontology_row_at(ontology_id, created_at).is_some());
+        assert!(tables.ontology_row_at(ontology_id, archived_at).is_none());
+        assert_eq!(
+            tables.ontology_ids[&ontology_id].temporal_metadata[0]
+                .provenance
+                .archived_by_id,
+            Some(archived_by_id)
+        );
+
+        let error = tables
+            .archive_ontology_type(&url(1), archived_by_id)
+            .expect_err("the ontology type should already be archived");
+        assert!(error.contains::<VersionedUrlAlreadyExists>());
+
+        let unarchived = tables
+            .unarchive_ontology_type(&url(1), &provenance())
+            .expect("could not unarchive ontology type");
+        assert!(
+            !unarchived
+                .transaction_time
+                .overlaps(&archived.transaction_time)
+        );
+        let unarchived_at = tables.transaction_time();
+        assert!(tables.ontology_row_at(ontology_id, unarchived_at).is_some());
+
+        let error = tables
+            .unarchive_ontology_type(&url(1), &provenance())
+            .expect_err("the ontology type should not be archived");
+        assert!(error.contains::<VersionedUrlAlreadyExists>());
+        let error = tables
+            .archive_ontology_type(&url(2), archived_by_id)
+            .expect_err("the ontology type should not exist");
+        assert!(error.contains::<OntologyVersionDoesNotExist>());
+    }
+}
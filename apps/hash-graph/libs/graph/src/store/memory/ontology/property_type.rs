@@ -0,0 +1,1031 @@
+use alloc::sync::Arc;
+use core::iter::once;
+use std::collections::{HashMap, HashSet};
+
+use authorization::{
+    AuthorizationApi,
+    backend::ModifyRelationshipOperation,
+    schema::{
+        PropertyTypeOwnerSubject, PropertyTypePermission, PropertyTypeRelationAndSubject,
+        WebPermission,
+    },
+    zanzibar::{Consistency, Zookie},
+};
+use error_stack::{Report, Result, ResultExt};
+use futures::StreamExt;
+use graph_types::{
+    account::{AccountId, EditionArchivedById, EditionCreatedById},
+    ontology::{
+        OntologyEditionProvenance, OntologyProvenance, OntologyTemporalMetadata,
+        OntologyTypeClassificationMetadata, OntologyTypeRecordId, PropertyTypeMetadata,
+        PropertyTypeWithMetadata,
+    },
+};
+use hash_graph_store::{
+    property_type::PropertyTypeQueryPath,
+    subgraph::{
+        Subgraph, SubgraphRecord,
+        edges::{EdgeDirection, GraphResolveDepths, OntologyEdgeKind},
+        identifier::{DataTypeVertexId, GraphElementVertexId, PropertyTypeVertexId},
+        temporal_axes::{QueryTemporalAxes, VariableAxis},
+    },
+};
+use temporal_versioning::RightBoundedTemporalInterval;
+use type_system::{
+    Validator,
+    schema::{
+        DataTypeUuid, OntologyTypeUuid, PropertyType, PropertyTypeUuid, PropertyTypeValidator,
+    },
+    url::{OntologyTypeVersion, VersionedUrl},
+};
+
+use crate::store::{
+    InsertionError, PropertyTypeStore, QueryError, StoreCache, StoreProvider, UpdateError,
+    crud::{QueryResult, Read, ReadPaginated, VersionedUrlSorting},
+    error::DeletionError,
+    memory::{
+        MemoryStore,
+        filter::{
+            Context, MemoryRecord, OntologyField, OntologyRow, OntologyTypeRow, Value,
+            ontology_rows, ontology_values,
+        },
+        ontology::{
+            OntologyReference, data_type::data_type_values, entity_type::entity_type_values,
+            referenced_rows,
+        },
+        table::{MemoryTables, OntologyEmbeddingRow, PropertyTypeRow},
+    },
+    ontology::{
+        ArchivePropertyTypeParams, CountPropertyTypesParams, CreatePropertyTypeParams,
+        GetPropertyTypeSubgraphParams, GetPropertyTypeSubgraphResponse, GetPropertyTypesParams,
+        GetPropertyTypesResponse, UnarchivePropertyTypeParams, UpdatePropertyTypeEmbeddingParams,
+        UpdatePropertyTypesParams,
+    },
+    postgres::TraversalContext,
+};
+
+impl OntologyTypeRow for PropertyTypeRow {
+    type Schema = PropertyType;
+
+    fn schema(&self) -> &Self::Schema {
+        &self.schema
+    }
+
+    fn embedding(&self) -> Option<&OntologyEmbeddingRow> {
+        self.embedding.as_ref()
+    }
+}
+
+/// Returns the values of `path` for the property types identified by `property_type_ids`.
+pub(crate) fn property_type_values<'t>(
+    context: &Context<'t>,
+    property_type_ids: impl IntoIterator<Item = OntologyTypeUuid>,
+    path: &PropertyTypeQueryPath<'_>,
+) -> Vec<Value<'t>> {
+    property_type_ids
+        .into_iter()
+        .filter_map(|property_type_id| {
+            context
+                .tables
+                .property_types
+                .get(&PropertyTypeUuid::from(property_type_id))
+                .map(|record| (property_type_id, record))
+        })
+        .flat_map(|(property_type_id, record)| referenced_rows(context, property_type_id, record))
+        .flat_map(|row| PropertyTypeWithMetadata::values(context, row, path))
+        .collect()
+}
+
+impl MemoryRecord for PropertyTypeWithMetadata {
+    type Row<'t> = OntologyRow<'t, PropertyTypeRow>;
+
+    fn rows<'t>(context: &Context<'t>) -> Vec<Self::Row<'t>> {
+        ontology_rows(
+            context,
+            context
+                .tables
+                .property_types
+                .iter()
+                .map(|(property_type_id, row)| (*property_type_id, row)),
+        )
+    }
+
+    fn values<'t>(
+        context: &Context<'t>,
+        row: Self::Row<'t>,
+        path: &PropertyTypeQueryPath<'_>,
+    ) -> Vec<Value<'t>> {
+        let field = match path {
+            PropertyTypeQueryPath::BaseUrl => OntologyField::BaseUrl,
+            PropertyTypeQueryPath::Version => OntologyField::Version,
+            PropertyTypeQueryPath::VersionedUrl => OntologyField::VersionedUrl,
+            PropertyTypeQueryPath::TransactionTime => OntologyField::TransactionTime,
+            PropertyTypeQueryPath::OwnedById => OntologyField::OwnedById,
+            PropertyTypeQueryPath::OntologyId => OntologyField::OntologyId,
+            PropertyTypeQueryPath::AdditionalMetadata => OntologyField::AdditionalMetadata,
+            PropertyTypeQueryPath::EditionProvenance(path) => {
+                OntologyField::EditionProvenance(path.as_ref())
+            }
+            PropertyTypeQueryPath::Embedding => OntologyField::Embedding,
+            PropertyTypeQueryPath::Schema(path) => OntologyField::Schema(path.as_ref()),
+            PropertyTypeQueryPath::Title => OntologyField::SchemaText("title"),
+            PropertyTypeQueryPath::Description => OntologyField::SchemaText("description"),
+            PropertyTypeQueryPath::DataTypeEdge {
+                edge_kind: OntologyEdgeKind::ConstrainsValuesOn,
+                path,
+            } => {
+                return data_type_values(
+                    context,
+                    context.tables.ontology_edges(
+                        row.ontology_id,
+                        OntologyReference::PropertyTypeConstrainsValuesOn,
+                        EdgeDirection::Outgoing,
+                    ),
+                    path,
+                );
+            }
+            PropertyTypeQueryPath::PropertyTypeEdge {
+                edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                path,
+                direction,
+            } => {
+                return property_type_values(
+                    context,
+                    context.tables.ontology_edges(
+                        row.ontology_id,
+                        OntologyReference::PropertyTypeConstrainsPropertiesOn,
+                        *direction,
+                    ),
+                    path,
+                );
+            }
+            PropertyTypeQueryPath::EntityTypeEdge {
+                edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                path,
+                inheritance_depth,
+            } => {
+                return entity_type_values(
+                    context,
+                    context.tables.ontology_edges(
+                        row.ontology_id,
+                        OntologyReference::EntityTypeConstrainsPropertiesOn {
+                            inheritance_depth: *inheritance_depth,
+                        },
+                        EdgeDirection::Incoming,
+                    ),
+                    path,
+                );
+            }
+            PropertyTypeQueryPath::DataTypeEdge { .. }
+            | PropertyTypeQueryPath::PropertyTypeEdge { .. }
+            | PropertyTypeQueryPath::EntityTypeEdge { .. } => return Vec::new(),
+        };
+
+        ontology_values(context, row, &field)
+    }
+
+    fn materialize<'t>(_: &Context<'t>, row: Self::Row<'t>) -> Self {
+        Self {
+            schema: (*row.record.schema).clone(),
+            metadata: PropertyTypeMetadata {
+                record_id: OntologyTypeRecordId::from(row.metadata.url.clone()),
+                classification: row.metadata.classification.clone(),
+                temporal_versioning: OntologyTemporalMetadata {
+                    transaction_time: row.temporal_metadata.transaction_time,
+                },
+                provenance: OntologyProvenance {
+                    edition: row.temporal_metadata.provenance.clone(),
+                },
+            },
+        }
+    }
+
+    fn clone_record(&self) -> Self {
+        Self {
+            schema: self.schema.clone(),
+            metadata: self.metadata.clone(),
+        }
+    }
+}
+
+impl MemoryTables {
+    /// Ensures that all types referenced by `property_type` exist.
+    ///
+    /// The Postgres store enforces this through foreign keys on the reference tables.
+    fn check_property_type_references(
+        &self,
+        property_type: &PropertyType,
+    ) -> Result<(), QueryError> {
+        let missing = property_type
+            .data_type_references()
+            .into_iter()
+            .map(|reference| &reference.url)
+            .chain(
+                property_type
+                    .property_type_references()
+                    .into_iter()
+                    .map(|reference| &reference.url),
+            )
+            .filter(|url| {
+                !self
+                    .ontology_ids
+                    .contains_key(&OntologyTypeUuid::from_url(url))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            let mut error = Report::new(QueryError);
+            for url in missing {
+                error = error.attach_printable(url);
+            }
+            Err(error)
+        }
+    }
+}
+
+impl<A> MemoryStore<A>
+where
+    A: AuthorizationApi,
+{
+    #[tracing::instrument(level = "debug", skip(property_types, authorization_api, zookie))]
+    pub(crate) async fn filter_property_types_by_permission<I, T>(
+        property_types: impl IntoIterator<Item = (I, T)> + Send,
+        actor_id: AccountId,
+        authorization_api: &A,
+        zookie: &Zookie<'static>,
+    ) -> Result<impl Iterator<Item = T>, QueryError>
+    where
+        I: Into<PropertyTypeUuid> + Send,
+        T: Send,
+    {
+        let (ids, property_types): (Vec<_>, Vec<_>) = property_types
+            .into_iter()
+            .map(|(id, edge)| (id.into(), edge))
+            .unzip();
+
+        let permissions = authorization_api
+            .check_property_types_permission(
+                actor_id,
+                PropertyTypePermission::View,
+                ids.iter().copied(),
+                Consistency::AtExactSnapshot(zookie),
+            )
+            .await
+            .change_context(QueryError)?
+            .0;
+
+        Ok(ids
+            .into_iter()
+            .zip(property_types)
+            .filter_map(move |(id, property_type)| {
+                permissions
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(false)
+                    .then_some(property_type)
+            }))
+    }
+
+    async fn get_property_types_impl(
+        &self,
+        actor_id: AccountId,
+        params: GetPropertyTypesParams<'_>,
+        temporal_axes: &QueryTemporalAxes,
+    ) -> Result<(GetPropertyTypesResponse, Zookie<'static>), QueryError> {
+        #[expect(clippy::if_then_some_else_none, reason = "Function is async")]
+        let count = if params.include_count {
+            Some(
+                self.count_property_types(actor_id, CountPropertyTypesParams {
+                    filter: params.filter.clone(),
+                    temporal_axes: params.temporal_axes.clone(),
+                    include_drafts: params.include_drafts,
+                })
+                .await?,
+            )
+        } else {
+            None
+        };
+
+        let mut visited_ontology_ids = HashSet::new();
+
+        let (data, artifacts) =
+            ReadPaginated::<PropertyTypeWithMetadata, VersionedUrlSorting>::read_paginated_vec(
+                self,
+                &params.filter,
+                Some(temporal_axes),
+                &VersionedUrlSorting {
+                    cursor: params.after,
+                },
+                params.limit,
+                params.include_drafts,
+            )
+            .await?;
+        let property_types = data
+            .into_iter()
+            .filter_map(|row| {
+                let property_type = row.decode_record(&artifacts);
+                let id = PropertyTypeUuid::from_url(&property_type.schema.id);
+                visited_ontology_ids
+                    .insert(id)
+                    .then_some((id, property_type))
+            })
+            .collect::<Vec<_>>();
+
+        let filtered_ids = property_types
+            .iter()
+            .map(|(property_type_id, _)| *property_type_id)
+            .collect::<Vec<_>>();
+
+        let (permissions, zookie) = self
+            .authorization_api
+            .check_property_types_permission(
+                actor_id,
+                PropertyTypePermission::View,
+                filtered_ids,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?;
+
+        let property_types = property_types
+            .into_iter()
+            .filter_map(|(id, property_type)| {
+                permissions
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(false)
+                    .then_some(property_type)
+            })
+            .collect::<Vec<_>>();
+
+        Ok((
+            GetPropertyTypesResponse {
+                cursor: if params.limit.is_some() {
+                    property_types
+                        .last()
+                        .map(|property_type| property_type.schema.id.clone())
+                } else {
+                    None
+                },
+                property_types,
+                count,
+            },
+            zookie,
+        ))
+    }
+
+    /// Internal method to read a [`PropertyTypeWithMetadata`] into a [`TraversalContext`].
+    ///
+    /// This is used to recursively resolve a type, so the result can be reused.
+    #[tracing::instrument(level = "info", skip(self, traversal_context, subgraph, zookie))]
+    pub(crate) async fn traverse_property_types(
+        &self,
+        mut property_type_queue: Vec<(
+            PropertyTypeUuid,
+            GraphResolveDepths,
+            RightBoundedTemporalInterval<VariableAxis>,
+        )>,
+        traversal_context: &mut TraversalContext,
+        actor_id: AccountId,
+        zookie: &Zookie<'static>,
+        subgraph: &mut Subgraph,
+    ) -> Result<(), QueryError> {
+        let tables = self.snapshot();
+        let mut data_type_queue = Vec::new();
+        let mut edges_to_traverse = HashMap::<OntologyEdgeKind, Vec<_>>::new();
+
+        while !property_type_queue.is_empty() {
+            edges_to_traverse.clear();
+
+            #[expect(clippy::iter_with_drain, reason = "false positive, vector is reused")]
+            for (property_type_ontology_id, graph_resolve_depths, traversal_interval) in
+                property_type_queue.drain(..)
+            {
+                for edge_kind in [
+                    OntologyEdgeKind::ConstrainsValuesOn,
+                    OntologyEdgeKind::ConstrainsPropertiesOn,
+                ] {
+                    if let Some(new_graph_resolve_depths) = graph_resolve_depths
+                        .decrement_depth_for_edge(edge_kind, EdgeDirection::Outgoing)
+                    {
+                        edges_to_traverse.entry(edge_kind).or_default().push((
+                            OntologyTypeUuid::from(property_type_ontology_id),
+                            new_graph_resolve_depths,
+                            traversal_interval,
+                        ));
+                    }
+                }
+            }
+
+            if let Some(traversal_data) =
+                edges_to_traverse.get(&OntologyEdgeKind::ConstrainsValuesOn)
+            {
+                data_type_queue.extend(
+                    Self::filter_data_types_by_permission(
+                        tables
+                            .read_ontology_edges(
+                                traversal_data,
+                                OntologyReference::PropertyTypeConstrainsValuesOn,
+                            )
+                            .into_iter()
+                            .map(|(target_id, edge)| (DataTypeUuid::from(target_id), edge)),
+                        actor_id,
+                        &self.authorization_api,
+                        zookie,
+                    )
+                    .await?
+                    .flat_map(|edge| {
+                        subgraph.insert_edge(
+                            &PropertyTypeVertexId::from(edge.left_endpoint),
+                            OntologyEdgeKind::ConstrainsValuesOn,
+                            EdgeDirection::Outgoing,
+                            DataTypeVertexId::from(edge.right_endpoint),
+                        );
+
+                        traversal_context.add_data_type_id(
+                            DataTypeUuid::from(edge.right_endpoint_ontology_id),
+                            edge.resolve_depths,
+                            edge.traversal_interval,
+                        )
+                    }),
+                );
+            }
+
+            if let Some(traversal_data) =
+                edges_to_traverse.get(&OntologyEdgeKind::ConstrainsPropertiesOn)
+            {
+                property_type_queue.extend(
+                    Self::filter_property_types_by_permission(
+                        tables
+                            .read_ontology_edges(
+                                traversal_data,
+                                OntologyReference::PropertyTypeConstrainsPropertiesOn,
+                            )
+                            .into_iter()
+                            .map(|(target_id, edge)| (PropertyTypeUuid::from(target_id), edge)),
+                        actor_id,
+                        &self.authorization_api,
+                        zookie,
+                    )
+                    .await?
+                    .flat_map(|edge| {
+                        subgraph.insert_edge(
+                            &PropertyTypeVertexId::from(edge.left_endpoint),
+                            OntologyEdgeKind::ConstrainsPropertiesOn,
+                            EdgeDirection::Outgoing,
+                            PropertyTypeVertexId::from(edge.right_endpoint),
+                        );
+
+                        traversal_context.add_property_type_id(
+                            PropertyTypeUuid::from(edge.right_endpoint_ontology_id),
+                            edge.resolve_depths,
+                            edge.traversal_interval,
+                        )
+                    }),
+                );
+            }
+        }
+
+        self.traverse_data_types(
+            data_type_queue,
+            traversal_context,
+            actor_id,
+            zookie,
+            subgraph,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn delete_property_types(&mut self) -> Result<(), DeletionError> {
+        let transaction = self.transaction().await.change_context(DeletionError)?;
+
+        transaction.write(|tables| {
+            let property_types = tables
+                .property_types
+                .drain()
+                .map(|(property_type_id, _)| OntologyTypeUuid::from(property_type_id))
+                .collect::<Vec<_>>();
+            tables.delete_ontology_ids(property_types);
+        });
+
+        transaction.commit().await.change_context(DeletionError)?;
+
+        Ok(())
+    }
+}
+
+impl<A> PropertyTypeStore for MemoryStore<A>
+where
+    A: AuthorizationApi,
+{
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn create_property_types<P, R>(
+        &mut self,
+        actor_id: AccountId,
+        params: P,
+    ) -> Result<Vec<PropertyTypeMetadata>, InsertionError>
+    where
+        P: IntoIterator<Item = CreatePropertyTypeParams<R>, IntoIter: Send> + Send,
+        R: IntoIterator<Item = PropertyTypeRelationAndSubject> + Send + Sync,
+    {
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        let mut relationships = HashSet::new();
+
+        let mut inserted_property_type_metadata = Vec::new();
+        let mut inserted_property_types = Vec::new();
+
+        let property_type_validator = PropertyTypeValidator;
+
+        for parameters in params {
+            let provenance = OntologyProvenance {
+                edition: OntologyEditionProvenance {
+                    created_by_id: EditionCreatedById::new(actor_id),
+                    archived_by_id: None,
+                    user_defined: parameters.provenance,
+                },
+            };
+
+            let record_id = OntologyTypeRecordId::from(parameters.schema.id.clone());
+            let property_type_id = PropertyTypeUuid::from_url(&parameters.schema.id);
+            if let OntologyTypeClassificationMetadata::Owned { owned_by_id } =
+                &parameters.classification
+            {
+                transaction
+                    .authorization_api
+                    .check_web_permission(
+                        actor_id,
+                        WebPermission::CreatePropertyType,
+                        *owned_by_id,
+                        Consistency::FullyConsistent,
+                    )
+                    .await
+                    .change_context(InsertionError)?
+                    .assert_permission()
+                    .change_context(InsertionError)?;
+
+                relationships.insert((property_type_id, PropertyTypeRelationAndSubject::Owner {
+                    subject: PropertyTypeOwnerSubject::Web { id: *owned_by_id },
+                    level: 0,
+                }));
+            }
+
+            relationships.extend(
+                parameters
+                    .relationships
+                    .into_iter()
+                    .map(|relation_and_subject| (property_type_id, relation_and_subject)),
+            );
+
+            if let Some((_ontology_id, temporal_versioning)) = transaction.write(|tables| {
+                tables.create_ontology_metadata(
+                    &parameters.schema.id,
+                    &parameters.classification,
+                    parameters.conflict_behavior,
+                    &provenance,
+                )
+            })? {
+                property_type_validator
+                    .validate_ref(&parameters.schema)
+                    .await
+                    .change_context(InsertionError)?;
+                transaction.write(|tables| {
+                    tables
+                        .property_types
+                        .insert(property_type_id, PropertyTypeRow {
+                            schema: Arc::new(parameters.schema.clone()),
+                            embedding: None,
+                        });
+                });
+                let metadata = PropertyTypeMetadata {
+                    record_id,
+                    classification: parameters.classification,
+                    temporal_versioning,
+                    provenance,
+                };
+
+                inserted_property_types.push(PropertyTypeWithMetadata {
+                    schema: parameters.schema,
+                    metadata: metadata.clone(),
+                });
+                inserted_property_type_metadata.push(metadata);
+            }
+        }
+
+        let tables = transaction.snapshot();
+        for property_type in &inserted_property_types {
+            tables
+                .check_property_type_references(&property_type.schema)
+                .change_context(InsertionError)
+                .attach_printable_lazy(|| {
+                    format!(
+                        "could not insert references for property type: {}",
+                        &property_type.schema.id
+                    )
+                })
+                .attach_lazy(|| property_type.schema.clone())?;
+        }
+
+        transaction
+            .authorization_api
+            .modify_property_type_relations(relationships.clone().into_iter().map(
+                |(resource, relation_and_subject)| {
+                    (
+                        ModifyRelationshipOperation::Create,
+                        resource,
+                        relation_and_subject,
+                    )
+                },
+            ))
+            .await
+            .change_context(InsertionError)?;
+
+        if let Err(error) = transaction.commit().await.change_context(InsertionError) {
+            let mut error = error.expand();
+
+            if let Err(auth_error) = self
+                .authorization_api
+                .modify_property_type_relations(relationships.into_iter().map(
+                    |(resource, relation_and_subject)| {
+                        (
+                            ModifyRelationshipOperation::Delete,
+                            resource,
+                            relation_and_subject,
+                        )
+                    },
+                ))
+                .await
+                .change_context(InsertionError)
+            {
+                error.push(auth_error);
+            }
+
+            Err(error.change_context(InsertionError))
+        } else {
+            if let Some(temporal_client) = &self.temporal_client {
+                temporal_client
+                    .start_update_property_type_embeddings_workflow(
+                        actor_id,
+                        &inserted_property_types,
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+            }
+
+            Ok(inserted_property_type_metadata)
+        }
+    }
+
+    async fn count_property_types(
+        &self,
+        actor_id: AccountId,
+        mut params: CountPropertyTypesParams<'_>,
+    ) -> Result<usize, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        Ok(self
+            .read(
+                &params.filter,
+                Some(&params.temporal_axes.resolve()),
+                params.include_drafts,
+            )
+            .await?
+            .count()
+            .await)
+    }
+
+    async fn get_property_types(
+        &self,
+        actor_id: AccountId,
+        mut params: GetPropertyTypesParams<'_>,
+    ) -> Result<GetPropertyTypesResponse, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        let temporal_axes = params.temporal_axes.clone().resolve();
+        self.get_property_types_impl(actor_id, params, &temporal_axes)
+            .await
+            .map(|(response, _)| response)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_property_type_subgraph(
+        &self,
+        actor_id: AccountId,
+        mut params: GetPropertyTypeSubgraphParams<'_>,
+    ) -> Result<GetPropertyTypeSubgraphResponse, QueryError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(QueryError)?;
+
+        let temporal_axes = params.temporal_axes.clone().resolve();
+        let time_axis = temporal_axes.variable_time_axis();
+
+        let (
+            GetPropertyTypesResponse {
+                property_types,
+                cursor,
+                count,
+            },
+            zookie,
+        ) = self
+            .get_property_types_impl(
+                actor_id,
+                GetPropertyTypesParams {
+                    filter: params.filter,
+                    temporal_axes: params.temporal_axes.clone(),
+                    after: params.after,
+                    limit: params.limit,
+                    include_drafts: params.include_drafts,
+                    include_count: params.include_count,
+                },
+                &temporal_axes,
+            )
+            .await?;
+
+        let mut subgraph = Subgraph::new(
+            params.graph_resolve_depths,
+            params.temporal_axes,
+            temporal_axes.clone(),
+        );
+
+        let (property_type_ids, property_type_vertex_ids): (Vec<_>, Vec<_>) = property_types
+            .iter()
+            .map(|property_type| {
+                (
+                    PropertyTypeUuid::from_url(&property_type.schema.id),
+                    GraphElementVertexId::from(property_type.vertex_id(time_axis)),
+                )
+            })
+            .unzip();
+        subgraph.roots.extend(property_type_vertex_ids);
+        subgraph.vertices.property_types = property_types
+            .into_iter()
+            .map(|property_type| (property_type.vertex_id(time_axis), property_type))
+            .collect();
+
+        let mut traversal_context = TraversalContext::default();
+
+        self.traverse_property_types(
+            property_type_ids
+                .into_iter()
+                .map(|id| {
+                    (
+                        id,
+                        subgraph.depths,
+                        subgraph.temporal_axes.resolved.variable_interval(),
+                    )
+                })
+                .collect(),
+            &mut traversal_context,
+            actor_id,
+            &zookie,
+            &mut subgraph,
+        )
+        .await?;
+
+        traversal_context
+            .read_traversed_vertices(self, &mut subgraph, params.include_drafts)
+            .await?;
+
+        Ok(GetPropertyTypeSubgraphResponse {
+            subgraph,
+            cursor,
+            count,
+        })
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn update_property_type<R>(
+        &mut self,
+        actor_id: AccountId,
+        params: UpdatePropertyTypesParams<R>,
+    ) -> Result<PropertyTypeMetadata, UpdateError>
+    where
+        R: IntoIterator<Item = PropertyTypeRelationAndSubject> + Send + Sync,
+    {
+        let property_type_validator = PropertyTypeValidator;
+
+        let old_ontology_id = PropertyTypeUuid::from_url(&VersionedUrl {
+            base_url: params.schema.id.base_url.clone(),
+            version: OntologyTypeVersion::new(
+                params
+                    .schema
+                    .id
+                    .version
+                    .inner()
+                    .checked_sub(1)
+                    .ok_or(UpdateError)
+                    .attach_printable(
+                        "The version of the data type is already at the lowest possible value",
+                    )?,
+            ),
+        });
+        self.authorization_api
+            .check_property_type_permission(
+                actor_id,
+                PropertyTypePermission::Update,
+                old_ontology_id,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)
+            .attach_printable(old_ontology_id.into_uuid())?;
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let provenance = OntologyProvenance {
+            edition: OntologyEditionProvenance {
+                created_by_id: EditionCreatedById::new(actor_id),
+                archived_by_id: None,
+                user_defined: params.provenance,
+            },
+        };
+
+        property_type_validator
+            .validate_ref(&params.schema)
+            .await
+            .change_context(UpdateError)?;
+        let (ontology_id, owned_by_id, temporal_versioning) = transaction.write(|tables| {
+            let updated =
+                tables.update_owned_ontology_id(&params.schema.id, &provenance.edition)?;
+            tables
+                .property_types
+                .insert(PropertyTypeUuid::from(updated.0), PropertyTypeRow {
+                    schema: Arc::new(params.schema.clone()),
+                    embedding: None,
+                });
+            Ok::<_, Report<UpdateError>>(updated)
+        })?;
+
+        transaction
+            .snapshot()
+            .check_property_type_references(&params.schema)
+            .change_context(UpdateError)
+            .attach_printable_lazy(|| {
+                format!(
+                    "could not insert references for property type: {}",
+                    params.schema.id
+                )
+            })
+            .attach_lazy(|| params.schema.clone())?;
+
+        let property_type_id = PropertyTypeUuid::from(ontology_id);
+        let relationships = params
+            .relationships
+            .into_iter()
+            .chain(once(PropertyTypeRelationAndSubject::Owner {
+                subject: PropertyTypeOwnerSubject::Web { id: owned_by_id },
+                level: 0,
+            }))
+            .collect::<Vec<_>>();
+
+        transaction
+            .authorization_api
+            .modify_property_type_relations(relationships.clone().into_iter().map(
+                |relation_and_subject| {
+                    (
+                        ModifyRelationshipOperation::Create,
+                        property_type_id,
+                        relation_and_subject,
+                    )
+                },
+            ))
+            .await
+            .change_context(UpdateError)?;
+
+        if let Err(error) = transaction.commit().await.change_context(UpdateError) {
+            let mut error = error.expand();
+
+            if let Err(auth_error) = self
+                .authorization_api
+                .modify_property_type_relations(relationships.into_iter().map(
+                    |relation_and_subject| {
+                        (
+                            ModifyRelationshipOperation::Delete,
+                            property_type_id,
+                            relation_and_subject,
+                        )
+                    },
+                ))
+                .await
+                .change_context(UpdateError)
+            {
+                error.push(auth_error);
+            }
+
+            Err(error.change_context(UpdateError))
+        } else {
+            let metadata = PropertyTypeMetadata {
+                record_id: OntologyTypeRecordId::from(params.schema.id.clone()),
+                classification: OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                temporal_versioning,
+                provenance,
+            };
+
+            if let Some(temporal_client) = &self.temporal_client {
+                temporal_client
+                    .start_update_property_type_embeddings_workflow(actor_id, &[
+                        PropertyTypeWithMetadata {
+                            schema: params.schema,
+                            metadata: metadata.clone(),
+                        },
+                    ])
+                    .await
+                    .change_context(UpdateError)?;
+            }
+
+            Ok(metadata)
+        }
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn archive_property_type(
+        &mut self,
+        actor_id: AccountId,
+        params: ArchivePropertyTypeParams<'_>,
+    ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        let temporal_metadata = transaction.write(|tables| {
+            tables
+                .archive_ontology_type(&params.property_type_id, EditionArchivedById::new(actor_id))
+        })?;
+        transaction.commit().await.change_context(UpdateError)?;
+        Ok(temporal_metadata)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn unarchive_property_type(
+        &mut self,
+        actor_id: AccountId,
+        params: UnarchivePropertyTypeParams<'_>,
+    ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        let temporal_metadata = transaction.write(|tables| {
+            tables.unarchive_ontology_type(&params.property_type_id, &OntologyEditionProvenance {
+                created_by_id: EditionCreatedById::new(actor_id),
+                archived_by_id: None,
+                user_defined: params.provenance,
+            })
+        })?;
+        transaction.commit().await.change_context(UpdateError)?;
+        Ok(temporal_metadata)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn update_property_type_embeddings(
+        &mut self,
+        _: AccountId,
+        params: UpdatePropertyTypeEmbeddingParams<'_>,
+    ) -> Result<(), UpdateError> {
+        // TODO: Add permission to allow updating embeddings
+        //   see https://linear.app/hash/issue/H-1870
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        transaction.write(|tables| {
+            tables.update_ontology_embedding(
+                &params.property_type_id,
+                OntologyEmbeddingRow {
+                    embedding: params.embedding.into_owned(),
+                    updated_at_transaction_time: params.updated_at_transaction_time,
+                },
+                |tables, ontology_id| {
+                    tables
+                        .property_types
+                        .get_mut(&PropertyTypeUuid::from(ontology_id))
+                        .map(|row| &mut row.embedding)
+                },
+            );
+        });
+        transaction.commit().await.change_context(UpdateError)
+    }
+}
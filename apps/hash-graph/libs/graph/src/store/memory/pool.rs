@@ -0,0 +1,55 @@
+use alloc::sync::Arc;
+
+use authorization::AuthorizationApi;
+use error_stack::Report;
+use temporal_client::TemporalClient;
+use tokio::sync::Mutex;
+
+use crate::store::{
+    StoreError, StorePool,
+    memory::{MemoryStore, TableSlot},
+};
+
+/// A pool handing out [`MemoryStore`]s which all operate on the same in-memory database.
+///
+/// Cloning the pool is cheap and the clone shares the database with the original pool.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorePool {
+    tables: Arc<TableSlot>,
+    writer: Arc<Mutex<()>>,
+}
+
+impl MemoryStorePool {
+    /// Creates a new pool backed by an empty in-memory database.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorePool for MemoryStorePool {
+    type Error = StoreError;
+    type Store<'pool, A: AuthorizationApi> = MemoryStore<A>;
+
+    async fn acquire<A: AuthorizationApi>(
+        &self,
+        authorization_api: A,
+        temporal_client: Option<Arc<TemporalClient>>,
+    ) -> Result<Self::Store<'_, A>, Report<Self::Error>> {
+        self.acquire_owned(authorization_api, temporal_client).await
+    }
+
+    async fn acquire_owned<A: AuthorizationApi>(
+        &self,
+        authorization_api: A,
+        temporal_client: Option<Arc<TemporalClient>>,
+    ) -> Result<Self::Store<'static, A>, Report<Self::Error>> {
+        Ok(MemoryStore {
+            tables: Arc::clone(&self.tables),
+            transaction: None,
+            writer: Arc::clone(&self.writer),
+            authorization_api,
+            temporal_client,
+        })
+    }
+}
@@ -0,0 +1,152 @@
+use alloc::sync::Arc;
+use std::collections::{HashMap, HashSet};
+
+use graph_types::{
+    Embedding,
+    account::{AccountGroupId, AccountId},
+    knowledge::{
+        Confidence,
+        entity::{
+            DraftId, EntityEditionId, EntityEditionProvenance, EntityUuid, InferredEntityProvenance,
+        },
+        link::LinkData,
+        property::{PropertyMetadataObject, PropertyObject},
+    },
+    ontology::{OntologyEditionProvenance, OntologyTypeClassificationMetadata},
+    owned_by_id::OwnedById,
+};
+use temporal_versioning::{DecisionTime, LeftClosedTemporalInterval, Timestamp, TransactionTime};
+use type_system::{
+    schema::{
+        ClosedEntityType, Conversions, DataType, DataTypeResolveData, DataTypeUuid, EntityType,
+        EntityTypeResolveData, EntityTypeUuid, OntologyTypeUuid, PropertyType, PropertyTypeUuid,
+    },
+    url::{BaseUrl, OntologyTypeVersion, VersionedUrl},
+};
+
+/// The complete state of an in-memory database.
+///
+/// The tables mirror the relational layout used by the Postgres store, but references which can be
+/// derived from a schema (e.g. the property types constrained by an entity type) are resolved
+/// from the stored resolve data instead of being materialized into separate tables.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct MemoryTables {
+    pub accounts: HashSet<AccountId>,
+    pub account_groups: HashSet<AccountGroupId>,
+    pub webs: HashSet<OwnedById>,
+    /// Maps each known [`BaseUrl`] to the latest version stored for it.
+    pub base_urls: HashMap<BaseUrl, OntologyTypeVersion>,
+    pub ontology_ids: HashMap<OntologyTypeUuid, OntologyIdRow>,
+    pub data_types: HashMap<DataTypeUuid, DataTypeRow>,
+    pub property_types: HashMap<PropertyTypeUuid, PropertyTypeRow>,
+    pub entity_types: HashMap<EntityTypeUuid, EntityTypeRow>,
+    pub entity_ids: HashMap<(OwnedById, EntityUuid), EntityIdRow>,
+    pub entity_editions: HashMap<EntityEditionId, Arc<EntityEditionRow>>,
+    pub entity_temporal_metadata: Vec<EntityTemporalMetadataRow>,
+    pub entity_embeddings: Vec<EntityEmbeddingRow>,
+    /// The latest transaction time handed out by [`Self::transaction_time`].
+    pub last_transaction_time: Option<Timestamp<TransactionTime>>,
+}
+
+impl MemoryTables {
+    /// Returns the current transaction time.
+    ///
+    /// In contrast to the database clock, which is fixed for the duration of a transaction,
+    /// consecutive operations on the in-memory store may happen within the same microsecond. To
+    /// avoid empty intervals the returned timestamp is guaranteed to be strictly greater than any
+    /// timestamp previously returned for these tables.
+    pub fn transaction_time(&mut self) -> Timestamp<TransactionTime> {
+        loop {
+            let now = Timestamp::now().remove_nanosecond();
+            if self
+                .last_transaction_time
+                .map_or(true, |last_transaction_time| now > last_transaction_time)
+            {
+                self.last_transaction_time = Some(now);
+                return now;
+            }
+            core::hint::spin_loop();
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct OntologyIdRow {
+    pub url: VersionedUrl,
+    pub classification: OntologyTypeClassificationMetadata,
+    pub temporal_metadata: Vec<OntologyTemporalMetadataRow>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct OntologyTemporalMetadataRow {
+    pub transaction_time: LeftClosedTemporalInterval<TransactionTime>,
+    pub provenance: OntologyEditionProvenance,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct OntologyEmbeddingRow {
+    pub embedding: Embedding<'static>,
+    pub updated_at_transaction_time: Timestamp<TransactionTime>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DataTypeRow {
+    pub schema: Arc<DataType>,
+    pub resolve_data: Arc<DataTypeResolveData>,
+    pub conversions: HashMap<BaseUrl, Conversions>,
+    pub embedding: Option<OntologyEmbeddingRow>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PropertyTypeRow {
+    pub schema: Arc<PropertyType>,
+    pub embedding: Option<OntologyEmbeddingRow>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EntityTypeRow {
+    pub schema: Arc<EntityType>,
+    pub closed_schema: Arc<ClosedEntityType>,
+    pub resolve_data: Arc<EntityTypeResolveData>,
+    pub embedding: Option<OntologyEmbeddingRow>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EntityIdRow {
+    pub provenance: InferredEntityProvenance,
+    pub link_data: Option<LinkData>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EntityEditionRow {
+    pub properties: PropertyObject,
+    pub property_metadata: PropertyMetadataObject,
+    pub archived: bool,
+    pub confidence: Option<Confidence>,
+    pub provenance: EntityEditionProvenance,
+    /// The entity types the edition was directly created with.
+    ///
+    /// Inherited entity types are resolved from the resolve data of these types.
+    pub entity_type_ids: Vec<VersionedUrl>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EntityTemporalMetadataRow {
+    pub owned_by_id: OwnedById,
+    pub entity_uuid: EntityUuid,
+    pub draft_id: Option<DraftId>,
+    pub edition_id: EntityEditionId,
+    pub decision_time: LeftClosedTemporalInterval<DecisionTime>,
+    pub transaction_time: LeftClosedTemporalInterval<TransactionTime>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EntityEmbeddingRow {
+    pub owned_by_id: OwnedById,
+    pub entity_uuid: EntityUuid,
+    pub draft_id: Option<DraftId>,
+    pub property: Option<BaseUrl>,
+    pub embedding: Embedding<'static>,
+    pub updated_at_transaction_time: Timestamp<TransactionTime>,
+    pub updated_at_decision_time: Timestamp<DecisionTime>,
+}
@@ -9,6 +9,7 @@ mod pool;
 mod validation;
 
 mod fetcher;
+mod memory;
 pub(crate) mod postgres;
 
 use hash_graph_store::account::AccountStore;
@@ -27,11 +28,12 @@ pub use self::{
         EntityQueryCursor, EntityQuerySorting, EntityQuerySortingRecord, EntityStore,
         EntityValidationType,
     },
+    memory::{MemoryStore, MemoryStorePool},
     migration::{Migration, MigrationState, StoreMigration},
     ontology::{DataTypeStore, EntityTypeStore, PropertyTypeStore},
     pool::StorePool,
     postgres::{AsClient, PostgresStore, PostgresStorePool},
-    validation::{StoreCache, StoreProvider},
+    validation::{AuthorizedStore, StoreCache, StoreProvider},
 };
 
 /// Describes the API of a store implementation.
@@ -0,0 +1,244 @@
+use error_stack::{Result, ResultExt};
+use graph_types::{
+    knowledge::entity::{DraftId, EntityEditionId, EntityUuid},
+    owned_by_id::OwnedById,
+};
+use tokio_postgres::{GenericClient, Transaction};
+use tracing::Instrument;
+
+use crate::store::{AsClient, PostgresStore, error::DeletionError};
+
+/// The rows stored for an entity which is about to be deleted.
+#[derive(Debug)]
+pub(crate) struct EntityDeletionRows {
+    pub draft_ids: Vec<DraftId>,
+    pub edition_ids: Vec<EntityEditionId>,
+    pub num_temporal_versions: usize,
+    pub num_embeddings: usize,
+}
+
+impl<C, A> PostgresStore<C, A>
+where
+    C: AsClient,
+    A: Send + Sync,
+{
+    /// Returns the links which have one of the specified entities as left or right entity.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) async fn read_links_of_entities(
+        &self,
+        owned_by_ids: &[OwnedById],
+        entity_uuids: &[EntityUuid],
+    ) -> Result<Vec<(OwnedById, EntityUuid)>, DeletionError> {
+        Ok(self
+            .client
+            .as_client()
+            .query(
+                "
+                    SELECT entity_has_left_entity.web_id, entity_has_left_entity.entity_uuid
+                      FROM entity_has_left_entity
+                      JOIN unnest($1::uuid[], $2::uuid[]) AS deleted(web_id, entity_uuid)
+                        ON entity_has_left_entity.left_web_id = deleted.web_id
+                       AND entity_has_left_entity.left_entity_uuid = deleted.entity_uuid
+                    UNION
+                    SELECT entity_has_right_entity.web_id, entity_has_right_entity.entity_uuid
+                      FROM entity_has_right_entity
+                      JOIN unnest($1::uuid[], $2::uuid[]) AS deleted(web_id, entity_uuid)
+                        ON entity_has_right_entity.right_web_id = deleted.web_id
+                       AND entity_has_right_entity.right_entity_uuid = deleted.entity_uuid;
+                ",
+                &[&owned_by_ids, &entity_uuids],
+            )
+            .instrument(tracing::trace_span!("query"))
+            .await
+            .change_context(DeletionError)?
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect())
+    }
+
+    /// Returns the rows stored for the specified entities in the order they were passed.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) async fn read_entity_deletion_rows(
+        &self,
+        owned_by_ids: &[OwnedById],
+        entity_uuids: &[EntityUuid],
+    ) -> Result<Vec<EntityDeletionRows>, DeletionError> {
+        self.client
+            .as_client()
+            .query(
+                "
+                    SELECT
+                        array(
+                            SELECT draft_id
+                              FROM entity_drafts
+                             WHERE entity_drafts.web_id = deleted.web_id
+                               AND entity_drafts.entity_uuid = deleted.entity_uuid
+                        ),
+                        array(
+                            SELECT DISTINCT entity_edition_id
+                              FROM entity_temporal_metadata
+                             WHERE entity_temporal_metadata.web_id = deleted.web_id
+                               AND entity_temporal_metadata.entity_uuid = deleted.entity_uuid
+                        ),
+                        (
+                            SELECT count(*)
+                              FROM entity_temporal_metadata
+                             WHERE entity_temporal_metadata.web_id = deleted.web_id
+                               AND entity_temporal_metadata.entity_uuid = deleted.entity_uuid
+                        ),
+                        (
+                            SELECT count(*)
+                              FROM entity_embeddings
+                             WHERE entity_embeddings.web_id = deleted.web_id
+                               AND entity_embeddings.entity_uuid = deleted.entity_uuid
+                        )
+                      FROM unnest($1::uuid[], $2::uuid[])
+                           WITH ORDINALITY
+                           AS deleted(web_id, entity_uuid, idx)
+                     ORDER BY deleted.idx;
+                ",
+                &[&owned_by_ids, &entity_uuids],
+            )
+            .instrument(tracing::trace_span!("query"))
+            .await
+            .change_context(DeletionError)?
+            .into_iter()
+            .map(|row| {
+                Ok(EntityDeletionRows {
+                    draft_ids: row.get(0),
+                    edition_ids: row.get(1),
+                    num_temporal_versions: usize::try_from(row.get::<_, i64>(2))
+                        .change_context(DeletionError)?,
+                    num_embeddings: usize::try_from(row.get::<_, i64>(3))
+                        .change_context(DeletionError)?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl<A> PostgresStore<Transaction<'_>, A>
+where
+    A: Send + Sync,
+{
+    /// Locks the specified entities until the transaction ends.
+    ///
+    /// Links reference their endpoints in `entity_ids`, so while an entity is locked no link to it
+    /// can be created. Links which were created before the lock was acquired are visible to
+    /// subsequent statements of the transaction.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) async fn lock_entities(
+        &self,
+        owned_by_ids: &[OwnedById],
+        entity_uuids: &[EntityUuid],
+    ) -> Result<(), DeletionError> {
+        self.as_client()
+            .query(
+                "
+                    SELECT 1
+                      FROM entity_ids
+                      JOIN unnest($1::uuid[], $2::uuid[]) AS deleted(web_id, entity_uuid)
+                        ON entity_ids.web_id = deleted.web_id
+                       AND entity_ids.entity_uuid = deleted.entity_uuid
+                       FOR UPDATE OF entity_ids;
+                ",
+                &[&owned_by_ids, &entity_uuids],
+            )
+            .instrument(tracing::trace_span!("query"))
+            .await
+            .change_context(DeletionError)?;
+
+        Ok(())
+    }
+
+    /// Removes every row stored for the specified entities.
+    ///
+    /// Links, which have one of the entities as left or right entity, have to be part of the
+    /// specified entities, otherwise the foreign key constraints are violated.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) async fn delete_entity_rows(
+        &self,
+        owned_by_ids: &[OwnedById],
+        entity_uuids: &[EntityUuid],
+    ) -> Result<(), DeletionError> {
+        for table in [
+            "entity_has_left_entity",
+            "entity_has_right_entity",
+            "entity_embeddings",
+        ] {
+            self.as_client()
+                .query(
+                    &format!(
+                        "
+                            DELETE FROM {table}
+                             USING unnest($1::uuid[], $2::uuid[]) AS deleted(web_id, entity_uuid)
+                             WHERE {table}.web_id = deleted.web_id
+                               AND {table}.entity_uuid = deleted.entity_uuid;
+                        "
+                    ),
+                    &[&owned_by_ids, &entity_uuids],
+                )
+                .await
+                .change_context(DeletionError)?;
+        }
+
+        let edition_ids = self
+            .as_client()
+            .query(
+                "
+                    DELETE FROM entity_temporal_metadata
+                     USING unnest($1::uuid[], $2::uuid[]) AS deleted(web_id, entity_uuid)
+                     WHERE entity_temporal_metadata.web_id = deleted.web_id
+                       AND entity_temporal_metadata.entity_uuid = deleted.entity_uuid
+                    RETURNING entity_temporal_metadata.entity_edition_id;
+                ",
+                &[&owned_by_ids, &entity_uuids],
+            )
+            .await
+            .change_context(DeletionError)?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect::<Vec<EntityEditionId>>();
+
+        self.as_client()
+            .query(
+                "
+                    DELETE FROM entity_is_of_type
+                     WHERE entity_edition_id = ANY($1);
+                ",
+                &[&edition_ids],
+            )
+            .await
+            .change_context(DeletionError)?;
+
+        self.as_client()
+            .query(
+                "
+                    DELETE FROM entity_editions
+                     WHERE entity_edition_id = ANY($1);
+                ",
+                &[&edition_ids],
+            )
+            .await
+            .change_context(DeletionError)?;
+
+        for table in ["entity_drafts", "entity_ids"] {
+            self.as_client()
+                .query(
+                    &format!(
+                        "
+                            DELETE FROM {table}
+                             USING unnest($1::uuid[], $2::uuid[]) AS deleted(web_id, entity_uuid)
+                             WHERE {table}.web_id = deleted.web_id
+                               AND {table}.entity_uuid = deleted.entity_uuid;
+                        "
+                    ),
+                    &[&owned_by_ids, &entity_uuids],
+                )
+                .await
+                .change_context(DeletionError)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+mod delete;
 mod query;
 mod read;
 use alloc::borrow::Cow;
@@ -14,7 +15,7 @@ use authorization::{
     zanzibar::{Consistency, Zookie},
 };
 use error_stack::{Report, ReportSink, Result, ResultExt, bail};
-use futures::{StreamExt as _, TryStreamExt, stream};
+use futures::{StreamExt as _, TryStreamExt, future::try_join_all, stream};
 use graph_types::{
     Embedding,
     account::{AccountId, CreatedById, EditionArchivedById, EditionCreatedById},
@@ -71,7 +72,8 @@ use crate::store::{
     crud::{QueryResult, Read, ReadPaginated, Sorting},
     error::{DeletionError, EntityDoesNotExist, RaceConditionOnUpdate},
     knowledge::{
-        CountEntitiesParams, CreateEntityParams, EntityQuerySorting, EntityValidationType,
+        CountEntitiesParams, CreateEntityParams, DeleteEntitiesParams, DeleteEntitiesResponse,
+        DeletedEntity, EntityDeletionReason, EntityQuerySorting, EntityValidationType,
         GetEntitiesParams, GetEntitiesResponse, GetEntitySubgraphParams, GetEntitySubgraphResponse,
         PatchEntityParams, QueryConversion, UpdateEntityEmbeddingsParams, ValidateEntityError,
         ValidateEntityParams,
@@ -1890,6 +1892,204 @@ where
 
         Ok(())
     }
+
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn permanently_delete_entities(
+        &mut self,
+        actor_id: AccountId,
+        mut params: DeleteEntitiesParams<'_>,
+    ) -> Result<DeleteEntitiesResponse, DeletionError> {
+        params
+            .filter
+            .convert_parameters(&StoreProvider {
+                store: &*self,
+                cache: StoreCache::default(),
+                authorization: Some((actor_id, Consistency::FullyConsistent)),
+            })
+            .await
+            .change_context(DeletionError)?;
+
+        let temporal_axes = params.temporal_axes.resolve();
+
+        // The link closure is computed inside of the transaction, so no link can be created to one
+        // of the deleted entities before they are removed.
+        let transaction = self.transaction().await.change_context(DeletionError)?;
+
+        let mut deleted_ids = HashSet::new();
+        let mut entity_ids = Vec::new();
+        let mut reasons = Vec::new();
+        let mut linked_entity_ids = Read::<Entity>::read_vec(
+            &transaction,
+            &params.filter,
+            Some(&temporal_axes),
+            params.include_drafts,
+        )
+        .await
+        .change_context(DeletionError)?
+        .into_iter()
+        .map(|entity| {
+            let entity_id = entity.metadata.record_id.entity_id;
+            (entity_id.owned_by_id, entity_id.entity_uuid)
+        })
+        .collect::<Vec<_>>();
+        let mut reason = EntityDeletionReason::Matched;
+        let mut num_visited_entities = 0;
+
+        // Links cannot exist without their endpoints, so links to deleted entities are deleted
+        // as well. As links may be linked themselves, this is repeated until no new link is found.
+        while !linked_entity_ids.is_empty() {
+            for entity_id in linked_entity_ids {
+                if deleted_ids.insert(entity_id) {
+                    entity_ids.push(entity_id);
+                    reasons.push(reason);
+                }
+            }
+            let (owned_by_ids, entity_uuids): (Vec<_>, Vec<_>) =
+                entity_ids[num_visited_entities..].iter().copied().unzip();
+            num_visited_entities = entity_ids.len();
+            transaction
+                .lock_entities(&owned_by_ids, &entity_uuids)
+                .await?;
+            linked_entity_ids = transaction
+                .read_links_of_entities(&owned_by_ids, &entity_uuids)
+                .await?
+                .into_iter()
+                .filter(|entity_id| !deleted_ids.contains(entity_id))
+                .collect();
+            reason = EntityDeletionReason::LinkEndpointDeleted;
+        }
+        let entity_ids = entity_ids
+            .into_iter()
+            .map(|(owned_by_id, entity_uuid)| EntityId {
+                owned_by_id,
+                entity_uuid,
+                draft_id: None,
+            })
+            .collect::<Vec<_>>();
+
+        let (permissions, _zookie) = transaction
+            .authorization_api
+            .check_entities_permission(
+                actor_id,
+                EntityPermission::FullAccess,
+                entity_ids.iter().copied(),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(DeletionError)?;
+        // The forbidden entities are not reported as the link closure may contain entities the
+        // actor is not allowed to view.
+        let is_forbidden = entity_ids.iter().any(|entity_id| {
+            !permissions
+                .get(&entity_id.entity_uuid)
+                .copied()
+                .unwrap_or(false)
+        });
+        if is_forbidden {
+            return Err(Report::new(DeletionError)
+                .attach(StatusCode::PermissionDenied)
+                .attach_printable(
+                    "The actor does not have permission to delete one or more entities",
+                ));
+        }
+
+        let (owned_by_ids, entity_uuids): (Vec<_>, Vec<_>) = entity_ids
+            .iter()
+            .map(|entity_id| (entity_id.owned_by_id, entity_id.entity_uuid))
+            .unzip();
+        let rows = transaction
+            .read_entity_deletion_rows(&owned_by_ids, &entity_uuids)
+            .await?;
+        let relationships = try_join_all(entity_ids.iter().map(|&entity_id| {
+            transaction
+                .authorization_api
+                .get_entity_relations(entity_id, Consistency::FullyConsistent)
+        }))
+        .await
+        .change_context(DeletionError)?;
+
+        let entities = entity_ids
+            .into_iter()
+            .zip(reasons)
+            .zip(rows)
+            .zip(relationships)
+            .map(
+                |(((entity_id, reason), rows), relationships)| DeletedEntity {
+                    entity_id,
+                    reason,
+                    draft_ids: rows.draft_ids,
+                    edition_ids: rows.edition_ids,
+                    num_temporal_versions: rows.num_temporal_versions,
+                    num_embeddings: rows.num_embeddings,
+                    relationships,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        if params.dry_run {
+            transaction.rollback().await.change_context(DeletionError)?;
+            return Ok(DeleteEntitiesResponse {
+                dry_run: true,
+                entities,
+            });
+        }
+
+        let relationships = entities
+            .iter()
+            .flat_map(|entity| {
+                entity
+                    .relationships
+                    .iter()
+                    .map(|relation_and_subject| (entity.entity_id, *relation_and_subject))
+            })
+            .collect::<Vec<_>>();
+
+        transaction
+            .delete_entity_rows(&owned_by_ids, &entity_uuids)
+            .await?;
+
+        transaction
+            .authorization_api
+            .modify_entity_relations(relationships.iter().copied().map(
+                |(entity_id, relation_and_subject)| {
+                    (
+                        ModifyRelationshipOperation::Delete,
+                        entity_id,
+                        relation_and_subject,
+                    )
+                },
+            ))
+            .await
+            .change_context(DeletionError)?;
+
+        if let Err(error) = transaction.commit().await.change_context(DeletionError) {
+            let mut error = error.expand();
+
+            if let Err(auth_error) = self
+                .authorization_api
+                .modify_entity_relations(relationships.into_iter().map(
+                    |(entity_id, relation_and_subject)| {
+                        (
+                            ModifyRelationshipOperation::Create,
+                            entity_id,
+                            relation_and_subject,
+                        )
+                    },
+                ))
+                .await
+                .change_context(DeletionError)
+            {
+                error.push(auth_error);
+            }
+
+            Err(error.change_context(DeletionError))
+        } else {
+            Ok(DeleteEntitiesResponse {
+                dry_run: false,
+                entities,
+            })
+        }
+    }
 }
 
 #[derive(Debug)]
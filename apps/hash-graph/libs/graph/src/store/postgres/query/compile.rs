@@ -417,6 +417,12 @@ impl<'p, 'q: 'p, R: PostgresRecord> SelectCompiler<'p, 'q, R> {
                 rhs.as_ref()
                     .map(|expression| self.compile_filter_expression(expression).0),
             ),
+            Filter::Exists(expression) => {
+                Condition::NotEqual(Some(self.compile_text_expression(expression)), None)
+            }
+            Filter::IsNull(expression) => {
+                Condition::Equal(Some(self.compile_text_expression(expression)), None)
+            }
             Filter::Greater(lhs, rhs) => Condition::Greater(
                 self.compile_filter_expression(lhs).0,
                 self.compile_filter_expression(rhs).0,
@@ -582,60 +588,40 @@ impl<'p, 'q: 'p, R: PostgresRecord> SelectCompiler<'p, 'q, R> {
                      `parameter` expression."
                 ),
             },
+            Filter::In(lhs, ParameterList::Parameters(parameters)) => Condition::InList(
+                self.compile_filter_expression(lhs).0,
+                parameters
+                    .iter()
+                    .map(|parameter| self.compile_parameter(parameter).0)
+                    .collect(),
+            ),
             Filter::In(lhs, rhs) => Condition::In(
                 self.compile_filter_expression(lhs).0,
                 self.compile_parameter_list(rhs).0,
             ),
             Filter::StartsWith(lhs, rhs) => {
-                let (left_filter, left_parameter) = self.compile_filter_expression(lhs);
-                let left_filter = if left_parameter == ParameterType::Any {
-                    Expression::Function(Function::JsonExtractText(Box::new(left_filter)))
-                } else {
-                    left_filter
-                };
-
-                let (right_filter, right_parameter) = self.compile_filter_expression(rhs);
-                let right_filter = if right_parameter == ParameterType::Any {
-                    Expression::Function(Function::JsonExtractText(Box::new(right_filter)))
-                } else {
-                    right_filter
-                };
-
-                Condition::StartsWith(left_filter, right_filter)
+                self.compile_text_condition(lhs, rhs, Condition::StartsWith)
             }
             Filter::EndsWith(lhs, rhs) => {
-                let (left_filter, left_parameter) = self.compile_filter_expression(lhs);
-                let left_filter = if left_parameter == ParameterType::Any {
-                    Expression::Function(Function::JsonExtractText(Box::new(left_filter)))
-                } else {
-                    left_filter
-                };
-
-                let (right_filter, right_parameter) = self.compile_filter_expression(rhs);
-                let right_filter = if right_parameter == ParameterType::Any {
-                    Expression::Function(Function::JsonExtractText(Box::new(right_filter)))
-                } else {
-                    right_filter
-                };
-
-                Condition::EndsWith(left_filter, right_filter)
+                self.compile_text_condition(lhs, rhs, Condition::EndsWith)
             }
             Filter::ContainsSegment(lhs, rhs) => {
-                let (left_filter, left_parameter) = self.compile_filter_expression(lhs);
-                let left_filter = if left_parameter == ParameterType::Any {
-                    Expression::Function(Function::JsonExtractText(Box::new(left_filter)))
-                } else {
-                    left_filter
-                };
-
-                let (right_filter, right_parameter) = self.compile_filter_expression(rhs);
-                let right_filter = if right_parameter == ParameterType::Any {
-                    Expression::Function(Function::JsonExtractText(Box::new(right_filter)))
+                self.compile_text_condition(lhs, rhs, Condition::ContainsSegment)
+            }
+            Filter::ILike(lhs, rhs) => self.compile_text_condition(lhs, rhs, Condition::ILike),
+            Filter::Matches(lhs, rhs) => self.compile_text_condition(lhs, rhs, Condition::Matches),
+            Filter::FullTextSearch(lhs, query) => {
+                let (filter, parameter) = self.compile_filter_expression(lhs);
+                let vector = if parameter == ParameterType::Any {
+                    Function::JsonToTsVector(Box::new(filter))
                 } else {
-                    right_filter
+                    Function::ToTsVector(Box::new(filter))
                 };
 
-                Condition::ContainsSegment(left_filter, right_filter)
+                Condition::TextSearchMatch(
+                    Expression::Function(vector),
+                    Expression::Function(Function::ToTsQuery(Box::new(self.add_parameter(query)))),
+                )
             }
         }
     }
@@ -820,6 +806,23 @@ impl<'p, 'q: 'p, R: PostgresRecord> SelectCompiler<'p, 'q, R> {
                     PathToken::Field(Cow::Borrowed(field)),
                 ))
             }
+            Some(JsonField::TextSearchRank(query)) => {
+                unreachable!("Text search query `{query}` should be handled by now")
+            }
+            Some(JsonField::TextSearchRankParameter(index)) => {
+                // `ts_rank_cd` returns a `real`, which is cast to be decodable as cursor
+                Expression::Cast(
+                    Box::new(Expression::Function(Function::TsRank(
+                        Box::new(Expression::Function(Function::JsonToTsVector(Box::new(
+                            column_expression,
+                        )))),
+                        Box::new(Expression::Function(Function::ToTsQuery(Box::new(
+                            Expression::Parameter(index),
+                        )))),
+                    ))),
+                    PostgresType::Float8,
+                )
+            }
             Some(JsonField::Label { inheritance_depth }) => {
                 if let Some(label_path) =
                     <R as QueryRecord>::QueryPath::label_property_path(inheritance_depth)
@@ -905,10 +908,12 @@ impl<'p, 'q: 'p, R: PostgresRecord> SelectCompiler<'p, 'q, R> {
         match expression {
             FilterExpression::Path { path } => {
                 let (column, json_field) = path.terminating_column();
-                let parameter_type = if let Some(JsonField::StaticText(_)) = json_field {
-                    ParameterType::Text
-                } else {
-                    column.parameter_type()
+                let parameter_type = match json_field {
+                    Some(JsonField::StaticText(_)) => ParameterType::Text,
+                    Some(JsonField::TextSearchRank(_) | JsonField::TextSearchRankParameter(_)) => {
+                        ParameterType::F64
+                    }
+                    _ => column.parameter_type(),
                 };
                 (self.compile_path_column(path), parameter_type)
             }
@@ -921,6 +926,36 @@ impl<'p, 'q: 'p, R: PostgresRecord> SelectCompiler<'p, 'q, R> {
         }
     }
 
+    /// Compiles the expression as text.
+    ///
+    /// JSON values are extracted as text, so a JSON `null` is compiled to `NULL`.
+    fn compile_text_expression(&mut self, expression: &'p FilterExpression<'q, R>) -> Expression
+    where
+        R::QueryPath<'q>: PostgresQueryPath,
+    {
+        let (expression, parameter_type) = self.compile_filter_expression(expression);
+        if parameter_type == ParameterType::Any {
+            Expression::Function(Function::JsonExtractText(Box::new(expression)))
+        } else {
+            expression
+        }
+    }
+
+    /// Compiles both expressions as text and combines them using `condition`.
+    fn compile_text_condition(
+        &mut self,
+        lhs: &'p FilterExpression<'q, R>,
+        rhs: &'p FilterExpression<'q, R>,
+        condition: impl FnOnce(Expression, Expression) -> Condition,
+    ) -> Condition
+    where
+        R::QueryPath<'q>: PostgresQueryPath,
+    {
+        let lhs = self.compile_text_expression(lhs);
+        let rhs = self.compile_text_expression(rhs);
+        condition(lhs, rhs)
+    }
+
     pub fn compile_parameter_list<'f: 'p>(
         &mut self,
         parameters: &'p ParameterList<'f>,
@@ -942,6 +977,9 @@ impl<'p, 'q: 'p, R: PostgresRecord> SelectCompiler<'p, 'q, R> {
                 self.artifacts.parameters.push(uuids);
                 ParameterType::Uuid
             }
+            ParameterList::Parameters(_) => {
+                unreachable!("A list of parameters should be handled by now")
+            }
         };
         (
             Expression::Parameter(self.artifacts.parameters.len()),
@@ -17,11 +17,18 @@ pub enum Condition {
     Greater(Expression, Expression),
     GreaterOrEqual(Expression, Expression),
     In(Expression, Expression),
+    /// Matches if the expression is equal to any expression in the list.
+    InList(Expression, Vec<Expression>),
     TimeIntervalContainsTimestamp(Expression, Expression),
     Overlap(Expression, Expression),
     StartsWith(Expression, Expression),
     EndsWith(Expression, Expression),
     ContainsSegment(Expression, Expression),
+    ILike(Expression, Expression),
+    /// Matches a text against a POSIX regular expression.
+    Matches(Expression, Expression),
+    /// Matches a text search vector against a text search query.
+    TextSearchMatch(Expression, Expression),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -113,6 +120,18 @@ impl Transpile for Condition {
                 rhs.transpile(fmt)?;
                 fmt.write_char(')')
             }
+            Self::InList(_, list) if list.is_empty() => fmt.write_str("FALSE"),
+            Self::InList(lhs, list) => {
+                lhs.transpile(fmt)?;
+                fmt.write_str(" IN (")?;
+                for (idx, expression) in list.iter().enumerate() {
+                    if idx > 0 {
+                        fmt.write_str(", ")?;
+                    }
+                    expression.transpile(fmt)?;
+                }
+                fmt.write_char(')')
+            }
             Self::TimeIntervalContainsTimestamp(lhs, rhs) => {
                 lhs.transpile(fmt)?;
                 fmt.write_str(" @> ")?;
@@ -141,6 +160,21 @@ impl Transpile for Condition {
                 rhs.transpile(fmt)?;
                 fmt.write_str(" || '%'")
             }
+            Self::ILike(lhs, rhs) => {
+                lhs.transpile(fmt)?;
+                fmt.write_str(" ILIKE ")?;
+                rhs.transpile(fmt)
+            }
+            Self::Matches(lhs, rhs) => {
+                lhs.transpile(fmt)?;
+                fmt.write_str(" ~ ")?;
+                rhs.transpile(fmt)
+            }
+            Self::TextSearchMatch(lhs, rhs) => {
+                lhs.transpile(fmt)?;
+                fmt.write_str(" @@ ")?;
+                rhs.transpile(fmt)
+            }
         }
     }
 }
@@ -152,7 +186,7 @@ mod tests {
     use graph_types::ontology::DataTypeWithMetadata;
     use hash_graph_store::{
         data_type::DataTypeQueryPath,
-        filter::{Filter, FilterExpression, Parameter},
+        filter::{Filter, FilterExpression, Parameter, ParameterList, TextSearchQuery},
     };
     use postgres_types::ToSql;
 
@@ -356,6 +390,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transpile_in_list_condition() {
+        test_condition(
+            &Filter::In(
+                FilterExpression::Path {
+                    path: DataTypeQueryPath::Title,
+                },
+                ParameterList::Parameters(vec![
+                    Parameter::Text(Cow::Borrowed("Text")),
+                    Parameter::Text(Cow::Borrowed("Number")),
+                ]),
+            ),
+            r#""data_types_0_1_0"."schema"->>'title' IN ($1, $2)"#,
+            &[&"Text", &"Number"],
+        );
+
+        test_condition(
+            &Filter::In(
+                FilterExpression::Path {
+                    path: DataTypeQueryPath::Title,
+                },
+                ParameterList::Parameters(Vec::new()),
+            ),
+            "FALSE",
+            &[],
+        );
+    }
+
+    #[test]
+    fn transpile_exists_condition() {
+        test_condition(
+            &Filter::Exists(FilterExpression::Path {
+                path: DataTypeQueryPath::Description,
+            }),
+            r#""data_types_0_1_0"."schema"->>'description' IS NOT NULL"#,
+            &[],
+        );
+
+        test_condition(
+            &Filter::Not(Box::new(Filter::Exists(FilterExpression::Path {
+                path: DataTypeQueryPath::Description,
+            }))),
+            r#"NOT("data_types_0_1_0"."schema"->>'description' IS NOT NULL)"#,
+            &[],
+        );
+
+        test_condition(
+            &Filter::IsNull(FilterExpression::Path {
+                path: DataTypeQueryPath::Description,
+            }),
+            r#""data_types_0_1_0"."schema"->>'description' IS NULL"#,
+            &[],
+        );
+    }
+
+    #[test]
+    fn transpile_pattern_conditions() {
+        test_condition(
+            &Filter::ILike(
+                FilterExpression::Path {
+                    path: DataTypeQueryPath::Title,
+                },
+                FilterExpression::Parameter {
+                    parameter: Parameter::Text(Cow::Borrowed("%text%")),
+                    convert: None,
+                },
+            ),
+            r#""data_types_0_1_0"."schema"->>'title' ILIKE $1"#,
+            &[&"%text%"],
+        );
+
+        test_condition(
+            &Filter::Matches(
+                FilterExpression::Path {
+                    path: DataTypeQueryPath::Title,
+                },
+                FilterExpression::Parameter {
+                    parameter: Parameter::Text(Cow::Borrowed("^[A-Z][a-z]+$")),
+                    convert: None,
+                },
+            ),
+            r#""data_types_0_1_0"."schema"->>'title' ~ $1"#,
+            &[&"^[A-Z][a-z]+$"],
+        );
+    }
+
+    #[test]
+    fn transpile_full_text_search_condition() {
+        let query = r#""plain text" val*"#
+            .parse::<TextSearchQuery>()
+            .expect("could not parse text search query");
+        test_condition(
+            &Filter::FullTextSearch(
+                FilterExpression::Path {
+                    path: DataTypeQueryPath::Description,
+                },
+                query.clone(),
+            ),
+            r#"to_tsvector('english'::regconfig, "data_types_0_1_0"."schema"->>'description') @@ to_tsquery('english'::regconfig, $1)"#,
+            &[&query],
+        );
+    }
+
     #[test]
     fn render_without_parameters() {
         test_condition(
@@ -36,7 +36,8 @@ impl PostgresQueryPath for EntityQueryPath<'_> {
             | Self::EditionProvenance(_)
             | Self::Archived
             | Self::EntityConfidence
-            | Self::PropertyMetadata(_) => {
+            | Self::PropertyMetadata(_)
+            | Self::TextSearchRank(_) => {
                 vec![Relation::EntityEditions]
             }
             Self::TypeBaseUrls | Self::TypeVersions => vec![Relation::EntityIsOfTypes],
@@ -168,6 +169,10 @@ impl PostgresQueryPath for EntityQueryPath<'_> {
                 Column::EntityIds(EntityIds::Provenance),
                 path.as_ref().map(JsonField::JsonPath),
             ),
+            Self::TextSearchRank(query) => (
+                Column::EntityEditions(EntityEditions::Properties),
+                Some(JsonField::TextSearchRank(query)),
+            ),
             Self::EditionProvenance(path) => (
                 Column::EntityEditions(EntityEditions::Provenance),
                 path.as_ref().map(JsonField::JsonPath),
@@ -9,6 +9,12 @@ use crate::store::postgres::query::{
     table::DatabaseColumn,
 };
 
+/// The text search configuration used for full-text search.
+///
+/// This has to match the configuration used in the full-text search indices, otherwise the
+/// indices cannot be used.
+const TEXT_SEARCH_CONFIGURATION: &str = "'english'::regconfig";
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Function {
     Min(Box<Expression>),
@@ -24,9 +30,20 @@ pub enum Function {
     Upper(Box<Expression>),
     Unnest(Box<Expression>),
     Now,
+    /// Converts text to a text search vector.
+    ToTsVector(Box<Expression>),
+    /// Converts all string values inside of a JSON value to a text search vector.
+    JsonToTsVector(Box<Expression>),
+    /// Converts a [`TextSearchQuery`] to a text search query.
+    ///
+    /// [`TextSearchQuery`]: hash_graph_store::filter::TextSearchQuery
+    ToTsQuery(Box<Expression>),
+    /// Ranks a text search vector for a text search query.
+    TsRank(Box<Expression>, Box<Expression>),
 }
 
 impl Transpile for Function {
+    #[expect(clippy::too_many_lines)]
     fn transpile(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Min(expression) => {
@@ -113,6 +130,28 @@ impl Transpile for Function {
                 path.transpile(fmt)?;
                 fmt.write_char(')')
             }
+            Self::ToTsVector(expression) => {
+                write!(fmt, "to_tsvector({TEXT_SEARCH_CONFIGURATION}, ")?;
+                expression.transpile(fmt)?;
+                fmt.write_char(')')
+            }
+            Self::JsonToTsVector(expression) => {
+                write!(fmt, "jsonb_to_tsvector({TEXT_SEARCH_CONFIGURATION}, ")?;
+                expression.transpile(fmt)?;
+                fmt.write_str(r#", '["string"]'::jsonb)"#)
+            }
+            Self::ToTsQuery(expression) => {
+                write!(fmt, "to_tsquery({TEXT_SEARCH_CONFIGURATION}, ")?;
+                expression.transpile(fmt)?;
+                fmt.write_char(')')
+            }
+            Self::TsRank(vector, query) => {
+                fmt.write_str("ts_rank_cd(")?;
+                vector.transpile(fmt)?;
+                fmt.write_str(", ")?;
+                query.transpile(fmt)?;
+                fmt.write_char(')')
+            }
         }
     }
 }
@@ -140,6 +179,7 @@ pub enum PostgresType {
     Row(Table),
     Text,
     JsonPath,
+    Float8,
 }
 
 impl Transpile for PostgresType {
@@ -152,6 +192,7 @@ impl Transpile for PostgresType {
             Self::Row(table) => table.transpile(fmt),
             Self::Text => fmt.write_str("text"),
             Self::JsonPath => fmt.write_str("jsonpath"),
+            Self::Float8 => fmt.write_str("float8"),
         }
     }
 }
@@ -832,6 +832,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entity_property_exists_query() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
+        let pinned_timestamp = temporal_axes.pinned_timestamp();
+        let mut compiler = SelectCompiler::<Entity>::with_asterisk(Some(&temporal_axes), false);
+        let json_path = JsonPath::from_path_tokens(vec![PathToken::Field(Cow::Borrowed(
+            r#"$."https://blockprotocol.org/@alice/types/property-type/name/""#,
+        ))]);
+
+        let filter = Filter::Exists(FilterExpression::Path {
+            path: EntityQueryPath::Properties(Some(json_path.clone())),
+        });
+        compiler.add_filter(&filter);
+
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT *
+            FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+            INNER JOIN "entity_editions" AS "entity_editions_0_1_0"
+              ON "entity_editions_0_1_0"."entity_edition_id" = "entity_temporal_metadata_0_0_0"."entity_edition_id"
+            WHERE "entity_temporal_metadata_0_0_0"."draft_id" IS NULL
+              AND "entity_temporal_metadata_0_0_0"."transaction_time" @> $2::TIMESTAMPTZ
+              AND "entity_temporal_metadata_0_0_0"."decision_time" && $3
+              AND ((jsonb_path_query_first("entity_editions_0_1_0"."properties", (($1::text)::jsonpath))) #>> '{}'::text[]) IS NOT NULL
+            "#,
+            &[
+                &json_path,
+                &pinned_timestamp,
+                &temporal_axes.variable_interval(),
+            ],
+        );
+    }
+
     #[test]
     fn entity_outgoing_link_query() {
         let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
@@ -5,7 +5,7 @@ use core::{
 };
 
 use hash_graph_store::{
-    filter::{JsonPath, ParameterType},
+    filter::{JsonPath, ParameterType, TextSearchQuery},
     subgraph::edges::EdgeDirection,
 };
 use postgres_types::ToSql;
@@ -368,7 +368,12 @@ pub enum JsonField<'p> {
     JsonPath(&'p JsonPath<'p>),
     JsonPathParameter(usize),
     StaticText(&'static str),
-    Label { inheritance_depth: Option<u32> },
+    Label {
+        inheritance_depth: Option<u32>,
+    },
+    /// The rank of the text search vector of the column for the provided query.
+    TextSearchRank(&'p TextSearchQuery),
+    TextSearchRankParameter(usize),
 }
 
 impl<'p> JsonField<'p> {
@@ -384,6 +389,13 @@ impl<'p> JsonField<'p> {
             Self::JsonPathParameter(index) => (JsonField::JsonPathParameter(index), None),
             Self::StaticText(text) => (JsonField::StaticText(text), None),
             Self::Label { inheritance_depth } => (JsonField::Label { inheritance_depth }, None),
+            Self::TextSearchRank(query) => (
+                JsonField::TextSearchRankParameter(current_parameter_index),
+                Some(query),
+            ),
+            Self::TextSearchRankParameter(index) => {
+                (JsonField::TextSearchRankParameter(index), None)
+            }
         }
     }
 }
@@ -24,6 +24,7 @@ use hash_graph_store::{
         PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
     },
 };
+use temporal_versioning::Timestamp;
 use tokio::sync::RwLock;
 use tokio_postgres::GenericClient;
 use type_system::{
@@ -35,7 +36,7 @@ use type_system::{
 };
 use validation::EntityProvider;
 
-use crate::store::{AsClient, PostgresStore, QueryError, crud::Read};
+use crate::store::{AsClient, MemoryStore, PostgresStore, QueryError, crud::Read};
 
 #[derive(Debug, Clone)]
 enum Access<T> {
@@ -132,15 +133,44 @@ pub struct StoreProvider<'a, S> {
     pub authorization: Option<(AccountId, Consistency<'static>)>,
 }
 
-impl<C, A> StoreProvider<'_, PostgresStore<C, A>>
+/// A store which exposes the [`AuthorizationApi`] it uses to check permissions.
+pub trait AuthorizedStore: Sync {
+    type Authorization: AuthorizationApi;
+
+    fn authorization_api(&self) -> &Self::Authorization;
+}
+
+impl<C, A> AuthorizedStore for PostgresStore<C, A>
 where
     C: AsClient,
     A: AuthorizationApi,
+{
+    type Authorization = A;
+
+    fn authorization_api(&self) -> &Self::Authorization {
+        &self.authorization_api
+    }
+}
+
+impl<A> AuthorizedStore for MemoryStore<A>
+where
+    A: AuthorizationApi,
+{
+    type Authorization = A;
+
+    fn authorization_api(&self) -> &Self::Authorization {
+        &self.authorization_api
+    }
+}
+
+impl<S> StoreProvider<'_, S>
+where
+    S: AuthorizedStore,
 {
     async fn authorize_data_type(&self, type_id: DataTypeUuid) -> Result<(), Report<QueryError>> {
         if let Some((actor_id, consistency)) = self.authorization {
             self.store
-                .authorization_api
+                .authorization_api()
                 .check_data_type_permission(
                     actor_id,
                     DataTypePermission::View,
@@ -157,10 +187,9 @@ where
     }
 }
 
-impl<C, A> OntologyTypeProvider<DataTypeWithMetadata> for StoreProvider<'_, PostgresStore<C, A>>
+impl<S> OntologyTypeProvider<DataTypeWithMetadata> for StoreProvider<'_, S>
 where
-    C: AsClient,
-    A: AuthorizationApi,
+    S: Read<DataTypeWithMetadata> + AuthorizedStore,
 {
     type Value = Arc<DataTypeWithMetadata>;
 
@@ -297,10 +326,105 @@ where
     }
 }
 
-impl<C, A> StoreProvider<'_, PostgresStore<C, A>>
+impl<A> DataTypeProvider for StoreProvider<'_, MemoryStore<A>>
 where
-    C: AsClient,
     A: AuthorizationApi,
+{
+    #[expect(refining_impl_trait)]
+    async fn is_parent_of(
+        &self,
+        child: &VersionedUrl,
+        parent: &BaseUrl,
+    ) -> Result<bool, Report<QueryError>> {
+        let tables = self.store.snapshot();
+
+        Ok(tables
+            .data_types
+            .get(&DataTypeUuid::from_url(child))
+            .is_some_and(|data_type| {
+                data_type
+                    .resolve_data
+                    .inheritance_depths()
+                    .any(|(target_id, _)| {
+                        tables
+                            .ontology_ids
+                            .get(&target_id.into())
+                            .is_some_and(|target| target.url.base_url == *parent)
+                    })
+            }))
+    }
+
+    #[expect(refining_impl_trait)]
+    async fn find_conversion(
+        &self,
+        source_data_type_id: &VersionedUrl,
+        target_data_type_id: &VersionedUrl,
+    ) -> Result<impl Borrow<Vec<ConversionExpression>>, Report<QueryError>> {
+        let source = DataTypeUuid::from_url(source_data_type_id);
+        let target = DataTypeUuid::from_url(target_data_type_id);
+
+        if let Some(cached) = self.cache.conversions.get(&(source, target)).await {
+            return cached;
+        }
+
+        let tables = self.store.snapshot();
+        let source_conversions = tables
+            .data_types
+            .get(&source)
+            .map(|data_type| &data_type.conversions);
+        let target_conversions = tables
+            .data_types
+            .get(&target)
+            .map(|data_type| &data_type.conversions);
+
+        let mut candidates = Vec::new();
+        if let (Some(source_conversions), Some(target_conversions)) =
+            (source_conversions, target_conversions)
+        {
+            for (base_url, source_conversion) in source_conversions {
+                if let Some(target_conversion) = target_conversions.get(base_url) {
+                    candidates.push(vec![
+                        source_conversion.to.clone(),
+                        target_conversion.from.clone(),
+                    ]);
+                }
+            }
+        }
+        if let Some(conversion) = source_conversions
+            .and_then(|conversions| conversions.get(&target_data_type_id.base_url))
+        {
+            candidates.push(vec![conversion.to.clone()]);
+        }
+        if let Some(conversion) = target_conversions
+            .and_then(|conversions| conversions.get(&source_data_type_id.base_url))
+        {
+            candidates.push(vec![conversion.from.clone()]);
+        }
+        candidates.dedup();
+
+        let [conversion] = <[_; 1]>::try_from(candidates).map_err(|candidates| {
+            Report::new(QueryError).attach_printable(format!(
+                "Found {} conversions between `{source_data_type_id}` and \
+                 `{target_data_type_id}`, expected exactly one",
+                candidates.len()
+            ))
+        })?;
+        let expression = conversion
+            .into_iter()
+            .map(|conversion| conversion.expression)
+            .collect();
+
+        Ok(self
+            .cache
+            .conversions
+            .grant((source, target), expression)
+            .await)
+    }
+}
+
+impl<S> StoreProvider<'_, S>
+where
+    S: AuthorizedStore,
 {
     async fn authorize_property_type(
         &self,
@@ -308,7 +432,7 @@ where
     ) -> Result<(), Report<QueryError>> {
         if let Some((actor_id, consistency)) = self.authorization {
             self.store
-                .authorization_api
+                .authorization_api()
                 .check_property_type_permission(
                     actor_id,
                     PropertyTypePermission::View,
@@ -325,10 +449,9 @@ where
     }
 }
 
-impl<C, A> OntologyTypeProvider<PropertyType> for StoreProvider<'_, PostgresStore<C, A>>
+impl<S> OntologyTypeProvider<PropertyType> for StoreProvider<'_, S>
 where
-    C: AsClient,
-    A: AuthorizationApi,
+    S: Read<PropertyTypeWithMetadata> + AuthorizedStore,
 {
     type Value = Arc<PropertyType>;
 
@@ -374,17 +497,14 @@ where
     }
 }
 
-impl<C, A> PropertyTypeProvider for StoreProvider<'_, PostgresStore<C, A>>
-where
-    C: AsClient,
-    A: AuthorizationApi,
+impl<S> PropertyTypeProvider for StoreProvider<'_, S> where
+    S: Read<PropertyTypeWithMetadata> + AuthorizedStore
 {
 }
 
-impl<C, A> StoreProvider<'_, PostgresStore<C, A>>
+impl<S> StoreProvider<'_, S>
 where
-    C: AsClient,
-    A: AuthorizationApi,
+    S: AuthorizedStore,
 {
     async fn authorize_entity_type(
         &self,
@@ -392,7 +512,7 @@ where
     ) -> Result<(), Report<QueryError>> {
         if let Some((actor_id, consistency)) = self.authorization {
             self.store
-                .authorization_api
+                .authorization_api()
                 .check_entity_type_permission(
                     actor_id,
                     EntityTypePermission::View,
@@ -407,7 +527,13 @@ where
 
         Ok(())
     }
+}
 
+impl<C, A> StoreProvider<'_, PostgresStore<C, A>>
+where
+    C: AsClient,
+    A: AuthorizationApi,
+{
     async fn fetch_entity_type(
         &self,
         type_id: &VersionedUrl,
@@ -550,10 +676,121 @@ where
     }
 }
 
-impl<C, A> EntityProvider for StoreProvider<'_, PostgresStore<C, A>>
+impl<A> StoreProvider<'_, MemoryStore<A>>
+where
+    A: AuthorizationApi,
+{
+    fn fetch_entity_type(
+        &self,
+        type_id: &VersionedUrl,
+    ) -> Result<ClosedEntityType, Report<QueryError>> {
+        let tables = self.store.snapshot();
+        let entity_type_id = EntityTypeUuid::from_url(type_id);
+
+        tables
+            .ontology_row_at(entity_type_id.into(), Timestamp::now())
+            .and_then(|_| tables.entity_types.get(&entity_type_id))
+            .map(|entity_type| (*entity_type.closed_schema).clone())
+            .ok_or_else(|| {
+                Report::new(QueryError).attach_printable(
+                    "Expected exactly one closed schema to be returned from the query but none \
+                     was returned",
+                )
+            })
+    }
+}
+
+impl<A> OntologyTypeProvider<ClosedEntityType> for StoreProvider<'_, MemoryStore<A>>
+where
+    A: AuthorizationApi,
+{
+    type Value = Arc<ClosedEntityType>;
+
+    #[expect(refining_impl_trait)]
+    async fn provide_type(
+        &self,
+        type_id: &VersionedUrl,
+    ) -> Result<Arc<ClosedEntityType>, Report<QueryError>> {
+        let entity_type_id = EntityTypeUuid::from_url(type_id);
+
+        if let Some(cached) = self.cache.entity_types.get(&entity_type_id).await {
+            return cached;
+        }
+
+        if let Err(error) = self.authorize_entity_type(entity_type_id).await {
+            self.cache.entity_types.deny(entity_type_id).await;
+            return Err(error);
+        }
+
+        let schema = match self.fetch_entity_type(type_id) {
+            Ok(schema) => schema,
+            Err(error) => {
+                self.cache.entity_types.malformed(entity_type_id).await;
+                return Err(error);
+            }
+        };
+
+        let schema = self.cache.entity_types.grant(entity_type_id, schema).await;
+        Ok(schema)
+    }
+}
+
+impl<A> EntityTypeProvider for StoreProvider<'_, MemoryStore<A>>
 where
-    C: AsClient,
     A: AuthorizationApi,
+{
+    #[expect(refining_impl_trait)]
+    async fn is_super_type_of(
+        &self,
+        parent: &VersionedUrl,
+        child: &VersionedUrl,
+    ) -> Result<bool, Report<QueryError>> {
+        let parent_id = EntityTypeUuid::from_url(parent);
+
+        Ok(self
+            .store
+            .snapshot()
+            .entity_types
+            .get(&EntityTypeUuid::from_url(child))
+            .is_some_and(|entity_type| {
+                entity_type
+                    .resolve_data
+                    .inheritance_depths()
+                    .any(|(target_id, _)| target_id == parent_id)
+            }))
+    }
+
+    #[expect(refining_impl_trait)]
+    async fn find_parents(
+        &self,
+        entity_types: &[VersionedUrl],
+    ) -> Result<Vec<VersionedUrl>, Report<QueryError>> {
+        let tables = self.store.snapshot();
+
+        let mut parents = entity_types
+            .iter()
+            .filter_map(|entity_type| {
+                tables
+                    .entity_types
+                    .get(&EntityTypeUuid::from_url(entity_type))
+            })
+            .flat_map(|entity_type| entity_type.resolve_data.inheritance_depths())
+            .filter_map(|(target_id, depth)| {
+                tables
+                    .ontology_ids
+                    .get(&target_id.into())
+                    .map(|target| (depth, target.url.clone()))
+            })
+            .collect::<Vec<_>>();
+        parents.sort_by_key(|(depth, _)| *depth);
+
+        Ok(parents.into_iter().map(|(_, url)| url).collect())
+    }
+}
+
+impl<S> EntityProvider for StoreProvider<'_, S>
+where
+    S: Read<Entity> + AuthorizedStore,
 {
     #[expect(refining_impl_trait)]
     async fn provide_entity(&self, entity_id: EntityId) -> Result<Arc<Entity>, Report<QueryError>> {
@@ -562,7 +799,7 @@ where
         }
         if let Some((actor_id, consistency)) = self.authorization {
             self.store
-                .authorization_api
+                .authorization_api()
                 .check_entity_permission(actor_id, EntityPermission::View, entity_id, consistency)
                 .await
                 .change_context(QueryError)?
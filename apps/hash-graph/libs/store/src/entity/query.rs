@@ -11,7 +11,7 @@ use utoipa::ToSchema;
 
 use crate::{
     entity_type::{EntityTypeQueryPath, EntityTypeQueryPathVisitor},
-    filter::{JsonPath, ParameterType, PathToken, QueryPath, parse_query_token},
+    filter::{JsonPath, ParameterType, PathToken, QueryPath, TextSearchQuery, parse_query_token},
     subgraph::edges::{EdgeDirection, KnowledgeGraphEdgeKind, SharedEdgeKind},
 };
 
@@ -431,6 +431,22 @@ pub enum EntityQueryPath<'p> {
     /// # Ok::<(), serde_json::Error>(())
     /// ```
     Embedding,
+    /// The rank of the properties of the [`Entity`] for a [`TextSearchQuery`].
+    ///
+    /// The more often the terms of the query appear in the properties, the higher the rank. This
+    /// is only available as a sorting key and deserializes from `["textSearchRank", "<query>"]`:
+    ///
+    /// ```rust
+    /// # use serde_json::json;
+    /// # use hash_graph_store::entity::EntityQueryPath;
+    /// let path =
+    ///     EntityQueryPath::deserialize_from_sorting_tokens(json!(["textSearchRank", "alice*"]))?;
+    /// assert_eq!(path.to_string(), "textSearchRank(alice:*)");
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    ///
+    /// [`Entity`]: graph_types::knowledge::entity::Entity
+    TextSearchRank(TextSearchQuery),
 }
 
 impl fmt::Display for EntityQueryPath<'_> {
@@ -455,6 +471,7 @@ impl fmt::Display for EntityQueryPath<'_> {
             Self::PropertyMetadata(Some(path)) => write!(fmt, "propertyMetadata.{path}"),
             Self::PropertyMetadata(None) => fmt.write_str("propertyMetadata"),
             Self::Embedding => fmt.write_str("embedding"),
+            Self::TextSearchRank(query) => write!(fmt, "textSearchRank({query})"),
             Self::EntityTypeEdge {
                 edge_kind: SharedEdgeKind::IsOfType,
                 path,
@@ -510,9 +527,10 @@ impl QueryPath for EntityQueryPath<'_> {
             | Self::PropertyMetadata(_)
             | Self::LeftEntityProvenance
             | Self::RightEntityProvenance => ParameterType::Any,
-            Self::EntityConfidence | Self::LeftEntityConfidence | Self::RightEntityConfidence => {
-                ParameterType::F64
-            }
+            Self::EntityConfidence
+            | Self::LeftEntityConfidence
+            | Self::RightEntityConfidence
+            | Self::TextSearchRank(_) => ParameterType::F64,
             Self::Embedding => ParameterType::Vector(Box::new(ParameterType::F64)),
             Self::Archived => ParameterType::Boolean,
             Self::EntityTypeEdge { path, .. } => path.expected_type(),
@@ -769,6 +787,7 @@ pub enum EntityQuerySortingToken {
     CreatedAtTransactionTime,
     CreatedAtDecisionTime,
     TypeTitle,
+    TextSearchRank,
 }
 
 /// Deserializes an [`EntityQueryPath`] from a string sequence.
@@ -781,7 +800,7 @@ impl EntityQuerySortingVisitor {
     pub(crate) const EXPECTING: &'static str =
         "one of `uuid`, `archived`, `properties`, `label`, `recordCreatedAtTransactionTime`, \
          `recordCreatedAtDecisionTime`, `createdAtTransactionTime`, `createdAtDecisionTime`, \
-         `typeTitle`";
+         `typeTitle`, `textSearchRank`";
 
     #[must_use]
     pub(crate) const fn new(position: usize) -> Self {
@@ -838,6 +857,13 @@ impl<'de> Visitor<'de> for EntityQuerySortingVisitor {
                 position: self.position,
             }
             .visit_seq(seq)?,
+            EntityQuerySortingToken::TextSearchRank => {
+                let query: String = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(self.position, &self))?;
+                self.position += 1;
+                EntityQueryPath::TextSearchRank(query.parse().map_err(de::Error::custom)?)
+            }
         })
     }
 }
@@ -888,6 +914,7 @@ impl<'de: 'p, 'p> EntityQueryPath<'p> {
             Self::Properties(path) => EntityQueryPath::Properties(path.map(JsonPath::into_owned)),
             Self::Label { inheritance_depth } => EntityQueryPath::Label { inheritance_depth },
             Self::Embedding => EntityQueryPath::Embedding,
+            Self::TextSearchRank(query) => EntityQueryPath::TextSearchRank(query),
             Self::EntityConfidence => EntityQueryPath::EntityConfidence,
             Self::LeftEntityConfidence => EntityQueryPath::LeftEntityConfidence,
             Self::LeftEntityProvenance => EntityQueryPath::LeftEntityProvenance,
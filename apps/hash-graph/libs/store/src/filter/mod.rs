@@ -1,5 +1,7 @@
 mod parameter;
 mod path;
+mod pattern;
+mod text_search;
 
 use alloc::borrow::Cow;
 use core::{borrow::Borrow, fmt, hash::Hash};
@@ -11,6 +13,7 @@ use graph_types::{
     knowledge::entity::{Entity, EntityId},
     ontology::{DataTypeProvider, DataTypeWithMetadata},
 };
+use hash_status::StatusCode;
 use serde::{Deserialize, de, de::IntoDeserializer};
 use type_system::{
     schema::DataTypeUuid,
@@ -20,6 +23,8 @@ use type_system::{
 pub use self::{
     parameter::{Parameter, ParameterConversionError, ParameterList, ParameterType},
     path::{JsonPath, PathToken},
+    pattern::{Pattern, PatternError},
+    text_search::{TextSearchPhrase, TextSearchQuery, TextSearchQueryError},
 };
 use crate::{
     data_type::DataTypeQueryPath,
@@ -111,6 +116,10 @@ pub enum Filter<'p, R: QueryRecord> {
         Option<FilterExpression<'p, R>>,
         Option<FilterExpression<'p, R>>,
     ),
+    /// Matches if the expression has a value, i.e. if it is neither missing nor JSON `null`.
+    Exists(FilterExpression<'p, R>),
+    /// Matches if the expression has no value, i.e. if it is either missing or JSON `null`.
+    IsNull(FilterExpression<'p, R>),
     Greater(FilterExpression<'p, R>, FilterExpression<'p, R>),
     GreaterOrEqual(FilterExpression<'p, R>, FilterExpression<'p, R>),
     Less(FilterExpression<'p, R>, FilterExpression<'p, R>),
@@ -120,11 +129,28 @@ pub enum Filter<'p, R: QueryRecord> {
         FilterExpression<'p, R>,
         FilterExpression<'p, R>,
     ),
-    #[serde(skip)]
+    /// Matches if the expression is equal to any of the values in the list.
     In(FilterExpression<'p, R>, ParameterList<'p>),
     StartsWith(FilterExpression<'p, R>, FilterExpression<'p, R>),
     EndsWith(FilterExpression<'p, R>, FilterExpression<'p, R>),
     ContainsSegment(FilterExpression<'p, R>, FilterExpression<'p, R>),
+    /// Matches if the text of the left expression matches the `LIKE` pattern of the right
+    /// expression, ignoring case.
+    ///
+    /// In the pattern `%` matches any sequence of characters and `_` matches a single character,
+    /// e.g. `%alice%` matches any text containing `alice`.
+    #[serde(rename = "ilike")]
+    ILike(FilterExpression<'p, R>, FilterExpression<'p, R>),
+    /// Matches if the text of the left expression matches the POSIX regular expression of the
+    /// right expression.
+    ///
+    /// See [`Pattern`] for the supported syntax.
+    Matches(FilterExpression<'p, R>, FilterExpression<'p, R>),
+    /// Matches if the text of the expression contains all terms of the [`TextSearchQuery`].
+    ///
+    /// Words are matched language-aware, i.e. stemmed and without stop words. If the expression
+    /// resolves to a JSON value, all string values inside of it are searched.
+    FullTextSearch(FilterExpression<'p, R>, TextSearchQuery),
 }
 
 impl<'p, R> Filter<'p, R>
@@ -284,7 +310,8 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`ParameterConversionError`] if conversion fails.
+    /// Returns [`ParameterConversionError`] if conversion fails or if the pattern of a
+    /// [`Filter::Matches`] is not a valid [`Pattern`].
     #[expect(
         clippy::too_many_lines,
         reason = "This is one big match statement. Structural queries has to be changed in the \
@@ -330,6 +357,11 @@ where
                     (..) => {}
                 }
             }
+            Self::Exists(expression) | Self::IsNull(expression) => {
+                expression
+                    .apply_parameter_conversion(data_type_provider)
+                    .await?;
+            }
             Self::Greater(lhs, rhs)
             | Self::GreaterOrEqual(lhs, rhs)
             | Self::Less(lhs, rhs)
@@ -392,24 +424,29 @@ where
             Self::In(lhs, rhs) => {
                 lhs.apply_parameter_conversion(data_type_provider).await?;
 
-                if let FilterExpression::Parameter {
-                    parameter,
-                    convert: _,
-                } = lhs
-                {
-                    match rhs {
+                match lhs {
+                    FilterExpression::Parameter {
+                        parameter,
+                        convert: _,
+                    } => match rhs {
                         ParameterList::DataTypeIds(_)
                         | ParameterList::PropertyTypeIds(_)
                         | ParameterList::EntityTypeIds(_)
                         | ParameterList::EntityEditionIds(_) => {
                             parameter.convert_to_parameter_type(ParameterType::Uuid)?;
                         }
+                        ParameterList::Parameters(_) => {}
+                    },
+                    FilterExpression::Path { path } => {
+                        rhs.convert_to_parameter_type(&path.expected_type())?;
                     }
                 }
             }
             Self::StartsWith(lhs, rhs)
             | Self::EndsWith(lhs, rhs)
-            | Self::ContainsSegment(lhs, rhs) => {
+            | Self::ContainsSegment(lhs, rhs)
+            | Self::ILike(lhs, rhs)
+            | Self::Matches(lhs, rhs) => {
                 lhs.apply_parameter_conversion(data_type_provider).await?;
                 rhs.apply_parameter_conversion(data_type_provider).await?;
 
@@ -429,12 +466,71 @@ where
                     parameter.convert_to_parameter_type(ParameterType::Text)?;
                 }
             }
+            Self::FullTextSearch(lhs, _) => {
+                lhs.apply_parameter_conversion(data_type_provider).await?;
+
+                if let FilterExpression::Parameter {
+                    parameter,
+                    convert: _,
+                } = lhs
+                {
+                    parameter.convert_to_parameter_type(ParameterType::Text)?;
+                }
+            }
+        }
+
+        // Postgres only reports an invalid pattern when the query is executed
+        if let Self::Matches(
+            _,
+            FilterExpression::Parameter {
+                parameter: Parameter::Text(pattern),
+                convert: _,
+            },
+        ) = self
+        {
+            pattern
+                .parse::<Pattern>()
+                .change_context_lazy(|| ParameterConversionError::InvalidPattern {
+                    pattern: pattern.clone().into_owned(),
+                })
+                .attach(StatusCode::InvalidArgument)?;
         }
 
         Ok(())
     }
 }
 
+impl<R: QueryRecord> Filter<'_, R> {
+    /// Returns `true` if the filter contains a [`Filter::CosineDistance`].
+    ///
+    /// Records matched by such a filter are ordered by their distance.
+    #[must_use]
+    pub fn contains_cosine_distance(&self) -> bool {
+        match self {
+            Self::All(filters) | Self::Any(filters) => {
+                filters.iter().any(Self::contains_cosine_distance)
+            }
+            Self::Not(filter) => filter.contains_cosine_distance(),
+            Self::CosineDistance(..) => true,
+            Self::Equal(..)
+            | Self::NotEqual(..)
+            | Self::Exists(..)
+            | Self::IsNull(..)
+            | Self::Greater(..)
+            | Self::GreaterOrEqual(..)
+            | Self::Less(..)
+            | Self::LessOrEqual(..)
+            | Self::In(..)
+            | Self::StartsWith(..)
+            | Self::EndsWith(..)
+            | Self::ContainsSegment(..)
+            | Self::ILike(..)
+            | Self::Matches(..)
+            | Self::FullTextSearch(..) => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct ParameterConversion {
@@ -661,4 +757,137 @@ mod tests {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn in_list() {
+        let entity_uuids = [Uuid::new_v4(), Uuid::new_v4()];
+
+        let expected = json!({
+          "in": [
+            { "path": ["uuid"] },
+            entity_uuids
+          ]
+        });
+
+        test_filter_representation(
+            &Filter::<Entity>::In(
+                FilterExpression::Path {
+                    path: EntityQueryPath::Uuid,
+                },
+                ParameterList::Parameters(entity_uuids.map(Parameter::Uuid).to_vec()),
+            ),
+            &expected,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn existence() {
+        let expected = json!({
+          "any": [
+            { "exists": { "path": ["description"] } },
+            { "isNull": { "path": ["title"] } }
+          ]
+        });
+
+        test_filter_representation(
+            &Filter::<DataTypeWithMetadata>::Any(vec![
+                Filter::Exists(FilterExpression::Path {
+                    path: DataTypeQueryPath::Description,
+                }),
+                Filter::IsNull(FilterExpression::Path {
+                    path: DataTypeQueryPath::Title,
+                }),
+            ]),
+            &expected,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn pattern_matching() {
+        let expected = json!({
+          "any": [
+            { "ilike": [
+              { "path": ["title"] },
+              { "parameter": "%text%" }
+            ]},
+            { "matches": [
+              { "path": ["title"] },
+              { "parameter": "^[a-z]+$" }
+            ]}
+          ]
+        });
+
+        test_filter_representation(
+            &Filter::<DataTypeWithMetadata>::Any(vec![
+                Filter::ILike(
+                    FilterExpression::Path {
+                        path: DataTypeQueryPath::Title,
+                    },
+                    FilterExpression::Parameter {
+                        parameter: Parameter::Text(Cow::Borrowed("%text%")),
+                        convert: None,
+                    },
+                ),
+                Filter::Matches(
+                    FilterExpression::Path {
+                        path: DataTypeQueryPath::Title,
+                    },
+                    FilterExpression::Parameter {
+                        parameter: Parameter::Text(Cow::Borrowed("^[a-z]+$")),
+                        convert: None,
+                    },
+                ),
+            ]),
+            &expected,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn invalid_in_list() {
+        let json = json!({
+          "in": [
+            { "path": ["uuid"] },
+            ["not a uuid"]
+          ]
+        });
+        let mut filter =
+            Filter::<Entity>::deserialize(&json).expect("could not deserialize filter");
+        let report = filter
+            .convert_parameters(&TestDataTypeProvider)
+            .await
+            .expect_err("converted invalid UUID");
+        assert!(matches!(
+            report.current_context(),
+            ParameterConversionError::InvalidParameterType {
+                expected: ParameterType::Uuid,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn full_text_search() {
+        let expected = json!({
+          "fullTextSearch": [
+            { "path": ["description"] },
+            "\"plain text\" val*"
+          ]
+        });
+
+        test_filter_representation(
+            &Filter::<DataTypeWithMetadata>::FullTextSearch(
+                FilterExpression::Path {
+                    path: DataTypeQueryPath::Description,
+                },
+                "\"plain text\" val*"
+                    .parse()
+                    .expect("could not parse text search query"),
+            ),
+            &expected,
+        )
+        .await;
+    }
 }
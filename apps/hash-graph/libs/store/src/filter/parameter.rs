@@ -3,7 +3,7 @@ use core::{fmt, mem, str::FromStr};
 
 use error_stack::{Context, Report, ResultExt, bail};
 use graph_types::{Embedding, knowledge::entity::EntityEditionId};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use serde_json::{Number as JsonNumber, Value as JsonValue};
 use temporal_versioning::Timestamp;
 use type_system::{
@@ -65,12 +65,52 @@ impl fmt::Display for ParameterType {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// A list of values used in [`Filter::In`].
+///
+/// Only [`ParameterList::Parameters`] can be deserialized, the other variants are used internally
+/// to pass lists of identifiers without converting them into [`Parameter`]s.
+///
+/// [`Filter::In`]: crate::filter::Filter::In
+#[derive(Debug, Clone, PartialEq)]
 pub enum ParameterList<'p> {
     DataTypeIds(&'p [DataTypeUuid]),
     PropertyTypeIds(&'p [PropertyTypeUuid]),
     EntityTypeIds(&'p [EntityTypeUuid]),
     EntityEditionIds(&'p [EntityEditionId]),
+    Parameters(Vec<Parameter<'p>>),
+}
+
+impl<'de> Deserialize<'de> for ParameterList<'_> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::deserialize(deserializer).map(Self::Parameters)
+    }
+}
+
+impl ParameterList<'_> {
+    /// Converts all [`Parameter`]s in the list to the `expected` type.
+    ///
+    /// The lists of identifiers are always compared to UUIDs, so they are not converted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterConversionError`] if any parameter cannot be converted.
+    pub(crate) fn convert_to_parameter_type(
+        &mut self,
+        expected: &ParameterType,
+    ) -> Result<(), Report<ParameterConversionError>> {
+        match self {
+            Self::DataTypeIds(_)
+            | Self::PropertyTypeIds(_)
+            | Self::EntityTypeIds(_)
+            | Self::EntityEditionIds(_) => Ok(()),
+            Self::Parameters(parameters) => parameters
+                .iter_mut()
+                .try_for_each(|parameter| parameter.convert_to_parameter_type(expected.clone())),
+        }
+    }
 }
 
 impl Parameter<'_> {
@@ -133,6 +173,9 @@ pub enum ParameterConversionError {
         actual: ActualParameterType,
         expected: ParameterType,
     },
+    InvalidPattern {
+        pattern: String,
+    },
 }
 
 impl fmt::Display for ParameterConversionError {
@@ -172,6 +215,9 @@ impl fmt::Display for ParameterConversionError {
             Self::NoConversionFound { from, to } => {
                 write!(fmt, "no conversion found from `{from}` to `{to}`")
             }
+            Self::InvalidPattern { pattern } => {
+                write!(fmt, "`{pattern}` is not a valid regular expression")
+            }
         }
     }
 }
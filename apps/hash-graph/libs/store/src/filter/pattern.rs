@@ -0,0 +1,344 @@
+use core::{
+    iter::Peekable,
+    str::{Chars, FromStr},
+};
+
+use regex::{Regex, RegexBuilder};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error)]
+pub enum PatternError {
+    #[error("{0} are not supported")]
+    Unsupported(&'static str),
+    #[error("`{0}` is not a valid embedded option")]
+    InvalidOption(char),
+    #[error("a quantifier can not be applied to a quantifier")]
+    RepeatedQuantifier,
+    #[error(transparent)]
+    Syntax(#[from] regex::Error),
+}
+
+/// A POSIX regular expression as used by [`Filter::Matches`].
+///
+/// Patterns follow the advanced regular expression (ARE) syntax of Postgres. Constructs without a
+/// counterpart in the memory store are rejected instead of being matched differently: back
+/// references, lookaround constraints, collating elements and equivalence classes, and basic or
+/// extended regular expressions selected by embedded options.
+///
+/// ```rust
+/// use hash_graph_store::filter::Pattern;
+///
+/// let pattern = "(?i)^\\malice\\M".parse::<Pattern>()?;
+/// assert!(pattern.is_match("Alice and Bob"));
+/// assert!(!pattern.is_match("Malice"));
+/// # Ok::<(), hash_graph_store::filter::PatternError>(())
+/// ```
+///
+/// [`Filter::Matches`]: super::Filter::Matches
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+}
+
+impl Pattern {
+    #[must_use]
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+/// Appends the escape following a backslash, translated to the syntax of [`Regex`].
+fn push_escape(
+    escape: Option<char>,
+    in_bracket: bool,
+    out: &mut String,
+) -> Result<(), PatternError> {
+    let translated = match escape {
+        Some('b') => "\\x08",
+        Some('B') => "\\\\",
+        Some('e') => "\\x1B",
+        Some('m') if !in_bracket => "\\b{start}",
+        Some('M') if !in_bracket => "\\b{end}",
+        Some('y') if !in_bracket => "\\b",
+        Some('Y') if !in_bracket => "\\B",
+        Some('Z') if !in_bracket => "\\z",
+        Some('1'..='9') if !in_bracket => {
+            return Err(PatternError::Unsupported("back references"));
+        }
+        // Other escapes have the same meaning in both syntaxes, a trailing backslash is reported
+        // by the regular expression parser
+        _ => {
+            out.push('\\');
+            out.extend(escape);
+            return Ok(());
+        }
+    };
+    out.push_str(translated);
+    Ok(())
+}
+
+/// Advances `chars` past `prefix` if they start with it.
+fn skip_prefix(chars: &mut Peekable<Chars<'_>>, prefix: &str) -> bool {
+    let mut lookahead = chars.clone();
+    if prefix.chars().all(|char| lookahead.next() == Some(char)) {
+        *chars = lookahead;
+        true
+    } else {
+        false
+    }
+}
+
+/// Translates the rest of a bracket expression, after its opening `[`.
+fn translate_bracket(
+    chars: &mut Peekable<Chars<'_>>,
+    out: &mut String,
+) -> Result<(), PatternError> {
+    out.push('[');
+    if chars.next_if_eq(&'^').is_some() {
+        out.push('^');
+    }
+    // A leading `]` is a member of the bracket expression
+    if chars.next_if_eq(&']').is_some() {
+        out.push_str("\\]");
+    }
+
+    let mut previous = None;
+    while let Some(char) = chars.next() {
+        match char {
+            ']' => {
+                out.push(']');
+                return Ok(());
+            }
+            '\\' => push_escape(chars.next(), true, out)?,
+            '[' => match chars.peek() {
+                Some(':') => {
+                    // Character classes such as `[:alpha:]` are supported by both syntaxes
+                    out.push('[');
+                    for char in chars.by_ref() {
+                        out.push(char);
+                        if char == ']' {
+                            break;
+                        }
+                    }
+                }
+                Some('.' | '=') => {
+                    return Err(PatternError::Unsupported(
+                        "collating elements and equivalence classes",
+                    ));
+                }
+                _ => out.push_str("\\["),
+            },
+            // Set operations of the memory store are literal characters in POSIX
+            '&' | '~' => {
+                out.push('\\');
+                out.push(char);
+            }
+            '-' if previous == Some('-') => out.push_str("\\-"),
+            char => out.push(char),
+        }
+        previous = Some(char);
+    }
+
+    // The missing `]` is reported by the regular expression parser
+    Ok(())
+}
+
+/// Translates the body of an advanced regular expression of Postgres into the syntax of [`Regex`].
+fn translate(pattern: &str) -> Result<String, PatternError> {
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    // Whether the previous atom is a quantifier and whether that quantifier is non-greedy
+    let mut quantifier = None;
+    while let Some(char) = chars.next() {
+        let is_bound = char == '{' && chars.peek().is_some_and(char::is_ascii_digit);
+        if matches!(char, '*' | '+' | '?') || is_bound {
+            match quantifier {
+                Some(false) if char == '?' => quantifier = Some(true),
+                // The memory store would nest the quantifiers, e.g. `a**`
+                Some(_) => return Err(PatternError::RepeatedQuantifier),
+                None => quantifier = Some(false),
+            }
+            out.push(char);
+            if is_bound {
+                for char in chars.by_ref() {
+                    out.push(char);
+                    if char == '}' {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        quantifier = None;
+
+        match char {
+            '\\' => push_escape(chars.next(), false, &mut out)?,
+            '[' if skip_prefix(&mut chars, "[:<:]]") => out.push_str("\\b{start}"),
+            '[' if skip_prefix(&mut chars, "[:>:]]") => out.push_str("\\b{end}"),
+            '[' => translate_bracket(&mut chars, &mut out)?,
+            '(' if chars.next_if_eq(&'?').is_some() => match chars.next() {
+                Some(':') => out.push_str("(?:"),
+                Some('=' | '!') => {
+                    return Err(PatternError::Unsupported("lookaround constraints"));
+                }
+                Some('<') if chars.next_if(|char| matches!(char, '=' | '!')).is_some() => {
+                    return Err(PatternError::Unsupported("lookaround constraints"));
+                }
+                // Postgres rejects named groups and embedded options after the start
+                _ => return Err(PatternError::Unsupported("named groups and inline options")),
+            },
+            // A brace which does not start a bound is a literal character in POSIX
+            '{' => out.push_str("\\{"),
+            char => out.push(char),
+        }
+    }
+    Ok(out)
+}
+
+/// The options of a pattern, which may be embedded at its start, e.g. `(?i)`.
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "The options correspond to the flags of the regular expression"
+)]
+struct Options {
+    case_insensitive: bool,
+    dot_matches_new_line: bool,
+    multi_line: bool,
+    ignore_whitespace: bool,
+    literal: bool,
+}
+
+impl Options {
+    /// Parses the embedded options at the start of `pattern` and returns the rest of the pattern.
+    fn parse(pattern: &str) -> Result<(Self, &str), PatternError> {
+        // Without embedded options `.` matches newlines and `^` and `$` only match at the ends
+        let mut options = Self {
+            case_insensitive: false,
+            dot_matches_new_line: true,
+            multi_line: false,
+            ignore_whitespace: false,
+            literal: false,
+        };
+
+        if let Some(literal) = pattern.strip_prefix("***=") {
+            options.literal = true;
+            return Ok((options, literal));
+        }
+        let pattern = pattern.strip_prefix("***:").unwrap_or(pattern);
+
+        let Some((embedded, rest)) = pattern
+            .strip_prefix("(?")
+            .and_then(|rest| rest.split_once(')'))
+            .filter(|(embedded, _)| embedded.chars().all(|char| char.is_ascii_alphabetic()))
+        else {
+            return Ok((options, pattern));
+        };
+
+        for option in embedded.chars() {
+            match option {
+                'c' => options.case_insensitive = false,
+                'i' => options.case_insensitive = true,
+                'n' | 'm' => (options.dot_matches_new_line, options.multi_line) = (false, true),
+                'p' => (options.dot_matches_new_line, options.multi_line) = (false, false),
+                's' => (options.dot_matches_new_line, options.multi_line) = (true, false),
+                'w' => (options.dot_matches_new_line, options.multi_line) = (true, true),
+                'x' => options.ignore_whitespace = true,
+                't' => options.ignore_whitespace = false,
+                'q' => options.literal = true,
+                'b' | 'e' => {
+                    return Err(PatternError::Unsupported(
+                        "basic and extended regular expressions",
+                    ));
+                }
+                option => return Err(PatternError::InvalidOption(option)),
+            }
+        }
+        Ok((options, rest))
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let (options, pattern) = Options::parse(pattern)?;
+        let translated = if options.literal {
+            regex::escape(pattern)
+        } else {
+            translate(pattern)?
+        };
+
+        Ok(Self {
+            regex: RegexBuilder::new(&translated)
+                .case_insensitive(options.case_insensitive)
+                .dot_matches_new_line(options.dot_matches_new_line)
+                .multi_line(options.multi_line)
+                .ignore_whitespace(options.ignore_whitespace && !options.literal)
+                .build()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        pattern
+            .parse::<Pattern>()
+            .expect("could not parse pattern")
+            .is_match(text)
+    }
+
+    #[test]
+    fn posix_semantics() {
+        assert!(matches("^a.c$", "a\nc"));
+        assert!(!matches("(?n)^a.c$", "a\nc"));
+        assert!(matches("(?n)^c$", "a\nc"));
+        assert!(matches("(?i)ALICE", "alice"));
+        assert!(matches("a{b}", "a{b}"));
+        assert!(matches("a{2}", "aa"));
+        assert!(matches("a{1,2}?b", "ab"));
+        assert!(matches("a+?b*?", "aa"));
+        assert!(matches("[]a]", "]"));
+        assert!(matches("[a[]", "["));
+        assert!(matches("[a&&b]", "&"));
+        assert!(matches("[[:digit:]]+", "42"));
+        assert!(matches("***=a.c", "a.c"));
+        assert!(!matches("***=a.c", "abc"));
+        assert!(matches("(?q)a+", "a+"));
+    }
+
+    #[test]
+    fn word_boundaries() {
+        assert!(matches("\\malice\\M", "hi alice!"));
+        assert!(!matches("\\malice\\M", "malice"));
+        assert!(matches("\\yalice\\y", "alice"));
+        assert!(!matches("al\\Yice", "al ice"));
+        assert!(matches("[[:<:]]bob[[:>:]]", "bob"));
+        assert!(!matches("[[:<:]]bob", "kabob"));
+        assert!(matches("a\\bc", "a\u{8}c"));
+    }
+
+    #[test]
+    fn invalid() {
+        for pattern in [
+            "(",
+            "[a",
+            "a**",
+            "a+??",
+            "\\",
+            "(a)\\1",
+            "a(?=b)",
+            "(?<name>a)",
+            "a(?i)b",
+            "[[.a.]]",
+            "(?b)a",
+            "(?z)a",
+        ] {
+            pattern
+                .parse::<Pattern>()
+                .expect_err("parsed an invalid pattern");
+        }
+    }
+}
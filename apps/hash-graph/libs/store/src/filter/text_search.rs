@@ -0,0 +1,249 @@
+#[cfg(feature = "postgres")]
+use core::error::Error;
+use core::{
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+#[cfg(feature = "postgres")]
+use bytes::BytesMut;
+#[cfg(feature = "postgres")]
+use postgres_types::{IsNull, ToSql, Type};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TextSearchQueryError {
+    #[error("the text search query does not contain any words")]
+    Empty,
+    #[error("the text search query contains an unterminated phrase")]
+    UnterminatedPhrase,
+}
+
+/// A sequence of words which have to appear next to each other in the searched text.
+///
+/// If `prefix` is set, the last word matches any word starting with it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextSearchPhrase {
+    words: Vec<String>,
+    prefix: bool,
+}
+
+impl TextSearchPhrase {
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (text, prefix) = text
+            .strip_suffix('*')
+            .map_or((text, false), |text| (text, true));
+
+        let words = text
+            .split(|character: char| !character.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+        (!words.is_empty()).then_some(Self { words, prefix })
+    }
+
+    #[must_use]
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    #[must_use]
+    pub const fn is_prefix(&self) -> bool {
+        self.prefix
+    }
+}
+
+impl fmt::Display for TextSearchPhrase {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let grouped = self.words.len() > 1;
+        if grouped {
+            fmt.write_char('(')?;
+        }
+        for (index, word) in self.words.iter().enumerate() {
+            if index > 0 {
+                fmt.write_str(" <-> ")?;
+            }
+            fmt.write_str(word)?;
+        }
+        if self.prefix {
+            fmt.write_str(":*")?;
+        }
+        if grouped {
+            fmt.write_char(')')?;
+        }
+        Ok(())
+    }
+}
+
+/// A language-aware full-text search query.
+///
+/// The query is parsed from a string of whitespace separated terms, all of which have to match:
+///
+/// - `"..."` matches the enclosed words as a phrase, i.e. next to each other and in order
+/// - a trailing `*` matches any word starting with the last word of the term
+/// - any other character than letters and digits separates words, so `full-text` is searched as the
+///   phrase `"full text"`
+///
+/// The [`Display`] implementation renders the query in the `tsquery` syntax used by Postgres.
+///
+/// ```rust
+/// use hash_graph_store::filter::TextSearchQuery;
+///
+/// let query = "\"Quick brown\" fox jump*".parse::<TextSearchQuery>()?;
+/// assert_eq!(query.to_string(), "(quick <-> brown) & fox & jump:*");
+/// # Ok::<(), hash_graph_store::filter::TextSearchQueryError>(())
+/// ```
+///
+/// [`Display`]: fmt::Display
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct TextSearchQuery {
+    phrases: Vec<TextSearchPhrase>,
+}
+
+impl TextSearchQuery {
+    #[must_use]
+    pub fn phrases(&self) -> &[TextSearchPhrase] {
+        &self.phrases
+    }
+}
+
+impl FromStr for TextSearchQuery {
+    type Err = TextSearchQueryError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        // Every quote toggles between unquoted text and a phrase
+        let mut phrases = Vec::new();
+        let mut in_phrase = true;
+        for segment in query.split('"') {
+            in_phrase = !in_phrase;
+            if in_phrase {
+                phrases.extend(TextSearchPhrase::parse(segment));
+            } else {
+                phrases.extend(
+                    segment
+                        .split_whitespace()
+                        .filter_map(TextSearchPhrase::parse),
+                );
+            }
+        }
+
+        if in_phrase {
+            return Err(TextSearchQueryError::UnterminatedPhrase);
+        }
+        if phrases.is_empty() {
+            return Err(TextSearchQueryError::Empty);
+        }
+        Ok(Self { phrases })
+    }
+}
+
+impl TryFrom<String> for TextSearchQuery {
+    type Error = TextSearchQueryError;
+
+    fn try_from(query: String) -> Result<Self, Self::Error> {
+        query.parse()
+    }
+}
+
+impl fmt::Display for TextSearchQuery {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, phrase) in self.phrases.iter().enumerate() {
+            if index > 0 {
+                fmt.write_str(" & ")?;
+            }
+            fmt::Display::fmt(phrase, fmt)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ToSql for TextSearchQuery {
+    // The query is passed as `TEXT` and has to be converted by `to_tsquery` in postgres.
+    postgres_types::accepts!(TEXT);
+
+    postgres_types::to_sql_checked!();
+
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>>
+    where
+        Self: Sized,
+    {
+        write!(out, "{self}")?;
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(query: &str) -> String {
+        query
+            .parse::<TextSearchQuery>()
+            .expect("could not parse text search query")
+            .to_string()
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(render("Alice"), "alice");
+        assert_eq!(render("  alice   bob "), "alice & bob");
+        assert_eq!(render("full-text search"), "(full <-> text) & search");
+    }
+
+    #[test]
+    fn phrases() {
+        assert_eq!(render(r#""alice bob""#), "(alice <-> bob)");
+        assert_eq!(
+            render(r#"say "hello world" twice"#),
+            "say & (hello <-> world) & twice"
+        );
+        assert_eq!(render(r#""single""#), "single");
+    }
+
+    #[test]
+    fn prefixes() {
+        assert_eq!(render("ali*"), "ali:*");
+        assert_eq!(render(r#""alice bo*""#), "(alice <-> bo:*)");
+        assert_eq!(render("bo* alice"), "bo:* & alice");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            "".parse::<TextSearchQuery>(),
+            Err(TextSearchQueryError::Empty)
+        );
+        assert_eq!(
+            "*".parse::<TextSearchQuery>(),
+            Err(TextSearchQueryError::Empty)
+        );
+        assert_eq!(
+            r#"" - ""#.parse::<TextSearchQuery>(),
+            Err(TextSearchQueryError::Empty)
+        );
+        assert_eq!(
+            r#""alice bob"#.parse::<TextSearchQuery>(),
+            Err(TextSearchQueryError::UnterminatedPhrase)
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            serde_json::from_value::<TextSearchQuery>(serde_json::json!("alice*"))
+                .expect("could not deserialize text search query"),
+            TextSearchQuery {
+                phrases: vec![TextSearchPhrase {
+                    words: vec!["alice".to_owned()],
+                    prefix: true,
+                }],
+            }
+        );
+        serde_json::from_value::<TextSearchQuery>(serde_json::json!(""))
+            .expect_err("deserialized an empty text search query");
+    }
+}
@@ -22,7 +22,7 @@ use error_stack::{Context, Report};
 use futures::TryStreamExt;
 use graph::{
     snapshot::SnapshotStore,
-    store::{PostgresStorePool, StorePool},
+    store::{MemoryStorePool, PostgresStorePool, StorePool},
 };
 use graph_api::rest::{middleware::span_trace_layer, status::status_to_response};
 use graph_type_defs::error::{ErrorInfo, StatusPayloads};
@@ -32,8 +32,56 @@ use tokio::io;
 use tokio_util::{codec::FramedRead, io::StreamReader};
 use uuid::Uuid;
 
+/// The store the test server operates on.
+enum TestStorePool {
+    Postgres(PostgresStorePool),
+    Memory(MemoryStorePool),
+}
+
+/// Acquires a store from the [`TestStorePool`] and evaluates `$call` with it.
+///
+/// Returns early with an error response if the store cannot be acquired.
+macro_rules! with_store {
+    ($pool:expr, $authorization_api:expr, | $store:ident | $call:expr) => {
+        match &**$pool {
+            TestStorePool::Postgres(pool) => {
+                let mut $store = pool
+                    .acquire($authorization_api, None)
+                    .await
+                    .map_err(store_acquisition_error)?;
+                $call
+            }
+            TestStorePool::Memory(pool) => {
+                let mut $store = pool
+                    .acquire($authorization_api, None)
+                    .await
+                    .map_err(store_acquisition_error)?;
+                $call
+            }
+        }
+    };
+}
+
 /// Create routes for interacting with entities.
 pub fn routes<A>(store_pool: PostgresStorePool, authorization_api: A) -> Router
+where
+    A: AuthorizationApi + ZanzibarBackend + Clone + Send + Sync + 'static,
+{
+    test_routes(TestStorePool::Postgres(store_pool), authorization_api)
+}
+
+/// Create routes for interacting with entities stored in an in-memory store.
+///
+/// Snapshots cannot be restored into the in-memory store, so `/snapshot` responds with
+/// [`StatusCode::Unimplemented`].
+pub fn memory_routes<A>(store_pool: MemoryStorePool, authorization_api: A) -> Router
+where
+    A: AuthorizationApi + ZanzibarBackend + Clone + Send + Sync + 'static,
+{
+    test_routes(TestStorePool::Memory(store_pool), authorization_api)
+}
+
+fn test_routes<A>(store_pool: TestStorePool, authorization_api: A) -> Router
 where
     A: AuthorizationApi + ZanzibarBackend + Clone + Send + Sync + 'static,
 {
@@ -89,13 +137,23 @@ fn report_to_response<C>(report: &Report<C>, code: impl Into<String>) -> Respons
 }
 
 async fn restore_snapshot<A>(
-    store_pool: Extension<Arc<PostgresStorePool>>,
+    store_pool: Extension<Arc<TestStorePool>>,
     authorization_api: Extension<Arc<A>>,
     snapshot: Body,
 ) -> Result<Response, Response>
 where
     A: AuthorizationApi + ZanzibarBackend + Send + Sync + Clone,
 {
+    let TestStorePool::Postgres(store_pool) = &**store_pool else {
+        return Err(status_to_response(Status::new(
+            StatusCode::Unimplemented,
+            Some("Snapshots cannot be restored into the in-memory store".to_owned()),
+            vec![StatusPayloads::ErrorInfo(ErrorInfo::new(
+                HashMap::new(),
+                "SNAPSHOT_RESTORATION_FAILURE".to_owned(),
+            ))],
+        )));
+    };
     let store = store_pool
         .acquire((**authorization_api).clone(), None)
         .await
@@ -128,7 +186,7 @@ where
 }
 
 async fn delete_accounts<A>(
-    pool: Extension<Arc<PostgresStorePool>>,
+    pool: Extension<Arc<TestStorePool>>,
     authorization_api: Extension<Arc<A>>,
 ) -> Result<Response, Response>
 where
@@ -156,15 +214,13 @@ where
             report_to_response(&report, "ACCOUNT_DELETION_FAILURE")
         })?;
 
-    pool.acquire(authorization_api, None)
-        .await
-        .map_err(store_acquisition_error)?
+    with_store!(pool, authorization_api, |store| store
         .delete_accounts(AccountId::new(Uuid::nil()))
-        .await
-        .map_err(|report| {
-            tracing::error!(error=?report, "Could not delete accounts");
-            report_to_response(&report, "ACCOUNT_DELETION_FAILURE")
-        })?;
+        .await)
+    .map_err(|report| {
+        tracing::error!(error=?report, "Could not delete accounts");
+        report_to_response(&report, "ACCOUNT_DELETION_FAILURE")
+    })?;
 
     Ok(status_to_response(Status::<()>::new(
         StatusCode::Ok,
@@ -174,7 +230,7 @@ where
 }
 
 async fn delete_data_types<A>(
-    pool: Extension<Arc<PostgresStorePool>>,
+    pool: Extension<Arc<TestStorePool>>,
     authorization_api: Extension<Arc<A>>,
 ) -> Result<Response, Response>
 where
@@ -192,26 +248,24 @@ where
             report_to_response(&report, "DATA_TYPE_DELETION_FAILURE")
         })?;
 
-    pool.acquire(authorization_api, None)
-        .await
-        .map_err(store_acquisition_error)?
+    with_store!(pool, authorization_api, |store| store
         .delete_data_types()
-        .await
-        .map_err(|report| {
-            tracing::error!(error=?report, "Could not delete data types");
-            status_to_response(Status::new(
-                report
-                    .request_ref::<StatusCode>()
-                    .copied()
-                    .next()
-                    .unwrap_or(StatusCode::Unknown),
-                Some(report.to_string()),
-                vec![StatusPayloads::ErrorInfo(ErrorInfo::new(
-                    HashMap::new(),
-                    "DATA_TYPE_DELETION_FAILURE".to_owned(),
-                ))],
-            ))
-        })?;
+        .await)
+    .map_err(|report| {
+        tracing::error!(error=?report, "Could not delete data types");
+        status_to_response(Status::new(
+            report
+                .request_ref::<StatusCode>()
+                .copied()
+                .next()
+                .unwrap_or(StatusCode::Unknown),
+            Some(report.to_string()),
+            vec![StatusPayloads::ErrorInfo(ErrorInfo::new(
+                HashMap::new(),
+                "DATA_TYPE_DELETION_FAILURE".to_owned(),
+            ))],
+        ))
+    })?;
 
     Ok(status_to_response(Status::<()>::new(
         StatusCode::Ok,
@@ -221,7 +275,7 @@ where
 }
 
 async fn delete_property_types<A>(
-    pool: Extension<Arc<PostgresStorePool>>,
+    pool: Extension<Arc<TestStorePool>>,
     authorization_api: Extension<Arc<A>>,
 ) -> Result<Response, Response>
 where
@@ -239,15 +293,13 @@ where
             report_to_response(&report, "PROPERTY_TYPE_DELETION_FAILURE")
         })?;
 
-    pool.acquire(authorization_api, None)
-        .await
-        .map_err(store_acquisition_error)?
+    with_store!(pool, authorization_api, |store| store
         .delete_property_types()
-        .await
-        .map_err(|report| {
-            tracing::error!(error=?report, "Could not delete property types");
-            report_to_response(&report, "PROPERTY_TYPE_DELETION_FAILURE")
-        })?;
+        .await)
+    .map_err(|report| {
+        tracing::error!(error=?report, "Could not delete property types");
+        report_to_response(&report, "PROPERTY_TYPE_DELETION_FAILURE")
+    })?;
 
     Ok(status_to_response(Status::<()>::new(
         StatusCode::Ok,
@@ -257,7 +309,7 @@ where
 }
 
 async fn delete_entity_types<A>(
-    pool: Extension<Arc<PostgresStorePool>>,
+    pool: Extension<Arc<TestStorePool>>,
     authorization_api: Extension<Arc<A>>,
 ) -> Result<Response, Response>
 where
@@ -275,15 +327,13 @@ where
             report_to_response(&report, "ENTITY_TYPE_DELETION_FAILURE")
         })?;
 
-    pool.acquire(authorization_api, None)
-        .await
-        .map_err(store_acquisition_error)?
+    with_store!(pool, authorization_api, |store| store
         .delete_entity_types()
-        .await
-        .map_err(|report| {
-            tracing::error!(error=?report, "Could not delete entity types");
-            report_to_response(&report, "ENTITY_TYPE_DELETION_FAILURE")
-        })?;
+        .await)
+    .map_err(|report| {
+        tracing::error!(error=?report, "Could not delete entity types");
+        report_to_response(&report, "ENTITY_TYPE_DELETION_FAILURE")
+    })?;
 
     Ok(status_to_response(Status::<()>::new(
         StatusCode::Ok,
@@ -293,7 +343,7 @@ where
 }
 
 async fn delete_entities<A>(
-    pool: Extension<Arc<PostgresStorePool>>,
+    pool: Extension<Arc<TestStorePool>>,
     authorization_api: Extension<Arc<A>>,
 ) -> Result<Response, Response>
 where
@@ -311,15 +361,13 @@ where
             report_to_response(&report, "ENTITY_DELETION_FAILURE")
         })?;
 
-    pool.acquire(authorization_api, None)
-        .await
-        .map_err(store_acquisition_error)?
+    with_store!(pool, authorization_api, |store| store
         .delete_entities()
-        .await
-        .map_err(|report| {
-            tracing::error!(error=?report, "Could not delete entities");
-            report_to_response(&report, "ENTITY_DELETION_FAILURE")
-        })?;
+        .await)
+    .map_err(|report| {
+        tracing::error!(error=?report, "Could not delete entities");
+        report_to_response(&report, "ENTITY_DELETION_FAILURE")
+    })?;
 
     Ok(status_to_response(Status::<()>::new(
         StatusCode::Ok,
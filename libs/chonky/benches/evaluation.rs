@@ -0,0 +1,120 @@
+#![expect(clippy::print_stderr, reason = "This is a benchmark")]
+
+//! Benchmarks and evaluates chunking strategies on a labelled corpus.
+//!
+//! Every strategy is timed by Criterion while chunking the corpus, embedding the chunks and
+//! searching them for every question, so the results can be analyzed and compared across runs by
+//! the `repo-chores` benches tooling. The quality of every strategy, the recall of the answers and
+//! the distribution of chunk sizes, is written as `evaluation.json` next to the folded stacks of
+//! its benchmark in the `out` directory and printed to stderr.
+//!
+//! Strategies are identified by stable names, as Criterion only compares benchmarks sharing the
+//! same ID.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chonky::{
+    chunker::{Chunker, Granularity, Overlap},
+    evaluation::{Corpus, Evaluator},
+    tokenizer::HeuristicTokenizer,
+};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use tracing_flame::FlameLayer;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+const GROUP_ID: &str = "chunking_evaluation";
+const CORPUS_ID: &str = "encyclopedia";
+const CORPUS: &[u8] = include_bytes!("fixtures/encyclopedia.json");
+
+/// Returns the directory of the artifacts of a benchmark.
+///
+/// This mirrors `generate_path` of `repo-chores`, which reads the folded stacks from there.
+fn artifact_directory(function_id: &str, value_str: &str) -> PathBuf {
+    let safe = |id: &str| {
+        id.replace(['?', '"', '/', '\\', '*', '<', '>', ':', '|', '^'], "_")
+            .trim()
+            .to_lowercase()
+    };
+    Path::new("out")
+        .join(safe(GROUP_ID))
+        .join(safe(function_id))
+        .join(safe(value_str))
+}
+
+fn setup_subscriber(directory: &Path) -> impl Drop {
+    struct Guard<A, B>(A, B);
+    #[expect(clippy::empty_drop)]
+    impl<A, B> Drop for Guard<A, B> {
+        fn drop(&mut self) {}
+    }
+
+    let (flame_layer, file_guard) = FlameLayer::with_file(directory.join("tracing.folded"))
+        .expect("could not create flame layer");
+    let subscriber = Registry::default().with(flame_layer);
+
+    let default_guard = tracing::subscriber::set_default(subscriber);
+    Guard(default_guard, file_guard)
+}
+
+fn strategies(tokenizer: HeuristicTokenizer) -> [(&'static str, Chunker<HeuristicTokenizer>); 5] {
+    [
+        ("sentence_64", Chunker::new(tokenizer, 64)),
+        ("sentence_128", Chunker::new(tokenizer, 128)),
+        ("sentence_256", Chunker::new(tokenizer, 256)),
+        (
+            "segment_128",
+            Chunker::new(tokenizer, 128).with_granularity(Granularity::Segment),
+        ),
+        (
+            "sentence_128_overlap_1",
+            Chunker::new(tokenizer, 128).with_overlap(Overlap::Units(1)),
+        ),
+    ]
+}
+
+fn bench_evaluation(crit: &mut Criterion) {
+    let corpus = Corpus::from_json(CORPUS).expect("invalid corpus");
+    let tokenizer = HeuristicTokenizer::default();
+    let evaluator = Evaluator::new(tokenizer);
+
+    let mut group = crit.benchmark_group(GROUP_ID);
+    for (function_id, chunker) in strategies(tokenizer) {
+        let chunk = |text: &str| {
+            let _span = tracing::info_span!("chunk").entered();
+            chunker.chunk_text(text)
+        };
+
+        let directory = artifact_directory(function_id, CORPUS_ID);
+        fs::create_dir_all(&directory).expect("could not create directory");
+        let evaluation = evaluator
+            .evaluate(&corpus, chunk)
+            .expect("could not evaluate chunks");
+        fs::write(directory.join("evaluation.json"), evaluation.to_json())
+            .expect("could not write evaluation");
+        eprintln!(
+            "{GROUP_ID}/{function_id}/{CORPUS_ID}: {}",
+            evaluation.to_json()
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new(function_id, CORPUS_ID),
+            &corpus,
+            |bencher, corpus| {
+                let _guard = setup_subscriber(&directory);
+                bencher.iter(|| {
+                    let _span = tracing::info_span!("evaluate").entered();
+                    evaluator
+                        .evaluate(corpus, chunk)
+                        .expect("could not evaluate chunks")
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_evaluation);
+criterion_main!(benches);
@@ -0,0 +1,224 @@
+use std::path::PathBuf;
+
+use chonky::{
+    MediaType, chunker::Granularity, code::Language, office::OfficeFormat, records::RecordFormat,
+    table::TableFormat,
+};
+use clap::{Parser, ValueEnum};
+
+/// The format of an input file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Markdown,
+    Html,
+    Pdf,
+    Docx,
+    Pptx,
+    Xlsx,
+    Eml,
+    Mbox,
+    Csv,
+    Json,
+    Jsonl,
+    Rust,
+    Typescript,
+    Tsx,
+    Python,
+}
+
+impl Format {
+    /// Returns the programming language of source code formats.
+    pub const fn language(self) -> Option<Language> {
+        match self {
+            Self::Text
+            | Self::Markdown
+            | Self::Html
+            | Self::Pdf
+            | Self::Docx
+            | Self::Pptx
+            | Self::Xlsx
+            | Self::Eml
+            | Self::Mbox
+            | Self::Csv
+            | Self::Json
+            | Self::Jsonl => None,
+            Self::Rust => Some(Language::Rust),
+            Self::Typescript => Some(Language::TypeScript),
+            Self::Tsx => Some(Language::Tsx),
+            Self::Python => Some(Language::Python),
+        }
+    }
+
+    /// Returns the format of structured data formats.
+    pub const fn record_format(self) -> Option<RecordFormat> {
+        match self {
+            Self::Csv => Some(RecordFormat::Csv),
+            Self::Json => Some(RecordFormat::Json),
+            Self::Jsonl => Some(RecordFormat::JsonLines),
+            Self::Text
+            | Self::Markdown
+            | Self::Html
+            | Self::Pdf
+            | Self::Docx
+            | Self::Pptx
+            | Self::Xlsx
+            | Self::Eml
+            | Self::Mbox
+            | Self::Rust
+            | Self::Typescript
+            | Self::Tsx
+            | Self::Python => None,
+        }
+    }
+}
+
+impl From<Language> for Format {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::Rust => Self::Rust,
+            Language::TypeScript => Self::Typescript,
+            Language::Tsx => Self::Tsx,
+            Language::Python => Self::Python,
+        }
+    }
+}
+
+impl From<OfficeFormat> for Format {
+    fn from(format: OfficeFormat) -> Self {
+        match format {
+            OfficeFormat::Docx => Self::Docx,
+            OfficeFormat::Pptx => Self::Pptx,
+            OfficeFormat::Xlsx => Self::Xlsx,
+        }
+    }
+}
+
+impl From<RecordFormat> for Format {
+    fn from(format: RecordFormat) -> Self {
+        match format {
+            RecordFormat::Csv => Self::Csv,
+            RecordFormat::Json => Self::Json,
+            RecordFormat::JsonLines => Self::Jsonl,
+        }
+    }
+}
+
+impl From<Format> for MediaType {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Text => Self::PlainText,
+            Format::Markdown => Self::Markdown,
+            Format::Html => Self::Html,
+            Format::Pdf => Self::Pdf,
+            Format::Docx => Self::Other(
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+                    .to_owned(),
+            ),
+            Format::Pptx => Self::Other(
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+                    .to_owned(),
+            ),
+            Format::Xlsx => Self::Other(
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet".to_owned(),
+            ),
+            Format::Eml => Self::Other("message/rfc822".to_owned()),
+            Format::Mbox => Self::Other("application/mbox".to_owned()),
+            Format::Csv => Self::Other("text/csv".to_owned()),
+            Format::Json => Self::Other("application/json".to_owned()),
+            Format::Jsonl => Self::Other("application/jsonl".to_owned()),
+            Format::Rust => Self::Other("text/x-rust".to_owned()),
+            Format::Typescript | Format::Tsx => Self::Other("text/x-typescript".to_owned()),
+            Format::Python => Self::Other("text/x-python".to_owned()),
+        }
+    }
+}
+
+/// The smallest unit packed into chunks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Unit {
+    Sentence,
+    Segment,
+}
+
+impl From<Unit> for Granularity {
+    fn from(unit: Unit) -> Self {
+        match unit {
+            Unit::Sentence => Self::Sentence,
+            Unit::Segment => Self::Segment,
+        }
+    }
+}
+
+/// How tables are rendered into chunks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum TableLayout {
+    Markdown,
+    Records,
+}
+
+impl From<TableLayout> for TableFormat {
+    fn from(layout: TableLayout) -> Self {
+        match layout {
+            TableLayout::Markdown => Self::Markdown,
+            TableLayout::Records => Self::Records,
+        }
+    }
+}
+
+/// Splits files into chunks and writes one JSON object per chunk.
+#[derive(Debug, Parser)]
+#[clap(version, about, long_about = None)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "command-line flags are independent switches"
+)]
+pub struct Args {
+    /// Files or directories to chunk. Directories are searched recursively.
+    #[arg(required = true)]
+    pub paths: Vec<PathBuf>,
+
+    /// Maximum number of tokens of a chunk.
+    #[arg(long, default_value_t = 512, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_tokens: u32,
+
+    /// Number of tokens consecutive chunks share.
+    #[arg(long, default_value_t = 0)]
+    pub overlap: u32,
+
+    /// The smallest unit packed into chunks.
+    #[arg(long, value_enum, default_value_t = Unit::Sentence)]
+    pub unit: Unit,
+
+    /// How tables are rendered: as Markdown tables or as one `header: value` record per row.
+    #[arg(long, value_enum, default_value_t = TableLayout::Markdown)]
+    pub table_format: TableLayout,
+
+    /// Prefixes the text of every chunk with its heading breadcrumb.
+    #[arg(long)]
+    pub breadcrumb_prefix: bool,
+
+    /// Detects the language of every paragraph and chunk to apply its sentence rules.
+    #[arg(long)]
+    pub detect_language: bool,
+
+    /// Format of all input files instead of detecting it from their extension and content.
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+
+    /// Attaches an embedding from the built-in hashing embedder to every chunk.
+    #[arg(long)]
+    pub embed: bool,
+
+    /// Number of dimensions of the attached embeddings.
+    #[arg(long, default_value_t = 3072, requires = "embed", value_parser = clap::value_parser!(u32).range(1..))]
+    pub dimensions: u32,
+
+    /// Prints statistics about every file to stderr.
+    #[arg(long)]
+    pub stats: bool,
+
+    /// File to write the chunks to instead of stdout.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
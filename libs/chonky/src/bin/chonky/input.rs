@@ -0,0 +1,268 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chonky::{
+    Chunk, Segment,
+    chunker::Chunker,
+    code::{CodeChunker, Language},
+    email::{self, Email},
+    html, markdown,
+    office::{OfficeFormat, docx, pptx, xlsx},
+    pdf,
+    records::{RecordChunker, RecordFormat},
+    segmentation::paragraphs,
+    tokenizer::Tokenizer,
+};
+use error_stack::{Report, ResultExt};
+
+use crate::args::Format;
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not read input")]
+pub struct InputError;
+
+/// Detects the format of a file from its extension or, if that is unknown, from its content.
+///
+/// Returns `None` for binary files in an unknown format.
+pub fn detect_format(path: &Path, source: &[u8]) -> Option<Format> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("md" | "markdown" | "mdx") => return Some(Format::Markdown),
+        Some("html" | "htm" | "xhtml") => return Some(Format::Html),
+        Some("pdf") => return Some(Format::Pdf),
+        Some("txt" | "text") => return Some(Format::Text),
+        Some("eml") => return Some(Format::Eml),
+        Some("mbox") => return Some(Format::Mbox),
+        Some(extension) => {
+            if let Some(format) = OfficeFormat::from_extension(extension) {
+                return Some(format.into());
+            }
+            if let Some(format) = RecordFormat::from_extension(extension) {
+                return Some(format.into());
+            }
+            if let Some(language) = Language::from_extension(extension) {
+                return Some(language.into());
+            }
+        }
+        None => {}
+    }
+
+    if source.starts_with(b"%PDF-") {
+        return Some(Format::Pdf);
+    }
+    // Office documents are ZIP archives, other archives are not supported
+    if source.starts_with(b"PK\x03\x04") {
+        return OfficeFormat::detect(source).map(Format::from);
+    }
+    if email::is_mbox(source) {
+        return Some(Format::Mbox);
+    }
+    let text = core::str::from_utf8(source).ok()?;
+    let head = text
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .get(..14)
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if head.starts_with("<!doctype html") || head.starts_with("<html") {
+        Some(Format::Html)
+    } else {
+        Some(Format::Text)
+    }
+}
+
+/// Returns the files at `paths`, searching directories recursively in lexicographic order.
+///
+/// # Errors
+///
+/// Returns [`InputError`] if a path does not exist or a directory could not be read.
+pub fn files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, Report<InputError>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)
+                .change_context(InputError)
+                .attach_printable_lazy(|| path.display().to_string())?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(InputError)
+                .attach_printable_lazy(|| path.display().to_string())?;
+            entries.sort();
+            files.extend(self::files(&entries)?);
+        } else if path.is_file() {
+            files.push(path.clone());
+        } else {
+            return Err(Report::new(InputError)
+                .attach_printable(format!("{} is not a file or directory", path.display())));
+        }
+    }
+    Ok(files)
+}
+
+/// Splits `source` into segments and chunks them.
+///
+/// Documents are chunked by `chunker`, source code by `code_chunker` and structured data by
+/// `record_chunker`. Returns the number of segments and the chunks.
+///
+/// # Errors
+///
+/// Returns [`InputError`] if the source is not valid in the given format.
+pub fn chunk<T>(
+    source: &[u8],
+    format: Format,
+    chunker: &Chunker<T>,
+    code_chunker: &CodeChunker<T>,
+    record_chunker: &RecordChunker<T>,
+) -> Result<(usize, Vec<Chunk>), Report<InputError>>
+where
+    T: Tokenizer,
+{
+    let text = || {
+        core::str::from_utf8(source)
+            .change_context(InputError)
+            .attach_printable("the file is not valid UTF-8")
+    };
+
+    if let Some(language) = format.language() {
+        let text = text()?;
+        let segments = code_chunker
+            .segment(text, language, &[] as &[&str])
+            .change_context(InputError)?;
+        let chunks = code_chunker
+            .chunk(text, language, &[] as &[&str])
+            .change_context(InputError)?;
+        return Ok((segments.len(), chunks));
+    }
+
+    if let Some(record_format) = format.record_format() {
+        let extraction = record_chunker
+            .extract(source, record_format)
+            .change_context(InputError)?;
+        let chunks = record_chunker
+            .chunk(extraction.text(), extraction.segments())
+            .change_context(InputError)?;
+        return Ok((extraction.segments().len(), chunks));
+    }
+
+    if matches!(format, Format::Eml | Format::Mbox) {
+        let emails = if format == Format::Mbox {
+            email::parse_mbox(source)
+        } else {
+            Email::parse(source).map(|email| vec![email])
+        }
+        .change_context(InputError)?;
+
+        // Every message is chunked on its own, so the ranges of a chunk refer to the text
+        // extracted from its message
+        let mut segment_count = 0;
+        let mut chunks = Vec::new();
+        for email in emails {
+            let (headers, extraction) = email.into_parts();
+            let (text, segments) = extraction.into_parts();
+            segment_count += segments.len();
+            for mut chunk in chunker.chunk(&text, &segments).change_context(InputError)? {
+                chunk.metadata.extend(headers.iter());
+                chunks.push(chunk);
+            }
+        }
+        return Ok((segment_count, chunks));
+    }
+
+    let (text, segments): (String, Vec<Segment>) = match format {
+        Format::Markdown => {
+            let text = text()?;
+            (text.to_owned(), markdown::segment(text))
+        }
+        Format::Html => html::extract(source).into_parts(),
+        Format::Pdf => pdf::extract(source)
+            .change_context(InputError)?
+            .into_parts(),
+        Format::Docx => docx::extract(source)
+            .change_context(InputError)?
+            .into_parts(),
+        Format::Pptx => pptx::extract(source)
+            .change_context(InputError)?
+            .into_parts(),
+        Format::Xlsx => xlsx::extract(source)
+            .change_context(InputError)?
+            .into_parts(),
+        // Emails, structured data and source code are chunked above
+        Format::Text
+        | Format::Eml
+        | Format::Mbox
+        | Format::Csv
+        | Format::Json
+        | Format::Jsonl
+        | Format::Rust
+        | Format::Typescript
+        | Format::Tsx
+        | Format::Python => {
+            let text = text()?;
+            (text.to_owned(), paragraphs(text))
+        }
+    };
+    let chunks = chunker.chunk(&text, &segments).change_context(InputError)?;
+    Ok((segments.len(), chunks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_formats() {
+        assert_eq!(
+            detect_format(Path::new("README.MD"), b"<html>"),
+            Some(Format::Markdown)
+        );
+        assert_eq!(
+            detect_format(Path::new("report"), b"%PDF-1.7\n"),
+            Some(Format::Pdf)
+        );
+        assert_eq!(
+            detect_format(Path::new("page"), b"\n  <!DOCTYPE html><html></html>"),
+            Some(Format::Html)
+        );
+        assert_eq!(
+            detect_format(Path::new("src/lib.rs"), b"pub mod store;"),
+            Some(Format::Rust)
+        );
+        assert_eq!(
+            detect_format(Path::new("notes"), b"Plain text."),
+            Some(Format::Text)
+        );
+        assert_eq!(
+            detect_format(Path::new("image"), b"\x89PNG\r\n\x1a\n\xff"),
+            None
+        );
+        assert_eq!(
+            detect_format(Path::new("Slides.PPTX"), b""),
+            Some(Format::Pptx)
+        );
+        assert_eq!(
+            detect_format(Path::new("archive"), b"PK\x03\x04\x14\x00"),
+            None
+        );
+        assert_eq!(
+            detect_format(Path::new("Inbox"), b"From ada@example.com Mon May 13"),
+            Some(Format::Mbox)
+        );
+        assert_eq!(
+            detect_format(Path::new("reply.eml"), b"From: ada@example.com"),
+            Some(Format::Eml)
+        );
+        assert_eq!(
+            detect_format(Path::new("export.TSV"), b"name\tcity"),
+            Some(Format::Csv)
+        );
+        assert_eq!(
+            detect_format(Path::new("events.ndjson"), b"{}"),
+            Some(Format::Jsonl)
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Command-line interface to split files into chunks.
+//!
+//! Every chunk is written as a single JSON object on its own line, which makes the output easy to
+//! inspect with tools such as `jq`.
+#![expect(
+    unreachable_pub,
+    reason = "This is a binary but as we want to document this crate as well this should be a \
+              warning instead"
+)]
+
+mod args;
+mod input;
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write as _},
+};
+
+use chonky::{
+    Chunk, MediaType,
+    chunker::{Chunker, Overlap},
+    code::CodeChunker,
+    embedding::HashingEmbedder,
+    language::LanguageDetector,
+    records::RecordChunker,
+    tokenizer::{HeuristicTokenizer, Tokenizer as _},
+};
+use clap::Parser as _;
+use error_stack::{Report, ResultExt};
+use serde_json::{Value as JsonValue, json};
+
+use self::args::Args;
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not chunk files")]
+pub struct CliError;
+
+/// Statistics about the chunks of a single file.
+#[derive(Debug)]
+struct Statistics {
+    segments: usize,
+    chunks: usize,
+    min_tokens: usize,
+    max_tokens: usize,
+    total_tokens: usize,
+}
+
+impl Statistics {
+    fn new(segments: usize, token_counts: &[usize]) -> Self {
+        Self {
+            segments,
+            chunks: token_counts.len(),
+            min_tokens: token_counts.iter().copied().min().unwrap_or_default(),
+            max_tokens: token_counts.iter().copied().max().unwrap_or_default(),
+            total_tokens: token_counts.iter().sum(),
+        }
+    }
+}
+
+fn chunk_json(
+    file: &str,
+    media_type: &MediaType,
+    index: usize,
+    chunk: &Chunk,
+    tokens: usize,
+    embedder: Option<HashingEmbedder>,
+) -> JsonValue {
+    let mut value = json!({
+        "file": file,
+        "media_type": media_type.essence(),
+        "index": index,
+        "text": chunk.text,
+        "content_hash": chunk.content_hash().to_string(),
+        "tokens": tokens,
+        "byte_range": [chunk.span.start.byte, chunk.span.end.byte],
+        "char_range": [chunk.span.start.char, chunk.span.end.char],
+        "segments": [chunk.segments.start, chunk.segments.end],
+        "metadata": chunk
+            .metadata
+            .iter()
+            .map(|(key, value)| (key.to_owned(), JsonValue::from(value)))
+            .collect::<serde_json::Map<_, _>>(),
+    });
+    if let Some(embedder) = embedder {
+        value["embedding"] = json!(embedder.embed_text(&chunk.text));
+    }
+    value
+}
+
+fn run(args: &Args) -> Result<(), Report<CliError>> {
+    let tokenizer = HeuristicTokenizer::default();
+    let mut chunker = Chunker::new(tokenizer, args.max_tokens as usize)
+        .with_overlap(match args.overlap {
+            0 => Overlap::None,
+            tokens => Overlap::Tokens(tokens as usize),
+        })
+        .with_granularity(args.unit.into())
+        .with_table_format(args.table_format.into())
+        .with_breadcrumb_prefix(args.breadcrumb_prefix);
+    if args.detect_language {
+        chunker = chunker.with_language_detector(LanguageDetector::new());
+    }
+    let code_chunker = CodeChunker::new(tokenizer, args.max_tokens as usize);
+    let record_chunker = RecordChunker::new(tokenizer, args.max_tokens as usize);
+    let embedder = args
+        .embed
+        .then(|| HashingEmbedder::new().with_dimensions(args.dimensions as usize));
+
+    let mut output: Box<dyn io::Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path)
+                .change_context(CliError)
+                .attach_printable_lazy(|| path.display().to_string())?,
+        )),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+    let mut stderr = io::stderr().lock();
+
+    for path in input::files(&args.paths).change_context(CliError)? {
+        let file = path.display().to_string();
+        let source = fs::read(&path)
+            .change_context(CliError)
+            .attach_printable_lazy(|| file.clone())?;
+        let Some(format) = args.format.or_else(|| input::detect_format(&path, &source)) else {
+            writeln!(stderr, "{file}: skipped, unknown format").change_context(CliError)?;
+            continue;
+        };
+
+        let media_type = MediaType::from(format);
+        let (segments, chunks) =
+            input::chunk(&source, format, &chunker, &code_chunker, &record_chunker)
+                .change_context(CliError)
+                .attach_printable_lazy(|| file.clone())?;
+
+        let mut token_counts = Vec::with_capacity(chunks.len());
+        for (index, chunk) in chunks.iter().enumerate() {
+            let tokens = tokenizer.count_tokens(&chunk.text);
+            token_counts.push(tokens);
+            serde_json::to_writer(
+                &mut output,
+                &chunk_json(&file, &media_type, index, chunk, tokens, embedder),
+            )
+            .change_context(CliError)?;
+            writeln!(output).change_context(CliError)?;
+        }
+
+        if args.stats {
+            let stats = Statistics::new(segments, &token_counts);
+            writeln!(
+                stderr,
+                "{file}: {media_type}, {} segments, {} chunks, {} tokens (min {}, max {})",
+                stats.segments,
+                stats.chunks,
+                stats.total_tokens,
+                stats.min_tokens,
+                stats.max_tokens
+            )
+            .change_context(CliError)?;
+        }
+    }
+
+    output.flush().change_context(CliError)
+}
+
+fn main() -> Result<(), Report<CliError>> {
+    run(&Args::parse())
+}
@@ -0,0 +1,195 @@
+use alloc::collections::VecDeque;
+use core::ops::Range;
+use std::collections::HashMap;
+
+use error_stack::{Report, ResultExt, ensure};
+
+use crate::{document::Metadata, hash::ContentHash, segment::Segment, span::Span};
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not create chunk from segments")]
+pub struct ChunkError;
+
+/// A piece of a document which is embedded and retrieved as a unit.
+///
+/// A chunk covers a contiguous range of [`Segment`]s. Its [`span`] covers the region of the
+/// document text those segments were taken from, so every chunk can be traced back to its source
+/// even if its [`text`] was rewritten, e.g. by prefixing it with contextual information.
+///
+/// [`span`]: Self::span
+/// [`text`]: Self::text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chunk {
+    pub text: String,
+    pub span: Span,
+    /// Indices of the segments this chunk was created from.
+    pub segments: Range<usize>,
+    pub metadata: Metadata,
+}
+
+impl Chunk {
+    /// Creates a chunk covering `segments[range]`.
+    ///
+    /// The text of the chunk is the text between the start of the first and the end of the last
+    /// segment, including anything in between.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkError`] if `range` is empty or out of bounds, or if the segments do not
+    /// refer to a valid region of `text`.
+    pub fn from_segments(
+        text: &str,
+        segments: &[Segment],
+        range: Range<usize>,
+    ) -> Result<Self, Report<ChunkError>> {
+        ensure!(!range.is_empty(), ChunkError);
+        let selected = segments
+            .get(range.clone())
+            .ok_or_else(|| Report::new(ChunkError))
+            .attach_printable_lazy(|| {
+                format!(
+                    "segment range {range:?} is out of bounds for {} segments",
+                    segments.len()
+                )
+            })?;
+
+        let span = selected
+            .iter()
+            .map(|segment| segment.span)
+            .reduce(Span::cover)
+            .unwrap_or_default();
+
+        Ok(Self {
+            text: span.slice(text).change_context(ChunkError)?.to_owned(),
+            span,
+            segments: range,
+            metadata: Metadata::new(),
+        })
+    }
+
+    /// Returns the hash of the text of this chunk.
+    ///
+    /// Two chunks with the same text have the same embedding, regardless of where they are located
+    /// in their documents, so the hash identifies the embedding of a chunk.
+    #[must_use]
+    pub fn content_hash(&self) -> ContentHash {
+        ContentHash::of(&self.text)
+    }
+}
+
+/// The difference between the chunks of two versions of a document.
+///
+/// Chunks are compared by their [`content_hash`], so a chunk which only moved inside of the
+/// document is unchanged. Only [`added`] chunks have to be embedded, the embeddings of
+/// [`unchanged`] chunks can be reused and the embeddings of [`removed`] chunks can be deleted.
+///
+/// Chunk boundaries only move inside of the section of a document which changed, as chunks never
+/// span more than one section. Edits to one section therefore leave the chunks of all other
+/// sections unchanged.
+///
+/// [`content_hash`]: Chunk::content_hash
+/// [`added`]: Self::added
+/// [`unchanged`]: Self::unchanged
+/// [`removed`]: Self::removed
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{chunk::ChunkDiff, chunker::Chunker, tokenizer::HeuristicTokenizer};
+///
+/// let chunker = Chunker::new(HeuristicTokenizer::default(), 6);
+/// let previous = chunker.chunk_text("First paragraph.\n\nSecond paragraph.")?;
+/// let current = chunker.chunk_text("First paragraph.\n\nChanged paragraph.")?;
+///
+/// let diff = ChunkDiff::new(previous, current);
+/// assert_eq!(diff.added[0].text, "Changed paragraph.");
+/// assert_eq!(diff.removed[0].text, "Second paragraph.");
+/// assert_eq!(diff.unchanged[0].text, "First paragraph.");
+/// # Ok::<(), error_stack::Report<chonky::chunker::ChunkingError>>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkDiff {
+    /// Chunks of the current version whose text does not occur in the previous version.
+    pub added: Vec<Chunk>,
+    /// Chunks of the previous version whose text does not occur in the current version anymore.
+    pub removed: Vec<Chunk>,
+    /// Chunks of the current version whose text also occurs in the previous version.
+    ///
+    /// The chunks carry their spans and segments in the current version.
+    pub unchanged: Vec<Chunk>,
+}
+
+impl ChunkDiff {
+    /// Compares the `previous` chunks of a document with its `current` chunks.
+    ///
+    /// Chunks with the same text are matched in order of their occurrence, so if a text occurs
+    /// more often in one version than in the other, the excess chunks are added or removed.
+    /// Added and unchanged chunks keep the order of `current`, removed chunks the order of
+    /// `previous`.
+    #[must_use]
+    pub fn new(previous: Vec<Chunk>, current: Vec<Chunk>) -> Self {
+        let mut previous = previous.into_iter().map(Some).collect::<Vec<_>>();
+        let mut occurrences = HashMap::<_, VecDeque<_>>::new();
+        for (index, chunk) in previous.iter().flatten().enumerate() {
+            occurrences
+                .entry(chunk.content_hash())
+                .or_default()
+                .push_back(index);
+        }
+
+        let mut diff = Self::default();
+        for chunk in current {
+            let matched = occurrences
+                .get_mut(&chunk.content_hash())
+                .and_then(VecDeque::pop_front);
+            match matched {
+                Some(index) => {
+                    previous[index] = None;
+                    diff.unchanged.push(chunk);
+                }
+                None => diff.added.push(chunk),
+            }
+        }
+        diff.removed = previous.into_iter().flatten().collect();
+
+        diff
+    }
+
+    /// Returns `true` if no chunk was added or removed.
+    #[must_use]
+    pub fn is_unchanged(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segment::SegmentKind;
+
+    #[test]
+    fn chunk_covers_segments() {
+        let text = "# Title\n\nFirst paragraph.\n\nSecond paragraph.";
+        let segments = [
+            Segment::new(
+                SegmentKind::Heading { level: 1 },
+                Span::from_byte_range(text, 0..7).expect("valid range"),
+            ),
+            Segment::new(
+                SegmentKind::Paragraph,
+                Span::from_byte_range(text, 9..25).expect("valid range"),
+            ),
+            Segment::new(
+                SegmentKind::Paragraph,
+                Span::from_byte_range(text, 27..44).expect("valid range"),
+            ),
+        ];
+
+        let chunk = Chunk::from_segments(text, &segments, 1..3).expect("valid chunk");
+        assert_eq!(chunk.text, "First paragraph.\n\nSecond paragraph.");
+        assert_eq!(chunk.span.byte_range(), 9..44);
+
+        let _: Report<_> = Chunk::from_segments(text, &segments, 2..4).expect_err("out of bounds");
+        let _: Report<_> = Chunk::from_segments(text, &segments, 1..1).expect_err("empty range");
+    }
+}
@@ -0,0 +1,521 @@
+use core::{fmt, iter, slice};
+
+use error_stack::{Report, ResultExt};
+use serde_json::{Value as JsonValue, json};
+
+use super::{Chunker, ChunkingError};
+use crate::{
+    chunk::Chunk,
+    document::Metadata,
+    segment::{Segment, SegmentKind},
+    segmentation::paragraphs,
+    span::Span,
+    tokenizer::Tokenizer,
+};
+
+/// The identifier of a chunk inside of a [`ChunkTree`].
+///
+/// Identifiers are the positions of the chunks in [`ChunkTree::nodes`], so they are only unique
+/// inside of a single tree.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChunkId(usize);
+
+impl ChunkId {
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl fmt::Display for ChunkId {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+/// The level of a chunk inside of a [`ChunkTree`], from the largest to the smallest chunks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChunkLevel {
+    /// All content below a heading, up to the next heading.
+    Section,
+    /// Consecutive segments of a section packed into the token budget of the [`Chunker`].
+    ParagraphGroup,
+    /// A run of consecutive sentences of a paragraph group.
+    SentenceWindow,
+}
+
+impl ChunkLevel {
+    /// Returns the name of the level, e.g. `paragraph_group`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Section => "section",
+            Self::ParagraphGroup => "paragraph_group",
+            Self::SentenceWindow => "sentence_window",
+        }
+    }
+}
+
+impl fmt::Display for ChunkLevel {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// A chunk together with its position in a [`ChunkTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkNode {
+    pub id: ChunkId,
+    pub level: ChunkLevel,
+    /// The chunk this chunk is part of, `None` for sections.
+    pub parent: Option<ChunkId>,
+    /// The chunks this chunk consists of, in document order.
+    pub children: Vec<ChunkId>,
+    pub chunk: Chunk,
+}
+
+impl ChunkNode {
+    fn to_json(&self) -> JsonValue {
+        json!({
+            "id": self.id.index(),
+            "level": self.level.as_str(),
+            "parent": self.parent.map(ChunkId::index),
+            "children": self.children.iter().map(|child| child.index()).collect::<Vec<_>>(),
+            "text": self.chunk.text,
+            "content_hash": self.chunk.content_hash().to_string(),
+            "byte_range": [self.chunk.span.start.byte, self.chunk.span.end.byte],
+            "char_range": [self.chunk.span.start.char, self.chunk.span.end.char],
+            "segments": [self.chunk.segments.start, self.chunk.segments.end],
+            "metadata": self
+                .chunk
+                .metadata
+                .iter()
+                .map(|(key, value)| (key.to_owned(), JsonValue::from(value)))
+                .collect::<serde_json::Map<_, _>>(),
+        })
+    }
+}
+
+/// A hierarchy of chunks of a document, created by the [`HierarchicalChunker`].
+///
+/// Sections form the roots of the tree, their children are paragraph groups, whose children are
+/// sentence windows. The children of a chunk cover the same text as the chunk itself, so a search
+/// can match small, precise chunks and pass their [`ancestors`] on as context.
+///
+/// Nodes are stored in pre-order: every chunk is followed by its descendants.
+///
+/// [`ancestors`]: Self::ancestors
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkTree {
+    nodes: Vec<ChunkNode>,
+}
+
+impl ChunkTree {
+    fn push(&mut self, level: ChunkLevel, parent: Option<ChunkId>, chunk: Chunk) -> ChunkId {
+        let id = ChunkId(self.nodes.len());
+        if let Some(parent) = parent {
+            self.nodes[parent.index()].children.push(id);
+        }
+        self.nodes.push(ChunkNode {
+            id,
+            level,
+            parent,
+            children: Vec::new(),
+            chunk,
+        });
+        id
+    }
+
+    /// Returns all chunks in pre-order.
+    #[must_use]
+    pub fn nodes(&self) -> &[ChunkNode] {
+        &self.nodes
+    }
+
+    /// Iterates over all chunks in pre-order.
+    pub fn iter(&self) -> slice::Iter<'_, ChunkNode> {
+        self.nodes.iter()
+    }
+
+    #[must_use]
+    pub fn get(&self, id: ChunkId) -> Option<&ChunkNode> {
+        self.nodes.get(id.index())
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the sections of the document.
+    pub fn roots(&self) -> impl Iterator<Item = &ChunkNode> {
+        self.nodes.iter().filter(|node| node.parent.is_none())
+    }
+
+    /// Returns all chunks of `level` in document order.
+    pub fn level(&self, level: ChunkLevel) -> impl Iterator<Item = &ChunkNode> {
+        self.nodes.iter().filter(move |node| node.level == level)
+    }
+
+    #[must_use]
+    pub fn parent(&self, id: ChunkId) -> Option<&ChunkNode> {
+        self.get(self.get(id)?.parent?)
+    }
+
+    pub fn children(&self, id: ChunkId) -> impl Iterator<Item = &ChunkNode> {
+        self.get(id)
+            .map(|node| node.children.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|child| self.get(*child))
+    }
+
+    /// Returns the parent of the chunk `id`, its parent and so on, up to the section.
+    pub fn ancestors(&self, id: ChunkId) -> impl Iterator<Item = &ChunkNode> {
+        iter::successors(self.parent(id), |node| {
+            node.parent.and_then(|parent| self.get(parent))
+        })
+    }
+
+    /// Returns all chunks below the chunk `id` in pre-order.
+    pub fn descendants(&self, id: ChunkId) -> impl Iterator<Item = &ChunkNode> {
+        // Descendants directly follow a chunk in pre-order, up to the next chunk on the same or a
+        // higher level
+        let nodes = self.get(id).map_or(&[] as &[ChunkNode], |node| {
+            let following = &self.nodes[id.index() + 1..];
+            let end = following
+                .iter()
+                .position(|other| other.level <= node.level)
+                .unwrap_or(following.len());
+            &following[..end]
+        });
+        nodes.iter()
+    }
+
+    /// Returns the other chunks with the same parent as the chunk `id` in document order.
+    ///
+    /// The siblings of a section are all other sections.
+    pub fn siblings(&self, id: ChunkId) -> impl Iterator<Item = &ChunkNode> {
+        let parent = self.get(id).map(|node| node.parent);
+        self.nodes
+            .iter()
+            .filter(move |node| Some(node.parent) == parent && node.id != id)
+    }
+
+    /// Serializes the tree to JSON as an array of chunks in pre-order.
+    ///
+    /// Every chunk is an object with its `id`, `level`, the ID of its `parent`, the IDs of its
+    /// `children`, its `text`, `content_hash`, `byte_range`, `char_range`, `segments` and
+    /// `metadata`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        JsonValue::Array(self.nodes.iter().map(ChunkNode::to_json).collect()).to_string()
+    }
+}
+
+impl<'t> IntoIterator for &'t ChunkTree {
+    type IntoIter = slice::Iter<'t, ChunkNode>;
+    type Item = &'t ChunkNode;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Splits a document into a hierarchy of sections, paragraph groups and sentence windows.
+///
+/// Paragraph groups are the chunks created by the wrapped [`Chunker`], with all of its settings
+/// applied. Their sections cover all groups below the same heading and are not limited in size.
+/// Every group is split into windows of [`window_size`] consecutive sentences. Tables and code
+/// blocks are not split into sentences, so their groups form a single window.
+///
+/// Small windows give precise matches when searching, while their groups and sections provide
+/// the surrounding context, see [`ChunkTree`].
+///
+/// [`window_size`]: Self::with_window_size
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{
+///     Extraction, SegmentKind,
+///     chunker::{ChunkLevel, Chunker, HierarchicalChunker},
+///     tokenizer::HeuristicTokenizer,
+/// };
+///
+/// let mut extraction = Extraction::new();
+/// extraction.push(SegmentKind::Heading { level: 1 }, "Cats");
+/// extraction.push(SegmentKind::Paragraph, "Cats sleep. Cats purr. Cats play.");
+///
+/// let chunker = HierarchicalChunker::new(Chunker::new(HeuristicTokenizer::default(), 64))
+///     .with_window_size(2);
+/// let tree = chunker.chunk(extraction.text(), extraction.segments())?;
+///
+/// let windows = tree
+///     .level(ChunkLevel::SentenceWindow)
+///     .map(|node| node.chunk.text.as_str())
+///     .collect::<Vec<_>>();
+/// assert_eq!(windows, ["Cats\n\nCats sleep.", "Cats purr. Cats play."]);
+///
+/// let window = tree
+///     .level(ChunkLevel::SentenceWindow)
+///     .nth(1)
+///     .expect("window");
+/// let context = tree
+///     .ancestors(window.id)
+///     .map(|node| node.level)
+///     .collect::<Vec<_>>();
+/// assert_eq!(context, [ChunkLevel::ParagraphGroup, ChunkLevel::Section]);
+/// # Ok::<(), error_stack::Report<chonky::chunker::ChunkingError>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct HierarchicalChunker<T> {
+    chunker: Chunker<T>,
+    window_size: usize,
+}
+
+impl<T> HierarchicalChunker<T>
+where
+    T: Tokenizer,
+{
+    pub const DEFAULT_WINDOW_SIZE: usize = 3;
+
+    /// Creates a hierarchical chunker which creates paragraph groups with `chunker`.
+    #[must_use]
+    pub const fn new(chunker: Chunker<T>) -> Self {
+        Self {
+            chunker,
+            window_size: Self::DEFAULT_WINDOW_SIZE,
+        }
+    }
+
+    /// Sets the number of sentences of a sentence window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is zero.
+    #[must_use]
+    pub const fn with_window_size(mut self, window_size: usize) -> Self {
+        assert!(window_size > 0, "a window needs at least one sentence");
+        self.window_size = window_size;
+        self
+    }
+
+    pub const fn chunker(&self) -> &Chunker<T> {
+        &self.chunker
+    }
+
+    /// Splits `group` into windows of consecutive sentences.
+    ///
+    /// Groups containing a table or code block are not split and form a single window.
+    fn windows(
+        &self,
+        text: &str,
+        segments: &[Segment],
+        group: &Chunk,
+    ) -> Result<Vec<Chunk>, Report<ChunkingError>> {
+        let mut sentences = Vec::new();
+        for index in group.segments.clone() {
+            let segment = &segments[index];
+            if matches!(
+                segment.kind,
+                SegmentKind::CodeBlock { .. } | SegmentKind::Table(_)
+            ) {
+                return Ok(vec![group.clone()]);
+            }
+
+            // Groups may start or end inside of a segment
+            let span = Span::new(
+                segment.span.start.max(group.span.start),
+                segment.span.end.min(group.span.end),
+            );
+            if span.is_empty() {
+                continue;
+            }
+            let content = span.slice(text).change_context(ChunkingError)?;
+            sentences.extend(
+                self.chunker
+                    .segmenter(segment, content)
+                    .sentences(content)
+                    .map(|sentence| (sentence.span.translate(span.start), index)),
+            );
+        }
+
+        let mut windows = Vec::new();
+        #[expect(
+            clippy::range_plus_one,
+            reason = "`Chunk::segments` is an exclusive range"
+        )]
+        for window in sentences.chunks(self.window_size) {
+            if let (Some(&(first, first_segment)), Some(&(last, last_segment))) =
+                (window.first(), window.last())
+            {
+                let span = first.cover(last);
+                windows.push(Chunk {
+                    text: span.slice(text).change_context(ChunkingError)?.to_owned(),
+                    span,
+                    segments: first_segment..last_segment + 1,
+                    metadata: Metadata::new(),
+                });
+            }
+        }
+        Ok(windows)
+    }
+
+    /// Splits the `segments` of `text` into a tree of chunks.
+    ///
+    /// `text` has to be the text the segments were created from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkingError`] if a segment does not refer to a valid region of `text`.
+    pub fn chunk(
+        &self,
+        text: &str,
+        segments: &[Segment],
+    ) -> Result<ChunkTree, Report<ChunkingError>> {
+        let mut tree = ChunkTree::default();
+
+        for section in self.chunker.section_units(text, segments)? {
+            let mut groups = Vec::new();
+            self.chunker
+                .pack(text, segments, &section.units, section.budget, &mut groups)
+                .change_context(ChunkingError)?;
+            let Some(span) = groups.iter().map(|group| group.span).reduce(Span::cover) else {
+                continue;
+            };
+
+            let mut windows = Vec::with_capacity(groups.len());
+            for group in &groups {
+                windows.push(self.windows(text, segments, group)?);
+            }
+
+            let first_segment = groups.iter().map(|group| group.segments.start).min();
+            let last_segment = groups.iter().map(|group| group.segments.end).max();
+            let mut section_chunk = Chunk {
+                text: span.slice(text).change_context(ChunkingError)?.to_owned(),
+                span,
+                segments: first_segment.unwrap_or_default()..last_segment.unwrap_or_default(),
+                metadata: Metadata::new(),
+            };
+            self.chunker
+                .annotate(&section, slice::from_mut(&mut section_chunk));
+            self.chunker.annotate(&section, &mut groups);
+
+            let section_id = tree.push(ChunkLevel::Section, None, section_chunk);
+            for (group, mut windows) in groups.into_iter().zip(windows) {
+                self.chunker.annotate(&section, &mut windows);
+                let group_id = tree.push(ChunkLevel::ParagraphGroup, Some(section_id), group);
+                for window in windows {
+                    tree.push(ChunkLevel::SentenceWindow, Some(group_id), window);
+                }
+            }
+        }
+
+        Ok(tree)
+    }
+
+    /// Splits `text` into paragraphs and chunks them into a tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkingError`] if the text could not be chunked.
+    pub fn chunk_text(&self, text: &str) -> Result<ChunkTree, Report<ChunkingError>> {
+        self.chunk(text, &paragraphs(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extraction::Extraction, tokenizer::HeuristicTokenizer};
+
+    fn tree() -> ChunkTree {
+        let mut extraction = Extraction::new();
+        extraction.push(SegmentKind::Heading { level: 1 }, "Guide");
+        extraction.push(SegmentKind::Paragraph, "One two. Three four. Five six.");
+        extraction.push(SegmentKind::Paragraph, "Seven eight. Nine ten.");
+        extraction.push(SegmentKind::Heading { level: 2 }, "Usage");
+        extraction.push(SegmentKind::Paragraph, "Run it. Stop it.");
+
+        HierarchicalChunker::new(Chunker::new(HeuristicTokenizer::default(), 10))
+            .with_window_size(2)
+            .chunk(extraction.text(), extraction.segments())
+            .expect("could be chunked")
+    }
+
+    fn texts<'t>(nodes: impl IntoIterator<Item = &'t ChunkNode>) -> Vec<&'t str> {
+        nodes
+            .into_iter()
+            .map(|node| node.chunk.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn builds_levels() {
+        let tree = tree();
+
+        assert_eq!(texts(tree.roots()), [
+            "Guide\n\nOne two. Three four. Five six.\n\nSeven eight. Nine ten.",
+            "Usage\n\nRun it. Stop it."
+        ]);
+        let sections = tree.roots().map(|node| node.id).collect::<Vec<_>>();
+        assert_eq!(texts(tree.children(sections[0])), [
+            "Guide\n\nOne two. Three four. Five six.",
+            "Seven eight. Nine ten."
+        ]);
+        assert_eq!(texts(tree.descendants(sections[1])), [
+            "Usage\n\nRun it. Stop it.",
+            "Usage\n\nRun it.",
+            "Stop it."
+        ]);
+        assert_eq!(
+            tree.roots()
+                .map(|node| node.chunk.metadata.get(super::super::BREADCRUMB_KEY))
+                .collect::<Vec<_>>(),
+            [Some("Guide"), Some("Guide > Usage")]
+        );
+
+        // Every chunk lies inside of its parent
+        for node in &tree {
+            if let Some(parent) = tree.parent(node.id) {
+                assert!(parent.chunk.span.contains(&node.chunk.span));
+                assert_eq!(parent.level, match node.level {
+                    ChunkLevel::SentenceWindow => ChunkLevel::ParagraphGroup,
+                    _ => ChunkLevel::Section,
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn walks_tree() {
+        let tree = tree();
+        let window = tree
+            .level(ChunkLevel::SentenceWindow)
+            .find(|node| node.chunk.text == "Three four. Five six.")
+            .expect("window exists");
+
+        assert_eq!(
+            tree.ancestors(window.id)
+                .map(|node| node.level)
+                .collect::<Vec<_>>(),
+            [ChunkLevel::ParagraphGroup, ChunkLevel::Section]
+        );
+        assert_eq!(texts(tree.siblings(window.id)), ["Guide\n\nOne two."]);
+        assert_eq!(tree.siblings(tree.nodes()[0].id).count(), 1);
+
+        let json = serde_json::from_str::<serde_json::Value>(&tree.to_json()).expect("valid JSON");
+        assert_eq!(json.as_array().map(Vec::len), Some(tree.len()));
+        assert_eq!(json[window.id.index()]["level"], "sentence_window");
+        assert_eq!(
+            json[window.id.index()]["parent"],
+            window.parent.map(ChunkId::index).expect("has parent")
+        );
+    }
+}
@@ -0,0 +1,851 @@
+//! Packing of segments into chunks.
+
+mod hierarchy;
+mod section;
+mod semantic;
+mod stream;
+
+use core::ops::Range;
+
+use error_stack::{Report, ResultExt};
+use unicode_segmentation::UnicodeSegmentation;
+
+use self::section::sections;
+pub use self::{
+    hierarchy::{ChunkId, ChunkLevel, ChunkNode, ChunkTree, HierarchicalChunker},
+    semantic::{BreakpointThreshold, SemanticChunker},
+    stream::StreamingChunker,
+};
+use crate::{
+    chunk::{Chunk, ChunkDiff},
+    document::Metadata,
+    language::{LANGUAGE_KEY, Language, LanguageDetector},
+    segment::{Segment, SegmentKind},
+    segmentation::{SentenceSegmenter, paragraphs},
+    span::{Offset, OffsetCursor, Span, SpanError},
+    table::{Table, TableFormat},
+    tokenizer::Tokenizer,
+};
+
+/// Metadata key of a chunk's heading breadcrumb, e.g. `Setup > Local HASH > Docker`.
+pub const BREADCRUMB_KEY: &str = "breadcrumb";
+
+const BREADCRUMB_SEPARATOR: &str = " > ";
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not chunk document")]
+pub struct ChunkingError;
+
+/// The smallest unit the [`Chunker`] packs into chunks.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Granularity {
+    /// Chunks are packed from single sentences.
+    #[default]
+    Sentence,
+    /// Chunks are packed from whole segments, e.g. paragraphs. Segments exceeding the budget are
+    /// split into sentences.
+    Segment,
+}
+
+/// How much content consecutive chunks share.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Overlap {
+    /// Consecutive chunks do not share content.
+    #[default]
+    None,
+    /// A chunk repeats as many trailing units of the previous chunk as fit into this number of
+    /// tokens.
+    Tokens(usize),
+    /// A chunk repeats this number of trailing units of the previous chunk, e.g. sentences when
+    /// packing with [`Granularity::Sentence`].
+    Units(usize),
+}
+
+/// A unit to be packed, always within the token budget.
+#[derive(Debug, Copy, Clone)]
+struct PackUnit {
+    span: Span,
+    segment: usize,
+    tokens: usize,
+}
+
+/// The units of a section of a document.
+#[derive(Debug)]
+struct SectionUnits {
+    units: Vec<PackUnit>,
+    budget: usize,
+    breadcrumb: String,
+    /// The breadcrumb without the section's own heading.
+    parent_breadcrumb: String,
+    /// The span of the heading opening the section, if any.
+    heading: Option<Span>,
+}
+
+/// Greedily packs units of a document into chunks under a maximum token budget.
+///
+/// Units are added to a chunk until the next unit would exceed the budget. Units which exceed the
+/// budget on their own are split further, first into sentences, then into words and, as a last
+/// resort, at character boundaries, so that no chunk exceeds the budget. The only exception are
+/// code blocks, which are never split and may form a chunk exceeding the budget.
+///
+/// Tables always form chunks of their own, rendered according to the [`TableFormat`]. Tables
+/// exceeding the budget are split into groups of rows, each rendered with the header of the
+/// table. Single rows are never split.
+///
+/// Token counts of a chunk are the sum of the token counts of its units, which is exact for
+/// whitespace-based tokenizers and a close approximation for BPE-based ones.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{
+///     chunker::{Chunker, Overlap},
+///     tokenizer::HeuristicTokenizer,
+/// };
+///
+/// let chunker = Chunker::new(HeuristicTokenizer::default(), 10).with_overlap(Overlap::Units(1));
+/// let chunks = chunker
+///     .chunk_text("One two three. Four five six. Seven eight nine. Ten.")
+///     .expect("could not chunk text");
+///
+/// let texts = chunks
+///     .iter()
+///     .map(|chunk| chunk.text.as_str())
+///     .collect::<Vec<_>>();
+/// assert_eq!(texts, [
+///     "One two three. Four five six.",
+///     "Four five six. Seven eight nine. Ten.",
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Chunker<T> {
+    tokenizer: T,
+    max_tokens: usize,
+    overlap: Overlap,
+    granularity: Granularity,
+    segmenter: SentenceSegmenter,
+    language_detector: Option<LanguageDetector>,
+    language_segmenters: Vec<(Language, SentenceSegmenter)>,
+    breadcrumb_prefix: bool,
+    table_format: TableFormat,
+}
+
+impl<T> Chunker<T>
+where
+    T: Tokenizer,
+{
+    /// Creates a chunker which creates chunks of at most `max_tokens` tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tokens` is zero.
+    #[must_use]
+    pub fn new(tokenizer: T, max_tokens: usize) -> Self {
+        assert!(max_tokens > 0, "the token budget has to be positive");
+
+        Self {
+            tokenizer,
+            max_tokens,
+            overlap: Overlap::None,
+            granularity: Granularity::Sentence,
+            segmenter: SentenceSegmenter::default(),
+            language_detector: None,
+            language_segmenters: Vec::new(),
+            breadcrumb_prefix: false,
+            table_format: TableFormat::Markdown,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_overlap(mut self, overlap: Overlap) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_granularity(mut self, granularity: Granularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    /// Prefixes the text of every chunk with its breadcrumb.
+    ///
+    /// The heading a chunk starts with is left out of its prefix. The prefix counts towards the
+    /// token budget.
+    #[must_use]
+    pub const fn with_breadcrumb_prefix(mut self, breadcrumb_prefix: bool) -> Self {
+        self.breadcrumb_prefix = breadcrumb_prefix;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_table_format(mut self, table_format: TableFormat) -> Self {
+        self.table_format = table_format;
+        self
+    }
+
+    #[must_use]
+    pub fn with_sentence_segmenter(mut self, segmenter: SentenceSegmenter) -> Self {
+        self.segmenter = segmenter;
+        self
+    }
+
+    /// Detects the language of every segment and chunk with `detector`.
+    ///
+    /// Segments are split into sentences with the rules of their language, see
+    /// [`SentenceSegmenter::for_language`]. The language is taken from the [`LANGUAGE_KEY`]
+    /// metadata entry of a segment if present and detected otherwise. Segments whose language is
+    /// unknown are split with the segmenter set by [`with_sentence_segmenter`].
+    ///
+    /// Every chunk records the language detected from its text in its metadata, see
+    /// [`Detection::annotate`].
+    ///
+    /// [`with_sentence_segmenter`]: Self::with_sentence_segmenter
+    /// [`Detection::annotate`]: crate::language::Detection::annotate
+    #[must_use]
+    pub fn with_language_detector(mut self, detector: LanguageDetector) -> Self {
+        self.language_segmenters = detector
+            .languages()
+            .iter()
+            .map(|&language| (language, SentenceSegmenter::for_language(language)))
+            .collect();
+        self.language_detector = Some(detector);
+        self
+    }
+
+    pub const fn tokenizer(&self) -> &T {
+        &self.tokenizer
+    }
+
+    pub const fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+
+    /// Returns the segmenter to split `segment`, whose text is `segment_text`, into sentences.
+    fn segmenter(&self, segment: &Segment, segment_text: &str) -> &SentenceSegmenter {
+        let Some(detector) = &self.language_detector else {
+            return &self.segmenter;
+        };
+
+        let language = segment
+            .metadata
+            .get(LANGUAGE_KEY)
+            .and_then(Language::from_code)
+            .or_else(|| {
+                detector
+                    .detect(segment_text)
+                    .map(|detection| detection.language)
+            });
+        language
+            .and_then(|language| {
+                self.language_segmenters
+                    .iter()
+                    .find(|(candidate, _)| *candidate == language)
+            })
+            .map_or(&self.segmenter, |(_, segmenter)| segmenter)
+    }
+
+    /// Splits `text`, which starts at `base`, into pieces which fit into `budget`.
+    ///
+    /// The text is split at word boundaries. Single words which exceed the budget are split at
+    /// character boundaries.
+    fn split_to_budget(
+        &self,
+        text: &str,
+        base: Offset,
+        segment: usize,
+        budget: usize,
+        units: &mut Vec<PackUnit>,
+    ) {
+        let mut cursor = OffsetCursor::new(text);
+        let mut push = |units: &mut Vec<PackUnit>, start: usize, end: usize| {
+            let Some(window) = text.get(start..end) else {
+                return;
+            };
+            let content = window.trim();
+            if content.is_empty() {
+                return;
+            }
+            let start = start + (window.len() - window.trim_start().len());
+            units.push(PackUnit {
+                span: cursor.span(start..start + content.len()).translate(base),
+                segment,
+                tokens: self.tokenizer.count_tokens(content),
+            });
+        };
+
+        let mut window_start = 0;
+        let mut window_tokens = 0;
+        for (start, piece) in text.split_word_bound_indices() {
+            let tokens = self.tokenizer.count_tokens(piece);
+            if tokens <= budget && window_tokens + tokens <= budget {
+                window_tokens += tokens;
+                continue;
+            }
+
+            push(units, window_start, start);
+            window_start = start;
+            window_tokens = tokens;
+
+            if tokens > budget {
+                let mut rest = piece;
+                while !rest.is_empty() {
+                    let length = self.longest_prefix_within_budget(rest, budget);
+                    let offset = start + (piece.len() - rest.len());
+                    push(units, offset, offset + length);
+                    rest = rest.get(length..).unwrap_or_default();
+                }
+                window_start = start + piece.len();
+                window_tokens = 0;
+            }
+        }
+        push(units, window_start, text.len());
+    }
+
+    /// Returns the length in bytes of the longest prefix of `text` fitting into `budget`.
+    ///
+    /// At least one character is always returned to guarantee progress.
+    fn longest_prefix_within_budget(&self, text: &str, budget: usize) -> usize {
+        let boundaries = text
+            .char_indices()
+            .map(|(index, _)| index)
+            .skip(1)
+            .chain([text.len()])
+            .collect::<Vec<_>>();
+
+        let fitting = boundaries.partition_point(|&end| {
+            self.tokenizer
+                .count_tokens(text.get(..end).unwrap_or_default())
+                <= budget
+        });
+        boundaries[fitting.saturating_sub(1)]
+    }
+
+    /// Splits `segments[range]` into units which fit into `budget`.
+    ///
+    /// Code blocks and tables are never split, even if they exceed the budget. Tables are split
+    /// into groups of rows when they are packed.
+    fn units(
+        &self,
+        text: &str,
+        segments: &[Segment],
+        range: Range<usize>,
+        budget: usize,
+    ) -> Result<Vec<PackUnit>, Report<SpanError>> {
+        let mut units = Vec::new();
+
+        for index in range {
+            let segment = &segments[index];
+            let segment_text = segment.span.slice(text)?;
+
+            let atomic = matches!(
+                segment.kind,
+                SegmentKind::CodeBlock { .. } | SegmentKind::Table(_)
+            );
+            if atomic || self.granularity == Granularity::Segment {
+                let tokens = self.tokenizer.count_tokens(segment_text);
+                if atomic || tokens <= budget {
+                    units.push(PackUnit {
+                        span: segment.span,
+                        segment: index,
+                        tokens,
+                    });
+                    continue;
+                }
+            }
+
+            for sentence in self
+                .segmenter(segment, segment_text)
+                .sentences(segment_text)
+            {
+                let tokens = self.tokenizer.count_tokens(sentence.text);
+                let span = sentence.span.translate(segment.span.start);
+                if tokens <= budget {
+                    units.push(PackUnit {
+                        span,
+                        segment: index,
+                        tokens,
+                    });
+                } else {
+                    self.split_to_budget(sentence.text, span.start, index, budget, &mut units);
+                }
+            }
+        }
+
+        Ok(units)
+    }
+
+    /// Returns the index of the first unit of the chunk following the chunk `units[start..end]`.
+    fn next_start(&self, units: &[PackUnit], start: usize, end: usize, budget: usize) -> usize {
+        let mut next = match self.overlap {
+            Overlap::None => end,
+            Overlap::Units(count) => end.saturating_sub(count),
+            Overlap::Tokens(overlap) => {
+                let mut next = end;
+                let mut tokens = 0;
+                while next > start && tokens + units[next - 1].tokens <= overlap {
+                    next -= 1;
+                    tokens += units[next].tokens;
+                }
+                next
+            }
+        }
+        .max(start + 1);
+
+        // Make sure the next chunk is able to include at least one new unit
+        if let Some(following) = units.get(end) {
+            let mut tokens = units[next..end]
+                .iter()
+                .map(|unit| unit.tokens)
+                .sum::<usize>();
+            while next < end && tokens + following.tokens > budget {
+                tokens -= units[next].tokens;
+                next += 1;
+            }
+        }
+
+        next
+    }
+
+    /// Splits the table of `unit` into groups of rows of at most `budget` tokens.
+    ///
+    /// Every group is rendered with the header of the table and forms a chunk spanning the whole
+    /// table.
+    fn pack_table(&self, table: &Table, unit: PackUnit, budget: usize, chunks: &mut Vec<Chunk>) {
+        let header = table.render_header(self.table_format);
+        let header_tokens = header
+            .as_deref()
+            .map_or(0, |header| self.tokenizer.count_tokens(header));
+        let rows = (0..table.body().len())
+            .map(|index| {
+                let row = table.render_row(self.table_format, index);
+                let tokens = self.tokenizer.count_tokens(&row);
+                (row, tokens)
+            })
+            .collect::<Vec<_>>();
+
+        let mut push = |lines: Vec<String>| {
+            #[expect(
+                clippy::range_plus_one,
+                reason = "`Chunk::segments` is a half-open range"
+            )]
+            chunks.push(Chunk {
+                text: header
+                    .iter()
+                    .cloned()
+                    .chain(lines)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                span: unit.span,
+                segments: unit.segment..unit.segment + 1,
+                metadata: Metadata::new(),
+            });
+        };
+
+        let mut group = Vec::new();
+        let mut tokens = header_tokens;
+        for (row, row_tokens) in rows {
+            if !group.is_empty() && tokens + row_tokens > budget {
+                push(core::mem::take(&mut group));
+                tokens = header_tokens;
+            }
+            group.push(row);
+            tokens += row_tokens;
+        }
+        if !group.is_empty() || table.body().is_empty() {
+            push(group);
+        }
+    }
+
+    /// Greedily packs `units` into chunks of at most `budget` tokens.
+    ///
+    /// Units of tables form chunks of their own.
+    fn pack(
+        &self,
+        text: &str,
+        segments: &[Segment],
+        units: &[PackUnit],
+        budget: usize,
+        chunks: &mut Vec<Chunk>,
+    ) -> Result<(), Report<SpanError>> {
+        let table = |unit: &PackUnit| match &segments[unit.segment].kind {
+            SegmentKind::Table(table) => Some(table),
+            _ => None,
+        };
+
+        let mut start = 0;
+        while start < units.len() {
+            if let Some(table) = table(&units[start]) {
+                self.pack_table(table, units[start], budget, chunks);
+                start += 1;
+                continue;
+            }
+
+            let mut end = start + 1;
+            let mut tokens = units[start].tokens;
+            while let Some(unit) = units.get(end) {
+                if table(unit).is_some() || tokens + unit.tokens > budget {
+                    break;
+                }
+                tokens += unit.tokens;
+                end += 1;
+            }
+
+            let (first, last) = (units[start], units[end - 1]);
+            let span = first.span.cover(last.span);
+            #[expect(
+                clippy::range_plus_one,
+                reason = "`Chunk::segments` is a half-open range"
+            )]
+            chunks.push(Chunk {
+                text: span.slice(text)?.to_owned(),
+                span,
+                segments: first.segment..last.segment + 1,
+                metadata: Metadata::new(),
+            });
+
+            if end == units.len() {
+                break;
+            }
+            // Tables are not repeated as overlap
+            start = if table(&units[end]).is_some() {
+                end
+            } else {
+                self.next_start(units, start, end, budget)
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Splits the `segments` of `text` into sections and returns the units of every section.
+    ///
+    /// Sections consisting only of a heading are skipped if the following section is nested in
+    /// them, as the heading is part of the breadcrumb of that section.
+    fn section_units(
+        &self,
+        text: &str,
+        segments: &[Segment],
+    ) -> Result<Vec<SectionUnits>, Report<ChunkingError>> {
+        let sections = sections(text, segments).change_context(ChunkingError)?;
+
+        let mut section_units = Vec::new();
+        for (index, section) in sections.iter().enumerate() {
+            if section.is_heading_only()
+                && sections
+                    .get(index + 1)
+                    .is_some_and(|next| next.is_nested_in(section))
+            {
+                continue;
+            }
+
+            let breadcrumb = section.breadcrumb.join(BREADCRUMB_SEPARATOR);
+            let parent_breadcrumb = section
+                .breadcrumb
+                .split_last()
+                .map(|(_, parents)| parents.join(BREADCRUMB_SEPARATOR))
+                .unwrap_or_default();
+            let budget = self
+                .breadcrumb_prefix(&breadcrumb)
+                .map_or(self.max_tokens, |prefix| {
+                    self.max_tokens
+                        .saturating_sub(self.tokenizer.count_tokens(&prefix))
+                        .max(1)
+                });
+
+            section_units.push(SectionUnits {
+                units: self
+                    .units(text, segments, section.segments.clone(), budget)
+                    .change_context(ChunkingError)?,
+                budget,
+                breadcrumb,
+                parent_breadcrumb,
+                heading: section.level.map(|_| segments[section.segments.start].span),
+            });
+        }
+
+        Ok(section_units)
+    }
+
+    /// Returns the prefix of the text of the chunks with `breadcrumb`, if enabled.
+    fn breadcrumb_prefix(&self, breadcrumb: &str) -> Option<String> {
+        (self.breadcrumb_prefix && !breadcrumb.is_empty()).then(|| format!("{breadcrumb}\n\n"))
+    }
+
+    /// Annotates the `chunks` of a `section` with the section's breadcrumb and, if enabled, their
+    /// language.
+    ///
+    /// Chunks starting with the section's heading are not prefixed with the heading again.
+    fn annotate(&self, section: &SectionUnits, chunks: &mut [Chunk]) {
+        if let Some(detector) = &self.language_detector {
+            for chunk in &mut *chunks {
+                if let Some(detection) = detector.detect(&chunk.text) {
+                    detection.annotate(&mut chunk.metadata);
+                }
+            }
+        }
+        if section.breadcrumb.is_empty() {
+            return;
+        }
+
+        for chunk in chunks {
+            let starts_with_heading = section
+                .heading
+                .is_some_and(|heading| chunk.span.start == heading.start);
+            let prefix = if starts_with_heading {
+                self.breadcrumb_prefix(&section.parent_breadcrumb)
+            } else {
+                self.breadcrumb_prefix(&section.breadcrumb)
+            };
+            if let Some(prefix) = prefix {
+                chunk.text.insert_str(0, &prefix);
+            }
+            chunk
+                .metadata
+                .insert(BREADCRUMB_KEY, section.breadcrumb.clone());
+        }
+    }
+
+    /// Packs the `segments` of `text` into chunks.
+    ///
+    /// `text` has to be the text the segments were created from.
+    ///
+    /// Headings split the segments into sections and a chunk never spans more than one section.
+    /// Every chunk inside a section is annotated with the section's breadcrumb, the titles of all
+    /// enclosing headings joined by `" > "`, in its [`BREADCRUMB_KEY`] metadata entry and, if
+    /// enabled by [`with_breadcrumb_prefix`], in front of its text. Chunks starting with the
+    /// section's heading are only prefixed with the enclosing headings.
+    ///
+    /// [`with_breadcrumb_prefix`]: Self::with_breadcrumb_prefix
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkingError`] if a segment does not refer to a valid region of `text`.
+    pub fn chunk(
+        &self,
+        text: &str,
+        segments: &[Segment],
+    ) -> Result<Vec<Chunk>, Report<ChunkingError>> {
+        let mut chunks = Vec::new();
+        for section in self.section_units(text, segments)? {
+            let first_chunk = chunks.len();
+            self.pack(text, segments, &section.units, section.budget, &mut chunks)
+                .change_context(ChunkingError)?;
+            self.annotate(&section, &mut chunks[first_chunk..]);
+        }
+
+        Ok(chunks)
+    }
+
+    /// Splits `text` into paragraphs and packs them into chunks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkingError`] if the text could not be chunked.
+    pub fn chunk_text(&self, text: &str) -> Result<Vec<Chunk>, Report<ChunkingError>> {
+        self.chunk(text, &paragraphs(text))
+    }
+
+    /// Chunks a new version of a document and compares the chunks to the `previous` chunks.
+    ///
+    /// `previous` has to be created with the same configuration, otherwise every chunk is likely
+    /// to differ. See [`ChunkDiff`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkingError`] if a segment does not refer to a valid region of `text`.
+    pub fn rechunk(
+        &self,
+        previous: Vec<Chunk>,
+        text: &str,
+        segments: &[Segment],
+    ) -> Result<ChunkDiff, Report<ChunkingError>> {
+        Ok(ChunkDiff::new(previous, self.chunk(text, segments)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extraction::Extraction, tokenizer::HeuristicTokenizer};
+
+    fn texts(chunks: &[Chunk]) -> Vec<&str> {
+        chunks.iter().map(|chunk| chunk.text.as_str()).collect()
+    }
+
+    #[test]
+    fn packs_greedily() {
+        let chunker = Chunker::new(HeuristicTokenizer::default(), 6);
+        let chunks = chunker
+            .chunk_text("A b c. D e f.\n\nG h i j. K l.")
+            .expect("could be chunked");
+
+        assert_eq!(texts(&chunks), ["A b c. D e f.", "G h i j. K l."]);
+        assert_eq!(chunks[0].segments, 0..1);
+        assert_eq!(chunks[1].segments, 1..2);
+    }
+
+    #[test]
+    fn overlaps_by_tokens() {
+        let chunker =
+            Chunker::new(HeuristicTokenizer::default(), 6).with_overlap(Overlap::Tokens(3));
+        let chunks = chunker
+            .chunk_text("Aa bb. Cc dd. Ee ff. Gg hh.")
+            .expect("could be chunked");
+
+        assert_eq!(texts(&chunks), ["Aa bb. Cc dd. Ee ff.", "Ee ff. Gg hh."]);
+    }
+
+    #[test]
+    fn packs_whole_segments() {
+        let text = "A b. C d.\n\nE f. G h. I j.";
+        let chunker =
+            Chunker::new(HeuristicTokenizer::default(), 6).with_granularity(Granularity::Segment);
+
+        assert_eq!(
+            texts(&chunker.chunk_text(text).expect("could be chunked")),
+            ["A b. C d.", "E f. G h. I j."]
+        );
+    }
+
+    #[test]
+    fn splits_oversized_units() {
+        let chunker = Chunker::new(HeuristicTokenizer::default(), 3);
+        let chunks = chunker
+            .chunk_text("one two three four five, six seven")
+            .expect("could be chunked");
+        assert_eq!(texts(&chunks), [
+            "one two",
+            "three four",
+            "five, six",
+            "seven"
+        ]);
+
+        let chunks = chunker
+            .chunk_text("abcdefghijklmnopqrstuvwxyz")
+            .expect("could be chunked");
+        assert_eq!(texts(&chunks), ["abcdefghijkl", "mnopqrstuvwx", "yz"]);
+    }
+
+    #[test]
+    fn spans_match_text() {
+        let text = "\u{dc}ber den Wolken. Die Freiheit ist grenzenlos.\n\nAlle \u{c4}ngste.";
+        let chunker =
+            Chunker::new(HeuristicTokenizer::default(), 5).with_overlap(Overlap::Units(1));
+
+        for chunk in chunker.chunk_text(text).expect("could be chunked") {
+            assert!(HeuristicTokenizer::default().count_tokens(&chunk.text) <= 5);
+            assert_eq!(chunk.span.slice(text).expect("valid span"), chunk.text);
+            assert_eq!(
+                chunk.span.char_len(),
+                chunk.text.chars().count(),
+                "{chunk:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn splits_tables_by_rows() {
+        let table = Table::new(
+            [["Item", "Price"], ["Tea", "4"], ["Coffee", "5"], [
+                "Cake", "7",
+            ]]
+            .iter()
+            .map(|row| row.iter().map(|cell| (*cell).to_owned()).collect())
+            .collect(),
+            1,
+        );
+        let mut extraction = Extraction::new();
+        extraction.push(SegmentKind::Paragraph, "Prices.");
+        extraction.push(
+            SegmentKind::Table(table.clone()),
+            &table.render(TableFormat::Markdown),
+        );
+        let (text, segments) = extraction.into_parts();
+
+        let chunker = Chunker::new(HeuristicTokenizer::default(), 22);
+        let chunks = chunker.chunk(&text, &segments).expect("could be chunked");
+        assert_eq!(texts(&chunks), [
+            "Prices.",
+            "| Item | Price |\n| --- | --- |\n| Tea | 4 |\n| Coffee | 5 |",
+            "| Item | Price |\n| --- | --- |\n| Cake | 7 |",
+        ]);
+        assert_eq!(chunks[2].segments, 1..2);
+
+        let chunks = chunker
+            .with_table_format(TableFormat::Records)
+            .chunk(&text, &segments)
+            .expect("could be chunked");
+        assert_eq!(texts(&chunks), [
+            "Prices.",
+            "Item: Tea; Price: 4\nItem: Coffee; Price: 5\nItem: Cake; Price: 7",
+        ]);
+    }
+
+    #[test]
+    fn rechunks_changed_sections_only() {
+        let document = |introduction: &str| {
+            let mut extraction = Extraction::new();
+            extraction.push(SegmentKind::Heading { level: 1 }, "Introduction");
+            extraction.push(SegmentKind::Paragraph, introduction);
+            extraction.push(SegmentKind::Heading { level: 1 }, "Usage");
+            extraction.push(
+                SegmentKind::Paragraph,
+                "Call the chunker. It returns chunks.",
+            );
+            extraction
+        };
+        let chunker = Chunker::new(HeuristicTokenizer::default(), 8);
+
+        let previous = document("Chonky splits documents.");
+        let previous = chunker
+            .chunk(previous.text(), previous.segments())
+            .expect("could be chunked");
+        let current = document("Chonky splits and embeds documents.");
+        let diff = chunker
+            .rechunk(previous, current.text(), current.segments())
+            .expect("could be chunked");
+
+        assert_eq!(texts(&diff.added), [
+            "Chonky splits and embeds",
+            "documents."
+        ]);
+        assert_eq!(texts(&diff.removed), ["Chonky splits documents."]);
+        assert_eq!(texts(&diff.unchanged), [
+            "Introduction",
+            "Usage\n\nCall the chunker.",
+            "It returns chunks."
+        ]);
+    }
+
+    #[test]
+    fn detects_languages() {
+        let text = "Am 3. Oktober ist Feiertag. Die L\u{e4}den sind zu.\n\nShops are closed on \
+                    public holidays.";
+        let chunker =
+            Chunker::new(HeuristicTokenizer::default(), 12).with_overlap(Overlap::Units(1));
+
+        // The ordinal ends a sentence with English rules
+        let chunks = chunker.chunk_text(text).expect("could be chunked");
+        assert_eq!(
+            texts(&chunks)[1],
+            "Oktober ist Feiertag. Die L\u{e4}den sind zu."
+        );
+        assert_eq!(chunks[0].metadata.get(LANGUAGE_KEY), None);
+
+        let chunks = chunker
+            .with_language_detector(LanguageDetector::new())
+            .chunk_text(text)
+            .expect("could be chunked");
+        assert_eq!(texts(&chunks), [
+            "Am 3. Oktober ist Feiertag.",
+            "Die L\u{e4}den sind zu.",
+            "Shops are closed on public holidays."
+        ]);
+        let languages = chunks
+            .iter()
+            .map(|chunk| chunk.metadata.get(LANGUAGE_KEY))
+            .collect::<Vec<_>>();
+        assert_eq!(languages, [Some("de"), Some("de"), Some("en")]);
+    }
+}
@@ -0,0 +1,151 @@
+use core::ops::Range;
+
+use error_stack::Report;
+
+use crate::{
+    segment::{Segment, SegmentKind, TITLE_KEY},
+    span::SpanError,
+};
+
+/// A run of segments below the same heading.
+#[derive(Debug)]
+pub(super) struct Section {
+    /// The segments of the section, starting with its heading, if any.
+    pub segments: Range<usize>,
+    /// The level of the heading opening the section.
+    pub level: Option<u8>,
+    /// Titles of the section's heading and all enclosing headings, outermost first.
+    pub breadcrumb: Vec<String>,
+}
+
+impl Section {
+    /// Returns `true` if the section consists of nothing but its heading.
+    pub(super) fn is_heading_only(&self) -> bool {
+        self.level.is_some() && self.segments.len() == 1
+    }
+
+    /// Returns `true` if the heading of this section is nested in the heading of `parent`.
+    pub(super) const fn is_nested_in(&self, parent: &Self) -> bool {
+        matches!((self.level, parent.level), (Some(level), Some(parent)) if level > parent)
+    }
+}
+
+fn title(segment: &Segment, text: &str) -> Result<String, Report<SpanError>> {
+    if let Some(title) = segment.metadata.get(TITLE_KEY) {
+        return Ok(title.to_owned());
+    }
+
+    Ok(segment
+        .text(text)?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+/// Splits `segments` into sections at every heading.
+pub(super) fn sections(
+    text: &str,
+    segments: &[Segment],
+) -> Result<Vec<Section>, Report<SpanError>> {
+    let mut sections = Vec::new();
+    let mut headings = Vec::<(u8, String)>::new();
+    let mut current = Section {
+        segments: 0..0,
+        level: None,
+        breadcrumb: Vec::new(),
+    };
+
+    for (index, segment) in segments.iter().enumerate() {
+        if let SegmentKind::Heading { level } = segment.kind {
+            if !current.segments.is_empty() {
+                sections.push(current);
+            }
+
+            while headings.last().is_some_and(|&(parent, _)| parent >= level) {
+                headings.pop();
+            }
+            headings.push((level, title(segment, text)?));
+
+            current = Section {
+                segments: index..index,
+                level: Some(level),
+                breadcrumb: headings.iter().map(|(_, title)| title.clone()).collect(),
+            };
+        }
+        current.segments.end = index + 1;
+    }
+
+    if !current.segments.is_empty() {
+        sections.push(current);
+    }
+
+    Ok(sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction::Extraction;
+
+    fn summary(extraction: &Extraction) -> Vec<(Range<usize>, Option<u8>, Vec<String>)> {
+        sections(extraction.text(), extraction.segments())
+            .expect("valid spans")
+            .into_iter()
+            .map(|section| (section.segments, section.level, section.breadcrumb))
+            .collect()
+    }
+
+    #[test]
+    fn empty_document() {
+        assert!(summary(&Extraction::new()).is_empty());
+    }
+
+    #[test]
+    fn nested_headings() {
+        let mut extraction = Extraction::new();
+        extraction.push(SegmentKind::Paragraph, "Preamble");
+        extraction.push(SegmentKind::Heading { level: 1 }, "Guide");
+        extraction.push(SegmentKind::Heading { level: 2 }, "Setup\n  steps");
+        extraction.push(SegmentKind::Paragraph, "Install it.");
+        extraction
+            .push(SegmentKind::Heading { level: 3 }, "3.1")
+            .expect("non-empty heading")
+            .metadata
+            .insert(TITLE_KEY, "Linux");
+        extraction.push(SegmentKind::Heading { level: 2 }, "Usage");
+        extraction.push(SegmentKind::Paragraph, "Run it.");
+
+        let sections = summary(&extraction);
+        assert_eq!(sections, [
+            (0..1, None, vec![]),
+            (1..2, Some(1), vec!["Guide".to_owned()]),
+            (2..4, Some(2), vec![
+                "Guide".to_owned(),
+                "Setup steps".to_owned()
+            ]),
+            (4..5, Some(3), vec![
+                "Guide".to_owned(),
+                "Setup steps".to_owned(),
+                "Linux".to_owned()
+            ]),
+            (5..7, Some(2), vec!["Guide".to_owned(), "Usage".to_owned()]),
+        ]);
+    }
+
+    #[test]
+    fn heading_only_and_nesting() {
+        let section = |segments, level| Section {
+            segments,
+            level,
+            breadcrumb: Vec::new(),
+        };
+
+        assert!(section(0..1, Some(1)).is_heading_only());
+        assert!(!section(0..2, Some(1)).is_heading_only());
+        assert!(!section(0..1, None).is_heading_only());
+
+        assert!(section(1..2, Some(2)).is_nested_in(&section(0..1, Some(1))));
+        assert!(!section(1..2, Some(2)).is_nested_in(&section(0..1, Some(2))));
+        assert!(!section(1..2, Some(2)).is_nested_in(&section(0..1, None)));
+    }
+}
@@ -0,0 +1,352 @@
+use error_stack::{Report, ResultExt, ensure};
+
+use super::{Chunker, ChunkingError};
+use crate::{
+    chunk::Chunk, embedding::Embedder, segment::Segment, segmentation::paragraphs,
+    tokenizer::Tokenizer,
+};
+
+/// Threshold on the distance between two neighbouring units above which a new chunk is started.
+///
+/// The threshold is derived from the distribution of all distances in a document, so it adapts to
+/// how similar the units of a document are in general.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BreakpointThreshold {
+    /// Splits where the distance exceeds this percentile of all distances, between `0` and `100`.
+    Percentile(f32),
+    /// Splits where the distance exceeds the mean of all distances by more than this number of
+    /// standard deviations.
+    StandardDeviations(f32),
+}
+
+impl Default for BreakpointThreshold {
+    fn default() -> Self {
+        Self::Percentile(95.0)
+    }
+}
+
+#[expect(
+    clippy::float_arithmetic,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "distances are statistically evaluated"
+)]
+impl BreakpointThreshold {
+    /// Returns the distance above which a new chunk is started, or `None` if there are no
+    /// `distances`.
+    fn distance(self, distances: &[f32]) -> Option<f32> {
+        if distances.is_empty() {
+            return None;
+        }
+
+        match self {
+            Self::Percentile(percentile) => {
+                let mut sorted = distances.to_vec();
+                sorted.sort_by(f32::total_cmp);
+
+                // Linear interpolation between the two closest ranks
+                let rank = percentile.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f32;
+                let lower = rank.floor() as usize;
+                let upper = rank.ceil() as usize;
+                let weight = rank - rank.floor();
+                Some((sorted[upper] - sorted[lower]).mul_add(weight, sorted[lower]))
+            }
+            Self::StandardDeviations(deviations) => {
+                let count = distances.len() as f32;
+                let mean = distances.iter().sum::<f32>() / count;
+                let variance = distances
+                    .iter()
+                    .map(|distance| (distance - mean).powi(2))
+                    .sum::<f32>()
+                    / count;
+                Some(variance.sqrt().mul_add(deviations, mean))
+            }
+        }
+    }
+}
+
+/// Returns the cosine distance between two vectors.
+///
+/// Zero vectors are not similar to any other vector.
+#[expect(
+    clippy::float_arithmetic,
+    reason = "cosine distance is computed on floating-point vectors"
+)]
+fn cosine_distance(lhs: &[f32], rhs: &[f32]) -> f32 {
+    let dot = lhs.iter().zip(rhs).map(|(lhs, rhs)| lhs * rhs).sum::<f32>();
+    let norm = |vector: &[f32]| vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    let norms = norm(lhs) * norm(rhs);
+    if norms > 0.0 { 1.0 - dot / norms } else { 1.0 }
+}
+
+/// Splits a document into chunks where its topic changes.
+///
+/// The units of every section, which are sentences unless configured otherwise on the wrapped
+/// [`Chunker`], are embedded together with [`window`] neighbouring units on either side. A new
+/// chunk is started between two units whenever the cosine distance of their embeddings exceeds
+/// the [`BreakpointThreshold`], so every chunk covers a topically coherent stretch of text.
+///
+/// The wrapped [`Chunker`] still enforces its hard token maximum, along with its other settings:
+/// stretches exceeding it are packed into several chunks, and sections and breadcrumbs are
+/// handled the same way.
+///
+/// [`window`]: Self::with_window
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{
+///     chunker::{BreakpointThreshold, Chunker, SemanticChunker},
+///     embedding::HashingEmbedder,
+///     tokenizer::HeuristicTokenizer,
+/// };
+///
+/// # tokio::runtime::Builder::new_current_thread()
+/// #     .build()
+/// #     .expect("runtime")
+/// #     .block_on(async {
+/// let chunker = SemanticChunker::new(
+///     Chunker::new(HeuristicTokenizer::default(), 100),
+///     HashingEmbedder::new(),
+/// )
+/// .with_threshold(BreakpointThreshold::StandardDeviations(1.0))
+/// .with_window(0);
+///
+/// let chunks = chunker
+///     .chunk_text(
+///         "Cats sleep all day. Cats sleep in the sun. The sun keeps cats warm. Rust compiles \
+///          fast code. Rust code is memory safe. Safe code prevents bugs.",
+///     )
+///     .await
+///     .expect("could not chunk text");
+///
+/// let texts = chunks
+///     .iter()
+///     .map(|chunk| chunk.text.as_str())
+///     .collect::<Vec<_>>();
+/// assert_eq!(texts, [
+///     "Cats sleep all day. Cats sleep in the sun. The sun keeps cats warm.",
+///     "Rust compiles fast code. Rust code is memory safe. Safe code prevents bugs.",
+/// ]);
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct SemanticChunker<T, E> {
+    chunker: Chunker<T>,
+    embedder: E,
+    threshold: BreakpointThreshold,
+    window: usize,
+}
+
+impl<T, E> SemanticChunker<T, E>
+where
+    T: Tokenizer + Sync,
+    E: Embedder + Sync,
+{
+    #[must_use]
+    pub fn new(chunker: Chunker<T>, embedder: E) -> Self {
+        Self {
+            chunker,
+            embedder,
+            threshold: BreakpointThreshold::default(),
+            window: 1,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold: BreakpointThreshold) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the number of neighbouring units on either side embedded together with a unit.
+    ///
+    /// Embedding units with their neighbours smooths out single units which are not
+    /// representative of their surroundings, e.g. short transitional sentences.
+    #[must_use]
+    pub const fn with_window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub const fn chunker(&self) -> &Chunker<T> {
+        &self.chunker
+    }
+
+    pub const fn embedder(&self) -> &E {
+        &self.embedder
+    }
+
+    /// Splits the `segments` of `text` into chunks at topic changes.
+    ///
+    /// `text` has to be the text the segments were created from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkingError`] if a segment does not refer to a valid region of `text` or if
+    /// the units could not be embedded.
+    pub async fn chunk(
+        &self,
+        text: &str,
+        segments: &[Segment],
+    ) -> Result<Vec<Chunk>, Report<ChunkingError>> {
+        let sections = self.chunker.section_units(text, segments)?;
+
+        let mut windows = Vec::new();
+        for section in &sections {
+            for index in 0..section.units.len() {
+                let first = section.units[index.saturating_sub(self.window)];
+                let last = section.units[(index + self.window).min(section.units.len() - 1)];
+                windows.push(
+                    first
+                        .span
+                        .cover(last.span)
+                        .slice(text)
+                        .change_context(ChunkingError)?,
+                );
+            }
+        }
+
+        let vectors = self
+            .embedder
+            .embed(&windows)
+            .await
+            .change_context(ChunkingError)?;
+        ensure!(
+            vectors.len() == windows.len(),
+            Report::new(ChunkingError).attach_printable(format!(
+                "expected {} embeddings, got {}",
+                windows.len(),
+                vectors.len()
+            ))
+        );
+
+        // Distances are only measured inside of sections, as sections are split anyway
+        let mut section_distances = Vec::with_capacity(sections.len());
+        let mut vectors = vectors.as_slice();
+        for section in &sections {
+            let (section_vectors, rest) = vectors.split_at(section.units.len());
+            vectors = rest;
+            section_distances.push(
+                section_vectors
+                    .iter()
+                    .zip(section_vectors.iter().skip(1))
+                    .map(|(lhs, rhs)| cosine_distance(lhs, rhs))
+                    .collect::<Vec<_>>(),
+            );
+        }
+        let threshold = self
+            .threshold
+            .distance(&section_distances.concat())
+            .unwrap_or(f32::INFINITY);
+
+        let mut chunks = Vec::new();
+        for (section, distances) in sections.iter().zip(&section_distances) {
+            let first_chunk = chunks.len();
+            let mut start = 0;
+            for (index, distance) in distances.iter().enumerate() {
+                if *distance > threshold {
+                    self.chunker
+                        .pack(
+                            text,
+                            segments,
+                            &section.units[start..=index],
+                            section.budget,
+                            &mut chunks,
+                        )
+                        .change_context(ChunkingError)?;
+                    start = index + 1;
+                }
+            }
+            self.chunker
+                .pack(
+                    text,
+                    segments,
+                    &section.units[start..],
+                    section.budget,
+                    &mut chunks,
+                )
+                .change_context(ChunkingError)?;
+            self.chunker.annotate(section, &mut chunks[first_chunk..]);
+        }
+
+        Ok(chunks)
+    }
+
+    /// Splits `text` into paragraphs and chunks them at topic changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkingError`] if the text could not be chunked.
+    pub async fn chunk_text(&self, text: &str) -> Result<Vec<Chunk>, Report<ChunkingError>> {
+        self.chunk(text, &paragraphs(text)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{embedding::HashingEmbedder, tokenizer::HeuristicTokenizer};
+
+    const TEXT: &str = "Cats sleep all day. Cats sleep in the sun. The sun keeps cats \
+                        warm.\n\nRust compiles fast code. Rust code is memory safe. Safe code \
+                        prevents bugs.";
+
+    fn texts(chunks: &[Chunk]) -> Vec<&str> {
+        chunks.iter().map(|chunk| chunk.text.as_str()).collect()
+    }
+
+    #[test]
+    fn computes_thresholds() {
+        let distances = [0.4, 0.2, 1.0, 0.6, 0.8];
+
+        let percentile = BreakpointThreshold::Percentile(50.0)
+            .distance(&distances)
+            .expect("distances are not empty");
+        assert!((percentile - 0.6).abs() < 1e-6);
+        let percentile = BreakpointThreshold::Percentile(90.0)
+            .distance(&distances)
+            .expect("distances are not empty");
+        assert!((percentile - 0.92).abs() < 1e-6);
+
+        let deviations = BreakpointThreshold::StandardDeviations(1.0)
+            .distance(&distances)
+            .expect("distances are not empty");
+        assert!((deviations - (0.6 + 0.08_f32.sqrt())).abs() < 1e-6);
+
+        assert_eq!(BreakpointThreshold::default().distance(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn splits_at_topic_changes() {
+        let chunker = SemanticChunker::new(
+            Chunker::new(HeuristicTokenizer::default(), 100),
+            HashingEmbedder::new(),
+        )
+        .with_threshold(BreakpointThreshold::Percentile(80.0))
+        .with_window(0);
+
+        let chunks = chunker.chunk_text(TEXT).await.expect("could be chunked");
+        assert_eq!(texts(&chunks), [
+            "Cats sleep all day. Cats sleep in the sun. The sun keeps cats warm.",
+            "Rust compiles fast code. Rust code is memory safe. Safe code prevents bugs.",
+        ]);
+        assert_eq!(chunks[0].segments, 0..1);
+        assert_eq!(chunks[1].segments, 1..2);
+    }
+
+    #[tokio::test]
+    async fn enforces_token_maximum() {
+        let tokenizer = HeuristicTokenizer::default();
+        let chunker = SemanticChunker::new(Chunker::new(tokenizer, 12), HashingEmbedder::new())
+            .with_threshold(BreakpointThreshold::StandardDeviations(1.0));
+
+        let chunks = chunker.chunk_text(TEXT).await.expect("could be chunked");
+        assert!(chunks.len() > 2);
+        for chunk in &chunks {
+            assert!(tokenizer.count_tokens(&chunk.text) <= 12, "{chunk:?}");
+            assert_eq!(chunk.span.slice(TEXT).expect("valid span"), chunk.text);
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use alloc::collections::VecDeque;
+use core::{mem, pin::Pin};
+use std::io;
+
+use error_stack::{Context, Report, bail};
+use futures::{StreamExt, TryStreamExt, stream};
+use futures_core::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::{Chunker, ChunkingError};
+use crate::{chunk::Chunk, span::Offset, tokenizer::Tokenizer};
+
+/// Number of bytes requested from a reader at once.
+const READ_SIZE: usize = 64 * 1024;
+
+/// Returns the end of the last empty line of `text` which ends at or after `from`.
+///
+/// All text before the returned position consists of complete paragraphs.
+fn paragraph_break(text: &str, from: usize) -> Option<usize> {
+    let mut newlines = text.rmatch_indices('\n').map(|(index, _)| index);
+    let mut end = newlines.next()?;
+    for start in newlines {
+        if end < from {
+            break;
+        }
+        if text
+            .get(start + 1..end)
+            .is_some_and(|line| line.trim().is_empty())
+        {
+            return Some(end + 1);
+        }
+        end = start;
+    }
+    None
+}
+
+/// Returns the position after the last line break of `text`, or else after its last whitespace.
+fn line_break(text: &str) -> Option<usize> {
+    text.rfind('\n').map(|index| index + 1).or_else(|| {
+        text.char_indices()
+            .rev()
+            .find(|(_, char)| char.is_whitespace())
+            .map(|(index, char)| index + char.len_utf8())
+    })
+}
+
+/// The state of a stream of chunks created by a [`StreamingChunker`].
+struct ChunkStream<'c, T, S> {
+    chunker: &'c StreamingChunker<T>,
+    source: Pin<Box<S>>,
+    /// Bytes of an incomplete UTF-8 sequence at the end of the last read.
+    incomplete: Vec<u8>,
+    /// Text which was not emitted as chunk yet.
+    buffer: String,
+    /// The offset of the start of `buffer` in the whole text.
+    base: Offset,
+    /// The number of segments before the start of `buffer`.
+    segment_base: usize,
+    /// The end of the text of `buffer` which was already chunked.
+    chunked: usize,
+    ready: VecDeque<Chunk>,
+    finished: bool,
+}
+
+impl<T, S, B, E> ChunkStream<'_, T, S>
+where
+    T: Tokenizer,
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: Context,
+{
+    /// Decodes `bytes` and appends them to the buffer.
+    fn push(&mut self, bytes: &[u8]) -> Result<(), Report<ChunkingError>> {
+        let mut input = mem::take(&mut self.incomplete);
+        input.extend_from_slice(bytes);
+
+        let previous_len = self.buffer.len();
+        match core::str::from_utf8(&input) {
+            Ok(text) => self.buffer.push_str(text),
+            Err(error) => {
+                let (valid, rest) = input.split_at(error.valid_up_to());
+                if error.error_len().is_some() {
+                    bail!(
+                        Report::new(ChunkingError)
+                            .attach_printable("the stream is not valid UTF-8")
+                            .attach_printable(format!(
+                                "invalid byte sequence at byte {}",
+                                self.base.byte + self.buffer.len() + valid.len()
+                            ))
+                    );
+                }
+                // The sequence is completed by the next read
+                self.buffer
+                    .push_str(core::str::from_utf8(valid).unwrap_or_default());
+                self.incomplete = rest.to_vec();
+            }
+        }
+
+        // An empty line may start in front of the new text
+        let from = self
+            .buffer
+            .get(..previous_len)
+            .and_then(|previous| previous.rfind('\n'))
+            .unwrap_or_default()
+            .max(self.chunked);
+        let boundary = paragraph_break(&self.buffer, from).or_else(|| {
+            (self.buffer.len() > self.chunker.max_buffer_size).then(|| {
+                line_break(&self.buffer)
+                    .filter(|&boundary| boundary > self.chunked)
+                    .unwrap_or(self.buffer.len())
+            })
+        });
+        if let Some(boundary) = boundary {
+            if boundary > self.chunked {
+                self.chunk(boundary, false)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Chunks the buffer up to `boundary`.
+    ///
+    /// Unless the stream is `finished`, the last chunk may grow with more text, so the text from
+    /// its start on is kept in the buffer and chunked again with the next complete text.
+    fn chunk(&mut self, boundary: usize, finished: bool) -> Result<(), Report<ChunkingError>> {
+        let text = self.buffer.get(..boundary).unwrap_or_default();
+        let mut chunks = self.chunker.chunker.chunk_text(text)?;
+        let retained = if finished { None } else { chunks.pop() };
+
+        let (keep, segments) = match (&retained, chunks.last()) {
+            (Some(retained), _) => (retained.span.start.byte, retained.segments.start),
+            (None, Some(last)) => (boundary, last.segments.end),
+            (None, None) => (boundary, 0),
+        };
+
+        for mut chunk in chunks {
+            chunk.span = chunk.span.translate(self.base);
+            chunk.segments =
+                chunk.segments.start + self.segment_base..chunk.segments.end + self.segment_base;
+            self.ready.push_back(chunk);
+        }
+
+        self.base = self
+            .base
+            .advance(self.buffer.get(..keep).unwrap_or_default());
+        self.segment_base += segments;
+        self.buffer.drain(..keep);
+        self.chunked = boundary - keep;
+        Ok(())
+    }
+
+    /// Chunks the remaining text after the source is exhausted.
+    fn finish(&mut self) -> Result<(), Report<ChunkingError>> {
+        self.finished = true;
+        if !self.incomplete.is_empty() {
+            bail!(
+                Report::new(ChunkingError)
+                    .attach_printable("the stream ends with an incomplete UTF-8 sequence")
+            );
+        }
+        self.chunk(self.buffer.len(), true)
+    }
+
+    /// Returns the next chunk, reading from the source only if no chunk is ready.
+    #[expect(
+        clippy::future_not_send,
+        reason = "the future is `Send` if the source and the tokenizer are"
+    )]
+    async fn next_chunk(&mut self) -> Result<Option<Chunk>, Report<ChunkingError>> {
+        loop {
+            if let Some(chunk) = self.ready.pop_front() {
+                return Ok(Some(chunk));
+            }
+            if self.finished {
+                return Ok(None);
+            }
+            match self.source.next().await {
+                Some(Ok(bytes)) => self.push(bytes.as_ref())?,
+                Some(Err(error)) => {
+                    return Err(Report::new(error).change_context(ChunkingError));
+                }
+                None => self.finish()?,
+            }
+        }
+    }
+}
+
+/// Chunks text which is read incrementally, e.g. logs too large to be held in memory.
+///
+/// Text is split into paragraphs at empty lines and packed by the wrapped [`Chunker`]. Only the
+/// text from the start of the last, possibly incomplete, chunk on is kept in memory. If no empty
+/// line occurs within [`max_buffer_size`] bytes, the text is split at the last line break, the
+/// last whitespace or, as a last resort, at the end of the buffered text.
+///
+/// The source is only read when the next chunk is requested and none is ready, so a slow
+/// consumer slows down reading. Spans of the chunks refer to the whole text and segment indices
+/// count the paragraphs of the whole text. As long as no split is forced by the buffer size, the
+/// chunks are the same as if the whole text had been chunked at once.
+///
+/// [`max_buffer_size`]: Self::with_max_buffer_size
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{
+///     chunker::{Chunker, StreamingChunker},
+///     tokenizer::HeuristicTokenizer,
+/// };
+/// use futures::{TryStreamExt, stream};
+///
+/// # tokio::runtime::Builder::new_current_thread()
+/// #     .build()
+/// #     .expect("runtime")
+/// #     .block_on(async {
+/// let chunker = StreamingChunker::new(Chunker::new(HeuristicTokenizer::default(), 4));
+///
+/// // The `ü` is split between two reads
+/// let reads = [&b"Gr\xc3"[..], b"\xbcn ist gut.\n\n", b"Das Ende."];
+/// let chunks = chunker
+///     .chunk_stream(stream::iter(reads.map(Ok::<_, std::io::Error>)))
+///     .try_collect::<Vec<_>>()
+///     .await
+///     .expect("could not chunk stream");
+///
+/// let chunks = chunks
+///     .iter()
+///     .map(|chunk| (chunk.text.as_str(), chunk.span.char_range()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(chunks, [("Grün ist gut.", 0..13), ("Das Ende.", 15..24)]);
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingChunker<T> {
+    chunker: Chunker<T>,
+    max_buffer_size: usize,
+}
+
+impl<T> StreamingChunker<T>
+where
+    T: Tokenizer,
+{
+    /// The default number of bytes buffered before a split is forced, one mebibyte.
+    pub const DEFAULT_MAX_BUFFER_SIZE: usize = 1024 * 1024;
+
+    #[must_use]
+    pub const fn new(chunker: Chunker<T>) -> Self {
+        Self {
+            chunker,
+            max_buffer_size: Self::DEFAULT_MAX_BUFFER_SIZE,
+        }
+    }
+
+    /// Sets the number of bytes buffered without an empty line before a split is forced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_buffer_size` is zero.
+    #[must_use]
+    pub const fn with_max_buffer_size(mut self, max_buffer_size: usize) -> Self {
+        assert!(
+            max_buffer_size > 0,
+            "the buffer has to hold at least one byte"
+        );
+        self.max_buffer_size = max_buffer_size;
+        self
+    }
+
+    pub const fn chunker(&self) -> &Chunker<T> {
+        &self.chunker
+    }
+
+    /// Chunks the UTF-8 text produced by `source`.
+    ///
+    /// UTF-8 sequences may be split between items of the stream.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields [`ChunkingError`] and ends if `source` fails, if the text is not valid
+    /// UTF-8, or if it could not be chunked.
+    pub fn chunk_stream<S, B, E>(
+        &self,
+        source: S,
+    ) -> impl Stream<Item = Result<Chunk, Report<ChunkingError>>>
+    where
+        S: Stream<Item = Result<B, E>>,
+        B: AsRef<[u8]>,
+        E: Context,
+    {
+        let state = ChunkStream {
+            chunker: self,
+            source: Box::pin(source),
+            incomplete: Vec::new(),
+            buffer: String::new(),
+            base: Offset::ZERO,
+            segment_base: 0,
+            chunked: 0,
+            ready: VecDeque::new(),
+            finished: false,
+        };
+        stream::try_unfold(state, |mut state| async move {
+            Ok(state.next_chunk().await?.map(|chunk| (chunk, state)))
+        })
+    }
+
+    /// Chunks the UTF-8 text read from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields [`ChunkingError`] and ends if reading fails, if the text is not valid
+    /// UTF-8, or if it could not be chunked.
+    pub fn chunk_reader<R>(
+        &self,
+        reader: R,
+    ) -> impl Stream<Item = Result<Chunk, Report<ChunkingError>>>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let reads = stream::try_unfold(reader, |mut reader| async move {
+            let mut buffer = vec![0; READ_SIZE];
+            let read = reader.read(&mut buffer).await?;
+            buffer.truncate(read);
+            Ok::<_, io::Error>((read > 0).then_some((buffer, reader)))
+        });
+        self.chunk_stream(reads.into_stream())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{chunker::Overlap, tokenizer::HeuristicTokenizer};
+
+    const TEXT: &str = "\u{dc}n\u{ef}c\u{f6}d\u{e9} text is split. Every read is short.\n\nA \
+                        second paragraph follows here. It has two sentences.\n  \nThe third \
+                        paragraph is long enough to be split into several chunks by itself. Its \
+                        sentences are packed greedily.\n\nEnd.";
+
+    fn reads(text: &str, size: usize) -> impl Stream<Item = Result<&[u8], io::Error>> {
+        stream::iter(text.as_bytes().chunks(size).map(Ok))
+    }
+
+    #[tokio::test]
+    async fn matches_chunker() {
+        for overlap in [Overlap::None, Overlap::Units(1)] {
+            let chunker = Chunker::new(HeuristicTokenizer::default(), 12).with_overlap(overlap);
+            let expected = chunker.chunk_text(TEXT).expect("could not chunk text");
+
+            let streaming = StreamingChunker::new(chunker);
+            for size in [1, 3, 7, TEXT.len()] {
+                let chunks = streaming
+                    .chunk_stream(reads(TEXT, size))
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .expect("could not chunk stream");
+                assert_eq!(chunks, expected, "reads of {size} bytes");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn bounds_buffer() {
+        let text = "word ".repeat(100);
+        let chunker = Chunker::new(HeuristicTokenizer::default(), 8);
+        let streaming = StreamingChunker::new(chunker).with_max_buffer_size(16);
+
+        let chunks = streaming
+            .chunk_stream(reads(&text, 4))
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("could not chunk stream");
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert_eq!(chunk.span.slice(&text).expect("valid span"), chunk.text);
+        }
+        // Splits are forced at whitespace, so no word is split
+        let texts = chunks
+            .iter()
+            .map(|chunk| chunk.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(texts.join(" "), text.trim_end());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_utf8() {
+        let chunker = StreamingChunker::new(Chunker::new(HeuristicTokenizer::default(), 8));
+
+        let reads = stream::iter([Ok::<_, io::Error>(&b"valid\n\n\xff"[..])]);
+        let _: Report<_> = chunker
+            .chunk_stream(reads)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect_err("invalid byte");
+
+        let reads = stream::iter([Ok::<_, io::Error>(&b"truncated \xc3"[..])]);
+        let _: Report<_> = chunker
+            .chunk_stream(reads)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect_err("incomplete sequence");
+    }
+
+    #[tokio::test]
+    async fn reads_async() {
+        let chunker = Chunker::new(HeuristicTokenizer::default(), 12);
+        let expected = chunker.chunk_text(TEXT).expect("could not chunk text");
+
+        let chunks = StreamingChunker::new(chunker)
+            .chunk_reader(TEXT.as_bytes())
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("could not chunk reader");
+        assert_eq!(chunks, expected);
+    }
+}
@@ -0,0 +1,736 @@
+//! Chunking of source code along syntactic units.
+//!
+//! Source files are parsed with [tree-sitter] and split at the boundaries of items such as
+//! functions, types, classes and modules instead of at arbitrary lines. Consecutive items which
+//! fit into the token budget together are packed into one chunk. Items exceeding the budget are
+//! split into their members, e.g. the methods of an `impl` block or a class, and items without
+//! members, such as long functions, are split into windows of whole lines.
+//!
+//! Every chunk records the path of the symbol it covers in its [`SYMBOL_PATH_KEY`] metadata entry,
+//! e.g. `graph::store::fetcher::FetchingStore::read`, and its lines in its [`LINES_KEY`] metadata
+//! entry.
+//!
+//! [tree-sitter]: https://tree-sitter.github.io/tree-sitter/
+
+use core::ops::Range;
+
+use error_stack::{Report, ResultExt};
+use tree_sitter::{Node, Parser};
+
+use crate::{
+    chunk::Chunk,
+    segment::{Segment, SegmentKind},
+    span::OffsetIndex,
+    tokenizer::Tokenizer,
+};
+
+/// Metadata key of the path of the symbol a segment or chunk covers.
+///
+/// The path consists of the module path passed to the [`CodeChunker`] and the names of all
+/// enclosing items, joined by the separator of the language, e.g. `store::Store::read` in Rust or
+/// `store.Store.read` in Python and TypeScript. Chunks covering several items carry the path of
+/// their common parent.
+pub const SYMBOL_PATH_KEY: &str = "symbol_path";
+
+/// Metadata key of the lines a segment or chunk covers as `"first-last"`, starting at `1`.
+pub const LINES_KEY: &str = "lines";
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not chunk source code")]
+pub struct CodeError;
+
+/// A programming language supported by the [`CodeChunker`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Language {
+    Rust,
+    TypeScript,
+    /// TypeScript with JSX syntax.
+    Tsx,
+    Python,
+}
+
+impl Language {
+    /// Returns the language of files with the given extension, without the leading dot.
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rs" => Some(Self::Rust),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "py" | "pyi" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the language as used for [`SegmentKind::CodeBlock`]s.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::TypeScript => "typescript",
+            Self::Tsx => "tsx",
+            Self::Python => "python",
+        }
+    }
+
+    /// Returns the separator between the components of a symbol path.
+    const fn separator(self) -> &'static str {
+        match self {
+            Self::Rust => "::",
+            Self::TypeScript | Self::Tsx | Self::Python => ".",
+        }
+    }
+
+    fn grammar(self) -> tree_sitter::Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+        }
+    }
+
+    /// Returns `true` for nodes which belong to the item following them, e.g. doc comments.
+    fn is_leading(self, kind: &str) -> bool {
+        match self {
+            Self::Rust => matches!(kind, "line_comment" | "block_comment" | "attribute_item"),
+            Self::TypeScript | Self::Tsx | Self::Python => kind == "comment",
+        }
+    }
+}
+
+/// The name and members of an item.
+#[derive(Default)]
+struct Item<'tree> {
+    name: Option<String>,
+    /// The node containing the members of the item, e.g. the body of a class.
+    body: Option<Node<'tree>>,
+}
+
+impl<'tree> Item<'tree> {
+    fn describe(language: Language, node: Node<'tree>, source: &str) -> Self {
+        let text = |node: Node<'_>| node.utf8_text(source.as_bytes()).ok().map(str::to_owned);
+        let field = |name: &str| node.child_by_field_name(name);
+        let named = |body: Option<Node<'tree>>| Self {
+            name: field("name").and_then(text),
+            body,
+        };
+
+        match (language, node.kind()) {
+            (Language::Rust, "impl_item") => {
+                let mut self_type = field("type");
+                if let Some(generic) = self_type.filter(|node| node.kind() == "generic_type") {
+                    self_type = generic.child_by_field_name("type");
+                }
+                Self {
+                    name: self_type.and_then(text),
+                    body: field("body"),
+                }
+            }
+            (Language::Rust, "trait_item" | "mod_item")
+            | (
+                Language::TypeScript | Language::Tsx,
+                "class_declaration" | "abstract_class_declaration" | "internal_module" | "module",
+            )
+            | (Language::Python, "class_definition") => named(field("body")),
+            (
+                Language::Rust,
+                "function_item"
+                | "function_signature_item"
+                | "struct_item"
+                | "enum_item"
+                | "union_item"
+                | "const_item"
+                | "static_item"
+                | "type_item"
+                | "macro_definition",
+            )
+            | (
+                Language::TypeScript | Language::Tsx,
+                "function_declaration"
+                | "generator_function_declaration"
+                | "function_signature"
+                | "interface_declaration"
+                | "type_alias_declaration"
+                | "enum_declaration"
+                | "method_definition"
+                | "abstract_method_signature"
+                | "public_field_definition",
+            )
+            | (Language::Python, "function_definition") => named(None),
+            (Language::TypeScript | Language::Tsx, "export_statement") => field("declaration")
+                .map(|declaration| Self::describe(language, declaration, source))
+                .unwrap_or_default(),
+            (
+                Language::TypeScript | Language::Tsx,
+                "lexical_declaration" | "variable_declaration",
+            ) => Self {
+                name: node
+                    .named_child(0)
+                    .and_then(|declarator| declarator.child_by_field_name("name"))
+                    .and_then(text),
+                body: None,
+            },
+            (Language::Python, "decorated_definition") => field("definition")
+                .map(|definition| Self::describe(language, definition, source))
+                .unwrap_or_default(),
+            _ => Self::default(),
+        }
+    }
+}
+
+/// A region of the source which is packed as a whole.
+#[derive(Debug)]
+struct Unit {
+    range: Range<usize>,
+    /// Path of the item containing this unit. Only units sharing a parent are packed together.
+    parent: Vec<String>,
+    name: Option<String>,
+    tokens: usize,
+}
+
+impl Unit {
+    fn symbol_path(&self) -> Vec<&str> {
+        self.parent
+            .iter()
+            .map(String::as_str)
+            .chain(self.name.as_deref())
+            .collect()
+    }
+}
+
+/// Removes surrounding whitespace from `range`, returning `None` if nothing is left.
+fn trim(source: &str, range: Range<usize>) -> Option<Range<usize>> {
+    let region = source.get(range.clone())?;
+    let content = region.trim();
+    let start = range.start + (region.len() - region.trim_start().len());
+    (!content.is_empty()).then(|| start..start + content.len())
+}
+
+/// Returns the 1-based number of the line containing `byte`.
+fn line_number(line_starts: &[usize], byte: usize) -> usize {
+    line_starts.partition_point(|&start| start <= byte)
+}
+
+/// Splits source code into chunks along the boundaries of syntactic units.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{
+///     code::{CodeChunker, LINES_KEY, Language, SYMBOL_PATH_KEY},
+///     tokenizer::HeuristicTokenizer,
+/// };
+///
+/// let source = "\
+/// pub struct Store;
+///
+/// impl Store {
+///     /// Reads a value.
+///     pub fn read(&self) -> u32 {
+///         let value = 42;
+///         value
+///     }
+///
+///     pub fn write(&mut self, value: u32) {
+///         drop(value);
+///     }
+/// }
+/// ";
+///
+/// let chunker = CodeChunker::new(HeuristicTokenizer::default(), 25);
+/// let chunks = chunker
+///     .chunk(source, Language::Rust, &["graph", "store"])
+///     .expect("could not chunk source");
+///
+/// let read = chunks
+///     .iter()
+///     .find(|chunk| chunk.text.contains("fn read"))
+///     .expect("`read` should be chunked");
+/// assert!(read.text.starts_with("/// Reads a value."));
+/// assert_eq!(
+///     read.metadata.get(SYMBOL_PATH_KEY),
+///     Some("graph::store::Store::read")
+/// );
+/// assert_eq!(read.metadata.get(LINES_KEY), Some("4-8"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeChunker<T> {
+    tokenizer: T,
+    max_tokens: usize,
+}
+
+impl<T> CodeChunker<T>
+where
+    T: Tokenizer,
+{
+    /// Creates a chunker which creates chunks of at most `max_tokens` tokens.
+    ///
+    /// Single lines exceeding the budget are not split and form a chunk exceeding the budget.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tokens` is zero.
+    #[must_use]
+    pub fn new(tokenizer: T, max_tokens: usize) -> Self {
+        assert!(max_tokens > 0, "the token budget has to be positive");
+
+        Self {
+            tokenizer,
+            max_tokens,
+        }
+    }
+
+    pub const fn tokenizer(&self) -> &T {
+        &self.tokenizer
+    }
+
+    pub const fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+
+    fn unit(
+        &self,
+        source: &str,
+        range: Range<usize>,
+        parent: &[String],
+        name: Option<&str>,
+    ) -> Unit {
+        Unit {
+            tokens: self
+                .tokenizer
+                .count_tokens(source.get(range.clone()).unwrap_or_default()),
+            range,
+            parent: parent.to_vec(),
+            name: name.map(str::to_owned),
+        }
+    }
+
+    /// Adds `range` as a unit, or as windows of whole lines if it exceeds the budget.
+    ///
+    /// Surrounding whitespace is not part of any unit.
+    fn push_region(
+        &self,
+        source: &str,
+        range: Range<usize>,
+        parent: &[String],
+        name: Option<&str>,
+        units: &mut Vec<Unit>,
+    ) {
+        let Some(range) = trim(source, range) else {
+            return;
+        };
+        let unit = self.unit(source, range.clone(), parent, name);
+        if unit.tokens <= self.max_tokens {
+            units.push(unit);
+            return;
+        }
+
+        // Windows of an item are members of that item
+        let mut path = parent.to_vec();
+        path.extend(name.map(str::to_owned));
+
+        let mut window = range.start..range.start;
+        for line in source.get(range).unwrap_or_default().split_inclusive('\n') {
+            let line_end = window.end + line.len();
+            let tokens = self
+                .tokenizer
+                .count_tokens(source.get(window.start..line_end).unwrap_or_default());
+            if tokens > self.max_tokens && !window.is_empty() {
+                units.extend(
+                    trim(source, window.clone())
+                        .map(|window| self.unit(source, window, &path, None)),
+                );
+                window.start = window.end;
+            }
+            window.end = line_end;
+        }
+        units.extend(trim(source, window).map(|window| self.unit(source, window, &path, None)));
+    }
+
+    /// Splits the `nodes` of a container into units.
+    ///
+    /// `bounds` is the region of the whole container. Anything before the first and after the last
+    /// node, e.g. the header of a class, is added as a unit of the container itself.
+    fn collect(
+        &self,
+        language: Language,
+        source: &str,
+        nodes: &[Node<'_>],
+        parent: &[String],
+        bounds: Range<usize>,
+        units: &mut Vec<Unit>,
+    ) {
+        let mut position = bounds.start;
+        let mut leading = None;
+        for node in nodes {
+            if language.is_leading(node.kind()) {
+                leading.get_or_insert(node.start_byte());
+                continue;
+            }
+
+            let start = leading.take().unwrap_or_else(|| node.start_byte());
+            self.push_region(source, position..start, parent, None, units);
+            position = node.end_byte();
+
+            let range = start..node.end_byte();
+            let item = Item::describe(language, *node, source);
+            let tokens = self
+                .tokenizer
+                .count_tokens(source.get(range.clone()).unwrap_or_default());
+
+            match item.body {
+                Some(body) if tokens > self.max_tokens => {
+                    let mut path = parent.to_vec();
+                    path.extend(item.name);
+                    let mut cursor = body.walk();
+                    let members = body.named_children(&mut cursor).collect::<Vec<_>>();
+                    self.collect(language, source, &members, &path, range, units);
+                }
+                _ => self.push_region(source, range, parent, item.name.as_deref(), units),
+            }
+        }
+        self.push_region(source, position..bounds.end, parent, None, units);
+    }
+
+    fn units<S>(
+        &self,
+        source: &str,
+        language: Language,
+        module: &[S],
+    ) -> Result<Vec<Unit>, Report<CodeError>>
+    where
+        S: AsRef<str>,
+    {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&language.grammar())
+            .change_context(CodeError)?;
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| Report::new(CodeError))
+            .attach_printable("the parser did not return a syntax tree")?;
+
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let nodes = root.named_children(&mut cursor).collect::<Vec<_>>();
+        let module = module
+            .iter()
+            .map(|component| component.as_ref().to_owned())
+            .collect::<Vec<_>>();
+
+        let mut units = Vec::new();
+        self.collect(
+            language,
+            source,
+            &nodes,
+            &module,
+            0..source.len(),
+            &mut units,
+        );
+        Ok(units)
+    }
+
+    /// Splits `source` into segments along syntactic units.
+    ///
+    /// Every segment is a [`SegmentKind::CodeBlock`] within the token budget, annotated with its
+    /// [`SYMBOL_PATH_KEY`] and [`LINES_KEY`]. `module` is the path of the module the source
+    /// belongs to and prefixes all symbol paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodeError`] if the source could not be parsed.
+    pub fn segment<S>(
+        &self,
+        source: &str,
+        language: Language,
+        module: &[S],
+    ) -> Result<Vec<Segment>, Report<CodeError>>
+    where
+        S: AsRef<str>,
+    {
+        let units = self.units(source, language, module)?;
+        Self::segments(source, language, &units)
+    }
+
+    fn segments(
+        source: &str,
+        language: Language,
+        units: &[Unit],
+    ) -> Result<Vec<Segment>, Report<CodeError>> {
+        let index = OffsetIndex::new(source);
+        let line_starts = Self::line_starts(source);
+
+        units
+            .iter()
+            .map(|unit| {
+                let mut segment = Segment::new(
+                    SegmentKind::CodeBlock {
+                        language: Some(language.name().to_owned()),
+                    },
+                    index
+                        .span(source, unit.range.clone())
+                        .change_context(CodeError)?,
+                );
+                Self::annotate(
+                    &mut segment.metadata,
+                    &unit.symbol_path(),
+                    language,
+                    &line_starts,
+                    &unit.range,
+                );
+                Ok(segment)
+            })
+            .collect()
+    }
+
+    fn line_starts(source: &str) -> Vec<usize> {
+        core::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect()
+    }
+
+    fn annotate(
+        metadata: &mut crate::Metadata,
+        symbol_path: &[&str],
+        language: Language,
+        line_starts: &[usize],
+        range: &Range<usize>,
+    ) {
+        if !symbol_path.is_empty() {
+            metadata.insert(SYMBOL_PATH_KEY, symbol_path.join(language.separator()));
+        }
+        metadata.insert(
+            LINES_KEY,
+            format!(
+                "{}-{}",
+                line_number(line_starts, range.start),
+                line_number(line_starts, range.end.saturating_sub(1).max(range.start))
+            ),
+        );
+    }
+
+    /// Splits `source` into chunks along syntactic units.
+    ///
+    /// The source is split into segments as by [`segment`], and consecutive segments sharing the
+    /// same parent item are packed into chunks of at most [`max_tokens`] tokens. `module` is the
+    /// path of the module the source belongs to and prefixes all symbol paths.
+    ///
+    /// [`segment`]: Self::segment
+    /// [`max_tokens`]: Self::max_tokens
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodeError`] if the source could not be parsed.
+    pub fn chunk<S>(
+        &self,
+        source: &str,
+        language: Language,
+        module: &[S],
+    ) -> Result<Vec<Chunk>, Report<CodeError>>
+    where
+        S: AsRef<str>,
+    {
+        let units = self.units(source, language, module)?;
+        let segments = Self::segments(source, language, &units)?;
+        let line_starts = Self::line_starts(source);
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while start < units.len() {
+            let mut end = start + 1;
+            let mut tokens = units[start].tokens;
+            while let Some(unit) = units.get(end) {
+                if unit.parent != units[start].parent || tokens + unit.tokens > self.max_tokens {
+                    break;
+                }
+                tokens += unit.tokens;
+                end += 1;
+            }
+
+            let mut chunk =
+                Chunk::from_segments(source, &segments, start..end).change_context(CodeError)?;
+            let symbol_path = if end - start == 1 {
+                units[start].symbol_path()
+            } else {
+                units[start].parent.iter().map(String::as_str).collect()
+            };
+            Self::annotate(
+                &mut chunk.metadata,
+                &symbol_path,
+                language,
+                &line_starts,
+                &chunk.span.byte_range(),
+            );
+            chunks.push(chunk);
+            start = end;
+        }
+
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::HeuristicTokenizer;
+
+    fn symbols(chunks: &[Chunk]) -> Vec<(&str, &str)> {
+        chunks
+            .iter()
+            .map(|chunk| {
+                (
+                    chunk.metadata.get(SYMBOL_PATH_KEY).unwrap_or_default(),
+                    chunk.metadata.get(LINES_KEY).unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn packs_small_items() {
+        let source = "use std::fmt;\n\nfn one() {}\n\nfn two() {}\n";
+        let chunker = CodeChunker::new(HeuristicTokenizer::default(), 100);
+        let chunks = chunker
+            .chunk(source, Language::Rust, &["crate"])
+            .expect("could be chunked");
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, source.trim_end());
+        assert_eq!(symbols(&chunks), [("crate", "1-5")]);
+    }
+
+    #[test]
+    fn splits_rust_items() {
+        let source = "//! Module docs.
+
+/// A store.
+#[derive(Debug)]
+pub struct Store {
+    values: Vec<u32>,
+}
+
+impl<T> Fetch for Store<T> {
+    fn fetch(&self, index: usize) -> Option<u32> {
+        self.values.get(index).copied()
+    }
+
+    fn fetch_all(&self) -> Vec<u32> {
+        let mut values = Vec::new();
+        for value in &self.values {
+            values.push(*value);
+        }
+        values
+    }
+}
+";
+        let chunker = CodeChunker::new(HeuristicTokenizer::default(), 30);
+        let chunks = chunker
+            .chunk(source, Language::Rust, &["graph", "store"])
+            .expect("could be chunked");
+
+        assert_eq!(symbols(&chunks), [
+            ("graph::store::Store", "1-7"),
+            ("graph::store::Store", "9-12"),
+            ("graph::store::Store::fetch_all", "14-17"),
+            ("graph::store::Store::fetch_all", "18-20"),
+            ("graph::store::Store", "21-21"),
+        ]);
+        assert!(chunks[0].text.starts_with("//! Module docs."));
+        assert!(chunks[0].text.ends_with("values: Vec<u32>,\n}"));
+        assert!(
+            chunks[1]
+                .text
+                .starts_with("impl<T> Fetch for Store<T> {\n    fn fetch")
+        );
+    }
+
+    #[test]
+    fn splits_oversized_functions_into_lines() {
+        let body = (0..20)
+            .map(|index| format!("    let value_{index} = {index};\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        let source = format!("fn long() {{\n{body}}}\n");
+        let chunker = CodeChunker::new(HeuristicTokenizer::default(), 40);
+        let chunks = chunker
+            .chunk(&source, Language::Rust, &[] as &[&str])
+            .expect("could be chunked");
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(HeuristicTokenizer::default().count_tokens(&chunk.text) <= 40);
+            assert_eq!(chunk.metadata.get(SYMBOL_PATH_KEY), Some("long"));
+            assert_eq!(chunk.span.slice(&source).expect("valid span"), chunk.text);
+        }
+        assert!(chunks[0].text.starts_with("fn long() {"));
+        assert!(chunks.last().expect("chunks").text.ends_with('}'));
+    }
+
+    #[test]
+    fn splits_python_classes() {
+        let source = r#"import os
+
+
+class Reader:
+    """Reads files."""
+
+    @staticmethod
+    def read(path):
+        with open(path) as file:
+            return file.read()
+
+    def exists(self, path):
+        return os.path.exists(path)
+"#;
+        let chunker = CodeChunker::new(HeuristicTokenizer::default(), 20);
+        let chunks = chunker
+            .chunk(source, Language::Python, &["io"])
+            .expect("could be chunked");
+
+        assert_eq!(symbols(&chunks), [
+            ("io", "1-1"),
+            ("io.Reader", "4-5"),
+            ("io.Reader.read", "7-10"),
+            ("io.Reader.exists", "12-13"),
+        ]);
+    }
+
+    #[test]
+    fn splits_typescript_classes() {
+        let source = "import { Store } from './store';
+
+// The graph client.
+export class Client {
+  private readonly store: Store;
+
+  constructor(store: Store) {
+    this.store = store;
+  }
+
+  async fetch(id: string): Promise<string | undefined> {
+    const value = await this.store.get(id);
+    return value?.toString();
+  }
+}
+
+export const createClient = (store: Store) => new Client(store);
+";
+        let chunker = CodeChunker::new(HeuristicTokenizer::default(), 40);
+        let chunks = chunker
+            .chunk(source, Language::TypeScript, &["client"])
+            .expect("could be chunked");
+
+        assert_eq!(symbols(&chunks), [
+            ("client", "1-1"),
+            ("client.Client", "3-9"),
+            ("client.Client", "11-15"),
+            ("client.createClient", "17-17"),
+        ]);
+        assert!(
+            chunks[1]
+                .text
+                .starts_with("// The graph client.\nexport class Client {")
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! Detection of near-duplicate chunks.
+//!
+//! Crawled websites repeat navigation and footers on every page and email threads quote earlier
+//! messages, so many chunks are identical or nearly identical. Embedding and storing every copy
+//! wastes resources and crowds out other results when searching.
+//!
+//! The [`Deduplicator`] estimates the [Jaccard similarity] of the word shingles of two chunks with
+//! [MinHash] signatures and groups chunks whose similarity reaches a threshold into a [`Cluster`].
+//! Candidate pairs are found with locality-sensitive hashing, so the chunks of many documents can
+//! be deduplicated without comparing every pair of chunks.
+//!
+//! [Jaccard similarity]: https://en.wikipedia.org/wiki/Jaccard_index
+//! [MinHash]: https://en.wikipedia.org/wiki/MinHash
+
+use std::collections::HashMap;
+
+use crate::{chunk::Chunk, hash::fnv1a, segmentation::words};
+
+/// Separates the words of a shingle before hashing, so that `["ab", "c"]` and `["a", "bc"]` differ.
+const WORD_SEPARATOR: u8 = 0x1F;
+
+/// Scrambles the bits of `value` with the finalizer of `SplitMix64`.
+const fn mix(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+/// The `MinHash` signature of a text.
+///
+/// Every value of the signature is the minimum of one hash function over the shingles of the text.
+/// The probability that two signatures agree in a value equals the Jaccard similarity of the sets
+/// of shingles of their texts.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MinHash {
+    values: Vec<u64>,
+}
+
+impl MinHash {
+    #[must_use]
+    pub fn values(&self) -> &[u64] {
+        &self.values
+    }
+
+    /// Estimates the Jaccard similarity of the texts of two signatures, between `0.0` and `1.0`.
+    ///
+    /// Signatures created with a different number of permutations have a similarity of `0.0`.
+    #[must_use]
+    #[expect(
+        clippy::float_arithmetic,
+        clippy::cast_precision_loss,
+        reason = "signatures have at most a few thousand values"
+    )]
+    pub fn similarity(&self, other: &Self) -> f64 {
+        if self.values.len() != other.values.len() || self.values.is_empty() {
+            return 0.0;
+        }
+        let equal = self
+            .values
+            .iter()
+            .zip(&other.values)
+            .filter(|(left, right)| left == right)
+            .count();
+        equal as f64 / self.values.len() as f64
+    }
+}
+
+/// A group of near-duplicate chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster<S> {
+    /// The representative of the cluster, which is the first of its chunks in input order.
+    pub canonical: Chunk,
+    /// The sources of all chunks of the cluster in input order, starting with the source of the
+    /// canonical chunk.
+    ///
+    /// A source occurs once for every chunk of the cluster it contains.
+    pub sources: Vec<S>,
+}
+
+impl<S> Cluster<S> {
+    /// Returns the number of chunks in this cluster, including the canonical chunk.
+    #[must_use]
+    pub fn occurrences(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns `true` if the cluster contains more than the canonical chunk.
+    #[must_use]
+    pub fn has_duplicates(&self) -> bool {
+        self.sources.len() > 1
+    }
+}
+
+/// Groups near-duplicate chunks.
+///
+/// The text of a chunk is split into lowercase [`words`], and every run of [`shingle_size`]
+/// consecutive words forms a shingle. Two chunks are near-duplicates if the Jaccard similarity of
+/// their shingles, estimated from [`MinHash`] signatures, reaches the [`threshold`].
+///
+/// Chunks are processed in input order. Every chunk joins the first cluster whose canonical chunk
+/// is similar enough, otherwise it becomes the canonical chunk of a new cluster. As candidates are
+/// found with locality-sensitive hashing, a pair of chunks slightly above the threshold may be
+/// missed, while identical chunks are always grouped together.
+///
+/// [`shingle_size`]: Self::with_shingle_size
+/// [`threshold`]: Self::with_threshold
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{Chunk, dedup::Deduplicator, segmentation::paragraphs};
+///
+/// let footer = "Copyright 2024 Example Inc. All rights reserved. Terms of service.";
+/// let pages = [
+///     ("about.html", format!("We build search tools.\n\n{footer}")),
+///     ("blog.html", format!("Our first post!\n\n{footer} Privacy.")),
+/// ];
+///
+/// // Chunk every page by paragraph
+/// let chunks = pages.iter().flat_map(|(source, text)| {
+///     paragraphs(text).into_iter().map(|paragraph| {
+///         let chunk = Chunk::from_segments(text, &[paragraph], 0..1).expect("valid chunk");
+///         (*source, chunk)
+///     })
+/// });
+/// let clusters = Deduplicator::new().with_threshold(0.7).deduplicate(chunks);
+///
+/// assert_eq!(clusters.len(), 3);
+/// assert_eq!(clusters[1].canonical.text, footer);
+/// assert_eq!(clusters[1].sources, ["about.html", "blog.html"]);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Deduplicator {
+    threshold: f64,
+    shingle_size: usize,
+    permutations: usize,
+}
+
+impl Deduplicator {
+    /// The default number of values of a signature.
+    pub const DEFAULT_PERMUTATIONS: usize = 128;
+    /// The default number of words per shingle.
+    pub const DEFAULT_SHINGLE_SIZE: usize = 3;
+    /// The default similarity threshold.
+    pub const DEFAULT_THRESHOLD: f64 = 0.8;
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            threshold: Self::DEFAULT_THRESHOLD,
+            shingle_size: Self::DEFAULT_SHINGLE_SIZE,
+            permutations: Self::DEFAULT_PERMUTATIONS,
+        }
+    }
+
+    /// Sets the minimum Jaccard similarity of two chunks to be considered near-duplicates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not in `(0.0, 1.0]`.
+    #[must_use]
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        assert!(
+            threshold > 0.0 && threshold <= 1.0,
+            "the similarity threshold has to be in (0, 1]"
+        );
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the number of consecutive words which form a shingle.
+    ///
+    /// Texts with fewer words form a single shingle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shingle_size` is zero.
+    #[must_use]
+    pub const fn with_shingle_size(mut self, shingle_size: usize) -> Self {
+        assert!(shingle_size > 0, "a shingle needs at least one word");
+        self.shingle_size = shingle_size;
+        self
+    }
+
+    /// Sets the number of values of a signature.
+    ///
+    /// More permutations estimate the similarity more precisely at the cost of speed and memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permutations` is zero.
+    #[must_use]
+    pub const fn with_permutations(mut self, permutations: usize) -> Self {
+        assert!(permutations > 0, "a signature needs at least one value");
+        self.permutations = permutations;
+        self
+    }
+
+    /// Computes the signature of `text`.
+    #[must_use]
+    pub fn signature(&self, text: &str) -> MinHash {
+        let words = words(text)
+            .map(|word| word.text.to_lowercase())
+            .collect::<Vec<_>>();
+        let shingles = words
+            .windows(self.shingle_size.min(words.len()).max(1))
+            .map(|shingle| {
+                let mut bytes = Vec::new();
+                for word in shingle {
+                    bytes.extend_from_slice(word.as_bytes());
+                    bytes.push(WORD_SEPARATOR);
+                }
+                fnv1a(&bytes)
+            })
+            .collect::<Vec<_>>();
+
+        let values = (0..self.permutations as u64)
+            .map(|permutation| {
+                let seed = mix(permutation);
+                shingles
+                    .iter()
+                    .map(|shingle| mix(shingle ^ seed))
+                    .min()
+                    .unwrap_or(u64::MAX)
+            })
+            .collect();
+        MinHash { values }
+    }
+
+    /// Returns the number of bands and rows per band of the locality-sensitive hash.
+    ///
+    /// Two signatures become candidates if they agree in all rows of at least one band. The
+    /// configuration is chosen so that the similarity at which this is as likely as not, roughly
+    /// `(1 / bands) ^ (1 / rows)`, is closest to the threshold, using as many values as possible.
+    #[expect(
+        clippy::float_arithmetic,
+        clippy::cast_precision_loss,
+        reason = "signatures have at most a few thousand values"
+    )]
+    #[expect(
+        clippy::integer_division,
+        clippy::integer_division_remainder_used,
+        reason = "values which do not fill a whole band are not used"
+    )]
+    fn bands(&self) -> (usize, usize) {
+        (1..=self.permutations)
+            .map(|rows| (self.permutations / rows, rows))
+            .min_by(|&(left_bands, left_rows), &(right_bands, right_rows)| {
+                let distance = |bands: usize, rows: usize| {
+                    ((1.0 / bands as f64).powf(1.0 / rows as f64) - self.threshold).abs()
+                };
+                distance(left_bands, left_rows)
+                    .total_cmp(&distance(right_bands, right_rows))
+                    .then((right_bands * right_rows).cmp(&(left_bands * left_rows)))
+            })
+            .unwrap_or((1, self.permutations))
+    }
+
+    /// Groups near-duplicate chunks and returns the clusters in order of their canonical chunks.
+    ///
+    /// Every chunk is paired with its source, e.g. the index of the chunk to deduplicate a single
+    /// document or an identifier of its document to deduplicate a batch of documents. Every chunk
+    /// is part of exactly one cluster, so clusters without duplicates hold unique chunks.
+    pub fn deduplicate<S>(&self, chunks: impl IntoIterator<Item = (S, Chunk)>) -> Vec<Cluster<S>> {
+        let (bands, rows) = self.bands();
+        let mut clusters = Vec::<(Cluster<S>, MinHash)>::new();
+        let mut buckets = HashMap::<(usize, u64), Vec<usize>>::new();
+
+        for (source, chunk) in chunks {
+            let signature = self.signature(&chunk.text);
+            let keys = signature
+                .values
+                .chunks_exact(rows)
+                .take(bands)
+                .enumerate()
+                .map(|(band, values)| {
+                    let bytes = values
+                        .iter()
+                        .flat_map(|value| value.to_le_bytes())
+                        .collect::<Vec<_>>();
+                    (band, fnv1a(&bytes))
+                })
+                .collect::<Vec<_>>();
+
+            let mut candidates = keys
+                .iter()
+                .filter_map(|key| buckets.get(key))
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>();
+            candidates.sort_unstable();
+            candidates.dedup();
+
+            let matched = candidates
+                .into_iter()
+                .find(|&index| clusters[index].1.similarity(&signature) >= self.threshold);
+            if let Some(index) = matched {
+                clusters[index].0.sources.push(source);
+            } else {
+                for key in keys {
+                    buckets.entry(key).or_default().push(clusters.len());
+                }
+                clusters.push((
+                    Cluster {
+                        canonical: chunk,
+                        sources: vec![source],
+                    },
+                    signature,
+                ));
+            }
+        }
+
+        clusters.into_iter().map(|(cluster, _)| cluster).collect()
+    }
+}
+
+impl Default for Deduplicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segmentation::paragraphs;
+
+    #[test]
+    fn estimates_similarity() {
+        let deduplicator = Deduplicator::new().with_permutations(256);
+        let text = "the quick brown fox jumps over the lazy dog near the river bank today";
+        let signature = deduplicator.signature(text);
+
+        assert!(
+            (signature.similarity(&deduplicator.signature(&text.to_uppercase())) - 1.0).abs()
+                < f64::EPSILON
+        );
+        let similar = deduplicator
+            .signature("the quick brown fox jumps over the lazy dog near the river bank tomorrow");
+        assert!(signature.similarity(&similar) > 0.6);
+        let different =
+            deduplicator.signature("a completely unrelated sentence about chunking text");
+        assert!(signature.similarity(&different) < 0.1);
+    }
+
+    #[test]
+    fn chooses_bands_near_threshold() {
+        assert_eq!(Deduplicator::new().with_threshold(1.0).bands(), (1, 128));
+        let (bands, rows) = Deduplicator::new().with_threshold(0.5).bands();
+        assert!(bands > 1 && rows > 1);
+    }
+
+    #[test]
+    fn deduplicates_single_document() {
+        let text = "Subscribe to our newsletter for weekly updates.\n\nChunking splits \
+                    documents.\n\nSubscribe to our newsletter for weekly updates!";
+        let chunks = paragraphs(text)
+            .into_iter()
+            .enumerate()
+            .map(|(index, paragraph)| {
+                let chunk = Chunk::from_segments(text, &[paragraph], 0..1).expect("valid chunk");
+                (index, chunk)
+            });
+
+        let clusters = Deduplicator::new().deduplicate(chunks);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].sources, [0, 2]);
+        assert_eq!(
+            clusters[0].canonical.text,
+            "Subscribe to our newsletter for weekly updates."
+        );
+        assert!(!clusters[1].has_duplicates());
+    }
+}
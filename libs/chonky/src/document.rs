@@ -0,0 +1,226 @@
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+use error_stack::{Report, ResultExt};
+
+/// The format of a [`Document`]'s source.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MediaType {
+    PlainText,
+    Markdown,
+    Html,
+    Pdf,
+    Other(String),
+}
+
+impl MediaType {
+    /// Parses a media type from its essence, e.g. `text/markdown`.
+    ///
+    /// Parameters such as `; charset=utf-8` are ignored. Unknown media types are kept as
+    /// [`MediaType::Other`].
+    #[must_use]
+    pub fn from_essence(media_type: &str) -> Self {
+        let essence = media_type
+            .split_once(';')
+            .map_or(media_type, |(essence, _)| essence)
+            .trim()
+            .to_ascii_lowercase();
+
+        match essence.as_str() {
+            "text/plain" => Self::PlainText,
+            "text/markdown" | "text/x-markdown" => Self::Markdown,
+            "text/html" | "application/xhtml+xml" => Self::Html,
+            "application/pdf" => Self::Pdf,
+            _ => Self::Other(essence),
+        }
+    }
+
+    #[must_use]
+    pub fn essence(&self) -> &str {
+        match self {
+            Self::PlainText => "text/plain",
+            Self::Markdown => "text/markdown",
+            Self::Html => "text/html",
+            Self::Pdf => "application/pdf",
+            Self::Other(essence) => essence,
+        }
+    }
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.essence())
+    }
+}
+
+/// Free-form key-value annotations attached to documents, segments and chunks.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Metadata(BTreeMap<String, String>);
+
+impl Metadata {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Inserts a value, returning the previous value for `key` if there was one.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        self.0.insert(key.into(), value.into())
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for Metadata
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<K, V> Extend<(K, V)> for Metadata
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        self.0.extend(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("the document source is not valid UTF-8")]
+pub struct DocumentError;
+
+/// A file to be segmented and chunked.
+///
+/// The document owns the raw bytes of its source. All [`Span`]s produced from a document refer
+/// to offsets inside of [`Document::text`].
+///
+/// [`Span`]: crate::Span
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Document {
+    source: Vec<u8>,
+    media_type: MediaType,
+    metadata: Metadata,
+}
+
+impl Document {
+    #[must_use]
+    pub fn new(source: impl Into<Vec<u8>>, media_type: MediaType) -> Self {
+        Self {
+            source: source.into(),
+            media_type,
+            metadata: Metadata::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &[u8] {
+        &self.source
+    }
+
+    #[must_use]
+    pub const fn media_type(&self) -> &MediaType {
+        &self.media_type
+    }
+
+    #[must_use]
+    pub const fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Returns the source of the document as text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DocumentError`] if the source is not valid UTF-8.
+    pub fn text(&self) -> Result<&str, Report<DocumentError>> {
+        core::str::from_utf8(&self.source)
+            .change_context(DocumentError)
+            .attach_printable_lazy(|| format!("media type: {}", self.media_type))
+    }
+
+    #[must_use]
+    pub fn into_source(self) -> Vec<u8> {
+        self.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn media_type_round_trip() {
+        for media_type in [
+            MediaType::PlainText,
+            MediaType::Markdown,
+            MediaType::Html,
+            MediaType::Pdf,
+        ] {
+            assert_eq!(MediaType::from_essence(media_type.essence()), media_type);
+        }
+
+        assert_eq!(
+            MediaType::from_essence("Text/HTML; charset=utf-8"),
+            MediaType::Html
+        );
+        assert_eq!(
+            MediaType::from_essence("image/png"),
+            MediaType::Other("image/png".to_owned())
+        );
+    }
+
+    #[test]
+    fn text_requires_utf8() {
+        let document = Document::new(b"hello".as_slice(), MediaType::PlainText);
+        assert_eq!(document.text().expect("valid UTF-8"), "hello");
+
+        let document = Document::new(vec![0xFF, 0xFE], MediaType::PlainText);
+        let _: Report<_> = document.text().expect_err("invalid UTF-8");
+    }
+}
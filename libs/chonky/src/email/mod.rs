@@ -0,0 +1,269 @@
+//! Extraction of segments from emails.
+//!
+//! Messages are parsed according to [RFC 5322] and MIME. Multipart messages are decoded together
+//! with their transfer encodings and character sets. The `text/plain` body of a message is
+//! preferred, messages with an HTML body only are extracted with the [HTML front-end], which drops
+//! scripts, styles and other boilerplate.
+//!
+//! Replies usually repeat the messages they reply to, so quoted lines, quoted reply chains and
+//! signatures are removed from the body. Only the text written by the sender is extracted.
+//!
+//! The headers of a message are available as [`Metadata`] of the [`Email`], see [`FROM_KEY`],
+//! [`TO_KEY`], [`CC_KEY`], [`DATE_KEY`], [`SUBJECT_KEY`], [`MESSAGE_ID_KEY`] and
+//! [`THREAD_ID_KEY`].
+//!
+//! [RFC 5322]: https://www.rfc-editor.org/rfc/rfc5322
+//! [HTML front-end]: crate::html
+
+mod reply;
+
+use alloc::borrow::Cow;
+
+use error_stack::{Report, ResultExt, ensure};
+use mail_parser::{Address, HeaderValue, Message, MessageParser, mailbox::mbox::MessageIterator};
+
+use crate::{document::Metadata, extraction::Extraction, html, segment::SegmentKind};
+
+/// Metadata key of the sender of a message, e.g. `Ada Lovelace <ada@example.com>`.
+pub const FROM_KEY: &str = "from";
+
+/// Metadata key of the recipients of a message, separated by `", "`.
+pub const TO_KEY: &str = "to";
+
+/// Metadata key of the carbon-copy recipients of a message, separated by `", "`.
+pub const CC_KEY: &str = "cc";
+
+/// Metadata key of the date of a message in RFC 3339 format, e.g. `2024-05-01T09:30:00+02:00`.
+pub const DATE_KEY: &str = "date";
+
+/// Metadata key of the subject of a message.
+pub const SUBJECT_KEY: &str = "subject";
+
+/// Metadata key of the `Message-ID` of a message, without angle brackets.
+pub const MESSAGE_ID_KEY: &str = "message_id";
+
+/// Metadata key of the ID of the thread of a message.
+///
+/// The thread ID is the `Message-ID` of the first message of the thread, taken from the
+/// `References` or `In-Reply-To` headers. Messages which do not reply to another message start a
+/// thread of their own.
+pub const THREAD_ID_KEY: &str = "thread_id";
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not parse email")]
+pub struct EmailError;
+
+/// Formats the mailboxes of an address header, e.g. `Ada Lovelace <ada@example.com>`.
+fn format_address(address: &Address<'_>) -> String {
+    address
+        .iter()
+        .filter_map(|mailbox| match (mailbox.name(), mailbox.address()) {
+            (Some(name), Some(address)) => Some(format!("{name} <{address}>")),
+            (None, Some(address)) => Some(address.to_owned()),
+            (Some(name), None) => Some(name.to_owned()),
+            (None, None) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the first message ID of a `References` or `In-Reply-To` header.
+fn first_id<'h>(header: &'h HeaderValue<'_>) -> Option<&'h str> {
+    header
+        .as_text_list()
+        .and_then(|ids| ids.first().copied())
+        .or_else(|| header.as_text())
+}
+
+/// A single email message.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::email::{Email, FROM_KEY, THREAD_ID_KEY};
+///
+/// let email = Email::parse(
+///     b"From: Ada <ada@example.com>\r\n\
+///       Subject: Re: Engines\r\n\
+///       Message-ID: <2@example.com>\r\n\
+///       In-Reply-To: <1@example.com>\r\n\
+///       \r\n\
+///       Sounds good.\r\n\
+///       \r\n\
+///       On Monday, Charles wrote:\r\n\
+///       > Shall we build it?\r\n",
+/// )?;
+///
+/// assert_eq!(
+///     email.metadata().get(FROM_KEY),
+///     Some("Ada <ada@example.com>")
+/// );
+/// assert_eq!(email.metadata().get(THREAD_ID_KEY), Some("1@example.com"));
+/// assert_eq!(email.extraction().text(), "Re: Engines\n\nSounds good.");
+/// # Ok::<(), error_stack::Report<chonky::email::EmailError>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email {
+    metadata: Metadata,
+    extraction: Extraction,
+}
+
+impl Email {
+    /// Parses a single message.
+    ///
+    /// The subject of the message is extracted as a heading, followed by the paragraphs of its
+    /// body without quoted replies and signatures.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EmailError`] if `source` is not an email message.
+    pub fn parse(source: &[u8]) -> Result<Self, Report<EmailError>> {
+        let message = MessageParser::default()
+            .parse(source)
+            .ok_or_else(|| Report::new(EmailError))?;
+        ensure!(
+            !message.headers().is_empty(),
+            Report::new(EmailError).attach_printable("the message has no headers")
+        );
+        Ok(Self::from_message(&message))
+    }
+
+    fn from_message(message: &Message<'_>) -> Self {
+        let mut metadata = Metadata::new();
+        for (key, address) in [
+            (FROM_KEY, message.from()),
+            (TO_KEY, message.to()),
+            (CC_KEY, message.cc()),
+        ] {
+            if let Some(address) = address.map(format_address) {
+                if !address.is_empty() {
+                    metadata.insert(key, address);
+                }
+            }
+        }
+        if let Some(date) = message.date() {
+            metadata.insert(DATE_KEY, date.to_rfc3339());
+        }
+        if let Some(subject) = message.subject() {
+            metadata.insert(SUBJECT_KEY, subject);
+        }
+        let message_id = message.message_id();
+        if let Some(message_id) = message_id {
+            metadata.insert(MESSAGE_ID_KEY, message_id);
+        }
+        if let Some(thread_id) = first_id(message.references())
+            .or_else(|| first_id(message.in_reply_to()))
+            .or(message_id)
+        {
+            metadata.insert(THREAD_ID_KEY, thread_id);
+        }
+
+        let mut extraction = Extraction::new();
+        if let Some(subject) = message.subject() {
+            extraction.push(SegmentKind::Heading { level: 1 }, subject);
+        }
+        // Messages without a plain text body list their HTML bodies as text bodies instead
+        for part in message.text_bodies() {
+            let body = if part.is_text_html() {
+                Cow::Owned(html::extract(part.contents()).into_parts().0)
+            } else {
+                Cow::Borrowed(part.text_contents().unwrap_or_default())
+            };
+            for paragraph in reply::strip(&body).split("\n\n") {
+                extraction.push(SegmentKind::Paragraph, paragraph);
+            }
+        }
+
+        Self {
+            metadata,
+            extraction,
+        }
+    }
+
+    /// Returns the headers of the message.
+    #[must_use]
+    pub const fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    #[must_use]
+    pub const fn extraction(&self) -> &Extraction {
+        &self.extraction
+    }
+
+    #[must_use]
+    pub fn into_parts(self) -> (Metadata, Extraction) {
+        (self.metadata, self.extraction)
+    }
+}
+
+/// Parses all messages of a mailbox in the mbox format.
+///
+/// Every message starts with a `From ` line. Messages which are not valid emails are skipped.
+///
+/// # Errors
+///
+/// Returns [`EmailError`] if the mailbox could not be read.
+pub fn parse_mbox(source: &[u8]) -> Result<Vec<Email>, Report<EmailError>> {
+    let mut emails = Vec::new();
+    for (index, message) in MessageIterator::new(source).enumerate() {
+        let message = message
+            .map_err(|_error| Report::new(EmailError))
+            .attach_printable_lazy(|| format!("message {index} of the mailbox"))?;
+        if let Ok(email) = Email::parse(message.contents()) {
+            emails.push(email);
+        }
+    }
+    Ok(emails)
+}
+
+/// Returns `true` if `source` looks like a mailbox in the mbox format.
+#[must_use]
+pub fn is_mbox(source: &[u8]) -> bool {
+    source.starts_with(b"From ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_malformed_input() {
+        let _: Report<_> = Email::parse(b"").expect_err("empty message");
+        let _: Report<_> =
+            Email::parse(b"no headers, just text\r\n").expect_err("text without headers");
+        let _: Report<_> =
+            Email::parse(b"\r\n\r\nBody without headers").expect_err("body without headers");
+    }
+
+    #[test]
+    fn empty_body() {
+        let email = Email::parse(
+            b"From: ada@example.com\r\n\
+              To: Charles Babbage <charles@example.com>, ada@example.com\r\n\
+              Subject: Engines\r\n\
+              References: <1@example.com> <2@example.com>\r\n\
+              \r\n",
+        )
+        .expect("valid email");
+
+        assert_eq!(email.metadata().get(FROM_KEY), Some("ada@example.com"));
+        assert_eq!(
+            email.metadata().get(TO_KEY),
+            Some("Charles Babbage <charles@example.com>, ada@example.com")
+        );
+        assert_eq!(email.metadata().get(CC_KEY), None);
+        assert_eq!(email.metadata().get(MESSAGE_ID_KEY), None);
+        assert_eq!(email.metadata().get(THREAD_ID_KEY), Some("1@example.com"));
+        assert_eq!(email.extraction().text(), "Engines");
+        assert_eq!(email.extraction().segments().len(), 1);
+    }
+
+    #[test]
+    fn empty_mailbox() {
+        assert!(!is_mbox(b""));
+        assert!(!is_mbox(b"From: ada@example.com\r\n"));
+        assert!(is_mbox(b"From ada@example.com Mon Jan  1 00:00:00 2024\n"));
+        assert!(parse_mbox(b"").expect("empty mailbox").is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//! Removal of quoted replies and signatures from the body of a message.
+
+/// Lines which start the signature of a message, after which nothing is extracted.
+///
+/// `-- ` is the signature delimiter of RFC 3676, which is frequently sent without the trailing
+/// space.
+const SIGNATURE_DELIMITERS: &[&str] = &["--", "__"];
+
+/// Prefixes of lines which clients append to messages, e.g. `Sent from my phone`.
+const CLIENT_SIGNATURES: &[&str] = &["Sent from my ", "Get Outlook for "];
+
+/// Returns `true` if `lines` start with the header a client puts above a quoted reply chain.
+fn is_reply_header(lines: &[&str]) -> bool {
+    let Some(line) = lines.first().map(|line| line.trim()) else {
+        return false;
+    };
+
+    // `On Mon, 1 Jan 2024, Ada <ada@example.com> wrote:`, which may be wrapped onto two lines
+    if line.starts_with("On ") {
+        return line.ends_with("wrote:")
+            || lines
+                .get(1)
+                .is_some_and(|next| next.trim().ends_with("wrote:"));
+    }
+
+    let dashes = line.trim_matches('-').trim();
+    if line.starts_with("---") && dashes.eq_ignore_ascii_case("Original Message") {
+        return true;
+    }
+
+    // Outlook quotes a block of headers instead, starting with `From:`
+    line.starts_with("From:")
+        && lines.iter().skip(1).take(4).any(|line| {
+            let line = line.trim_start();
+            line.starts_with("Sent:") || line.starts_with("Date:")
+        })
+}
+
+/// Removes quoted lines, reply chains and the signature from `body`.
+///
+/// Lines starting with `>` are quoted and removed. Everything after the header of a quoted reply
+/// chain, e.g. `On Monday, Ada wrote:`, or after a signature delimiter is removed.
+pub(crate) fn strip(body: &str) -> String {
+    let lines = body.lines().collect::<Vec<_>>();
+
+    let mut kept = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let line = line.trim_end();
+        if SIGNATURE_DELIMITERS.contains(&line) || is_reply_header(&lines[index..]) {
+            break;
+        }
+        if !line.trim_start().starts_with('>') {
+            kept.push(line);
+        }
+    }
+
+    while let Some(line) = kept.last() {
+        let line = line.trim();
+        if line.is_empty()
+            || CLIENT_SIGNATURES
+                .iter()
+                .any(|signature| line.starts_with(signature))
+        {
+            kept.pop();
+        } else {
+            break;
+        }
+    }
+
+    kept.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_replies() {
+        assert_eq!(
+            strip(
+                "Thanks!\r\n\r\n> Can you review?\r\n\r\nDone.\r\n\r\nOn Tue, 2 Jan 2024 at \
+                 10:00, Ada\r\n<ada@example.com> wrote:\r\n> Earlier message"
+            ),
+            "Thanks!\n\n\nDone."
+        );
+        assert_eq!(
+            strip("Approved.\n\n-----Original Message-----\nFrom: Ada\nSent: Monday"),
+            "Approved."
+        );
+        assert_eq!(
+            strip("Approved.\n\nFrom: Ada Lovelace\nSent: Monday\nTo: Charles\n\nEarlier"),
+            "Approved."
+        );
+        assert_eq!(strip("On it.\n\nSent from my phone\n"), "On it.");
+    }
+
+    #[test]
+    fn strips_signatures() {
+        assert_eq!(strip("See you.\n-- \nAda\nAnalyst"), "See you.");
+        assert_eq!(strip("See you.\n\n--\nAda"), "See you.");
+        assert_eq!(
+            strip("From: the top\nOn the other hand, this stays."),
+            "From: the top\nOn the other hand, this stays."
+        );
+    }
+}
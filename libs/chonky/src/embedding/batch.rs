@@ -0,0 +1,268 @@
+use core::time::Duration;
+
+use error_stack::{Report, ResultExt, ensure};
+use futures::{StreamExt, TryStreamExt, stream};
+
+use super::{Embedder, EmbeddingError};
+
+/// Splits the input of an [`Embedder`] into batches and retries failed batches.
+///
+/// Embedding APIs limit the number of texts per request and occasionally fail transiently, e.g.
+/// when a rate limit is hit. This wrapper splits the input into batches of at most
+/// [`batch_size`] texts, embeds up to [`concurrency`] batches at the same time, and retries a
+/// failed batch up to [`max_retries`] times. The delay before a retry starts at
+/// [`initial_backoff`] and doubles with every further attempt.
+///
+/// The vectors returned by the wrapped embedder are checked to match the number of texts and its
+/// [`dimensions`].
+///
+/// [`batch_size`]: Self::with_batch_size
+/// [`concurrency`]: Self::with_concurrency
+/// [`max_retries`]: Self::with_max_retries
+/// [`initial_backoff`]: Self::with_initial_backoff
+/// [`dimensions`]: Embedder::dimensions
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::embedding::{BatchedEmbedder, Embedder, HashingEmbedder};
+///
+/// # tokio::runtime::Builder::new_current_thread()
+/// #     .enable_time()
+/// #     .build()
+/// #     .expect("runtime")
+/// #     .block_on(async {
+/// let embedder = BatchedEmbedder::new(HashingEmbedder::new()).with_batch_size(2);
+///
+/// let vectors = embedder
+///     .embed(&["first", "second", "third"])
+///     .await
+///     .expect("embedding should succeed");
+/// assert_eq!(vectors.len(), 3);
+/// assert_eq!(vectors[2], HashingEmbedder::new().embed_text("third"));
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchedEmbedder<E> {
+    embedder: E,
+    batch_size: usize,
+    concurrency: usize,
+    max_retries: u32,
+    initial_backoff: Duration,
+}
+
+impl<E> BatchedEmbedder<E> {
+    #[must_use]
+    pub const fn new(embedder: E) -> Self {
+        Self {
+            embedder,
+            batch_size: 64,
+            concurrency: 4,
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+
+    /// Sets the maximum number of texts sent to the wrapped embedder at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    #[must_use]
+    pub const fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "a batch has to contain at least one text");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the maximum number of batches which are embedded at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `concurrency` is zero.
+    #[must_use]
+    pub const fn with_concurrency(mut self, concurrency: usize) -> Self {
+        assert!(
+            concurrency > 0,
+            "at least one batch has to be embedded at a time"
+        );
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets how often a failed batch is retried before giving up.
+    #[must_use]
+    pub const fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry of a failed batch.
+    #[must_use]
+    pub const fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    #[must_use]
+    pub const fn embedder(&self) -> &E {
+        &self.embedder
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> E {
+        self.embedder
+    }
+}
+
+impl<E> BatchedEmbedder<E>
+where
+    E: Embedder + Sync,
+{
+    async fn embed_batch(&self, batch: &[&str]) -> Result<Vec<Vec<f32>>, Report<EmbeddingError>> {
+        let mut attempt = 0;
+        let vectors = loop {
+            match self.embedder.embed(batch).await {
+                Ok(vectors) => break vectors,
+                Err(error) if attempt < self.max_retries => {
+                    let backoff = self
+                        .initial_backoff
+                        .saturating_mul(2_u32.saturating_pow(attempt));
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                    drop(error);
+                }
+                Err(error) => {
+                    return Err(
+                        error.attach_printable(format!("gave up after {} attempts", attempt + 1))
+                    );
+                }
+            }
+        };
+
+        ensure!(
+            vectors.len() == batch.len(),
+            Report::new(EmbeddingError).attach_printable(format!(
+                "expected {} vectors, got {}",
+                batch.len(),
+                vectors.len()
+            ))
+        );
+        let dimensions = self.embedder.dimensions();
+        if let Some(vector) = vectors.iter().find(|vector| vector.len() != dimensions) {
+            return Err(Report::new(EmbeddingError).attach_printable(format!(
+                "expected vectors with {dimensions} dimensions, got {}",
+                vector.len()
+            )));
+        }
+
+        Ok(vectors)
+    }
+}
+
+impl<E> Embedder for BatchedEmbedder<E>
+where
+    E: Embedder + Sync,
+{
+    fn dimensions(&self) -> usize {
+        self.embedder.dimensions()
+    }
+
+    async fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, Report<EmbeddingError>> {
+        let batches = texts
+            .chunks(self.batch_size)
+            .map(|batch| self.embed_batch(batch))
+            .collect::<Vec<_>>();
+        let batches = stream::iter(batches)
+            .buffered(self.concurrency)
+            .try_collect::<Vec<_>>()
+            .await
+            .attach_printable_lazy(|| format!("embedding {} texts", texts.len()))?;
+
+        Ok(batches.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use error_stack::bail;
+
+    use super::*;
+    use crate::embedding::HashingEmbedder;
+
+    /// Fails the first `failures` calls and records the largest batch it was called with.
+    struct Flaky {
+        inner: HashingEmbedder,
+        failures: AtomicUsize,
+        largest_batch: AtomicUsize,
+    }
+
+    impl Flaky {
+        const fn new(failures: usize) -> Self {
+            Self {
+                inner: HashingEmbedder::new().with_dimensions(8),
+                failures: AtomicUsize::new(failures),
+                largest_batch: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl Embedder for Flaky {
+        fn dimensions(&self) -> usize {
+            self.inner.dimensions()
+        }
+
+        async fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, Report<EmbeddingError>> {
+            self.largest_batch.fetch_max(texts.len(), Ordering::Relaxed);
+            let remaining = self.failures.load(Ordering::Relaxed);
+            if remaining > 0 {
+                self.failures.store(remaining - 1, Ordering::Relaxed);
+                bail!(EmbeddingError);
+            }
+            self.inner.embed(texts).await
+        }
+    }
+
+    #[tokio::test]
+    async fn splits_into_batches() {
+        let embedder = BatchedEmbedder::new(Flaky::new(0))
+            .with_batch_size(2)
+            .with_concurrency(2);
+        let texts = ["a", "b", "c", "d", "e"];
+
+        let vectors = embedder
+            .embed(&texts)
+            .await
+            .expect("embedding should succeed");
+        assert_eq!(embedder.embedder().largest_batch.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            vectors,
+            HashingEmbedder::new()
+                .with_dimensions(8)
+                .embed(&texts)
+                .await
+                .expect("embedding should succeed")
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_failed_batches() {
+        let embedder = BatchedEmbedder::new(Flaky::new(2))
+            .with_max_retries(2)
+            .with_initial_backoff(Duration::ZERO);
+        embedder
+            .embed(&["a", "b"])
+            .await
+            .expect("embedding should succeed after retrying");
+
+        let embedder = BatchedEmbedder::new(Flaky::new(2))
+            .with_max_retries(1)
+            .with_initial_backoff(Duration::ZERO);
+        let _: Report<_> = embedder
+            .embed(&["a", "b"])
+            .await
+            .expect_err("embedding should give up");
+    }
+}
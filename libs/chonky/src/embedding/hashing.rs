@@ -0,0 +1,146 @@
+use core::future::{self, Future};
+
+use error_stack::Report;
+
+use super::{Embedder, EmbeddingError};
+use crate::{hash::fnv1a, segmentation::words};
+
+/// Embeds texts locally by feature hashing.
+///
+/// Every word of a text is lowercased and hashed into one of [`dimensions`] buckets, with the sign
+/// of its contribution also taken from the hash. The resulting vector is normalized to unit
+/// length, so the cosine similarity of two vectors grows with the number of words their texts
+/// share. Texts without any words are embedded as the zero vector.
+///
+/// The embedder does not capture meaning beyond shared words, but it is deterministic, fast, and
+/// does not require a model. By default it uses [`DEFAULT_DIMENSIONS`], the dimensionality of the
+/// embeddings stored in the graph.
+///
+/// [`dimensions`]: Self::with_dimensions
+/// [`DEFAULT_DIMENSIONS`]: Self::DEFAULT_DIMENSIONS
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::embedding::HashingEmbedder;
+///
+/// let embedder = HashingEmbedder::new();
+/// let vector = embedder.embed_text("Chunking is fun");
+/// assert_eq!(vector.len(), HashingEmbedder::DEFAULT_DIMENSIONS);
+/// assert_eq!(vector, embedder.embed_text("chunking IS fun!"));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl HashingEmbedder {
+    /// The default number of dimensions, matching `Embedding::DIM` in `graph-types`.
+    pub const DEFAULT_DIMENSIONS: usize = 3072;
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            dimensions: Self::DEFAULT_DIMENSIONS,
+        }
+    }
+
+    /// Sets the number of dimensions of the returned vectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dimensions` is zero.
+    #[must_use]
+    pub const fn with_dimensions(mut self, dimensions: usize) -> Self {
+        assert!(dimensions > 0, "an embedding needs at least one dimension");
+        self.dimensions = dimensions;
+        self
+    }
+
+    /// Embeds a single text.
+    #[must_use]
+    #[expect(
+        clippy::float_arithmetic,
+        reason = "vectors are normalized to unit length"
+    )]
+    pub fn embed_text(self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0_f32; self.dimensions];
+        let buckets = self.dimensions as u64;
+
+        for word in words(text) {
+            let hash = fnv1a(word.text.to_lowercase().as_bytes());
+            // The bucket is less than `dimensions`, so it always fits into `usize`
+            let bucket = hash
+                .checked_rem(buckets)
+                .and_then(|bucket| usize::try_from(bucket).ok())
+                .unwrap_or_default();
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            if let Some(value) = vector.get_mut(bucket) {
+                *value += sign;
+            }
+        }
+
+        let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for value in &mut vector {
+                *value /= norm;
+            }
+        }
+        vector
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Embedder for HashingEmbedder {
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn embed(
+        &self,
+        texts: &[&str],
+    ) -> impl Future<Output = Result<Vec<Vec<f32>>, Report<EmbeddingError>>> + Send {
+        future::ready(Ok(texts.iter().map(|text| self.embed_text(text)).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[expect(clippy::float_arithmetic, reason = "cosine similarity of unit vectors")]
+    fn similarity(lhs: &[f32], rhs: &[f32]) -> f32 {
+        lhs.iter().zip(rhs).map(|(lhs, rhs)| lhs * rhs).sum()
+    }
+
+    #[test]
+    fn embeds_deterministically() {
+        let embedder = HashingEmbedder::new().with_dimensions(256);
+        let vector = embedder.embed_text("The quick brown fox");
+
+        assert_eq!(vector.len(), 256);
+        assert_eq!(vector, embedder.embed_text("the QUICK brown fox."));
+        assert!((similarity(&vector, &vector) - 1.0).abs() < 1e-5);
+        assert!(
+            embedder
+                .embed_text("  ...  ")
+                .iter()
+                .all(|value| *value == 0.0)
+        );
+    }
+
+    #[test]
+    fn shared_words_are_similar() {
+        let embedder = HashingEmbedder::new();
+        let cats = embedder.embed_text("Cats sleep most of the day");
+        let more_cats = embedder.embed_text("Cats sleep in the sun");
+        let rust = embedder.embed_text("Rust guarantees memory safety");
+
+        assert!(similarity(&cats, &more_cats) > similarity(&cats, &rust));
+    }
+}
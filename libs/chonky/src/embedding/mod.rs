@@ -0,0 +1,79 @@
+//! Embedding of chunks into vectors.
+//!
+//! The [`Embedder`] trait abstracts over embedding models, whether they are called through a
+//! remote API or run locally. [`BatchedEmbedder`] wraps any embedder to split large inputs into
+//! batches, embed several batches concurrently, and retry failed batches.
+//!
+//! Vectors are returned as `Vec<f32>`, which converts directly into the `Embedding` type of
+//! `graph-types` by `Embedding::from(vector)`. [`HashingEmbedder`] produces vectors of exactly
+//! that dimensionality without calling any model, which makes it suitable for tests and offline
+//! deployments.
+
+mod batch;
+mod hashing;
+
+use core::future::Future;
+
+use error_stack::Report;
+
+pub use self::{batch::BatchedEmbedder, hashing::HashingEmbedder};
+use crate::chunk::Chunk;
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not embed texts")]
+pub struct EmbeddingError;
+
+/// Converts texts into vectors of a fixed dimensionality.
+pub trait Embedder {
+    /// Returns the number of dimensions of every vector returned by [`embed`].
+    ///
+    /// [`embed`]: Self::embed
+    fn dimensions(&self) -> usize;
+
+    /// Embeds `texts` and returns one vector per text, in the same order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EmbeddingError`] if the texts could not be embedded.
+    fn embed(
+        &self,
+        texts: &[&str],
+    ) -> impl Future<Output = Result<Vec<Vec<f32>>, Report<EmbeddingError>>> + Send;
+
+    /// Embeds the text of every chunk and returns one vector per chunk, in the same order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EmbeddingError`] if the chunks could not be embedded.
+    fn embed_chunks(
+        &self,
+        chunks: &[Chunk],
+    ) -> impl Future<Output = Result<Vec<Vec<f32>>, Report<EmbeddingError>>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            let texts = chunks
+                .iter()
+                .map(|chunk| chunk.text.as_str())
+                .collect::<Vec<_>>();
+            self.embed(&texts).await
+        }
+    }
+}
+
+impl<E> Embedder for &E
+where
+    E: Embedder + Sync + ?Sized,
+{
+    fn dimensions(&self) -> usize {
+        (**self).dimensions()
+    }
+
+    fn embed(
+        &self,
+        texts: &[&str],
+    ) -> impl Future<Output = Result<Vec<Vec<f32>>, Report<EmbeddingError>>> + Send {
+        (**self).embed(texts)
+    }
+}
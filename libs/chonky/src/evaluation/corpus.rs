@@ -0,0 +1,280 @@
+use core::ops::Range;
+
+use error_stack::{Report, ResultExt, bail};
+use serde_json::Value as JsonValue;
+
+use crate::span::Span;
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not read corpus")]
+pub struct CorpusError;
+
+/// A document of a [`Corpus`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorpusDocument {
+    pub id: String,
+    pub text: String,
+}
+
+/// A question of a [`Corpus`] with the spans of its document answering it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Question {
+    pub text: String,
+    /// Index of the document answering the question.
+    pub document: usize,
+    pub answers: Vec<Span>,
+}
+
+/// A set of documents and questions labelled with the spans answering them.
+///
+/// A corpus is read from JSON of the form
+///
+/// ```json
+/// {
+///   "documents": [{ "id": "engine", "text": "The engine computes Bernoulli numbers." }],
+///   "questions": [
+///     {
+///       "question": "What does the engine compute?",
+///       "document": "engine",
+///       "answers": ["Bernoulli numbers", { "start": 0, "end": 10 }]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Answers are either given as text, which is located at its first occurrence in the document,
+/// or as byte range of the document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Corpus {
+    documents: Vec<CorpusDocument>,
+    questions: Vec<Question>,
+}
+
+impl Corpus {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            documents: Vec::new(),
+            questions: Vec::new(),
+        }
+    }
+
+    /// Reads a corpus from its JSON representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CorpusError`] if `source` is not a valid corpus, e.g. if a question refers to an
+    /// unknown document or an answer can not be found in its document.
+    pub fn from_json(source: &[u8]) -> Result<Self, Report<CorpusError>> {
+        let value = serde_json::from_slice::<JsonValue>(source).change_context(CorpusError)?;
+
+        let mut corpus = Self::new();
+        for document in array(&value, "documents")? {
+            corpus.push_document(string(document, "id")?, string(document, "text")?)?;
+        }
+        for question in array(&value, "questions")? {
+            let text = string(question, "question")?;
+            let id = string(question, "document")?;
+            let document = corpus
+                .documents
+                .iter()
+                .find(|document| document.id == id)
+                .ok_or_else(|| Report::new(CorpusError))
+                .attach_printable_lazy(|| format!("unknown document `{id}`"))?;
+
+            let answers = array(question, "answers")?
+                .iter()
+                .map(|answer| match answer {
+                    JsonValue::String(answer) => {
+                        let start = document
+                            .text
+                            .find(answer.as_str())
+                            .ok_or_else(|| Report::new(CorpusError))
+                            .attach_printable_lazy(|| {
+                                format!("answer `{answer}` does not occur in document `{id}`")
+                            })?;
+                        Ok(start..start + answer.len())
+                    }
+                    JsonValue::Object(_) => Ok(index(answer, "start")?..index(answer, "end")?),
+                    JsonValue::Null
+                    | JsonValue::Bool(_)
+                    | JsonValue::Number(_)
+                    | JsonValue::Array(_) => Err(Report::new(CorpusError)
+                        .attach_printable("an answer is either a text or a byte range")),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            corpus.push_question(text, id, answers)?;
+        }
+
+        Ok(corpus)
+    }
+
+    #[must_use]
+    pub fn documents(&self) -> &[CorpusDocument] {
+        &self.documents
+    }
+
+    #[must_use]
+    pub fn questions(&self) -> &[Question] {
+        &self.questions
+    }
+
+    /// Adds a document to the corpus.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CorpusError`] if the corpus already contains a document with the same `id`.
+    pub fn push_document(
+        &mut self,
+        id: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Result<(), Report<CorpusError>> {
+        let id = id.into();
+        if self.documents.iter().any(|document| document.id == id) {
+            bail!(Report::new(CorpusError).attach_printable(format!("duplicate document `{id}`")));
+        }
+        self.documents.push(CorpusDocument {
+            id,
+            text: text.into(),
+        });
+        Ok(())
+    }
+
+    /// Adds a question answered by the byte ranges `answers` of the document `document`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CorpusError`] if the document does not exist, if there are no answers, or if an
+    /// answer is empty or does not refer to a valid region of the document.
+    pub fn push_question(
+        &mut self,
+        text: impl Into<String>,
+        document: &str,
+        answers: impl IntoIterator<Item = Range<usize>>,
+    ) -> Result<(), Report<CorpusError>> {
+        let (index, CorpusDocument { text: source, .. }) = self
+            .documents
+            .iter()
+            .enumerate()
+            .find(|(_, candidate)| candidate.id == document)
+            .ok_or_else(|| Report::new(CorpusError))
+            .attach_printable_lazy(|| format!("unknown document `{document}`"))?;
+
+        let answers = answers
+            .into_iter()
+            .map(|range| {
+                let span = Span::from_byte_range(source, range.clone())
+                    .change_context(CorpusError)
+                    .attach_printable_lazy(|| {
+                        format!("invalid answer {range:?} in document `{document}`")
+                    })?;
+                if span.is_empty() {
+                    bail!(
+                        Report::new(CorpusError)
+                            .attach_printable(format!("empty answer in document `{document}`"))
+                    );
+                }
+                Ok(span)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if answers.is_empty() {
+            bail!(Report::new(CorpusError).attach_printable("a question needs an answer"));
+        }
+
+        self.questions.push(Question {
+            text: text.into(),
+            document: index,
+            answers,
+        });
+        Ok(())
+    }
+}
+
+fn member<'v>(value: &'v JsonValue, key: &str) -> Result<&'v JsonValue, Report<CorpusError>> {
+    value
+        .get(key)
+        .ok_or_else(|| Report::new(CorpusError))
+        .attach_printable_lazy(|| format!("missing member `{key}`"))
+}
+
+fn array<'v>(value: &'v JsonValue, key: &str) -> Result<&'v [JsonValue], Report<CorpusError>> {
+    member(value, key)?
+        .as_array()
+        .map(Vec::as_slice)
+        .ok_or_else(|| Report::new(CorpusError))
+        .attach_printable_lazy(|| format!("`{key}` has to be an array"))
+}
+
+fn string<'v>(value: &'v JsonValue, key: &str) -> Result<&'v str, Report<CorpusError>> {
+    member(value, key)?
+        .as_str()
+        .ok_or_else(|| Report::new(CorpusError))
+        .attach_printable_lazy(|| format!("`{key}` has to be a string"))
+}
+
+fn index(value: &JsonValue, key: &str) -> Result<usize, Report<CorpusError>> {
+    member(value, key)?
+        .as_u64()
+        .and_then(|index| usize::try_from(index).ok())
+        .ok_or_else(|| Report::new(CorpusError))
+        .attach_printable_lazy(|| format!("`{key}` has to be a byte offset"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_corpus() {
+        let corpus = Corpus::from_json(
+            br#"{
+                "documents": [
+                    { "id": "a", "text": "Ada wrote the first program." },
+                    { "id": "b", "text": "Charles designed the engine." }
+                ],
+                "questions": [
+                    { "question": "Who designed the engine?", "document": "b", "answers": ["Charles"] },
+                    { "question": "What did Ada write?", "document": "a", "answers": [{ "start": 10, "end": 27 }] }
+                ]
+            }"#,
+        )
+        .expect("valid corpus");
+
+        assert_eq!(corpus.documents().len(), 2);
+        let answers = corpus
+            .questions()
+            .iter()
+            .map(|question| {
+                let document = &corpus.documents()[question.document];
+                question.answers[0]
+                    .slice(&document.text)
+                    .expect("valid answer")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(answers, ["Charles", "the first program"]);
+    }
+
+    #[test]
+    fn rejects_invalid_corpora() {
+        let mut corpus = Corpus::new();
+        corpus.push_document("a", "text").expect("new document");
+
+        let _: Report<_> = corpus
+            .push_document("a", "other")
+            .expect_err("duplicate document");
+        let _: Report<_> = corpus
+            .push_question("?", "b", Some(0..1))
+            .expect_err("unknown document");
+        let _: Report<_> = corpus
+            .push_question("?", "a", Some(2..10))
+            .expect_err("answer out of bounds");
+        let _: Report<_> = corpus
+            .push_question("?", "a", [])
+            .expect_err("missing answer");
+        let _: Report<_> = Corpus::from_json(
+            br#"{ "documents": [{ "id": "a", "text": "text" }], "questions": [{ "question": "?", "document": "a", "answers": ["missing"] }] }"#,
+        )
+        .expect_err("answer not in document");
+    }
+}
@@ -0,0 +1,409 @@
+//! Evaluation of chunking strategies against a labelled corpus.
+//!
+//! Whether a chunking strategy works well depends on whether the chunks it creates can be found
+//! again by the questions they answer. The [`Evaluator`] chunks every document of a [`Corpus`],
+//! embeds the chunks with the [`HashingEmbedder`] and searches them for every question of the
+//! corpus by brute-force cosine similarity. It reports the fraction of answer spans found in the
+//! top `k` results, the recall@k, together with the distribution of chunk sizes, so strategies
+//! can be compared on the same corpus without calling a model.
+
+mod corpus;
+
+use error_stack::{Context, Report, ResultExt};
+use serde_json::json;
+
+pub use self::corpus::{Corpus, CorpusDocument, CorpusError, Question};
+use crate::{chunk::Chunk, embedding::HashingEmbedder, span::Span, tokenizer::Tokenizer};
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not evaluate chunks")]
+pub struct EvaluationError;
+
+/// The fraction of answers found in the top search results.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Recall {
+    /// The number of top search results searched for answers, the `k` of recall@k.
+    pub cutoff: usize,
+    /// Between `0.0` and `1.0`.
+    pub recall: f64,
+}
+
+/// Summary statistics of a set of sizes, e.g. the token counts of chunks.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct SizeDistribution {
+    pub count: usize,
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub median: usize,
+    /// The 90th percentile.
+    pub p90: usize,
+}
+
+impl SizeDistribution {
+    /// Computes the distribution of `sizes`, all statistics are zero if there are no sizes.
+    ///
+    /// Percentiles use the nearest-rank method, so they are always one of the sizes.
+    #[must_use]
+    #[expect(
+        clippy::float_arithmetic,
+        clippy::cast_precision_loss,
+        reason = "statistics are reported as floating-point numbers"
+    )]
+    pub fn of(sizes: impl IntoIterator<Item = usize>) -> Self {
+        let mut sizes = sizes.into_iter().collect::<Vec<_>>();
+        sizes.sort_unstable();
+        let (Some(&min), Some(&max)) = (sizes.first(), sizes.last()) else {
+            return Self::default();
+        };
+
+        let count = sizes.len();
+        let mean = sizes.iter().sum::<usize>() as f64 / count as f64;
+        let variance = sizes
+            .iter()
+            .map(|&size| (size as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+        let percentile = |percent: usize| {
+            let rank = (percent * count).div_ceil(100).max(1);
+            sizes.get(rank - 1).copied().unwrap_or(max)
+        };
+
+        Self {
+            count,
+            min,
+            max,
+            mean,
+            std_dev: variance.sqrt(),
+            median: percentile(50),
+            p90: percentile(90),
+        }
+    }
+}
+
+/// The result of evaluating the chunks of a [`Corpus`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    pub questions: usize,
+    pub answers: usize,
+    /// The recall for every cutoff the [`Evaluator`] was configured with, in ascending order.
+    pub recall: Vec<Recall>,
+    /// The fraction of answers contained in any chunk.
+    ///
+    /// Answers split across chunks can never be found, so this is an upper bound of the recall.
+    pub coverage: f64,
+    /// The distribution of the token counts of all chunks.
+    pub chunk_tokens: SizeDistribution,
+}
+
+impl Evaluation {
+    /// Returns the recall in the top `cutoff` results, `None` if `cutoff` was not evaluated.
+    #[must_use]
+    pub fn recall_at(&self, cutoff: usize) -> Option<f64> {
+        self.recall
+            .iter()
+            .find(|recall| recall.cutoff == cutoff)
+            .map(|recall| recall.recall)
+    }
+
+    /// Serializes the evaluation as JSON object, with the recall keyed by its cutoff, e.g.
+    /// `{"recall": {"1": 0.5, "5": 1.0}, ...}`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let recall = self
+            .recall
+            .iter()
+            .map(|recall| (recall.cutoff.to_string(), json!(recall.recall)))
+            .collect::<serde_json::Map<_, _>>();
+        let tokens = &self.chunk_tokens;
+
+        json!({
+            "questions": self.questions,
+            "answers": self.answers,
+            "recall": recall,
+            "coverage": self.coverage,
+            "chunk_tokens": {
+                "count": tokens.count,
+                "min": tokens.min,
+                "max": tokens.max,
+                "mean": tokens.mean,
+                "std_dev": tokens.std_dev,
+                "median": tokens.median,
+                "p90": tokens.p90,
+            },
+        })
+        .to_string()
+    }
+}
+
+/// A chunk of the evaluated corpus.
+#[derive(Debug)]
+struct IndexedChunk {
+    document: usize,
+    span: Span,
+    embedding: Vec<f32>,
+}
+
+/// Returns the cosine similarity of two vectors of unit length or zero.
+#[expect(
+    clippy::float_arithmetic,
+    reason = "cosine similarity is computed on floating-point vectors"
+)]
+fn similarity(lhs: &[f32], rhs: &[f32]) -> f32 {
+    lhs.iter().zip(rhs).map(|(lhs, rhs)| lhs * rhs).sum()
+}
+
+/// Measures how well chunks created from a [`Corpus`] can be retrieved by its questions.
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: self
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{
+///     chunker::Chunker,
+///     evaluation::{Corpus, Evaluator},
+///     tokenizer::HeuristicTokenizer,
+/// };
+///
+/// let mut corpus = Corpus::new();
+/// corpus.push_document(
+///     "engine",
+///     "The engine computes Bernoulli numbers.\n\nIt is powered by steam.",
+/// )?;
+/// corpus.push_question("What is the engine powered by?", "engine", [46..62])?;
+///
+/// let tokenizer = HeuristicTokenizer::default();
+/// let chunker = Chunker::new(tokenizer, 16);
+/// let evaluation = Evaluator::new(tokenizer)
+///     .with_cutoffs([1])
+///     .evaluate(&corpus, |text| chunker.chunk_text(text))?;
+///
+/// assert_eq!(evaluation.recall_at(1), Some(1.0));
+/// assert_eq!(evaluation.chunk_tokens.count, 2);
+/// # Ok::<(), Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Evaluator<T> {
+    tokenizer: T,
+    embedder: HashingEmbedder,
+    cutoffs: Vec<usize>,
+}
+
+impl<T> Evaluator<T>
+where
+    T: Tokenizer,
+{
+    /// The cutoffs the recall is reported for by default.
+    pub const DEFAULT_CUTOFFS: [usize; 4] = [1, 3, 5, 10];
+
+    /// Creates an evaluator counting the tokens of chunks with `tokenizer`.
+    #[must_use]
+    pub fn new(tokenizer: T) -> Self {
+        Self {
+            tokenizer,
+            embedder: HashingEmbedder::new(),
+            cutoffs: Self::DEFAULT_CUTOFFS.to_vec(),
+        }
+    }
+
+    /// Sets the embedder used to search the chunks.
+    #[must_use]
+    pub const fn with_embedder(mut self, embedder: HashingEmbedder) -> Self {
+        self.embedder = embedder;
+        self
+    }
+
+    /// Sets the numbers of top search results the recall is reported for, the `k` of recall@k.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cutoffs` is empty or contains zero.
+    #[must_use]
+    pub fn with_cutoffs(mut self, cutoffs: impl IntoIterator<Item = usize>) -> Self {
+        let mut cutoffs = cutoffs.into_iter().collect::<Vec<_>>();
+        assert!(
+            !cutoffs.is_empty(),
+            "the recall has to be reported for a cutoff"
+        );
+        assert!(!cutoffs.contains(&0), "a cutoff has to be positive");
+        cutoffs.sort_unstable();
+        cutoffs.dedup();
+        self.cutoffs = cutoffs;
+        self
+    }
+
+    pub const fn tokenizer(&self) -> &T {
+        &self.tokenizer
+    }
+
+    /// Chunks every document of `corpus` with `chunk` and evaluates the chunks.
+    ///
+    /// `chunk` is called with the text of every document in turn and has to return chunks with
+    /// spans referring to that text. An answer is found if it lies completely inside a chunk of
+    /// its document in the top results for its question. Chunks of all documents compete in
+    /// the search, ties are broken by the order of the chunks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EvaluationError`] if a document could not be chunked.
+    #[expect(
+        clippy::float_arithmetic,
+        clippy::cast_precision_loss,
+        reason = "recall is reported as floating-point number"
+    )]
+    pub fn evaluate<F, C>(
+        &self,
+        corpus: &Corpus,
+        mut chunk: F,
+    ) -> Result<Evaluation, Report<EvaluationError>>
+    where
+        F: FnMut(&str) -> Result<Vec<Chunk>, Report<C>>,
+        C: Context,
+    {
+        let mut chunks = Vec::new();
+        let mut tokens = Vec::new();
+        for (index, document) in corpus.documents().iter().enumerate() {
+            for chunk in chunk(&document.text)
+                .change_context(EvaluationError)
+                .attach_printable_lazy(|| format!("document `{}`", document.id))?
+            {
+                tokens.push(self.tokenizer.count_tokens(&chunk.text));
+                chunks.push(IndexedChunk {
+                    document: index,
+                    span: chunk.span,
+                    embedding: self.embedder.embed_text(&chunk.text),
+                });
+            }
+        }
+
+        let max_cutoff = self.cutoffs.last().copied().unwrap_or_default();
+        let mut answers = 0_usize;
+        let mut covered = 0_usize;
+        let mut found = vec![0_usize; self.cutoffs.len()];
+        let mut scores = Vec::with_capacity(chunks.len());
+        for question in corpus.questions() {
+            let query = self.embedder.embed_text(&question.text);
+            scores.clear();
+            scores.extend(
+                chunks
+                    .iter()
+                    .enumerate()
+                    .map(|(index, chunk)| (index, similarity(&query, &chunk.embedding))),
+            );
+            scores.sort_by(|(lhs_index, lhs), (rhs_index, rhs)| {
+                rhs.total_cmp(lhs).then_with(|| lhs_index.cmp(rhs_index))
+            });
+
+            for answer in &question.answers {
+                let answers_in = |chunk: &IndexedChunk| {
+                    chunk.document == question.document && chunk.span.contains(answer)
+                };
+
+                answers += 1;
+                if chunks.iter().any(answers_in) {
+                    covered += 1;
+                }
+                let rank = scores
+                    .iter()
+                    .take(max_cutoff)
+                    .position(|&(index, _)| chunks.get(index).is_some_and(answers_in));
+                if let Some(rank) = rank {
+                    for (cutoff, found) in self.cutoffs.iter().zip(&mut found) {
+                        if rank < *cutoff {
+                            *found += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        let fraction = |count: usize| {
+            if answers == 0 {
+                0.0
+            } else {
+                count as f64 / answers as f64
+            }
+        };
+        Ok(Evaluation {
+            questions: corpus.questions().len(),
+            answers,
+            recall: self
+                .cutoffs
+                .iter()
+                .zip(found)
+                .map(|(&cutoff, found)| Recall {
+                    cutoff,
+                    recall: fraction(found),
+                })
+                .collect(),
+            coverage: fraction(covered),
+            chunk_tokens: SizeDistribution::of(tokens),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{chunker::Chunker, tokenizer::HeuristicTokenizer};
+
+    #[test]
+    fn computes_size_distribution() {
+        let distribution = SizeDistribution::of([4, 1, 3, 2, 10]);
+        assert_eq!(distribution.count, 5);
+        assert_eq!((distribution.min, distribution.max), (1, 10));
+        assert!((distribution.mean - 4.0).abs() < f64::EPSILON);
+        assert!((distribution.std_dev - 10.0_f64.sqrt()).abs() < 1e-9);
+        assert_eq!((distribution.median, distribution.p90), (3, 10));
+
+        assert_eq!(SizeDistribution::of([]), SizeDistribution::default());
+    }
+
+    #[test]
+    fn measures_recall() {
+        let mut corpus = Corpus::new();
+        corpus
+            .push_document(
+                "animals",
+                "Cats sleep most of the day.\n\nDogs bark at the mailman.\n\nFish swim in schools.",
+            )
+            .expect("new document");
+        corpus
+            .push_document(
+                "code",
+                "Rust guarantees memory safety without a garbage collector.",
+            )
+            .expect("new document");
+        // Found at the first rank
+        corpus
+            .push_question("Why do dogs bark?", "animals", Some(29..54))
+            .expect("valid question");
+        // Never found, as the answer spans two paragraphs
+        corpus
+            .push_question("Which animals sleep and bark?", "animals", Some(0..54))
+            .expect("valid question");
+
+        let tokenizer = HeuristicTokenizer::default();
+        let chunker = Chunker::new(tokenizer, 12);
+        let evaluation = Evaluator::new(tokenizer)
+            .with_cutoffs([3, 1])
+            .evaluate(&corpus, |text| chunker.chunk_text(text))
+            .expect("could evaluate chunks");
+
+        assert_eq!((evaluation.questions, evaluation.answers), (2, 2));
+        assert_eq!(evaluation.recall_at(1), Some(0.5));
+        assert_eq!(evaluation.recall_at(3), Some(0.5));
+        assert_eq!(evaluation.recall_at(5), None);
+        assert!((evaluation.coverage - 0.5).abs() < f64::EPSILON);
+        assert_eq!(evaluation.chunk_tokens.count, 4);
+
+        let json =
+            serde_json::from_str::<serde_json::Value>(&evaluation.to_json()).expect("valid JSON");
+        assert_eq!(json["recall"]["1"], 0.5);
+        assert_eq!(json["chunk_tokens"]["count"], 4);
+    }
+}
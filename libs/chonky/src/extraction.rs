@@ -0,0 +1,91 @@
+use crate::{
+    segment::{Segment, SegmentKind},
+    span::{Offset, Span},
+};
+
+/// Separator between the text of two consecutive segments.
+const SEPARATOR: &str = "\n\n";
+
+/// Plain text extracted from a document, together with the segments it consists of.
+///
+/// Front-ends for formats which cannot be segmented in place, e.g. HTML or PDF, extract the
+/// content of every block into a new text. The text of consecutive segments is separated by an
+/// empty line and all [`Span`]s refer to the extracted text.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{Extraction, SegmentKind};
+///
+/// let mut extraction = Extraction::new();
+/// extraction.push(SegmentKind::Heading { level: 1 }, "Title");
+/// extraction.push(SegmentKind::Paragraph, "  First paragraph.\n");
+/// extraction.push(SegmentKind::Paragraph, " ");
+///
+/// assert_eq!(extraction.text(), "Title\n\nFirst paragraph.");
+/// assert_eq!(extraction.segments().len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Extraction {
+    text: String,
+    end: Offset,
+    segments: Vec<Segment>,
+}
+
+impl Extraction {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            text: String::new(),
+            end: Offset::ZERO,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Appends `content` as a new segment and returns it.
+    ///
+    /// Surrounding whitespace is removed from `content`. Returns `None` if nothing is left.
+    pub fn push(&mut self, kind: SegmentKind, content: &str) -> Option<&mut Segment> {
+        let content = content.trim();
+        if content.is_empty() {
+            return None;
+        }
+
+        if !self.text.is_empty() {
+            self.text.push_str(SEPARATOR);
+            self.end = self.end.advance(SEPARATOR);
+        }
+
+        let start = self.end;
+        self.text.push_str(content);
+        self.end = self.end.advance(content);
+
+        self.segments
+            .push(Segment::new(kind, Span::new(start, self.end)));
+        self.segments.last_mut()
+    }
+
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    #[must_use]
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn segments_mut(&mut self) -> &mut [Segment] {
+        &mut self.segments
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    #[must_use]
+    pub fn into_parts(self) -> (String, Vec<Segment>) {
+        (self.text, self.segments)
+    }
+}
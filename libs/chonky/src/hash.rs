@@ -0,0 +1,125 @@
+use core::{fmt, str::FromStr};
+
+use error_stack::{Report, ensure};
+use sha2::{Digest as _, Sha256};
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Hashes `bytes` with the 64-bit FNV-1a function, which is stable across platforms and releases.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not parse content hash")]
+pub struct ContentHashError;
+
+/// The SHA-256 hash of the text of a [`Segment`] or [`Chunk`].
+///
+/// Content hashes only depend on the text they were computed from, so they are stable across
+/// runs, machines and versions of this library. They can be stored alongside embeddings to detect
+/// which chunks of a changed document have to be embedded again, see [`ChunkDiff`].
+///
+/// Content hashes are formatted as 64 lowercase hexadecimal digits.
+///
+/// [`Segment`]: crate::Segment
+/// [`Chunk`]: crate::Chunk
+/// [`ChunkDiff`]: crate::chunk::ChunkDiff
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::ContentHash;
+///
+/// let hash = ContentHash::of("Hello, world!");
+/// assert_eq!(
+///     hash.to_string(),
+///     "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3"
+/// );
+/// assert_eq!(hash.to_string().parse::<ContentHash>()?, hash);
+/// # Ok::<(), error_stack::Report<chonky::hash::ContentHashError>>(())
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContentHash([u8; 32]);
+
+impl ContentHash {
+    /// Computes the hash of `content`.
+    #[must_use]
+    pub fn of(content: &str) -> Self {
+        Self(Sha256::digest(content.as_bytes()).into())
+    }
+
+    #[must_use]
+    pub const fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ContentHash {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "ContentHash({self})")
+    }
+}
+
+impl fmt::Display for ContentHash {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(fmt, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ContentHash {
+    type Err = Report<ContentHashError>;
+
+    fn from_str(hash: &str) -> Result<Self, Self::Err> {
+        ensure!(
+            hash.len() == 64 && hash.bytes().all(|byte| byte.is_ascii_hexdigit()),
+            ContentHashError
+        );
+
+        let mut bytes = [0; 32];
+        for (byte, digits) in bytes.iter_mut().zip(hash.as_bytes().chunks_exact(2)) {
+            let digits = core::str::from_utf8(digits).map_err(|_error| ContentHashError)?;
+            *byte = u8::from_str_radix(digits, 16).map_err(|_error| ContentHashError)?;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let hash = ContentHash::of("");
+        assert_eq!(
+            hash.to_string(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hash.to_string()
+                .to_ascii_uppercase()
+                .parse::<ContentHash>()
+                .expect("valid hash"),
+            hash
+        );
+        assert_ne!(ContentHash::of("a"), ContentHash::of("b"));
+
+        let _: Report<_> = "e3b0".parse::<ContentHash>().expect_err("too short");
+        let _: Report<_> = "z"
+            .repeat(64)
+            .parse::<ContentHash>()
+            .expect_err("not hexadecimal");
+    }
+}
@@ -0,0 +1,541 @@
+//! Extraction of segments from HTML documents.
+//!
+//! Web pages contain a lot more than their content: navigation, footers, scripts and styles are
+//! removed, while headings, paragraphs, lists, tables, code blocks and the text of links are
+//! extracted into an [`Extraction`]. Every segment records the path of the element it was
+//! extracted from in its [`DOM_PATH_KEY`] metadata entry.
+//!
+//! Parsing follows the [HTML Standard], so malformed documents are handled the same way a browser
+//! handles them.
+//!
+//! [HTML Standard]: https://html.spec.whatwg.org/multipage/parsing.html
+
+use scraper::{ElementRef, Html, Node};
+
+use crate::{
+    extraction::Extraction,
+    segment::SegmentKind,
+    table::{Cell, Table, TableFormat},
+};
+
+/// Metadata key of the path of the element a segment was extracted from, e.g.
+/// `html > body > main > p:nth-of-type(2)`.
+///
+/// The path is a valid CSS selector.
+pub const DOM_PATH_KEY: &str = "dom_path";
+
+/// Elements which never contain content worth extracting.
+const BOILERPLATE_ELEMENTS: &[&str] = &[
+    "aside", "button", "canvas", "dialog", "footer", "form", "head", "iframe", "nav", "noscript",
+    "object", "script", "select", "style", "svg", "template",
+];
+
+/// ARIA roles of landmarks which never contain content worth extracting.
+const BOILERPLATE_ROLES: &[&str] = &[
+    "banner",
+    "complementary",
+    "contentinfo",
+    "navigation",
+    "search",
+];
+
+/// Elements which start a new block of text.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "blockquote",
+    "body",
+    "caption",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+fn is_boilerplate(element: ElementRef<'_>) -> bool {
+    let element = element.value();
+    BOILERPLATE_ELEMENTS.contains(&element.name())
+        || element
+            .attr("role")
+            .is_some_and(|role| BOILERPLATE_ROLES.contains(&role))
+        || element.attr("hidden").is_some()
+        || element.attr("aria-hidden") == Some("true")
+}
+
+fn is_block(element: ElementRef<'_>) -> bool {
+    BLOCK_ELEMENTS.contains(&element.value().name())
+}
+
+fn is_list(element: ElementRef<'_>) -> bool {
+    matches!(element.value().name(), "ul" | "ol")
+}
+
+/// Returns the step of `element` in a DOM path, e.g. `p:nth-of-type(2)`.
+fn path_step(element: ElementRef<'_>) -> String {
+    let name = element.value().name();
+    let same_name = |sibling: &ElementRef<'_>| sibling.value().name() == name;
+
+    let before = element
+        .prev_siblings()
+        .filter_map(ElementRef::wrap)
+        .filter(same_name)
+        .count();
+    let after = element
+        .next_siblings()
+        .filter_map(ElementRef::wrap)
+        .filter(same_name)
+        .count();
+    if before + after == 0 {
+        name.to_owned()
+    } else {
+        format!("{name}:nth-of-type({})", before + 1)
+    }
+}
+
+/// Collects inline text and collapses whitespace the way a browser renders it.
+#[derive(Debug, Default)]
+struct InlineText {
+    text: String,
+    pending_space: bool,
+}
+
+impl InlineText {
+    fn push_str(&mut self, text: &str) {
+        for char in text.chars() {
+            if char.is_whitespace() {
+                self.pending_space = true;
+            } else {
+                if self.pending_space && !self.text.is_empty() && !self.text.ends_with('\n') {
+                    self.text.push(' ');
+                }
+                self.pending_space = false;
+                self.text.push(char);
+            }
+        }
+    }
+
+    fn push_line_break(&mut self) {
+        self.text.push('\n');
+        self.pending_space = false;
+    }
+
+    /// Appends the text of `element` and its descendants.
+    ///
+    /// Nested lists are skipped if `skip_lists` is set.
+    fn push_element(&mut self, element: ElementRef<'_>, skip_lists: bool) {
+        if is_boilerplate(element) || (skip_lists && is_list(element)) {
+            return;
+        }
+        if element.value().name() == "br" {
+            self.push_line_break();
+            return;
+        }
+
+        // Separate the text of nested blocks, e.g. paragraphs inside of a list item
+        let block = is_block(element);
+        self.pending_space |= block;
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.push_str(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.push_element(child, skip_lists);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.pending_space |= block;
+    }
+
+    fn take(&mut self) -> String {
+        self.pending_space = false;
+        core::mem::take(&mut self.text)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Extractor {
+    extraction: Extraction,
+    /// Loose text of the current container which is not part of any block.
+    inline: InlineText,
+}
+
+impl Extractor {
+    fn push(&mut self, kind: SegmentKind, text: &str, path: &str) {
+        if let Some(segment) = self.extraction.push(kind, text) {
+            segment.metadata.insert(DOM_PATH_KEY, path);
+        }
+    }
+
+    fn push_inline(&mut self, kind: SegmentKind, element: ElementRef<'_>, path: &str) {
+        let mut text = InlineText::default();
+        text.push_element(element, matches!(kind, SegmentKind::ListItem { .. }));
+        self.push(kind, &text.take(), path);
+    }
+
+    fn flush(&mut self, path: &str) {
+        let text = self.inline.take();
+        self.push(SegmentKind::Text, &text, path);
+    }
+
+    fn table(&mut self, table: ElementRef<'_>, path: &str) {
+        fn collect_rows<'a>(element: ElementRef<'a>, rows: &mut Vec<ElementRef<'a>>) {
+            for child in element.child_elements() {
+                match child.value().name() {
+                    "tr" => rows.push(child),
+                    "thead" | "tbody" | "tfoot" => collect_rows(child, rows),
+                    _ => {}
+                }
+            }
+        }
+
+        fn span(cell: ElementRef<'_>, attribute: &str) -> usize {
+            cell.value()
+                .attr(attribute)
+                .and_then(|span| span.trim().parse().ok())
+                .unwrap_or(1)
+        }
+
+        let mut rows = Vec::new();
+        collect_rows(table, &mut rows);
+
+        // Rows of `<thead>` form the header, otherwise leading rows consisting only of `<th>`
+        let in_head = |row: &ElementRef<'_>| {
+            row.parent()
+                .and_then(ElementRef::wrap)
+                .is_some_and(|parent| parent.value().name() == "thead")
+        };
+        let header_rows = if rows.iter().any(in_head) {
+            rows.iter().take_while(|row| in_head(row)).count()
+        } else {
+            rows.iter()
+                .take_while(|row| {
+                    let mut cells = row
+                        .child_elements()
+                        .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                        .peekable();
+                    cells.peek().is_some() && cells.all(|cell| cell.value().name() == "th")
+                })
+                .count()
+        };
+
+        let table = Table::from_cells(
+            rows.into_iter().map(|row| {
+                row.child_elements()
+                    .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                    .map(|cell| {
+                        let mut text = InlineText::default();
+                        text.push_element(cell, false);
+                        Cell::new(text.take())
+                            .with_row_span(span(cell, "rowspan"))
+                            .with_column_span(span(cell, "colspan"))
+                    })
+            }),
+            header_rows,
+        )
+        .with_detected_header();
+
+        if !table.is_empty() {
+            let text = table.render(TableFormat::Markdown);
+            self.push(SegmentKind::Table(table), &text, path);
+        }
+    }
+
+    fn code_block(&mut self, pre: ElementRef<'_>, path: &str) {
+        let language = [Some(pre), pre.child_elements().next()]
+            .into_iter()
+            .flatten()
+            .flat_map(|element| element.value().classes())
+            .find_map(|class| {
+                class
+                    .strip_prefix("language-")
+                    .or_else(|| class.strip_prefix("lang-"))
+            })
+            .map(ToOwned::to_owned);
+
+        let text = pre.text().collect::<String>();
+        self.push(SegmentKind::CodeBlock { language }, &text, path);
+    }
+
+    fn list(&mut self, list: ElementRef<'_>, path: &str, depth: u8) {
+        for item in list.child_elements() {
+            if is_boilerplate(item) || item.value().name() != "li" {
+                continue;
+            }
+
+            let item_path = format!("{path} > {}", path_step(item));
+            self.push_inline(SegmentKind::ListItem { depth }, item, &item_path);
+            for nested in item.child_elements().filter(|child| is_list(*child)) {
+                let nested_path = format!("{item_path} > {}", path_step(nested));
+                self.list(nested, &nested_path, depth.saturating_add(1));
+            }
+        }
+    }
+
+    fn container(&mut self, container: ElementRef<'_>, path: &str) {
+        for child in container.children() {
+            let element = match child.value() {
+                Node::Text(text) => {
+                    self.inline.push_str(text);
+                    continue;
+                }
+                Node::Element(_) => match ElementRef::wrap(child) {
+                    Some(element) => element,
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            if is_boilerplate(element) {
+                continue;
+            }
+            if !is_block(element) {
+                self.inline.push_element(element, false);
+                continue;
+            }
+
+            self.flush(path);
+            let element_path = format!("{path} > {}", path_step(element));
+            match element.value().name() {
+                name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+                    let level = name
+                        .strip_prefix('h')
+                        .and_then(|level| level.parse().ok())
+                        .unwrap_or(1);
+                    self.push_inline(SegmentKind::Heading { level }, element, &element_path);
+                }
+                "p" | "dt" | "dd" | "figcaption" | "summary" | "caption" => {
+                    self.push_inline(SegmentKind::Paragraph, element, &element_path);
+                }
+                "ul" | "ol" => self.list(element, &element_path, 0),
+                "table" => self.table(element, &element_path),
+                "pre" => self.code_block(element, &element_path),
+                "hr" => {}
+                _ => self.container(element, &element_path),
+            }
+        }
+
+        self.flush(path);
+    }
+}
+
+/// Extracts the content of an HTML document.
+///
+/// The source is decoded as UTF-8, invalid sequences are replaced by `U+FFFD`. Everything is
+/// processed in memory, no external resources are loaded.
+///
+/// Navigation, headers and footers marked as landmarks, sidebars, forms, scripts, styles and
+/// hidden elements are removed. Headings, paragraphs, list items, tables and code blocks are
+/// extracted as segments of the corresponding [`SegmentKind`], with whitespace collapsed. Text
+/// which is not part of any of these blocks is extracted as [`SegmentKind::Text`]. Tables are
+/// extracted as Markdown tables, with merged cells repeated in every position they cover. Rows of
+/// `<thead>` or leading rows of `<th>` cells form the header of a table, otherwise a header row is
+/// detected with [`Table::with_detected_header`].
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::html::{self, DOM_PATH_KEY};
+///
+/// let source = br#"
+///     <html>
+///       <body>
+///         <nav><a href="/">Home</a></nav>
+///         <main>
+///           <h1>Chunking</h1>
+///           <p>Read the <a href="/docs">docs</a>.</p>
+///         </main>
+///         <script>track();</script>
+///       </body>
+///     </html>
+/// "#;
+///
+/// let extraction = html::extract(source);
+/// assert_eq!(extraction.text(), "Chunking\n\nRead the docs.");
+/// assert_eq!(
+///     extraction.segments()[1].metadata.get(DOM_PATH_KEY),
+///     Some("html > body > main > p")
+/// );
+/// ```
+#[must_use]
+pub fn extract(source: &[u8]) -> Extraction {
+    let document = Html::parse_document(&String::from_utf8_lossy(source));
+
+    let mut extractor = Extractor::default();
+    extractor.container(document.root_element(), "html");
+    extractor.extraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(extraction: &Extraction) -> Vec<(SegmentKind, &str, &str)> {
+        extraction
+            .segments()
+            .iter()
+            .map(|segment| {
+                (
+                    segment.kind.clone(),
+                    segment.text(extraction.text()).expect("valid span"),
+                    segment.metadata.get(DOM_PATH_KEY).expect("DOM path"),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn removes_boilerplate() {
+        let extraction = extract(
+            br#"<!DOCTYPE html>
+            <html>
+              <head><title>Page</title><style>p { color: red; }</style></head>
+              <body>
+                <header role="banner">Site name</header>
+                <nav><ul><li>Home</li></ul></nav>
+                <article>
+                  <h2>Title</h2>
+                  <p hidden>Hidden</p>
+                  <p>Some <b>bold</b>
+                     text.</p>
+                </article>
+                <aside>Related</aside>
+                <footer>Copyright</footer>
+                <script>alert("hi")</script>
+              </body>
+            </html>"#,
+        );
+
+        assert_eq!(blocks(&extraction), [
+            (
+                SegmentKind::Heading { level: 2 },
+                "Title",
+                "html > body > article > h2"
+            ),
+            (
+                SegmentKind::Paragraph,
+                "Some bold text.",
+                "html > body > article > p:nth-of-type(2)"
+            ),
+        ]);
+    }
+
+    #[test]
+    fn extracts_blocks() {
+        let extraction = extract(
+            br#"<body>
+              <div>Loose <em>text</em><br>next line</div>
+              <ol>
+                <li><p>First</p></li>
+                <li>Second
+                  <ul><li>Nested</li></ul>
+                </li>
+              </ol>
+              <table>
+                <thead><tr><th>Name</th><th>Port</th></tr></thead>
+                <tbody><tr><td>graph</td><td>4000</td></tr></tbody>
+              </table>
+              <pre><code class="language-rust">fn main() {
+    println!("hi");
+}</code></pre>
+            </body>"#,
+        );
+
+        assert_eq!(blocks(&extraction), [
+            (
+                SegmentKind::Text,
+                "Loose text\nnext line",
+                "html > body > div"
+            ),
+            (
+                SegmentKind::ListItem { depth: 0 },
+                "First",
+                "html > body > ol > li:nth-of-type(1)"
+            ),
+            (
+                SegmentKind::ListItem { depth: 0 },
+                "Second",
+                "html > body > ol > li:nth-of-type(2)"
+            ),
+            (
+                SegmentKind::ListItem { depth: 1 },
+                "Nested",
+                "html > body > ol > li:nth-of-type(2) > ul > li"
+            ),
+            (
+                SegmentKind::Table(Table::from_cells(
+                    [vec![Cell::new("Name"), Cell::new("Port")], vec![
+                        Cell::new("graph"),
+                        Cell::new("4000")
+                    ]],
+                    1
+                )),
+                "| Name | Port |\n| --- | --- |\n| graph | 4000 |",
+                "html > body > table"
+            ),
+            (
+                SegmentKind::CodeBlock {
+                    language: Some("rust".to_owned())
+                },
+                "fn main() {\n    println!(\"hi\");\n}",
+                "html > body > pre"
+            ),
+        ]);
+    }
+
+    #[test]
+    fn extracts_table_structure() {
+        let extraction = extract(
+            br#"<table>
+              <tr><th rowspan="2">Region</th><th colspan="2">Sales</th></tr>
+              <tr><th>2023</th><th>2024</th></tr>
+              <tr><td>North</td><td>10</td><td>12</td></tr>
+              <tr><td>South</td><td colspan="2">9</td></tr>
+            </table>"#,
+        );
+
+        let [segment] = extraction.segments() else {
+            panic!("expected a single segment");
+        };
+        let SegmentKind::Table(table) = &segment.kind else {
+            panic!("expected a table");
+        };
+        assert_eq!(table.header_rows(), 2);
+        assert_eq!(table.body(), [["North", "10", "12"], ["South", "9", "9"]]);
+        assert_eq!(
+            segment.text(extraction.text()).expect("valid span"),
+            "| Region | Sales / 2023 | Sales / 2024 |\n| --- | --- | --- |\n| North | 10 | 12 \
+             |\n| South | 9 | 9 |"
+        );
+    }
+}
@@ -0,0 +1,471 @@
+//! Detection of the script and language of text.
+//!
+//! The [`LanguageDetector`] first determines the dominant [`Script`] of a text from the Unicode
+//! blocks of its letters. Text written in Chinese characters is Japanese if it contains kana and
+//! Chinese otherwise. Languages sharing the Latin script are told apart by the character trigrams
+//! of their words, which are compared to frequency profiles shipped with the crate using a naive
+//! Bayes classifier.
+//!
+//! The detected [`Language`] selects the sentence boundary rules of the [`Chunker`], see
+//! [`Chunker::with_language_detector`], and is recorded in the metadata of segments and chunks
+//! under [`LANGUAGE_KEY`] and [`LANGUAGE_CONFIDENCE_KEY`], so that retrieval is able to filter by
+//! language.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chonky::language::{Language, LanguageDetector, Script};
+//!
+//! let detector = LanguageDetector::new();
+//! let detection = detector
+//!     .detect("Die Kinder spielen am Nachmittag im Garten hinter dem Haus.")
+//!     .expect("text has letters");
+//!
+//! assert_eq!(detection.language, Language::German);
+//! assert_eq!(detection.script, Script::Latin);
+//! assert!(detection.confidence > 0.9);
+//! ```
+//!
+//! [`Chunker`]: crate::chunker::Chunker
+//! [`Chunker::with_language_detector`]: crate::chunker::Chunker::with_language_detector
+
+mod script;
+
+use core::fmt;
+use std::collections::HashMap;
+
+use error_stack::Report;
+
+pub use self::script::Script;
+use self::script::ScriptCounts;
+use crate::{document::Metadata, segment::Segment, span::SpanError};
+
+/// Metadata key of the ISO 639-1 code of the detected language of a segment or chunk, e.g. `de`.
+pub const LANGUAGE_KEY: &str = "language";
+
+/// Metadata key of the confidence of the detected language, between `0.00` and `1.00`.
+pub const LANGUAGE_CONFIDENCE_KEY: &str = "language_confidence";
+
+/// Metadata key of the dominant script of a segment or chunk, e.g. `latin`.
+pub const SCRIPT_KEY: &str = "script";
+
+/// Marks the start and end of a word in a trigram.
+const WORD_BOUNDARY: char = '_';
+
+/// The maximum number of trigrams of a text considered for detection.
+const MAX_TRIGRAMS: usize = 1024;
+
+/// The minimum share of kana among Chinese characters and kana for text to be Japanese.
+const MIN_KANA_SHARE: f64 = 0.1;
+
+/// A language the [`LanguageDetector`] is able to detect.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+    Japanese,
+    Chinese,
+}
+
+impl Language {
+    pub const ALL: [Self; 6] = [
+        Self::English,
+        Self::German,
+        Self::French,
+        Self::Spanish,
+        Self::Japanese,
+        Self::Chinese,
+    ];
+
+    /// Returns the ISO 639-1 code of the language, e.g. `"en"`.
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::French => "fr",
+            Self::Spanish => "es",
+            Self::Japanese => "ja",
+            Self::Chinese => "zh",
+        }
+    }
+
+    /// Returns the language with the ISO 639-1 `code`, ignoring case.
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Returns the trigram profile of a language written in the Latin script.
+    ///
+    /// Every line of a profile holds one of the most frequent trigrams of a sample text in the
+    /// language and its number of occurrences, separated by a space.
+    const fn profile(self) -> Option<&'static str> {
+        match self {
+            Self::English => Some(include_str!("profiles/en.txt")),
+            Self::German => Some(include_str!("profiles/de.txt")),
+            Self::French => Some(include_str!("profiles/fr.txt")),
+            Self::Spanish => Some(include_str!("profiles/es.txt")),
+            Self::Japanese | Self::Chinese => None,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.code())
+    }
+}
+
+/// The detected language of a text.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Detection {
+    pub language: Language,
+    /// The script most letters of the text are written in.
+    pub script: Script,
+    /// How certain the detection is, between `0.0` and `1.0`.
+    ///
+    /// Short texts and texts mixing several languages or scripts have a lower confidence.
+    pub confidence: f64,
+}
+
+impl Detection {
+    /// Records the detection in `metadata` under [`LANGUAGE_KEY`], [`LANGUAGE_CONFIDENCE_KEY`] and
+    /// [`SCRIPT_KEY`].
+    pub fn annotate(&self, metadata: &mut Metadata) {
+        metadata.insert(LANGUAGE_KEY, self.language.code());
+        metadata.insert(LANGUAGE_CONFIDENCE_KEY, format!("{:.2}", self.confidence));
+        metadata.insert(SCRIPT_KEY, self.script.as_str());
+    }
+}
+
+/// The trigram log-probabilities of a language written in the Latin script.
+#[derive(Debug, Clone)]
+struct Profile {
+    language: Language,
+    log_probabilities: HashMap<&'static str, f64>,
+    /// The log-probability of a trigram which is not part of the profile.
+    unseen: f64,
+}
+
+impl Profile {
+    #[expect(
+        clippy::float_arithmetic,
+        clippy::cast_precision_loss,
+        reason = "profiles have a few hundred trigrams"
+    )]
+    fn new(language: Language, source: &'static str) -> Self {
+        let counts = source
+            .lines()
+            .filter_map(|line| {
+                let (trigram, count) = line.split_once(' ')?;
+                Some((trigram, count.parse::<u32>().ok()?))
+            })
+            .collect::<Vec<_>>();
+        // Add-one smoothing, so that unseen trigrams do not rule out a language
+        let total = counts
+            .iter()
+            .map(|&(_, count)| f64::from(count))
+            .sum::<f64>()
+            + counts.len() as f64
+            + 1.0;
+
+        Self {
+            language,
+            log_probabilities: counts
+                .into_iter()
+                .map(|(trigram, count)| (trigram, ((f64::from(count) + 1.0) / total).ln()))
+                .collect(),
+            unseen: total.recip().ln(),
+        }
+    }
+
+    fn log_likelihood(&self, trigrams: &[String]) -> f64 {
+        trigrams
+            .iter()
+            .map(|trigram| {
+                self.log_probabilities
+                    .get(trigram.as_str())
+                    .copied()
+                    .unwrap_or(self.unseen)
+            })
+            .sum()
+    }
+}
+
+/// Returns the trigrams of the words of `text`, with word boundaries marked by
+/// [`WORD_BOUNDARY`].
+fn trigrams(text: &str) -> Vec<String> {
+    let mut trigrams = Vec::new();
+    let mut word = vec![WORD_BOUNDARY];
+    let flush = |word: &mut Vec<char>, trigrams: &mut Vec<String>| {
+        if word.len() > 1 {
+            word.push(WORD_BOUNDARY);
+            trigrams.extend(
+                word.windows(3)
+                    .map(|window| window.iter().collect::<String>()),
+            );
+        }
+        word.truncate(1);
+    };
+
+    for char in text.chars() {
+        if trigrams.len() >= MAX_TRIGRAMS {
+            break;
+        }
+        if char.is_alphabetic() {
+            word.extend(char.to_lowercase());
+        } else {
+            flush(&mut word, &mut trigrams);
+        }
+    }
+    flush(&mut word, &mut trigrams);
+    trigrams.truncate(MAX_TRIGRAMS);
+    trigrams
+}
+
+/// Detects the script and language of text.
+///
+/// See the [module documentation](self) for how languages are detected.
+#[derive(Debug, Clone)]
+pub struct LanguageDetector {
+    languages: Vec<Language>,
+    profiles: Vec<Profile>,
+}
+
+impl LanguageDetector {
+    /// Creates a detector for all supported languages.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_languages(Language::ALL)
+    }
+
+    /// Creates a detector choosing between `languages` only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `languages` is empty.
+    #[must_use]
+    pub fn with_languages(languages: impl IntoIterator<Item = Language>) -> Self {
+        let mut languages = languages.into_iter().collect::<Vec<_>>();
+        languages.sort_unstable();
+        languages.dedup();
+        assert!(
+            !languages.is_empty(),
+            "the detector needs at least one language"
+        );
+
+        let profiles = languages
+            .iter()
+            .filter_map(|&language| Some(Profile::new(language, language.profile()?)))
+            .collect();
+        Self {
+            languages,
+            profiles,
+        }
+    }
+
+    /// Returns the languages the detector chooses between, ordered as in [`Language::ALL`].
+    #[must_use]
+    pub fn languages(&self) -> &[Language] {
+        &self.languages
+    }
+
+    /// Detects the language of `text`.
+    ///
+    /// Returns [`None`] if `text` does not contain letters or if its dominant script is not used
+    /// by any of the languages of the detector.
+    #[must_use]
+    #[expect(
+        clippy::float_arithmetic,
+        clippy::cast_precision_loss,
+        reason = "confidences are floating-point numbers"
+    )]
+    pub fn detect(&self, text: &str) -> Option<Detection> {
+        let counts = ScriptCounts::of(text);
+        let script = counts.dominant()?;
+        let share = |letters: usize| letters as f64 / counts.total() as f64;
+
+        match script {
+            Script::Han | Script::Kana => {
+                let kana = counts.get(Script::Kana);
+                let cjk = counts.get(Script::Han) + kana;
+                let kana_share = kana as f64 / cjk as f64;
+
+                let language = if kana_share >= MIN_KANA_SHARE && self.supports(Language::Japanese)
+                {
+                    Language::Japanese
+                } else if kana_share < MIN_KANA_SHARE && self.supports(Language::Chinese) {
+                    Language::Chinese
+                } else {
+                    return None;
+                };
+                Some(Detection {
+                    language,
+                    script,
+                    confidence: share(cjk),
+                })
+            }
+            Script::Latin => {
+                let trigrams = trigrams(text);
+                let likelihoods = self
+                    .profiles
+                    .iter()
+                    .map(|profile| (profile.language, profile.log_likelihood(&trigrams)))
+                    .collect::<Vec<_>>();
+                let &(language, best) = likelihoods
+                    .iter()
+                    .max_by(|(_, lhs), (_, rhs)| lhs.total_cmp(rhs))?;
+
+                // The posterior probability of the best language, assuming equal priors
+                let evidence = likelihoods
+                    .iter()
+                    .map(|&(_, likelihood)| (likelihood - best).exp())
+                    .sum::<f64>();
+                Some(Detection {
+                    language,
+                    script,
+                    confidence: share(counts.get(Script::Latin)) / evidence,
+                })
+            }
+            Script::Greek
+            | Script::Cyrillic
+            | Script::Hebrew
+            | Script::Arabic
+            | Script::Devanagari
+            | Script::Thai
+            | Script::Hangul => None,
+        }
+    }
+
+    /// Detects the language of every segment and records it in the segment's metadata.
+    ///
+    /// Segments whose language can not be detected are left unchanged. `text` has to be the text
+    /// the segments were created from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpanError`] if a segment does not refer to a valid region of `text`.
+    pub fn annotate(&self, text: &str, segments: &mut [Segment]) -> Result<(), Report<SpanError>> {
+        for segment in segments {
+            if let Some(detection) = self.detect(segment.text(text)?) {
+                detection.annotate(&mut segment.metadata);
+            }
+        }
+        Ok(())
+    }
+
+    fn supports(&self, language: Language) -> bool {
+        self.languages.contains(&language)
+    }
+}
+
+impl Default for LanguageDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segmentation::paragraphs;
+
+    fn language(text: &str) -> Option<Language> {
+        LanguageDetector::new()
+            .detect(text)
+            .map(|detection| detection.language)
+    }
+
+    #[test]
+    fn detects_latin_languages() {
+        assert_eq!(
+            language("The children were playing in the garden behind the house all afternoon."),
+            Some(Language::English)
+        );
+        assert_eq!(
+            language("Die Kinder haben den ganzen Nachmittag im Garten hinter dem Haus gespielt."),
+            Some(Language::German)
+        );
+        assert_eq!(
+            language(
+                "Les enfants ont jou\u{e9} tout l'apr\u{e8}s-midi dans le jardin derri\u{e8}re la \
+                 maison."
+            ),
+            Some(Language::French)
+        );
+        assert_eq!(
+            language(
+                "Los ni\u{f1}os jugaron toda la tarde en el jard\u{ed}n detr\u{e1}s de la casa."
+            ),
+            Some(Language::Spanish)
+        );
+    }
+
+    #[test]
+    fn detects_cjk_languages() {
+        // "I am a student." in Japanese and Chinese
+        let japanese = "\u{79c1}\u{306f}\u{5b66}\u{751f}\u{3067}\u{3059}\u{3002}";
+        let chinese = "\u{6211}\u{662f}\u{5b66}\u{751f}\u{3002}";
+
+        let detection = LanguageDetector::new()
+            .detect(japanese)
+            .expect("text has letters");
+        assert_eq!(detection.language, Language::Japanese);
+        assert!((detection.confidence - 1.0).abs() < f64::EPSILON);
+        assert_eq!(language(chinese), Some(Language::Chinese));
+        assert_eq!(
+            LanguageDetector::with_languages([Language::Chinese]).detect(japanese),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_text() {
+        assert_eq!(language("1, 2, 3!"), None);
+        // "Hello" in Russian
+        assert_eq!(
+            language(
+                "\u{417}\u{434}\u{440}\u{430}\u{432}\u{441}\u{442}\u{432}\u{443}\u{439}\u{442}\\
+                 u{435}"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn confidence_reflects_mixed_scripts() {
+        let detector = LanguageDetector::new();
+        let pure = detector
+            .detect("The weather will be dry and warm this week.")
+            .expect("text has letters");
+        let mixed = detector
+            .detect("The weather will be dry and warm this week. \u{6211}\u{662f}\u{5b66}\u{751f}")
+            .expect("text has letters");
+
+        assert_eq!(mixed.language, Language::English);
+        assert!(mixed.confidence < pure.confidence);
+    }
+
+    #[test]
+    fn annotates_segments() {
+        let text = "Das Wetter wird in dieser Woche trocken und warm sein.\n\nThe weather will be \
+                    dry and warm this week.";
+        let mut segments = paragraphs(text);
+        LanguageDetector::new()
+            .annotate(text, &mut segments)
+            .expect("valid segments");
+
+        let languages = segments
+            .iter()
+            .map(|segment| segment.metadata.get(LANGUAGE_KEY))
+            .collect::<Vec<_>>();
+        assert_eq!(languages, [Some("de"), Some("en")]);
+        assert_eq!(segments[1].metadata.get(SCRIPT_KEY), Some("latin"));
+        assert!(segments[1].metadata.get(LANGUAGE_CONFIDENCE_KEY).is_some());
+    }
+}
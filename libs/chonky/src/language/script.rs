@@ -0,0 +1,189 @@
+use core::fmt;
+
+/// A writing system, identified by the Unicode block of a character.
+///
+/// Only scripts relevant for telling supported languages apart are distinguished. Characters of
+/// other scripts, digits, punctuation and symbols do not have a script.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Thai,
+    Hangul,
+    /// Chinese characters, also used in Japanese as kanji.
+    Han,
+    /// Japanese hiragana and katakana.
+    Kana,
+}
+
+impl Script {
+    pub const ALL: [Self; 10] = [
+        Self::Latin,
+        Self::Greek,
+        Self::Cyrillic,
+        Self::Hebrew,
+        Self::Arabic,
+        Self::Devanagari,
+        Self::Thai,
+        Self::Hangul,
+        Self::Han,
+        Self::Kana,
+    ];
+
+    /// Returns the script of `char`, if it is a letter of a known script.
+    #[must_use]
+    pub fn of(char: char) -> Option<Self> {
+        if !char.is_alphabetic() {
+            return None;
+        }
+
+        Some(match char {
+            'a'..='z'
+            | 'A'..='Z'
+            | '\u{00C0}'..='\u{024F}'
+            | '\u{1E00}'..='\u{1EFF}'
+            | '\u{FF21}'..='\u{FF3A}'
+            | '\u{FF41}'..='\u{FF5A}' => Self::Latin,
+            '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Self::Greek,
+            '\u{0400}'..='\u{052F}' => Self::Cyrillic,
+            '\u{0590}'..='\u{05FF}' => Self::Hebrew,
+            '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}' => Self::Arabic,
+            '\u{0900}'..='\u{097F}' => Self::Devanagari,
+            '\u{0E00}'..='\u{0E7F}' => Self::Thai,
+            '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => {
+                Self::Hangul
+            }
+            '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+                Self::Kana
+            }
+            '\u{3005}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FA1F}' => Self::Han,
+            _ => return None,
+        })
+    }
+
+    /// Returns the lowercase name of the script, e.g. `"latin"`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Latin => "latin",
+            Self::Greek => "greek",
+            Self::Cyrillic => "cyrillic",
+            Self::Hebrew => "hebrew",
+            Self::Arabic => "arabic",
+            Self::Devanagari => "devanagari",
+            Self::Thai => "thai",
+            Self::Hangul => "hangul",
+            Self::Han => "han",
+            Self::Kana => "kana",
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// The number of letters of every [`Script`] in a text.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(super) struct ScriptCounts {
+    counts: [usize; Script::ALL.len()],
+}
+
+impl ScriptCounts {
+    pub(super) fn of(text: &str) -> Self {
+        let mut counts = Self::default();
+        for script in text.chars().filter_map(Script::of) {
+            counts.counts[script.index()] += 1;
+        }
+        counts
+    }
+
+    pub(super) const fn get(&self, script: Script) -> usize {
+        self.counts[script.index()]
+    }
+
+    pub(super) fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the script with the most letters, preferring the earlier script on ties.
+    pub(super) fn dominant(&self) -> Option<Script> {
+        Script::ALL
+            .into_iter()
+            .filter(|&script| self.get(script) > 0)
+            .max_by_key(|&script| (self.get(script), core::cmp::Reverse(script)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_range_boundaries() {
+        for (char, script) in [
+            ('\u{BF}', None),
+            ('\u{C0}', Some(Script::Latin)),
+            ('\u{D7}', None),
+            ('\u{24F}', Some(Script::Latin)),
+            ('\u{250}', None),
+            ('\u{FF21}', Some(Script::Latin)),
+            ('\u{3A9}', Some(Script::Greek)),
+            ('\u{40F}', Some(Script::Cyrillic)),
+            ('\u{3040}', None),
+            ('\u{3041}', Some(Script::Kana)),
+            ('\u{30FB}', None),
+            ('\u{30FC}', Some(Script::Kana)),
+            ('\u{FF66}', Some(Script::Kana)),
+            ('\u{3005}', Some(Script::Han)),
+            ('\u{4E00}', Some(Script::Han)),
+            ('\u{AC00}', Some(Script::Hangul)),
+        ] {
+            assert_eq!(Script::of(char), script, "{:?}", char.escape_unicode());
+        }
+    }
+
+    #[test]
+    fn ignores_digits_punctuation_and_marks() {
+        for char in ['7', '\u{663}', ' ', '.', '\u{3002}', '\u{301}', '\u{1F600}'] {
+            assert_eq!(Script::of(char), None, "{:?}", char.escape_unicode());
+        }
+    }
+
+    #[test]
+    fn dominant_script() {
+        assert_eq!(ScriptCounts::of("").dominant(), None);
+        assert_eq!(ScriptCounts::of("123 !?").dominant(), None);
+
+        let counts = ScriptCounts::of("Tokyo \u{6771}\u{4EAC}\u{3068}");
+        assert_eq!(counts.get(Script::Latin), 5);
+        assert_eq!(counts.get(Script::Han), 2);
+        assert_eq!(counts.get(Script::Kana), 1);
+        assert_eq!(counts.total(), 8);
+        assert_eq!(counts.dominant(), Some(Script::Latin));
+
+        // Ties are broken in favour of the script listed first
+        assert_eq!(
+            ScriptCounts::of("\u{3042}\u{6F22}").dominant(),
+            Some(Script::Han)
+        );
+        assert_eq!(
+            ScriptCounts::of("ab\u{3B1}\u{3B2}").dominant(),
+            Some(Script::Latin)
+        );
+    }
+}
@@ -1,26 +1,40 @@
 #![doc = include_str!("../README.md")]
 
-/// Adds two numbers together
-///
-/// # Example
-///
-/// ```rust
-/// use chonky::add;
-///
-/// assert_eq!(add(1, 3), 4);
-/// ```
-#[must_use]
-pub const fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+extern crate alloc;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub mod chunk;
+pub mod chunker;
+#[cfg(feature = "code")]
+pub mod code;
+pub mod dedup;
+pub mod document;
+#[cfg(feature = "email")]
+pub mod email;
+pub mod embedding;
+pub mod evaluation;
+mod extraction;
+pub mod hash;
+#[cfg(feature = "html")]
+pub mod html;
+pub mod language;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+#[cfg(feature = "office")]
+pub mod office;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+pub mod records;
+pub mod segment;
+pub mod segmentation;
+pub mod span;
+pub mod table;
+pub mod tokenizer;
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use self::{
+    chunk::Chunk,
+    document::{Document, MediaType, Metadata},
+    extraction::Extraction,
+    hash::ContentHash,
+    segment::{Segment, SegmentKind},
+    span::{Offset, Span},
+};
@@ -0,0 +1,334 @@
+//! Segmentation of Markdown documents.
+//!
+//! Documents are parsed as [CommonMark] with the [GitHub Flavored Markdown] extensions for tables,
+//! strikethrough, task lists and footnotes. Every block is mapped to a format-neutral
+//! [`SegmentKind`] and its [`Span`] refers to the Markdown source, including the markup.
+//!
+//! Together with the [`Chunker`], code blocks are never split, tables are split by rows with their
+//! header repeated and every chunk is annotated with the breadcrumb of the headings it is nested
+//! in.
+//!
+//! [CommonMark]: https://commonmark.org
+//! [GitHub Flavored Markdown]: https://github.github.com/gfm/
+//! [`Span`]: crate::Span
+//! [`Chunker`]: crate::chunker::Chunker
+
+use core::ops::Range;
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+use crate::{
+    segment::{Segment, SegmentKind, TITLE_KEY},
+    span::OffsetCursor,
+    table::{Cell, Table},
+};
+
+/// A list item whose text has not been emitted as a segment yet.
+#[derive(Debug)]
+struct OpenItem {
+    start: usize,
+    depth: u8,
+    emitted: bool,
+}
+
+/// A table whose rows are being collected.
+#[derive(Debug, Default)]
+struct OpenTable {
+    rows: Vec<Vec<Cell>>,
+    header_rows: usize,
+    cell: Option<String>,
+}
+
+impl OpenTable {
+    /// Adds an event nested in the table.
+    fn push(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(Tag::TableHead | Tag::TableRow) => self.rows.push(Vec::new()),
+            Event::End(TagEnd::TableHead) => self.header_rows = self.rows.len(),
+            Event::Start(Tag::TableCell) => self.cell = Some(String::new()),
+            Event::End(TagEnd::TableCell) => {
+                if let Some((cell, row)) = self.cell.take().zip(self.rows.last_mut()) {
+                    row.push(Cell::new(cell));
+                }
+            }
+            Event::Text(content) | Event::Code(content) => {
+                if let Some(cell) = &mut self.cell {
+                    cell.push_str(&content);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Removes surrounding whitespace from the region `range` of `text`.
+fn trim(text: &str, range: Range<usize>) -> Range<usize> {
+    let Some(content) = text.get(range.clone()) else {
+        return range;
+    };
+    let start = range.start + (content.len() - content.trim_start().len());
+    start..start + content.trim().len()
+}
+
+/// Splits a Markdown document into segments.
+///
+/// Headings, paragraphs, list items, tables and code blocks are returned in document order. List
+/// items are returned as a whole, including nested paragraphs and code blocks, but excluding
+/// nested lists, which are returned as items of their own. HTML blocks are returned as
+/// [`SegmentKind::Text`]. Tables are returned with their cells and their header row.
+///
+/// The plain text of every heading is stored in its [`TITLE_KEY`] metadata entry.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{
+///     chunker::{BREADCRUMB_KEY, Chunker},
+///     markdown,
+///     tokenizer::HeuristicTokenizer,
+/// };
+///
+/// let text = "# Setup\n\n## Local HASH\n\n### Docker\n\nRun `docker compose up`.\n";
+/// let segments = markdown::segment(text);
+///
+/// let chunks = Chunker::new(HeuristicTokenizer::default(), 64)
+///     .chunk(text, &segments)
+///     .expect("could not chunk text");
+///
+/// assert_eq!(chunks.len(), 1);
+/// assert_eq!(chunks[0].text, "### Docker\n\nRun `docker compose up`.");
+/// assert_eq!(
+///     chunks[0].metadata.get(BREADCRUMB_KEY),
+///     Some("Setup > Local HASH > Docker")
+/// );
+/// ```
+#[must_use]
+pub fn segment(text: &str) -> Vec<Segment> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+
+    let mut cursor = OffsetCursor::new(text);
+    let mut segments = Vec::new();
+    let mut push = |kind, range: Range<usize>, title: Option<String>| {
+        let range = trim(text, range);
+        if range.is_empty() {
+            return;
+        }
+        let mut segment = Segment::new(kind, cursor.span(range));
+        if let Some(title) = title {
+            segment.metadata.insert(TITLE_KEY, title);
+        }
+        segments.push(segment);
+    };
+
+    let mut items = Vec::<OpenItem>::new();
+    let mut title: Option<String> = None;
+    let mut table: Option<OpenTable> = None;
+    // Code blocks are emitted as a whole, nothing nested in them is emitted again
+    let mut opaque = 0_usize;
+
+    for (event, range) in Parser::new_ext(text, options).into_offset_iter() {
+        // Everything nested in a table is part of its cells
+        if let Some(table) = &mut table {
+            if event != Event::End(TagEnd::Table) {
+                table.push(event);
+                continue;
+            }
+        }
+
+        match event {
+            Event::Start(Tag::List(_)) => {
+                if let Some(item) = items.last_mut().filter(|item| !item.emitted) {
+                    item.emitted = true;
+                    push(
+                        SegmentKind::ListItem { depth: item.depth },
+                        item.start..range.start,
+                        None,
+                    );
+                }
+            }
+            Event::Start(Tag::Item) => items.push(OpenItem {
+                start: range.start,
+                depth: u8::try_from(items.len()).unwrap_or(u8::MAX),
+                emitted: false,
+            }),
+            Event::End(TagEnd::Item) => {
+                if let Some(item) = items.pop().filter(|item| !item.emitted) {
+                    push(
+                        SegmentKind::ListItem { depth: item.depth },
+                        item.start..range.end,
+                        None,
+                    );
+                }
+            }
+            _ if !items.is_empty() => {}
+
+            Event::Start(Tag::Heading { .. }) => title = Some(String::new()),
+            Event::End(TagEnd::Heading(level)) => push(
+                SegmentKind::Heading { level: level as u8 },
+                range,
+                title.take().map(|title| title.trim().to_owned()),
+            ),
+            Event::Text(content) | Event::Code(content) if opaque == 0 => {
+                if let Some(title) = &mut title {
+                    title.push_str(&content);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(title) = &mut title {
+                    title.push(' ');
+                }
+            }
+
+            Event::Start(Tag::Paragraph) if opaque == 0 => {
+                push(SegmentKind::Paragraph, range, None);
+            }
+            Event::Start(Tag::HtmlBlock) if opaque == 0 => push(SegmentKind::Text, range, None),
+            Event::Start(Tag::CodeBlock(kind)) if opaque == 0 => {
+                opaque += 1;
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().map(ToOwned::to_owned)
+                    }
+                    CodeBlockKind::Indented => None,
+                };
+                push(SegmentKind::CodeBlock { language }, range, None);
+            }
+            Event::Start(Tag::Table(_)) if opaque == 0 => table = Some(OpenTable::default()),
+            Event::End(TagEnd::Table) => {
+                if let Some(table) = table.take() {
+                    push(
+                        SegmentKind::Table(Table::from_cells(table.rows, table.header_rows)),
+                        range,
+                        None,
+                    );
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => opaque = opaque.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chunker::{BREADCRUMB_KEY, Chunker},
+        tokenizer::HeuristicTokenizer,
+    };
+
+    const DOCUMENT: &str = "# Setup
+
+Install the *tools* first.
+
+## Local HASH
+
+- Clone the repository
+- Install dependencies
+  - yarn
+  - cargo
+
+### Docker
+
+```sh
+docker compose up
+```
+
+| Service | Port |
+| ------- | ---- |
+| graph   | 4000 |
+";
+
+    #[test]
+    fn segments_blocks() {
+        let segments = segment(DOCUMENT);
+        let blocks = segments
+            .iter()
+            .map(|segment| {
+                (
+                    segment.kind.clone(),
+                    segment.text(DOCUMENT).expect("valid span"),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(blocks, [
+            (SegmentKind::Heading { level: 1 }, "# Setup"),
+            (SegmentKind::Paragraph, "Install the *tools* first."),
+            (SegmentKind::Heading { level: 2 }, "## Local HASH"),
+            (SegmentKind::ListItem { depth: 0 }, "- Clone the repository"),
+            (SegmentKind::ListItem { depth: 0 }, "- Install dependencies"),
+            (SegmentKind::ListItem { depth: 1 }, "- yarn"),
+            (SegmentKind::ListItem { depth: 1 }, "- cargo"),
+            (SegmentKind::Heading { level: 3 }, "### Docker"),
+            (
+                SegmentKind::CodeBlock {
+                    language: Some("sh".to_owned())
+                },
+                "```sh\ndocker compose up\n```"
+            ),
+            (
+                SegmentKind::Table(Table::from_cells(
+                    [vec![Cell::new("Service"), Cell::new("Port")], vec![
+                        Cell::new("graph"),
+                        Cell::new("4000")
+                    ]],
+                    1
+                )),
+                "| Service | Port |\n| ------- | ---- |\n| graph   | 4000 |"
+            ),
+        ]);
+        assert_eq!(segments[2].metadata.get(TITLE_KEY), Some("Local HASH"));
+    }
+
+    #[test]
+    fn annotates_breadcrumbs() {
+        let segments = segment(DOCUMENT);
+        let chunks = Chunker::new(HeuristicTokenizer::default(), 64)
+            .with_breadcrumb_prefix(true)
+            .chunk(DOCUMENT, &segments)
+            .expect("could not chunk text");
+
+        let breadcrumbs = chunks
+            .iter()
+            .map(|chunk| chunk.metadata.get(BREADCRUMB_KEY).expect("breadcrumb"))
+            .collect::<Vec<_>>();
+        assert_eq!(breadcrumbs, [
+            "Setup",
+            "Setup > Local HASH",
+            "Setup > Local HASH > Docker",
+            "Setup > Local HASH > Docker"
+        ]);
+        // Chunks starting with their heading are only prefixed with the enclosing headings
+        assert_eq!(chunks[0].text, "# Setup\n\nInstall the *tools* first.");
+        assert_eq!(
+            chunks[2].text,
+            "Setup > Local HASH\n\n### Docker\n\n```sh\ndocker compose up\n```"
+        );
+        // Tables form chunks of their own
+        assert_eq!(
+            chunks[3].text,
+            "Setup > Local HASH > Docker\n\n| Service | Port |\n| --- | --- |\n| graph | 4000 |"
+        );
+    }
+
+    #[test]
+    fn never_splits_code_blocks_or_table_rows() {
+        let segments = segment(DOCUMENT);
+        let chunks = Chunker::new(HeuristicTokenizer::default(), 3)
+            .chunk(DOCUMENT, &segments)
+            .expect("could not chunk text");
+
+        let texts = chunks
+            .iter()
+            .map(|chunk| chunk.text.as_str())
+            .collect::<Vec<_>>();
+        assert!(texts.contains(&"```sh\ndocker compose up\n```"));
+        assert!(texts.contains(&"| Service | Port |\n| --- | --- |\n| graph | 4000 |"));
+    }
+}
@@ -0,0 +1,460 @@
+//! Extraction of segments from Word documents.
+
+use std::collections::HashMap;
+
+use error_stack::Report;
+use roxmltree::Node;
+
+use super::{OfficeError, PART_KEY, Package, attribute, child, descendant, is, is_on, parse};
+use crate::{
+    extraction::Extraction,
+    segment::SegmentKind,
+    table::{Cell, Table, TableFormat},
+};
+
+/// Metadata key of the name of the style of a paragraph or table, e.g. `Heading 2`.
+pub const STYLE_KEY: &str = "style";
+
+/// Maximum depth of style inheritance which is followed, which guards against cycles.
+const MAX_STYLE_DEPTH: usize = 16;
+
+/// A paragraph or table style.
+#[derive(Debug, Default)]
+struct Style {
+    name: Option<String>,
+    based_on: Option<String>,
+    outline_level: Option<u8>,
+    numbered: bool,
+}
+
+/// The styles of a document by their identifier.
+#[derive(Debug, Default)]
+struct Styles {
+    styles: HashMap<String, Style>,
+    default_paragraph: Option<String>,
+}
+
+impl Styles {
+    fn parse(root: Node<'_, '_>) -> Self {
+        let mut styles = Self::default();
+        for node in root.children().filter(|node| is(*node, "style")) {
+            let Some(id) = attribute(node, "styleId") else {
+                continue;
+            };
+            if attribute(node, "type") == Some("paragraph")
+                && matches!(attribute(node, "default"), Some("1" | "true" | "on"))
+            {
+                styles.default_paragraph = Some(id.to_owned());
+            }
+
+            let properties = child(node, "pPr");
+            styles.styles.insert(id.to_owned(), Style {
+                name: child(node, "name")
+                    .and_then(|name| attribute(name, "val"))
+                    .map(ToOwned::to_owned),
+                based_on: child(node, "basedOn")
+                    .and_then(|based_on| attribute(based_on, "val"))
+                    .map(ToOwned::to_owned),
+                outline_level: properties.and_then(outline_level),
+                numbered: properties
+                    .and_then(|properties| child(properties, "numPr"))
+                    .is_some(),
+            });
+        }
+        styles
+    }
+
+    /// Returns the styles `id` inherits from, starting with the style itself.
+    fn chain<'s>(&'s self, id: &'s str) -> impl Iterator<Item = &'s Style> {
+        let mut next = Some(id);
+        core::iter::from_fn(move || {
+            let style = self.styles.get(next?)?;
+            next = style.based_on.as_deref();
+            Some(style)
+        })
+        .take(MAX_STYLE_DEPTH)
+    }
+
+    fn name<'s>(&'s self, id: &'s str) -> &'s str {
+        self.styles
+            .get(id)
+            .and_then(|style| style.name.as_deref())
+            .unwrap_or(id)
+    }
+
+    /// Returns the heading level of paragraphs with the style `id`, if any.
+    fn heading_level(&self, id: &str) -> Option<u8> {
+        self.chain(id).find_map(|style| {
+            let name = style.name.as_deref()?.to_ascii_lowercase();
+            if name == "title" {
+                Some(1)
+            } else if let Some(level) = name.strip_prefix("heading ") {
+                level.trim().parse().ok()
+            } else {
+                style.outline_level
+            }
+        })
+    }
+
+    fn is_numbered(&self, id: &str) -> bool {
+        self.chain(id).any(|style| style.numbered)
+    }
+}
+
+/// Returns the heading level of an outline level in paragraph properties, starting at `1`.
+///
+/// Outline level `9` is the level of body text.
+fn outline_level(properties: Node<'_, '_>) -> Option<u8> {
+    let level = child(properties, "outlineLvl")
+        .and_then(|level| attribute(level, "val"))?
+        .parse::<u8>()
+        .ok()?;
+    (level < 9).then_some(level + 1)
+}
+
+/// Appends the text of the runs in `node` to `text`.
+///
+/// Deleted text, field instructions and the content of text boxes are skipped.
+fn push_text(node: Node<'_, '_>, text: &mut String) {
+    for node in node.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "t" => text.push_str(node.text().unwrap_or_default()),
+            "tab" | "ptab" => text.push('\t'),
+            "br" | "cr" => text.push('\n'),
+            "noBreakHyphen" => text.push('-'),
+            "del" | "delText" | "instrText" | "pPr" | "rPr" | "txbxContent" | "fldData" => {}
+            _ => push_text(node, text),
+        }
+    }
+}
+
+fn paragraph_text(paragraph: Node<'_, '_>) -> String {
+    let mut text = String::new();
+    push_text(paragraph, &mut text);
+    text
+}
+
+/// The vertical merge state of a table cell.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum VerticalMerge {
+    None,
+    Restart,
+    Continue,
+}
+
+struct Extractor<'p> {
+    extraction: Extraction,
+    styles: Styles,
+    part: &'p str,
+}
+
+impl Extractor<'_> {
+    fn push(&mut self, kind: SegmentKind, text: &str, style: Option<&str>) {
+        if let Some(segment) = self.extraction.push(kind, text) {
+            segment.metadata.insert(PART_KEY, self.part);
+            if let Some(style) = style {
+                segment
+                    .metadata
+                    .insert(STYLE_KEY, self.styles.name(style).to_owned());
+            }
+        }
+    }
+
+    /// Extracts the paragraphs and tables in `node`.
+    fn blocks(&mut self, node: Node<'_, '_>) {
+        for node in node.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "p" => self.paragraph(node),
+                "tbl" => self.table(node),
+                "sdt" => {
+                    if let Some(content) = child(node, "sdtContent") {
+                        self.blocks(content);
+                    }
+                }
+                "customXml" | "ins" | "smartTag" => self.blocks(node),
+                _ => {}
+            }
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: Node<'_, '_>) {
+        let properties = child(paragraph, "pPr");
+        let style = properties
+            .and_then(|properties| child(properties, "pStyle"))
+            .and_then(|style| attribute(style, "val"))
+            .map(ToOwned::to_owned)
+            .or_else(|| self.styles.default_paragraph.clone());
+
+        let heading = properties.and_then(outline_level).or_else(|| {
+            style
+                .as_deref()
+                .and_then(|style| self.styles.heading_level(style))
+        });
+        let numbering = properties.and_then(|properties| child(properties, "numPr"));
+
+        let numbered = numbering.is_some()
+            || style
+                .as_deref()
+                .is_some_and(|style| self.styles.is_numbered(style));
+
+        let kind = match heading {
+            Some(level) => SegmentKind::Heading { level },
+            None if numbered => SegmentKind::ListItem {
+                depth: numbering
+                    .and_then(|numbering| child(numbering, "ilvl"))
+                    .and_then(|level| attribute(level, "val"))
+                    .and_then(|level| level.parse().ok())
+                    .unwrap_or(0),
+            },
+            None => SegmentKind::Paragraph,
+        };
+
+        self.push(kind, &paragraph_text(paragraph), style.as_deref());
+    }
+
+    fn table(&mut self, table: Node<'_, '_>) {
+        let mut grid = Vec::new();
+        let mut header_rows = 0;
+        for (index, row) in table.children().filter(|node| is(*node, "tr")).enumerate() {
+            let properties = child(row, "trPr");
+            if header_rows == index
+                && is_on(properties.and_then(|properties| child(properties, "tblHeader")))
+            {
+                header_rows += 1;
+            }
+
+            let skipped = properties
+                .and_then(|properties| child(properties, "gridBefore"))
+                .and_then(|skipped| attribute(skipped, "val"))
+                .and_then(|skipped| skipped.parse::<usize>().ok())
+                .unwrap_or(0);
+            let mut cells = vec![(String::new(), 1, VerticalMerge::None); skipped];
+
+            for cell in row.children().filter(|node| is(*node, "tc")) {
+                let properties = child(cell, "tcPr");
+                let columns = properties
+                    .and_then(|properties| child(properties, "gridSpan"))
+                    .and_then(|span| attribute(span, "val"))
+                    .and_then(|span| span.parse::<usize>().ok())
+                    .unwrap_or(1);
+                let merge = match properties.and_then(|properties| child(properties, "vMerge")) {
+                    None => VerticalMerge::None,
+                    Some(merge) if attribute(merge, "val") == Some("restart") => {
+                        VerticalMerge::Restart
+                    }
+                    Some(_) => VerticalMerge::Continue,
+                };
+                let text = cell
+                    .descendants()
+                    .filter(|node| is(*node, "p"))
+                    .map(paragraph_text)
+                    .filter(|text| !text.trim().is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                cells.push((text, columns, merge));
+            }
+            grid.push(cells);
+        }
+
+        // Cells continuing a vertical merge are covered by the row span of the cell above
+        let column_of = |row: &[(String, usize, VerticalMerge)], index: usize| {
+            row[..index]
+                .iter()
+                .map(|(_, columns, _)| columns)
+                .sum::<usize>()
+        };
+        let rows = grid.iter().enumerate().map(|(row_index, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, (_, _, merge))| *merge != VerticalMerge::Continue)
+                .map(|(index, (text, columns, merge))| {
+                    let mut cell = Cell::new(text.clone()).with_column_span(*columns);
+                    if *merge == VerticalMerge::Restart {
+                        let column = column_of(row, index);
+                        let continued = grid[row_index + 1..]
+                            .iter()
+                            .take_while(|below| {
+                                below
+                                    .iter()
+                                    .enumerate()
+                                    .any(|(below_index, (_, _, merge))| {
+                                        *merge == VerticalMerge::Continue
+                                            && column_of(below, below_index) == column
+                                    })
+                            })
+                            .count();
+                        cell = cell.with_row_span(continued + 1);
+                    }
+                    cell
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let table_node = table;
+        let table = Table::from_cells(rows, header_rows).with_detected_header();
+        if table.is_empty() {
+            return;
+        }
+        let style = descendant(table_node, &["tblPr", "tblStyle"])
+            .and_then(|style| attribute(style, "val"))
+            .map(ToOwned::to_owned);
+        let text = table.render(TableFormat::Markdown);
+        self.push(SegmentKind::Table(table), &text, style.as_deref());
+    }
+}
+
+/// Extracts the text of a Word document.
+///
+/// The paragraphs and tables of the document body are extracted in document order. Paragraphs
+/// with a heading style, e.g. `Heading 2`, or an outline level are extracted as headings,
+/// numbered and bulleted paragraphs as list items with their nesting level. The name of the style
+/// of every paragraph and table is stored in its [`STYLE_KEY`] metadata entry.
+///
+/// Tables are extracted as Markdown tables, with merged cells repeated in every position they
+/// cover. Rows marked to repeat on every page form the header of a table, otherwise a header row
+/// is detected with [`Table::with_detected_header`].
+///
+/// Headers, footers, footnotes and comments are not extracted. Tracked changes are extracted as
+/// if they were accepted.
+///
+/// # Errors
+///
+/// Returns [`OfficeError`] if the source is not a Word document or if the document could not be
+/// parsed.
+pub fn extract(source: &[u8]) -> Result<Extraction, Report<OfficeError>> {
+    let mut package = Package::open(source)?;
+    let part = package.main_part()?;
+
+    let styles = match package
+        .relationships(&part)?
+        .into_iter()
+        .find(|relationship| relationship.kind == "styles")
+    {
+        Some(relationship) => match package.read_optional(&relationship.target)? {
+            Some(content) => Styles::parse(parse(&content, &relationship.target)?.root_element()),
+            None => Styles::default(),
+        },
+        None => Styles::default(),
+    };
+
+    let content = package.read(&part)?;
+    let document = parse(&content, &part)?;
+    let Some(body) = child(document.root_element(), "body") else {
+        return Err(Report::new(OfficeError).attach_printable(format!("{part} has no body")));
+    };
+
+    let mut extractor = Extractor {
+        extraction: Extraction::new(),
+        styles,
+        part: &part,
+    };
+    extractor.blocks(body);
+    Ok(extractor.extraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::office::tests::{main_relationship, package};
+
+    const NAMESPACE: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+
+    fn document(body: &str) -> String {
+        format!(r#"<w:document xmlns:w="{NAMESPACE}">{body}</w:document>"#)
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        let relationships = main_relationship("word/document.xml");
+
+        for (source, message) in [
+            (b"".to_vec(), "empty source"),
+            (b"PK\x03\x04 truncated".to_vec(), "truncated archive"),
+            (
+                package(&[("word/document.xml", &document("<w:body/>"))]),
+                "missing relationships",
+            ),
+            (
+                package(&[("_rels/.rels", &relationships)]),
+                "missing main part",
+            ),
+            (
+                package(&[
+                    ("_rels/.rels", &relationships),
+                    ("word/document.xml", "<w:document><w:body>"),
+                ]),
+                "malformed XML",
+            ),
+            (
+                package(&[
+                    ("_rels/.rels", &relationships),
+                    ("word/document.xml", &document("")),
+                ]),
+                "missing body",
+            ),
+        ] {
+            let _: Report<_> = extract(&source).expect_err(message);
+        }
+    }
+
+    #[test]
+    fn empty_document() {
+        let extraction = extract(&package(&[
+            ("_rels/.rels", &main_relationship("word/document.xml")),
+            (
+                "word/document.xml",
+                &document("<w:body><w:p/><w:p><w:r><w:t>  </w:t></w:r></w:p><w:sectPr/></w:body>"),
+            ),
+        ]))
+        .expect("valid document");
+
+        assert!(extraction.text().is_empty());
+        assert!(extraction.segments().is_empty());
+    }
+
+    #[test]
+    fn run_text() {
+        let content = document(concat!(
+            "<w:p>",
+            "<w:r><w:t>Tracked</w:t><w:tab/></w:r>",
+            "<w:del><w:r><w:delText>gone</w:delText></w:r></w:del>",
+            "<w:ins><w:r><w:t>added</w:t><w:br/><w:t>line</w:t></w:r></w:ins>",
+            "<w:r><w:fldChar/><w:instrText>PAGE</w:instrText><w:noBreakHyphen/></w:r>",
+            "</w:p>",
+        ));
+        let document = Document::parse(&content).expect("valid XML");
+        let paragraph = child(document.root_element(), "p").expect("paragraph");
+
+        assert_eq!(paragraph_text(paragraph), "Tracked\tadded\nline-");
+    }
+
+    #[test]
+    fn cyclic_styles() {
+        let content = format!(
+            r#"<w:styles xmlns:w="{NAMESPACE}">
+                <w:style w:type="paragraph" w:styleId="A"><w:basedOn w:val="B"/></w:style>
+                <w:style w:type="paragraph" w:styleId="B"><w:basedOn w:val="A"/></w:style>
+                <w:style w:type="paragraph" w:styleId="Outlined">
+                    <w:name w:val="Outlined"/><w:basedOn w:val="A"/><w:pPr><w:outlineLvl w:val="1"/></w:pPr>
+                </w:style>
+                <w:style w:type="paragraph" w:styleId="Body">
+                    <w:name w:val="Body Text"/><w:pPr><w:outlineLvl w:val="9"/></w:pPr>
+                </w:style>
+                <w:style w:styleId="Unnamed"/>
+                <w:style><w:name w:val="No identifier"/></w:style>
+            </w:styles>"#
+        );
+        let document = Document::parse(&content).expect("valid XML");
+        let styles = Styles::parse(document.root_element());
+
+        assert_eq!(styles.heading_level("A"), None);
+        assert!(!styles.is_numbered("A"));
+        assert_eq!(styles.heading_level("Outlined"), Some(2));
+        assert_eq!(styles.heading_level("Body"), None);
+        assert_eq!(styles.heading_level("Missing"), None);
+        assert_eq!(styles.name("Unnamed"), "Unnamed");
+        assert_eq!(styles.styles.len(), 5);
+    }
+}
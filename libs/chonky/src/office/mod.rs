@@ -0,0 +1,308 @@
+//! Extraction of segments from Office Open XML documents.
+//!
+//! Word documents (DOCX), presentations (PPTX) and workbooks (XLSX) are ZIP archives of XML parts.
+//! The archive is read from memory and the parts are parsed directly, so no external converter is
+//! required.
+//!
+//! Every segment records the part of the package it was extracted from in its [`PART_KEY`]
+//! metadata entry, e.g. `word/document.xml`. The format-specific modules add further metadata,
+//! such as the paragraph style of a Word paragraph or the slide number of a presentation.
+
+pub mod docx;
+pub mod pptx;
+pub mod xlsx;
+
+use std::io::{Cursor, Read as _};
+
+use error_stack::{Report, ResultExt, bail};
+use roxmltree::{Document, Node};
+use zip::{ZipArchive, result::ZipError};
+
+use crate::extraction::Extraction;
+
+/// Metadata key of the path of the part of the package a segment was extracted from, e.g.
+/// `ppt/slides/slide2.xml`.
+pub const PART_KEY: &str = "part";
+
+/// Maximum size of a single part in bytes, which protects against decompression bombs.
+const MAX_PART_SIZE: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not extract text from Office document")]
+pub struct OfficeError;
+
+/// The kind of an Office Open XML document.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OfficeFormat {
+    Docx,
+    Pptx,
+    Xlsx,
+}
+
+impl OfficeFormat {
+    /// Returns the format of the document in the file with `extension`, if any.
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "docx" | "docm" | "dotx" => Some(Self::Docx),
+            "pptx" | "pptm" | "potx" => Some(Self::Pptx),
+            "xlsx" | "xlsm" | "xltx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+
+    /// Detects the format of the document in `source` from the type of its main part.
+    ///
+    /// Returns `None` if `source` is not an Office Open XML document.
+    #[must_use]
+    pub fn detect(source: &[u8]) -> Option<Self> {
+        let mut package = Package::open(source).ok()?;
+        let main = package.main_part().ok()?;
+        let content_types = package.read("[Content_Types].xml").ok()?;
+        let document = Document::parse(&content_types).ok()?;
+
+        let content_type = document
+            .root_element()
+            .children()
+            .filter(|node| is(*node, "Override"))
+            .find(|node| {
+                attribute(*node, "PartName")
+                    .is_some_and(|name| name.trim_start_matches('/') == main)
+            })
+            .and_then(|node| attribute(node, "ContentType"))?;
+
+        if content_type.contains("wordprocessingml") {
+            Some(Self::Docx)
+        } else if content_type.contains("presentationml") {
+            Some(Self::Pptx)
+        } else if content_type.contains("spreadsheetml") {
+            Some(Self::Xlsx)
+        } else {
+            None
+        }
+    }
+}
+
+/// Extracts the text of an Office Open XML document of any supported format.
+///
+/// The format is detected from the type of the main part of the package.
+///
+/// # Errors
+///
+/// Returns [`OfficeError`] if the source is not a supported Office Open XML document or if one of
+/// its parts could not be parsed.
+pub fn extract(source: &[u8]) -> Result<Extraction, Report<OfficeError>> {
+    match OfficeFormat::detect(source) {
+        Some(OfficeFormat::Docx) => docx::extract(source),
+        Some(OfficeFormat::Pptx) => pptx::extract(source),
+        Some(OfficeFormat::Xlsx) => xlsx::extract(source),
+        None => {
+            bail!(Report::new(OfficeError).attach_printable("not a DOCX, PPTX or XLSX document"))
+        }
+    }
+}
+
+/// A relationship from one part of a package to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Relationship {
+    id: String,
+    /// The last segment of the relationship type URI, e.g. `slide` or `notesSlide`.
+    kind: String,
+    /// The path of the target part inside of the package.
+    target: String,
+}
+
+/// A package of XML parts, read from a ZIP archive in memory.
+struct Package<'s> {
+    archive: ZipArchive<Cursor<&'s [u8]>>,
+}
+
+impl<'s> Package<'s> {
+    fn open(source: &'s [u8]) -> Result<Self, Report<OfficeError>> {
+        Ok(Self {
+            archive: ZipArchive::new(Cursor::new(source))
+                .change_context(OfficeError)
+                .attach_printable("the document is not a ZIP archive")?,
+        })
+    }
+
+    /// Reads the part at `path`, returning `None` if it does not exist.
+    fn read_optional(&mut self, path: &str) -> Result<Option<String>, Report<OfficeError>> {
+        let file = match self.archive.by_name(path) {
+            Ok(file) => file,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(error) => {
+                return Err(Report::new(error)
+                    .change_context(OfficeError)
+                    .attach_printable(path.to_owned()));
+            }
+        };
+
+        let mut content = String::new();
+        file.take(MAX_PART_SIZE + 1)
+            .read_to_string(&mut content)
+            .change_context(OfficeError)
+            .attach_printable_lazy(|| path.to_owned())?;
+        if content.len() as u64 > MAX_PART_SIZE {
+            bail!(
+                Report::new(OfficeError)
+                    .attach_printable(format!("{path} exceeds {MAX_PART_SIZE} bytes"))
+            );
+        }
+        Ok(Some(content))
+    }
+
+    fn read(&mut self, path: &str) -> Result<String, Report<OfficeError>> {
+        self.read_optional(path)?
+            .ok_or_else(|| Report::new(OfficeError).attach_printable(format!("{path} is missing")))
+    }
+
+    /// Returns the relationships of the part at `path`.
+    fn relationships(&mut self, path: &str) -> Result<Vec<Relationship>, Report<OfficeError>> {
+        let (directory, name) = path.rsplit_once('/').unwrap_or(("", path));
+        let rels_path = if directory.is_empty() {
+            format!("_rels/{name}.rels")
+        } else {
+            format!("{directory}/_rels/{name}.rels")
+        };
+        let Some(content) = self.read_optional(&rels_path)? else {
+            return Ok(Vec::new());
+        };
+        let document = parse(&content, &rels_path)?;
+
+        Ok(document
+            .root_element()
+            .children()
+            .filter(|node| is(*node, "Relationship"))
+            .filter(|node| attribute(*node, "TargetMode") != Some("External"))
+            .filter_map(|node| {
+                Some(Relationship {
+                    id: attribute(node, "Id")?.to_owned(),
+                    kind: attribute(node, "Type")?
+                        .rsplit('/')
+                        .next()
+                        .unwrap_or_default()
+                        .to_owned(),
+                    target: resolve(directory, attribute(node, "Target")?),
+                })
+            })
+            .collect())
+    }
+
+    /// Returns the path of the main part of the package, e.g. `word/document.xml`.
+    fn main_part(&mut self) -> Result<String, Report<OfficeError>> {
+        self.relationships("")?
+            .into_iter()
+            .find(|relationship| relationship.kind == "officeDocument")
+            .map(|relationship| relationship.target)
+            .ok_or_else(|| {
+                Report::new(OfficeError).attach_printable("the package has no main document")
+            })
+    }
+}
+
+/// Resolves the relationship `target` relative to the `directory` of its source part.
+fn resolve(directory: &str, target: &str) -> String {
+    let mut segments = if target.starts_with('/') {
+        Vec::new()
+    } else {
+        directory
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>()
+    };
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+fn parse<'t>(content: &'t str, path: &str) -> Result<Document<'t>, Report<OfficeError>> {
+    Document::parse(content)
+        .change_context(OfficeError)
+        .attach_printable_lazy(|| path.to_owned())
+}
+
+/// Returns the value of the attribute with the local name `name`, regardless of its namespace.
+///
+/// Transitional and strict Office Open XML documents use different namespaces for the same
+/// elements and attributes, so only local names are compared.
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .find(|attribute| attribute.name() == name)
+        .map(|attribute| attribute.value())
+}
+
+/// Returns the first child element of `node` with the local name `name`.
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.tag_name().name() == name)
+}
+
+/// Returns the element at the end of `path`, a sequence of local names of nested child elements.
+fn descendant<'a, 'input>(node: Node<'a, 'input>, path: &[&str]) -> Option<Node<'a, 'input>> {
+    path.iter().try_fold(node, |node, name| child(node, name))
+}
+
+/// Returns `true` if `node` is an element with the local name `name`.
+fn is(node: Node<'_, '_>, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name
+}
+
+/// Parses an OOXML boolean property such as `<w:b/>` or `<w:b w:val="false"/>`.
+fn is_on(node: Option<Node<'_, '_>>) -> bool {
+    node.is_some_and(|node| !matches!(attribute(node, "val"), Some("0" | "false" | "off" | "none")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::*;
+
+    /// Relationships of a package whose main part is `target`.
+    pub(in crate::office) fn main_relationship(target: &str) -> String {
+        format!(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="{target}"/></Relationships>"#
+        )
+    }
+
+    /// Writes `parts` into a ZIP archive.
+    pub(in crate::office) fn package(parts: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, content) in parts {
+            writer
+                .start_file(*path, SimpleFileOptions::default())
+                .expect("could not start part");
+            writer
+                .write_all(content.as_bytes())
+                .expect("could not write part");
+        }
+        writer
+            .finish()
+            .expect("could not finish package")
+            .into_inner()
+    }
+
+    #[test]
+    fn resolves_targets() {
+        assert_eq!(resolve("word", "document.xml"), "word/document.xml");
+        assert_eq!(resolve("", "word/document.xml"), "word/document.xml");
+        assert_eq!(
+            resolve("ppt/slides", "../notesSlides/notesSlide1.xml"),
+            "ppt/notesSlides/notesSlide1.xml"
+        );
+        assert_eq!(
+            resolve("xl", "/xl/worksheets/sheet1.xml"),
+            "xl/worksheets/sheet1.xml"
+        );
+    }
+}
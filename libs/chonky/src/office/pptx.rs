@@ -0,0 +1,331 @@
+//! Extraction of segments from presentations.
+
+use error_stack::Report;
+use roxmltree::Node;
+
+use super::{OfficeError, PART_KEY, Package, attribute, child, descendant, is, parse};
+use crate::{
+    extraction::Extraction,
+    segment::SegmentKind,
+    table::{Cell, Table, TableFormat},
+};
+
+/// Metadata key of the number of the slide a segment was extracted from, starting at `1`.
+pub const SLIDE_KEY: &str = "slide";
+
+/// Metadata key which is set to `true` for segments extracted from the speaker notes of a slide.
+pub const SPEAKER_NOTES_KEY: &str = "speaker_notes";
+
+/// The role of a shape on a slide, given by its placeholder type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Placeholder {
+    Title,
+    Body,
+    /// Slide numbers, dates, footers and the slide image on notes pages, which are not extracted.
+    Ignored,
+    Other,
+}
+
+impl Placeholder {
+    fn of(shape: Node<'_, '_>) -> Option<Self> {
+        let placeholder = descendant(shape, &["nvSpPr", "nvPr", "ph"])?;
+        Some(match attribute(placeholder, "type") {
+            Some("title" | "ctrTitle") => Self::Title,
+            // Placeholders without a type are body placeholders
+            Some("body") | None => Self::Body,
+            Some("sldNum" | "dt" | "ftr" | "hdr" | "sldImg") => Self::Ignored,
+            Some(_) => Self::Other,
+        })
+    }
+}
+
+/// Returns the text of a paragraph of a text body.
+fn paragraph_text(paragraph: Node<'_, '_>) -> String {
+    let mut text = String::new();
+    for node in paragraph.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "r" | "fld" => {
+                if let Some(content) = child(node, "t").and_then(|node| node.text()) {
+                    text.push_str(content);
+                }
+            }
+            "br" => text.push('\n'),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Returns `true` if the table cell is covered by a merged cell, whose spans already include it.
+fn is_covered(cell: Node<'_, '_>) -> bool {
+    attribute(cell, "hMerge").is_some() || attribute(cell, "vMerge").is_some()
+}
+
+#[derive(Debug, Default)]
+struct Extractor {
+    extraction: Extraction,
+    slide: usize,
+    part: String,
+    notes: bool,
+}
+
+impl Extractor {
+    fn push(&mut self, kind: SegmentKind, text: &str) {
+        if let Some(segment) = self.extraction.push(kind, text) {
+            segment.metadata.insert(SLIDE_KEY, self.slide.to_string());
+            segment.metadata.insert(PART_KEY, self.part.clone());
+            if self.notes {
+                segment.metadata.insert(SPEAKER_NOTES_KEY, "true");
+            }
+        }
+    }
+
+    /// Extracts the shapes of a shape tree or group in document order.
+    fn shapes(&mut self, tree: Node<'_, '_>) {
+        for shape in tree.children().filter(Node::is_element) {
+            match shape.tag_name().name() {
+                "sp" => self.shape(shape),
+                "grpSp" => self.shapes(shape),
+                "graphicFrame" => {
+                    if let Some(table) = shape.descendants().find(|node| is(*node, "tbl")) {
+                        self.table(table);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn shape(&mut self, shape: Node<'_, '_>) {
+        let placeholder = Placeholder::of(shape);
+        if placeholder == Some(Placeholder::Ignored) {
+            return;
+        }
+        let Some(body) = child(shape, "txBody") else {
+            return;
+        };
+        let paragraphs = body.children().filter(|node| is(*node, "p"));
+
+        if placeholder == Some(Placeholder::Title) && !self.notes {
+            let title = paragraphs.map(paragraph_text).collect::<Vec<_>>().join(" ");
+            self.push(SegmentKind::Heading { level: 1 }, &title);
+            return;
+        }
+
+        for paragraph in paragraphs {
+            let level = child(paragraph, "pPr")
+                .and_then(|properties| attribute(properties, "lvl"))
+                .and_then(|level| level.parse::<u8>().ok());
+            let bulleted = child(paragraph, "pPr").is_some_and(|properties| {
+                child(properties, "buChar").is_some() || child(properties, "buAutoNum").is_some()
+            });
+
+            // Text in body placeholders is a bulleted list unless bullets are turned off
+            let no_bullet = child(paragraph, "pPr")
+                .and_then(|properties| child(properties, "buNone"))
+                .is_some();
+            let kind = if !self.notes
+                && !no_bullet
+                && (bulleted || level.is_some() || placeholder == Some(Placeholder::Body))
+            {
+                SegmentKind::ListItem {
+                    depth: level.unwrap_or(0),
+                }
+            } else {
+                SegmentKind::Paragraph
+            };
+            self.push(kind, &paragraph_text(paragraph));
+        }
+    }
+
+    fn table(&mut self, table: Node<'_, '_>) {
+        // Tables styled with a distinct first row have a header row
+        let header_rows = usize::from(matches!(
+            child(table, "tblPr").and_then(|properties| attribute(properties, "firstRow")),
+            Some("1" | "true")
+        ));
+
+        let rows = table
+            .children()
+            .filter(|node| is(*node, "tr"))
+            .map(|row| {
+                row.children()
+                    .filter(|node| is(*node, "tc") && !is_covered(*node))
+                    .map(|cell| {
+                        let text = cell
+                            .descendants()
+                            .filter(|node| is(*node, "p"))
+                            .map(paragraph_text)
+                            .filter(|text| !text.trim().is_empty())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        let span = |name| {
+                            attribute(cell, name)
+                                .and_then(|span| span.parse().ok())
+                                .unwrap_or(1)
+                        };
+                        Cell::new(text)
+                            .with_column_span(span("gridSpan"))
+                            .with_row_span(span("rowSpan"))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let table = Table::from_cells(rows, header_rows).with_detected_header();
+        if !table.is_empty() {
+            let text = table.render(TableFormat::Markdown);
+            self.push(SegmentKind::Table(table), &text);
+        }
+    }
+}
+
+/// Extracts the text of a presentation.
+///
+/// Slides are extracted in presentation order, every slide followed by its speaker notes. The
+/// title of a slide is extracted as a heading, so every slide forms a section of its own.
+/// Paragraphs of body placeholders and bulleted paragraphs are extracted as list items with their
+/// indentation level, other text as paragraphs. Tables are extracted as Markdown tables.
+///
+/// Every segment records the number of its slide in its [`SLIDE_KEY`] metadata entry. Segments
+/// extracted from speaker notes are marked with the [`SPEAKER_NOTES_KEY`] metadata entry.
+///
+/// Slide numbers, dates, footers and text inherited from slide layouts and masters are not
+/// extracted.
+///
+/// # Errors
+///
+/// Returns [`OfficeError`] if the source is not a presentation or if one of its slides could not
+/// be parsed.
+pub fn extract(source: &[u8]) -> Result<Extraction, Report<OfficeError>> {
+    let mut package = Package::open(source)?;
+    let part = package.main_part()?;
+    let relationships = package.relationships(&part)?;
+
+    let content = package.read(&part)?;
+    let presentation = parse(&content, &part)?;
+    let slides = child(presentation.root_element(), "sldIdLst")
+        .into_iter()
+        .flat_map(|list| list.children().filter(|node| is(*node, "sldId")))
+        .filter_map(|slide| {
+            // `id` is the slide ID, the namespaced `r:id` the ID of the relationship to the slide
+            let id = slide
+                .attributes()
+                .find(|attribute| attribute.name() == "id" && attribute.namespace().is_some())?
+                .value();
+            relationships
+                .iter()
+                .find(|relationship| relationship.id == id)
+                .map(|relationship| relationship.target.clone())
+        })
+        .collect::<Vec<_>>();
+
+    let mut extractor = Extractor::default();
+    for (index, slide) in slides.iter().enumerate() {
+        let notes = package
+            .relationships(slide)?
+            .into_iter()
+            .find(|relationship| relationship.kind == "notesSlide")
+            .map(|relationship| relationship.target);
+
+        for (part, is_notes) in [(Some(slide.clone()), false), (notes, true)] {
+            let Some(part) = part else {
+                continue;
+            };
+            let Some(content) = package.read_optional(&part)? else {
+                continue;
+            };
+            let document = parse(&content, &part)?;
+
+            extractor.slide = index + 1;
+            extractor.part = part;
+            extractor.notes = is_notes;
+            if let Some(tree) = descendant(document.root_element(), &["cSld", "spTree"]) {
+                extractor.shapes(tree);
+            }
+        }
+    }
+
+    Ok(extractor.extraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::office::tests::{main_relationship, package};
+
+    const PRESENTATION: &str = r#"<p:presentation xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><p:sldIdLst><p:sldId id="256" r:id="rId2"/></p:sldIdLst></p:presentation>"#;
+
+    const PRESENTATION_RELATIONSHIPS: &str = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide" Target="slides/slide1.xml"/></Relationships>"#;
+
+    fn presentation(slide: Option<&str>) -> Vec<u8> {
+        let relationships = main_relationship("ppt/presentation.xml");
+        let mut parts = vec![
+            ("_rels/.rels", relationships.as_str()),
+            ("ppt/presentation.xml", PRESENTATION),
+            (
+                "ppt/_rels/presentation.xml.rels",
+                PRESENTATION_RELATIONSHIPS,
+            ),
+        ];
+        if let Some(slide) = slide {
+            parts.push(("ppt/slides/slide1.xml", slide));
+        }
+        package(&parts)
+    }
+
+    #[test]
+    fn rejects_malformed_presentations() {
+        for (source, message) in [
+            (b"".to_vec(), "empty source"),
+            (
+                package(&[("ppt/presentation.xml", PRESENTATION)]),
+                "missing relationships",
+            ),
+            (presentation(Some("<p:sld><p:cSld>")), "malformed slide"),
+        ] {
+            let _: Report<_> = extract(&source).expect_err(message);
+        }
+    }
+
+    #[test]
+    fn empty_presentation() {
+        let empty = package(&[
+            ("_rels/.rels", &main_relationship("ppt/presentation.xml")),
+            (
+                "ppt/presentation.xml",
+                r#"<p:presentation xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"/>"#,
+            ),
+        ]);
+        assert!(extract(&empty).expect("valid presentation").is_empty());
+
+        // Slides whose part is missing are skipped
+        assert!(
+            extract(&presentation(None))
+                .expect("valid presentation")
+                .is_empty()
+        );
+        assert!(
+            extract(&presentation(Some(
+                r#"<p:sld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree/></p:cSld></p:sld>"#
+            )))
+            .expect("valid presentation")
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn run_text() {
+        let content = concat!(
+            r#"<a:p xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">"#,
+            "<a:pPr/><a:r><a:rPr/><a:t>Slide</a:t></a:r><a:br/>",
+            r#"<a:fld type="slidenum"><a:t>3</a:t></a:fld><a:r/><a:endParaRPr/>"#,
+            "</a:p>",
+        );
+        let document = Document::parse(content).expect("valid XML");
+
+        assert_eq!(paragraph_text(document.root_element()), "Slide\n3");
+    }
+}
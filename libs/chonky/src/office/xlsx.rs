@@ -0,0 +1,286 @@
+//! Extraction of segments from workbooks.
+
+use alloc::collections::BTreeMap;
+
+use error_stack::Report;
+use roxmltree::Node;
+
+use super::{OfficeError, PART_KEY, Package, attribute, child, is, parse};
+use crate::{
+    extraction::Extraction,
+    segment::SegmentKind,
+    table::{Table, TableFormat},
+};
+
+/// Metadata key of the name of the sheet a segment was extracted from.
+pub const SHEET_KEY: &str = "sheet";
+
+/// Metadata key of the range of cells a table was extracted from, e.g. `A1:C4`.
+pub const CELL_RANGE_KEY: &str = "range";
+
+/// Maximum number of columns of a sheet, which is also the limit of spreadsheet applications.
+const MAX_COLUMNS: usize = 1 << 14;
+
+/// Returns the zero-based row and column of a cell reference such as `B3`.
+fn cell_position(reference: &str) -> Option<(usize, usize)> {
+    let digits = reference.find(|character: char| character.is_ascii_digit())?;
+    let (letters, digits) = reference.split_at(digits);
+    if letters.is_empty() {
+        return None;
+    }
+
+    let mut column = 0_usize;
+    for letter in letters.bytes() {
+        if !letter.is_ascii_uppercase() {
+            return None;
+        }
+        column = column * 26 + usize::from(letter - b'A') + 1;
+        if column > MAX_COLUMNS {
+            return None;
+        }
+    }
+    let row = digits.parse::<usize>().ok()?.checked_sub(1)?;
+    Some((row, column - 1))
+}
+
+/// Returns the letters of the zero-based `column`, e.g. `AA` for `26`.
+#[expect(
+    clippy::integer_division_remainder_used,
+    clippy::cast_possible_truncation,
+    reason = "column letters are the digits of a bijective base-26 numeral"
+)]
+fn column_name(column: usize) -> String {
+    let mut letters = Vec::new();
+    let mut remaining = column + 1;
+    while remaining > 0 {
+        remaining -= 1;
+        letters.push(char::from(b'A' + (remaining % 26) as u8));
+        remaining /= 26;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Returns the text of a rich or plain string item, skipping phonetic runs.
+fn string_item(item: Node<'_, '_>) -> String {
+    item.children()
+        .filter(Node::is_element)
+        .filter_map(|node| match node.tag_name().name() {
+            "t" => node.text(),
+            "r" => child(node, "t").and_then(|text| text.text()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the displayed value of a cell.
+///
+/// Numbers are returned as stored, so dates are returned as serial numbers.
+fn cell_value(cell: Node<'_, '_>, shared_strings: &[String]) -> String {
+    let value = child(cell, "v").and_then(|value| value.text());
+    match attribute(cell, "t") {
+        Some("s") => value
+            .and_then(|index| index.trim().parse::<usize>().ok())
+            .and_then(|index| shared_strings.get(index))
+            .cloned()
+            .unwrap_or_default(),
+        Some("inlineStr") => child(cell, "is").map(string_item).unwrap_or_default(),
+        Some("b") => match value.map(str::trim) {
+            Some("1") => "TRUE".to_owned(),
+            Some("0") => "FALSE".to_owned(),
+            _ => String::new(),
+        },
+        _ => value.unwrap_or_default().to_owned(),
+    }
+}
+
+/// Returns the non-empty cells of a worksheet by their position.
+fn cells(worksheet: Node<'_, '_>, shared_strings: &[String]) -> BTreeMap<(usize, usize), String> {
+    let mut cells = BTreeMap::new();
+    let Some(data) = child(worksheet, "sheetData") else {
+        return cells;
+    };
+
+    // Row and cell references are optional, in which case they follow the previous one
+    let mut row_index = 0;
+    for row in data.children().filter(|node| is(*node, "row")) {
+        row_index = attribute(row, "r")
+            .and_then(|row| row.parse::<usize>().ok())
+            .and_then(|row| row.checked_sub(1))
+            .unwrap_or(row_index);
+
+        let mut column = 0;
+        for cell in row.children().filter(|node| is(*node, "c")) {
+            if let Some((_, referenced)) = attribute(cell, "r").and_then(cell_position) {
+                column = referenced;
+            }
+            if column >= MAX_COLUMNS {
+                break;
+            }
+            let value = cell_value(cell, shared_strings);
+            if !value.trim().is_empty() {
+                cells.insert((row_index, column), value);
+            }
+            column += 1;
+        }
+        row_index += 1;
+    }
+
+    // The value of a merged cell is stored in its top-left cell only
+    let merged = child(worksheet, "mergeCells")
+        .into_iter()
+        .flat_map(|merged| merged.children().filter(|node| is(*node, "mergeCell")))
+        .filter_map(|merged| {
+            let (start, end) = attribute(merged, "ref")?.split_once(':')?;
+            Some((cell_position(start)?, cell_position(end)?))
+        });
+    for ((first_row, first_column), (last_row, last_column)) in merged {
+        let Some(value) = cells.get(&(first_row, first_column)).cloned() else {
+            continue;
+        };
+        for row in first_row..=last_row {
+            for column in first_column..=last_column {
+                cells.entry((row, column)).or_insert_with(|| value.clone());
+            }
+        }
+    }
+
+    cells
+}
+
+/// Returns the rows of the cells spanning from the first to the last used column, together with
+/// the range of cells they were taken from.
+///
+/// Empty rows are skipped.
+fn grid(cells: &BTreeMap<(usize, usize), String>) -> Option<(Vec<Vec<String>>, String)> {
+    let first_column = cells.keys().map(|(_, column)| *column).min()?;
+    let last_column = cells.keys().map(|(_, column)| *column).max()?;
+    let (first_row, _) = *cells.keys().next()?;
+    let (last_row, _) = *cells.keys().next_back()?;
+
+    let mut rows = Vec::<(usize, Vec<String>)>::new();
+    for (&(row, column), value) in cells {
+        if rows.last().is_none_or(|(index, _)| *index != row) {
+            rows.push((row, vec![String::new(); last_column - first_column + 1]));
+        }
+        if let Some((_, cells)) = rows.last_mut() {
+            cells[column - first_column].clone_from(value);
+        }
+    }
+
+    let range = format!(
+        "{}{}:{}{}",
+        column_name(first_column),
+        first_row + 1,
+        column_name(last_column),
+        last_row + 1
+    );
+    Some((rows.into_iter().map(|(_, cells)| cells).collect(), range))
+}
+
+/// Extracts the text of a workbook.
+///
+/// Every sheet is extracted as a heading with the name of the sheet, followed by a Markdown table
+/// of the used range of cells, so every sheet forms a section of its own. Empty rows are skipped
+/// and the values of merged cells are repeated in every cell they cover. A header row is detected
+/// with [`Table::with_detected_header`].
+///
+/// Cells are extracted with their stored value: formulas are replaced by their cached result and
+/// numbers, including dates, are not formatted. Every segment records the name of its sheet in its
+/// [`SHEET_KEY`] metadata entry and tables record the range of cells they cover in their
+/// [`CELL_RANGE_KEY`] metadata entry.
+///
+/// # Errors
+///
+/// Returns [`OfficeError`] if the source is not a workbook or if one of its sheets could not be
+/// parsed.
+pub fn extract(source: &[u8]) -> Result<Extraction, Report<OfficeError>> {
+    let mut package = Package::open(source)?;
+    let part = package.main_part()?;
+    let relationships = package.relationships(&part)?;
+
+    let shared_strings = match relationships
+        .iter()
+        .find(|relationship| relationship.kind == "sharedStrings")
+    {
+        Some(relationship) => match package.read_optional(&relationship.target)? {
+            Some(content) => parse(&content, &relationship.target)?
+                .root_element()
+                .children()
+                .filter(|node| is(*node, "si"))
+                .map(string_item)
+                .collect(),
+            None => Vec::new(),
+        },
+        None => Vec::new(),
+    };
+
+    let content = package.read(&part)?;
+    let workbook = parse(&content, &part)?;
+    let sheets = child(workbook.root_element(), "sheets")
+        .into_iter()
+        .flat_map(|sheets| sheets.children().filter(|node| is(*node, "sheet")))
+        .filter_map(|sheet| {
+            // `sheetId` is the sheet ID, the namespaced `r:id` the ID of the relationship to it
+            let id = sheet
+                .attributes()
+                .find(|attribute| attribute.name() == "id" && attribute.namespace().is_some())?
+                .value();
+            let relationship = relationships
+                .iter()
+                .find(|relationship| relationship.id == id)?;
+            let name = attribute(sheet, "name").unwrap_or_default().to_owned();
+            Some((name, relationship.target.clone()))
+        })
+        .collect::<Vec<_>>();
+
+    let mut extraction = Extraction::new();
+    for (name, part) in sheets {
+        // Chart sheets and dialog sheets have no cells
+        let Some(content) = package.read_optional(&part)? else {
+            continue;
+        };
+        let document = parse(&content, &part)?;
+        let Some((rows, range)) = grid(&cells(document.root_element(), &shared_strings)) else {
+            continue;
+        };
+
+        if let Some(segment) = extraction.push(SegmentKind::Heading { level: 1 }, &name) {
+            segment.metadata.insert(SHEET_KEY, name.clone());
+            segment.metadata.insert(PART_KEY, part.clone());
+        }
+
+        let table = Table::new(rows, 0).with_detected_header();
+        let text = table.render(TableFormat::Markdown);
+        if let Some(segment) = extraction.push(SegmentKind::Table(table), &text) {
+            segment.metadata.insert(SHEET_KEY, name);
+            segment.metadata.insert(PART_KEY, part);
+            segment.metadata.insert(CELL_RANGE_KEY, range);
+        }
+    }
+
+    Ok(extraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cell_references() {
+        assert_eq!(cell_position("A1"), Some((0, 0)));
+        assert_eq!(cell_position("B3"), Some((2, 1)));
+        assert_eq!(cell_position("AA10"), Some((9, 26)));
+        assert_eq!(cell_position("XFD1"), Some((0, 16_383)));
+        assert_eq!(cell_position("XFE1"), None);
+        assert_eq!(cell_position("A0"), None);
+        assert_eq!(cell_position("12"), None);
+        assert_eq!(cell_position("a1"), None);
+
+        for column in [0, 1, 25, 26, 27, 701, 702, 16_383] {
+            assert_eq!(
+                cell_position(&format!("{}1", column_name(column))),
+                Some((0, column))
+            );
+        }
+    }
+}
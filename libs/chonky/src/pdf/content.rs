@@ -0,0 +1,551 @@
+use alloc::collections::BTreeMap;
+
+use lopdf::{Dictionary, Document, Encoding, Object, ObjectId};
+
+/// Height of a glyph above the baseline, relative to the font size.
+const ASCENT: f32 = 0.8;
+/// Depth of a glyph below the baseline, relative to the font size.
+const DESCENT: f32 = 0.2;
+/// Glyph width in thousandths of the font size if a font does not specify any widths.
+const DEFAULT_WIDTH: f32 = 500.0;
+
+/// An affine transformation `[a b c d e f]` as used by PDF content streams.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Matrix([f32; 6]);
+
+#[expect(
+    clippy::min_ident_chars,
+    clippy::many_single_char_names,
+    reason = "names follow the PDF specification"
+)]
+impl Matrix {
+    const IDENTITY: Self = Self([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    const fn translation(x: f32, y: f32) -> Self {
+        Self([1.0, 0.0, 0.0, 1.0, x, y])
+    }
+
+    fn from_operands(operands: &[Object]) -> Option<Self> {
+        let mut matrix = [0.0; 6];
+        if operands.len() != matrix.len() {
+            return None;
+        }
+        for (value, operand) in matrix.iter_mut().zip(operands) {
+            *value = operand.as_float().ok()?;
+        }
+        Some(Self(matrix))
+    }
+
+    /// Returns the transformation applying `self` first and `other` second.
+    fn then(self, other: Self) -> Self {
+        let [a1, b1, c1, d1, e1, f1] = self.0;
+        let [a2, b2, c2, d2, e2, f2] = other.0;
+        Self([
+            a1.mul_add(a2, b1 * c2),
+            a1.mul_add(b2, b1 * d2),
+            c1.mul_add(a2, d1 * c2),
+            c1.mul_add(b2, d1 * d2),
+            e1.mul_add(a2, f1.mul_add(c2, e2)),
+            e1.mul_add(b2, f1.mul_add(d2, f2)),
+        ])
+    }
+
+    fn apply(self, x: f32, y: f32) -> (f32, f32) {
+        let [a, b, c, d, e, f] = self.0;
+        (a.mul_add(x, c.mul_add(y, e)), b.mul_add(x, d.mul_add(y, f)))
+    }
+
+    /// Returns the factor by which vertical distances are scaled.
+    fn vertical_scale(self) -> f32 {
+        let [.., c, d, _, _] = self.0;
+        c.hypot(d)
+    }
+}
+
+/// The parts of a font needed to decode text and to measure glyphs.
+struct Font<'d> {
+    encoding: Option<Encoding<'d>>,
+    /// Composite fonts use two bytes per character code.
+    composite: bool,
+    first_char: u32,
+    widths: Vec<f32>,
+    cid_widths: BTreeMap<u32, f32>,
+    default_width: f32,
+}
+
+impl<'d> Font<'d> {
+    fn load(document: &'d Document, font: &'d Dictionary) -> Self {
+        let deref = |object: &'d Object| {
+            document
+                .dereference(object)
+                .map_or(object, |(_, object)| object)
+        };
+        let numbers = |object: &'d Object| {
+            deref(object)
+                .as_array()
+                .map(|values| {
+                    values
+                        .iter()
+                        .map(|value| deref(value).as_float().unwrap_or(0.0))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+
+        let composite = font
+            .get(b"Subtype")
+            .and_then(Object::as_name)
+            .is_ok_and(|subtype| subtype == b"Type0");
+
+        let mut this = Self {
+            encoding: font.get_font_encoding(document).ok(),
+            composite,
+            first_char: 0,
+            widths: Vec::new(),
+            cid_widths: BTreeMap::new(),
+            default_width: DEFAULT_WIDTH,
+        };
+
+        if composite {
+            let Some(descendant) = font
+                .get(b"DescendantFonts")
+                .map(deref)
+                .and_then(Object::as_array)
+                .ok()
+                .and_then(|fonts| fonts.first())
+                .map(deref)
+                .and_then(|font| font.as_dict().ok())
+            else {
+                return this;
+            };
+
+            this.default_width = descendant
+                .get(b"DW")
+                .and_then(Object::as_float)
+                .unwrap_or(1000.0);
+
+            // Entries are either `first [w1 w2 ...]` or `first last w`
+            let widths = descendant
+                .get(b"W")
+                .map(deref)
+                .and_then(Object::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let mut index = 0;
+            while let Some(first) = widths.get(index).and_then(|first| first.as_i64().ok()) {
+                let first = u32::try_from(first).unwrap_or_default();
+                match widths.get(index + 1).map(deref) {
+                    Some(Object::Array(_)) => {
+                        for (cid, width) in (first..).zip(numbers(&widths[index + 1])) {
+                            this.cid_widths.insert(cid, width);
+                        }
+                        index += 2;
+                    }
+                    Some(last) => {
+                        let last = last
+                            .as_i64()
+                            .ok()
+                            .and_then(|last| u32::try_from(last).ok())
+                            .unwrap_or(first);
+                        let width = widths
+                            .get(index + 2)
+                            .and_then(|width| width.as_float().ok())
+                            .unwrap_or(this.default_width);
+                        for cid in first..=last.min(first.saturating_add(0xFFFF)) {
+                            this.cid_widths.insert(cid, width);
+                        }
+                        index += 3;
+                    }
+                    None => break,
+                }
+            }
+        } else {
+            this.first_char = font
+                .get(b"FirstChar")
+                .and_then(Object::as_i64)
+                .ok()
+                .and_then(|first| u32::try_from(first).ok())
+                .unwrap_or_default();
+            this.widths = font.get(b"Widths").map(numbers).unwrap_or_default();
+            if let Some(missing) = font
+                .get(b"FontDescriptor")
+                .map(deref)
+                .and_then(Object::as_dict)
+                .and_then(|descriptor| descriptor.get(b"MissingWidth"))
+                .and_then(Object::as_float)
+                .ok()
+                .filter(|width| *width > 0.0)
+            {
+                this.default_width = missing;
+            }
+        }
+
+        this
+    }
+
+    fn codes<'b>(&self, bytes: &'b [u8]) -> impl Iterator<Item = u32> + 'b {
+        let width = if self.composite { 2 } else { 1 };
+        bytes.chunks(width).map(|code| {
+            code.iter()
+                .fold(0, |code, byte| code << 8 | u32::from(*byte))
+        })
+    }
+
+    /// Returns the width of the glyph for `code` in thousandths of the font size.
+    fn width(&self, code: u32) -> f32 {
+        if self.composite {
+            return self
+                .cid_widths
+                .get(&code)
+                .copied()
+                .unwrap_or(self.default_width);
+        }
+
+        code.checked_sub(self.first_char)
+            .and_then(|index| self.widths.get(usize::try_from(index).ok()?))
+            .copied()
+            .filter(|width| *width > 0.0)
+            .unwrap_or(self.default_width)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> String {
+        self.encoding
+            .as_ref()
+            .and_then(|encoding| Document::decode_text(encoding, bytes).ok())
+            .unwrap_or_else(|| bytes.iter().copied().map(char::from).collect())
+    }
+}
+
+/// Text shown by a single text-showing operator.
+///
+/// Coordinates are in the default user space of the page, with the y-axis pointing up.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TextRun {
+    pub text: String,
+    pub left: f32,
+    pub bottom: f32,
+    pub right: f32,
+    pub top: f32,
+    pub font_size: f32,
+}
+
+#[derive(Clone)]
+struct GraphicsState {
+    ctm: Matrix,
+    font: Option<Vec<u8>>,
+    font_size: f32,
+    char_spacing: f32,
+    word_spacing: f32,
+    horizontal_scaling: f32,
+    leading: f32,
+    rise: f32,
+}
+
+impl Default for GraphicsState {
+    fn default() -> Self {
+        Self {
+            ctm: Matrix::IDENTITY,
+            font: None,
+            font_size: 0.0,
+            char_spacing: 0.0,
+            word_spacing: 0.0,
+            horizontal_scaling: 1.0,
+            leading: 0.0,
+            rise: 0.0,
+        }
+    }
+}
+
+struct Interpreter<'d> {
+    fonts: BTreeMap<Vec<u8>, Font<'d>>,
+    state: GraphicsState,
+    stack: Vec<GraphicsState>,
+    text_matrix: Matrix,
+    line_matrix: Matrix,
+    runs: Vec<TextRun>,
+}
+
+impl Interpreter<'_> {
+    fn next_line(&mut self, x: f32, y: f32) {
+        self.line_matrix = Matrix::translation(x, y).then(self.line_matrix);
+        self.text_matrix = self.line_matrix;
+    }
+
+    fn show(&mut self, bytes: &[u8]) {
+        let state = &self.state;
+        let Some(font) = state.font.as_ref().and_then(|font| self.fonts.get(font)) else {
+            return;
+        };
+
+        let mut advance = 0.0;
+        for code in font.codes(bytes) {
+            let word_spacing = if !font.composite && code == 32 {
+                state.word_spacing
+            } else {
+                0.0
+            };
+            advance += (font.width(code) / 1000.0).mul_add(state.font_size, state.char_spacing)
+                + word_spacing;
+        }
+        advance *= state.horizontal_scaling;
+
+        let matrix = self.text_matrix.then(state.ctm);
+        let low = state.font_size.mul_add(-DESCENT, state.rise);
+        let high = state.font_size.mul_add(ASCENT, state.rise);
+        let corners = [(0.0, low), (advance, low), (0.0, high), (advance, high)]
+            .map(|(x, y)| matrix.apply(x, y));
+
+        let text = font.decode(bytes);
+        if !text.trim().is_empty() {
+            self.runs.push(TextRun {
+                text,
+                left: corners
+                    .iter()
+                    .map(|(x, _)| *x)
+                    .fold(f32::INFINITY, f32::min),
+                bottom: corners
+                    .iter()
+                    .map(|(_, y)| *y)
+                    .fold(f32::INFINITY, f32::min),
+                right: corners
+                    .iter()
+                    .map(|(x, _)| *x)
+                    .fold(f32::NEG_INFINITY, f32::max),
+                top: corners
+                    .iter()
+                    .map(|(_, y)| *y)
+                    .fold(f32::NEG_INFINITY, f32::max),
+                font_size: state.font_size * matrix.vertical_scale(),
+            });
+        }
+
+        self.text_matrix = Matrix::translation(advance, 0.0).then(self.text_matrix);
+    }
+
+    fn execute(&mut self, operator: &str, operands: &[Object]) {
+        let number = |index: usize| {
+            operands
+                .get(index)
+                .and_then(|operand| operand.as_float().ok())
+                .unwrap_or(0.0)
+        };
+
+        match operator {
+            "q" => self.stack.push(self.state.clone()),
+            "Q" => {
+                if let Some(state) = self.stack.pop() {
+                    self.state = state;
+                }
+            }
+            "cm" => {
+                if let Some(matrix) = Matrix::from_operands(operands) {
+                    self.state.ctm = matrix.then(self.state.ctm);
+                }
+            }
+            "BT" => {
+                self.text_matrix = Matrix::IDENTITY;
+                self.line_matrix = Matrix::IDENTITY;
+            }
+            "Tf" => {
+                self.state.font = operands
+                    .first()
+                    .and_then(|name| name.as_name().ok())
+                    .map(ToOwned::to_owned);
+                self.state.font_size = number(1);
+            }
+            "Tc" => self.state.char_spacing = number(0),
+            "Tw" => self.state.word_spacing = number(0),
+            "Tz" => self.state.horizontal_scaling = number(0) / 100.0,
+            "TL" => self.state.leading = number(0),
+            "Ts" => self.state.rise = number(0),
+            "Td" => self.next_line(number(0), number(1)),
+            "TD" => {
+                self.state.leading = -number(1);
+                self.next_line(number(0), number(1));
+            }
+            "Tm" => {
+                if let Some(matrix) = Matrix::from_operands(operands) {
+                    self.text_matrix = matrix;
+                    self.line_matrix = matrix;
+                }
+            }
+            "T*" => self.next_line(0.0, -self.state.leading),
+            "Tj" => {
+                if let Some(Ok(bytes)) = operands.first().map(Object::as_str) {
+                    self.show(bytes);
+                }
+            }
+            "'" | "\"" => {
+                if operator == "\"" {
+                    self.state.word_spacing = number(0);
+                    self.state.char_spacing = number(1);
+                }
+                self.next_line(0.0, -self.state.leading);
+                if let Some(Ok(bytes)) = operands.last().map(Object::as_str) {
+                    self.show(bytes);
+                }
+            }
+            "TJ" => {
+                let Some(Ok(elements)) = operands.first().map(Object::as_array) else {
+                    return;
+                };
+                for element in elements {
+                    if let Object::String(bytes, _) = element {
+                        self.show(bytes);
+                    } else {
+                        let adjustment = element.as_float().unwrap_or(0.0);
+                        let offset = -adjustment / 1000.0
+                            * self.state.font_size
+                            * self.state.horizontal_scaling;
+                        self.text_matrix = Matrix::translation(offset, 0.0).then(self.text_matrix);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the text shown on a page in content stream order.
+///
+/// Text inside of form `XObject`s and annotations is not extracted.
+pub(super) fn text_runs(document: &Document, page: ObjectId) -> Result<Vec<TextRun>, lopdf::Error> {
+    let fonts = document
+        .get_page_fonts(page)?
+        .into_iter()
+        .map(|(name, font)| (name, Font::load(document, font)))
+        .collect();
+    let content = document.get_and_decode_page_content(page)?;
+
+    let mut interpreter = Interpreter {
+        fonts,
+        state: GraphicsState::default(),
+        stack: Vec::new(),
+        text_matrix: Matrix::IDENTITY,
+        line_matrix: Matrix::IDENTITY,
+        runs: Vec::new(),
+    };
+    for operation in &content.operations {
+        interpreter.execute(&operation.operator, &operation.operands);
+    }
+
+    Ok(interpreter.runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use lopdf::{StringFormat, dictionary};
+
+    use super::*;
+
+    fn simple_font() -> Font<'static> {
+        Font {
+            encoding: None,
+            composite: false,
+            first_char: 65,
+            widths: vec![600.0, 0.0],
+            cid_widths: BTreeMap::new(),
+            default_width: DEFAULT_WIDTH,
+        }
+    }
+
+    fn interpreter(fonts: BTreeMap<Vec<u8>, Font<'_>>) -> Interpreter<'_> {
+        Interpreter {
+            fonts,
+            state: GraphicsState::default(),
+            stack: Vec::new(),
+            text_matrix: Matrix::IDENTITY,
+            line_matrix: Matrix::IDENTITY,
+            runs: Vec::new(),
+        }
+    }
+
+    fn string(text: &str) -> Object {
+        Object::String(text.as_bytes().to_vec(), StringFormat::Literal)
+    }
+
+    #[test]
+    fn positions_text_runs() {
+        let mut interpreter = interpreter(BTreeMap::from([(b"F1".to_vec(), simple_font())]));
+        interpreter.execute("BT", &[]);
+        interpreter.execute("Tf", &[Object::Name(b"F1".to_vec()), Object::Integer(10)]);
+        interpreter.execute("Td", &[Object::Integer(100), Object::Integer(700)]);
+        interpreter.execute("Tj", &[string("AB")]);
+        interpreter.execute("Tj", &[string("C")]);
+
+        let [first, second] = interpreter.runs.as_slice() else {
+            panic!("expected two runs, got {:?}", interpreter.runs);
+        };
+        assert_eq!(first.text, "AB");
+        // `A` is 600 units wide, `B` has no width and falls back to the default width
+        assert!((first.left - 100.0).abs() < f32::EPSILON);
+        assert!((first.right - 111.0).abs() < f32::EPSILON);
+        assert!((first.bottom - 698.0).abs() < f32::EPSILON);
+        assert!((first.top - 708.0).abs() < f32::EPSILON);
+        assert!((first.font_size - 10.0).abs() < f32::EPSILON);
+        assert_eq!(second.text, "C");
+        assert!((second.left - 111.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn ignores_malformed_operators() {
+        assert_eq!(Matrix::from_operands(&vec![Object::Integer(1); 5]), None);
+        assert_eq!(
+            Matrix::from_operands(&[
+                Object::Integer(1),
+                Object::Integer(0),
+                Object::Integer(0),
+                Object::Integer(1),
+                Object::Null,
+                Object::Integer(0),
+            ]),
+            None
+        );
+
+        let mut interpreter = interpreter(BTreeMap::from([(b"F1".to_vec(), simple_font())]));
+        interpreter.execute("Q", &[]);
+        interpreter.execute("Tm", &[string("not a matrix")]);
+        interpreter.execute("Tj", &[string("no font selected")]);
+        interpreter.execute("Tf", &[Object::Name(b"F2".to_vec()), Object::Integer(10)]);
+        interpreter.execute("Tj", &[string("unknown font")]);
+        interpreter.execute("Tf", &[Object::Name(b"F1".to_vec()), Object::Integer(10)]);
+        interpreter.execute("Tj", &[Object::Integer(1)]);
+        interpreter.execute("TJ", &[string("not an array")]);
+        interpreter.execute("Tj", &[string("  ")]);
+        interpreter.execute("'", &[]);
+        interpreter.execute("Unknown", &[Object::Null]);
+
+        assert!(interpreter.runs.is_empty());
+        assert_eq!(interpreter.text_matrix, Matrix::IDENTITY);
+    }
+
+    #[test]
+    fn composite_widths() {
+        let document = Document::new();
+        let font = dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type0",
+            "DescendantFonts" => vec![Object::Dictionary(dictionary! {
+                "DW" => 900,
+                // A truncated `first last w` entry at the end is ignored
+                "W" => vec![
+                    Object::Integer(1),
+                    Object::Array(vec![Object::Integer(600), Object::Integer(700)]),
+                    Object::Integer(10),
+                    Object::Integer(12),
+                    Object::Integer(300),
+                    Object::Integer(20),
+                ],
+            })],
+        };
+        let font = Font::load(&document, &font);
+
+        assert!(font.composite);
+        assert_eq!(font.codes(&[0, 1, 0, 11, 7]).collect::<Vec<_>>(), [
+            1, 11, 7
+        ]);
+        for (code, width) in [(1, 600.0), (2, 700.0), (3, 900.0), (12, 300.0), (20, 900.0)] {
+            assert!((font.width(code) - width).abs() < f32::EPSILON, "{code}");
+        }
+    }
+}
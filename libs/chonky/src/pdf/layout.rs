@@ -0,0 +1,552 @@
+//! Reconstruction of lines, reading order and blocks from positioned text.
+//!
+//! All coordinates are in points with the origin in the top-left corner of the page.
+
+use core::ptr;
+
+use super::{BoundingBox, content::TextRun};
+use crate::table::{Table, TableFormat};
+
+/// Maximum horizontal distance between two runs of the same line, relative to the font size.
+const MAX_WORD_GAP: f32 = 1.0;
+/// Minimum horizontal distance between two runs to be separated by a space, relative to the font
+/// size.
+const MIN_SPACE_GAP: f32 = 0.15;
+/// Minimum vertical distance between two lines to start a new block, relative to the font size.
+const MIN_BLOCK_GAP: f32 = 0.4;
+/// Minimum width of the whitespace between two columns, relative to the font size.
+const MIN_GUTTER: f32 = 1.0;
+/// Minimum width of the whitespace between two columns of a table, relative to the font size.
+const MIN_TABLE_GUTTER: f32 = 0.5;
+/// Maximum vertical distance between two rows of a table, relative to the font size.
+const MAX_ROW_GAP: f32 = 1.5;
+/// Maximum average number of words of a cell of a table with two columns.
+///
+/// Two columns of running text are aligned the same way as a table with two columns, but their
+/// lines are longer than typical cells.
+const MAX_CELL_WORDS: f32 = 3.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Line {
+    pub text: String,
+    pub bounds: BoundingBox,
+    pub font_size: f32,
+}
+
+impl Line {
+    fn from_run(run: TextRun, page_height: f32) -> Self {
+        Self {
+            text: run.text,
+            bounds: BoundingBox {
+                left: run.left,
+                top: page_height - run.top,
+                right: run.right,
+                bottom: page_height - run.bottom,
+            },
+            font_size: run.font_size,
+        }
+    }
+
+    fn height(&self) -> f32 {
+        self.bounds.bottom - self.bounds.top
+    }
+
+    /// Returns `true` if `run` continues this line.
+    fn continues(&self, run: &Self) -> bool {
+        let overlap =
+            self.bounds.bottom.min(run.bounds.bottom) - self.bounds.top.max(run.bounds.top);
+        let gap = run.bounds.left - self.bounds.right;
+        let size = self.font_size.max(run.font_size);
+
+        overlap >= self.height().min(run.height()) * 0.5
+            && gap >= -MIN_SPACE_GAP * size
+            && gap <= MAX_WORD_GAP * size
+    }
+
+    fn push(&mut self, run: &Self) {
+        let gap = run.bounds.left - self.bounds.right;
+        if gap > MIN_SPACE_GAP * self.font_size.max(run.font_size)
+            && !self.text.ends_with(char::is_whitespace)
+            && !run.text.starts_with(char::is_whitespace)
+        {
+            self.text.push(' ');
+        }
+        self.text.push_str(&run.text);
+        self.bounds = self.bounds.union(run.bounds);
+        self.font_size = self.font_size.max(run.font_size);
+    }
+}
+
+/// Joins text runs into lines.
+pub(super) fn lines(runs: Vec<TextRun>, page_height: f32) -> Vec<Line> {
+    let mut runs = runs
+        .into_iter()
+        .map(|run| Line::from_run(run, page_height))
+        .collect::<Vec<_>>();
+    runs.sort_by(|lhs, rhs| {
+        lhs.bounds
+            .bottom
+            .total_cmp(&rhs.bounds.bottom)
+            .then(lhs.bounds.left.total_cmp(&rhs.bounds.left))
+    });
+
+    let mut lines = Vec::<Line>::new();
+    for run in runs {
+        match lines.iter_mut().rev().find(|line| line.continues(&run)) {
+            Some(line) => line.push(&run),
+            None => lines.push(run),
+        }
+    }
+
+    for line in &mut lines {
+        line.text = line.text.trim().to_owned();
+    }
+    lines
+}
+
+/// Returns the horizontal intervals inside of `left..right` which are not covered by `lines`.
+fn horizontal_gaps(lines: &[&Line], left: f32, right: f32, min_width: f32) -> Vec<(f32, f32)> {
+    let mut covered = lines
+        .iter()
+        .map(|line| (line.bounds.left, line.bounds.right))
+        .collect::<Vec<_>>();
+    covered.sort_by(|lhs, rhs| lhs.0.total_cmp(&rhs.0));
+
+    let mut gaps = Vec::new();
+    let mut position = left;
+    for (start, end) in covered {
+        if start - position >= min_width {
+            gaps.push((position, start));
+        }
+        position = position.max(end);
+    }
+    if right - position >= min_width {
+        gaps.push((position, right));
+    }
+    gaps
+}
+
+/// Splits `lines` at every horizontal position not covered by any line, from top to bottom.
+fn rows<'l>(lines: &[&'l Line]) -> Vec<Vec<&'l Line>> {
+    let mut lines = lines.to_vec();
+    lines.sort_by(|lhs, rhs| lhs.bounds.top.total_cmp(&rhs.bounds.top));
+
+    let mut rows = Vec::<Vec<&Line>>::new();
+    let mut bottom = f32::NEG_INFINITY;
+    for line in lines {
+        match rows.last_mut() {
+            Some(row) if line.bounds.top < bottom => row.push(line),
+            _ => rows.push(vec![line]),
+        }
+        bottom = bottom.max(line.bounds.bottom);
+    }
+    rows
+}
+
+fn x_range(lines: &[&Line]) -> (f32, f32) {
+    lines
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(left, right), line| {
+            (left.min(line.bounds.left), right.max(line.bounds.right))
+        })
+}
+
+/// A run of consecutive rows which share a column gutter.
+struct Region<'l> {
+    lines: Vec<&'l Line>,
+    gutter: Option<(f32, f32)>,
+    /// Whether any row has text on both sides of the gutter.
+    interior: bool,
+}
+
+/// Appends `lines` to `ordered` in reading order using a recursive XY-cut.
+///
+/// Columns are read from left to right and each column from top to bottom. A row of text spanning
+/// all columns, e.g. a title, ends the columns above it.
+fn cut<'l>(lines: &[&'l Line], min_gutter: f32, ordered: &mut Vec<&'l Line>) {
+    if lines.len() <= 1 {
+        ordered.extend(lines);
+        return;
+    }
+
+    let (left, right) = x_range(lines);
+
+    // Split into columns if a gutter runs through all lines
+    let columns = horizontal_gaps(lines, left, right, min_gutter);
+    if !columns.is_empty() {
+        let mut bounds = columns.iter().map(|(start, _)| *start).collect::<Vec<_>>();
+        bounds.push(f32::INFINITY);
+        for (index, bound) in bounds.iter().enumerate() {
+            let previous = index
+                .checked_sub(1)
+                .map_or(f32::NEG_INFINITY, |index| columns[index].1);
+            let column = lines
+                .iter()
+                .copied()
+                .filter(|line| line.bounds.left >= previous && line.bounds.right <= *bound)
+                .collect::<Vec<_>>();
+            cut(&column, min_gutter, ordered);
+        }
+        return;
+    }
+
+    // Otherwise, split into rows and group consecutive rows sharing a gutter into regions
+    let mut regions = Vec::<Region<'l>>::new();
+    for row in rows(lines) {
+        let gaps = horizontal_gaps(&row, left, right, min_gutter);
+        let (row_left, row_right) = x_range(&row);
+        let shared = regions.last().and_then(|region| {
+            let (start, end) = region.gutter?;
+            gaps.iter()
+                .map(|&(gap_start, gap_end)| (start.max(gap_start), end.min(gap_end)))
+                .find(|(start, end)| end - start >= min_gutter)
+        });
+
+        if let (Some(region), Some(gutter)) = (regions.last_mut(), shared) {
+            region.interior |= row_left < gutter.0 && row_right > gutter.1;
+            region.gutter = Some(gutter);
+            region.lines.extend(row);
+        } else {
+            let gutter = gaps
+                .iter()
+                .copied()
+                .max_by(|lhs, rhs| (lhs.1 - lhs.0).total_cmp(&(rhs.1 - rhs.0)));
+            regions.push(Region {
+                interior: gutter.is_some_and(|gutter| row_left < gutter.0 && row_right > gutter.1),
+                gutter,
+                lines: row,
+            });
+        }
+    }
+
+    if regions.len() == 1 {
+        let mut lines = lines.to_vec();
+        lines.sort_by(|lhs, rhs| {
+            lhs.bounds
+                .top
+                .total_cmp(&rhs.bounds.top)
+                .then(lhs.bounds.left.total_cmp(&rhs.bounds.left))
+        });
+        ordered.extend(lines);
+        return;
+    }
+
+    for region in regions {
+        if region.interior {
+            cut(&region.lines, min_gutter, ordered);
+        } else {
+            for row in rows(&region.lines) {
+                cut(&row, min_gutter, ordered);
+            }
+        }
+    }
+}
+
+/// Returns the horizontal extent of every column of a table consisting of `rows`.
+fn table_columns(rows: &[Vec<&Line>], min_gutter: f32) -> Vec<(f32, f32)> {
+    let lines = rows.concat();
+    let (left, right) = x_range(&lines);
+
+    let mut columns = Vec::new();
+    let mut start = left;
+    for (gap_start, gap_end) in horizontal_gaps(&lines, left, right, min_gutter) {
+        columns.push((start, gap_start));
+        start = gap_end;
+    }
+    columns.push((start, right));
+    columns
+}
+
+/// Returns `true` if `rows` form a table and not, for example, two columns of running text.
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "the number of words and cells is small"
+)]
+fn is_table(rows: &[Vec<&Line>], columns: usize) -> bool {
+    let cells = rows.iter().map(Vec::len).sum::<usize>();
+    let words = rows
+        .iter()
+        .flatten()
+        .map(|line| line.text.split_whitespace().count())
+        .sum::<usize>();
+
+    rows.len() >= 2
+        && columns >= 2
+        && (columns >= 3 || words as f32 <= cells as f32 * MAX_CELL_WORDS)
+}
+
+/// Creates a block from the lines of a table.
+fn table_block(rows: &[Vec<&Line>], columns: &[(f32, f32)]) -> Block {
+    let cells = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|&(left, right)| {
+                    let mut lines = row
+                        .iter()
+                        .filter(|line| line.bounds.left >= left && line.bounds.right <= right)
+                        .collect::<Vec<_>>();
+                    lines.sort_by(|lhs, rhs| lhs.bounds.left.total_cmp(&rhs.bounds.left));
+                    lines
+                        .iter()
+                        .map(|line| line.text.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect()
+        })
+        .collect();
+    let table = Table::new(cells, 0).with_detected_header();
+
+    let lines = rows.concat();
+    Block {
+        text: table.render(TableFormat::Markdown),
+        bounds: lines
+            .iter()
+            .map(|line| line.bounds)
+            .reduce(BoundingBox::union)
+            .unwrap_or(BoundingBox {
+                left: 0.0,
+                top: 0.0,
+                right: 0.0,
+                bottom: 0.0,
+            }),
+        font_size: lines.iter().map(|line| line.font_size).fold(0.0, f32::max),
+        lines: rows.len(),
+        table: Some(table),
+    }
+}
+
+/// Detects tables in `lines` and returns them as blocks together with all remaining lines.
+///
+/// A table is a run of at least two consecutive rows of at least two lines each, which are
+/// separated by the same gutters. Rows of a table are at most [`MAX_ROW_GAP`] apart.
+pub(super) fn tables(lines: &[Line]) -> (Vec<Block>, Vec<Line>) {
+    let size = body_font_size(
+        lines
+            .iter()
+            .map(|line| (line.font_size, line.text.as_str())),
+    )
+    .unwrap_or(10.0);
+    let min_gutter = size * MIN_TABLE_GUTTER;
+
+    // Runs of consecutive rows sharing the same columns
+    let mut runs = Vec::new();
+    let mut run = Vec::<Vec<&Line>>::new();
+    for row in rows(&lines.iter().collect::<Vec<_>>()) {
+        let continues = row.len() >= 2
+            && run.last().is_some_and(|last| {
+                let bottom = last
+                    .iter()
+                    .map(|line| line.bounds.bottom)
+                    .fold(f32::NEG_INFINITY, f32::max);
+                let top = row
+                    .iter()
+                    .map(|line| line.bounds.top)
+                    .fold(f32::INFINITY, f32::min);
+                top - bottom <= MAX_ROW_GAP * size && {
+                    let mut extended = run.clone();
+                    extended.push(row.clone());
+                    table_columns(&extended, min_gutter).len()
+                        == table_columns(&run, min_gutter).len()
+                }
+            });
+
+        if continues {
+            run.push(row);
+        } else {
+            runs.push(core::mem::take(&mut run));
+            if row.len() >= 2 {
+                run.push(row);
+            }
+        }
+    }
+    runs.push(run);
+
+    let mut blocks = Vec::new();
+    let mut table_lines = Vec::<&Line>::new();
+    for run in runs {
+        let columns = table_columns(&run, min_gutter);
+        if is_table(&run, columns.len()) {
+            blocks.push(table_block(&run, &columns));
+            table_lines.extend(run.into_iter().flatten());
+        }
+    }
+
+    let rest = lines
+        .iter()
+        .filter(|line| {
+            !table_lines
+                .iter()
+                .any(|table_line| ptr::eq(*table_line, *line))
+        })
+        .cloned()
+        .collect();
+    (blocks, rest)
+}
+
+/// Rounds a font size to half points to compensate for rounding errors.
+pub(super) fn round_font_size(size: f32) -> f32 {
+    (size * 2.0).round() / 2.0
+}
+
+/// Returns the font size most characters are set in from pairs of font sizes and texts.
+pub(super) fn body_font_size<'t>(text: impl IntoIterator<Item = (f32, &'t str)>) -> Option<f32> {
+    let mut sizes = Vec::<(f32, usize)>::new();
+    for (size, text) in text {
+        let size = round_font_size(size);
+        let count = text.chars().count();
+        match sizes
+            .iter_mut()
+            .find(|(known, _)| known.total_cmp(&size).is_eq())
+        {
+            Some((_, total)) => *total += count,
+            None => sizes.push((size, count)),
+        }
+    }
+    sizes
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(size, _)| size)
+}
+
+/// Returns `lines` in reading order.
+pub(super) fn reading_order(lines: &[Line]) -> Vec<&Line> {
+    let lines = lines.iter().collect::<Vec<_>>();
+    let min_gutter = body_font_size(
+        lines
+            .iter()
+            .map(|line| (line.font_size, line.text.as_str())),
+    )
+    .unwrap_or(10.0)
+        * MIN_GUTTER;
+
+    let mut ordered = Vec::with_capacity(lines.len());
+    cut(&lines, min_gutter, &mut ordered);
+    ordered
+}
+
+/// Lines of text forming a paragraph, a heading or a table.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Block {
+    pub text: String,
+    pub bounds: BoundingBox,
+    pub font_size: f32,
+    pub lines: usize,
+    pub table: Option<Table>,
+}
+
+impl Block {
+    fn starts_with(line: &Line) -> Self {
+        Self {
+            text: line.text.clone(),
+            bounds: line.bounds,
+            font_size: line.font_size,
+            lines: 1,
+            table: None,
+        }
+    }
+
+    /// Returns `true` if `line` continues the paragraph ending with `last`.
+    fn continues(last: &Line, line: &Line) -> bool {
+        let gap = line.bounds.top - last.bounds.bottom;
+        let overlaps = line.bounds.left < last.bounds.right && last.bounds.left < line.bounds.right;
+        let same_size = (line.font_size - last.font_size).abs() <= last.font_size * 0.1;
+
+        overlaps
+            && same_size
+            && gap >= -last.height() * 0.5
+            && gap <= MIN_BLOCK_GAP * last.font_size
+    }
+
+    fn push(&mut self, line: &Line) {
+        // Join words hyphenated at the end of a line
+        let hyphenated = self
+            .text
+            .strip_suffix('-')
+            .is_some_and(|text| text.ends_with(char::is_alphabetic))
+            && line.text.starts_with(char::is_lowercase);
+        if hyphenated {
+            self.text.pop();
+        } else {
+            self.text.push(' ');
+        }
+        self.text.push_str(&line.text);
+        self.bounds = self.bounds.union(line.bounds);
+        self.lines += 1;
+    }
+}
+
+/// Joins lines in reading order into blocks.
+pub(super) fn blocks(lines: &[&Line]) -> Vec<Block> {
+    let mut blocks = Vec::<Block>::new();
+    let mut last: Option<&Line> = None;
+    for line in lines {
+        match (blocks.last_mut(), last) {
+            (Some(block), Some(last)) if Block::continues(last, line) => block.push(line),
+            _ => blocks.push(Block::starts_with(line)),
+        }
+        last = Some(line);
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str, left: f32, top: f32) -> Line {
+        Line {
+            text: text.to_owned(),
+            bounds: BoundingBox {
+                left,
+                top,
+                right: left + 100.0,
+                bottom: top + 10.0,
+            },
+            font_size: 10.0,
+        }
+    }
+
+    #[test]
+    fn reads_columns_in_order() {
+        let lines = [
+            Line {
+                text: "title".to_owned(),
+                bounds: BoundingBox {
+                    left: 0.0,
+                    top: 0.0,
+                    right: 250.0,
+                    bottom: 20.0,
+                },
+                font_size: 20.0,
+            },
+            line("b1", 150.0, 40.0),
+            line("a1", 0.0, 40.0),
+            line("a2", 0.0, 52.0),
+            line("b2", 150.0, 52.0),
+            line("a3", 0.0, 74.0),
+            line("b3", 150.0, 74.0),
+            line("b4", 150.0, 86.0),
+            Line {
+                text: "footer".to_owned(),
+                bounds: BoundingBox {
+                    left: 0.0,
+                    top: 120.0,
+                    right: 250.0,
+                    bottom: 130.0,
+                },
+                font_size: 10.0,
+            },
+        ];
+
+        let ordered = reading_order(&lines)
+            .into_iter()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ordered, [
+            "title", "a1", "a2", "a3", "b1", "b2", "b3", "b4", "footer"
+        ]);
+    }
+}
@@ -0,0 +1,273 @@
+//! Extraction of segments from PDF documents.
+//!
+//! PDF files do not store paragraphs, only positioned runs of glyphs. The text runs of every page
+//! are joined into lines, the lines are put into reading order, which follows multi-column layouts
+//! column by column, and consecutive lines are joined into paragraphs. Blocks set in a larger font
+//! than the body text are extracted as headings. Rows of lines aligned to the same columns are
+//! extracted as tables.
+//!
+//! Every segment records the page it was extracted from in its [`PAGE_KEY`] metadata entry and its
+//! position on that page in its [`BOUNDING_BOX_KEY`] metadata entry, so chunks can be linked back
+//! to the region of the page they were created from.
+//!
+//! Extraction runs fully locally. It is geared towards documents containing text, scanned pages
+//! without a text layer do not yield any segments.
+#![expect(
+    clippy::float_arithmetic,
+    reason = "PDF coordinates are floating-point numbers"
+)]
+
+mod content;
+mod layout;
+
+use core::{fmt, str::FromStr};
+
+use error_stack::{Report, ResultExt, bail};
+use lopdf::{Document, Object, ObjectId};
+
+use self::layout::Block;
+use crate::{extraction::Extraction, segment::SegmentKind};
+
+/// Metadata key of the page number a segment was extracted from, starting at `1`.
+pub const PAGE_KEY: &str = "page";
+
+/// Metadata key of the [`BoundingBox`] of a segment on its page.
+pub const BOUNDING_BOX_KEY: &str = "bounding_box";
+
+/// Metadata key of the size of the page a segment was extracted from as `"width height"` in
+/// points.
+pub const PAGE_SIZE_KEY: &str = "page_size";
+
+/// Minimum font size of a heading, relative to the font size of the body text.
+const MIN_HEADING_SCALE: f32 = 1.15;
+/// Maximum number of lines of a heading.
+const MAX_HEADING_LINES: usize = 3;
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not extract text from PDF document")]
+pub struct PdfError;
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not parse bounding box")]
+pub struct ParseBoundingBoxError;
+
+/// A rectangle on a page in points, with the origin in the top-left corner of the page.
+///
+/// Bounding boxes are stored in segment metadata as `"left top right bottom"`.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::pdf::BoundingBox;
+///
+/// let bounding_box = BoundingBox {
+///     left: 72.0,
+///     top: 90.5,
+///     right: 540.0,
+///     bottom: 130.25,
+/// };
+/// assert_eq!(bounding_box.to_string(), "72.00 90.50 540.00 130.25");
+/// assert_eq!(
+///     "72.00 90.50 540.00 130.25"
+///         .parse::<BoundingBox>()
+///         .expect("valid bounding box"),
+///     bounding_box
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoundingBox {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl BoundingBox {
+    /// Returns the smallest bounding box containing both `self` and `other`.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+}
+
+impl fmt::Display for BoundingBox {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "{:.2} {:.2} {:.2} {:.2}",
+            self.left, self.top, self.right, self.bottom
+        )
+    }
+}
+
+impl FromStr for BoundingBox {
+    type Err = Report<ParseBoundingBoxError>;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut coordinates = value.split_whitespace().map(f32::from_str);
+        let mut next = || {
+            coordinates
+                .next()
+                .ok_or_else(|| Report::new(ParseBoundingBoxError))?
+                .change_context(ParseBoundingBoxError)
+        };
+
+        let bounding_box = Self {
+            left: next()?,
+            top: next()?,
+            right: next()?,
+            bottom: next()?,
+        };
+        if coordinates.next().is_some() {
+            bail!(
+                Report::new(ParseBoundingBoxError)
+                    .attach_printable("expected exactly four coordinates")
+            );
+        }
+        Ok(bounding_box)
+    }
+}
+
+/// Returns the size of a page from its (possibly inherited) media box.
+fn page_size(document: &Document, page: ObjectId) -> Option<(f32, f32)> {
+    let mut node = document.get_dictionary(page).ok()?;
+    loop {
+        if let Ok(media_box) = node
+            .get_deref(b"MediaBox", document)
+            .and_then(Object::as_array)
+        {
+            let values = media_box
+                .iter()
+                .map(|value| value.as_float().ok())
+                .collect::<Option<Vec<_>>>()?;
+            let [left, bottom, right, top] = values.as_slice() else {
+                return None;
+            };
+            return Some((right - left, top - bottom));
+        }
+        node = node
+            .get_deref(b"Parent", document)
+            .and_then(Object::as_dict)
+            .ok()?;
+    }
+}
+
+struct Page {
+    number: u32,
+    width: f32,
+    height: f32,
+    blocks: Vec<Block>,
+}
+
+/// Extracts the text of a PDF document.
+///
+/// Headings, paragraphs and tables are extracted in reading order, page by page. Lines are joined
+/// with a single space and words hyphenated at the end of a line are joined again. Tables are
+/// extracted as Markdown tables, with one row per row of lines and one column per run of
+/// horizontally aligned lines. A header row is detected with [`Table::with_detected_header`].
+///
+/// [`Table::with_detected_header`]: crate::table::Table::with_detected_header
+///
+/// Glyph widths are taken from the fonts embedded in the document. Fonts without width
+/// information, e.g. non-embedded standard fonts, use an average glyph width, which makes
+/// bounding boxes less precise.
+///
+/// # Errors
+///
+/// Returns [`PdfError`] if the source is not a valid PDF document, if the document is encrypted,
+/// or if the content of a page could not be decoded.
+pub fn extract(source: &[u8]) -> Result<Extraction, Report<PdfError>> {
+    let document = Document::load_mem(source).change_context(PdfError)?;
+    if document.is_encrypted() {
+        bail!(Report::new(PdfError).attach_printable("the document is encrypted"));
+    }
+
+    let mut pages = Vec::new();
+    for (number, page) in document.get_pages() {
+        let (width, height) = page_size(&document, page).unwrap_or((612.0, 792.0));
+        let runs = content::text_runs(&document, page)
+            .change_context(PdfError)
+            .attach_printable_lazy(|| format!("page {number}"))?;
+
+        let (tables, lines) = layout::tables(&layout::lines(runs, height));
+        let mut blocks = layout::blocks(&layout::reading_order(&lines));
+        // Tables are placed before the first block below them
+        for table in tables {
+            let position = blocks
+                .iter()
+                .position(|block| {
+                    block.bounds.top > table.bounds.top
+                        && block.bounds.left.max(table.bounds.left)
+                            < block.bounds.right.min(table.bounds.right)
+                })
+                .unwrap_or(blocks.len());
+            blocks.insert(position, table);
+        }
+
+        pages.push(Page {
+            number,
+            width,
+            height,
+            blocks,
+        });
+    }
+
+    let blocks = pages.iter().flat_map(|page| &page.blocks);
+    let body_size = layout::body_font_size(
+        blocks
+            .clone()
+            .map(|block| (block.font_size, block.text.as_str())),
+    )
+    .unwrap_or(0.0);
+    let is_heading = |block: &Block| {
+        block.table.is_none()
+            && block.font_size >= body_size * MIN_HEADING_SCALE
+            && block.lines <= MAX_HEADING_LINES
+    };
+
+    // Larger fonts map to higher levels, i.e. smaller level numbers
+    let mut heading_sizes = blocks
+        .filter(|block| is_heading(block))
+        .map(|block| layout::round_font_size(block.font_size))
+        .collect::<Vec<_>>();
+    heading_sizes.sort_by(|lhs, rhs| rhs.total_cmp(lhs));
+    heading_sizes.dedup();
+
+    let mut extraction = Extraction::new();
+    for page in &pages {
+        for block in &page.blocks {
+            let kind = match &block.table {
+                Some(table) => SegmentKind::Table(table.clone()),
+                None if is_heading(block) => {
+                    let size = layout::round_font_size(block.font_size);
+                    let level = heading_sizes
+                        .iter()
+                        .position(|heading_size| heading_size.total_cmp(&size).is_eq())
+                        .unwrap_or_default();
+                    SegmentKind::Heading {
+                        level: u8::try_from(level + 1).unwrap_or(u8::MAX).min(6),
+                    }
+                }
+                None => SegmentKind::Paragraph,
+            };
+
+            if let Some(segment) = extraction.push(kind, &block.text) {
+                segment.metadata.insert(PAGE_KEY, page.number.to_string());
+                segment
+                    .metadata
+                    .insert(BOUNDING_BOX_KEY, block.bounds.to_string());
+                segment.metadata.insert(
+                    PAGE_SIZE_KEY,
+                    format!("{:.2} {:.2}", page.width, page.height),
+                );
+            }
+        }
+    }
+
+    Ok(extraction)
+}
@@ -0,0 +1,127 @@
+//! Parsing of delimiter-separated values.
+
+/// Delimiters detected by [`sniff_delimiter`] in order of preference.
+const DELIMITERS: &[char] = &[',', '\t', ';', '|'];
+
+/// Number of lines inspected to detect the delimiter.
+const SNIFF_LINES: usize = 16;
+
+/// Counts the occurrences of `delimiter` in `line` outside of quoted fields.
+fn count_delimiters(line: &str, delimiter: char) -> usize {
+    let mut quoted = false;
+    let mut count = 0;
+    for char in line.chars() {
+        if char == '"' {
+            quoted = !quoted;
+        } else if char == delimiter && !quoted {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Detects the delimiter of `source`, falling back to a comma.
+///
+/// The delimiter is the candidate occurring the same number of times in each of the first lines.
+/// If no candidate occurs consistently, the one occurring most often in the first line is chosen.
+pub(super) fn sniff_delimiter(source: &str) -> char {
+    let lines = source
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(SNIFF_LINES)
+        .collect::<Vec<_>>();
+
+    // `max_by_key` returns the last maximum, so candidates are visited in reverse to prefer
+    // earlier ones
+    DELIMITERS
+        .iter()
+        .rev()
+        .filter_map(|&delimiter| {
+            let counts = lines
+                .iter()
+                .map(|line| count_delimiters(line, delimiter))
+                .collect::<Vec<_>>();
+            let first = *counts.first()?;
+            (first > 0).then(|| {
+                let consistent = counts.iter().all(|&count| count == first);
+                (consistent, first, delimiter)
+            })
+        })
+        .max_by_key(|&(consistent, count, _)| (consistent, count))
+        .map_or(',', |(_, _, delimiter)| delimiter)
+}
+
+/// Splits `source` into rows of fields as described in [RFC 4180].
+///
+/// Fields may be quoted with `"`, quoted fields may contain delimiters, line breaks and quotes
+/// escaped as `""`. Empty lines are skipped.
+///
+/// [RFC 4180]: https://www.rfc-editor.org/rfc/rfc4180
+pub(super) fn parse(source: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+
+    let mut chars = source.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(char) = chars.next() {
+        if quoted {
+            if char != '"' {
+                field.push(char);
+            } else if chars.next_if_eq(&'"').is_some() {
+                field.push('"');
+            } else {
+                quoted = false;
+            }
+        } else if char == '"' {
+            quoted = true;
+        } else if char == delimiter {
+            row.push(core::mem::take(&mut field));
+        } else if char == '\n' || char == '\r' {
+            if char == '\r' {
+                chars.next_if_eq(&'\n');
+            }
+            if !row.is_empty() || !field.is_empty() {
+                row.push(core::mem::take(&mut field));
+                rows.push(core::mem::take(&mut row));
+            }
+        } else {
+            field.push(char);
+        }
+    }
+    if !row.is_empty() || !field.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_delimiters() {
+        assert_eq!(sniff_delimiter("a,b,c\n1,2,3\n"), ',');
+        assert_eq!(sniff_delimiter("a\tb\n\"1,5\"\t2\n"), '\t');
+        assert_eq!(sniff_delimiter("name;price\nTea, green;3,50\n"), ';');
+        assert_eq!(sniff_delimiter("a|b\n1|2"), '|');
+        assert_eq!(sniff_delimiter("single column\nvalue"), ',');
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        assert_eq!(
+            parse(
+                "\u{feff}name,quote\r\nAda,\"Said \"\"hi\"\",\nthen left\"\r\n\r\nCharles,\n",
+                ','
+            ),
+            [
+                vec!["name", "quote"],
+                vec!["Ada", "Said \"hi\",\nthen left"],
+                vec!["Charles", ""],
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Rendering of JSON values as key/value text.
+
+use core::fmt::Write as _;
+
+use serde_json::Value as JsonValue;
+
+use super::path::{JsonPath, PathElement};
+
+/// Returns `true` if `value` is an object or array which can be split into its members.
+pub(super) fn has_members(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Object(object) => !object.is_empty(),
+        JsonValue::Array(array) => !array.is_empty(),
+        JsonValue::Null | JsonValue::Bool(_) | JsonValue::Number(_) | JsonValue::String(_) => false,
+    }
+}
+
+/// Returns the name a record at `path` is rendered with, e.g. `tags[2]` for `/people/0/tags/2`.
+///
+/// The name is the last key of the path followed by all indices after it.
+pub(super) fn name(path: &JsonPath) -> String {
+    let mut name = String::new();
+    for element in path.elements() {
+        match element {
+            PathElement::Key(key) => key.clone_into(&mut name),
+            PathElement::Index(index) => {
+                if !name.is_empty() {
+                    write!(name, "[{index}]").expect("writing to a string is infallible");
+                }
+            }
+        }
+    }
+    name
+}
+
+fn flatten(value: &JsonValue, key: &str, lines: &mut Vec<String>) {
+    match value {
+        JsonValue::Object(object) if !object.is_empty() => {
+            for (member, value) in object {
+                if key.is_empty() {
+                    flatten(value, member, lines);
+                } else {
+                    flatten(value, &format!("{key}.{member}"), lines);
+                }
+            }
+        }
+        JsonValue::Array(array) if !array.is_empty() => {
+            for (index, value) in array.iter().enumerate() {
+                flatten(value, &format!("{key}[{index}]"), lines);
+            }
+        }
+        _ => {
+            let value = match value {
+                JsonValue::String(string) => string.clone(),
+                other => other.to_string(),
+            };
+            if key.is_empty() {
+                lines.push(value);
+            } else {
+                lines.push(format!("{key}: {value}"));
+            }
+        }
+    }
+}
+
+/// Renders `value` as one `key: value` line per scalar, prefixing all keys with `name`.
+///
+/// Keys of nested objects are joined by `.`, array indices are appended in brackets, e.g.
+/// `address.lines[0]: 1 Main Street`.
+pub(super) fn render(value: &JsonValue, name: &str) -> String {
+    let mut lines = Vec::new();
+    flatten(value, name, &mut lines);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn renders_values() {
+        let value = json!({
+            "name": "Ada",
+            "address": { "city": "London", "lines": ["1 Main Street"] },
+            "tags": [],
+            "age": 36,
+        });
+
+        assert_eq!(
+            render(&value, ""),
+            "address.city: London\naddress.lines[0]: 1 Main Street\nage: 36\nname: Ada\ntags: []"
+        );
+        assert_eq!(render(&value["age"], "age"), "age: 36");
+        assert_eq!(render(&json!("plain"), ""), "plain");
+    }
+
+    #[test]
+    fn names_records() {
+        let path = JsonPath::new().join("people").join(0).join("tags").join(2);
+        assert_eq!(name(&path), "tags[2]");
+        assert_eq!(name(&JsonPath::new().join(3)), "");
+        assert_eq!(name(&JsonPath::new()), "");
+    }
+}
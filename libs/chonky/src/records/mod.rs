@@ -0,0 +1,445 @@
+//! Chunking of structured data: CSV, JSON and JSON Lines.
+//!
+//! Structured data is split into records, which are rendered as `key: value` text. Every row of a
+//! CSV file is a record whose keys are the column names from the header row. JSON documents and
+//! every line of a JSON Lines file form a single record if they fit into the token budget,
+//! otherwise they are split into their members, recursively, until every record fits.
+//!
+//! Records point back to their source: every segment and chunk records the rows of a CSV file or
+//! the lines of a JSON Lines file it covers in its [`ROWS_KEY`] metadata entry, and the location
+//! inside of a JSON value as [`JsonPath`] in its [`JSON_POINTER_KEY`] and [`PROPERTY_PATH_KEY`]
+//! metadata entries.
+
+mod csv;
+mod json;
+mod path;
+
+use core::ops::Range;
+
+use error_stack::{Report, ResultExt};
+use serde_json::Value as JsonValue;
+
+pub use self::path::{JsonPath, JsonPathError, PathElement};
+use crate::{
+    chunk::Chunk,
+    document::Metadata,
+    extraction::Extraction,
+    segment::{Segment, SegmentKind},
+    table::{Table, TableFormat},
+    tokenizer::Tokenizer,
+};
+
+/// Metadata key of the rows of a CSV file or the lines of a JSON Lines file a segment or chunk
+/// covers as `"first-last"`, starting at `1`.
+///
+/// The header row of a CSV file is row `1`, empty lines are not counted.
+pub const ROWS_KEY: &str = "rows";
+
+/// Metadata key of the location of a record inside of a JSON value as JSON Pointer, e.g.
+/// `/people/0`.
+///
+/// Chunks covering several records carry the location of the value containing all of them.
+pub const JSON_POINTER_KEY: &str = "json_pointer";
+
+/// Metadata key of the location of a record inside of a JSON value as JSON array, e.g.
+/// `["people",0]`, see [`JsonPath::to_property_path`].
+pub const PROPERTY_PATH_KEY: &str = "property_path";
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not chunk structured data")]
+pub struct RecordError;
+
+/// A format of structured data supported by the [`RecordChunker`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RecordFormat {
+    /// Delimiter-separated values with a header row, e.g. CSV or TSV.
+    ///
+    /// The delimiter is detected from the content.
+    Csv,
+    Json,
+    /// One JSON value per line.
+    JsonLines,
+}
+
+impl RecordFormat {
+    /// Returns the format of files with the given extension, without the leading dot.
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" | "tsv" | "tab" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "jsonl" | "ndjson" => Some(Self::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+/// The location of a record in its source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    rows: Option<Range<usize>>,
+    path: Option<JsonPath>,
+}
+
+impl Location {
+    fn of(segment: &Segment) -> Self {
+        #[expect(
+            clippy::range_plus_one,
+            reason = "`Location::rows` is a half-open range"
+        )]
+        let rows = segment.metadata.get(ROWS_KEY).and_then(|rows| {
+            let (first, last) = rows.split_once('-')?;
+            Some(first.parse().ok()?..last.parse::<usize>().ok()? + 1)
+        });
+        let path = segment
+            .metadata
+            .get(JSON_POINTER_KEY)
+            .and_then(|pointer| pointer.parse().ok());
+        Self { rows, path }
+    }
+
+    /// Returns the key of the group of records this record may be packed with.
+    ///
+    /// Records of the top level of a source, e.g. the rows of a CSV file or the lines of a JSON
+    /// Lines file, may be packed together. Records split from a value are only packed with
+    /// other members of the same value.
+    fn group(&self) -> Option<(Option<&Range<usize>>, JsonPath)> {
+        let path = self.path.as_ref().filter(|path| !path.is_empty())?;
+        Some((self.rows.as_ref(), path.parent().unwrap_or_default()))
+    }
+
+    fn annotate(&self, metadata: &mut Metadata) {
+        if let Some(rows) = &self.rows {
+            metadata.insert(ROWS_KEY, format!("{}-{}", rows.start, rows.end - 1));
+        }
+        if let Some(path) = &self.path {
+            metadata.insert(JSON_POINTER_KEY, path.to_string());
+            metadata.insert(PROPERTY_PATH_KEY, path.to_property_path());
+        }
+    }
+}
+
+/// Splits structured data into records and packs consecutive records into chunks.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::{
+///     records::{JSON_POINTER_KEY, RecordChunker, RecordFormat},
+///     tokenizer::HeuristicTokenizer,
+/// };
+///
+/// let source = r#"{
+///     "service": "graph",
+///     "ports": [4000, 4001, 4002, 4003, 4004, 4005, 4006, 4007, 4008, 4009]
+/// }"#;
+///
+/// let chunker = RecordChunker::new(HeuristicTokenizer::default(), 24);
+/// let extraction = chunker.extract(source.as_bytes(), RecordFormat::Json)?;
+/// let chunks = chunker.chunk(extraction.text(), extraction.segments())?;
+///
+/// let pointers = chunks
+///     .iter()
+///     .map(|chunk| chunk.metadata.get(JSON_POINTER_KEY).unwrap_or_default())
+///     .collect::<Vec<_>>();
+/// assert_eq!(pointers, ["/ports", "/ports", "/service"]);
+/// assert!(
+///     chunks[0]
+///         .text
+///         .starts_with("ports[0]: 4000\n\nports[1]: 4001")
+/// );
+/// # Ok::<(), error_stack::Report<chonky::records::RecordError>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecordChunker<T> {
+    tokenizer: T,
+    max_tokens: usize,
+}
+
+impl<T> RecordChunker<T>
+where
+    T: Tokenizer,
+{
+    /// Creates a chunker which creates chunks of at most `max_tokens` tokens.
+    ///
+    /// Records exceeding the budget which cannot be split further, such as a long CSV row or a
+    /// long string, form a chunk exceeding the budget.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tokens` is zero.
+    #[must_use]
+    pub fn new(tokenizer: T, max_tokens: usize) -> Self {
+        assert!(max_tokens > 0, "the token budget has to be positive");
+
+        Self {
+            tokenizer,
+            max_tokens,
+        }
+    }
+
+    pub const fn tokenizer(&self) -> &T {
+        &self.tokenizer
+    }
+
+    pub const fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+
+    fn push(extraction: &mut Extraction, text: &str, location: &Location) {
+        if let Some(segment) = extraction.push(SegmentKind::Text, text) {
+            location.annotate(&mut segment.metadata);
+        }
+    }
+
+    /// Splits `value` into records within the token budget.
+    fn push_json(
+        &self,
+        extraction: &mut Extraction,
+        value: &JsonValue,
+        path: JsonPath,
+        rows: Option<Range<usize>>,
+    ) {
+        let text = json::render(value, &json::name(&path));
+        if !json::has_members(value) || self.tokenizer.count_tokens(&text) <= self.max_tokens {
+            Self::push(extraction, &text, &Location {
+                rows,
+                path: Some(path),
+            });
+            return;
+        }
+
+        match value {
+            JsonValue::Object(object) => {
+                for (key, member) in object {
+                    self.push_json(extraction, member, path.join(key.as_str()), rows.clone());
+                }
+            }
+            JsonValue::Array(array) => {
+                for (index, item) in array.iter().enumerate() {
+                    self.push_json(extraction, item, path.join(index), rows.clone());
+                }
+            }
+            JsonValue::Null | JsonValue::Bool(_) | JsonValue::Number(_) | JsonValue::String(_) => {}
+        }
+    }
+
+    /// Splits `source` into records.
+    ///
+    /// Every record forms a [`SegmentKind::Text`] segment annotated with its location, see the
+    /// [module documentation](self).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecordError`] if `source` is not valid UTF-8 or not valid JSON.
+    pub fn extract(
+        &self,
+        source: &[u8],
+        format: RecordFormat,
+    ) -> Result<Extraction, Report<RecordError>> {
+        let source = core::str::from_utf8(source)
+            .change_context(RecordError)
+            .attach_printable("the source is not valid UTF-8")?;
+
+        let mut extraction = Extraction::new();
+        match format {
+            RecordFormat::Csv => {
+                let table = Table::new(csv::parse(source, csv::sniff_delimiter(source)), 1);
+                for index in 0..table.body().len() {
+                    // Body rows follow the header row
+                    let row = index + 2;
+                    #[expect(
+                        clippy::range_plus_one,
+                        reason = "`Location::rows` is a half-open range"
+                    )]
+                    Self::push(
+                        &mut extraction,
+                        &table.render_row(TableFormat::Records, index),
+                        &Location {
+                            rows: Some(row..row + 1),
+                            path: None,
+                        },
+                    );
+                }
+            }
+            RecordFormat::Json => {
+                let value = serde_json::from_str(source).change_context(RecordError)?;
+                self.push_json(&mut extraction, &value, JsonPath::new(), None);
+            }
+            RecordFormat::JsonLines => {
+                for (index, line) in source.lines().enumerate() {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let value = serde_json::from_str(line)
+                        .change_context(RecordError)
+                        .attach_printable_lazy(|| format!("line {}", index + 1))?;
+                    self.push_json(
+                        &mut extraction,
+                        &value,
+                        JsonPath::new(),
+                        Some(index + 1..index + 2),
+                    );
+                }
+            }
+        }
+
+        Ok(extraction)
+    }
+
+    /// Packs consecutive records of `text` into chunks of at most [`max_tokens`] tokens.
+    ///
+    /// `segments` have to be created by [`extract`]. Records of the top level of the source are
+    /// packed together, records split from a larger value only with other members of the same
+    /// value. Every chunk is annotated with the rows and the JSON path covering all of its
+    /// records.
+    ///
+    /// [`max_tokens`]: Self::max_tokens
+    /// [`extract`]: Self::extract
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecordError`] if a segment does not refer to a valid region of `text`.
+    pub fn chunk(
+        &self,
+        text: &str,
+        segments: &[Segment],
+    ) -> Result<Vec<Chunk>, Report<RecordError>> {
+        let locations = segments.iter().map(Location::of).collect::<Vec<_>>();
+        let tokens = segments
+            .iter()
+            .map(|segment| {
+                segment
+                    .text(text)
+                    .map(|record| self.tokenizer.count_tokens(record))
+                    .change_context(RecordError)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while start < segments.len() {
+            let group = locations[start].group();
+            let mut end = start + 1;
+            let mut chunk_tokens = tokens[start];
+            while end < segments.len()
+                && locations[end].group() == group
+                && chunk_tokens + tokens[end] <= self.max_tokens
+            {
+                chunk_tokens += tokens[end];
+                end += 1;
+            }
+
+            let mut chunk =
+                Chunk::from_segments(text, segments, start..end).change_context(RecordError)?;
+            let covered = &locations[start..end];
+            Location {
+                rows: covered
+                    .iter()
+                    .filter_map(|location| location.rows.clone())
+                    .reduce(|rows, other| rows.start.min(other.start)..rows.end.max(other.end)),
+                path: covered
+                    .iter()
+                    .filter_map(|location| location.path.clone())
+                    .reduce(|path, other| path.common_prefix(&other)),
+            }
+            .annotate(&mut chunk.metadata);
+            chunks.push(chunk);
+            start = end;
+        }
+
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::HeuristicTokenizer;
+
+    fn locations(chunks: &[Chunk]) -> Vec<(&str, &str)> {
+        chunks
+            .iter()
+            .map(|chunk| {
+                (
+                    chunk.metadata.get(ROWS_KEY).unwrap_or_default(),
+                    chunk.metadata.get(JSON_POINTER_KEY).unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    fn chunk(max_tokens: usize, source: &str, format: RecordFormat) -> Vec<Chunk> {
+        let chunker = RecordChunker::new(HeuristicTokenizer::default(), max_tokens);
+        let extraction = chunker
+            .extract(source.as_bytes(), format)
+            .expect("could be extracted");
+        chunker
+            .chunk(extraction.text(), extraction.segments())
+            .expect("could be chunked")
+    }
+
+    #[test]
+    fn chunks_csv_rows() {
+        let source = "name\tcity\nAda\tLondon\nCharles\tLondon\nMary\tCambridge\n";
+        let chunks = chunk(20, source, RecordFormat::Csv);
+
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.text.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "name: Ada; city: London\n\nname: Charles; city: London",
+                "name: Mary; city: Cambridge"
+            ]
+        );
+        assert_eq!(locations(&chunks), [("2-3", ""), ("4-4", "")]);
+    }
+
+    #[test]
+    fn splits_json_values() {
+        let source = r#"[
+            {"name": "Ada", "born": 1815},
+            {"name": "Charles", "born": 1791, "inventions": ["difference engine", "analytical engine", "cowcatcher"]},
+            {"name": "Mary", "born": 1780}
+        ]"#;
+        let chunks = chunk(16, source, RecordFormat::Json);
+
+        assert_eq!(locations(&chunks), [
+            ("", "/0"),
+            ("", "/1/born"),
+            ("", "/1/inventions/0"),
+            ("", "/1/inventions"),
+            ("", "/1/name"),
+            ("", "/2")
+        ]);
+        assert_eq!(
+            chunks[3].text,
+            "inventions[1]: analytical engine\n\ninventions[2]: cowcatcher"
+        );
+        assert_eq!(
+            chunks[3].metadata.get(PROPERTY_PATH_KEY),
+            Some(r#"[1,"inventions"]"#)
+        );
+    }
+
+    #[test]
+    fn chunks_json_lines() {
+        let source = "{\"id\": 1}\n\n{\"id\": 2}\n{\"id\": 3, \"note\": \"A rather long note \
+                      which does not fit into the budget.\"}\n";
+        let chunks = chunk(12, source, RecordFormat::JsonLines);
+
+        assert_eq!(locations(&chunks), [
+            ("1-3", ""),
+            ("4-4", "/id"),
+            ("4-4", "/note")
+        ]);
+        assert_eq!(chunks[0].text, "id: 1\n\nid: 2");
+
+        let error = RecordChunker::new(HeuristicTokenizer::default(), 12)
+            .extract(b"{}\n{", RecordFormat::JsonLines)
+            .expect_err("invalid JSON");
+        assert!(format!("{error:?}").contains("line 2"));
+    }
+}
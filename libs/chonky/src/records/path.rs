@@ -0,0 +1,241 @@
+use core::{fmt, str::FromStr};
+
+use error_stack::{Report, bail};
+use serde_json::Value as JsonValue;
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not parse JSON pointer")]
+pub struct JsonPathError;
+
+/// An element of a [`JsonPath`], the key of an object member or the index of an array item.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PathElement {
+    Key(String),
+    Index(usize),
+}
+
+impl From<usize> for PathElement {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl From<String> for PathElement {
+    fn from(key: String) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<&str> for PathElement {
+    fn from(key: &str) -> Self {
+        Self::Key(key.to_owned())
+    }
+}
+
+/// The location of a value inside of a JSON document.
+///
+/// Paths are formatted and parsed as [JSON Pointers], e.g. `/people/0/name`. When parsing, path
+/// elements consisting of digits only are read as array indices.
+///
+/// The elements of a path mirror the elements of a `PropertyPath` of the HASH graph, and
+/// [`to_property_path`] serializes a path in the same format. Paths into the properties of an
+/// entity, whose keys are base URLs, can be deserialized as `PropertyPath` directly.
+///
+/// [JSON Pointers]: https://www.rfc-editor.org/rfc/rfc6901
+/// [`to_property_path`]: Self::to_property_path
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::records::{JsonPath, PathElement};
+///
+/// let path = "/people/0/a~1b".parse::<JsonPath>()?;
+/// assert_eq!(path.elements(), [
+///     PathElement::Key("people".to_owned()),
+///     PathElement::Index(0),
+///     PathElement::Key("a/b".to_owned()),
+/// ]);
+/// assert_eq!(path.to_string(), "/people/0/a~1b");
+/// assert_eq!(path.to_property_path(), r#"["people",0,"a/b"]"#);
+/// # Ok::<(), error_stack::Report<chonky::records::JsonPathError>>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JsonPath {
+    elements: Vec<PathElement>,
+}
+
+impl JsonPath {
+    /// Creates the path of the root of a document.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            elements: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn elements(&self) -> &[PathElement] {
+        &self.elements
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns `true` for the path of the root of a document.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn push(&mut self, element: impl Into<PathElement>) {
+        self.elements.push(element.into());
+    }
+
+    /// Returns the path of the child `element` of the value at this path.
+    #[must_use]
+    pub fn join(&self, element: impl Into<PathElement>) -> Self {
+        let mut path = self.clone();
+        path.push(element);
+        path
+    }
+
+    /// Returns the path of the value containing the value at this path, `None` for the root.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.elements.split_last()?;
+        Some(Self {
+            elements: parent.to_vec(),
+        })
+    }
+
+    #[must_use]
+    pub fn starts_with(&self, other: &Self) -> bool {
+        self.elements.starts_with(&other.elements)
+    }
+
+    /// Returns the longest path both paths start with.
+    #[must_use]
+    pub fn common_prefix(&self, other: &Self) -> Self {
+        self.elements
+            .iter()
+            .zip(&other.elements)
+            .take_while(|(element, other)| element == other)
+            .map(|(element, _)| element.clone())
+            .collect()
+    }
+
+    /// Serializes the path as JSON array of keys and indices, the format of a `PropertyPath`.
+    #[must_use]
+    pub fn to_property_path(&self) -> String {
+        JsonValue::Array(
+            self.elements
+                .iter()
+                .map(|element| match element {
+                    PathElement::Key(key) => JsonValue::from(key.as_str()),
+                    PathElement::Index(index) => JsonValue::from(*index),
+                })
+                .collect(),
+        )
+        .to_string()
+    }
+}
+
+impl FromIterator<PathElement> for JsonPath {
+    fn from_iter<T: IntoIterator<Item = PathElement>>(iter: T) -> Self {
+        Self {
+            elements: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for element in &self.elements {
+            match element {
+                PathElement::Key(key) => {
+                    write!(fmt, "/{}", key.replace('~', "~0").replace('/', "~1"))?;
+                }
+                PathElement::Index(index) => write!(fmt, "/{index}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = Report<JsonPathError>;
+
+    fn from_str(pointer: &str) -> Result<Self, Self::Err> {
+        if pointer.is_empty() {
+            return Ok(Self::new());
+        }
+        let Some(pointer) = pointer.strip_prefix('/') else {
+            bail!(Report::new(JsonPathError).attach_printable("a JSON pointer starts with `/`"));
+        };
+
+        pointer
+            .split('/')
+            .map(|element| {
+                let is_index = !element.is_empty()
+                    && element.bytes().all(|byte| byte.is_ascii_digit())
+                    && (element == "0" || !element.starts_with('0'));
+                if is_index {
+                    if let Ok(index) = element.parse() {
+                        return Ok(PathElement::Index(index));
+                    }
+                }
+
+                let mut key = String::with_capacity(element.len());
+                let mut chars = element.chars();
+                while let Some(char) = chars.next() {
+                    if char == '~' {
+                        match chars.next() {
+                            Some('0') => key.push('~'),
+                            Some('1') => key.push('/'),
+                            _ => bail!(
+                                Report::new(JsonPathError)
+                                    .attach_printable("`~` has to be followed by `0` or `1`")
+                            ),
+                        }
+                    } else {
+                        key.push(char);
+                    }
+                }
+                Ok(PathElement::Key(key))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pointers() {
+        for pointer in ["", "/", "/a~0b/~1/10/01", "/0/-"] {
+            let path = pointer.parse::<JsonPath>().expect("valid pointer");
+            assert_eq!(path.to_string(), pointer);
+        }
+        assert_eq!(
+            "/01".parse::<JsonPath>().expect("valid pointer").elements(),
+            [PathElement::Key("01".to_owned())]
+        );
+
+        let _: Report<_> = "a".parse::<JsonPath>().expect_err("missing slash");
+        let _: Report<_> = "/a~2".parse::<JsonPath>().expect_err("invalid escape");
+    }
+
+    #[test]
+    fn relates_paths() {
+        let path = JsonPath::new().join("people").join(1).join("name");
+        let other = JsonPath::new().join("people").join(1).join("age");
+
+        assert_eq!(path.common_prefix(&other).to_string(), "/people/1");
+        assert_eq!(path.parent(), Some(JsonPath::new().join("people").join(1)));
+        assert!(path.starts_with(&path.common_prefix(&other)));
+        assert_eq!(JsonPath::new().parent(), None);
+    }
+}
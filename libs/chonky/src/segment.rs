@@ -0,0 +1,79 @@
+use error_stack::Report;
+
+use crate::{
+    document::Metadata,
+    hash::ContentHash,
+    span::{Span, SpanError},
+    table::Table,
+};
+
+/// Metadata key of the plain title of a [`SegmentKind::Heading`], without any markup.
+///
+/// If a heading has no title, its text is used instead.
+pub const TITLE_KEY: &str = "title";
+
+/// The structural role of a [`Segment`].
+///
+/// Segment kinds are format-neutral: a heading is a heading regardless of whether it came from a
+/// Markdown `#`, an HTML `<h1>` or a styled paragraph in a word processor document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SegmentKind {
+    /// A heading with its nesting level, starting at `1` for the top-most level.
+    Heading {
+        level: u8,
+    },
+    Paragraph,
+    /// An item of a list with its nesting depth, starting at `0` for top-level items.
+    ListItem {
+        depth: u8,
+    },
+    /// A table together with its structure.
+    Table(Table),
+    CodeBlock {
+        language: Option<String>,
+    },
+    /// Text without any further known structure.
+    Text,
+}
+
+/// A typed region of a document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub kind: SegmentKind,
+    /// The region of the document text this segment covers.
+    pub span: Span,
+    pub metadata: Metadata,
+}
+
+impl Segment {
+    #[must_use]
+    pub const fn new(kind: SegmentKind, span: Span) -> Self {
+        Self {
+            kind,
+            span,
+            metadata: Metadata::new(),
+        }
+    }
+
+    /// Returns the text of this segment.
+    ///
+    /// `text` has to be the text of the document the segment was created from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpanError`] if the segment does not refer to a valid region of `text`.
+    pub fn text<'t>(&self, text: &'t str) -> Result<&'t str, Report<SpanError>> {
+        self.span.slice(text)
+    }
+
+    /// Returns the hash of the text of this segment.
+    ///
+    /// `text` has to be the text of the document the segment was created from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpanError`] if the segment does not refer to a valid region of `text`.
+    pub fn content_hash(&self, text: &str) -> Result<ContentHash, Report<SpanError>> {
+        self.text(text).map(ContentHash::of)
+    }
+}
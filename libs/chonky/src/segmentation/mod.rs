@@ -0,0 +1,39 @@
+//! Splitting of plain text into paragraphs, sentences and words.
+//!
+//! Boundaries follow the rules of [Unicode Standard Annex #29], with a few adjustments on top to
+//! improve sentence detection for prose:
+//!
+//! - Known abbreviations such as "e.g." or "Dr." and single-letter initials do not end a sentence.
+//! - A single line break inside a sentence (e.g. in hard-wrapped text) does not end a sentence,
+//!   while an empty line always does.
+//! - Leading and trailing whitespace is not part of a sentence.
+//!
+//! [`SentenceSegmenter::for_language`] selects the abbreviations and rules of a [`Language`], e.g.
+//! to keep German ordinals such as "am 3. Oktober" inside a sentence.
+//!
+//! Every returned [`Unit`] borrows from the original text and carries its [`Span`] inside of it.
+//!
+//! [Unicode Standard Annex #29]: https://www.unicode.org/reports/tr29/
+//! [`Language`]: crate::language::Language
+//! [`Span`]: crate::Span
+
+mod paragraph;
+mod sentence;
+mod word;
+
+pub use self::{
+    paragraph::paragraphs,
+    sentence::{
+        ENGLISH_ABBREVIATIONS, FRENCH_ABBREVIATIONS, GERMAN_ABBREVIATIONS, SPANISH_ABBREVIATIONS,
+        SentenceSegmenter, Sentences,
+    },
+    word::{Words, words},
+};
+use crate::span::Span;
+
+/// A sentence or word borrowed from the text it was segmented from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Unit<'t> {
+    pub text: &'t str,
+    pub span: Span,
+}
@@ -0,0 +1,110 @@
+use crate::{
+    segment::{Segment, SegmentKind},
+    span::OffsetCursor,
+};
+
+/// Splits plain text into paragraphs separated by empty lines.
+///
+/// Lines which only contain whitespace count as empty. The returned segments do not include the
+/// separating whitespace.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::segmentation::paragraphs;
+///
+/// let text = "First paragraph,\nstill first.\n\n  \nSecond paragraph.\n";
+/// let paragraphs = paragraphs(text)
+///     .into_iter()
+///     .map(|paragraph| paragraph.span.slice(text).expect("valid span"))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(paragraphs, [
+///     "First paragraph,\nstill first.",
+///     "Second paragraph."
+/// ]);
+/// ```
+#[must_use]
+pub fn paragraphs(text: &str) -> Vec<Segment> {
+    let mut cursor = OffsetCursor::new(text);
+    let mut segments = Vec::new();
+    let mut paragraph: Option<(usize, usize)> = None;
+
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let content = line.trim();
+        if content.is_empty() {
+            if let Some((start, end)) = paragraph.take() {
+                segments.push(Segment::new(
+                    SegmentKind::Paragraph,
+                    cursor.span(start..end),
+                ));
+            }
+        } else {
+            let content_start = line_start + (line.len() - line.trim_start().len());
+            let content_end = content_start + content.len();
+            paragraph = Some(
+                paragraph.map_or((content_start, content_end), |(start, _)| {
+                    (start, content_end)
+                }),
+            );
+        }
+        line_start += line.len();
+    }
+
+    if let Some((start, end)) = paragraph {
+        segments.push(Segment::new(
+            SegmentKind::Paragraph,
+            cursor.span(start..end),
+        ));
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str) -> Vec<&str> {
+        paragraphs(text)
+            .into_iter()
+            .map(|paragraph| paragraph.span.slice(text).expect("valid span"))
+            .collect()
+    }
+
+    #[test]
+    fn empty_text() {
+        assert!(paragraphs("").is_empty());
+        assert!(paragraphs(" \n\t\n\r\n  ").is_empty());
+    }
+
+    #[test]
+    fn line_endings() {
+        assert_eq!(
+            texts("\r\n\r\n  First\r\nline.\r\n \t\r\nSecond.\r\n\r\n"),
+            ["First\r\nline.", "Second."]
+        );
+        assert_eq!(texts("Single line without a break"), [
+            "Single line without a break"
+        ]);
+    }
+
+    #[test]
+    fn multibyte_offsets() {
+        let text = "\u{C4}pfel\n\n\u{3000}\n\u{1F34E} \u{4E00}\n";
+        let paragraphs = paragraphs(text);
+        assert_eq!(
+            paragraphs
+                .iter()
+                .map(|paragraph| paragraph.span.slice(text).expect("valid span"))
+                .collect::<Vec<_>>(),
+            ["\u{C4}pfel", "\u{1F34E} \u{4E00}"]
+        );
+        assert!(
+            paragraphs
+                .iter()
+                .all(|paragraph| paragraph.kind == SegmentKind::Paragraph)
+        );
+    }
+}
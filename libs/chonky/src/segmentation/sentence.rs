@@ -0,0 +1,456 @@
+use alloc::collections::BTreeSet;
+use core::iter::{FusedIterator, Peekable};
+
+use unicode_segmentation::{USentenceBoundIndices, UnicodeSegmentation};
+
+use super::Unit;
+use crate::{language::Language, span::OffsetCursor};
+
+/// Common English abbreviations which are followed by a period but do not end a sentence.
+///
+/// Entries are lowercase and without the trailing period.
+pub const ENGLISH_ABBREVIATIONS: &[&str] = &[
+    "al", "approx", "apr", "aug", "ca", "cf", "co", "corp", "dec", "dept", "dr", "e.g", "eq",
+    "est", "feb", "fig", "figs", "gen", "gov", "i.e", "inc", "jan", "jr", "jul", "jun", "ltd",
+    "mr", "mrs", "ms", "mt", "nov", "oct", "ph.d", "pp", "prof", "resp", "sen", "sep", "sept",
+    "sr", "st", "u.k", "u.s", "vol", "vs",
+];
+
+/// Common German abbreviations which are followed by a period but do not end a sentence.
+///
+/// Entries are lowercase and without the trailing period.
+pub const GERMAN_ABBREVIATIONS: &[&str] = &[
+    "abb", "abs", "abt", "aug", "bd", "bspw", "bzw", "ca", "d.h", "dez", "dr", "evtl", "feb",
+    "febr", "geb", "gest", "ggf", "hr", "hrsg", "inkl", "jan", "jh", "jhd", "mio", "mrd", "nov",
+    "nr", "o.\u{e4}", "okt", "prof", "sept", "sog", "st", "str", "tel", "u.a", "u.\u{e4}", "u.u",
+    "usw", "vgl", "z.b", "z.t", "zzgl",
+];
+
+/// Common French abbreviations which are followed by a period but do not end a sentence.
+///
+/// Entries are lowercase and without the trailing period.
+pub const FRENCH_ABBREVIATIONS: &[&str] = &[
+    "av",
+    "avr",
+    "bd",
+    "c.-\u{e0}-d",
+    "cf",
+    "chap",
+    "d\u{e9}c",
+    "dr",
+    "env",
+    "ex",
+    "f\u{e9}vr",
+    "janv",
+    "juil",
+    "me",
+    "mgr",
+    "mlle",
+    "mlles",
+    "mm",
+    "mme",
+    "mmes",
+    "nov",
+    "oct",
+    "p.ex",
+    "pp",
+    "pr",
+    "sept",
+    "st",
+    "ste",
+    "vol",
+];
+
+/// Common Spanish abbreviations which are followed by a period but do not end a sentence.
+///
+/// Entries are lowercase and without the trailing period.
+pub const SPANISH_ABBREVIATIONS: &[&str] = &[
+    "abr",
+    "ago",
+    "aprox",
+    "arq",
+    "av",
+    "avda",
+    "c\u{ed}a",
+    "dic",
+    "dr",
+    "dra",
+    "ee.uu",
+    "ej",
+    "ene",
+    "feb",
+    "ing",
+    "lic",
+    "n\u{fa}m",
+    "nov",
+    "oct",
+    "p.ej",
+    "p\u{e1}g",
+    "p\u{e1}gs",
+    "prof",
+    "sept",
+    "sr",
+    "sra",
+    "sres",
+    "srta",
+    "tel",
+    "ud",
+    "uds",
+    "vd",
+    "vds",
+];
+
+/// Characters which may end a sentence.
+const TERMINATORS: &[char] = &[
+    '.', '!', '?', '\u{2026}', '\u{203C}', '\u{2047}', '\u{2048}', '\u{2049}', '\u{3002}',
+    '\u{FF01}', '\u{FF0E}', '\u{FF1F}', '\u{FF61}',
+];
+
+/// Characters which may trail a sentence terminator, such as closing quotes or brackets.
+const CLOSERS: &[char] = &[
+    '"', '\'', ')', ']', '}', '\u{00BB}', '\u{2019}', '\u{201D}', '\u{300D}', '\u{300F}',
+    '\u{FF09}',
+];
+
+/// Characters which may precede a word, such as opening quotes or brackets.
+const OPENERS: &[char] = &[
+    '"', '\'', '(', '[', '{', '\u{00A1}', '\u{00AB}', '\u{00BF}', '\u{2018}', '\u{201C}',
+    '\u{300C}', '\u{300E}', '\u{FF08}',
+];
+
+/// Splits text into sentences.
+///
+/// See the [module documentation](super) for the rules applied.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::segmentation::SentenceSegmenter;
+///
+/// let segmenter = SentenceSegmenter::default();
+/// let sentences = segmenter
+///     .sentences("Dr. Smith paid $3.50, e.g. for coffee. Then he left... Did he?")
+///     .map(|sentence| sentence.text)
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(sentences, [
+///     "Dr. Smith paid $3.50, e.g. for coffee.",
+///     "Then he left...",
+///     "Did he?",
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SentenceSegmenter {
+    abbreviations: BTreeSet<String>,
+    ordinals: bool,
+}
+
+impl SentenceSegmenter {
+    /// Creates a segmenter which does not know any abbreviations.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            abbreviations: BTreeSet::new(),
+            ordinals: false,
+        }
+    }
+
+    /// Creates a segmenter with the rules of `language`.
+    ///
+    /// Languages written in the Latin script know the common abbreviations of the language and
+    /// German treats numbers followed by a period as ordinals. Chinese and Japanese sentences end
+    /// at full-width terminators, which do not need to be followed by whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chonky::{language::Language, segmentation::SentenceSegmenter};
+    ///
+    /// let segmenter = SentenceSegmenter::for_language(Language::German);
+    /// let sentences = segmenter
+    ///     .sentences(
+    ///         "Am 3. Oktober ist Feiertag, d.h. die L\u{e4}den sind zu. Wir bleiben z.B. daheim.",
+    ///     )
+    ///     .map(|sentence| sentence.text)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(sentences, [
+    ///     "Am 3. Oktober ist Feiertag, d.h. die L\u{e4}den sind zu.",
+    ///     "Wir bleiben z.B. daheim.",
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn for_language(language: Language) -> Self {
+        match language {
+            Language::English => Self::default(),
+            Language::German => Self::new()
+                .with_abbreviations(GERMAN_ABBREVIATIONS)
+                .with_ordinals(true),
+            Language::French => Self::new().with_abbreviations(FRENCH_ABBREVIATIONS),
+            Language::Spanish => Self::new().with_abbreviations(SPANISH_ABBREVIATIONS),
+            Language::Japanese | Language::Chinese => Self::new(),
+        }
+    }
+
+    /// Adds abbreviations which do not end a sentence when followed by a period.
+    ///
+    /// Abbreviations are matched case-insensitively and are expected without their trailing
+    /// period, e.g. `"e.g"` or `"Dr"`.
+    #[must_use]
+    pub fn with_abbreviations<A>(mut self, abbreviations: impl IntoIterator<Item = A>) -> Self
+    where
+        A: AsRef<str>,
+    {
+        self.abbreviations.extend(
+            abbreviations
+                .into_iter()
+                .map(|abbreviation| abbreviation.as_ref().to_lowercase()),
+        );
+        self
+    }
+
+    /// Treats numbers of one or two digits followed by a period as ordinals, which do not end a
+    /// sentence, e.g. "am 3. Oktober" in German.
+    ///
+    /// Longer numbers, such as years, still end a sentence.
+    #[must_use]
+    pub const fn with_ordinals(mut self, ordinals: bool) -> Self {
+        self.ordinals = ordinals;
+        self
+    }
+
+    /// Returns an iterator over the sentences of `text`.
+    #[must_use]
+    pub fn sentences<'s, 't>(&'s self, text: &'t str) -> Sentences<'s, 't> {
+        Sentences {
+            segmenter: self,
+            text,
+            inner: text.split_sentence_bound_indices().peekable(),
+            cursor: OffsetCursor::new(text),
+        }
+    }
+
+    fn ends_with_abbreviation(&self, sentence: &str) -> bool {
+        let Some(sentence) = sentence.strip_suffix('.') else {
+            return false;
+        };
+
+        let word = sentence
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or(sentence)
+            .trim_start_matches(OPENERS);
+
+        let mut chars = word.chars();
+        match (chars.next(), chars.next()) {
+            (None, _) => false,
+            // Initials, e.g. "J. R. R. Tolkien"
+            (Some(initial), None) => {
+                initial.is_alphabetic() || (self.ordinals && initial.is_ascii_digit())
+            }
+            (Some(first), Some(second)) => {
+                (self.ordinals
+                    && word.len() == 2
+                    && first.is_ascii_digit()
+                    && second.is_ascii_digit())
+                    || self.abbreviations.contains(&word.to_lowercase())
+            }
+        }
+    }
+
+    fn should_merge(&self, current: &str) -> bool {
+        let sentence = current.trim_end();
+        if sentence.is_empty() {
+            return false;
+        }
+
+        if self.ends_with_abbreviation(sentence) {
+            return true;
+        }
+
+        // A line break without a terminator is a soft wrap unless it starts a new paragraph
+        let trailing = current.get(sentence.len()..).unwrap_or_default();
+        let ends_with_terminator = sentence.trim_end_matches(CLOSERS).ends_with(TERMINATORS);
+        !ends_with_terminator && trailing.matches('\n').count() <= 1
+    }
+}
+
+impl Default for SentenceSegmenter {
+    fn default() -> Self {
+        Self::new().with_abbreviations(ENGLISH_ABBREVIATIONS)
+    }
+}
+
+/// Iterator over the sentences of a text, created by [`SentenceSegmenter::sentences`].
+#[derive(Debug, Clone)]
+pub struct Sentences<'s, 't> {
+    segmenter: &'s SentenceSegmenter,
+    text: &'t str,
+    inner: Peekable<USentenceBoundIndices<'t>>,
+    cursor: OffsetCursor<'t>,
+}
+
+impl<'t> Iterator for Sentences<'_, 't> {
+    type Item = Unit<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (start, first) = self.inner.next()?;
+            let mut end = start + first.len();
+
+            while let Some(&(next_start, next)) = self.inner.peek() {
+                if !self
+                    .segmenter
+                    .should_merge(self.text.get(start..end).unwrap_or_default())
+                {
+                    break;
+                }
+                end = next_start + next.len();
+                self.inner.next();
+            }
+
+            let raw = self.text.get(start..end).unwrap_or_default();
+            let text = raw.trim();
+            if text.is_empty() {
+                continue;
+            }
+
+            let start = start + (raw.len() - raw.trim_start().len());
+            return Some(Unit {
+                text,
+                span: self.cursor.span(start..start + text.len()),
+            });
+        }
+    }
+}
+
+impl FusedIterator for Sentences<'_, '_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentences(text: &str) -> Vec<&str> {
+        SentenceSegmenter::default()
+            .sentences(text)
+            .map(|sentence| sentence.text)
+            .collect()
+    }
+
+    #[test]
+    fn abbreviations_and_initials() {
+        assert_eq!(
+            sentences("Mr. J. R. R. Tolkien met Prof. Lewis. They talked, i.e. argued."),
+            [
+                "Mr. J. R. R. Tolkien met Prof. Lewis.",
+                "They talked, i.e. argued."
+            ]
+        );
+    }
+
+    #[test]
+    fn decimals_and_ellipses() {
+        assert_eq!(
+            sentences("Pi is roughly 3.14159. Or is it... let me check. No."),
+            ["Pi is roughly 3.14159.", "Or is it... let me check.", "No."]
+        );
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(
+            sentences("She said \"Stop!\" Then she left. (It was late.) Nobody followed."),
+            [
+                "She said \"Stop!\"",
+                "Then she left.",
+                "(It was late.)",
+                "Nobody followed."
+            ]
+        );
+    }
+
+    #[test]
+    fn line_breaks() {
+        assert_eq!(
+            sentences("This sentence is\nhard-wrapped.\nThis one is not.\n\nA heading\n\nText"),
+            [
+                "This sentence is\nhard-wrapped.",
+                "This one is not.",
+                "A heading",
+                "Text"
+            ]
+        );
+    }
+
+    #[test]
+    fn cjk_without_whitespace() {
+        let text = "\u{4f60}\u{597d}\u{3002}\u{518d}\u{89c1}\u{ff01}";
+        let units = SentenceSegmenter::default()
+            .sentences(text)
+            .collect::<Vec<_>>();
+
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0].span.char_range(), 0..3);
+        assert_eq!(units[1].span.char_range(), 3..6);
+        assert_eq!(units[1].span.byte_range(), 9..18);
+    }
+
+    #[test]
+    fn language_rules() {
+        let sentences = |language, text| {
+            SentenceSegmenter::for_language(language)
+                .sentences(text)
+                .map(|sentence| sentence.text)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            sentences(
+                Language::German,
+                "Im 19. Jh. wuchs die Stadt. Sie z\u{e4}hlte 1900 ca. 5000 Einwohner. Das war \
+                 1900."
+            ),
+            [
+                "Im 19. Jh. wuchs die Stadt.",
+                "Sie z\u{e4}hlte 1900 ca. 5000 Einwohner.",
+                "Das war 1900."
+            ]
+        );
+        assert_eq!(
+            sentences(
+                Language::French,
+                "Mme. Dupont arrive, cf. la lettre. Elle repart."
+            ),
+            ["Mme. Dupont arrive, cf. la lettre.", "Elle repart."]
+        );
+        assert_eq!(
+            sentences(
+                Language::Spanish,
+                "La Sra. Garc\u{ed}a lleg\u{f3}. \u{bf}Y el Sr. L\u{f3}pez? No vino."
+            ),
+            [
+                "La Sra. Garc\u{ed}a lleg\u{f3}.",
+                "\u{bf}Y el Sr. L\u{f3}pez?",
+                "No vino."
+            ]
+        );
+        // English does not know German abbreviations and ordinals
+        assert_eq!(sentences(Language::English, "Am 3. Oktober."), [
+            "Am 3.", "Oktober."
+        ]);
+    }
+
+    #[test]
+    fn spans_exclude_whitespace() {
+        let text = "  First.   Second.  ";
+        let units = SentenceSegmenter::default()
+            .sentences(text)
+            .collect::<Vec<_>>();
+
+        assert_eq!(units[0].span.byte_range(), 2..8);
+        assert_eq!(units[1].span.byte_range(), 11..18);
+        for unit in units {
+            assert_eq!(unit.span.slice(text).expect("valid span"), unit.text);
+        }
+    }
+}
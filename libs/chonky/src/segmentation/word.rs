@@ -0,0 +1,71 @@
+use core::iter::FusedIterator;
+
+use unicode_segmentation::{UnicodeSegmentation, UnicodeWordIndices};
+
+use super::Unit;
+use crate::span::OffsetCursor;
+
+/// Iterator over the words of a text, created by [`words`].
+#[derive(Debug)]
+pub struct Words<'t> {
+    inner: UnicodeWordIndices<'t>,
+    cursor: OffsetCursor<'t>,
+}
+
+impl<'t> Iterator for Words<'t> {
+    type Item = Unit<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, text) = self.inner.next()?;
+
+        Some(Unit {
+            text,
+            span: self.cursor.span(start..start + text.len()),
+        })
+    }
+}
+
+impl FusedIterator for Words<'_> {}
+
+/// Returns the words of `text`.
+///
+/// Only word-like units, i.e. those containing at least one alphanumeric character, are returned.
+/// Punctuation and whitespace are skipped. Numbers such as `3.14` or `1,000` are kept as a single
+/// word. Ideographic scripts which do not separate words by whitespace are split into single
+/// ideographs.
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::segmentation::words;
+///
+/// let words = words("It costs $3.50, doesn't it?")
+///     .map(|word| word.text)
+///     .collect::<Vec<_>>();
+/// assert_eq!(words, ["It", "costs", "3.50", "doesn't", "it"]);
+/// ```
+#[must_use]
+pub fn words(text: &str) -> Words<'_> {
+    Words {
+        inner: text.unicode_word_indices(),
+        cursor: OffsetCursor::new(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cjk_words_have_char_offsets() {
+        let text = "\u{6211}\u{559c}\u{6b22} Rust";
+        let units = words(text).collect::<Vec<_>>();
+
+        assert_eq!(units.len(), 4);
+        assert_eq!(units[1].text, "\u{559c}");
+        assert_eq!(units[1].span.byte_range(), 3..6);
+        assert_eq!(units[1].span.char_range(), 1..2);
+        assert_eq!(units[3].text, "Rust");
+        assert_eq!(units[3].span.char_range(), 4..8);
+    }
+}
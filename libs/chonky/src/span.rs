@@ -0,0 +1,330 @@
+use core::ops::Range;
+
+use error_stack::{Report, ensure};
+
+/// A position inside a text, expressed both in UTF-8 bytes and in Unicode scalar values.
+///
+/// Byte offsets are what Rust uses to slice a `&str`, while character offsets are what most other
+/// consumers (JavaScript, databases, UI highlighting) expect. Carrying both avoids having to
+/// re-scan the source whenever a consumer needs the other representation.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Offset {
+    pub byte: usize,
+    pub char: usize,
+}
+
+impl Offset {
+    pub const ZERO: Self = Self { byte: 0, char: 0 };
+
+    #[must_use]
+    pub const fn new(byte: usize, char: usize) -> Self {
+        Self { byte, char }
+    }
+
+    /// Returns the offset directly after `text`, assuming `text` starts at `self`.
+    #[must_use]
+    pub fn advance(self, text: &str) -> Self {
+        Self {
+            byte: self.byte + text.len(),
+            char: self.char + text.chars().count(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SpanError {
+    #[error("the span ends before it starts")]
+    Inverted,
+    #[error("the span is out of bounds of the text")]
+    OutOfBounds,
+    #[error("the span does not lie on a character boundary")]
+    NotCharBoundary,
+}
+
+/// A half-open region `[start, end)` inside a text.
+///
+/// The span does not borrow the text it refers to, it is up to the caller to use it with the same
+/// text it was created from.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub start: Offset,
+    pub end: Offset,
+}
+
+impl Span {
+    #[must_use]
+    pub const fn new(start: Offset, end: Offset) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates a span from a byte range, computing the character offsets from `text`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Inverted`] if `range.end` is smaller than `range.start`
+    /// - [`OutOfBounds`] if `range.end` is larger than the length of `text`
+    /// - [`NotCharBoundary`] if either end of the range does not lie on a character boundary
+    ///
+    /// [`Inverted`]: SpanError::Inverted
+    /// [`OutOfBounds`]: SpanError::OutOfBounds
+    /// [`NotCharBoundary`]: SpanError::NotCharBoundary
+    pub fn from_byte_range(text: &str, range: Range<usize>) -> Result<Self, Report<SpanError>> {
+        ensure!(range.start <= range.end, SpanError::Inverted);
+        ensure!(range.end <= text.len(), SpanError::OutOfBounds);
+        ensure!(
+            text.is_char_boundary(range.start) && text.is_char_boundary(range.end),
+            SpanError::NotCharBoundary
+        );
+
+        let start = Offset::ZERO.advance(text.get(..range.start).unwrap_or_default());
+        let end = start.advance(text.get(range).unwrap_or_default());
+
+        Ok(Self { start, end })
+    }
+
+    #[must_use]
+    pub const fn byte_range(&self) -> Range<usize> {
+        self.start.byte..self.end.byte
+    }
+
+    #[must_use]
+    pub const fn char_range(&self) -> Range<usize> {
+        self.start.char..self.end.char
+    }
+
+    #[must_use]
+    pub const fn byte_len(&self) -> usize {
+        self.end.byte.saturating_sub(self.start.byte)
+    }
+
+    #[must_use]
+    pub const fn char_len(&self) -> usize {
+        self.end.char.saturating_sub(self.start.char)
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.start.byte >= self.end.byte
+    }
+
+    /// Returns `true` if `other` lies completely inside of `self`.
+    #[must_use]
+    pub const fn contains(&self, other: &Self) -> bool {
+        self.start.byte <= other.start.byte && other.end.byte <= self.end.byte
+    }
+
+    /// Returns the smallest span covering both `self` and `other`.
+    #[must_use]
+    pub fn cover(self, other: Self) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// Moves a span relative to `base` into the coordinate system `base` is expressed in.
+    ///
+    /// This is used to turn a span inside of a slice of a text into a span of the whole text,
+    /// where `base` is the start of the slice.
+    #[must_use]
+    pub const fn translate(self, base: Offset) -> Self {
+        Self {
+            start: Offset::new(self.start.byte + base.byte, self.start.char + base.char),
+            end: Offset::new(self.end.byte + base.byte, self.end.char + base.char),
+        }
+    }
+
+    /// Returns the text this span refers to.
+    ///
+    /// # Errors
+    ///
+    /// - [`Inverted`] if the span ends before it starts
+    /// - [`OutOfBounds`] if the span does not fit into `text`
+    /// - [`NotCharBoundary`] if the span does not lie on character boundaries of `text`
+    ///
+    /// [`Inverted`]: SpanError::Inverted
+    /// [`OutOfBounds`]: SpanError::OutOfBounds
+    /// [`NotCharBoundary`]: SpanError::NotCharBoundary
+    pub fn slice<'t>(&self, text: &'t str) -> Result<&'t str, Report<SpanError>> {
+        ensure!(self.start.byte <= self.end.byte, SpanError::Inverted);
+        ensure!(self.end.byte <= text.len(), SpanError::OutOfBounds);
+
+        text.get(self.byte_range())
+            .ok_or_else(|| Report::new(SpanError::NotCharBoundary))
+    }
+}
+
+/// Lookup table to translate byte offsets into [`Offset`]s without re-scanning the whole text.
+///
+/// For ASCII-only text the translation is free. Otherwise, a checkpoint is stored every
+/// [`Self::CHECKPOINT_INTERVAL`] bytes and only the remainder is scanned on lookup.
+#[derive(Debug, Clone)]
+pub struct OffsetIndex {
+    len: usize,
+    checkpoints: Option<Vec<Offset>>,
+}
+
+impl OffsetIndex {
+    pub const CHECKPOINT_INTERVAL: usize = 1024;
+
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        if text.is_ascii() {
+            return Self {
+                len: text.len(),
+                checkpoints: None,
+            };
+        }
+
+        let mut checkpoints = vec![Offset::ZERO];
+        let mut next_checkpoint = Self::CHECKPOINT_INTERVAL;
+        for (char_index, (byte_index, _)) in text.char_indices().enumerate() {
+            if byte_index >= next_checkpoint {
+                checkpoints.push(Offset::new(byte_index, char_index));
+                next_checkpoint = byte_index + Self::CHECKPOINT_INTERVAL;
+            }
+        }
+
+        Self {
+            len: text.len(),
+            checkpoints: Some(checkpoints),
+        }
+    }
+
+    /// Translates a byte offset in `text` into an [`Offset`].
+    ///
+    /// `text` has to be the same text the index was created from.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfBounds`] if `byte` is larger than the length of `text`
+    /// - [`NotCharBoundary`] if `byte` does not lie on a character boundary
+    ///
+    /// [`OutOfBounds`]: SpanError::OutOfBounds
+    /// [`NotCharBoundary`]: SpanError::NotCharBoundary
+    pub fn offset(&self, text: &str, byte: usize) -> Result<Offset, Report<SpanError>> {
+        ensure!(
+            byte <= self.len && byte <= text.len(),
+            SpanError::OutOfBounds
+        );
+        ensure!(text.is_char_boundary(byte), SpanError::NotCharBoundary);
+
+        let Some(checkpoints) = &self.checkpoints else {
+            return Ok(Offset::new(byte, byte));
+        };
+
+        let checkpoint = match checkpoints.binary_search_by_key(&byte, |offset| offset.byte) {
+            Ok(index) => return Ok(checkpoints[index]),
+            Err(index) => checkpoints[index.saturating_sub(1)],
+        };
+
+        Ok(checkpoint.advance(text.get(checkpoint.byte..byte).unwrap_or_default()))
+    }
+
+    /// Translates a byte range in `text` into a [`Span`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Span::from_byte_range`].
+    pub fn span(&self, text: &str, range: Range<usize>) -> Result<Span, Report<SpanError>> {
+        ensure!(range.start <= range.end, SpanError::Inverted);
+
+        Ok(Span::new(
+            self.offset(text, range.start)?,
+            self.offset(text, range.end)?,
+        ))
+    }
+}
+
+/// Forward-only translation of byte offsets into [`Offset`]s.
+///
+/// Most producers of spans walk a text from start to end, in which case only the text between two
+/// consecutive lookups has to be scanned. Seeking backwards restarts the scan from the beginning.
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetCursor<'t> {
+    text: &'t str,
+    current: Offset,
+}
+
+impl<'t> OffsetCursor<'t> {
+    pub(crate) const fn new(text: &'t str) -> Self {
+        Self {
+            text,
+            current: Offset::ZERO,
+        }
+    }
+
+    /// Returns the offset for `byte`, which has to lie on a character boundary of the text.
+    pub(crate) fn seek(&mut self, byte: usize) -> Offset {
+        debug_assert!(
+            self.text.is_char_boundary(byte),
+            "offset has to lie on a character boundary"
+        );
+
+        if byte < self.current.byte {
+            self.current = Offset::ZERO;
+        }
+
+        self.current = self
+            .current
+            .advance(self.text.get(self.current.byte..byte).unwrap_or_default());
+        self.current
+    }
+
+    pub(crate) fn span(&mut self, range: Range<usize>) -> Span {
+        let start = self.seek(range.start);
+        let end = self.seek(range.end);
+        Span::new(start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_from_byte_range() {
+        let text = "Gr\u{fc}\u{df}e, world";
+        let span = Span::from_byte_range(text, 8..text.len()).expect("valid range");
+
+        assert_eq!(span.start, Offset::new(8, 6));
+        assert_eq!(span.end, Offset::new(14, 12));
+        assert_eq!(span.slice(text).expect("valid span"), " world");
+    }
+
+    #[test]
+    fn span_rejects_invalid_ranges() {
+        let text = "Gr\u{fc}\u{df}e";
+
+        assert_eq!(
+            Span::from_byte_range(text, 3..4)
+                .expect_err("not on a boundary")
+                .current_context(),
+            &SpanError::NotCharBoundary
+        );
+        assert_eq!(
+            Span::from_byte_range(text, 0..100)
+                .expect_err("out of bounds")
+                .current_context(),
+            &SpanError::OutOfBounds
+        );
+    }
+
+    #[test]
+    fn offset_index_matches_linear_scan() {
+        let mut text = "\u{e4}".repeat(3000);
+        text.push_str("end");
+        let index = OffsetIndex::new(&text);
+
+        for (char_index, (byte_index, _)) in text.char_indices().enumerate().step_by(97) {
+            assert_eq!(
+                index.offset(&text, byte_index).expect("valid offset"),
+                Offset::new(byte_index, char_index)
+            );
+        }
+        assert_eq!(
+            index.offset(&text, text.len()).expect("valid offset"),
+            Offset::new(text.len(), 3003)
+        );
+    }
+}
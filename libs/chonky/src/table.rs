@@ -0,0 +1,453 @@
+//! Tables with rows, columns and header rows.
+//!
+//! Front-ends describe a table as rows of [`Cell`]s, which may span several rows or columns. The
+//! resulting [`Table`] is a rectangular grid in which merged cells are repeated in every position
+//! they cover, so every value can be related to the header of its column.
+
+use core::ops::Range;
+
+/// Separator between the header cells of a column with several header rows.
+const HEADER_SEPARATOR: &str = " / ";
+
+/// A cell of a table row as found in a document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub text: String,
+    /// The number of rows this cell covers, at least `1`.
+    pub row_span: usize,
+    /// The number of columns this cell covers, at least `1`.
+    pub column_span: usize,
+}
+
+impl Cell {
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    #[must_use]
+    pub fn with_row_span(mut self, row_span: usize) -> Self {
+        self.row_span = row_span.max(1);
+        self
+    }
+
+    #[must_use]
+    pub fn with_column_span(mut self, column_span: usize) -> Self {
+        self.column_span = column_span.max(1);
+        self
+    }
+}
+
+/// How a [`Table`] is rendered into text.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum TableFormat {
+    /// A Markdown table, one line per row.
+    #[default]
+    Markdown,
+    /// One line per row, in which every value is prefixed with the header of its column, e.g.
+    /// `Service: graph; Port: 4000`.
+    ///
+    /// Rows of tables without a header are rendered as values separated by `" | "`.
+    Records,
+}
+
+/// A table as a rectangular grid of cell texts.
+///
+/// The first [`header_rows`] rows form the header of the table, all other rows its body.
+///
+/// [`header_rows`]: Self::header_rows
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::table::{Cell, Table, TableFormat};
+///
+/// let table = Table::from_cells(
+///     [
+///         vec![Cell::new("Service"), Cell::new("Port")],
+///         vec![Cell::new("graph"), Cell::new("4000")],
+///         vec![Cell::new("type-fetcher"), Cell::new("4455")],
+///     ],
+///     0,
+/// )
+/// .with_detected_header();
+///
+/// assert_eq!(table.header_rows(), 1);
+/// assert_eq!(
+///     table.render(TableFormat::Markdown),
+///     "| Service | Port |\n| --- | --- |\n| graph | 4000 |\n| type-fetcher | 4455 |"
+/// );
+/// assert_eq!(
+///     table.render(TableFormat::Records),
+///     "Service: graph; Port: 4000\nService: type-fetcher; Port: 4455"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Table {
+    rows: Vec<Vec<String>>,
+    header_rows: usize,
+}
+
+impl Table {
+    /// Creates a table from the texts of its cells.
+    ///
+    /// Rows shorter than the longest row are padded with empty cells. `header_rows` is capped at
+    /// the number of rows.
+    #[must_use]
+    pub fn new(mut rows: Vec<Vec<String>>, header_rows: usize) -> Self {
+        let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+        for row in &mut rows {
+            row.resize(columns, String::new());
+        }
+
+        Self {
+            header_rows: header_rows.min(rows.len()),
+            rows,
+        }
+    }
+
+    /// Creates a table from rows of cells, which may span several rows or columns.
+    ///
+    /// Every cell is placed in the first column of its row which is not covered by a cell of a
+    /// previous row spanning several rows. Merged cells are repeated in every position they
+    /// cover. Cells spanning past the last row are cut off.
+    #[must_use]
+    pub fn from_cells<R>(rows: R, header_rows: usize) -> Self
+    where
+        R: IntoIterator<Item: IntoIterator<Item = Cell>>,
+    {
+        // Per column, the text of a cell of a previous row which covers it and the number of rows
+        // it still covers
+        let mut spanning = Vec::<Option<(String, usize)>>::new();
+        let mut grid = Vec::new();
+
+        for cells in rows {
+            let mut row = Vec::new();
+            for cell in cells {
+                take_spanning(&mut spanning, &mut row);
+                let text = normalize(&cell.text);
+                for _ in 0..cell.column_span {
+                    if cell.row_span > 1 {
+                        if spanning.len() <= row.len() {
+                            spanning.resize(row.len() + 1, None);
+                        }
+                        spanning[row.len()] = Some((text.clone(), cell.row_span - 1));
+                    }
+                    row.push(text.clone());
+                }
+            }
+            // Cells of previous rows may also cover the end of the row
+            while row.len() < spanning.len() {
+                if spanning[row.len()].is_none() {
+                    row.push(String::new());
+                }
+                take_spanning(&mut spanning, &mut row);
+            }
+
+            grid.push(row);
+        }
+
+        Self::new(grid, header_rows)
+    }
+
+    /// Marks the first row as header if the table has no header yet and the first row looks like
+    /// one.
+    ///
+    /// The first row is considered a header if all of its cells are distinct, non-empty and not
+    /// numeric, while at least one column contains a numeric value below it.
+    #[must_use]
+    pub fn with_detected_header(mut self) -> Self {
+        if self.header_rows == 0 && self.has_header_row() {
+            self.header_rows = 1;
+        }
+        self
+    }
+
+    fn has_header_row(&self) -> bool {
+        let Some((first, body)) = self.rows.split_first() else {
+            return false;
+        };
+        if body.is_empty() {
+            return false;
+        }
+
+        let distinct = first
+            .iter()
+            .enumerate()
+            .all(|(index, cell)| !first[..index].contains(cell));
+        let labels = first
+            .iter()
+            .all(|cell| !cell.is_empty() && !is_numeric(cell));
+        let numeric_column = (0..first.len()).any(|column| {
+            body.iter()
+                .any(|row| row.get(column).is_some_and(|cell| is_numeric(cell)))
+        });
+
+        distinct && labels && numeric_column
+    }
+
+    /// Returns all rows of the table, including the header rows.
+    #[must_use]
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    #[must_use]
+    pub const fn header_rows(&self) -> usize {
+        self.header_rows
+    }
+
+    #[must_use]
+    pub fn header(&self) -> &[Vec<String>] {
+        &self.rows[..self.header_rows]
+    }
+
+    #[must_use]
+    pub fn body(&self) -> &[Vec<String>] {
+        &self.rows[self.header_rows..]
+    }
+
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().flatten().all(String::is_empty)
+    }
+
+    /// Returns the name of every column, the texts of its header cells joined by `" / "`.
+    ///
+    /// Header cells repeated because of a merged cell are only included once. Returns `None` if
+    /// the table has no header.
+    #[must_use]
+    pub fn column_names(&self) -> Option<Vec<String>> {
+        if self.header_rows == 0 {
+            return None;
+        }
+
+        Some(
+            (0..self.columns())
+                .map(|column| {
+                    let mut parts = Vec::<&str>::new();
+                    for row in self.header() {
+                        let cell = row[column].as_str();
+                        if !cell.is_empty() && parts.last() != Some(&cell) {
+                            parts.push(cell);
+                        }
+                    }
+                    parts.join(HEADER_SEPARATOR)
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the lines rendered in front of every group of body rows, if any.
+    ///
+    /// Only [`TableFormat::Markdown`] has header lines, records repeat the header in every line.
+    #[must_use]
+    pub fn render_header(&self, format: TableFormat) -> Option<String> {
+        match format {
+            TableFormat::Markdown => self.column_names().map(|names| {
+                format!(
+                    "{}\n{}",
+                    markdown_row(&names),
+                    markdown_row(&vec!["---".to_owned(); names.len()])
+                )
+            }),
+            TableFormat::Records => None,
+        }
+    }
+
+    /// Renders the body row at `index` as a single line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds of the body.
+    #[must_use]
+    pub fn render_row(&self, format: TableFormat, index: usize) -> String {
+        let row = &self.body()[index];
+        match (format, self.column_names()) {
+            (TableFormat::Markdown, _) => markdown_row(row),
+            (TableFormat::Records, None) => row.join(" | "),
+            (TableFormat::Records, Some(names)) => names
+                .iter()
+                .zip(row)
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(name, value)| {
+                    if name.is_empty() {
+                        value.clone()
+                    } else {
+                        format!("{name}: {value}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("; "),
+        }
+    }
+
+    /// Renders the header followed by the body rows in `rows`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is out of bounds of the body.
+    #[must_use]
+    pub fn render_rows(&self, format: TableFormat, rows: Range<usize>) -> String {
+        self.render_header(format)
+            .into_iter()
+            .chain(rows.map(|index| self.render_row(format, index)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders the whole table.
+    #[must_use]
+    pub fn render(&self, format: TableFormat) -> String {
+        self.render_rows(format, 0..self.body().len())
+    }
+}
+
+/// Appends the cells of previous rows covering the next columns of `row`.
+fn take_spanning(spanning: &mut [Option<(String, usize)>], row: &mut Vec<String>) {
+    while let Some(Some((text, remaining))) = spanning.get_mut(row.len()) {
+        row.push(text.clone());
+        *remaining -= 1;
+        if *remaining == 0 {
+            spanning[row.len() - 1] = None;
+        }
+    }
+}
+
+/// Collapses whitespace in the text of a cell, so every row is rendered on a single line.
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns if `text` is a number, possibly with a currency, a percent sign or thousands
+/// separators.
+fn is_numeric(text: &str) -> bool {
+    let number = text
+        .trim_matches(|char: char| {
+            matches!(char, '$' | '\u{20ac}' | '\u{a3}' | '%') || char.is_whitespace()
+        })
+        .replace(',', "");
+    !number.is_empty() && number.parse::<f64>().is_ok()
+}
+
+fn markdown_row(cells: &[String]) -> String {
+    let cells = cells
+        .iter()
+        .map(|cell| cell.replace('|', "\\|"))
+        .collect::<Vec<_>>();
+    format!("| {} |", cells.join(" | "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(table: &Table) -> Vec<Vec<&str>> {
+        table
+            .rows()
+            .iter()
+            .map(|row| row.iter().map(String::as_str).collect())
+            .collect()
+    }
+
+    #[test]
+    fn expands_merged_cells() {
+        let table = Table::from_cells(
+            [
+                vec![
+                    Cell::new("Region").with_row_span(2),
+                    Cell::new("Sales").with_column_span(2),
+                ],
+                vec![Cell::new("2023"), Cell::new("2024")],
+                vec![
+                    Cell::new("North"),
+                    Cell::new("10").with_row_span(2),
+                    Cell::new("12"),
+                ],
+                vec![Cell::new("South"), Cell::new("9")],
+            ],
+            2,
+        );
+
+        assert_eq!(rows(&table), [
+            ["Region", "Sales", "Sales"],
+            ["Region", "2023", "2024"],
+            ["North", "10", "12"],
+            ["South", "10", "9"],
+        ]);
+        assert_eq!(
+            table.column_names(),
+            Some(vec![
+                "Region".to_owned(),
+                "Sales / 2023".to_owned(),
+                "Sales / 2024".to_owned()
+            ])
+        );
+        assert_eq!(
+            table.render(TableFormat::Records),
+            "Region: North; Sales / 2023: 10; Sales / 2024: 12\nRegion: South; Sales / 2023: 10; \
+             Sales / 2024: 9"
+        );
+    }
+
+    #[test]
+    fn pads_short_rows() {
+        let table = Table::from_cells(
+            [
+                vec![Cell::new("a").with_row_span(3), Cell::new("b")],
+                vec![],
+                vec![Cell::new("c"), Cell::new("d")],
+            ],
+            0,
+        );
+
+        assert_eq!(rows(&table), [["a", "b", ""], ["a", "", ""], [
+            "a", "c", "d"
+        ]]);
+    }
+
+    #[test]
+    fn detects_headers() {
+        let table = |rows: &[&[&str]]| {
+            Table::new(
+                rows.iter()
+                    .map(|row| row.iter().map(|cell| (*cell).to_owned()).collect())
+                    .collect(),
+                0,
+            )
+            .with_detected_header()
+            .header_rows()
+        };
+
+        assert_eq!(table(&[&["Name", "Price"], &["Tea", "$1,200.50"]]), 1);
+        assert_eq!(table(&[&["Name", "Kind"], &["Tea", "Drink"]]), 0);
+        assert_eq!(table(&[&["2023", "Price"], &["Tea", "4"]]), 0);
+        assert_eq!(table(&[&["Name", ""], &["Tea", "4"]]), 0);
+        assert_eq!(table(&[&["Name", "Price"]]), 0);
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let table = Table::new(
+            vec![vec!["a|b".to_owned(), "c".to_owned()], vec![
+                "d".to_owned(),
+                "e".to_owned(),
+            ]],
+            0,
+        );
+        assert_eq!(
+            table.render(TableFormat::Markdown),
+            "| a\\|b | c |\n| d | e |"
+        );
+        assert_eq!(table.render(TableFormat::Records), "a|b | c\nd | e");
+    }
+}
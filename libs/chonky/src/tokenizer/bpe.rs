@@ -0,0 +1,289 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use error_stack::{Report, ResultExt, bail};
+
+use super::Tokenizer;
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not load tokenizer")]
+pub struct TokenizerError;
+
+/// Maps every byte to a printable character, as done by GPT-2 style byte-level BPE vocabularies.
+///
+/// Printable Latin-1 bytes map to themselves, all others are shifted to start at `U+0100`.
+fn byte_to_char() -> [char; 256] {
+    let mut table = ['\0'; 256];
+    let mut shifted = 0;
+    for (byte, entry) in (0..=u8::MAX).zip(&mut table) {
+        let code_point = if matches!(byte, b'!'..=b'~' | 0xA1..=0xAC | 0xAE..=0xFF) {
+            u32::from(byte)
+        } else {
+            shifted += 1;
+            255 + shifted
+        };
+        *entry = char::from_u32(code_point)
+            .unwrap_or_else(|| unreachable!("all code points below U+0200 are valid characters"));
+    }
+    table
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum CharClass {
+    Letter,
+    Number,
+    Whitespace,
+    Other,
+}
+
+impl CharClass {
+    fn of(char: char) -> Self {
+        if char.is_alphabetic() {
+            Self::Letter
+        } else if char.is_numeric() {
+            Self::Number
+        } else if char.is_whitespace() {
+            Self::Whitespace
+        } else {
+            Self::Other
+        }
+    }
+}
+
+const CONTRACTIONS: &[&str] = &["'s", "'t", "'re", "'ve", "'m", "'ll", "'d"];
+
+/// Splits text into words the same way the GPT-2 pre-tokenizer does.
+///
+/// This is a hand-written equivalent of the pattern
+/// `'s|'t|'re|'ve|'m|'ll|'d| ?\p{L}+| ?\p{N}+| ?[^\s\p{L}\p{N}]+|\s+(?!\S)|\s+`.
+fn pre_tokenize(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    core::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let length = CONTRACTIONS
+            .iter()
+            .find(|contraction| rest.starts_with(**contraction))
+            .map_or_else(|| word_length(rest), |contraction| contraction.len());
+
+        let (word, remainder) = rest.split_at(length);
+        rest = remainder;
+        Some(word)
+    })
+}
+
+/// Returns the length of the word at the start of `text`, optionally preceded by a single space.
+fn word_length(text: &str) -> usize {
+    let mut chars = text.chars();
+    let (prefix, class) = match (chars.next(), chars.next()) {
+        (Some(' '), Some(next)) if !next.is_whitespace() => (1, CharClass::of(next)),
+        (Some(first), _) => (0, CharClass::of(first)),
+        (None, _) => return 0,
+    };
+
+    let body = text.get(prefix..).unwrap_or_default();
+    let run = body
+        .char_indices()
+        .find(|&(_, char)| CharClass::of(char) != class)
+        .map_or(body.len(), |(index, _)| index);
+
+    match class {
+        // Leave the last whitespace character for the following word
+        CharClass::Whitespace if run < body.len() => body
+            .get(..run)
+            .and_then(|whitespace| whitespace.char_indices().next_back())
+            .map_or(run, |(last, _)| if last == 0 { run } else { last }),
+        _ => prefix + run,
+    }
+}
+
+/// A byte-level byte-pair-encoding tokenizer as used by the GPT family of models.
+///
+/// The tokenizer is loaded from a `vocab.json` mapping tokens to their ids and a `merges.txt`
+/// listing one merge per line, ordered by priority. Both files are distributed alongside most
+/// BPE-based models, so the tokenizer can be used fully offline.
+#[derive(Debug, Clone)]
+pub struct BpeTokenizer {
+    vocabulary: HashMap<String, u32>,
+    /// Priority of merging the left token with the right token, lower ranks are merged first.
+    ranks: HashMap<String, HashMap<String, usize>>,
+    byte_to_char: [char; 256],
+}
+
+impl BpeTokenizer {
+    /// Creates a tokenizer from the contents of a `vocab.json` and a `merges.txt` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TokenizerError`] if the vocabulary is not a JSON object mapping tokens to ids, if
+    /// a merge is not a pair of tokens, or if the vocabulary does not contain every single byte.
+    pub fn new(vocabulary: &str, merges: &str) -> Result<Self, Report<TokenizerError>> {
+        let vocabulary: HashMap<String, u32> = serde_json::from_str(vocabulary)
+            .change_context(TokenizerError)
+            .attach_printable("the vocabulary is not a JSON object mapping tokens to ids")?;
+
+        let mut ranks = HashMap::<_, HashMap<_, _>>::new();
+        let mut rank = 0;
+        for (line_number, line) in merges.lines().enumerate() {
+            if line.starts_with("#version") || line.trim().is_empty() {
+                continue;
+            }
+
+            let Some((left, right)) = line.split_once(' ') else {
+                bail!(Report::new(TokenizerError).attach_printable(format!(
+                    "line {}: expected a pair of tokens",
+                    line_number + 1
+                )));
+            };
+            ranks
+                .entry(left.to_owned())
+                .or_default()
+                .entry(right.to_owned())
+                .or_insert(rank);
+            rank += 1;
+        }
+
+        let byte_to_char = byte_to_char();
+        if let Some(missing) = byte_to_char
+            .iter()
+            .find(|char| !vocabulary.contains_key(char.encode_utf8(&mut [0; 4]) as &str))
+        {
+            bail!(Report::new(TokenizerError).attach_printable(format!(
+                "the vocabulary does not contain the byte token `{missing}`"
+            )));
+        }
+
+        Ok(Self {
+            vocabulary,
+            ranks,
+            byte_to_char,
+        })
+    }
+
+    /// Loads a tokenizer from a `vocab.json` and a `merges.txt` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TokenizerError`] if either file cannot be read or is invalid, see
+    /// [`BpeTokenizer::new`].
+    pub fn from_files(
+        vocabulary: impl AsRef<Path>,
+        merges: impl AsRef<Path>,
+    ) -> Result<Self, Report<TokenizerError>> {
+        let read = |path: &Path| {
+            fs::read_to_string(path)
+                .change_context(TokenizerError)
+                .attach_printable_lazy(|| format!("could not read `{}`", path.display()))
+        };
+
+        Self::new(&read(vocabulary.as_ref())?, &read(merges.as_ref())?)
+    }
+
+    fn merge_word(&self, word: &str) -> Vec<String> {
+        let mut symbols = word
+            .bytes()
+            .map(|byte| String::from(self.byte_to_char[usize::from(byte)]))
+            .collect::<Vec<_>>();
+
+        while symbols.len() > 1 {
+            let Some((left, right)) = symbols
+                .iter()
+                .zip(symbols.iter().skip(1))
+                .filter_map(|(left, right)| {
+                    self.ranks
+                        .get(left)
+                        .and_then(|ranks| ranks.get(right))
+                        .map(|rank| (rank, left, right))
+                })
+                .min_by_key(|(rank, ..)| **rank)
+                .map(|(_, left, right)| (left.clone(), right.clone()))
+            else {
+                break;
+            };
+
+            let mut merged = Vec::with_capacity(symbols.len());
+            let mut index = 0;
+            while index < symbols.len() {
+                if index + 1 < symbols.len()
+                    && symbols[index] == left
+                    && symbols[index + 1] == right
+                {
+                    merged.push(format!("{left}{right}"));
+                    index += 2;
+                } else {
+                    merged.push(core::mem::take(&mut symbols[index]));
+                    index += 1;
+                }
+            }
+            symbols = merged;
+        }
+
+        symbols
+    }
+
+    /// Encodes `text` into token ids.
+    #[must_use]
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        pre_tokenize(text)
+            .flat_map(|word| self.merge_word(word))
+            .filter_map(|symbol| self.vocabulary.get(&symbol).copied())
+            .collect()
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        pre_tokenize(text)
+            .map(|word| self.merge_word(word).len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pre_tokenize_like_gpt2() {
+        assert_eq!(
+            pre_tokenize("Hello world, it's 2024!  Bye\n").collect::<Vec<_>>(),
+            [
+                "Hello", " world", ",", " it", "'s", " 2024", "!", " ", " Bye", "\n"
+            ]
+        );
+    }
+
+    fn tokenizer() -> BpeTokenizer {
+        let mut vocabulary = (0..)
+            .zip(byte_to_char())
+            .map(|(id, char)| (String::from(char), id))
+            .collect::<HashMap<_, _>>();
+        vocabulary.extend([
+            ("lo".to_owned(), 256),
+            ("low".to_owned(), 257),
+            ("\u{120}low".to_owned(), 258),
+            ("er".to_owned(), 259),
+        ]);
+        let vocabulary = serde_json::to_string(&vocabulary).expect("serializable vocabulary");
+        let merges = "#version: 0.2\nl o\nlo w\n\u{120} low\ne r\n";
+
+        BpeTokenizer::new(&vocabulary, merges).expect("valid tokenizer")
+    }
+
+    #[test]
+    fn merges_by_rank() {
+        let tokenizer = tokenizer();
+
+        assert_eq!(tokenizer.encode("lower low"), [257, 259, 258]);
+        assert_eq!(tokenizer.count_tokens("lower low"), 3);
+        assert_eq!(tokenizer.count_tokens("slow"), 2);
+    }
+
+    #[test]
+    fn rejects_incomplete_vocabulary() {
+        let _: Report<_> = BpeTokenizer::new(r#"{"a": 0}"#, "").expect_err("missing bytes");
+        let _: Report<_> =
+            BpeTokenizer::new("{}", "#version: 0.2\nmalformed\n").expect_err("malformed merge");
+    }
+}
@@ -0,0 +1,130 @@
+//! Counting of tokens to enforce chunk budgets.
+//!
+//! Embedding models limit their input by tokens, not by characters, and every model family uses
+//! its own tokenizer. The [`Tokenizer`] trait allows a deployment to match the budget of whatever
+//! model it uses, either approximately with the [`HeuristicTokenizer`] or exactly with a
+//! [`BpeTokenizer`] loaded from the model's vocabulary.
+
+mod bpe;
+
+pub use self::bpe::{BpeTokenizer, TokenizerError};
+
+/// Counts the tokens a model would see for a given text.
+pub trait Tokenizer {
+    /// Returns the number of tokens in `text`.
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+impl<T> Tokenizer for &T
+where
+    T: Tokenizer + ?Sized,
+{
+    fn count_tokens(&self, text: &str) -> usize {
+        (**self).count_tokens(text)
+    }
+}
+
+/// Approximates token counts without a vocabulary.
+///
+/// Text is split at whitespace and every word counts as one token per started
+/// [`chars_per_token`] characters. Characters of scripts which are not separated by whitespace,
+/// such as Chinese or Japanese, count as one token each.
+///
+/// This matches typical BPE tokenizers on English prose within a few percent and is fully
+/// deterministic.
+///
+/// [`chars_per_token`]: Self::with_chars_per_token
+///
+/// # Example
+///
+/// ```rust
+/// use chonky::tokenizer::{HeuristicTokenizer, Tokenizer};
+///
+/// let tokenizer = HeuristicTokenizer::default();
+/// assert_eq!(tokenizer.count_tokens("Chunking is fun"), 4);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HeuristicTokenizer {
+    chars_per_token: usize,
+}
+
+impl HeuristicTokenizer {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { chars_per_token: 4 }
+    }
+
+    /// Sets the number of characters a single token covers on average.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chars_per_token` is zero.
+    #[must_use]
+    pub const fn with_chars_per_token(mut self, chars_per_token: usize) -> Self {
+        assert!(
+            chars_per_token > 0,
+            "a token has to cover at least one character"
+        );
+        self.chars_per_token = chars_per_token;
+        self
+    }
+}
+
+impl Default for HeuristicTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns `true` for characters of scripts which do not use whitespace to separate words.
+pub(crate) const fn is_unspaced_script(char: char) -> bool {
+    matches!(char,
+        '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{20000}'..='\u{2FA1F}' // CJK Unified Ideographs Extension B and later
+        | '\u{0E00}'..='\u{0E7F}' // Thai
+    )
+}
+
+impl Tokenizer for HeuristicTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.split_whitespace()
+            .map(|word| {
+                let mut tokens = 0;
+                let mut run: usize = 0;
+                for char in word.chars() {
+                    if is_unspaced_script(char) {
+                        tokens += run.div_ceil(self.chars_per_token) + 1;
+                        run = 0;
+                    } else {
+                        run += 1;
+                    }
+                }
+                tokens + run.div_ceil(self.chars_per_token)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heuristic_token_counts() {
+        let tokenizer = HeuristicTokenizer::default();
+
+        assert_eq!(tokenizer.count_tokens(""), 0);
+        assert_eq!(tokenizer.count_tokens("   \n\t"), 0);
+        assert_eq!(tokenizer.count_tokens("a bb ccc dddd"), 4);
+        assert_eq!(tokenizer.count_tokens("internationalization"), 5);
+        assert_eq!(
+            tokenizer.count_tokens("\u{4f60}\u{597d}\u{3002}Rust"),
+            3 + 1
+        );
+        assert_eq!(tokenizer.with_chars_per_token(1).count_tokens("one two"), 6);
+    }
+}
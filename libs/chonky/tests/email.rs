@@ -0,0 +1,118 @@
+use chonky::{
+    Extraction, SegmentKind,
+    email::{
+        self, CC_KEY, DATE_KEY, Email, FROM_KEY, MESSAGE_ID_KEY, SUBJECT_KEY, THREAD_ID_KEY, TO_KEY,
+    },
+};
+use error_stack::Report;
+
+fn read(name: &str) -> Vec<u8> {
+    std::fs::read(format!(
+        "{}/tests/fixtures/email/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .expect("could not read fixture")
+}
+
+/// Returns the kind and text of every segment.
+fn segments(extraction: &Extraction) -> Vec<(SegmentKind, &str)> {
+    extraction
+        .segments()
+        .iter()
+        .map(|segment| {
+            (
+                segment.kind.clone(),
+                segment.text(extraction.text()).expect("valid span"),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn multipart_message() {
+    let email = Email::parse(&read("multipart.eml")).expect("could parse email");
+
+    let metadata = email.metadata();
+    assert_eq!(
+        metadata.get(FROM_KEY),
+        Some("Ada Lovelace <ada@example.com>")
+    );
+    assert_eq!(
+        metadata.get(TO_KEY),
+        Some("Charles Babbage <charles@example.com>, team@example.com")
+    );
+    assert_eq!(metadata.get(CC_KEY), Some("Mary <mary@example.com>"));
+    assert_eq!(metadata.get(DATE_KEY), Some("2024-05-14T09:30:00+02:00"));
+    assert_eq!(
+        metadata.get(SUBJECT_KEY),
+        Some("Re: Analytical engine notes")
+    );
+    assert_eq!(metadata.get(MESSAGE_ID_KEY), Some("3@example.com"));
+    assert_eq!(metadata.get(THREAD_ID_KEY), Some("1@example.com"));
+
+    // The plain text body is decoded and preferred, the quoted reply and the attachment are
+    // dropped
+    assert_eq!(segments(email.extraction()), [
+        (
+            SegmentKind::Heading { level: 1 },
+            "Re: Analytical engine notes"
+        ),
+        (
+            SegmentKind::Paragraph,
+            "The Bernoulli numbers are computed in st\u{e9}ps, see the diagram."
+        ),
+        (SegmentKind::Paragraph, "It needs a few more cards."),
+    ]);
+}
+
+#[test]
+fn html_message() {
+    let email = Email::parse(&read("html.eml")).expect("could parse email");
+
+    assert_eq!(email.metadata().get(FROM_KEY), Some("events@example.com"));
+    assert_eq!(
+        email.metadata().get(THREAD_ID_KEY),
+        Some("tickets@example.com")
+    );
+
+    let text = email.extraction().text();
+    assert_eq!(
+        text,
+        "Lecture tickets\n\nTickets are available now.\n\nBook early."
+    );
+}
+
+#[test]
+fn mailbox() {
+    let source = read("thread.mbox");
+    assert!(email::is_mbox(&source));
+
+    let emails = email::parse_mbox(&source).expect("could parse mailbox");
+    let messages = emails
+        .iter()
+        .map(|email| {
+            (
+                email.metadata().get(MESSAGE_ID_KEY),
+                email.metadata().get(THREAD_ID_KEY),
+                email.extraction().text(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(messages, [
+        (
+            Some("1@example.com"),
+            Some("1@example.com"),
+            "Engine\n\nShall we build it?"
+        ),
+        (
+            Some("2@example.com"),
+            Some("1@example.com"),
+            "Re: Engine\n\nYes, next week."
+        ),
+    ]);
+}
+
+#[test]
+fn invalid_message() {
+    let _: Report<_> = Email::parse(b"").expect_err("empty message");
+}
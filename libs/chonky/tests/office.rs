@@ -0,0 +1,204 @@
+use chonky::{
+    Extraction, SegmentKind,
+    office::{
+        self, OfficeFormat, PART_KEY,
+        docx::{self, STYLE_KEY},
+        pptx::{self, SLIDE_KEY, SPEAKER_NOTES_KEY},
+        xlsx::{self, CELL_RANGE_KEY, SHEET_KEY},
+    },
+    table::Table,
+};
+
+fn read(name: &str) -> Vec<u8> {
+    std::fs::read(format!(
+        "{}/tests/fixtures/office/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .expect("could not read fixture")
+}
+
+/// Returns the kind, text and the metadata entry `key` of every segment.
+fn segments(extraction: &Extraction, key: &str) -> Vec<(SegmentKind, String, Option<String>)> {
+    extraction
+        .segments()
+        .iter()
+        .map(|segment| {
+            (
+                segment.kind.clone(),
+                segment
+                    .text(extraction.text())
+                    .expect("valid span")
+                    .to_owned(),
+                segment.metadata.get(key).map(ToOwned::to_owned),
+            )
+        })
+        .collect()
+}
+
+fn kinds(extraction: &Extraction) -> Vec<&str> {
+    extraction
+        .segments()
+        .iter()
+        .map(|segment| match segment.kind {
+            SegmentKind::Heading { .. } => "heading",
+            SegmentKind::Paragraph => "paragraph",
+            SegmentKind::ListItem { .. } => "list item",
+            SegmentKind::Table(_) => "table",
+            _ => "other",
+        })
+        .collect()
+}
+
+#[test]
+fn detects_formats() {
+    assert_eq!(
+        OfficeFormat::detect(&read("document.docx")),
+        Some(OfficeFormat::Docx)
+    );
+    assert_eq!(
+        OfficeFormat::detect(&read("presentation.pptx")),
+        Some(OfficeFormat::Pptx)
+    );
+    assert_eq!(
+        OfficeFormat::detect(&read("workbook.xlsx")),
+        Some(OfficeFormat::Xlsx)
+    );
+    assert_eq!(OfficeFormat::detect(b"plain text"), None);
+    let report = office::extract(b"plain text").expect_err("plain text is not an Office document");
+    assert!(format!("{report:?}").contains("not a DOCX, PPTX or XLSX document"));
+    assert_eq!(
+        OfficeFormat::from_extension("PPTX"),
+        Some(OfficeFormat::Pptx)
+    );
+}
+
+#[test]
+fn word_document() {
+    let extraction = docx::extract(&read("document.docx")).expect("could not extract document");
+
+    assert_eq!(kinds(&extraction), [
+        "heading",
+        "heading",
+        "paragraph",
+        "list item",
+        "list item",
+        "heading",
+        "table",
+        "paragraph"
+    ]);
+    let segments = segments(&extraction, STYLE_KEY);
+    assert_eq!(segments[0].0, SegmentKind::Heading { level: 1 });
+    assert_eq!(segments[5].0, SegmentKind::Heading { level: 2 });
+    assert_eq!(segments[4].0, SegmentKind::ListItem { depth: 1 });
+
+    // Deleted text is skipped, inserted text is kept
+    assert_eq!(segments[2].1, "Revenue grew in every region.");
+    assert_eq!(segments[5].2.as_deref(), Some("heading 2"));
+
+    // Vertically merged cells are repeated, the header is taken from the repeated row
+    assert_eq!(
+        segments[6].1,
+        "| Region | Quarter | Revenue |\n| --- | --- | --- |\n| Europe | Q1 | 120 |\n| Europe | \
+         Q2 | 135 |\n| Total | Total | 255 |"
+    );
+    assert_eq!(segments[6].2.as_deref(), Some("Table Grid"));
+
+    assert!(
+        extraction
+            .segments()
+            .iter()
+            .all(|segment| { segment.metadata.get(PART_KEY) == Some("word/document.xml") })
+    );
+}
+
+#[test]
+fn presentation() {
+    let extraction =
+        pptx::extract(&read("presentation.pptx")).expect("could not extract presentation");
+
+    assert_eq!(segments(&extraction, SLIDE_KEY), [
+        (
+            SegmentKind::Heading { level: 1 },
+            "Roadmap 2025".to_owned(),
+            Some("1".to_owned())
+        ),
+        (
+            SegmentKind::Paragraph,
+            "Product team".to_owned(),
+            Some("1".to_owned())
+        ),
+        (
+            SegmentKind::Heading { level: 1 },
+            "Goals".to_owned(),
+            Some("2".to_owned())
+        ),
+        (
+            SegmentKind::ListItem { depth: 0 },
+            "Ship the beta".to_owned(),
+            Some("2".to_owned())
+        ),
+        (
+            SegmentKind::ListItem { depth: 1 },
+            "Collect feedback".to_owned(),
+            Some("2".to_owned())
+        ),
+        (
+            SegmentKind::Paragraph,
+            "Mention the pilot customers.".to_owned(),
+            Some("2".to_owned())
+        ),
+        (
+            SegmentKind::Heading { level: 1 },
+            "Timeline".to_owned(),
+            Some("3".to_owned())
+        ),
+        (
+            SegmentKind::Table(Table::new(
+                vec![
+                    vec!["Milestone".to_owned(), "Date".to_owned()],
+                    vec!["Beta".to_owned(), "March".to_owned()],
+                    vec!["Launch".to_owned(), "June".to_owned()],
+                ],
+                1
+            )),
+            "| Milestone | Date |\n| --- | --- |\n| Beta | March |\n| Launch | June |".to_owned(),
+            Some("3".to_owned())
+        ),
+    ]);
+
+    let notes = extraction
+        .segments()
+        .iter()
+        .filter(|segment| segment.metadata.get(SPEAKER_NOTES_KEY) == Some("true"))
+        .map(|segment| segment.metadata.get(PART_KEY))
+        .collect::<Vec<_>>();
+    assert_eq!(notes, [Some("ppt/notesSlides/notesSlide1.xml")]);
+}
+
+#[test]
+fn workbook() {
+    let extraction = xlsx::extract(&read("workbook.xlsx")).expect("could not extract workbook");
+
+    // The empty sheet is skipped
+    assert_eq!(kinds(&extraction), ["heading", "table", "heading", "table"]);
+    let sheets = segments(&extraction, SHEET_KEY);
+    assert_eq!(sheets[0].1, "Sales");
+    assert_eq!(sheets[2].1, "Notes");
+    assert_eq!(sheets[3].2.as_deref(), Some("Notes"));
+
+    // Rich text, formulas and booleans are extracted with their values, empty rows are skipped
+    let ranges = segments(&extraction, CELL_RANGE_KEY);
+    assert_eq!(
+        ranges[1].1,
+        "| Region | Units | Price |\n| --- | --- | --- |\n| North America | 12 | 9.5 |\n| Europe \
+         | 24 | FALSE |"
+    );
+    assert_eq!(ranges[1].2.as_deref(), Some("B2:D5"));
+
+    // Merged cells are repeated, inline strings are extracted
+    assert_eq!(
+        ranges[3].1,
+        "| Prices exclude taxes | Prices exclude taxes |\n| See the | Sales sheet |"
+    );
+    assert_eq!(ranges[3].2.as_deref(), Some("A1:B2"));
+}
@@ -0,0 +1,155 @@
+use chonky::{
+    SegmentKind,
+    pdf::{self, BOUNDING_BOX_KEY, BoundingBox, PAGE_KEY, PAGE_SIZE_KEY},
+};
+
+fn extract(name: &str) -> Vec<(SegmentKind, String, u32)> {
+    let source = std::fs::read(format!(
+        "{}/tests/fixtures/pdf/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .expect("could not read fixture");
+    let extraction = pdf::extract(&source).expect("could not extract PDF");
+
+    extraction
+        .segments()
+        .iter()
+        .map(|segment| {
+            (
+                segment.kind.clone(),
+                segment
+                    .text(extraction.text())
+                    .expect("valid span")
+                    .to_owned(),
+                segment
+                    .metadata
+                    .get(PAGE_KEY)
+                    .expect("page number")
+                    .parse()
+                    .expect("valid page number"),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn single_column() {
+    assert_eq!(extract("single-column.pdf"), [
+        (
+            SegmentKind::Heading { level: 1 },
+            "Annual Report".to_owned(),
+            1
+        ),
+        (
+            SegmentKind::Heading { level: 2 },
+            "Introduction".to_owned(),
+            1
+        ),
+        (
+            SegmentKind::Paragraph,
+            "Chonky splits documents into segments and packs them into chunks which fit the \
+             context window of an embedding model."
+                .to_owned(),
+            1
+        ),
+        (
+            SegmentKind::Paragraph,
+            "Every chunk links back to the page it was extracted from.".to_owned(),
+            1
+        ),
+        (SegmentKind::Heading { level: 2 }, "Results".to_owned(), 2),
+        (
+            SegmentKind::Paragraph,
+            "Citations point to a page and a region on it.".to_owned(),
+            2
+        ),
+    ]);
+}
+
+#[test]
+fn two_columns() {
+    let texts = extract("two-columns.pdf")
+        .into_iter()
+        .map(|(_, text, _)| text)
+        .collect::<Vec<_>>();
+
+    assert_eq!(texts, [
+        "Two Column Layout",
+        "The left column starts here and continues on a second line of text.",
+        "A second paragraph in the left column.",
+        "The right column follows after the left column has been read completely.",
+        "It ends with this paragraph.",
+        "This footnote spans the full width of the page below both of the columns above it.",
+    ]);
+}
+
+#[test]
+fn composite_font() {
+    assert_eq!(extract("composite-font.pdf"), [(
+        SegmentKind::Paragraph,
+        "Gr\u{fc}\u{df}e aus K\u{f6}ln".to_owned(),
+        1
+    )]);
+}
+
+#[test]
+fn bounding_boxes() {
+    let source = std::fs::read(format!(
+        "{}/tests/fixtures/pdf/two-columns.pdf",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .expect("could not read fixture");
+    let extraction = pdf::extract(&source).expect("could not extract PDF");
+
+    let bounding_boxes = extraction
+        .segments()
+        .iter()
+        .map(|segment| {
+            assert_eq!(segment.metadata.get(PAGE_SIZE_KEY), Some("612.00 792.00"));
+            segment
+                .metadata
+                .get(BOUNDING_BOX_KEY)
+                .expect("bounding box")
+                .parse::<BoundingBox>()
+                .expect("valid bounding box")
+        })
+        .collect::<Vec<_>>();
+
+    // The title is at the top left, the second column starts right of the first one
+    let [title, left, _, right, ..] = bounding_boxes.as_slice() else {
+        panic!("expected at least four segments");
+    };
+    assert!(title.top < left.top);
+    assert!((title.left - 72.0).abs() < 0.01);
+    assert!((left.top - right.top).abs() < 0.01);
+    assert!(left.right < right.left);
+    assert!((right.left - 320.0).abs() < 0.01);
+}
+
+#[test]
+fn tables() {
+    let segments = extract("table.pdf");
+    let kinds = segments
+        .iter()
+        .map(|(kind, text, _)| match kind {
+            SegmentKind::Table(table) => {
+                assert_eq!(table.header_rows(), 1);
+                assert_eq!(table.columns(), 3);
+                "table"
+            }
+            SegmentKind::Heading { .. } => "heading",
+            _ => text.as_str(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, [
+        "heading",
+        "Revenue grew in every region.",
+        "table",
+        "All figures are in thousands.",
+    ]);
+    assert_eq!(
+        segments[2].1,
+        "| Region | Revenue | Growth |\n| --- | --- | --- |\n| North | 1,200 | 12% |\n| South | \
+         950 | 8% |"
+    );
+}
@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+
+use authorization::AuthorizationApi;
+use graph::store::{
+    EntityStore,
+    knowledge::{
+        CountEntitiesParams, CreateEntityParams, DeleteEntitiesParams, EntityDeletionReason,
+        PatchEntityParams,
+    },
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    knowledge::{
+        entity::{Entity, EntityId, ProvidedEntityEditionProvenance},
+        link::LinkData,
+        property::{
+            Property, PropertyObject, PropertyPatchOperation, PropertyPath, PropertyProvenance,
+            PropertyWithMetadata, PropertyWithMetadataObject,
+        },
+    },
+    owned_by_id::OwnedById,
+};
+use hash_graph_store::{
+    filter::Filter,
+    subgraph::temporal_axes::{
+        PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
+    },
+};
+use pretty_assertions::assert_eq;
+use temporal_versioning::TemporalBound;
+use type_system::url::{BaseUrl, OntologyTypeVersion, VersionedUrl};
+
+use crate::{DatabaseApi, DatabaseTestWrapper};
+
+async fn seed<A: AuthorizationApi>(
+    database: &mut DatabaseTestWrapper<A>,
+) -> DatabaseApi<'_, &mut A> {
+    database
+        .seed(
+            [
+                data_type::VALUE_V1,
+                data_type::TEXT_V1,
+                data_type::NUMBER_V1,
+            ],
+            [
+                property_type::NAME_V1,
+                property_type::AGE_V1,
+                property_type::FAVORITE_SONG_V1,
+                property_type::FAVORITE_FILM_V1,
+                property_type::HOBBY_V1,
+                property_type::INTERESTS_V1,
+            ],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::link::ACQUAINTANCE_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database")
+}
+
+fn person_entity_type_id() -> VersionedUrl {
+    VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    }
+}
+
+fn friend_of_entity_type_id() -> VersionedUrl {
+    VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/friend-of/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    }
+}
+
+fn alice() -> PropertyObject {
+    serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity")
+}
+
+fn bob() -> PropertyObject {
+    serde_json::from_str(entity::PERSON_BOB_V1).expect("could not parse entity")
+}
+
+fn temporal_axes() -> QueryTemporalAxesUnresolved {
+    QueryTemporalAxesUnresolved::DecisionTime {
+        pinned: PinnedTemporalAxisUnresolved::new(None),
+        variable: VariableTemporalAxisUnresolved::new(Some(TemporalBound::Unbounded), None),
+    }
+}
+
+async fn create_person<A: AuthorizationApi>(
+    api: &mut DatabaseApi<'_, A>,
+    properties: PropertyObject,
+) -> Entity {
+    api.create_entity(api.account_id, CreateEntityParams {
+        owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+        entity_uuid: None,
+        decision_time: None,
+        entity_type_ids: HashSet::from([person_entity_type_id()]),
+        properties: PropertyWithMetadataObject::from_parts(properties, None)
+            .expect("could not create property with metadata object"),
+        confidence: None,
+        link_data: None,
+        draft: false,
+        relationships: [],
+        provenance: ProvidedEntityEditionProvenance::default(),
+    })
+    .await
+    .expect("could not create entity")
+}
+
+async fn create_friend_of<A: AuthorizationApi>(
+    api: &mut DatabaseApi<'_, A>,
+    left_entity_id: EntityId,
+    right_entity_id: EntityId,
+) -> Entity {
+    api.create_entity(api.account_id, CreateEntityParams {
+        owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+        entity_uuid: None,
+        decision_time: None,
+        entity_type_ids: HashSet::from([friend_of_entity_type_id()]),
+        properties: PropertyWithMetadataObject::from_parts(PropertyObject::empty(), None)
+            .expect("could not create property with metadata object"),
+        confidence: None,
+        link_data: Some(LinkData {
+            left_entity_id,
+            right_entity_id,
+            left_entity_confidence: None,
+            left_entity_provenance: PropertyProvenance::default(),
+            right_entity_confidence: None,
+            right_entity_provenance: PropertyProvenance::default(),
+        }),
+        draft: false,
+        relationships: [],
+        provenance: ProvidedEntityEditionProvenance::default(),
+    })
+    .await
+    .expect("could not create link")
+}
+
+async fn count_entities<A: AuthorizationApi>(api: &DatabaseApi<'_, A>) -> usize {
+    api.count_entities(api.account_id, CountEntitiesParams {
+        filter: Filter::All(Vec::new()),
+        temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+            pinned: PinnedTemporalAxisUnresolved::new(None),
+            variable: VariableTemporalAxisUnresolved::new(None, None),
+        },
+        include_drafts: true,
+    })
+    .await
+    .expect("could not count entities")
+}
+
+#[must_use]
+async fn check_entity_exists<A: AuthorizationApi>(api: &DatabaseApi<'_, A>, id: EntityId) -> bool {
+    api.get_entity_by_id(api.account_id, id, None, None)
+        .await
+        .is_ok()
+}
+
+#[tokio::test]
+async fn delete_entity_with_links() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = seed(&mut database).await;
+
+    let alice_entity = create_person(&mut api, alice()).await;
+    let bob_entity = create_person(&mut api, bob()).await;
+    let alice_id = alice_entity.metadata.record_id.entity_id;
+    let bob_id = bob_entity.metadata.record_id.entity_id;
+    let link_entity = create_friend_of(&mut api, alice_id, bob_id).await;
+    let link_id = link_entity.metadata.record_id.entity_id;
+
+    api.patch_entity(api.account_id, PatchEntityParams {
+        entity_id: alice_id,
+        properties: vec![PropertyPatchOperation::Replace {
+            path: PropertyPath::default(),
+            property: PropertyWithMetadata::from_parts(Property::Object(bob()), None)
+                .expect("could not create property with metadata"),
+        }],
+        entity_type_ids: HashSet::new(),
+        archived: None,
+        draft: None,
+        decision_time: None,
+        confidence: None,
+        provenance: ProvidedEntityEditionProvenance::default(),
+    })
+    .await
+    .expect("could not patch entity");
+    assert_eq!(count_entities(&api).await, 3);
+
+    let response = api
+        .permanently_delete_entities(api.account_id, DeleteEntitiesParams {
+            filter: Filter::for_entity_by_entity_id(alice_id),
+            temporal_axes: temporal_axes(),
+            include_drafts: false,
+            dry_run: false,
+        })
+        .await
+        .expect("could not delete entities");
+    assert!(!response.dry_run);
+
+    let deleted = response
+        .entities
+        .iter()
+        .map(|entity| (entity.entity_id, entity.reason))
+        .collect::<Vec<_>>();
+    assert_eq!(deleted, [
+        (alice_id, EntityDeletionReason::Matched),
+        (link_id, EntityDeletionReason::LinkEndpointDeleted),
+    ]);
+    assert_eq!(response.entities[0].edition_ids.len(), 2);
+    assert_eq!(response.entities[0].num_temporal_versions, 3);
+    assert_eq!(response.entities[1].edition_ids.len(), 1);
+
+    assert_eq!(count_entities(&api).await, 1);
+    assert!(!check_entity_exists(&api, alice_id).await);
+    assert!(!check_entity_exists(&api, link_id).await);
+    assert!(check_entity_exists(&api, bob_id).await);
+}
+
+#[tokio::test]
+async fn dry_run_does_not_delete() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = seed(&mut database).await;
+
+    let alice_entity = create_person(&mut api, alice()).await;
+    let bob_entity = create_person(&mut api, bob()).await;
+    let alice_id = alice_entity.metadata.record_id.entity_id;
+    let bob_id = bob_entity.metadata.record_id.entity_id;
+    let link_entity = create_friend_of(&mut api, alice_id, bob_id).await;
+
+    let response = api
+        .permanently_delete_entities(api.account_id, DeleteEntitiesParams {
+            filter: Filter::for_entity_by_entity_id(bob_id),
+            temporal_axes: temporal_axes(),
+            include_drafts: false,
+            dry_run: true,
+        })
+        .await
+        .expect("could not delete entities");
+    assert!(response.dry_run);
+    assert_eq!(
+        response
+            .entities
+            .iter()
+            .map(|entity| entity.entity_id)
+            .collect::<Vec<_>>(),
+        [bob_id, link_entity.metadata.record_id.entity_id]
+    );
+
+    assert_eq!(count_entities(&api).await, 3);
+}
@@ -0,0 +1,470 @@
+use core::iter;
+use std::collections::HashSet;
+
+use authorization::AuthorizationApi;
+use error_stack::Report;
+use graph::store::{
+    EntityStore, QueryError,
+    knowledge::{
+        CreateEntityParams, EntityKeywordSearch, EntitySearchSignal, EntityVectorSearch,
+        PatchEntityParams, SearchEntitiesParams, SearchEntitiesResponse,
+        UpdateEntityEmbeddingsParams,
+    },
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    Embedding,
+    knowledge::{
+        entity::{Entity, EntityEmbedding, EntityId, EntityUuid, ProvidedEntityEditionProvenance},
+        property::{
+            Property, PropertyObject, PropertyPatchOperation, PropertyPath, PropertyWithMetadata,
+            PropertyWithMetadataObject,
+        },
+    },
+    owned_by_id::OwnedById,
+};
+use hash_graph_store::{
+    entity::EntityQueryPath,
+    filter::{Filter, FilterExpression, Parameter},
+    subgraph::temporal_axes::{
+        PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
+    },
+};
+use hash_status::StatusCode;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use temporal_versioning::{TemporalBound, Timestamp};
+use type_system::url::{BaseUrl, OntologyTypeVersion, VersionedUrl};
+use uuid::Uuid;
+
+use crate::{DatabaseApi, DatabaseTestWrapper, StoreBackend};
+
+const PAGE_TEXTS: [&str; 4] = [
+    "A blank page",
+    "A blank page, a blank line and a blank space",
+    "A blank page and a blank line",
+    "Intentionally left empty",
+];
+
+/// The angles in degrees between the embeddings of the pages and the [`query_embedding`].
+///
+/// The cosine distances are `0.5`, `0.13`, `1.0` and `0.0` respectively.
+const PAGE_EMBEDDING_ANGLES: [f32; 4] = [60.0, 30.0, 90.0, 0.0];
+
+fn embedding(angle: f32) -> Embedding<'static> {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    iter::once(cos)
+        .chain(iter::once(sin))
+        .chain(iter::repeat(0.0))
+        .take(Embedding::DIM)
+        .collect()
+}
+
+fn query_embedding() -> Embedding<'static> {
+    embedding(0.0)
+}
+
+async fn insert<A: AuthorizationApi>(
+    database: &mut DatabaseTestWrapper<A>,
+) -> DatabaseApi<'_, &mut A> {
+    let mut api = database
+        .seed(
+            [
+                data_type::VALUE_V1,
+                data_type::TEXT_V1,
+                data_type::NUMBER_V1,
+            ],
+            [
+                property_type::NAME_V1,
+                property_type::AGE_V1,
+                property_type::TEXT_V1,
+                property_type::FAVORITE_SONG_V1,
+                property_type::FAVORITE_FILM_V1,
+                property_type::HOBBY_V1,
+                property_type::INTERESTS_V1,
+            ],
+            [
+                entity_type::PERSON_V1,
+                entity_type::PAGE_V1,
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::link::ACQUAINTANCE_OF_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_entity_type = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    };
+    let page_entity_type = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/page/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    };
+
+    let alice: PropertyObject =
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let pages = PAGE_TEXTS.map(|text| {
+        serde_json::from_value::<PropertyObject>(json!({
+            "https://blockprotocol.org/@alice/types/property-type/text/": text
+        }))
+        .expect("could not parse entity")
+    });
+    let entities = iter::once((alice, &person_entity_type, None)).chain(
+        pages
+            .into_iter()
+            .zip(PAGE_EMBEDDING_ANGLES)
+            .map(|(page, angle)| (page, &page_entity_type, Some(embedding(angle)))),
+    );
+
+    for (idx, (properties, type_id, embedding)) in entities.enumerate() {
+        let entity = api
+            .create_entity(api.account_id, CreateEntityParams {
+                owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+                entity_uuid: Some(EntityUuid::new(Uuid::from_u128(idx as u128))),
+                decision_time: None,
+                entity_type_ids: HashSet::from([type_id.clone()]),
+                properties: PropertyWithMetadataObject::from_parts(properties, None)
+                    .expect("could not create property with metadata object"),
+                confidence: None,
+                link_data: None,
+                draft: false,
+                relationships: [],
+                provenance: ProvidedEntityEditionProvenance::default(),
+            })
+            .await
+            .expect("could not create entity");
+
+        if let Some(embedding) = embedding {
+            api.update_entity_embeddings(api.account_id, UpdateEntityEmbeddingsParams {
+                entity_id: entity.metadata.record_id.entity_id,
+                embeddings: vec![EntityEmbedding {
+                    property: None,
+                    embedding,
+                }],
+                updated_at_transaction_time: Timestamp::now(),
+                updated_at_decision_time: Timestamp::now(),
+                reset: true,
+            })
+            .await
+            .expect("could not update embeddings");
+        }
+    }
+
+    api
+}
+
+fn vector_search(weight: f64) -> EntityVectorSearch<'static> {
+    EntityVectorSearch {
+        embedding: query_embedding(),
+        max_distance: 0.75,
+        weight,
+    }
+}
+
+fn keyword_search(weight: f64) -> EntityKeywordSearch {
+    EntityKeywordSearch {
+        query: "blank".parse().expect("could not parse text search query"),
+        weight,
+    }
+}
+
+fn search_params(
+    filter: Filter<'static, Entity>,
+    vector: Option<EntityVectorSearch<'static>>,
+    keyword: Option<EntityKeywordSearch>,
+    limit: usize,
+) -> SearchEntitiesParams<'static> {
+    SearchEntitiesParams {
+        filter,
+        temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+            pinned: PinnedTemporalAxisUnresolved::new(None),
+            variable: VariableTemporalAxisUnresolved::new(None, None),
+        },
+        include_drafts: false,
+        vector,
+        keyword,
+        limit,
+        rank_constant: 60,
+    }
+}
+
+/// Returns the indices of the found entities and the signals with their ranks.
+fn results(response: &SearchEntitiesResponse) -> Vec<(u128, Vec<(EntitySearchSignal, usize)>)> {
+    response
+        .results
+        .iter()
+        .map(|result| {
+            (
+                result
+                    .entity
+                    .metadata
+                    .record_id
+                    .entity_id
+                    .entity_uuid
+                    .into_uuid()
+                    .as_u128(),
+                result
+                    .matches
+                    .iter()
+                    .map(|search_match| (search_match.signal, search_match.rank))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn single_signal() {
+    let mut database = DatabaseTestWrapper::with_backend(StoreBackend::Postgres).await;
+    let api = insert(&mut database).await;
+
+    let response = api
+        .search_entities(
+            api.account_id,
+            search_params(Filter::All(Vec::new()), Some(vector_search(1.0)), None, 10),
+        )
+        .await
+        .expect("could not search entities");
+    assert_eq!(results(&response), [
+        (4, vec![(EntitySearchSignal::Vector, 1)]),
+        (2, vec![(EntitySearchSignal::Vector, 2)]),
+        (1, vec![(EntitySearchSignal::Vector, 3)]),
+    ]);
+
+    let response = api
+        .search_entities(
+            api.account_id,
+            search_params(Filter::All(Vec::new()), None, Some(keyword_search(1.0)), 10),
+        )
+        .await
+        .expect("could not search entities");
+    assert_eq!(results(&response), [
+        (2, vec![(EntitySearchSignal::Keyword, 1)]),
+        (3, vec![(EntitySearchSignal::Keyword, 2)]),
+        (1, vec![(EntitySearchSignal::Keyword, 3)]),
+    ]);
+
+    let _: Report<QueryError> = api
+        .search_entities(
+            api.account_id,
+            search_params(Filter::All(Vec::new()), None, None, 10),
+        )
+        .await
+        .expect_err("searched without any signal");
+}
+
+#[tokio::test]
+async fn reciprocal_rank_fusion() {
+    let mut database = DatabaseTestWrapper::with_backend(StoreBackend::Postgres).await;
+    let api = insert(&mut database).await;
+
+    let response = api
+        .search_entities(
+            api.account_id,
+            search_params(
+                Filter::All(Vec::new()),
+                Some(vector_search(1.0)),
+                Some(keyword_search(1.0)),
+                10,
+            ),
+        )
+        .await
+        .expect("could not search entities");
+    assert_eq!(results(&response), [
+        (2, vec![
+            (EntitySearchSignal::Vector, 2),
+            (EntitySearchSignal::Keyword, 1)
+        ]),
+        (1, vec![
+            (EntitySearchSignal::Vector, 3),
+            (EntitySearchSignal::Keyword, 3)
+        ]),
+        (4, vec![(EntitySearchSignal::Vector, 1)]),
+        (3, vec![(EntitySearchSignal::Keyword, 2)]),
+    ]);
+
+    for result in &response.results {
+        let score = result
+            .matches
+            .iter()
+            .map(|search_match| search_match.score)
+            .sum::<f64>();
+        assert!((result.score - score).abs() < f64::EPSILON);
+    }
+    assert!((response.results[0].score - (1.0 / 62.0 + 1.0 / 61.0)).abs() < f64::EPSILON);
+
+    // Only the best `limit` candidates of each signal are fused.
+    let response = api
+        .search_entities(
+            api.account_id,
+            search_params(
+                Filter::All(Vec::new()),
+                Some(vector_search(1.0)),
+                Some(keyword_search(1.0)),
+                2,
+            ),
+        )
+        .await
+        .expect("could not search entities");
+    assert_eq!(results(&response), [
+        (2, vec![
+            (EntitySearchSignal::Vector, 2),
+            (EntitySearchSignal::Keyword, 1)
+        ]),
+        (4, vec![(EntitySearchSignal::Vector, 1)]),
+    ]);
+}
+
+#[tokio::test]
+async fn weighted_signals() {
+    let mut database = DatabaseTestWrapper::with_backend(StoreBackend::Postgres).await;
+    let api = insert(&mut database).await;
+
+    let response = api
+        .search_entities(
+            api.account_id,
+            search_params(
+                Filter::All(Vec::new()),
+                Some(vector_search(1.0)),
+                Some(keyword_search(0.0)),
+                10,
+            ),
+        )
+        .await
+        .expect("could not search entities");
+    assert_eq!(
+        results(&response)
+            .into_iter()
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>(),
+        [4, 2, 1, 3]
+    );
+}
+
+#[tokio::test]
+async fn respects_filter() {
+    let mut database = DatabaseTestWrapper::with_backend(StoreBackend::Postgres).await;
+    let api = insert(&mut database).await;
+
+    let filter = Filter::Not(Box::new(Filter::Equal(
+        Some(FilterExpression::Path {
+            path: EntityQueryPath::Uuid,
+        }),
+        Some(FilterExpression::Parameter {
+            parameter: Parameter::Uuid(Uuid::from_u128(2)),
+            convert: None,
+        }),
+    )));
+    let response = api
+        .search_entities(
+            api.account_id,
+            search_params(
+                filter,
+                Some(vector_search(1.0)),
+                Some(keyword_search(1.0)),
+                10,
+            ),
+        )
+        .await
+        .expect("could not search entities");
+    assert_eq!(results(&response), [
+        (1, vec![
+            (EntitySearchSignal::Vector, 2),
+            (EntitySearchSignal::Keyword, 2)
+        ]),
+        (4, vec![(EntitySearchSignal::Vector, 1)]),
+        (3, vec![(EntitySearchSignal::Keyword, 1)]),
+    ]);
+}
+
+#[tokio::test]
+async fn ranks_entities_once() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = insert(&mut database).await;
+
+    let page = serde_json::from_value::<PropertyObject>(json!({
+        "https://blockprotocol.org/@alice/types/property-type/text/": "A blank line"
+    }))
+    .expect("could not parse entity");
+    api.patch_entity(api.account_id, PatchEntityParams {
+        entity_id: EntityId {
+            owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+            entity_uuid: EntityUuid::new(Uuid::from_u128(3)),
+            draft_id: None,
+        },
+        properties: vec![PropertyPatchOperation::Replace {
+            path: PropertyPath::default(),
+            property: PropertyWithMetadata::from_parts(Property::Object(page), None)
+                .expect("could not create property with metadata"),
+        }],
+        entity_type_ids: HashSet::new(),
+        archived: None,
+        draft: None,
+        decision_time: None,
+        confidence: None,
+        provenance: ProvidedEntityEditionProvenance::default(),
+    })
+    .await
+    .expect("could not update entity");
+
+    // Both editions of the updated page match, but the page is only ranked once.
+    let mut params = search_params(Filter::All(Vec::new()), None, Some(keyword_search(1.0)), 3);
+    params.temporal_axes = QueryTemporalAxesUnresolved::DecisionTime {
+        pinned: PinnedTemporalAxisUnresolved::new(None),
+        variable: VariableTemporalAxisUnresolved::new(Some(TemporalBound::Unbounded), None),
+    };
+    let response = api
+        .search_entities(api.account_id, params)
+        .await
+        .expect("could not search entities");
+    let mut ranked = results(&response);
+    ranked.sort_by_key(|(idx, _)| *idx);
+    assert_eq!(
+        ranked
+            .into_iter()
+            .map(|(idx, matches)| (idx, matches.len()))
+            .collect::<Vec<_>>(),
+        [(1, 1), (2, 1), (3, 1)]
+    );
+}
+
+#[tokio::test]
+async fn invalid_parameters() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let api = insert(&mut database).await;
+
+    for params in [
+        search_params(Filter::All(Vec::new()), None, None, 10),
+        search_params(Filter::All(Vec::new()), Some(vector_search(1.0)), None, 0),
+        search_params(Filter::All(Vec::new()), Some(vector_search(-1.0)), None, 10),
+        search_params(
+            Filter::All(Vec::new()),
+            None,
+            Some(keyword_search(f64::NAN)),
+            10,
+        ),
+        search_params(
+            Filter::All(Vec::new()),
+            Some(vector_search(1.0)),
+            Some(keyword_search(f64::INFINITY)),
+            10,
+        ),
+    ] {
+        let report = api
+            .search_entities(api.account_id, params)
+            .await
+            .expect_err("searched with invalid parameters");
+        assert_eq!(
+            report.request_ref::<StatusCode>().next(),
+            Some(&StatusCode::InvalidArgument),
+        );
+    }
+}
@@ -0,0 +1,440 @@
+use alloc::borrow::Cow;
+use core::iter;
+use std::collections::HashSet;
+
+use authorization::AuthorizationApi;
+use graph::store::{
+    EntityQuerySorting, EntityQuerySortingRecord, EntityStore, Ordering,
+    knowledge::{CreateEntityParams, GetEntitiesParams},
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    knowledge::{
+        entity::{Entity, EntityUuid, ProvidedEntityEditionProvenance},
+        property::{PropertyObject, PropertyWithMetadataObject},
+    },
+    owned_by_id::OwnedById,
+};
+use hash_graph_store::{
+    entity::EntityQueryPath,
+    filter::{Filter, FilterExpression, JsonPath, Parameter, ParameterList, PathToken},
+    subgraph::temporal_axes::{
+        PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
+    },
+};
+use hash_status::StatusCode;
+use pretty_assertions::assert_eq;
+use serde_json::{Value as JsonValue, json};
+use type_system::url::{BaseUrl, OntologyTypeVersion, VersionedUrl};
+use uuid::Uuid;
+
+use crate::{DatabaseApi, DatabaseTestWrapper, StoreBackend};
+
+const PAGE_TEXTS: [&str; 4] = [
+    "A blank page",
+    "A blank page, a blank line and a blank space",
+    "A blank page and a blank line",
+    "Intentionally left empty",
+];
+
+const NICKNAME_V1: &str = r#"{
+  "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/property-type",
+  "kind": "propertyType",
+  "$id": "https://blockprotocol.org/@alice/types/property-type/nickname/v/1",
+  "title": "Nickname",
+  "description": "A nickname, which may be explicitly set to null.",
+  "oneOf": [
+    { "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1" },
+    { "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/null/v/1" }
+  ]
+}"#;
+
+const PROFILE_V1: &str = r#"{
+  "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+  "kind": "entityType",
+  "$id": "https://blockprotocol.org/@alice/types/entity-type/profile/v/1",
+  "type": "object",
+  "title": "Profile",
+  "description": "A profile of a user.",
+  "properties": {
+    "https://blockprotocol.org/@alice/types/property-type/nickname/": {
+      "$ref": "https://blockprotocol.org/@alice/types/property-type/nickname/v/1"
+    }
+  }
+}"#;
+
+async fn insert<A: AuthorizationApi>(
+    database: &mut DatabaseTestWrapper<A>,
+) -> DatabaseApi<'_, &mut A> {
+    let mut api = database
+        .seed(
+            [
+                data_type::VALUE_V1,
+                data_type::TEXT_V1,
+                data_type::NUMBER_V1,
+                data_type::NULL_V1,
+            ],
+            [
+                property_type::NAME_V1,
+                property_type::AGE_V1,
+                property_type::TEXT_V1,
+                property_type::FAVORITE_SONG_V1,
+                property_type::FAVORITE_FILM_V1,
+                property_type::HOBBY_V1,
+                property_type::INTERESTS_V1,
+                NICKNAME_V1,
+            ],
+            [
+                entity_type::PERSON_V1,
+                entity_type::PAGE_V1,
+                PROFILE_V1,
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::link::ACQUAINTANCE_OF_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_entity_type = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    };
+    let page_entity_type = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/page/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    };
+
+    let alice: PropertyObject =
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let pages = PAGE_TEXTS.map(|text| {
+        serde_json::from_value::<PropertyObject>(json!({
+            "https://blockprotocol.org/@alice/types/property-type/text/": text
+        }))
+        .expect("could not parse entity")
+    });
+    let entities = iter::once((alice, &person_entity_type))
+        .chain(pages.into_iter().map(|page| (page, &page_entity_type)));
+
+    for (idx, (properties, type_id)) in entities.enumerate() {
+        let properties = PropertyWithMetadataObject::from_parts(properties, None)
+            .expect("could not create property with metadata object");
+        create(&mut api, idx as u128, properties, type_id).await;
+    }
+
+    api
+}
+
+async fn create<A: AuthorizationApi>(
+    api: &mut DatabaseApi<'_, A>,
+    idx: u128,
+    properties: PropertyWithMetadataObject,
+    type_id: &VersionedUrl,
+) {
+    api.create_entity(api.account_id, CreateEntityParams {
+        owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+        entity_uuid: Some(EntityUuid::new(Uuid::from_u128(idx))),
+        decision_time: None,
+        entity_type_ids: HashSet::from([type_id.clone()]),
+        properties,
+        confidence: None,
+        link_data: None,
+        draft: false,
+        relationships: [],
+        provenance: ProvidedEntityEditionProvenance::default(),
+    })
+    .await
+    .expect("could not create entity");
+}
+
+fn params(filter: Filter<'_, Entity>) -> GetEntitiesParams<'_> {
+    GetEntitiesParams {
+        filter,
+        temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+            pinned: PinnedTemporalAxisUnresolved::new(None),
+            variable: VariableTemporalAxisUnresolved::new(None, None),
+        },
+        sorting: EntityQuerySorting {
+            paths: vec![EntityQuerySortingRecord {
+                path: EntityQueryPath::Uuid,
+                ordering: Ordering::Ascending,
+                nulls: None,
+            }],
+            cursor: None,
+        },
+        limit: None,
+        conversions: Vec::new(),
+        include_count: false,
+        include_drafts: false,
+        include_web_ids: false,
+        include_created_by_ids: false,
+        include_edition_created_by_ids: false,
+        include_type_ids: false,
+    }
+}
+
+/// Returns the indices of the entities matching `filter` ordered by their UUID.
+async fn search<A: AuthorizationApi>(
+    api: &DatabaseApi<'_, A>,
+    filter: Filter<'_, Entity>,
+) -> Vec<u128> {
+    api.get_entities(api.account_id, params(filter))
+        .await
+        .expect("could not get entities")
+        .entities
+        .iter()
+        .map(|entity| {
+            entity
+                .metadata
+                .record_id
+                .entity_id
+                .entity_uuid
+                .into_uuid()
+                .as_u128()
+        })
+        .collect()
+}
+
+fn property_path(property_type: &'static str) -> FilterExpression<'static, Entity> {
+    FilterExpression::Path {
+        path: EntityQueryPath::Properties(Some(JsonPath::from_path_tokens(vec![
+            PathToken::Field(Cow::Borrowed(property_type)),
+        ]))),
+    }
+}
+
+fn text_property_path() -> FilterExpression<'static, Entity> {
+    property_path("https://blockprotocol.org/@alice/types/property-type/text/")
+}
+
+const fn text_parameter(text: &'static str) -> FilterExpression<'static, Entity> {
+    FilterExpression::Parameter {
+        parameter: Parameter::Text(Cow::Borrowed(text)),
+        convert: None,
+    }
+}
+
+fn uuid_in(indices: &[u128]) -> Filter<'static, Entity> {
+    Filter::In(
+        FilterExpression::Path {
+            path: EntityQueryPath::Uuid,
+        },
+        ParameterList::Parameters(
+            indices
+                .iter()
+                .map(|&idx| Parameter::Uuid(Uuid::from_u128(idx)))
+                .collect(),
+        ),
+    )
+}
+
+#[tokio::test]
+async fn in_list() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let api = insert(&mut database).await;
+
+    assert_eq!(search(&api, uuid_in(&[1, 3, 99])).await, [1, 3]);
+    assert_eq!(search(&api, uuid_in(&[])).await, [] as [u128; 0]);
+    assert_eq!(
+        search(&api, Filter::Not(Box::new(uuid_in(&[1, 3, 99])))).await,
+        [0, 2, 4]
+    );
+
+    let json = json!({
+        "in": [
+            { "path": ["uuid"] },
+            [Uuid::from_u128(2), Uuid::from_u128(4)]
+        ]
+    })
+    .to_string();
+    let filter =
+        serde_json::from_str::<Filter<Entity>>(&json).expect("could not deserialize filter");
+    assert_eq!(search(&api, filter).await, [2, 4]);
+}
+
+#[tokio::test]
+async fn exists() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let api = insert(&mut database).await;
+
+    assert_eq!(search(&api, Filter::Exists(text_property_path())).await, [
+        1, 2, 3, 4
+    ]);
+    assert_eq!(
+        search(
+            &api,
+            Filter::Not(Box::new(Filter::Exists(text_property_path())))
+        )
+        .await,
+        [0]
+    );
+    assert_eq!(
+        search(
+            &api,
+            Filter::Exists(property_path(
+                "https://blockprotocol.org/@alice/types/property-type/name/"
+            ))
+        )
+        .await,
+        [0]
+    );
+}
+
+#[tokio::test]
+async fn exists_json_null() {
+    const NICKNAME: &str = "https://blockprotocol.org/@alice/types/property-type/nickname/";
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = insert(&mut database).await;
+
+    let profile_entity_type = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/profile/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    };
+    let nickname = |value: JsonValue, data_type: &str| {
+        serde_json::from_value::<PropertyWithMetadataObject>(json!({
+            "value": {
+                NICKNAME: {
+                    "value": value,
+                    "metadata": {
+                        "dataTypeId": format!(
+                            "https://blockprotocol.org/@blockprotocol/types/data-type/{data_type}/v/1"
+                        )
+                    }
+                }
+            }
+        }))
+        .expect("could not parse entity")
+    };
+    create(
+        &mut api,
+        5,
+        nickname(JsonValue::Null, "null"),
+        &profile_entity_type,
+    )
+    .await;
+    create(
+        &mut api,
+        6,
+        nickname(json!("Al"), "text"),
+        &profile_entity_type,
+    )
+    .await;
+    create(
+        &mut api,
+        7,
+        PropertyWithMetadataObject::default(),
+        &profile_entity_type,
+    )
+    .await;
+
+    // A JSON `null` is treated the same as a missing property
+    assert_eq!(
+        search(&api, Filter::Exists(property_path(NICKNAME))).await,
+        [6]
+    );
+    assert_eq!(
+        search(&api, Filter::IsNull(property_path(NICKNAME))).await,
+        [0, 1, 2, 3, 4, 5, 7]
+    );
+    assert_eq!(
+        search(
+            &api,
+            Filter::Not(Box::new(Filter::Exists(property_path(NICKNAME))))
+        )
+        .await,
+        [0, 1, 2, 3, 4, 5, 7]
+    );
+    assert_eq!(
+        search(
+            &api,
+            Filter::Not(Box::new(Filter::IsNull(property_path(NICKNAME))))
+        )
+        .await,
+        [6]
+    );
+}
+
+#[tokio::test]
+async fn ilike() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let api = insert(&mut database).await;
+
+    for (pattern, expected) in [
+        ("%BLANK LINE%", vec![2, 3]),
+        ("a blank page", vec![1]),
+        ("a blank page%", vec![1, 2, 3]),
+        ("%EMPTY", vec![4]),
+        ("%alice%", vec![]),
+    ] {
+        assert_eq!(
+            search(
+                &api,
+                Filter::ILike(text_property_path(), text_parameter(pattern))
+            )
+            .await,
+            expected,
+            "unexpected result for `{pattern}`"
+        );
+    }
+}
+
+#[tokio::test]
+async fn matches() {
+    let mut database = DatabaseTestWrapper::with_backend(StoreBackend::Postgres).await;
+    let api = insert(&mut database).await;
+
+    for (pattern, expected) in [
+        ("^A blank page$", vec![1]),
+        ("line|empty", vec![2, 3, 4]),
+        ("(blank.*){3}", vec![2]),
+        ("^a blank", vec![]),
+        ("(?i)^a blank", vec![1, 2, 3]),
+        ("\\mline\\M", vec![2, 3]),
+        ("[[:<:]]space[[:>:]]", vec![2]),
+        ("***=blank.*", vec![]),
+    ] {
+        assert_eq!(
+            search(
+                &api,
+                Filter::Matches(text_property_path(), text_parameter(pattern))
+            )
+            .await,
+            expected,
+            "unexpected result for `{pattern}`"
+        );
+    }
+}
+
+#[tokio::test]
+async fn matches_invalid_pattern() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let api = insert(&mut database).await;
+
+    for pattern in ["(blank", "blank**", "(a)\\1"] {
+        let report = api
+            .get_entities(
+                api.account_id,
+                params(Filter::Matches(
+                    text_property_path(),
+                    text_parameter(pattern),
+                )),
+            )
+            .await
+            .expect_err("an invalid pattern should be rejected");
+        assert_eq!(
+            report.request_ref::<StatusCode>().next(),
+            Some(&StatusCode::InvalidArgument),
+            "unexpected status for `{pattern}`"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use core::iter;
+use std::collections::HashSet;
+
+use authorization::AuthorizationApi;
+use graph::store::{
+    EntityQuerySorting, EntityQuerySortingRecord, EntityStore, NullOrdering, Ordering,
+    knowledge::{CreateEntityParams, GetEntitiesParams, GetEntitiesResponse},
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    knowledge::{
+        entity::{Entity, EntityUuid, ProvidedEntityEditionProvenance},
+        property::{PropertyObject, PropertyWithMetadataObject},
+    },
+    owned_by_id::OwnedById,
+};
+use hash_graph_store::{
+    entity::EntityQueryPath,
+    filter::{Filter, FilterExpression, TextSearchQuery},
+    subgraph::temporal_axes::{
+        PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
+    },
+};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use type_system::url::{BaseUrl, OntologyTypeVersion, VersionedUrl};
+use uuid::Uuid;
+
+use crate::{DatabaseApi, DatabaseTestWrapper, StoreBackend};
+
+const PAGE_TEXTS: [&str; 4] = [
+    "A blank page",
+    "A blank page, a blank line and a blank space",
+    "A blank page and a blank line",
+    "Intentionally left empty",
+];
+
+async fn insert<A: AuthorizationApi>(
+    database: &mut DatabaseTestWrapper<A>,
+) -> DatabaseApi<'_, &mut A> {
+    let mut api = database
+        .seed(
+            [
+                data_type::VALUE_V1,
+                data_type::TEXT_V1,
+                data_type::NUMBER_V1,
+            ],
+            [
+                property_type::NAME_V1,
+                property_type::AGE_V1,
+                property_type::TEXT_V1,
+                property_type::FAVORITE_SONG_V1,
+                property_type::FAVORITE_FILM_V1,
+                property_type::HOBBY_V1,
+                property_type::INTERESTS_V1,
+            ],
+            [
+                entity_type::PERSON_V1,
+                entity_type::PAGE_V1,
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::link::ACQUAINTANCE_OF_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_entity_type = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    };
+    let page_entity_type = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/page/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    };
+
+    let alice: PropertyObject =
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let pages = PAGE_TEXTS.map(|text| {
+        serde_json::from_value::<PropertyObject>(json!({
+            "https://blockprotocol.org/@alice/types/property-type/text/": text
+        }))
+        .expect("could not parse entity")
+    });
+    let entities = iter::once((alice, &person_entity_type))
+        .chain(pages.into_iter().map(|page| (page, &page_entity_type)));
+
+    for (idx, (properties, type_id)) in entities.enumerate() {
+        api.create_entity(api.account_id, CreateEntityParams {
+            owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+            entity_uuid: Some(EntityUuid::new(Uuid::from_u128(idx as u128))),
+            decision_time: None,
+            entity_type_ids: HashSet::from([type_id.clone()]),
+            properties: PropertyWithMetadataObject::from_parts(properties, None)
+                .expect("could not create property with metadata object"),
+            confidence: None,
+            link_data: None,
+            draft: false,
+            relationships: [],
+            provenance: ProvidedEntityEditionProvenance::default(),
+        })
+        .await
+        .expect("could not create entity");
+    }
+
+    api
+}
+
+fn query(query: &str) -> TextSearchQuery {
+    query.parse().expect("could not parse text search query")
+}
+
+fn search_filter(text: &str) -> Filter<'static, Entity> {
+    Filter::FullTextSearch(
+        FilterExpression::Path {
+            path: EntityQueryPath::Properties(None),
+        },
+        query(text),
+    )
+}
+
+/// Returns the indices of the entities matching `filter` in the order of `sorting`.
+async fn search<A: AuthorizationApi>(
+    api: &DatabaseApi<'_, A>,
+    filter: Filter<'static, Entity>,
+    sorting: &[EntityQuerySortingRecord<'static>],
+    chunk_size: usize,
+) -> Vec<u128> {
+    let mut cursor = None;
+    let mut indices = Vec::new();
+
+    loop {
+        let GetEntitiesResponse {
+            entities,
+            cursor: new_cursor,
+            ..
+        } = api
+            .get_entities(api.account_id, GetEntitiesParams {
+                filter: filter.clone(),
+                temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+                    pinned: PinnedTemporalAxisUnresolved::new(None),
+                    variable: VariableTemporalAxisUnresolved::new(None, None),
+                },
+                sorting: EntityQuerySorting {
+                    paths: sorting.to_vec(),
+                    cursor: cursor.take(),
+                },
+                limit: Some(chunk_size),
+                conversions: Vec::new(),
+                include_count: false,
+                include_drafts: false,
+                include_web_ids: false,
+                include_created_by_ids: false,
+                include_edition_created_by_ids: false,
+                include_type_ids: false,
+            })
+            .await
+            .expect("could not get entities");
+
+        let num_entities = entities.len();
+        indices.extend(entities.iter().map(|entity| {
+            entity
+                .metadata
+                .record_id
+                .entity_id
+                .entity_uuid
+                .into_uuid()
+                .as_u128()
+        }));
+        if num_entities < chunk_size {
+            break;
+        }
+        match new_cursor {
+            Some(new_cursor) => cursor = Some(new_cursor),
+            None => break,
+        }
+    }
+
+    indices
+}
+
+const fn uuid_sorting() -> EntityQuerySortingRecord<'static> {
+    EntityQuerySortingRecord {
+        path: EntityQueryPath::Uuid,
+        ordering: Ordering::Ascending,
+        nulls: None,
+    }
+}
+
+#[tokio::test]
+async fn filter() {
+    let mut database = DatabaseTestWrapper::with_backend(StoreBackend::Postgres).await;
+    let api = insert(&mut database).await;
+
+    let sorting = [uuid_sorting()];
+    for (text, expected) in [
+        ("blank", vec![1, 2, 3]),
+        ("BLANK page", vec![1, 2, 3]),
+        ("blank space", vec![2]),
+        (r#""blank line""#, vec![2, 3]),
+        (r#""left empty""#, vec![4]),
+        ("ali*", vec![0]),
+        ("blank alice", vec![]),
+    ] {
+        assert_eq!(
+            search(&api, search_filter(text), &sorting, 10).await,
+            expected,
+            "unexpected result for `{text}`"
+        );
+    }
+
+    let negated = Filter::Not(Box::new(search_filter("blank")));
+    assert_eq!(search(&api, negated, &sorting, 10).await, [0, 4]);
+}
+
+#[tokio::test]
+async fn sort_by_rank() {
+    let mut database = DatabaseTestWrapper::with_backend(StoreBackend::Postgres).await;
+    let api = insert(&mut database).await;
+
+    let sorting = [
+        EntityQuerySortingRecord {
+            path: EntityQueryPath::TextSearchRank(query("blank")),
+            ordering: Ordering::Descending,
+            nulls: Some(NullOrdering::Last),
+        },
+        uuid_sorting(),
+    ];
+
+    for chunk_size in 1..=5 {
+        assert_eq!(
+            search(&api, search_filter("blank"), &sorting, chunk_size).await,
+            [2, 3, 1]
+        );
+        assert_eq!(
+            search(&api, Filter::All(Vec::new()), &sorting, chunk_size).await,
+            [2, 3, 1, 0, 4]
+        );
+    }
+}
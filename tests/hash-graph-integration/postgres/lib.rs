@@ -13,9 +13,13 @@ extern crate alloc;
 extern crate core;
 
 mod data_type;
+mod deletion;
 mod drafts;
 mod entity;
+mod entity_search;
 mod entity_type;
+mod filter_operators;
+mod full_text_search;
 mod interconnected_graph;
 mod links;
 mod multi_type;
@@ -23,6 +27,7 @@ mod partial_updates;
 mod property_metadata;
 mod property_type;
 mod sorting;
+mod store_parity;
 
 use std::collections::HashMap;
 
@@ -42,12 +47,14 @@ use graph::{
     Environment, load_env,
     store::{
         DataTypeStore, DatabaseConnectionInfo, DatabasePoolConfig, DatabaseType, EntityStore,
-        EntityTypeStore, InsertionError, PostgresStore, PostgresStorePool, PropertyTypeStore,
-        QueryError, StorePool, UpdateError,
+        EntityTypeStore, InsertionError, MemoryStore, MemoryStorePool, PostgresStore,
+        PostgresStorePool, PropertyTypeStore, QueryError, StorePool, UpdateError,
+        error::DeletionError,
         knowledge::{
-            CountEntitiesParams, CreateEntityParams, GetEntitiesParams, GetEntitiesResponse,
-            GetEntitySubgraphParams, GetEntitySubgraphResponse, PatchEntityParams,
-            UpdateEntityEmbeddingsParams, ValidateEntityError, ValidateEntityParams,
+            CountEntitiesParams, CreateEntityParams, DeleteEntitiesParams, DeleteEntitiesResponse,
+            GetEntitiesParams, GetEntitiesResponse, GetEntitySubgraphParams,
+            GetEntitySubgraphResponse, PatchEntityParams, UpdateEntityEmbeddingsParams,
+            ValidateEntityError, ValidateEntityParams,
         },
         ontology::{
             ArchiveDataTypeParams, ArchiveEntityTypeParams, ArchivePropertyTypeParams,
@@ -83,13 +90,56 @@ use tokio_postgres::{NoTls, Transaction};
 use type_system::schema::{DataType, EntityType, PropertyType};
 use uuid::Uuid;
 
+/// The store implementation the tests are run against.
+///
+/// Tests which assert the semantics of Postgres, e.g. of regular expressions or the ranking of a
+/// full-text search, are run against [`Self::Postgres`] regardless of `HASH_GRAPH_TEST_STORE`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StoreBackend {
+    Postgres,
+    Memory,
+}
+
+impl StoreBackend {
+    /// Reads the backend from `HASH_GRAPH_TEST_STORE`, defaulting to Postgres.
+    fn from_env() -> Self {
+        match std::env::var("HASH_GRAPH_TEST_STORE").as_deref() {
+            Ok("memory") => Self::Memory,
+            Ok("postgres") | Err(_) => Self::Postgres,
+            Ok(backend) => panic!("unknown store backend `{backend}`"),
+        }
+    }
+}
+
+enum TestConnection<A: AuthorizationApi> {
+    Postgres {
+        _pool: PostgresStorePool,
+        connection: <PostgresStorePool as StorePool>::Store<'static, A>,
+    },
+    Memory(MemoryStore<A>),
+}
+
+enum TestStore<'pool, A: AuthorizationApi> {
+    Postgres(PostgresStore<Transaction<'pool>, A>),
+    Memory(MemoryStore<A>),
+}
+
+/// Calls the same expression on the store of either backend.
+macro_rules! dispatch {
+    ($store:expr, | $binding:ident | $call:expr) => {
+        match $store {
+            TestStore::Postgres($binding) => $call,
+            TestStore::Memory($binding) => $call,
+        }
+    };
+}
+
 pub struct DatabaseTestWrapper<A: AuthorizationApi> {
-    _pool: PostgresStorePool,
-    connection: <PostgresStorePool as StorePool>::Store<'static, A>,
+    connection: TestConnection<A>,
 }
 
 pub struct DatabaseApi<'pool, A: AuthorizationApi> {
-    store: PostgresStore<Transaction<'pool>, A>,
+    store: TestStore<'pool, A>,
     account_id: AccountId,
 }
 
@@ -147,10 +197,26 @@ pub fn init_logging() {
 }
 
 impl DatabaseTestWrapper<NoAuthorization> {
+    /// Connects to the store selected by `HASH_GRAPH_TEST_STORE`.
     pub async fn new() -> Self {
+        Self::with_backend(StoreBackend::from_env()).await
+    }
+
+    /// Connects to the given store, independent of `HASH_GRAPH_TEST_STORE`.
+    pub async fn with_backend(backend: StoreBackend) -> Self {
         load_env(Environment::Test);
         init_logging();
 
+        if backend == StoreBackend::Memory {
+            let connection = MemoryStorePool::new()
+                .acquire_owned(NoAuthorization, None)
+                .await
+                .expect("could not acquire a memory store");
+            return Self {
+                connection: TestConnection::Memory(connection),
+            };
+        }
+
         let user = std::env::var("HASH_GRAPH_PG_USER").unwrap_or_else(|_| "graph".to_owned());
         let password =
             std::env::var("HASH_GRAPH_PG_PASSWORD").unwrap_or_else(|_| "graph".to_owned());
@@ -180,8 +246,10 @@ impl DatabaseTestWrapper<NoAuthorization> {
             .expect("could not acquire a database connection");
 
         Self {
-            _pool: pool,
-            connection,
+            connection: TestConnection::Postgres {
+                _pool: pool,
+                connection,
+            },
         }
     }
 }
@@ -198,84 +266,94 @@ impl<A: AuthorizationApi> DatabaseTestWrapper<A> {
         P: IntoIterator<Item = &'static str, IntoIter: Send> + Send,
         E: IntoIterator<Item = &'static str, IntoIter: Send> + Send,
     {
-        let mut store = self
-            .connection
-            .transaction()
-            .await
-            .expect("could not start test transaction");
-
-        let account_id = AccountId::new(Uuid::new_v4());
-        store
+        let store = match &mut self.connection {
+            TestConnection::Postgres { connection, .. } => TestStore::Postgres(
+                connection
+                    .transaction()
+                    .await
+                    .expect("could not start test transaction"),
+            ),
+            TestConnection::Memory(connection) => TestStore::Memory(
+                connection
+                    .transaction()
+                    .await
+                    .expect("could not start test transaction"),
+            ),
+        };
+        let mut api = DatabaseApi {
+            store,
+            account_id: AccountId::new(Uuid::new_v4()),
+        };
+        let account_id = api.account_id;
+
+        dispatch!(&mut api.store, |store| store
             .insert_account_id(account_id, InsertAccountIdParams { account_id })
-            .await
-            .expect("could not insert account id");
-        store
+            .await)
+        .expect("could not insert account id");
+        dispatch!(&mut api.store, |store| store
             .insert_web_id(account_id, InsertWebIdParams {
                 owned_by_id: OwnedById::new(account_id.into_uuid()),
                 owner: WebOwnerSubject::Account { id: account_id },
             })
-            .await
-            .expect("could not create web id");
-
-        store
-            .create_data_types(
-                account_id,
-                data_types.into_iter().map(|data_type_str| {
-                    let schema: DataType = serde_json::from_str(data_type_str)
-                        .expect("could not parse data type representation");
-                    CreateDataTypeParams {
-                        schema,
-                        classification: OntologyTypeClassificationMetadata::Owned {
-                            owned_by_id: OwnedById::new(account_id.into_uuid()),
-                        },
-                        relationships: data_type_relationships(),
-                        conflict_behavior: ConflictBehavior::Skip,
-                        provenance: ProvidedOntologyEditionProvenance::default(),
-                        conversions: HashMap::new(),
-                    }
-                }),
-            )
-            .await?;
-
-        store
-            .create_property_types(
-                account_id,
-                property_types.into_iter().map(|property_type_str| {
-                    let schema: PropertyType = serde_json::from_str(property_type_str)
-                        .expect("could not property data type representation");
-                    CreatePropertyTypeParams {
-                        schema,
-                        classification: OntologyTypeClassificationMetadata::Owned {
-                            owned_by_id: OwnedById::new(account_id.into_uuid()),
-                        },
-                        relationships: property_type_relationships(),
-                        conflict_behavior: ConflictBehavior::Skip,
-                        provenance: ProvidedOntologyEditionProvenance::default(),
-                    }
-                }),
-            )
-            .await?;
-
-        store
-            .create_entity_types(
-                account_id,
-                entity_types.into_iter().map(|entity_type_str| {
-                    let schema: EntityType = serde_json::from_str(entity_type_str)
-                        .expect("could not entity data type representation");
-                    CreateEntityTypeParams {
-                        schema,
-                        classification: OntologyTypeClassificationMetadata::Owned {
-                            owned_by_id: OwnedById::new(account_id.into_uuid()),
-                        },
-                        relationships: entity_type_relationships(),
-                        conflict_behavior: ConflictBehavior::Skip,
-                        provenance: ProvidedOntologyEditionProvenance::default(),
-                    }
-                }),
-            )
-            .await?;
-
-        Ok(DatabaseApi { store, account_id })
+            .await)
+        .expect("could not create web id");
+
+        api.create_data_types(
+            account_id,
+            data_types.into_iter().map(|data_type_str| {
+                let schema: DataType = serde_json::from_str(data_type_str)
+                    .expect("could not parse data type representation");
+                CreateDataTypeParams {
+                    schema,
+                    classification: OntologyTypeClassificationMetadata::Owned {
+                        owned_by_id: OwnedById::new(account_id.into_uuid()),
+                    },
+                    relationships: data_type_relationships(),
+                    conflict_behavior: ConflictBehavior::Skip,
+                    provenance: ProvidedOntologyEditionProvenance::default(),
+                    conversions: HashMap::new(),
+                }
+            }),
+        )
+        .await?;
+
+        api.create_property_types(
+            account_id,
+            property_types.into_iter().map(|property_type_str| {
+                let schema: PropertyType = serde_json::from_str(property_type_str)
+                    .expect("could not property data type representation");
+                CreatePropertyTypeParams {
+                    schema,
+                    classification: OntologyTypeClassificationMetadata::Owned {
+                        owned_by_id: OwnedById::new(account_id.into_uuid()),
+                    },
+                    relationships: property_type_relationships(),
+                    conflict_behavior: ConflictBehavior::Skip,
+                    provenance: ProvidedOntologyEditionProvenance::default(),
+                }
+            }),
+        )
+        .await?;
+
+        api.create_entity_types(
+            account_id,
+            entity_types.into_iter().map(|entity_type_str| {
+                let schema: EntityType = serde_json::from_str(entity_type_str)
+                    .expect("could not entity data type representation");
+                CreateEntityTypeParams {
+                    schema,
+                    classification: OntologyTypeClassificationMetadata::Owned {
+                        owned_by_id: OwnedById::new(account_id.into_uuid()),
+                    },
+                    relationships: entity_type_relationships(),
+                    conflict_behavior: ConflictBehavior::Skip,
+                    provenance: ProvidedOntologyEditionProvenance::default(),
+                }
+            }),
+        )
+        .await?;
+
+        Ok(api)
     }
 }
 
@@ -289,7 +367,9 @@ impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
         P: IntoIterator<Item = CreateDataTypeParams<R>, IntoIter: Send> + Send,
         R: IntoIterator<Item = DataTypeRelationAndSubject> + Send + Sync,
     {
-        self.store.create_data_types(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .create_data_types(actor_id, params)
+            .await)
     }
 
     async fn count_data_types(
@@ -297,7 +377,9 @@ impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: CountDataTypesParams<'_>,
     ) -> Result<usize, QueryError> {
-        self.store.count_data_types(actor_id, params).await
+        dispatch!(&self.store, |store| store
+            .count_data_types(actor_id, params)
+            .await)
     }
 
     async fn get_data_types(
@@ -317,7 +399,9 @@ impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
             })
             .await?;
 
-        let mut response = self.store.get_data_types(actor_id, params).await?;
+        let mut response = dispatch!(&self.store, |store| store
+            .get_data_types(actor_id, params)
+            .await)?;
 
         // We can ensure that `count_data_types` and `get_data_types` return the same count;
         assert_eq!(response.count, Some(count));
@@ -349,7 +433,9 @@ impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
             })
             .await?;
 
-        let mut response = self.store.get_data_type_subgraph(actor_id, params).await?;
+        let mut response = dispatch!(&self.store, |store| store
+            .get_data_type_subgraph(actor_id, params)
+            .await)?;
 
         // We can ensure that `count_data_types` and `get_data_type_subgraph` return the same count;
         assert_eq!(response.count, Some(count));
@@ -372,7 +458,9 @@ impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
     where
         R: IntoIterator<Item = DataTypeRelationAndSubject> + Send + Sync,
     {
-        self.store.update_data_type(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .update_data_type(actor_id, params)
+            .await)
     }
 
     async fn archive_data_type(
@@ -380,7 +468,9 @@ impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: ArchiveDataTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.store.archive_data_type(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .archive_data_type(actor_id, params)
+            .await)
     }
 
     async fn unarchive_data_type(
@@ -388,7 +478,9 @@ impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: UnarchiveDataTypeParams,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.store.unarchive_data_type(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .unarchive_data_type(actor_id, params)
+            .await)
     }
 
     async fn update_data_type_embeddings(
@@ -396,13 +488,15 @@ impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: UpdateDataTypeEmbeddingParams<'_>,
     ) -> Result<(), UpdateError> {
-        self.store
+        dispatch!(&mut self.store, |store| store
             .update_data_type_embeddings(actor_id, params)
-            .await
+            .await)
     }
 
     async fn reindex_data_type_cache(&mut self) -> Result<(), UpdateError> {
-        self.store.reindex_entity_type_cache().await
+        dispatch!(&mut self.store, |store| store
+            .reindex_entity_type_cache()
+            .await)
     }
 }
 
@@ -416,7 +510,9 @@ impl<A: AuthorizationApi> PropertyTypeStore for DatabaseApi<'_, A> {
         P: IntoIterator<Item = CreatePropertyTypeParams<R>, IntoIter: Send> + Send,
         R: IntoIterator<Item = PropertyTypeRelationAndSubject> + Send + Sync,
     {
-        self.store.create_property_types(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .create_property_types(actor_id, params)
+            .await)
     }
 
     async fn count_property_types(
@@ -424,7 +520,9 @@ impl<A: AuthorizationApi> PropertyTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: CountPropertyTypesParams<'_>,
     ) -> Result<usize, QueryError> {
-        self.store.count_property_types(actor_id, params).await
+        dispatch!(&self.store, |store| store
+            .count_property_types(actor_id, params)
+            .await)
     }
 
     async fn get_property_types(
@@ -444,7 +542,9 @@ impl<A: AuthorizationApi> PropertyTypeStore for DatabaseApi<'_, A> {
             })
             .await?;
 
-        let mut response = self.store.get_property_types(actor_id, params).await?;
+        let mut response = dispatch!(&self.store, |store| store
+            .get_property_types(actor_id, params)
+            .await)?;
 
         // We can ensure that `count_property_types` and `get_property_types` return the same count;
         assert_eq!(response.count, Some(count));
@@ -477,10 +577,9 @@ impl<A: AuthorizationApi> PropertyTypeStore for DatabaseApi<'_, A> {
             })
             .await?;
 
-        let mut response = self
-            .store
+        let mut response = dispatch!(&self.store, |store| store
             .get_property_type_subgraph(actor_id, params)
-            .await?;
+            .await)?;
 
         // We can ensure that `count_property_types` and `get_property_type_subgraph` return the
         // same count;
@@ -505,7 +604,9 @@ impl<A: AuthorizationApi> PropertyTypeStore for DatabaseApi<'_, A> {
     where
         R: IntoIterator<Item = PropertyTypeRelationAndSubject> + Send + Sync,
     {
-        self.store.update_property_type(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .update_property_type(actor_id, params)
+            .await)
     }
 
     async fn archive_property_type(
@@ -513,7 +614,9 @@ impl<A: AuthorizationApi> PropertyTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: ArchivePropertyTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.store.archive_property_type(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .archive_property_type(actor_id, params)
+            .await)
     }
 
     async fn unarchive_property_type(
@@ -521,7 +624,9 @@ impl<A: AuthorizationApi> PropertyTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: UnarchivePropertyTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.store.unarchive_property_type(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .unarchive_property_type(actor_id, params)
+            .await)
     }
 
     async fn update_property_type_embeddings(
@@ -529,9 +634,9 @@ impl<A: AuthorizationApi> PropertyTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: UpdatePropertyTypeEmbeddingParams<'_>,
     ) -> Result<(), UpdateError> {
-        self.store
+        dispatch!(&mut self.store, |store| store
             .update_property_type_embeddings(actor_id, params)
-            .await
+            .await)
     }
 }
 
@@ -545,7 +650,9 @@ impl<A: AuthorizationApi> EntityTypeStore for DatabaseApi<'_, A> {
         P: IntoIterator<Item = CreateEntityTypeParams<R>, IntoIter: Send> + Send,
         R: IntoIterator<Item = EntityTypeRelationAndSubject> + Send + Sync,
     {
-        self.store.create_entity_types(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .create_entity_types(actor_id, params)
+            .await)
     }
 
     async fn count_entity_types(
@@ -553,7 +660,9 @@ impl<A: AuthorizationApi> EntityTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: CountEntityTypesParams<'_>,
     ) -> Result<usize, QueryError> {
-        self.store.count_entity_types(actor_id, params).await
+        dispatch!(&self.store, |store| store
+            .count_entity_types(actor_id, params)
+            .await)
     }
 
     async fn get_entity_types(
@@ -573,7 +682,9 @@ impl<A: AuthorizationApi> EntityTypeStore for DatabaseApi<'_, A> {
             })
             .await?;
 
-        let mut response = self.store.get_entity_types(actor_id, params).await?;
+        let mut response = dispatch!(&self.store, |store| store
+            .get_entity_types(actor_id, params)
+            .await)?;
 
         // We can ensure that `count_entity_types` and `get_entity_types` return the same count;
         assert_eq!(response.count, Some(count));
@@ -605,10 +716,9 @@ impl<A: AuthorizationApi> EntityTypeStore for DatabaseApi<'_, A> {
             })
             .await?;
 
-        let mut response = self
-            .store
+        let mut response = dispatch!(&self.store, |store| store
             .get_entity_type_subgraph(actor_id, params)
-            .await?;
+            .await)?;
 
         // We can ensure that `count_entity_types` and `get_entity_type_subgraph` return the same
         // count;
@@ -632,7 +742,9 @@ impl<A: AuthorizationApi> EntityTypeStore for DatabaseApi<'_, A> {
     where
         R: IntoIterator<Item = EntityTypeRelationAndSubject> + Send + Sync,
     {
-        self.store.update_entity_type(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .update_entity_type(actor_id, params)
+            .await)
     }
 
     async fn archive_entity_type(
@@ -640,7 +752,9 @@ impl<A: AuthorizationApi> EntityTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: ArchiveEntityTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.store.archive_entity_type(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .archive_entity_type(actor_id, params)
+            .await)
     }
 
     async fn unarchive_entity_type(
@@ -648,7 +762,9 @@ impl<A: AuthorizationApi> EntityTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: UnarchiveEntityTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.store.unarchive_entity_type(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .unarchive_entity_type(actor_id, params)
+            .await)
     }
 
     async fn update_entity_type_embeddings(
@@ -656,13 +772,15 @@ impl<A: AuthorizationApi> EntityTypeStore for DatabaseApi<'_, A> {
         actor_id: AccountId,
         params: UpdateEntityTypeEmbeddingParams<'_>,
     ) -> Result<(), UpdateError> {
-        self.store
+        dispatch!(&mut self.store, |store| store
             .update_entity_type_embeddings(actor_id, params)
-            .await
+            .await)
     }
 
     async fn reindex_entity_type_cache(&mut self) -> Result<(), UpdateError> {
-        self.store.reindex_entity_type_cache().await
+        dispatch!(&mut self.store, |store| store
+            .reindex_entity_type_cache()
+            .await)
     }
 }
 
@@ -678,7 +796,9 @@ where
     where
         R: IntoIterator<Item = EntityRelationAndSubject> + Send,
     {
-        self.store.create_entities(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .create_entities(actor_id, params)
+            .await)
     }
 
     async fn validate_entities(
@@ -687,9 +807,9 @@ where
         consistency: Consistency<'_>,
         params: Vec<ValidateEntityParams<'_>>,
     ) -> Result<(), ValidateEntityError> {
-        self.store
+        dispatch!(&self.store, |store| store
             .validate_entities(actor_id, consistency, params)
-            .await
+            .await)
     }
 
     async fn get_entities(
@@ -709,7 +829,9 @@ where
             })
             .await?;
 
-        let mut response = self.store.get_entities(actor_id, params).await?;
+        let mut response = dispatch!(&self.store, |store| store
+            .get_entities(actor_id, params)
+            .await)?;
 
         // We can ensure that `count_entities` and `get_entity` return the same count;
         assert_eq!(response.count, Some(count));
@@ -740,7 +862,9 @@ where
                 include_drafts: params.include_drafts,
             })
             .await?;
-        let mut response = self.store.get_entity_subgraph(actor_id, params).await?;
+        let mut response = dispatch!(&self.store, |store| store
+            .get_entity_subgraph(actor_id, params)
+            .await)?;
 
         // We can ensure that `count_entities` and `get_entity` return the same count;
         assert_eq!(response.count, Some(count));
@@ -760,7 +884,9 @@ where
         actor_id: AccountId,
         params: CountEntitiesParams<'_>,
     ) -> Result<usize, QueryError> {
-        self.store.count_entities(actor_id, params).await
+        dispatch!(&self.store, |store| store
+            .count_entities(actor_id, params)
+            .await)
     }
 
     async fn get_entity_by_id(
@@ -770,9 +896,9 @@ where
         transaction_time: Option<Timestamp<TransactionTime>>,
         decision_time: Option<Timestamp<DecisionTime>>,
     ) -> Result<Entity, QueryError> {
-        self.store
+        dispatch!(&self.store, |store| store
             .get_entity_by_id(actor_id, entity_id, transaction_time, decision_time)
-            .await
+            .await)
     }
 
     async fn patch_entity(
@@ -780,7 +906,9 @@ where
         actor_id: AccountId,
         params: PatchEntityParams,
     ) -> Result<Entity, UpdateError> {
-        self.store.patch_entity(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .patch_entity(actor_id, params)
+            .await)
     }
 
     async fn update_entity_embeddings(
@@ -788,11 +916,23 @@ where
         actor_id: AccountId,
         params: UpdateEntityEmbeddingsParams<'_>,
     ) -> Result<(), UpdateError> {
-        self.store.update_entity_embeddings(actor_id, params).await
+        dispatch!(&mut self.store, |store| store
+            .update_entity_embeddings(actor_id, params)
+            .await)
     }
 
     async fn reindex_entity_cache(&mut self) -> Result<(), UpdateError> {
-        self.store.reindex_entity_cache().await
+        dispatch!(&mut self.store, |store| store.reindex_entity_cache().await)
+    }
+
+    async fn permanently_delete_entities(
+        &mut self,
+        actor_id: AccountId,
+        params: DeleteEntitiesParams<'_>,
+    ) -> Result<DeleteEntitiesResponse, DeletionError> {
+        dispatch!(&mut self.store, |store| store
+            .permanently_delete_entities(actor_id, params)
+            .await)
     }
 }
 
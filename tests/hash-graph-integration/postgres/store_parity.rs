@@ -0,0 +1,678 @@
+//! Runs the same scenarios against [`PostgresStore`] and [`MemoryStore`] and checks that both
+//! backends observe the same state.
+//!
+//! Transaction times and edition IDs are assigned by the store and differ between the backends, so
+//! all scenarios use fixed entity UUIDs and decision times and only compare the remaining data.
+//!
+//! [`PostgresStore`]: graph::store::PostgresStore
+//! [`MemoryStore`]: graph::store::MemoryStore
+
+use alloc::borrow::Cow;
+use core::str::FromStr;
+use std::collections::HashSet;
+
+use authorization::AuthorizationApi;
+use graph::store::{
+    EntityQuerySorting, EntityQuerySortingRecord, EntityStore, Ordering,
+    knowledge::{CreateEntityParams, GetEntitiesParams, PatchEntityParams},
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    knowledge::{
+        entity::{Entity, EntityId, EntityUuid, ProvidedEntityEditionProvenance},
+        property::{
+            Property, PropertyObject, PropertyPatchOperation, PropertyPath, PropertyWithMetadata,
+            PropertyWithMetadataObject,
+        },
+    },
+    owned_by_id::OwnedById,
+};
+use hash_graph_store::{
+    entity::EntityQueryPath,
+    filter::{Filter, FilterExpression, JsonPath, Parameter, ParameterList, PathToken},
+    subgraph::temporal_axes::{
+        PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
+    },
+};
+use pretty_assertions::assert_eq;
+use temporal_versioning::{
+    ClosedTemporalBound, DecisionTime, LeftClosedTemporalInterval, OpenTemporalBound,
+    TemporalBound, Timestamp,
+};
+use type_system::url::{BaseUrl, OntologyTypeVersion, VersionedUrl};
+use uuid::Uuid;
+
+use crate::{DatabaseApi, DatabaseTestWrapper, StoreBackend};
+
+const NAME: &str = "https://blockprotocol.org/@alice/types/property-type/name/";
+const AGE: &str = "https://blockprotocol.org/@alice/types/property-type/age/";
+
+/// An entity edition stripped of everything which is assigned by the store.
+#[derive(Debug, PartialEq)]
+struct Edition {
+    uuid: u128,
+    draft: bool,
+    archived: bool,
+    properties: PropertyObject,
+    decision_time: LeftClosedTemporalInterval<DecisionTime>,
+}
+
+impl From<Entity> for Edition {
+    fn from(entity: Entity) -> Self {
+        Self {
+            uuid: entity
+                .metadata
+                .record_id
+                .entity_id
+                .entity_uuid
+                .into_uuid()
+                .as_u128(),
+            draft: entity.metadata.record_id.entity_id.draft_id.is_some(),
+            archived: entity.metadata.archived,
+            properties: entity.properties,
+            decision_time: entity.metadata.temporal_versioning.decision_time,
+        }
+    }
+}
+
+/// Runs `$scenario` on both backends, asserts that the outcomes are equal and returns it.
+macro_rules! assert_parity {
+    ($scenario:ident) => {{
+        let postgres = $scenario(StoreBackend::Postgres).await;
+        let memory = $scenario(StoreBackend::Memory).await;
+        assert_eq!(postgres, memory, "the memory store diverged from Postgres");
+        memory
+    }};
+}
+
+async fn seed<A: AuthorizationApi>(
+    database: &mut DatabaseTestWrapper<A>,
+) -> DatabaseApi<'_, &mut A> {
+    database
+        .seed(
+            [
+                data_type::VALUE_V1,
+                data_type::TEXT_V1,
+                data_type::NUMBER_V1,
+            ],
+            [
+                property_type::NAME_V1,
+                property_type::AGE_V1,
+                property_type::TEXT_V1,
+                property_type::FAVORITE_SONG_V1,
+                property_type::FAVORITE_FILM_V1,
+                property_type::HOBBY_V1,
+                property_type::INTERESTS_V1,
+            ],
+            [
+                entity_type::PERSON_V1,
+                entity_type::PAGE_V1,
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::link::ACQUAINTANCE_OF_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database")
+}
+
+fn person_entity_type_id() -> VersionedUrl {
+    VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: OntologyTypeVersion::new(1),
+    }
+}
+
+fn person(json: &str) -> PropertyObject {
+    serde_json::from_str(json).expect("could not parse entity")
+}
+
+fn decision_time(time: &str) -> Timestamp<DecisionTime> {
+    Timestamp::from_str(time).expect("could not parse timestamp")
+}
+
+async fn create_person<A: AuthorizationApi>(
+    api: &mut DatabaseApi<'_, A>,
+    uuid: u128,
+    properties: PropertyObject,
+    draft: bool,
+    time: &str,
+) -> EntityId {
+    api.create_entity(api.account_id, CreateEntityParams {
+        owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+        entity_uuid: Some(EntityUuid::new(Uuid::from_u128(uuid))),
+        decision_time: Some(decision_time(time)),
+        entity_type_ids: HashSet::from([person_entity_type_id()]),
+        properties: PropertyWithMetadataObject::from_parts(properties, None)
+            .expect("could not create property with metadata object"),
+        confidence: None,
+        link_data: None,
+        draft,
+        relationships: [],
+        provenance: ProvidedEntityEditionProvenance::default(),
+    })
+    .await
+    .expect("could not create entity")
+    .metadata
+    .record_id
+    .entity_id
+}
+
+async fn patch_person<A: AuthorizationApi>(
+    api: &mut DatabaseApi<'_, A>,
+    entity_id: EntityId,
+    properties: Option<PropertyObject>,
+    draft: Option<bool>,
+    archived: Option<bool>,
+    time: &str,
+) -> EntityId {
+    api.patch_entity(api.account_id, PatchEntityParams {
+        entity_id,
+        properties: properties
+            .map(|properties| PropertyPatchOperation::Replace {
+                path: PropertyPath::default(),
+                property: PropertyWithMetadata::from_parts(Property::Object(properties), None)
+                    .expect("could not create property with metadata"),
+            })
+            .into_iter()
+            .collect(),
+        entity_type_ids: HashSet::new(),
+        archived,
+        draft,
+        decision_time: Some(decision_time(time)),
+        confidence: None,
+        provenance: ProvidedEntityEditionProvenance::default(),
+    })
+    .await
+    .expect("could not update entity")
+    .metadata
+    .record_id
+    .entity_id
+}
+
+/// Returns the editions matching `filter` ordered by UUID, draft state and decision time.
+async fn editions<A: AuthorizationApi>(
+    api: &DatabaseApi<'_, A>,
+    filter: Filter<'_, Entity>,
+    temporal_axes: QueryTemporalAxesUnresolved,
+    include_drafts: bool,
+) -> Vec<Edition> {
+    let mut editions: Vec<_> = api
+        .get_entities(api.account_id, GetEntitiesParams {
+            filter,
+            temporal_axes,
+            sorting: EntityQuerySorting {
+                paths: vec![EntityQuerySortingRecord {
+                    path: EntityQueryPath::Uuid,
+                    ordering: Ordering::Ascending,
+                    nulls: None,
+                }],
+                cursor: None,
+            },
+            limit: None,
+            conversions: Vec::new(),
+            include_count: false,
+            include_drafts,
+            include_web_ids: false,
+            include_created_by_ids: false,
+            include_edition_created_by_ids: false,
+            include_type_ids: false,
+        })
+        .await
+        .expect("could not get entities")
+        .entities
+        .into_iter()
+        .map(Edition::from)
+        .collect();
+    editions.sort_by_key(|edition| (edition.uuid, edition.draft, edition.decision_time));
+    editions
+}
+
+fn current_time() -> QueryTemporalAxesUnresolved {
+    QueryTemporalAxesUnresolved::DecisionTime {
+        pinned: PinnedTemporalAxisUnresolved::new(None),
+        variable: VariableTemporalAxisUnresolved::new(None, None),
+    }
+}
+
+fn all_decision_times() -> QueryTemporalAxesUnresolved {
+    QueryTemporalAxesUnresolved::DecisionTime {
+        pinned: PinnedTemporalAxisUnresolved::new(None),
+        variable: VariableTemporalAxisUnresolved::new(Some(TemporalBound::Unbounded), None),
+    }
+}
+
+fn pinned_decision_time(time: &str) -> QueryTemporalAxesUnresolved {
+    QueryTemporalAxesUnresolved::TransactionTime {
+        pinned: PinnedTemporalAxisUnresolved::new(Some(decision_time(time))),
+        variable: VariableTemporalAxisUnresolved::new(None, None),
+    }
+}
+
+fn interval(start: &str, end: Option<&str>) -> LeftClosedTemporalInterval<DecisionTime> {
+    LeftClosedTemporalInterval::new(
+        ClosedTemporalBound::Inclusive(decision_time(start)),
+        end.map_or(OpenTemporalBound::Unbounded, |end| {
+            OpenTemporalBound::Exclusive(decision_time(end))
+        }),
+    )
+}
+
+fn property_path(property_type: &'static str) -> FilterExpression<'static, Entity> {
+    FilterExpression::Path {
+        path: EntityQueryPath::Properties(Some(JsonPath::from_path_tokens(vec![
+            PathToken::Field(Cow::Borrowed(property_type)),
+        ]))),
+    }
+}
+
+const fn text_parameter(text: &'static str) -> FilterExpression<'static, Entity> {
+    FilterExpression::Parameter {
+        parameter: Parameter::Text(Cow::Borrowed(text)),
+        convert: None,
+    }
+}
+
+#[tokio::test]
+async fn temporal_versions() {
+    #[derive(Debug, PartialEq)]
+    struct Outcome {
+        all: Vec<Edition>,
+        current: Vec<Edition>,
+        in_2001: Vec<Edition>,
+        before_creation: Vec<Edition>,
+    }
+
+    async fn scenario(backend: StoreBackend) -> Outcome {
+        let mut database = DatabaseTestWrapper::with_backend(backend).await;
+        let mut api = seed(&mut database).await;
+
+        let entity_id = create_person(
+            &mut api,
+            1,
+            person(entity::PERSON_ALICE_V1),
+            false,
+            "2000-01-01T00:00:00Z",
+        )
+        .await;
+        patch_person(
+            &mut api,
+            entity_id,
+            Some(person(entity::PERSON_BOB_V1)),
+            None,
+            None,
+            "2001-01-01T00:00:00Z",
+        )
+        .await;
+        patch_person(
+            &mut api,
+            entity_id,
+            Some(person(entity::PERSON_CHARLES_V1)),
+            None,
+            None,
+            "2002-01-01T00:00:00Z",
+        )
+        .await;
+
+        Outcome {
+            all: editions(&api, Filter::All(Vec::new()), all_decision_times(), false).await,
+            current: editions(&api, Filter::All(Vec::new()), current_time(), false).await,
+            in_2001: editions(
+                &api,
+                Filter::All(Vec::new()),
+                pinned_decision_time("2001-06-01T00:00:00Z"),
+                false,
+            )
+            .await,
+            before_creation: editions(
+                &api,
+                Filter::All(Vec::new()),
+                pinned_decision_time("1999-01-01T00:00:00Z"),
+                false,
+            )
+            .await,
+        }
+    }
+
+    let outcome = assert_parity!(scenario);
+
+    assert_eq!(
+        outcome
+            .all
+            .iter()
+            .map(|edition| edition.decision_time)
+            .collect::<Vec<_>>(),
+        [
+            interval("2000-01-01T00:00:00Z", Some("2001-01-01T00:00:00Z")),
+            interval("2001-01-01T00:00:00Z", Some("2002-01-01T00:00:00Z")),
+            interval("2002-01-01T00:00:00Z", None),
+        ]
+    );
+    assert_eq!(outcome.current.len(), 1);
+    assert_eq!(
+        outcome.current[0].properties,
+        person(entity::PERSON_CHARLES_V1)
+    );
+    assert_eq!(outcome.in_2001.len(), 1);
+    assert_eq!(outcome.in_2001[0].properties, person(entity::PERSON_BOB_V1));
+    assert!(outcome.before_creation.is_empty());
+}
+
+#[tokio::test]
+async fn drafts() {
+    #[derive(Debug, PartialEq)]
+    struct Outcome {
+        published: Vec<Edition>,
+        with_drafts: Vec<Edition>,
+        after_publishing: Vec<Edition>,
+    }
+
+    async fn scenario(backend: StoreBackend) -> Outcome {
+        let mut database = DatabaseTestWrapper::with_backend(backend).await;
+        let mut api = seed(&mut database).await;
+
+        let alice = create_person(
+            &mut api,
+            1,
+            person(entity::PERSON_ALICE_V1),
+            false,
+            "2000-01-01T00:00:00Z",
+        )
+        .await;
+        let alice_draft = patch_person(
+            &mut api,
+            alice,
+            Some(person(entity::PERSON_BOB_V1)),
+            Some(true),
+            None,
+            "2001-01-01T00:00:00Z",
+        )
+        .await;
+        let charles = create_person(
+            &mut api,
+            2,
+            person(entity::PERSON_CHARLES_V1),
+            true,
+            "2000-01-01T00:00:00Z",
+        )
+        .await;
+
+        let published = editions(&api, Filter::All(Vec::new()), all_decision_times(), false).await;
+        let with_drafts = editions(&api, Filter::All(Vec::new()), all_decision_times(), true).await;
+
+        patch_person(
+            &mut api,
+            alice_draft,
+            None,
+            Some(false),
+            None,
+            "2002-01-01T00:00:00Z",
+        )
+        .await;
+        patch_person(
+            &mut api,
+            charles,
+            None,
+            Some(false),
+            None,
+            "2002-01-01T00:00:00Z",
+        )
+        .await;
+
+        Outcome {
+            published,
+            with_drafts,
+            after_publishing: editions(&api, Filter::All(Vec::new()), current_time(), true).await,
+        }
+    }
+
+    let outcome = assert_parity!(scenario);
+
+    assert_eq!(
+        outcome
+            .published
+            .iter()
+            .map(|edition| edition.uuid)
+            .collect::<Vec<_>>(),
+        [1]
+    );
+    assert_eq!(
+        outcome
+            .with_drafts
+            .iter()
+            .map(|edition| (edition.uuid, edition.draft))
+            .collect::<Vec<_>>(),
+        [(1, false), (1, true), (2, true)]
+    );
+    assert!(
+        outcome
+            .after_publishing
+            .iter()
+            .all(|edition| !edition.draft)
+    );
+    assert_eq!(
+        outcome
+            .after_publishing
+            .iter()
+            .map(|edition| (edition.uuid, &edition.properties))
+            .collect::<Vec<_>>(),
+        [
+            (1, &person(entity::PERSON_BOB_V1)),
+            (2, &person(entity::PERSON_CHARLES_V1)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn archiving() {
+    #[derive(Debug, PartialEq)]
+    struct Outcome {
+        current: Vec<Edition>,
+        not_archived: Vec<Edition>,
+        all: Vec<Edition>,
+    }
+
+    async fn scenario(backend: StoreBackend) -> Outcome {
+        let mut database = DatabaseTestWrapper::with_backend(backend).await;
+        let mut api = seed(&mut database).await;
+
+        let alice = create_person(
+            &mut api,
+            1,
+            person(entity::PERSON_ALICE_V1),
+            false,
+            "2000-01-01T00:00:00Z",
+        )
+        .await;
+        create_person(
+            &mut api,
+            2,
+            person(entity::PERSON_BOB_V1),
+            false,
+            "2000-01-01T00:00:00Z",
+        )
+        .await;
+        patch_person(
+            &mut api,
+            alice,
+            None,
+            None,
+            Some(true),
+            "2001-01-01T00:00:00Z",
+        )
+        .await;
+
+        Outcome {
+            current: editions(&api, Filter::All(Vec::new()), current_time(), false).await,
+            not_archived: editions(
+                &api,
+                Filter::Equal(
+                    Some(FilterExpression::Path {
+                        path: EntityQueryPath::Archived,
+                    }),
+                    Some(FilterExpression::Parameter {
+                        parameter: Parameter::Boolean(false),
+                        convert: None,
+                    }),
+                ),
+                current_time(),
+                false,
+            )
+            .await,
+            all: editions(&api, Filter::All(Vec::new()), all_decision_times(), false).await,
+        }
+    }
+
+    let outcome = assert_parity!(scenario);
+
+    assert_eq!(
+        outcome
+            .current
+            .iter()
+            .map(|edition| (edition.uuid, edition.archived))
+            .collect::<Vec<_>>(),
+        [(1, true), (2, false)]
+    );
+    assert_eq!(
+        outcome
+            .not_archived
+            .iter()
+            .map(|edition| edition.uuid)
+            .collect::<Vec<_>>(),
+        [2]
+    );
+    assert_eq!(
+        outcome
+            .all
+            .iter()
+            .map(|edition| (edition.uuid, edition.archived))
+            .collect::<Vec<_>>(),
+        [(1, false), (1, true), (2, false)]
+    );
+}
+
+#[tokio::test]
+async fn filters() {
+    async fn scenario(backend: StoreBackend) -> Vec<Vec<u128>> {
+        let mut database = DatabaseTestWrapper::with_backend(backend).await;
+        let mut api = seed(&mut database).await;
+
+        for (uuid, json) in [
+            (1, entity::PERSON_ALICE_V1),
+            (2, entity::PERSON_BOB_V1),
+            (3, entity::PERSON_CHARLES_V1),
+        ] {
+            create_person(&mut api, uuid, person(json), false, "2000-01-01T00:00:00Z").await;
+        }
+
+        let name_is = |name: &'static str| {
+            Filter::Equal(Some(property_path(NAME)), Some(text_parameter(name)))
+        };
+        let filters = [
+            name_is("Bob"),
+            Filter::NotEqual(Some(property_path(NAME)), Some(text_parameter("Bob"))),
+            Filter::StartsWith(property_path(NAME), text_parameter("Ch")),
+            Filter::EndsWith(property_path(NAME), text_parameter("e")),
+            Filter::Exists(property_path(AGE)),
+            Filter::Not(Box::new(Filter::Exists(property_path(AGE)))),
+            Filter::ILike(property_path(NAME), text_parameter("%B%")),
+            Filter::Matches(property_path(NAME), text_parameter("^(Alice|Bob)$")),
+            Filter::In(
+                FilterExpression::Path {
+                    path: EntityQueryPath::Uuid,
+                },
+                ParameterList::Parameters(vec![
+                    Parameter::Uuid(Uuid::from_u128(1)),
+                    Parameter::Uuid(Uuid::from_u128(3)),
+                ]),
+            ),
+            Filter::Any(vec![name_is("Alice"), name_is("Charles")]),
+            Filter::All(vec![
+                Filter::Exists(property_path(AGE)),
+                Filter::Not(Box::new(name_is("Bob"))),
+            ]),
+        ];
+
+        let mut outcome = Vec::new();
+        for filter in filters {
+            outcome.push(
+                editions(&api, filter, current_time(), false)
+                    .await
+                    .into_iter()
+                    .map(|edition| edition.uuid)
+                    .collect::<Vec<_>>(),
+            );
+        }
+        outcome
+    }
+
+    let outcome = assert_parity!(scenario);
+
+    assert_eq!(outcome, [
+        vec![2],
+        vec![1, 3],
+        vec![3],
+        vec![1],
+        vec![2, 3],
+        vec![1],
+        vec![2],
+        vec![1, 2],
+        vec![1, 3],
+        vec![1, 3],
+        vec![3],
+    ]);
+}
+
+#[tokio::test]
+async fn full_text_search() {
+    async fn scenario(backend: StoreBackend) -> Vec<Vec<u128>> {
+        let mut database = DatabaseTestWrapper::with_backend(backend).await;
+        let mut api = seed(&mut database).await;
+
+        for (uuid, name) in [(1, "Alice is running"), (2, "Bob runs"), (3, "Charles")] {
+            let properties = person(&format!(r#"{{"{NAME}": "{name}"}}"#));
+            create_person(&mut api, uuid, properties, false, "2000-01-01T00:00:00Z").await;
+        }
+
+        let mut outcome = Vec::new();
+        for query in [
+            "run",
+            "running",
+            "RUNS charles",
+            "is",
+            r#""alice running""#,
+            r#""alice is running""#,
+            "ali*",
+        ] {
+            let filter = Filter::FullTextSearch(
+                FilterExpression::Path {
+                    path: EntityQueryPath::Properties(None),
+                },
+                query.parse().expect("could not parse text search query"),
+            );
+            outcome.push(
+                editions(&api, filter, current_time(), false)
+                    .await
+                    .into_iter()
+                    .map(|edition| edition.uuid)
+                    .collect::<Vec<_>>(),
+            );
+        }
+        outcome
+    }
+
+    let outcome = assert_parity!(scenario);
+
+    assert_eq!(outcome, [
+        vec![1, 2],
+        vec![1, 2],
+        vec![],
+        vec![],
+        vec![],
+        vec![1],
+        vec![1],
+    ]);
+}